@@ -0,0 +1,216 @@
+//! Time per stage of the pipeline — parse, ref resolution, transform, and
+//! each generator's emit — on the bundled fixtures and on a synthetic spec
+//! large enough to show how the stages scale.
+//!
+//! Run with `cargo bench -p oag-cli --bench pipeline`; add a filter such as
+//! `-- emit/node-client` to run one group. Criterion keeps the last run in
+//! `target/criterion` and reports the change against it.
+
+use std::fmt::Write;
+use std::hint::black_box;
+
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+use oag_core::CodeGenerator;
+use oag_core::config::GeneratorConfig;
+use oag_core::parse::{self, ref_resolve::RefResolver};
+use oag_core::transform;
+use oag_fastapi_server::FastapiServerGenerator;
+use oag_node_client::NodeClientGenerator;
+use oag_react_swr_client::ReactSwrClientGenerator;
+
+const FIXTURES: &[(&str, &str)] = &[
+    (
+        "petstore-3.2",
+        include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml"),
+    ),
+    (
+        "sse-chat",
+        include_str!("../../oag-core/tests/fixtures/sse-chat.yaml"),
+    ),
+    (
+        "anthropic-messages",
+        include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml"),
+    ),
+];
+
+/// Resources in the synthetic spec; each adds five operations and three schemas.
+const SYNTHETIC_RESOURCES: usize = 200;
+
+/// A spec with `resources` CRUD resources, one tag each: a `Thing{i}` object
+/// with an enum status, a `NewThing{i}` body, list/create/get/update/delete
+/// operations, and an SSE `watch` operation on every tenth resource.
+fn synthetic_spec(resources: usize) -> String {
+    let mut yaml = String::from(
+        "openapi: \"3.1.0\"\ninfo:\n  title: Synthetic\n  version: \"1.0.0\"\npaths:\n",
+    );
+    for i in 0..resources {
+        write!(
+            yaml,
+            r##"  /things{i}:
+    get:
+      operationId: listThings{i}
+      tags: [things{i}]
+      parameters:
+        - {{ name: limit, in: query, schema: {{ type: integer }} }}
+        - {{ name: cursor, in: query, schema: {{ type: string }} }}
+      responses:
+        "200":
+          description: A page
+          content:
+            application/json:
+              schema: {{ type: array, items: {{ $ref: "#/components/schemas/Thing{i}" }} }}
+    post:
+      operationId: createThing{i}
+      tags: [things{i}]
+      requestBody:
+        required: true
+        content:
+          application/json:
+            schema: {{ $ref: "#/components/schemas/NewThing{i}" }}
+      responses:
+        "201":
+          description: Created
+          content:
+            application/json:
+              schema: {{ $ref: "#/components/schemas/Thing{i}" }}
+  /things{i}/{{id}}:
+    parameters:
+      - {{ name: id, in: path, required: true, schema: {{ type: string }} }}
+    get:
+      operationId: getThing{i}
+      tags: [things{i}]
+      responses:
+        "200":
+          description: The thing
+          content:
+            application/json:
+              schema: {{ $ref: "#/components/schemas/Thing{i}" }}
+    patch:
+      operationId: updateThing{i}
+      tags: [things{i}]
+      requestBody:
+        content:
+          application/json:
+            schema: {{ $ref: "#/components/schemas/NewThing{i}" }}
+      responses:
+        "200":
+          description: Updated
+          content:
+            application/json:
+              schema: {{ $ref: "#/components/schemas/Thing{i}" }}
+    delete:
+      operationId: deleteThing{i}
+      tags: [things{i}]
+      responses:
+        "204": {{ description: Deleted }}
+"##
+        )
+        .unwrap();
+        if i % 10 == 0 {
+            write!(
+                yaml,
+                r##"  /things{i}/{{id}}/watch:
+    get:
+      operationId: watchThing{i}
+      tags: [things{i}]
+      parameters:
+        - {{ name: id, in: path, required: true, schema: {{ type: string }} }}
+      responses:
+        "200":
+          description: Changes
+          content:
+            text/event-stream:
+              schema: {{ $ref: "#/components/schemas/Thing{i}" }}
+"##
+            )
+            .unwrap();
+        }
+    }
+
+    yaml.push_str("components:\n  schemas:\n");
+    for i in 0..resources {
+        write!(
+            yaml,
+            r##"    Thing{i}Status: {{ type: string, enum: [active, archived, deleted] }}
+    NewThing{i}:
+      type: object
+      required: [name]
+      properties:
+        name: {{ type: string }}
+        description: {{ type: string }}
+        labels: {{ type: object, additionalProperties: {{ type: string }} }}
+    Thing{i}:
+      type: object
+      required: [id, name, status, createdAt]
+      properties:
+        id: {{ type: string }}
+        name: {{ type: string }}
+        description: {{ type: string }}
+        status: {{ $ref: "#/components/schemas/Thing{i}Status" }}
+        createdAt: {{ type: string, format: date-time }}
+        owner:
+          type: object
+          properties:
+            id: {{ type: string }}
+            email: {{ type: string }}
+"##
+        )
+        .unwrap();
+    }
+    yaml
+}
+
+/// The fixtures, then the synthetic spec.
+fn specs() -> Vec<(String, String)> {
+    let mut specs: Vec<(String, String)> = FIXTURES
+        .iter()
+        .map(|(name, yaml)| (name.to_string(), yaml.to_string()))
+        .collect();
+    specs.push((
+        format!("synthetic-{SYNTHETIC_RESOURCES}"),
+        synthetic_spec(SYNTHETIC_RESOURCES),
+    ));
+    specs
+}
+
+fn core_stages(c: &mut Criterion) {
+    for (name, yaml) in specs() {
+        let spec = parse::from_yaml(&yaml).unwrap();
+
+        c.bench_with_input(BenchmarkId::new("parse", &name), &yaml, |b, yaml| {
+            b.iter(|| parse::from_yaml(black_box(yaml)).unwrap())
+        });
+        c.bench_with_input(BenchmarkId::new("resolve", &name), &spec, |b, spec| {
+            b.iter(|| {
+                RefResolver::new(spec)
+                    .resolve_spec(black_box(spec))
+                    .unwrap()
+            })
+        });
+        c.bench_with_input(BenchmarkId::new("transform", &name), &spec, |b, spec| {
+            b.iter(|| transform::transform(black_box(spec)).unwrap())
+        });
+    }
+}
+
+fn emit(c: &mut Criterion) {
+    let generators: [(&str, &dyn CodeGenerator); 3] = [
+        ("node-client", &NodeClientGenerator),
+        ("react-swr-client", &ReactSwrClientGenerator),
+        ("fastapi-server", &FastapiServerGenerator),
+    ];
+    let config = GeneratorConfig::default();
+
+    for (name, yaml) in specs() {
+        let ir = transform::transform(&parse::from_yaml(&yaml).unwrap()).unwrap();
+        for (generator_name, generator) in generators {
+            let id = BenchmarkId::new(format!("emit/{generator_name}"), &name);
+            c.bench_with_input(id, &ir, |b, ir| {
+                b.iter(|| generator.generate(black_box(ir), &config).unwrap())
+            });
+        }
+    }
+}
+
+criterion_group!(benches, core_stages, emit);
+criterion_main!(benches);
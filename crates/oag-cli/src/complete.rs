@@ -0,0 +1,249 @@
+//! Dynamic shell completion: `oag __complete <arg> <prefix>` prints the values
+//! the project offers for a flag, and [`dynamic_script`] wires the scripts
+//! from `oag completions` to call it. Only the config file and the last run's
+//! manifest are read, never the spec, so completing stays instant.
+
+use std::fs;
+use std::path::Path;
+use std::process::ExitCode;
+
+use clap::{CommandFactory, ValueEnum};
+use clap_complete::Shell;
+
+use oag_core::config::CONFIG_FILE_NAME;
+use oag_core::run;
+
+/// The hidden subcommand the completion scripts call.
+pub const COMMAND: &str = "__complete";
+
+/// `oag __complete <arg> [prefix]`: print the candidates, one per line.
+pub fn run(args: &[String]) -> ExitCode {
+    let Some(arg) = args
+        .first()
+        .and_then(|arg| CompletionArg::from_str(arg, false).ok())
+    else {
+        return ExitCode::FAILURE;
+    };
+    let prefix = args.get(1).map_or("", String::as_str);
+    for value in candidates(arg, prefix, Path::new("")) {
+        println!("{value}");
+    }
+    ExitCode::SUCCESS
+}
+
+/// A flag whose values come from the project rather than the CLI definition.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionArg {
+    /// `generate --target`: the generators in the config, or every generator
+    /// when none are configured.
+    Target,
+    /// `--profile`: the entries of the config's `profiles`.
+    Profile,
+    /// `inspect --schema`: the schemas of the last generated spec.
+    Schema,
+}
+
+/// The values for `arg` starting with `prefix`, for the project in `root`.
+pub fn candidates(arg: CompletionArg, prefix: &str, root: &Path) -> Vec<String> {
+    let values = match arg {
+        CompletionArg::Target => {
+            let configured = config_keys(root, "generators");
+            if configured.is_empty() {
+                possible_values("generate", "target")
+            } else {
+                configured
+            }
+        }
+        CompletionArg::Profile => config_keys(root, "profiles"),
+        CompletionArg::Schema => run::read_manifest(root)
+            .map(|manifest| manifest.summary.schemas)
+            .unwrap_or_default(),
+    };
+    values
+        .into_iter()
+        .filter(|value| value.starts_with(prefix))
+        .collect()
+}
+
+/// The keys of the config's top-level `section` map, in file order. A missing
+/// or unreadable config has none.
+fn config_keys(root: &Path, section: &str) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(root.join(CONFIG_FILE_NAME)) else {
+        return Vec::new();
+    };
+    let Ok(config) = serde_yaml_ng::from_str::<serde_yaml_ng::Value>(&content) else {
+        return Vec::new();
+    };
+    config
+        .get(section)
+        .and_then(|value| value.as_mapping())
+        .map(|map| {
+            map.keys()
+                .filter_map(|key| key.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// The visible values clap itself accepts for `subcommand`'s `arg`.
+fn possible_values(subcommand: &str, arg: &str) -> Vec<String> {
+    let command = crate::Cli::command();
+    command
+        .find_subcommand(subcommand)
+        .and_then(|sub| sub.get_arguments().find(|a| a.get_id() == arg))
+        .map(|arg| {
+            arg.get_possible_values()
+                .iter()
+                .filter(|value| !value.is_hide_set())
+                .map(|value| value.get_name().to_string())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Shell code appended to `shell`'s completion script so the [`CompletionArg`]
+/// flags complete through `oag __complete`, falling back to the static script.
+/// `None` for shells that only get static completions.
+pub fn dynamic_script(shell: Shell) -> Option<&'static str> {
+    match shell {
+        Shell::Bash => Some(BASH),
+        Shell::Zsh => Some(ZSH),
+        Shell::Fish => Some(FISH),
+        _ => None,
+    }
+}
+
+const BASH: &str = r#"
+_oag_dynamic() {
+    local arg
+    case "${COMP_WORDS[COMP_CWORD-1]}" in
+        --target) arg=target ;;
+        --profile) arg=profile ;;
+        --schema) arg=schema ;;
+    esac
+    if [[ -n "$arg" ]]; then
+        local IFS=$'\n'
+        COMPREPLY=($(oag __complete "$arg" "${COMP_WORDS[COMP_CWORD]}" 2>/dev/null))
+        [[ ${#COMPREPLY[@]} -gt 0 ]] && return 0
+    fi
+    _oag "$@"
+}
+
+complete -F _oag_dynamic -o bashdefault -o default oag
+"#;
+
+const ZSH: &str = r#"
+_oag_dynamic() {
+    local arg
+    case "${words[CURRENT-1]}" in
+        --target) arg=target ;;
+        --profile) arg=profile ;;
+        --schema) arg=schema ;;
+    esac
+    if [[ -n "$arg" ]]; then
+        local -a values
+        values=(${(f)"$(oag __complete "$arg" "${words[CURRENT]}" 2>/dev/null)"})
+        if (( ${#values} )); then
+            compadd -a values
+            return 0
+        fi
+    fi
+    _oag "$@"
+}
+
+compdef _oag_dynamic oag
+"#;
+
+const FISH: &str = r#"
+complete -c oag -e -l target
+complete -c oag -n "__fish_oag_using_subcommand generate" -l target -d 'Run only this generator' -r -f -a "(oag __complete target (commandline -ct))"
+complete -c oag -n "__fish_oag_using_subcommand generate; or __fish_oag_using_subcommand inspect; or __fish_oag_using_subcommand diff-spec" -l profile -f -a "(oag __complete profile (commandline -ct))"
+complete -c oag -n "__fish_oag_using_subcommand inspect" -l schema -f -a "(oag __complete schema (commandline -ct))"
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = "
+generators:
+  node-client:
+    output: out/node
+  fastapi-server:
+    output: out/server
+profiles:
+  public: {}
+  partner: {}
+  internal: {}
+";
+
+    fn project(config: Option<&str>) -> tempfile::TempDir {
+        let tmp = tempfile::tempdir().unwrap();
+        if let Some(config) = config {
+            fs::write(tmp.path().join(CONFIG_FILE_NAME), config).unwrap();
+        }
+        tmp
+    }
+
+    #[test]
+    fn targets_come_from_the_config() {
+        let tmp = project(Some(CONFIG));
+        assert_eq!(
+            candidates(CompletionArg::Target, "", tmp.path()),
+            ["node-client", "fastapi-server"]
+        );
+        assert_eq!(
+            candidates(CompletionArg::Target, "fa", tmp.path()),
+            ["fastapi-server"]
+        );
+    }
+
+    #[test]
+    fn targets_fall_back_to_every_generator() {
+        let tmp = project(None);
+        assert_eq!(
+            candidates(CompletionArg::Target, "", tmp.path()),
+            [
+                "node-client",
+                "react-swr-client",
+                "fastapi-server",
+                "json-schema"
+            ]
+        );
+    }
+
+    #[test]
+    fn profiles_come_from_the_config() {
+        let tmp = project(Some(CONFIG));
+        assert_eq!(
+            candidates(CompletionArg::Profile, "p", tmp.path()),
+            ["public", "partner"]
+        );
+        assert!(candidates(CompletionArg::Profile, "", project(None).path()).is_empty());
+        let broken = project(Some("profiles: [public"));
+        assert!(candidates(CompletionArg::Profile, "", broken.path()).is_empty());
+    }
+
+    #[test]
+    fn schemas_come_from_the_last_manifest() {
+        let tmp = project(None);
+        assert!(candidates(CompletionArg::Schema, "", tmp.path()).is_empty());
+
+        let manifest = run::Manifest {
+            inputs: run::RunInputs {
+                oag_version: String::new(),
+                spec_hash: String::new(),
+                config_hash: String::new(),
+            },
+            files: Default::default(),
+            summary: run::SpecSummary {
+                schemas: vec!["Pet".into(), "PetList".into(), "Error".into()],
+            },
+        };
+        run::write_manifest(tmp.path(), &manifest).unwrap();
+        assert_eq!(
+            candidates(CompletionArg::Schema, "Pet", tmp.path()),
+            ["Pet", "PetList"]
+        );
+    }
+}
@@ -1,52 +1,195 @@
 use std::fs;
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, ExitCode};
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 
-use oag_core::config::{self, CONFIG_FILE_NAME, GeneratorId, OagConfig};
-use oag_core::ir::IrSpec;
-use oag_core::parse;
-use oag_core::transform::{self, TransformOptions};
-use oag_core::{CodeGenerator, GeneratedFile};
+use oag_core::GeneratedFile;
+use oag_core::config::{self, CONFIG_FILE_NAME, GeneratorId, OagConfig, OutputLayout};
+use oag_core::examples;
+use oag_core::fixture;
+use oag_core::ir::{IrSpec, diff, dump as ir_dump};
+use oag_core::parse::{self, ref_resolve::RefResolver, spec::OpenApiSpec};
+use oag_core::report;
+use oag_core::run::{self, GeneratorRegistry, SpecSource};
+use oag_core::test_report::{self, TestCoverage};
+use oag_core::transform;
 use oag_fastapi_server::FastapiServerGenerator;
+use oag_json_schema::JsonSchemaGenerator;
 use oag_node_client::NodeClientGenerator;
 use oag_react_swr_client::ReactSwrClientGenerator;
 
+mod complete;
+mod reporter;
+
+use reporter::{LogFormat, Reporter, Verbosity};
+
 #[derive(Parser)]
 #[command(name = "oag", about = "OpenAPI 3.x code generator", version)]
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// Report errors only
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    quiet: bool,
+
+    /// Also report phase timings and a summary; twice for debug logs
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    verbose: u8,
+
+    /// Progress output on stderr: `text`, or `json` for one object per event
+    #[arg(long, global = true, default_value = "text")]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Generate code from an OpenAPI spec
     Generate {
-        /// Path to the OpenAPI spec file (YAML or JSON)
+        /// Path to the OpenAPI spec file (YAML or JSON), or `-` for stdin
         #[arg(short, long)]
         input: Option<PathBuf>,
+
+        /// Spec format; defaults to the file extension, or YAML for stdin
+        #[arg(long)]
+        input_format: Option<SpecFormat>,
+
+        /// Regenerate even if the spec and config are unchanged since the last run
+        #[arg(long)]
+        force: bool,
+
+        /// Also print a report to stdout
+        #[arg(long)]
+        report: Option<ReportKind>,
+
+        /// Run only this generator, with its config section if there is one
+        #[arg(long)]
+        target: Option<Target>,
+
+        /// Omit JSDoc comments from generated TypeScript
+        #[arg(long)]
+        no_jsdoc: bool,
+
+        /// Emit only the schema types, no client, hooks or routes, as for a
+        /// spec without operations
+        #[arg(long)]
+        types_only: bool,
+
+        /// Print the single generated file to stdout instead of writing files;
+        /// forces bundled layout and no scaffold
+        #[arg(long, conflicts_with = "report")]
+        stdout: bool,
+
+        /// Apply this entry of the config's `profiles` over its root settings
+        #[arg(long)]
+        profile: Option<String>,
+
+        /// Fail instead of warning when a generator would ignore an option
+        /// or can't parse its `scaffold`
+        #[arg(long)]
+        deny_warnings: bool,
+
+        /// Also write the IR the generators receive, after every transform
+        /// pass, to this JSON file, for debugging generator output
+        #[arg(long, value_name = "PATH")]
+        emit_ir: Option<PathBuf>,
+
+        /// Generate from an IR written by `--emit-ir` instead of a spec,
+        /// skipping parsing and transforming
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["input", "input_format", "emit_ir"])]
+        from_ir: Option<PathBuf>,
     },
 
     /// Validate an OpenAPI spec
     Validate {
-        /// Path to the OpenAPI spec file
+        /// Path to the OpenAPI spec file, or `-` for stdin
         #[arg(short, long)]
         input: PathBuf,
+
+        /// Spec format; defaults to the file extension, or YAML for stdin
+        #[arg(long)]
+        input_format: Option<SpecFormat>,
+
+        /// Fail on `$ref`s to missing schemas, on schemas using keywords the
+        /// generators can only approximate, and on examples that don't match
+        /// their schemas
+        #[arg(long)]
+        strict: bool,
+
+        /// Also print a report to stdout
+        #[arg(long)]
+        report: Option<ReportKind>,
+
+        /// Findings format: `text` warnings on stderr, or `json` on stdout
+        #[arg(long, default_value = "text")]
+        format: ValidateFormat,
     },
 
     /// Inspect the parsed IR of an OpenAPI spec
     Inspect {
-        /// Path to the OpenAPI spec file
+        /// Path to the OpenAPI spec file, or `-` for stdin
         #[arg(short, long)]
         input: PathBuf,
 
+        /// Spec format; defaults to the file extension, or YAML for stdin
+        #[arg(long)]
+        input_format: Option<SpecFormat>,
+
         /// Output format
         #[arg(long, default_value = "yaml")]
         format: InspectFormat,
+
+        /// Show this schema's fields (or variants) instead of the summary
+        #[arg(long)]
+        schema: Option<String>,
+
+        /// Dump the whole IR, as `generate --emit-ir` writes it, instead of
+        /// the summary
+        #[arg(long, conflicts_with = "schema")]
+        full: bool,
+
+        /// Apply this entry of the config's `profiles` over its root settings
+        #[arg(long)]
+        profile: Option<String>,
+    },
+
+    /// Compare two versions of a spec: changes to operations, parameters,
+    /// bodies, schemas and enums, split into breaking and non-breaking
+    DiffSpec {
+        /// The old spec
+        old: PathBuf,
+
+        /// The new spec
+        new: PathBuf,
+
+        /// Output format
+        #[arg(long, default_value = "text")]
+        format: DiffFormat,
+
+        /// Apply this entry of the config's `profiles` over its root settings
+        #[arg(long)]
+        profile: Option<String>,
+    },
+
+    /// Extract operations and the components they use into a minimal spec,
+    /// for bug reports and generator tests
+    Fixture {
+        /// The spec to extract from
+        #[arg(long)]
+        from: PathBuf,
+
+        /// Comma-separated `operationId`s to keep
+        #[arg(long, value_delimiter = ',', required = true)]
+        operations: Vec<String>,
+
+        /// Where to write the spec (JSON for a `.json` path, else YAML);
+        /// defaults to YAML on stdout
+        #[arg(long)]
+        out: Option<PathBuf>,
     },
 
     /// Initialize a new oag configuration
@@ -63,136 +206,246 @@ enum Commands {
     },
 }
 
+#[derive(Clone, Copy, ValueEnum)]
+enum SpecFormat {
+    Yaml,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ReportKind {
+    /// Per category, how many spec elements the generators support fully,
+    /// approximate, or drop, with the locations of the last two
+    Features,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Target {
+    #[value(alias = "typescript")]
+    NodeClient,
+    #[value(alias = "react")]
+    ReactSwrClient,
+    #[value(alias = "fastapi")]
+    FastapiServer,
+    JsonSchema,
+}
+
+impl Target {
+    fn id(self) -> GeneratorId {
+        match self {
+            Target::NodeClient => GeneratorId::NodeClient,
+            Target::ReactSwrClient => GeneratorId::ReactSwrClient,
+            Target::FastapiServer => GeneratorId::FastapiServer,
+            Target::JsonSchema => GeneratorId::JsonSchema,
+        }
+    }
+}
+
 #[derive(Clone, ValueEnum)]
 enum InspectFormat {
     Yaml,
     Json,
 }
 
-fn main() -> Result<()> {
-    env_logger::init();
+#[derive(Clone, ValueEnum)]
+enum ValidateFormat {
+    Text,
+    Json,
+}
+
+#[derive(Clone, ValueEnum)]
+enum DiffFormat {
+    Text,
+    Json,
+}
+
+fn main() -> ExitCode {
+    // Not a clap subcommand: completion scripts can't describe `__complete`.
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some(complete::COMMAND) {
+        return complete::run(&args[2..]);
+    }
 
     let cli = Cli::parse();
+    let verbosity = Verbosity::from_flags(cli.quiet, cli.verbose);
+    reporter::init_logger(verbosity, cli.log_format);
+    let reporter = Reporter::new(verbosity, cli.log_format);
 
-    match cli.command {
-        Commands::Generate { input } => cmd_generate(input),
+    let result = run_command(cli.command, &reporter);
+    if let Err(error) = &result {
+        reporter.error(error);
+    }
+    reporter.summary(result.is_ok());
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(_) => ExitCode::FAILURE,
+    }
+}
+
+fn run_command(command: Commands, reporter: &Reporter) -> Result<()> {
+    match command {
+        Commands::Generate {
+            input,
+            input_format,
+            force,
+            report,
+            target,
+            no_jsdoc,
+            types_only,
+            stdout,
+            profile,
+            deny_warnings,
+            emit_ir,
+            from_ir,
+        } => cmd_generate(
+            input,
+            input_format,
+            force,
+            report,
+            target,
+            no_jsdoc,
+            types_only,
+            stdout,
+            profile,
+            deny_warnings,
+            emit_ir,
+            from_ir,
+            reporter,
+        ),
+
+        Commands::Validate {
+            input,
+            input_format,
+            strict,
+            report,
+            format,
+        } => cmd_validate(input, input_format, strict, report, format, reporter),
 
-        Commands::Validate { input } => cmd_validate(input),
+        Commands::Inspect {
+            input,
+            input_format,
+            format,
+            schema,
+            full,
+            profile,
+        } => cmd_inspect(input, input_format, format, schema, full, profile, reporter),
 
-        Commands::Inspect { input, format } => cmd_inspect(input, format),
+        Commands::DiffSpec {
+            old,
+            new,
+            format,
+            profile,
+        } => cmd_diff_spec(old, new, format, profile),
 
-        Commands::Init { force } => cmd_init(force),
+        Commands::Fixture {
+            from,
+            operations,
+            out,
+        } => cmd_fixture(from, &operations, out, reporter),
+
+        Commands::Init { force } => cmd_init(force, reporter),
 
         Commands::Completions { shell } => {
             let mut cmd = <Cli as clap::CommandFactory>::command();
             clap_complete::generate(shell, &mut cmd, "oag", &mut std::io::stdout());
+            if let Some(script) = complete::dynamic_script(shell) {
+                print!("{script}");
+            }
             Ok(())
         }
     }
 }
 
-/// Try to load the project config file from the current directory.
-fn try_load_config() -> Result<Option<OagConfig>> {
+/// Try to load the project config file from the current directory, with
+/// `profile` applied.
+fn try_load_config(profile: Option<&str>) -> Result<Option<OagConfig>> {
     let config_path = PathBuf::from(CONFIG_FILE_NAME);
-    config::load_config(&config_path).map_err(|e| anyhow::anyhow!(e))
+    config::load_config_profile(&config_path, profile).map_err(|e| anyhow::anyhow!(e))
 }
 
-fn load_spec(path: &PathBuf, cfg: &OagConfig) -> Result<IrSpec> {
-    let content =
-        fs::read_to_string(path).with_context(|| format!("failed to read {}", path.display()))?;
-
-    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("yaml");
-
-    let parsed = match ext {
-        "json" => parse::from_json(&content)?,
-        _ => parse::from_yaml(&content)?,
+/// Where to read the spec from: stdin for `-`, otherwise the file at `input`.
+/// An explicit `format` overrides detection by file extension.
+fn spec_source(input: PathBuf, format: Option<SpecFormat>) -> Result<SpecSource> {
+    let content = if input == Path::new("-") {
+        let mut content = String::new();
+        io::stdin().lock().read_to_string(&mut content)?;
+        content
+    } else if format.is_some() {
+        fs::read_to_string(&input)
+            .map_err(|e| anyhow::anyhow!("failed to read {}: {e}", input.display()))?
+    } else {
+        return Ok(SpecSource::Path(input));
     };
 
-    let options = TransformOptions {
-        naming_strategy: cfg.naming.strategy,
-        aliases: cfg.naming.aliases.clone(),
-    };
-
-    let ir = transform::transform_with_options(&parsed, &options)?;
-    Ok(ir)
-}
-
-/// Look up a generator by its ID.
-fn get_generator(id: &GeneratorId) -> Box<dyn CodeGenerator> {
-    match id {
-        GeneratorId::NodeClient => Box::new(NodeClientGenerator),
-        GeneratorId::ReactSwrClient => Box::new(ReactSwrClientGenerator),
-        GeneratorId::FastapiServer => Box::new(FastapiServerGenerator),
-    }
+    Ok(match format.unwrap_or(SpecFormat::Yaml) {
+        SpecFormat::Yaml => SpecSource::Yaml(content),
+        SpecFormat::Json => SpecSource::Json(content),
+    })
 }
 
-/// Write generated files to disk under the given base directory.
-fn write_files(base: &Path, files: &[GeneratedFile]) -> Result<()> {
-    for file in files {
-        let path = base.join(&file.path);
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("failed to create directory {}", parent.display()))?;
-        }
-        fs::write(&path, &file.content)
-            .with_context(|| format!("failed to write {}", path.display()))?;
-        eprintln!("  wrote {}", path.display());
-    }
-    Ok(())
+/// All built-in generators.
+fn registry() -> GeneratorRegistry {
+    let mut registry = GeneratorRegistry::new();
+    registry
+        .register(NodeClientGenerator)
+        .register(ReactSwrClientGenerator)
+        .register(FastapiServerGenerator)
+        .register(JsonSchemaGenerator);
+    registry
 }
 
 /// Try to run formatters on the output directory based on config file presence.
-fn try_run_formatter(output_dir: &Path) {
+fn try_run_formatter(output_dir: &Path, reporter: &Reporter) {
     if output_dir.join("biome.json").exists() {
-        try_run_biome(output_dir);
+        try_run_biome(output_dir, reporter);
     }
     if output_dir.join("ruff.toml").exists() {
-        try_run_ruff(output_dir);
+        try_run_ruff(output_dir, reporter);
     }
 }
 
 /// Try to run Biome formatter on the output directory.
-fn try_run_biome(output_dir: &Path) {
+fn try_run_biome(output_dir: &Path, reporter: &Reporter) {
     match Command::new("npx")
         .args(["@biomejs/biome", "check", "--write", "."])
         .current_dir(output_dir)
         .output()
     {
         Ok(result) if result.status.success() => {
-            eprintln!("  formatted with biome");
+            reporter.info("formatted with biome");
         }
         Ok(_result) => {
-            eprintln!(
-                "  warning: biome formatting had issues (non-zero exit), output may need manual formatting"
+            reporter.warning(
+                "biome formatting had issues (non-zero exit), output may need manual formatting",
             );
         }
         Err(_) => {
-            eprintln!(
-                "  note: biome not found — run `npx @biomejs/biome check --write .` in {} to format",
+            reporter.info(format!(
+                "note: biome not found — run `npx @biomejs/biome check --write .` in {} to format",
                 output_dir.display()
-            );
+            ));
         }
     }
 }
 
 /// Try to run Ruff formatter and linter on the output directory.
-fn try_run_ruff(output_dir: &Path) {
+fn try_run_ruff(output_dir: &Path, reporter: &Reporter) {
     match Command::new("ruff")
         .args(["format", "."])
         .current_dir(output_dir)
         .output()
     {
         Ok(result) if result.status.success() => {
-            eprintln!("  formatted with ruff");
+            reporter.info("formatted with ruff");
         }
         Ok(_) => {
-            eprintln!("  warning: ruff format had issues (non-zero exit)");
+            reporter.warning("ruff format had issues (non-zero exit)");
         }
         Err(_) => {
-            eprintln!(
-                "  note: ruff not found — run `ruff format . && ruff check --fix .` in {} to format",
+            reporter.info(format!(
+                "note: ruff not found — run `ruff format . && ruff check --fix .` in {} to format",
                 output_dir.display()
-            );
+            ));
             return;
         }
     }
@@ -203,113 +456,383 @@ fn try_run_ruff(output_dir: &Path) {
         .output()
     {
         Ok(result) if result.status.success() => {
-            eprintln!("  linted with ruff");
+            reporter.info("linted with ruff");
         }
         Ok(_) => {
-            eprintln!("  warning: ruff check had issues (non-zero exit)");
+            reporter.warning("ruff check had issues (non-zero exit)");
         }
         Err(_) => {}
     }
 }
 
-/// Generate the "do not edit" README.
-fn readme_content() -> &'static str {
-    r#"# Generated Code — Do Not Edit
-
-This directory is **auto-generated** by [oag](https://github.com/urmzd/openapi-generator).
-Any manual changes will be overwritten the next time `oag generate` is run.
-
-To regenerate, run:
-```
-oag generate
-```
-
-To customize the generated output, edit your `.urmzd.oag.yaml` configuration file.
-"#
-}
-
-fn cmd_generate(input: Option<PathBuf>) -> Result<()> {
-    let cfg = try_load_config()?.unwrap_or_default();
+#[allow(clippy::too_many_arguments)]
+fn cmd_generate(
+    input: Option<PathBuf>,
+    input_format: Option<SpecFormat>,
+    force: bool,
+    report: Option<ReportKind>,
+    target: Option<Target>,
+    no_jsdoc: bool,
+    types_only: bool,
+    stdout: bool,
+    profile: Option<String>,
+    deny_warnings: bool,
+    emit_ir: Option<PathBuf>,
+    from_ir: Option<PathBuf>,
+    reporter: &Reporter,
+) -> Result<()> {
+    let mut cfg = try_load_config(profile.as_deref())?.unwrap_or_default();
     let input = input.unwrap_or_else(|| PathBuf::from(&cfg.input));
-    let ir = load_spec(&input, &cfg)?;
+
+    if let Some(target) = target {
+        let id = target.id();
+        cfg.generators.retain(|g, _| *g == id);
+        cfg.generators.entry(id).or_default();
+    }
+    // Before the flags below, which set options on every generator.
+    let config_warnings = run::validate_config(&cfg, &registry())?;
+    for (id, warning) in &config_warnings {
+        reporter.generator_warning(*id, warning);
+    }
+    if deny_warnings && !config_warnings.is_empty() {
+        anyhow::bail!(
+            "{} config warning(s) with --deny-warnings",
+            config_warnings.len()
+        );
+    }
+    for gen_config in cfg.generators.values_mut() {
+        if no_jsdoc {
+            gen_config.no_jsdoc = Some(true);
+        }
+        gen_config.types_only = types_only;
+    }
 
     if cfg.generators.is_empty() {
-        eprintln!("No generators configured. Add a `generators` section to your config.");
+        reporter.info("No generators configured. Add a `generators` section to your config.");
         return Ok(());
     }
 
-    for (gen_id, gen_config) in &cfg.generators {
-        eprintln!("Generating {} → {}", gen_id, gen_config.output);
-        let generator = get_generator(gen_id);
-        let files = generator
-            .generate(&ir, gen_config)
-            .map_err(|e| anyhow::anyhow!(e))?;
+    let root = Path::new("");
+    let (ir, inputs) = match from_ir {
+        // A dumped IR has no spec text to record in the manifest.
+        Some(path) => (reporter.time("load", || load_ir_dump(&path))?, None),
+        None => {
+            let source = spec_source(input, input_format)?;
+            // Without anything to print or dump, an unchanged spec and config
+            // whose outputs are still on disk skip even parsing.
+            let inputs = run::run_inputs(&cfg, &source)?;
+            if let Some(inputs) = &inputs
+                && !force
+                && !stdout
+                && report.is_none()
+                && emit_ir.is_none()
+                && reporter.time("check", || run::is_manifest_current(root, inputs))
+            {
+                reporter.info("Generated files are up to date with the spec and config.");
+                reporter.info("Run `oag generate --force` to regenerate anyway.");
+                return Ok(());
+            }
+            (
+                reporter.time("load", || run::load_ir(&cfg, source))?,
+                inputs,
+            )
+        }
+    };
+    if let Some(path) = &emit_ir {
+        fs::write(path, ir_dump::to_json(&ir))
+            .map_err(|e| anyhow::anyhow!("failed to write {}: {e}", path.display()))?;
+        reporter.file(path);
+    }
+    if stdout {
+        return generate_to_stdout(cfg, &ir, reporter);
+    }
+    if let (Some(kind), Some(spec)) = (report, ir.raw_spec.as_deref()) {
+        print_report(kind, spec, &ir.diagnostics);
+    }
+    let hash = run::generation_hash(&cfg, &ir);
+    if !force && run::is_up_to_date(root, &hash) {
+        // Record the new spec text, so the next run skips parsing it.
+        if let (Some(inputs), Some(previous)) = (inputs, run::read_manifest(root)) {
+            let manifest = run::Manifest {
+                inputs,
+                summary: run::SpecSummary::of(&ir),
+                ..previous
+            };
+            run::write_manifest(root, &manifest)?;
+        }
+        reporter.info("Spec and config unchanged since the last run; nothing to generate.");
+        reporter.info("Run `oag generate --force` to regenerate anyway.");
+        return Ok(());
+    }
 
-        let output_dir = PathBuf::from(&gen_config.output);
-        fs::create_dir_all(&output_dir).with_context(|| {
-            format!("failed to create output directory {}", output_dir.display())
-        })?;
+    let result = reporter.time("generate", || run::generate_from_ir(&cfg, &registry(), &ir))?;
+    for warning in &result.warnings {
+        reporter.warning(warning);
+    }
 
-        write_files(&output_dir, &files)?;
+    let mut all_written = Vec::new();
+    for output in &result.outputs {
+        reporter.begin_generator(output.id, &output.output, output.types_only);
+        for warning in &output.warnings {
+            reporter.warning(warning);
+        }
+        let hint = match output.id {
+            GeneratorId::NodeClient | GeneratorId::ReactSwrClient => {
+                "; set `scaffold.existing_repo: true` to keep the project's own"
+            }
+            _ => "",
+        };
+        for path in run::overwritten_project_files(output, root, &all_written) {
+            reporter.warning(format!(
+                "{} already exists and wasn't generated by oag; it is overwritten{hint}",
+                path.display()
+            ));
+        }
 
-        // Add README.md
-        let readme_path = output_dir.join("README.md");
-        fs::write(&readme_path, readme_content())
-            .with_context(|| format!("failed to write {}", readme_path.display()))?;
-        eprintln!("  wrote {}", readme_path.display());
+        let written = reporter.time("write", || run::write_generator_output(output, root))?;
+        for path in &written {
+            reporter.file(path);
+        }
 
         // Auto-run formatter based on config file presence
-        try_run_formatter(&output_dir);
+        reporter.time("format", || try_run_formatter(&output.output, reporter));
+        report_test_coverage(output, reporter);
 
-        eprintln!(
-            "Generated {} files in {}",
-            files.len() + 1, // +1 for README
-            output_dir.display()
-        );
+        reporter.end_generator(written.len(), &output.output);
+        all_written.extend(written);
+    }
+
+    run::write_cached_hash(root, &hash)?;
+    if let Some(inputs) = inputs {
+        run::write_manifest(root, &run::manifest_for(inputs, &ir, root, &all_written)?)?;
+    }
+    if cfg.changelog
+        && let Some(path) = run::update_changelog(root, &ir)?
+    {
+        reporter.file(&path);
     }
 
-    eprintln!(
-        "\nThe generated directories should not be edited manually — changes will be overwritten."
+    reporter.info(
+        "\nThe generated directories should not be edited manually — changes will be overwritten.",
     );
     Ok(())
 }
 
-fn cmd_validate(input: PathBuf) -> Result<()> {
-    let content = fs::read_to_string(&input)
-        .with_context(|| format!("failed to read {}", input.display()))?;
+/// Summarize the generator's `test-coverage.json` (`test_report: true`), if
+/// it wrote one: how many operations no generated test calls, and which.
+fn report_test_coverage(output: &run::GeneratorOutput, reporter: &Reporter) {
+    let Some(file) = output
+        .files
+        .iter()
+        .find(|file| file.path.rsplit('/').next() == Some(test_report::REPORT_FILE))
+    else {
+        return;
+    };
+    let Ok(coverage) = TestCoverage::from_json(&file.content) else {
+        return;
+    };
+    let path = file.to_platform_path(&output.output);
+    let untested = coverage.untested();
+    if untested.is_empty() {
+        reporter.info(format!(
+            "every operation has generated tests (see {})",
+            path.display()
+        ));
+    } else {
+        reporter.info(format!(
+            "{} of {} operations have no generated tests: {} (see {})",
+            untested.len(),
+            coverage.operations.len(),
+            untested.join(", "),
+            path.display()
+        ));
+    }
+}
 
-    let ext = input.extension().and_then(|e| e.to_str()).unwrap_or("yaml");
+/// Read an IR written by `--emit-ir`.
+fn load_ir_dump(path: &Path) -> Result<IrSpec> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("failed to read {}: {e}", path.display()))?;
+    ir_dump::from_json(&content).map_err(|e| anyhow::anyhow!("{}: {e}", path.display()))
+}
 
-    let parsed = match ext {
-        "json" => parse::from_json(&content)?,
-        _ => parse::from_yaml(&content)?,
-    };
+/// Generate the only configured generator with bundled layout and no scaffold,
+/// and print its one source file to stdout.
+fn generate_to_stdout(mut cfg: OagConfig, ir: &IrSpec, reporter: &Reporter) -> Result<()> {
+    if cfg.generators.len() > 1 {
+        let ids: Vec<&str> = cfg.generators.keys().map(|id| id.as_str()).collect();
+        anyhow::bail!(
+            "--stdout prints a single generator's output, but the config has {}; pick one with --target",
+            ids.join(", ")
+        );
+    }
+    for gen_config in cfg.generators.values_mut() {
+        gen_config.layout = OutputLayout::Bundled;
+        gen_config.scaffold = None;
+    }
+
+    let result = reporter.time("generate", || run::generate_from_ir(&cfg, &registry(), ir))?;
+    let output = &result.outputs[0];
+    for warning in result.warnings.iter().chain(&output.warnings) {
+        reporter.warning(warning);
+    }
 
-    eprintln!(
+    let files: Vec<&GeneratedFile> = output
+        .files
+        .iter()
+        .filter(|f| f.path != "README.md")
+        .collect();
+    match files.as_slice() {
+        [file] => {
+            print!("{}", file.content);
+            Ok(())
+        }
+        _ => {
+            let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+            anyhow::bail!(
+                "--stdout needs a single generated file, but {} produces {}; drop the extra files with `files.exclude` or generate to a directory",
+                output.id,
+                paths.join(", ")
+            )
+        }
+    }
+}
+
+fn cmd_validate(
+    input: PathBuf,
+    input_format: Option<SpecFormat>,
+    strict: bool,
+    report: Option<ReportKind>,
+    format: ValidateFormat,
+    reporter: &Reporter,
+) -> Result<()> {
+    let source = spec_source(input, input_format)?;
+    let parsed = reporter.time("parse", || source.parse())?;
+
+    reporter.info(format!(
         "Valid OpenAPI {} spec: {}",
         parsed.openapi, parsed.info.title
-    );
-    eprintln!("  Version: {}", parsed.info.version);
-    eprintln!("  Paths: {}", parsed.paths.len());
+    ));
+    reporter.info(format!("  Version: {}", parsed.info.version));
+    reporter.info(format!("  Paths: {}", parsed.paths.len()));
+    if !parsed.webhooks.is_empty() {
+        reporter.info(format!("  Webhooks: {}", parsed.webhooks.len()));
+    }
 
     if let Some(ref components) = parsed.components {
-        eprintln!("  Schemas: {}", components.schemas.len());
+        reporter.info(format!("  Schemas: {}", components.schemas.len()));
+        for (section, count) in [
+            ("Parameters", components.parameters.len()),
+            ("Request bodies", components.request_bodies.len()),
+            ("Responses", components.responses.len()),
+            ("Security schemes", components.security_schemes.len()),
+            ("Links", components.links.len()),
+        ] {
+            if count > 0 {
+                reporter.info(format!("  {section}: {count}"));
+            }
+        }
+    }
+
+    // Report every broken `$ref` at once, where the transform fails on the first
+    let refs = RefResolver::new(&parsed).unresolved_refs(&parsed);
+    if !refs.is_empty() {
+        if matches!(format, ValidateFormat::Json) {
+            let findings = serde_json::json!({ "refs": refs });
+            println!("{}", serde_json::to_string_pretty(&findings)?);
+        }
+        let list: String = refs.iter().map(|r| format!("\n  {r}")).collect();
+        anyhow::bail!("{} `$ref`(s) don't resolve:{list}", refs.len());
     }
 
     // Also validate that it transforms to IR successfully
-    let ir = transform::transform(&parsed)?;
-    eprintln!("  Operations: {}", ir.operations.len());
-    eprintln!("  IR Schemas: {}", ir.schemas.len());
+    let options = transform::TransformOptions {
+        strict,
+        ..Default::default()
+    };
+    let ir = reporter.time("transform", || {
+        transform::transform_with_options(&parsed, &options)
+    })?;
+    reporter.info(format!("  Operations: {}", ir.operations.len()));
+    reporter.info(format!("  IR Schemas: {}", ir.schemas.len()));
+    let mismatches = examples::check_examples(&parsed, &ir);
+    match format {
+        ValidateFormat::Text => {
+            for diagnostic in &ir.diagnostics {
+                reporter.warning(diagnostic);
+            }
+            for mismatch in &mismatches {
+                reporter.warning(mismatch);
+            }
+        }
+        ValidateFormat::Json => {
+            let findings = serde_json::json!({
+                "diagnostics": ir.diagnostics,
+                "examples": mismatches,
+                "refs": refs,
+            });
+            println!("{}", serde_json::to_string_pretty(&findings)?);
+        }
+    }
+    if let Some(kind) = report {
+        print_report(kind, &parsed, &ir.diagnostics);
+    }
 
-    eprintln!("Validation successful.");
+    let approximated = ir
+        .schemas
+        .iter()
+        .filter(|s| !s.unsupported_keywords().is_empty())
+        .count();
+    if strict && approximated > 0 {
+        anyhow::bail!("{approximated} schema(s) use unsupported keywords (--strict)");
+    }
+    if strict && !mismatches.is_empty() {
+        anyhow::bail!(
+            "{} example(s) don't match their schemas (--strict)",
+            mismatches.len()
+        );
+    }
+
+    reporter.info("Validation successful.");
     Ok(())
 }
 
-fn cmd_inspect(input: PathBuf, format: InspectFormat) -> Result<()> {
-    let cfg = OagConfig::default();
-    let ir = load_spec(&input, &cfg)?;
+fn print_report(kind: ReportKind, spec: &OpenApiSpec, diagnostics: &[String]) {
+    match kind {
+        ReportKind::Features => print!("{}", report::feature_report(spec, diagnostics)),
+    }
+}
+
+/// Dump a summary of the IR, built with the project config's naming options
+/// (aliases, schema aliases) so that names match the generated code. With
+/// `full`, dump the whole IR instead; its JSON is what `--emit-ir` writes.
+fn cmd_inspect(
+    input: PathBuf,
+    input_format: Option<SpecFormat>,
+    format: InspectFormat,
+    schema: Option<String>,
+    full: bool,
+    profile: Option<String>,
+    reporter: &Reporter,
+) -> Result<()> {
+    let cfg = try_load_config(profile.as_deref())?.unwrap_or_default();
+    let source = spec_source(input, input_format)?;
+    let ir = reporter.time("load", || run::load_ir(&cfg, source))?;
 
-    let summary = build_inspect_summary(&ir);
+    if full {
+        match format {
+            InspectFormat::Yaml => {
+                print!("{}", serde_yaml_ng::to_string(&ir_dump::IrDump::new(&ir))?)
+            }
+            InspectFormat::Json => print!("{}", ir_dump::to_json(&ir)),
+        }
+        return Ok(());
+    }
+
+    let summary = match schema {
+        Some(name) => build_schema_detail(&ir, &name)?,
+        None => build_inspect_summary(&ir),
+    };
 
     match format {
         InspectFormat::Yaml => {
@@ -325,6 +848,55 @@ fn cmd_inspect(input: PathBuf, format: InspectFormat) -> Result<()> {
     Ok(())
 }
 
+/// Diff the IRs of two specs, built with the project config's naming options
+/// so that names match the generated code.
+fn cmd_diff_spec(
+    old: PathBuf,
+    new: PathBuf,
+    format: DiffFormat,
+    profile: Option<String>,
+) -> Result<()> {
+    let cfg = try_load_config(profile.as_deref())?.unwrap_or_default();
+    let old_ir = run::load_ir(&cfg, SpecSource::Path(old))?;
+    let new_ir = run::load_ir(&cfg, SpecSource::Path(new))?;
+    let diff = diff::diff(&old_ir, &new_ir);
+
+    match format {
+        DiffFormat::Text => print!("{diff}"),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+    }
+    Ok(())
+}
+
+/// Write the part of a spec the named operations use, as parsed (`$ref`s
+/// intact), to `out` or stdout.
+fn cmd_fixture(
+    from: PathBuf,
+    operations: &[String],
+    out: Option<PathBuf>,
+    reporter: &Reporter,
+) -> Result<()> {
+    let spec = reporter.time("parse", || SpecSource::Path(from).parse())?;
+    let fixture = fixture::extract_operations(&spec, operations)?;
+    let Some(out) = out else {
+        print!("{}", parse::to_yaml(&fixture)?);
+        return Ok(());
+    };
+    let content = match out.extension().and_then(|e| e.to_str()) {
+        Some("json") => parse::to_json(&fixture)? + "\n",
+        _ => parse::to_yaml(&fixture)?,
+    };
+    fs::write(&out, content)
+        .map_err(|e| anyhow::anyhow!("failed to write {}: {e}", out.display()))?;
+    let schemas = fixture.components.map_or(0, |c| c.schemas.len());
+    reporter.info(format!(
+        "Wrote {} operation(s) and {schemas} schema(s) to {}",
+        operations.len(),
+        out.display()
+    ));
+    Ok(())
+}
+
 fn build_inspect_summary(ir: &IrSpec) -> serde_json::Value {
     let schemas: Vec<serde_json::Value> = ir
         .schemas
@@ -354,13 +926,30 @@ fn build_inspect_summary(ir: &IrSpec) -> serde_json::Value {
             serde_json::json!({
                 "name": op.name.camel_case,
                 "method": op.method.as_str(),
-                "path": op.path,
+                "path": op.raw_path,
                 "return_kind": return_kind,
                 "tags": op.tags,
+                "auth": op.auth(),
+                "security_schemes": op.security_schemes(&ir.security),
             })
         })
         .collect();
 
+    // Shared components the IR inlines into operations
+    let components = ir
+        .raw_spec
+        .as_ref()
+        .and_then(|spec| spec.components.as_ref());
+    let security_schemes: Vec<serde_json::Value> = components
+        .into_iter()
+        .flat_map(|c| &c.security_schemes)
+        .map(|(name, scheme)| serde_json::json!({ "name": name, "type": scheme.scheme_type }))
+        .collect();
+    let parameters: Vec<&String> = components
+        .into_iter()
+        .flat_map(|c| c.parameters.keys())
+        .collect();
+
     serde_json::json!({
         "info": {
             "title": ir.info.title,
@@ -369,10 +958,64 @@ fn build_inspect_summary(ir: &IrSpec) -> serde_json::Value {
         "schemas": schemas,
         "operations": operations,
         "modules": ir.modules.iter().map(|m| &m.name.original).collect::<Vec<_>>(),
+        "security_schemes": security_schemes,
+        "parameters": parameters,
     })
 }
 
-fn cmd_init(force: bool) -> Result<()> {
+/// One schema for `inspect --schema`, found by its generated or spec name:
+/// an object's fields with their flags, or the variants or target of the rest.
+fn build_schema_detail(ir: &IrSpec, name: &str) -> Result<serde_json::Value> {
+    use oag_core::ir::IrSchema;
+
+    let schema = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == name || s.name().original == name)
+        .ok_or_else(|| anyhow::anyhow!("no schema named `{name}`"))?;
+    let mut detail = serde_json::json!({ "name": schema.name().pascal_case });
+    match schema {
+        IrSchema::Object(obj) => {
+            let fields: Vec<serde_json::Value> = obj
+                .fields
+                .iter()
+                .map(|f| {
+                    serde_json::json!({
+                        "name": f.original_name,
+                        "type": diff::type_name(&f.field_type),
+                        "required": f.required,
+                        "read_only": f.read_only,
+                        "write_only": f.write_only,
+                        "deprecated": f.deprecated,
+                    })
+                })
+                .collect();
+            detail["kind"] = "object".into();
+            detail["description"] = obj.description.clone().into();
+            detail["fields"] = fields.into();
+        }
+        IrSchema::Enum(e) => {
+            detail["kind"] = "enum".into();
+            detail["description"] = e.description.clone().into();
+            detail["variants"] = e.variants.clone().into();
+        }
+        IrSchema::Alias(alias) => {
+            detail["kind"] = "alias".into();
+            detail["description"] = alias.description.clone().into();
+            detail["target"] = diff::type_name(&alias.target).into();
+        }
+        IrSchema::Union(union) => {
+            let variants: Vec<String> = union.variants.iter().map(diff::type_name).collect();
+            detail["kind"] = "union".into();
+            detail["composition"] = union.kind.keyword().into();
+            detail["description"] = union.description.clone().into();
+            detail["variants"] = variants.into();
+        }
+    }
+    Ok(detail)
+}
+
+fn cmd_init(force: bool, reporter: &Reporter) -> Result<()> {
     let config_path = PathBuf::from(CONFIG_FILE_NAME);
 
     if config_path.exists() && !force {
@@ -383,6 +1026,6 @@ fn cmd_init(force: bool) -> Result<()> {
     }
 
     fs::write(&config_path, config::default_config_content())?;
-    eprintln!("Created {}", config_path.display());
+    reporter.file(&config_path);
     Ok(())
 }
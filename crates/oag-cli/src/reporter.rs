@@ -0,0 +1,379 @@
+//! Progress output on stderr, as text for people or one JSON object per line
+//! for build systems (`--log-format json`), filtered by `-q`/`-v`/`-vv`.
+//!
+//! Command results (`--stdout`, reports, `inspect`, `diff-spec`) still go to
+//! stdout; everything about how a run went goes through a [`Reporter`].
+
+use std::cell::{Cell, RefCell};
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use clap::ValueEnum;
+use oag_core::config::GeneratorId;
+use serde_json::{Value, json};
+
+/// How much a run reports, from `-q`, `-v` and `-vv`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Verbosity {
+    /// Errors only.
+    Quiet,
+    /// Progress, files written and warnings.
+    Normal,
+    /// Also how long each phase took, and a closing summary in text mode.
+    Verbose,
+    /// Also the library's debug logs, e.g. each IR pass as it runs.
+    Debug,
+}
+
+impl Verbosity {
+    pub fn from_flags(quiet: bool, verbose: u8) -> Self {
+        match (quiet, verbose) {
+            (true, _) => Verbosity::Quiet,
+            (false, 0) => Verbosity::Normal,
+            (false, 1) => Verbosity::Verbose,
+            (false, _) => Verbosity::Debug,
+        }
+    }
+
+    /// The level the library's `log` output is filtered to; `RUST_LOG`
+    /// still overrides it.
+    pub fn log_level(self) -> log::LevelFilter {
+        match self {
+            Verbosity::Quiet => log::LevelFilter::Error,
+            Verbosity::Normal => log::LevelFilter::Warn,
+            Verbosity::Verbose => log::LevelFilter::Info,
+            Verbosity::Debug => log::LevelFilter::Debug,
+        }
+    }
+}
+
+/// How reported events are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Lines for people.
+    Text,
+    /// One JSON object per event, with an `event` field naming its kind.
+    Json,
+}
+
+/// Writes a run's events to stderr (or `W` in tests), counting the files
+/// written and warnings for the closing [`summary`](Reporter::summary).
+pub struct Reporter<W: Write = io::Stderr> {
+    verbosity: Verbosity,
+    format: LogFormat,
+    out: RefCell<W>,
+    /// The generator whose output is being written: text lines are indented
+    /// under it and JSON records name it.
+    generator: Cell<Option<GeneratorId>>,
+    files: Cell<usize>,
+    warnings: Cell<usize>,
+    started: Instant,
+}
+
+impl Reporter {
+    pub fn new(verbosity: Verbosity, format: LogFormat) -> Self {
+        Reporter::with_writer(verbosity, format, io::stderr())
+    }
+}
+
+impl<W: Write> Reporter<W> {
+    pub fn with_writer(verbosity: Verbosity, format: LogFormat, out: W) -> Self {
+        Reporter {
+            verbosity,
+            format,
+            out: RefCell::new(out),
+            generator: Cell::new(None),
+            files: Cell::new(0),
+            warnings: Cell::new(0),
+            started: Instant::now(),
+        }
+    }
+
+    /// A progress message. JSON records drop the text indentation.
+    pub fn info(&self, message: impl Display) {
+        let message = message.to_string();
+        self.emit(
+            Verbosity::Normal,
+            || self.indented(&message),
+            || json!({ "event": "info", "message": message.trim_start() }),
+        );
+    }
+
+    /// A warning, counted in the summary.
+    pub fn warning(&self, message: impl Display) {
+        self.warnings.set(self.warnings.get() + 1);
+        let message = message.to_string();
+        self.emit(
+            Verbosity::Normal,
+            || self.indented(&format!("warning: {message}")),
+            || json!({ "event": "warning", "message": message }),
+        );
+    }
+
+    /// A warning about generator `id`'s config, before its output is written.
+    pub fn generator_warning(&self, id: GeneratorId, message: impl Display) {
+        let previous = self.generator.replace(Some(id));
+        self.warnings.set(self.warnings.get() + 1);
+        let message = message.to_string();
+        self.emit(
+            Verbosity::Normal,
+            || format!("warning: {id}: {message}"),
+            || json!({ "event": "warning", "message": message }),
+        );
+        self.generator.set(previous);
+    }
+
+    /// Start reporting generator `id`'s output to `output`, noting when it
+    /// only emits types.
+    pub fn begin_generator(&self, id: GeneratorId, output: &Path, types_only: bool) {
+        let mode = if types_only { " (types only)" } else { "" };
+        self.emit(
+            Verbosity::Normal,
+            || format!("Generating {id} → {}{mode}", output.display()),
+            || {
+                let mut record = json!({ "event": "generator", "generator": id.as_str(), "output": output.display().to_string() });
+                if types_only {
+                    record["types_only"] = Value::Bool(true);
+                }
+                record
+            },
+        );
+        self.generator.set(Some(id));
+    }
+
+    /// Finish the current generator, which wrote `files` files to `output`.
+    pub fn end_generator(&self, files: usize, output: &Path) {
+        self.generator.set(None);
+        self.emit(
+            Verbosity::Normal,
+            || format!("Generated {files} files in {}", output.display()),
+            || Value::Null,
+        );
+    }
+
+    /// A file written to disk.
+    pub fn file(&self, path: &Path) {
+        self.files.set(self.files.get() + 1);
+        self.emit(
+            Verbosity::Normal,
+            || self.indented(&format!("wrote {}", path.display())),
+            || json!({ "event": "file", "path": path.display().to_string() }),
+        );
+    }
+
+    /// How long `phase` took. Shown with `-v` in text mode, always in JSON.
+    pub fn phase(&self, phase: &str, duration: Duration) {
+        let ms = duration.as_millis();
+        let level = match self.format {
+            LogFormat::Text => Verbosity::Verbose,
+            LogFormat::Json => Verbosity::Normal,
+        };
+        self.emit(
+            level,
+            || self.indented(&format!("{phase} took {ms} ms")),
+            || json!({ "event": "phase", "phase": phase, "duration_ms": ms }),
+        );
+    }
+
+    /// Run `f` as `phase`, reporting how long it took.
+    pub fn time<T>(&self, phase: &str, f: impl FnOnce() -> T) -> T {
+        let start = Instant::now();
+        let value = f();
+        self.phase(phase, start.elapsed());
+        value
+    }
+
+    /// An error that ends the run; reported even with `-q`.
+    pub fn error(&self, error: &anyhow::Error) {
+        self.emit(
+            Verbosity::Quiet,
+            || format!("Error: {error:?}"),
+            || json!({ "event": "error", "message": format!("{error:#}") }),
+        );
+    }
+
+    /// The closing record: files written, warnings and total duration.
+    /// Shown with `-v` in text mode, always in JSON.
+    pub fn summary(&self, success: bool) {
+        let files = self.files.get();
+        let warnings = self.warnings.get();
+        let ms = self.started.elapsed().as_millis();
+        let level = match self.format {
+            LogFormat::Text => Verbosity::Verbose,
+            LogFormat::Json => Verbosity::Normal,
+        };
+        self.emit(
+            level,
+            || format!("{files} files written, {warnings} warning(s), {ms} ms"),
+            || {
+                json!({
+                    "event": "summary",
+                    "success": success,
+                    "files_written": files,
+                    "warnings": warnings,
+                    "duration_ms": ms,
+                })
+            },
+        );
+    }
+
+    fn indented(&self, line: &str) -> String {
+        match self.generator.get() {
+            Some(_) => format!("  {line}"),
+            None => line.to_string(),
+        }
+    }
+
+    /// Write the text line or JSON record (skipped if `Null`) when the run
+    /// is at least as verbose as `level`. JSON records inside a generator
+    /// name it.
+    fn emit(
+        &self,
+        level: Verbosity,
+        text: impl FnOnce() -> String,
+        record: impl FnOnce() -> Value,
+    ) {
+        if self.verbosity < level {
+            return;
+        }
+        let line = match self.format {
+            LogFormat::Text => text(),
+            LogFormat::Json => {
+                let mut record = record();
+                let Value::Object(members) = &mut record else {
+                    return;
+                };
+                if let Some(id) = self.generator.get() {
+                    members
+                        .entry("generator")
+                        .or_insert_with(|| id.as_str().into());
+                }
+                record.to_string()
+            }
+        };
+        // Losing progress output isn't worth failing the run over.
+        let _ = writeln!(self.out.borrow_mut(), "{line}");
+    }
+
+    #[cfg(test)]
+    fn into_inner(self) -> W {
+        self.out.into_inner()
+    }
+}
+
+/// Route the library's `log` output through env_logger at `verbosity`'s
+/// level, as JSON records (`"event": "log"`) when `format` is JSON.
+pub fn init_logger(verbosity: Verbosity, format: LogFormat) {
+    let mut builder = env_logger::Builder::new();
+    builder
+        .filter_level(verbosity.log_level())
+        .parse_default_env();
+    if format == LogFormat::Json {
+        builder.format(|buf, record| {
+            let line = json!({
+                "event": "log",
+                "level": record.level().as_str(),
+                "target": record.target(),
+                "message": record.args().to_string(),
+            });
+            writeln!(buf, "{line}")
+        });
+    }
+    builder.init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(verbosity: Verbosity, report: impl FnOnce(&Reporter<Vec<u8>>)) -> Vec<Value> {
+        let reporter = Reporter::with_writer(verbosity, LogFormat::Json, Vec::new());
+        report(&reporter);
+        String::from_utf8(reporter.into_inner())
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn json_records_name_the_event_and_generator() {
+        let records = records(Verbosity::Normal, |reporter| {
+            reporter.info("Spec loaded");
+            reporter.begin_generator(GeneratorId::NodeClient, Path::new("out/ts"), false);
+            reporter.file(Path::new("out/ts/src/client.ts"));
+            reporter.warning("  indented");
+            reporter.phase("write", Duration::from_millis(12));
+            reporter.end_generator(1, Path::new("out/ts"));
+            reporter.summary(true);
+        });
+
+        assert_eq!(
+            records[0],
+            json!({ "event": "info", "message": "Spec loaded" })
+        );
+        assert_eq!(
+            records[1],
+            json!({ "event": "generator", "generator": "node-client", "output": "out/ts" })
+        );
+        assert_eq!(
+            records[2],
+            json!({ "event": "file", "generator": "node-client", "path": "out/ts/src/client.ts" })
+        );
+        assert_eq!(records[3]["event"], "warning");
+        assert_eq!(records[3]["generator"], "node-client");
+        assert_eq!(
+            records[4],
+            json!({ "event": "phase", "generator": "node-client", "phase": "write", "duration_ms": 12 })
+        );
+
+        // `end_generator` has no record of its own; the summary closes the run.
+        let summary = &records[5];
+        assert_eq!(records.len(), 6);
+        assert_eq!(summary["event"], "summary");
+        assert_eq!(summary["success"], true);
+        assert_eq!(summary["files_written"], 1);
+        assert_eq!(summary["warnings"], 1);
+        assert!(summary["duration_ms"].is_u64());
+        assert!(summary.get("generator").is_none());
+    }
+
+    #[test]
+    fn quiet_reports_only_errors() {
+        let records = records(Verbosity::Quiet, |reporter| {
+            reporter.info("Spec loaded");
+            reporter.warning("something odd");
+            reporter.file(Path::new("a.ts"));
+            reporter.summary(false);
+            reporter.error(&anyhow::anyhow!("spec not found"));
+        });
+        assert_eq!(
+            records,
+            [json!({ "event": "error", "message": "spec not found" })]
+        );
+    }
+
+    #[test]
+    fn text_indents_under_a_generator_and_times_phases_with_verbose() {
+        let text = |verbosity| {
+            let reporter = Reporter::with_writer(verbosity, LogFormat::Text, Vec::new());
+            reporter.generator_warning(GeneratorId::FastapiServer, "ignores `split_by`");
+            reporter.begin_generator(GeneratorId::FastapiServer, Path::new("out/py"), true);
+            reporter.file(Path::new("out/py/main.py"));
+            reporter.phase("format", Duration::from_millis(3));
+            reporter.end_generator(1, Path::new("out/py"));
+            String::from_utf8(reporter.into_inner()).unwrap()
+        };
+
+        assert_eq!(
+            text(Verbosity::Normal),
+            "warning: fastapi-server: ignores `split_by`\n\
+             Generating fastapi-server → out/py (types only)\n  \
+             wrote out/py/main.py\n\
+             Generated 1 files in out/py\n"
+        );
+        assert!(text(Verbosity::Verbose).contains("\n  format took 3 ms\n"));
+    }
+}
@@ -0,0 +1,919 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
+const CONDITIONAL: &str = include_str!("../../oag-core/tests/fixtures/conditional-schemas.yaml");
+const ANTHROPIC: &str = include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml");
+const EXAMPLES: &str = include_str!("../../oag-core/tests/fixtures/examples.yaml");
+const SHARED_COMPONENTS: &str =
+    include_str!("../../oag-core/tests/fixtures/shared-components.yaml");
+
+/// Run `oag` in `dir` with `stdin` piped in.
+fn oag(dir: &Path, args: &[&str], stdin: &str) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_oag"))
+        .args(args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("failed to run oag");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn validate_reads_spec_from_stdin() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = oag(tmp.path(), &["validate", "--input", "-"], PETSTORE);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    assert!(stderr.contains("Valid OpenAPI 3.2.0 spec: Petstore"));
+}
+
+#[test]
+fn inspect_reads_json_from_stdin() {
+    let tmp = tempfile::tempdir().unwrap();
+    let spec: serde_json::Value = serde_yaml_ng::from_str(PETSTORE).unwrap();
+    let output = oag(
+        tmp.path(),
+        &[
+            "inspect",
+            "-i",
+            "-",
+            "--input-format",
+            "json",
+            "--format",
+            "json",
+        ],
+        &spec.to_string(),
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["info"]["title"], "Petstore");
+}
+
+#[test]
+fn inspect_labels_public_and_inherited_operations() {
+    let tmp = tempfile::tempdir().unwrap();
+    let spec = include_str!("../../oag-core/tests/fixtures/cookie-auth.yaml");
+    let output = oag(
+        tmp.path(),
+        &["inspect", "-i", "-", "--format", "json"],
+        spec,
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let operation = |name: &str| {
+        summary["operations"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|op| op["name"] == name)
+            .unwrap()
+            .clone()
+    };
+    assert_eq!(operation("login")["auth"], "public");
+    assert_eq!(
+        operation("login")["security_schemes"],
+        serde_json::json!([])
+    );
+    assert_eq!(operation("getMe")["auth"], "inherited");
+    assert_eq!(
+        operation("getMe")["security_schemes"],
+        serde_json::json!(["session"])
+    );
+    assert_eq!(operation("status")["auth"], "operation");
+    assert_eq!(
+        operation("status")["security_schemes"],
+        serde_json::json!(["bearer"])
+    );
+    assert_eq!(
+        summary["security_schemes"],
+        serde_json::json!([
+            { "name": "session", "type": "apiKey" },
+            { "name": "bearer", "type": "http" },
+        ])
+    );
+}
+
+#[test]
+fn inspect_lists_shared_parameters() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = oag(
+        tmp.path(),
+        &["inspect", "-i", "-", "--format", "json"],
+        SHARED_COMPONENTS,
+    );
+    assert!(output.status.success());
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(summary["parameters"], serde_json::json!(["PageFilter"]));
+    assert_eq!(summary["security_schemes"], serde_json::json!([]));
+}
+
+#[test]
+fn generate_warns_about_ignored_options() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "generators:\n  fastapi-server:\n    output: server\n    split_by: operation\n",
+    )
+    .unwrap();
+
+    let output = oag(tmp.path(), &["generate", "--deny-warnings"], "");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("warning: fastapi-server: `split_by`: not supported by fastapi-server")
+    );
+    assert!(!tmp.path().join("server").exists());
+
+    let output = oag(tmp.path(), &["generate"], "");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(tmp.path().join("server/routes.py").is_file());
+}
+
+#[test]
+fn generate_warns_before_overwriting_project_files() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    let app = tmp.path().join("app");
+    fs::create_dir_all(&app).unwrap();
+    let host_package = "{ \"name\": \"host-app\" }\n";
+    fs::write(app.join("package.json"), host_package).unwrap();
+    let config = tmp.path().join(".urmzd.oag.yaml");
+    fs::write(
+        &config,
+        "generators:\n  node-client:\n    output: app\n    scaffold:\n      formatter: false\n",
+    )
+    .unwrap();
+
+    let output = oag(tmp.path(), &["generate"], "");
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("package.json already exists and wasn't generated by oag; it is overwritten; set `scaffold.existing_repo: true` to keep the project's own"),
+        "{stderr}"
+    );
+    assert!(!stderr.contains("tsconfig.json already exists"));
+    assert_ne!(
+        fs::read_to_string(app.join("package.json")).unwrap(),
+        host_package
+    );
+
+    // Once oag wrote a file, replacing it again is expected.
+    let output = oag(tmp.path(), &["generate", "--force"], "");
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("already exists"));
+
+    fs::write(app.join("package.json"), host_package).unwrap();
+    fs::write(
+        &config,
+        "generators:\n  node-client:\n    output: app\n    scaffold:\n      existing_repo: true\n",
+    )
+    .unwrap();
+    let output = oag(tmp.path(), &["generate"], "");
+    assert!(output.status.success());
+    assert!(!String::from_utf8_lossy(&output.stderr).contains("already exists"));
+    assert_eq!(
+        fs::read_to_string(app.join("package.json")).unwrap(),
+        host_package
+    );
+    assert!(app.join("DEPENDENCIES.md").is_file());
+
+    // Generators sharing a directory don't warn about each other's files.
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "allow_overlap: true\ngenerators:\n  node-client:\n    output: out/x\n    scaffold:\n      formatter: false\n  react-swr-client:\n    output: out/x\n    scaffold:\n      formatter: false\n",
+    )
+    .unwrap();
+    let output = oag(tmp.path(), &["generate"], "");
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!stderr.contains("already exists"), "{stderr}");
+}
+
+#[test]
+fn generate_reports_untested_operations() {
+    let tmp = tempfile::tempdir().unwrap();
+    let spec = include_str!("../../oag-core/tests/fixtures/stream-name-collision.yaml");
+    fs::write(tmp.path().join("openapi.yaml"), spec).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "generators:\n  node-client:\n    output: out\n    test_report: true\n    scaffold:\n      formatter: false\n",
+    )
+    .unwrap();
+
+    let output = oag(tmp.path(), &["generate"], "");
+    assert!(output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let report = Path::new("out").join("src").join("test-coverage.json");
+    assert!(
+        stderr.contains(&format!(
+            "1 of 2 operations have no generated tests: chatStream (see {})",
+            report.display()
+        )),
+        "{stderr}"
+    );
+    let coverage = fs::read_to_string(tmp.path().join(report)).unwrap();
+    assert!(coverage.contains("\"chatStream\": []"), "{coverage}");
+}
+
+#[test]
+fn inspect_shows_one_schema_with_its_deprecated_fields() {
+    let tmp = tempfile::tempdir().unwrap();
+    let spec = include_str!("../../oag-core/tests/fixtures/deprecated-fields.yaml");
+    let output = oag(
+        tmp.path(),
+        &[
+            "inspect", "-i", "-", "--format", "json", "--schema", "Account",
+        ],
+        spec,
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let detail: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(detail["kind"], "object");
+    let deprecated: Vec<&str> = detail["fields"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|f| f["deprecated"] == true)
+        .map(|f| f["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(deprecated, ["legacyId", "nickname"]);
+
+    let output = oag(
+        tmp.path(),
+        &["inspect", "-i", "-", "--schema", "Nope"],
+        spec,
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no schema named `Nope`"));
+}
+
+#[test]
+fn generate_applies_the_selected_profile() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "generators:\n  node-client:\n    output: out/internal\n    scaffold: false\n\
+         profiles:\n  public:\n    generators:\n      node-client:\n        output: out/public\n",
+    )
+    .unwrap();
+
+    let output = oag(tmp.path(), &["generate", "--profile", "public"], "");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(tmp.path().join("out/public/src/client.ts").is_file());
+    assert!(!tmp.path().join("out/public/package.json").exists());
+    assert!(!tmp.path().join("out/internal").exists());
+
+    let output = oag(tmp.path(), &["generate", "--profile", "admin"], "");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("unknown profile `admin`"));
+}
+
+#[test]
+fn completion_offers_config_and_last_run_values() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "generators:\n  json-schema:\n    output: out/schema\n\
+         profiles:\n  public: {}\n  partner: {}\n",
+    )
+    .unwrap();
+    let complete = |args: &[&str]| {
+        let output = oag(tmp.path(), args, "");
+        assert!(output.status.success());
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert_eq!(complete(&["__complete", "target"]), "json-schema\n");
+    assert_eq!(complete(&["__complete", "profile", "pu"]), "public\n");
+    assert_eq!(complete(&["__complete", "schema"]), "");
+    assert!(oag(tmp.path(), &["generate"], "").status.success());
+    assert_eq!(
+        complete(&["__complete", "schema", "Pe"]),
+        "Pet\nPetStatus\n"
+    );
+
+    let script = complete(&["completions", "bash"]);
+    assert!(script.contains("oag __complete \"$arg\""));
+}
+
+#[test]
+fn inspect_shows_schema_aliases_from_the_config() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "naming:\n  schema_aliases:\n    inline_response_200_1: PetListResponse\n",
+    )
+    .unwrap();
+
+    let spec = include_str!("../../oag-core/tests/fixtures/schema-aliases.yaml");
+    let output = oag(
+        tmp.path(),
+        &["inspect", "-i", "-", "--format", "json"],
+        spec,
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let summary: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let names: Vec<&str> = summary["schemas"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|s| s["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        names,
+        ["PetListResponse", "InlineResponse400", "SearchResult"]
+    );
+}
+
+#[test]
+fn generate_reads_spec_from_stdin() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "generators:\n  node-client:\n    output: out\n    scaffold: false\n",
+    )
+    .unwrap();
+
+    let output = oag(tmp.path(), &["generate", "--input", "-"], PETSTORE);
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let client = fs::read_to_string(tmp.path().join("out/src/client.ts")).unwrap();
+    assert!(client.contains("listPets"));
+}
+
+#[test]
+fn generate_emits_and_reads_ir_dumps() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "generators:\n  node-client:\n    output: out\n    scaffold: false\n",
+    )
+    .unwrap();
+
+    let output = oag(tmp.path(), &["generate", "--emit-ir", "ir.json"], "");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let dump = fs::read_to_string(tmp.path().join("ir.json")).unwrap();
+    assert!(dump.contains("\"format\": \"oag-ir\""));
+    let inspected = oag(
+        tmp.path(),
+        &[
+            "inspect",
+            "-i",
+            "openapi.yaml",
+            "--full",
+            "--format",
+            "json",
+        ],
+        "",
+    );
+    assert_eq!(String::from_utf8_lossy(&inspected.stdout), dump);
+
+    let client = tmp.path().join("out/src/client.ts");
+    let direct = fs::read_to_string(&client).unwrap();
+    fs::remove_file(&client).unwrap();
+    let output = oag(
+        tmp.path(),
+        &["generate", "--from-ir", "ir.json", "--force"],
+        "",
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(fs::read_to_string(&client).unwrap(), direct);
+
+    // A dump from another oag version fails instead of being misread.
+    let version = format!("\"version\": \"{}\"", env!("CARGO_PKG_VERSION"));
+    fs::write(
+        tmp.path().join("ir.json"),
+        dump.replacen(&version, "\"version\": \"0.0.1\"", 1),
+    )
+    .unwrap();
+    let output = oag(
+        tmp.path(),
+        &["generate", "--from-ir", "ir.json", "--force"],
+        "",
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("IR dump is from oag 0.0.1"));
+}
+
+#[test]
+fn generate_skips_unchanged_ir() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    let config = tmp.path().join(".urmzd.oag.yaml");
+    fs::write(
+        &config,
+        "generators:\n  node-client:\n    output: out\n    scaffold: false\n",
+    )
+    .unwrap();
+
+    let first = oag(tmp.path(), &["generate"], "");
+    assert!(first.status.success());
+    let hash = fs::read_to_string(tmp.path().join(".oag-cache/ir.sha256")).unwrap();
+    assert_eq!(hash.trim().len(), 64);
+
+    // A spec edit the IR doesn't see skips generation once it's parsed.
+    fs::write(
+        tmp.path().join("openapi.yaml"),
+        format!("{PETSTORE}\n# reviewed\n"),
+    )
+    .unwrap();
+    let second = oag(tmp.path(), &["generate"], "");
+    assert!(String::from_utf8_lossy(&second.stderr).contains("nothing to generate"));
+
+    // A deleted output is generated again, even though the IR is unchanged.
+    let client = tmp.path().join("out/src/client.ts");
+    let generated = fs::read_to_string(&client).unwrap();
+    fs::remove_file(&client).unwrap();
+    fs::write(
+        tmp.path().join("openapi.yaml"),
+        format!("{PETSTORE}\n# reviewed again\n"),
+    )
+    .unwrap();
+    let third = oag(tmp.path(), &["generate"], "");
+    assert!(third.status.success());
+    assert!(!String::from_utf8_lossy(&third.stderr).contains("nothing to generate"));
+    assert_eq!(fs::read_to_string(&client).unwrap(), generated);
+
+    // A config change regenerates even though the spec didn't change.
+    fs::write(
+        &config,
+        "generators:\n  node-client:\n    output: out\n    layout: bundled\n    scaffold: false\n",
+    )
+    .unwrap();
+    let changed = oag(tmp.path(), &["generate"], "");
+    assert!(changed.status.success());
+    assert!(!String::from_utf8_lossy(&changed.stderr).contains("nothing to generate"));
+    assert_ne!(
+        fs::read_to_string(tmp.path().join(".oag-cache/ir.sha256")).unwrap(),
+        hash
+    );
+}
+
+#[test]
+fn generate_skips_parsing_when_outputs_are_current() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "generators:\n  node-client:\n    output: out\n    scaffold: false\n",
+    )
+    .unwrap();
+    // The JSON events of a run, and its phases with their durations.
+    let run = |args: &[&str]| {
+        let output = oag(
+            tmp.path(),
+            &[&["--log-format", "json", "generate"], args].concat(),
+            "",
+        );
+        assert!(output.status.success());
+        let events: Vec<serde_json::Value> = String::from_utf8_lossy(&output.stderr)
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let phases: Vec<(String, u64)> = events
+            .iter()
+            .filter(|e| e["event"] == "phase")
+            .map(|e| {
+                let phase = e["phase"].as_str().unwrap().to_string();
+                (phase, e["duration_ms"].as_u64().unwrap())
+            })
+            .collect();
+        (events, phases)
+    };
+    let total = |phases: &[(String, u64)]| phases.iter().map(|(_, ms)| ms).sum::<u64>();
+
+    let (_, first) = run(&[]);
+    assert!(first.iter().any(|(phase, _)| phase == "load"));
+    let manifest = tmp.path().join(".oag-cache/manifest.json");
+    assert!(manifest.exists());
+
+    // The second run only checks the manifest: no parsing, no writes.
+    let client = tmp.path().join("out/src/client.ts");
+    let generated = fs::read_to_string(&client).unwrap();
+    let modified = fs::metadata(&client).unwrap().modified().unwrap();
+    let (events, second) = run(&[]);
+    let names: Vec<&str> = second.iter().map(|(phase, _)| phase.as_str()).collect();
+    assert_eq!(names, ["check"]);
+    assert!(total(&second) <= total(&first));
+    assert!(!events.iter().any(|e| e["event"] == "file"));
+    assert!(events.iter().any(|e| {
+        e["message"]
+            .as_str()
+            .is_some_and(|m| m.contains("up to date"))
+    }));
+    assert_eq!(fs::metadata(&client).unwrap().modified().unwrap(), modified);
+
+    // An edited or deleted output is no longer current, so the spec is
+    // parsed again and the file restored.
+    fs::write(&client, "// edited\n").unwrap();
+    let (_, edited) = run(&[]);
+    assert!(edited.iter().any(|(phase, _)| phase == "load"));
+    assert_eq!(fs::read_to_string(&client).unwrap(), generated);
+    fs::remove_file(&client).unwrap();
+    let (events, deleted) = run(&[]);
+    assert!(deleted.iter().any(|(phase, _)| phase == "generate"));
+    assert!(events.iter().any(|e| e["event"] == "file"));
+    assert_eq!(fs::read_to_string(&client).unwrap(), generated);
+    let (events, forced) = run(&["--force"]);
+    assert!(!forced.iter().any(|(phase, _)| phase == "check"));
+    assert!(events.iter().any(|e| e["event"] == "file"));
+}
+
+#[test]
+fn generate_writes_changelog_of_api_changes() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join(".urmzd.oag.yaml"),
+        "changelog: true\ngenerators:\n  node-client:\n    output: out\n    scaffold: false\n",
+    )
+    .unwrap();
+    let changelog = tmp.path().join("CHANGELOG.generated.md");
+
+    // The first run only records the IR to diff against.
+    assert!(oag(tmp.path(), &["generate"], "").status.success());
+    assert!(tmp.path().join(".oag-cache/ir.json").exists());
+    assert!(!changelog.exists());
+
+    let added = PETSTORE.replace(
+        "components:\n",
+        "  /health:\n    get:\n      operationId: getHealth\n      responses:\n        \"204\":\n          description: Healthy\n\ncomponents:\n",
+    );
+    fs::write(tmp.path().join("openapi.yaml"), added).unwrap();
+    let second = oag(tmp.path(), &["generate"], "");
+    assert!(second.status.success());
+    assert!(String::from_utf8_lossy(&second.stderr).contains("CHANGELOG.generated.md"));
+    assert_eq!(
+        fs::read_to_string(&changelog).unwrap(),
+        "# Changelog\n\n## 1.0.0\n\n### Added\n\n- `GET /health (getHealth)`: operation added\n"
+    );
+
+    // Later entries go on top.
+    fs::write(tmp.path().join("openapi.yaml"), PETSTORE).unwrap();
+    assert!(oag(tmp.path(), &["generate"], "").status.success());
+    let content = fs::read_to_string(&changelog).unwrap();
+    assert!(content.starts_with(
+        "# Changelog\n\n## 1.0.0\n\n### Removed\n\n- **Breaking:** `GET /health (getHealth)`: operation removed\n\n## 1.0.0\n"
+    ));
+}
+
+#[test]
+fn validate_strict_rejects_approximated_schemas() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let lenient = oag(tmp.path(), &["validate", "-i", "-"], CONDITIONAL);
+    let stderr = String::from_utf8_lossy(&lenient.stderr);
+    assert!(lenient.status.success(), "{stderr}");
+    assert!(stderr.contains("warning: schema `Shipment` uses `if`"));
+
+    let strict = oag(
+        tmp.path(),
+        &["validate", "-i", "-", "--strict"],
+        CONDITIONAL,
+    );
+    assert!(!strict.status.success());
+    assert!(String::from_utf8_lossy(&strict.stderr).contains("unsupported keywords"));
+}
+
+#[test]
+fn validate_strict_rejects_broken_refs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let spec = PETSTORE.replace(
+        "#/components/schemas/Pet\"",
+        "#/components/schemas/Missing\"",
+    );
+
+    let lenient = oag(tmp.path(), &["validate", "-i", "-"], &spec);
+    assert!(lenient.status.success());
+    assert!(String::from_utf8_lossy(&lenient.stderr).contains("unknown schema `Missing`"));
+
+    let strict = oag(tmp.path(), &["validate", "-i", "-", "--strict"], &spec);
+    assert!(!strict.status.success());
+    assert!(String::from_utf8_lossy(&strict.stderr).contains("unknown schema `Missing`"));
+}
+
+#[test]
+fn validate_counts_shared_components() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = oag(tmp.path(), &["validate", "-i", "-"], SHARED_COMPONENTS);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    for line in ["Parameters: 1", "Request bodies: 1", "Responses: 2"] {
+        assert!(stderr.contains(line), "missing {line:?} in {stderr}");
+    }
+    assert!(!stderr.contains("Security schemes:"));
+}
+
+#[test]
+fn validate_reports_every_broken_component_ref() {
+    let tmp = tempfile::tempdir().unwrap();
+    let spec = SHARED_COMPONENTS
+        .replace(
+            "#/components/parameters/PageFilter\"",
+            "#/components/parameters/Paging\"",
+        )
+        .replace(
+            "#/components/requestBodies/Pet\"",
+            "#/components/responses/Pet\"",
+        );
+
+    let output = oag(tmp.path(), &["validate", "-i", "-"], &spec);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("4 `$ref`(s) don't resolve:"), "{stderr}");
+    assert!(stderr.contains(
+        "`#/components/parameters/Paging` (parameters): reference target not found: #/components/parameters/Paging"
+    ));
+
+    let json = oag(
+        tmp.path(),
+        &["validate", "-i", "-", "--format", "json"],
+        &spec,
+    );
+    assert!(!json.status.success());
+    let findings: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    let refs = findings["refs"].as_array().unwrap();
+    assert_eq!(refs.len(), 4);
+    assert_eq!(
+        refs[1],
+        serde_json::json!({
+            "section": "requestBodies",
+            "ref": "#/components/responses/Pet",
+            "message": "invalid reference format: expected section 'requestBodies', got 'responses' in #/components/responses/Pet",
+        })
+    );
+}
+
+#[test]
+fn validate_checks_examples_against_their_schemas() {
+    let tmp = tempfile::tempdir().unwrap();
+
+    let lenient = oag(tmp.path(), &["validate", "-i", "-"], EXAMPLES);
+    let stderr = String::from_utf8_lossy(&lenient.stderr);
+    assert!(lenient.status.success(), "{stderr}");
+    assert!(stderr.contains(
+        "warning: POST /pets request body (application/json) example `broken` at `$.tags[1]`: expected a string, found a number (7)"
+    ));
+    assert!(!stderr.contains("example `valid`"));
+
+    let strict = oag(tmp.path(), &["validate", "-i", "-", "--strict"], EXAMPLES);
+    assert!(!strict.status.success());
+    assert!(
+        String::from_utf8_lossy(&strict.stderr)
+            .contains("3 example(s) don't match their schemas (--strict)")
+    );
+
+    let json = oag(
+        tmp.path(),
+        &["validate", "-i", "-", "--format", "json"],
+        EXAMPLES,
+    );
+    assert!(json.status.success());
+    let findings: serde_json::Value = serde_json::from_slice(&json.stdout).unwrap();
+    let examples = findings["examples"].as_array().unwrap();
+    assert_eq!(examples.len(), 3);
+    assert_eq!(
+        examples[0],
+        serde_json::json!({
+            "location": "POST /pets request body (application/json) example `broken`",
+            "path": "$",
+            "message": "missing required field `name`",
+        })
+    );
+}
+
+#[test]
+fn validate_prints_feature_report() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = oag(
+        tmp.path(),
+        &["validate", "-i", "-", "--report", "features"],
+        CONDITIONAL,
+    );
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("not/if/then/else: 0 supported, 1 approximated, 0 dropped\n"));
+    assert!(stdout.contains("  approximated Shipment — `if`, `then`, `else` not enforced\n"));
+}
+
+#[test]
+fn generate_prints_bundled_client_to_stdout() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = oag(
+        tmp.path(),
+        &["generate", "-i", "-", "--target", "typescript", "--stdout"],
+        PETSTORE,
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.starts_with("// Auto-generated by oag — do not edit (bundled)\n"));
+    assert!(stdout.contains("export interface Pet {"));
+    assert!(stdout.contains(
+        "  /**\n   * List all pets\n   *\n   * @returns A list of pets\n   */\n  async listPets("
+    ));
+    assert!(!stdout.contains("from \"./"));
+    // Nothing is written to disk.
+    assert_eq!(fs::read_dir(tmp.path()).unwrap().count(), 0);
+
+    let no_jsdoc = oag(
+        tmp.path(),
+        &[
+            "generate",
+            "-i",
+            "-",
+            "--target",
+            "typescript",
+            "--stdout",
+            "--no-jsdoc",
+        ],
+        PETSTORE,
+    );
+    assert!(no_jsdoc.status.success());
+    let stdout = String::from_utf8_lossy(&no_jsdoc.stdout);
+    assert!(stdout.contains("async listPets("));
+    assert!(!stdout.contains("List all pets"));
+    assert!(!stdout.contains("@returns"));
+}
+
+#[test]
+fn generate_types_only_prints_just_the_models() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = oag(
+        tmp.path(),
+        &[
+            "generate",
+            "-i",
+            "-",
+            "--target",
+            "fastapi-server",
+            "--stdout",
+            "--types-only",
+        ],
+        PETSTORE,
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{stderr}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("class Pet(BaseModel):"));
+    assert!(!stdout.contains("APIRouter"));
+}
+
+#[test]
+fn generate_stdout_rejects_multi_file_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let output = oag(
+        tmp.path(),
+        &[
+            "generate",
+            "-i",
+            "-",
+            "--target",
+            "fastapi-server",
+            "--stdout",
+        ],
+        PETSTORE,
+    );
+    assert!(!output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(
+        String::from_utf8_lossy(&output.stderr)
+            .contains("--stdout needs a single generated file, but fastapi-server produces")
+    );
+}
+
+#[test]
+fn diff_spec_reports_breaking_changes_as_json() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("old.yaml"), PETSTORE).unwrap();
+    fs::write(
+        tmp.path().join("new.yaml"),
+        PETSTORE.replace("operationId: listPets", "operationId: findPets"),
+    )
+    .unwrap();
+
+    let output = oag(
+        tmp.path(),
+        &["diff-spec", "old.yaml", "new.yaml", "--format", "json"],
+        "",
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let diff: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        diff["changes"],
+        serde_json::json!([{
+            "kind": "operation_renamed",
+            "severity": "breaking",
+            "location": "GET /pets (findPets)",
+            "message": "renamed from `listPets` to `findPets`",
+        }])
+    );
+}
+
+#[test]
+fn fixture_extracts_operations_with_the_components_they_use() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(tmp.path().join("big.yaml"), ANTHROPIC).unwrap();
+
+    let output = oag(
+        tmp.path(),
+        &[
+            "fixture",
+            "--from",
+            "big.yaml",
+            "--operations",
+            "createMessage,listModels",
+            "--out",
+            "minimal.yaml",
+        ],
+        "",
+    );
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let content = fs::read_to_string(tmp.path().join("minimal.yaml")).unwrap();
+    let spec = oag_core::parse::from_yaml(&content).unwrap();
+    assert_eq!(spec.info.title, "Anthropic Messages API");
+    let ir = oag_core::transform::transform(&spec).unwrap();
+    let operations: Vec<&str> = ir
+        .operations
+        .iter()
+        .map(|op| op.name.camel_case.as_str())
+        .collect();
+    assert!(operations.contains(&"createMessage"));
+    assert!(operations.contains(&"listModels"));
+    assert!(!operations.contains(&"countTokens"));
+    assert!(!content.contains("CountTokensRequest:"));
+    // Every kept schema is referenced from somewhere else in the fixture.
+    for name in spec.components.unwrap().schemas.keys() {
+        assert!(
+            content.contains(&format!("#/components/schemas/{name}")),
+            "unreferenced schema `{name}`"
+        );
+    }
+
+    let output = oag(
+        tmp.path(),
+        &["fixture", "--from", "big.yaml", "--operations", "nope"],
+        "",
+    );
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("no operation with id `nope`"));
+}
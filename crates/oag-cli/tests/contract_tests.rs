@@ -0,0 +1,69 @@
+//! End-to-end run of the generated contract suite: the petstore node client
+//! calls the petstore FastAPI stubs over HTTP.
+//!
+//! Ignored by default since it installs npm packages and needs Python with
+//! FastAPI and uvicorn; run with `cargo test -p oag-cli --test contract_tests
+//! -- --ignored`.
+
+use std::fs;
+use std::process::Command;
+
+const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
+
+const CONFIG: &str = r#"input: openapi.yaml
+generators:
+  node-client:
+    output: client
+    contract_tests: true
+    scaffold:
+      package_name: "@test/contract-client"
+      formatter: false
+      bundler: false
+      test_runner: vitest
+  fastapi-server:
+    output: server
+"#;
+
+#[test]
+#[ignore = "needs Node.js, npm and Python with FastAPI and uvicorn"]
+fn petstore_client_passes_against_the_fastapi_stubs() {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    fs::write(dir.join("openapi.yaml"), PETSTORE).unwrap();
+    fs::write(dir.join(".urmzd.oag.yaml"), CONFIG).unwrap();
+
+    let generate = Command::new(env!("CARGO_BIN_EXE_oag"))
+        .arg("generate")
+        .current_dir(dir)
+        .output()
+        .expect("failed to run oag");
+    assert!(
+        generate.status.success(),
+        "oag generate failed:\n{}",
+        String::from_utf8_lossy(&generate.stderr)
+    );
+
+    let install = Command::new("npm")
+        .args(["install", "--no-audit", "--no-fund"])
+        .current_dir(dir.join("client"))
+        .output()
+        .expect("failed to run npm install");
+    assert!(
+        install.status.success(),
+        "npm install failed:\n{}",
+        String::from_utf8_lossy(&install.stderr)
+    );
+
+    let python = if cfg!(windows) { "python" } else { "python3" };
+    let run = Command::new(python)
+        .args(["contract/run.py", "--client", "../client"])
+        .current_dir(dir.join("server"))
+        .output()
+        .expect("failed to run contract/run.py");
+    assert!(
+        run.status.success(),
+        "contract suite failed:\n{}\n{}",
+        String::from_utf8_lossy(&run.stdout),
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
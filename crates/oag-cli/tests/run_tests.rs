@@ -0,0 +1,510 @@
+use oag_core::GeneratedFile;
+use oag_core::config::{GeneratorConfig, GeneratorId, GeneratorNamingConfig, OagConfig};
+use oag_core::error::{RunError, TransformError};
+use oag_core::ir::dump;
+use oag_core::run::{self, GENERATED_README, GeneratorRegistry, SpecSource};
+use oag_fastapi_server::FastapiServerGenerator;
+use oag_node_client::NodeClientGenerator;
+use oag_react_swr_client::ReactSwrClientGenerator;
+
+const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
+const ANTHROPIC: &str = include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml");
+const SCHEMAS_ONLY: &str = include_str!("../../oag-core/tests/fixtures/schemas-only.yaml");
+
+fn registry() -> GeneratorRegistry {
+    let mut registry = GeneratorRegistry::new();
+    registry
+        .register(NodeClientGenerator)
+        .register(ReactSwrClientGenerator)
+        .register(FastapiServerGenerator);
+    registry
+}
+
+fn config(generators: &[(GeneratorId, &str)]) -> OagConfig {
+    let mut cfg = OagConfig::default();
+    for (id, output) in generators {
+        cfg.generators.insert(
+            *id,
+            GeneratorConfig {
+                output: output.to_string(),
+                ..Default::default()
+            },
+        );
+    }
+    cfg
+}
+
+fn paths(output: &run::GeneratorOutput) -> Vec<&str> {
+    output.files.iter().map(|f| f.path.as_str()).collect()
+}
+
+#[test]
+fn generates_every_configured_generator_in_memory() {
+    let cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+
+    assert_eq!(result.outputs.len(), 2);
+
+    let node = &result.outputs[0];
+    assert_eq!(node.id, GeneratorId::NodeClient);
+    assert_eq!(node.output.to_str(), Some("out/node"));
+    assert_eq!(
+        paths(node),
+        [
+            "src/types.ts",
+            "src/sse.ts",
+            "src/client.ts",
+            "src/index.ts",
+            "README.md"
+        ]
+    );
+    assert!(node.warnings.is_empty());
+    let readme = node.files.iter().find(|f| f.path == "README.md").unwrap();
+    assert_eq!(readme.content, GENERATED_README);
+
+    let api = &result.outputs[1];
+    assert_eq!(api.id, GeneratorId::FastapiServer);
+    assert!(paths(api).contains(&"routes.py"));
+}
+
+#[test]
+fn applies_naming_options_and_file_filters() {
+    let mut cfg = config(&[(GeneratorId::ReactSwrClient, "out/react")]);
+    cfg.naming
+        .aliases
+        .insert("listPets".to_string(), "allPets".to_string());
+    cfg.generators[&GeneratorId::ReactSwrClient].files.exclude = vec!["src/sse.ts".to_string()];
+
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+    let react = &result.outputs[0];
+
+    assert!(!paths(react).contains(&"src/sse.ts"));
+    assert!(paths(react).contains(&"src/hooks.tsx"));
+    assert!(!react.warnings.is_empty(), "client.ts still imports sse.ts");
+
+    let hooks = react
+        .files
+        .iter()
+        .find(|f| f.path == "src/hooks.tsx")
+        .unwrap();
+    assert!(hooks.content.contains("useAllPets"));
+}
+
+#[test]
+fn per_generator_naming_overlays_global_aliases() {
+    let mut cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    cfg.naming
+        .aliases
+        .insert("listPets".to_string(), "allPets".to_string());
+    cfg.generators[&GeneratorId::NodeClient].naming = Some(GeneratorNamingConfig {
+        aliases: [("getPet".to_string(), "pet".to_string())].into(),
+        ..Default::default()
+    });
+
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+    let file = |output: &run::GeneratorOutput, path: &str| {
+        output
+            .files
+            .iter()
+            .find(|f| f.path == path)
+            .unwrap()
+            .content
+            .clone()
+    };
+
+    let client = file(&result.outputs[0], "src/client.ts");
+    assert!(client.contains("async allPets("));
+    assert!(client.contains("async pet("));
+    assert!(!client.contains("async getPet("));
+
+    let routes = file(&result.outputs[1], "routes.py");
+    assert!(routes.contains("async def all_pets("));
+    assert!(routes.contains("async def get_pet("));
+    assert!(!routes.contains("async def pet("));
+}
+
+#[test]
+fn index_json_lists_each_generators_own_names() {
+    let mut cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    let node = &mut cfg.generators[&GeneratorId::NodeClient];
+    node.index_json = true;
+    node.naming = Some(GeneratorNamingConfig {
+        aliases: [("getPet".to_string(), "pet".to_string())].into(),
+        ..Default::default()
+    });
+
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+    assert!(!paths(&result.outputs[1]).contains(&"oag.operations.json"));
+
+    let index = result.outputs[0]
+        .files
+        .iter()
+        .find(|f| f.path == "oag.operations.json")
+        .unwrap();
+    let index: serde_json::Value = serde_json::from_str(&index.content).unwrap();
+    let names: Vec<&str> = index["operations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|op| op["name"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"pet"));
+    assert!(!names.contains(&"getPet"));
+}
+
+#[test]
+fn generating_from_an_ir_dump_matches_generating_from_the_spec() {
+    let mut cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::ReactSwrClient, "out/react"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    // Per-generator naming rebuilds the IR from the dump's raw spec.
+    cfg.generators[&GeneratorId::NodeClient].naming = Some(GeneratorNamingConfig {
+        aliases: [("getPet".to_string(), "pet".to_string())].into(),
+        ..Default::default()
+    });
+
+    let ir = run::load_ir(&cfg, SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+    let direct = run::generate_from_ir(&cfg, &registry(), &ir).unwrap();
+    let dumped = dump::from_json(&dump::to_json(&ir)).unwrap();
+    let from_dump = run::generate_from_ir(&cfg, &registry(), &dumped).unwrap();
+
+    assert_eq!(direct.outputs.len(), from_dump.outputs.len());
+    for (direct, from_dump) in direct.outputs.iter().zip(&from_dump.outputs) {
+        assert_eq!(direct.id, from_dump.id);
+        let files = |output: &run::GeneratorOutput| -> Vec<(String, String)> {
+            output
+                .files
+                .iter()
+                .map(|f| (f.path.clone(), f.content.clone()))
+                .collect()
+        };
+        assert_eq!(files(direct), files(from_dump), "{}", direct.id);
+    }
+}
+
+#[test]
+fn type_prefix_renames_every_schema_type() {
+    let mut cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    cfg.generators[&GeneratorId::NodeClient].type_prefix = Some("Api".to_string());
+
+    let ir = run::load_ir(&cfg, SpecSource::Yaml(ANTHROPIC.to_string())).unwrap();
+    let result = run::generate_from_ir(&cfg, &registry(), &ir).unwrap();
+    let file = |output: &run::GeneratorOutput, path: &str| {
+        output
+            .files
+            .iter()
+            .find(|f| f.path == path)
+            .unwrap()
+            .content
+            .clone()
+    };
+
+    let mut names: Vec<&str> = ir
+        .schemas
+        .iter()
+        .map(|s| s.name().pascal_case.as_str())
+        .collect();
+    names.push("CreateMessageStreamEvent");
+    let is_ident = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    let types = file(&result.outputs[0], "src/types.ts");
+    for name in &names {
+        let unprefixed = types.match_indices(name).any(|(i, _)| {
+            !types[..i].ends_with(is_ident) && !types[i + name.len()..].starts_with(is_ident)
+        });
+        assert!(!unprefixed, "`{name}` is left unprefixed in types.ts");
+        assert!(
+            types.contains(&format!("Api{name}")),
+            "no `Api{name}` in types.ts"
+        );
+    }
+
+    // Signatures use the prefixed names; wire names don't change.
+    let client = file(&result.outputs[0], "src/client.ts");
+    assert!(client.contains("body: ApiCreateMessageRequest"));
+    assert!(client.contains("AsyncGenerator<ApiCreateMessageStreamEvent"));
+    assert!(types.contains("stop_reason"));
+
+    // Other generators are left alone.
+    let models = file(&result.outputs[1], "models.py");
+    assert!(models.contains("class TextBlock(BaseModel):"));
+}
+
+#[test]
+fn schema_aliases_leave_no_stale_references() {
+    let mut cfg = config(&[(GeneratorId::NodeClient, "out/node")]);
+    cfg.naming.schema_aliases = [
+        (
+            "InlineResponse2001".to_string(),
+            "PetListResponse".to_string(),
+        ),
+        ("InlineResponse400".to_string(), "SearchError".to_string()),
+    ]
+    .into();
+
+    let spec = include_str!("../../oag-core/tests/fixtures/schema-aliases.yaml");
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(spec.to_string())).unwrap();
+    let node = &result.outputs[0];
+
+    for file in &node.files {
+        assert!(
+            !file.content.contains("InlineResponse"),
+            "{} still mentions an old schema name",
+            file.path
+        );
+    }
+    let types = &node
+        .files
+        .iter()
+        .find(|f| f.path == "src/types.ts")
+        .unwrap()
+        .content;
+    assert!(types.contains("export interface PetListResponse {"));
+    assert!(types.contains("export type SearchResult = PetListResponse | SearchError;"));
+}
+
+#[test]
+fn contract_tests_on_one_generator_emit_both_halves() {
+    let mut cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    cfg.generators
+        .get_mut(&GeneratorId::FastapiServer)
+        .unwrap()
+        .contract_tests = true;
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+
+    let node = &result.outputs[0];
+    assert!(paths(node).contains(&"contract/client.contract.ts"));
+    assert!(paths(node).contains(&"contract/vitest.config.ts"));
+    let api = &result.outputs[1];
+    for path in [
+        "contract/mocks.json",
+        "contract/server.py",
+        "contract/run.py",
+    ] {
+        assert!(paths(api).contains(&path), "no {path}");
+    }
+
+    // Every operation the client calls has a canned response on the server.
+    let suite = &node
+        .files
+        .iter()
+        .find(|f| f.path == "contract/client.contract.ts")
+        .unwrap()
+        .content;
+    let mocks = &api
+        .files
+        .iter()
+        .find(|f| f.path == "contract/mocks.json")
+        .unwrap()
+        .content;
+    let mocks: Vec<serde_json::Value> = serde_json::from_str(mocks).unwrap();
+    assert_eq!(suite.matches("  it(\"").count(), mocks.len());
+}
+
+#[test]
+fn specs_without_operations_generate_types_only() {
+    let cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::ReactSwrClient, "out/react"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    let result = run::generate(
+        &cfg,
+        &registry(),
+        SpecSource::Yaml(SCHEMAS_ONLY.to_string()),
+    )
+    .unwrap();
+
+    let [node, react, api] = &result.outputs[..] else {
+        panic!("expected three outputs");
+    };
+    assert!(node.types_only && react.types_only && api.types_only);
+    assert_eq!(paths(node), ["src/types.ts", "src/index.ts", "README.md"]);
+    assert_eq!(paths(react), ["src/types.ts", "src/index.ts", "README.md"]);
+    assert_eq!(paths(api), ["models.py", "README.md"]);
+
+    let types = &node.files[0].content;
+    for decl in [
+        "export interface Pet {",
+        "export type PetStatus =",
+        "export interface Owner {",
+    ] {
+        assert!(types.contains(decl), "no {decl}");
+    }
+    assert_eq!(
+        node.files[1].content,
+        "// Auto-generated by oag — do not edit\nexport * from \"./types\";\n"
+    );
+    assert!(api.files[0].content.contains("class Pet(BaseModel):"));
+}
+
+#[test]
+fn types_only_applies_to_specs_with_operations() {
+    let mut cfg = config(&[
+        (GeneratorId::NodeClient, "out/node"),
+        (GeneratorId::FastapiServer, "out/api"),
+    ]);
+    for gen_config in cfg.generators.values_mut() {
+        gen_config.types_only = true;
+    }
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+
+    assert_eq!(
+        paths(&result.outputs[0]),
+        ["src/types.ts", "src/index.ts", "README.md"]
+    );
+    assert_eq!(paths(&result.outputs[1]), ["models.py", "README.md"]);
+
+    // Without the flag, the same spec gets its client.
+    let result = run::generate(
+        &config(&[(GeneratorId::NodeClient, "out/node")]),
+        &registry(),
+        SpecSource::Yaml(PETSTORE.to_string()),
+    )
+    .unwrap();
+    assert!(!result.outputs[0].types_only);
+}
+
+#[test]
+fn per_generator_alias_collisions_are_errors() {
+    let mut cfg = config(&[(GeneratorId::NodeClient, "out/node")]);
+    cfg.generators[&GeneratorId::NodeClient].naming = Some(GeneratorNamingConfig {
+        aliases: [("getPet".to_string(), "listPets".to_string())].into(),
+        ..Default::default()
+    });
+
+    let err = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap_err();
+    assert!(matches!(
+        err,
+        RunError::Transform(TransformError::AliasCollision { .. })
+    ));
+}
+
+#[test]
+fn unregistered_generator_is_an_error() {
+    let cfg = config(&[(GeneratorId::FastapiServer, "out/api")]);
+    let mut registry = GeneratorRegistry::new();
+    registry.register(NodeClientGenerator);
+
+    let err = run::generate(&cfg, &registry, SpecSource::Yaml(PETSTORE.to_string())).unwrap_err();
+    assert!(matches!(
+        err,
+        RunError::UnregisteredGenerator(GeneratorId::FastapiServer)
+    ));
+}
+
+#[test]
+fn overlapping_outputs_are_an_error() {
+    let mut cfg = config(&[
+        (GeneratorId::NodeClient, "out/client"),
+        (GeneratorId::ReactSwrClient, "out/./client"),
+    ]);
+    for gen_config in cfg.generators.values_mut() {
+        gen_config.scaffold = Some(serde_json::json!({ "package_name": "pets" }));
+    }
+
+    let err = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap_err();
+    let RunError::OutputOverlap { overlaps } = &err else {
+        panic!("expected an overlap error, got {err}");
+    };
+    let package = overlaps
+        .iter()
+        .find(|o| o.path == std::path::Path::new("out/client/package.json"))
+        .expect("package.json should conflict");
+    assert_eq!(package.first, GeneratorId::NodeClient);
+    assert_eq!(package.second, GeneratorId::ReactSwrClient);
+    // Identical files don't conflict.
+    assert!(overlaps.iter().all(|o| !o.path.ends_with("README.md")));
+    let message = err.to_string();
+    assert!(
+        message.contains("out/client/package.json (written by node-client and react-swr-client)")
+    );
+    assert!(message.contains("allow_overlap: true"));
+
+    cfg.allow_overlap = true;
+    assert!(run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).is_ok());
+}
+
+#[test]
+fn writes_output_under_root() {
+    let cfg = config(&[(GeneratorId::NodeClient, "out/node")]);
+    let result = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+
+    let tmp = tempfile::tempdir().unwrap();
+    let written = run::write_output(&result, tmp.path()).unwrap();
+
+    assert_eq!(written.len(), result.outputs[0].files.len());
+    let client = tmp.path().join("out/node/src/client.ts");
+    assert!(written.contains(&client));
+    assert!(client.exists());
+    assert!(tmp.path().join("out/node/README.md").exists());
+}
+
+#[test]
+fn hostile_source_dir_fails_before_writing() {
+    let mut cfg = config(&[(GeneratorId::NodeClient, "out/node")]);
+    cfg.generators[&GeneratorId::NodeClient].source_dir = "../../escaped".to_string();
+
+    let err = run::generate(&cfg, &registry(), SpecSource::Yaml(PETSTORE.to_string()))
+        .unwrap_err()
+        .to_string();
+    assert_eq!(
+        err,
+        "node-client: refusing to write `../../escaped`: `..` components aren't allowed"
+    );
+}
+
+#[test]
+fn write_rejects_paths_outside_the_output() {
+    let tmp = tempfile::tempdir().unwrap();
+    let root = tmp.path().join("project");
+    let output = |path: &str| run::GeneratorOutput {
+        id: GeneratorId::NodeClient,
+        output: "out".into(),
+        files: vec![
+            GeneratedFile::new("index.ts", ""),
+            GeneratedFile::new(path, "pwned"),
+        ],
+        warnings: Vec::new(),
+        types_only: false,
+    };
+
+    for path in ["../../escaped.ts", "/tmp/escaped.ts"] {
+        let err = run::write_generator_output(&output(path), &root).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains(&format!("refusing to write `{path}`"))
+        );
+        // Nothing is written, not even the safe files before it.
+        assert!(!root.join("out/index.ts").exists());
+    }
+    assert!(!tmp.path().join("escaped.ts").exists());
+
+    #[cfg(unix)]
+    {
+        std::fs::create_dir_all(root.join("out")).unwrap();
+        std::os::unix::fs::symlink(tmp.path(), root.join("out/link")).unwrap();
+        let err = run::write_generator_output(&output("link/escaped.ts"), &root).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("it resolves outside the output directory")
+        );
+        assert!(!tmp.path().join("escaped.ts").exists());
+    }
+}
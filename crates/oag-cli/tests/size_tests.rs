@@ -0,0 +1,103 @@
+//! Size guardrails: upper bounds on the files each generator emits for the
+//! petstore fixture, so accidental duplication (a helper emitted twice, an
+//! operation rendered once per variant) fails CI instead of shipping.
+//!
+//! Budgets sit about 25% above the current output. When a change grows the
+//! output on purpose, raise the affected budgets to roughly 25% above the new
+//! sizes the failure message reports; see CONTRIBUTING.md.
+
+use oag_core::config::{GeneratorConfig, GeneratorId, OagConfig};
+use oag_core::run::{self, GeneratorRegistry, SpecSource};
+use oag_fastapi_server::FastapiServerGenerator;
+use oag_node_client::NodeClientGenerator;
+use oag_react_swr_client::ReactSwrClientGenerator;
+
+const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
+
+/// Every file each generator emits with its default config, and its maximum
+/// size in bytes.
+const BUDGETS: &[(GeneratorId, &[(&str, usize)])] = &[
+    (
+        GeneratorId::NodeClient,
+        &[
+            ("src/types.ts", 1_450),
+            ("src/sse.ts", 18_500),
+            ("src/client.ts", 27_700),
+            ("src/index.ts", 550),
+            ("README.md", 450),
+        ],
+    ),
+    (
+        GeneratorId::ReactSwrClient,
+        &[
+            ("src/types.ts", 1_450),
+            ("src/sse.ts", 18_500),
+            ("src/client.ts", 27_700),
+            ("src/hooks.tsx", 4_700),
+            ("src/provider.tsx", 1_300),
+            ("src/index.tsx", 700),
+            ("README.md", 450),
+        ],
+    ),
+    (
+        GeneratorId::FastapiServer,
+        &[
+            ("models.py", 1_000),
+            ("routes.py", 1_800),
+            ("sse.py", 1_900),
+            ("main.py", 200),
+            ("__init__.py", 50),
+            ("py.typed", 50),
+            ("README.md", 450),
+        ],
+    ),
+];
+
+#[test]
+fn petstore_output_stays_within_budget() {
+    let mut registry = GeneratorRegistry::new();
+    registry
+        .register(NodeClientGenerator)
+        .register(ReactSwrClientGenerator)
+        .register(FastapiServerGenerator);
+    let mut cfg = OagConfig::default();
+    for (id, _) in BUDGETS {
+        cfg.generators.insert(
+            *id,
+            GeneratorConfig {
+                output: "out".to_string(),
+                ..Default::default()
+            },
+        );
+    }
+    let result = run::generate(&cfg, &registry, SpecSource::Yaml(PETSTORE.to_string())).unwrap();
+
+    let mut over = Vec::new();
+    for (id, budget) in BUDGETS {
+        let output = result.outputs.iter().find(|o| o.id == *id).unwrap();
+        let mut emitted: Vec<&str> = output.files.iter().map(|f| f.path.as_str()).collect();
+        let mut budgeted: Vec<&str> = budget.iter().map(|(path, _)| *path).collect();
+        emitted.sort_unstable();
+        budgeted.sort_unstable();
+        assert_eq!(
+            emitted, budgeted,
+            "{id:?} emits a different set of files than BUDGETS lists"
+        );
+
+        for file in &output.files {
+            let (_, max) = budget.iter().find(|(path, _)| *path == file.path).unwrap();
+            if file.content.len() > *max {
+                over.push(format!(
+                    "{id:?} {}: {} bytes (budget {max})",
+                    file.path,
+                    file.content.len()
+                ));
+            }
+        }
+    }
+    assert!(
+        over.is_empty(),
+        "generated files over budget:\n  {}\nif the growth is intended, raise BUDGETS",
+        over.join("\n  ")
+    );
+}
@@ -0,0 +1,95 @@
+//! Allocations and time per parse + transform of the Anthropic fixture, for
+//! the text and value parsers and the borrowed and owned transforms.
+//!
+//! Run with `cargo bench -p oag-core --bench transform`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use oag_core::parse;
+use oag_core::transform::{self, TransformOptions};
+
+const ANTHROPIC: &str = include_str!("../tests/fixtures/anthropic-messages.yaml");
+const ITERATIONS: u32 = 200;
+
+/// The system allocator, counting every allocation.
+struct Counting;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(new_size, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+/// Run `setup` then `f` `ITERATIONS` times, counting only `f`.
+fn bench<T, R>(name: &str, mut setup: impl FnMut() -> T, mut f: impl FnMut(T) -> R) {
+    let mut allocations = 0;
+    let mut bytes = 0;
+    let mut elapsed = Duration::ZERO;
+    for _ in 0..ITERATIONS {
+        let input = setup();
+        let (a, b) = (
+            ALLOCATIONS.load(Ordering::Relaxed),
+            BYTES.load(Ordering::Relaxed),
+        );
+        let start = Instant::now();
+        black_box(f(input));
+        elapsed += start.elapsed();
+        allocations += ALLOCATIONS.load(Ordering::Relaxed) - a;
+        bytes += BYTES.load(Ordering::Relaxed) - b;
+    }
+    println!(
+        "{name:<30} {:>8} allocs {:>10} bytes {:>10.1?}",
+        allocations / ITERATIONS as usize,
+        bytes / ITERATIONS as usize,
+        elapsed / ITERATIONS
+    );
+}
+
+fn main() {
+    let options = TransformOptions::default();
+    let value: serde_yaml_ng::Value = serde_yaml_ng::from_str(ANTHROPIC).unwrap();
+    let spec = parse::from_yaml(ANTHROPIC).unwrap();
+
+    println!("per iteration, anthropic-messages.yaml:");
+    bench(
+        "parse::from_yaml",
+        || (),
+        |()| parse::from_yaml(ANTHROPIC).unwrap(),
+    );
+    bench(
+        "parse::from_yaml_value",
+        || value.clone(),
+        |value| parse::from_yaml_value(value).unwrap(),
+    );
+    bench(
+        "transform_with_options(&spec)",
+        || (),
+        |()| transform::transform_with_options(&spec, &options).unwrap(),
+    );
+    bench(
+        "transform_owned(spec)",
+        || spec.clone(),
+        |spec| transform::transform_owned(spec, &options).unwrap(),
+    );
+}
@@ -3,8 +3,10 @@ use std::fs;
 use std::path::Path;
 
 use indexmap::IndexMap;
-use serde::de;
-use serde::{Deserialize, Deserializer};
+use serde::de::{self, DeserializeOwned};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::ir::QueryObjectNotation;
 
 /// A tool setting that can be a named tool or explicitly disabled.
 ///
@@ -49,11 +51,346 @@ impl<'de> Deserialize<'de> for ToolSetting {
     }
 }
 
+/// The `version` a scaffold gives the generated package (`package.json`,
+/// `pyproject.toml`).
+///
+/// In YAML: `spec` → `Spec`, `spec+date` → `SpecDate`, anything else (`2.1.0`)
+/// → `Literal`. Absent keeps the scaffold's `0.1.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScaffoldVersion {
+    /// The spec's `info.version`.
+    Spec,
+    /// The spec's `info.version` with a `-YYYYMMDD` prerelease tag of the
+    /// generation date (UTC, or `SOURCE_DATE_EPOCH` when set).
+    SpecDate,
+    Literal(String),
+}
+
+/// A package version sanitized to semver: `1.0` → `1.0.0`, `v2` → `2.0.0`,
+/// a calendar date `2023-06-01` → `2023.6.1`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageVersion {
+    pub release: [u64; 3],
+    /// Prerelease identifiers (`beta.1` → `["beta", "1"]`).
+    pub pre: Vec<String>,
+    /// The `spec+date` tag, `YYYYMMDD`.
+    pub date: Option<String>,
+    /// Build metadata identifiers.
+    pub build: Vec<String>,
+}
+
+impl ScaffoldVersion {
+    /// The package version for a spec at `spec_version`, generated on `date`
+    /// (`YYYYMMDD`). `Err` with a warning when it isn't a version.
+    pub fn resolve(&self, spec_version: &str, date: &str) -> Result<PackageVersion, String> {
+        let (source, raw) = match self {
+            ScaffoldVersion::Spec | ScaffoldVersion::SpecDate => ("info.version", spec_version),
+            ScaffoldVersion::Literal(literal) => ("scaffold.version", literal.as_str()),
+        };
+        let mut version = PackageVersion::parse(raw)
+            .ok_or_else(|| format!("`{source}` `{raw}` isn't a version; using 0.0.0"))?;
+        if *self == ScaffoldVersion::SpecDate {
+            version.date = Some(date.to_string());
+        }
+        Ok(version)
+    }
+
+    /// The semver version for `package.json`, generated today; `0.0.0`, with
+    /// a warning logged, when there is none.
+    pub fn semver(&self, spec_version: &str) -> String {
+        match self.resolve(spec_version, &today()) {
+            Ok(version) => version.semver(),
+            Err(warning) => {
+                log::warn!("{warning}");
+                "0.0.0".to_string()
+            }
+        }
+    }
+
+    /// The PEP 440 version for `pyproject.toml`, generated today; `0.0.0`,
+    /// with a warning logged, when there is none.
+    pub fn pep440(&self, spec_version: &str) -> String {
+        let version = self.resolve(spec_version, &today()).and_then(|version| {
+            version.pep440().ok_or_else(|| {
+                format!(
+                    "`{}` has no PEP 440 equivalent; using 0.0.0",
+                    version.semver()
+                )
+            })
+        });
+        version.unwrap_or_else(|warning| {
+            log::warn!("{warning}");
+            "0.0.0".to_string()
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for ScaffoldVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer).map_err(de::Error::custom)?;
+        match value {
+            serde_json::Value::String(s) if s == "spec" => Ok(ScaffoldVersion::Spec),
+            serde_json::Value::String(s) if s == "spec+date" => Ok(ScaffoldVersion::SpecDate),
+            serde_json::Value::String(s) => Ok(ScaffoldVersion::Literal(s)),
+            // An unquoted `version: 2.1` in YAML.
+            serde_json::Value::Number(n) => Ok(ScaffoldVersion::Literal(n.to_string())),
+            _ => Err(de::Error::custom(
+                "expected `spec`, `spec+date` or a version string",
+            )),
+        }
+    }
+}
+
+impl PackageVersion {
+    /// Sanitize `raw` to semver: a leading `v` is dropped, missing minor and
+    /// patch numbers are zero and leading zeros are stripped. `None` when it
+    /// isn't a version at all (`latest`, `1.2.3.4`).
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim();
+        let raw = raw.strip_prefix(['v', 'V']).unwrap_or(raw);
+        if let Some(release) = calendar_date(raw) {
+            return Some(PackageVersion {
+                release,
+                pre: Vec::new(),
+                date: None,
+                build: Vec::new(),
+            });
+        }
+        let (raw, build) = match raw.split_once('+') {
+            Some((raw, build)) => (raw, identifiers(build, false)?),
+            None => (raw, Vec::new()),
+        };
+        let (release, pre) = match raw.split_once('-') {
+            Some((release, pre)) => (release, identifiers(pre, true)?),
+            None => (raw, Vec::new()),
+        };
+        let numbers = release
+            .split('.')
+            .map(number)
+            .collect::<Option<Vec<u64>>>()?;
+        if numbers.len() > 3 {
+            return None;
+        }
+        let mut release = [0; 3];
+        release[..numbers.len()].copy_from_slice(&numbers);
+        Some(PackageVersion {
+            release,
+            pre,
+            date: None,
+            build,
+        })
+    }
+
+    /// `1.0.0-beta.1.20240301+build.5`.
+    pub fn semver(&self) -> String {
+        let [major, minor, patch] = self.release;
+        let mut version = format!("{major}.{minor}.{patch}");
+        let pre: Vec<&str> = self
+            .pre
+            .iter()
+            .map(String::as_str)
+            .chain(self.date.as_deref())
+            .collect();
+        if !pre.is_empty() {
+            version.push('-');
+            version.push_str(&pre.join("."));
+        }
+        if !self.build.is_empty() {
+            version.push('+');
+            version.push_str(&self.build.join("."));
+        }
+        version
+    }
+
+    /// `1.0.0b1.dev20240301+build.5`: an `alpha`, `beta` or `rc` prerelease
+    /// becomes `a`, `b` or `rc`, the date a `.dev` release, which also sorts
+    /// before the release, and build metadata a local version. `None` for
+    /// other prereleases.
+    pub fn pep440(&self) -> Option<String> {
+        let [major, minor, patch] = self.release;
+        let mut version = format!("{major}.{minor}.{patch}{}", pep440_pre(&self.pre)?);
+        if let Some(date) = &self.date {
+            version.push_str(&format!(".dev{date}"));
+        }
+        if !self.build.is_empty() {
+            version.push('+');
+            version.push_str(&self.build.join(".").to_ascii_lowercase().replace('-', "."));
+        }
+        Some(version)
+    }
+}
+
+/// `YYYY-MM-DD` as a `[year, month, day]` release.
+fn calendar_date(raw: &str) -> Option<[u64; 3]> {
+    let mut parts = raw.split('-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    let fits = [(year, 4), (month, 2), (day, 2)]
+        .iter()
+        .all(|(part, len)| part.len() == *len);
+    if parts.next().is_some() || !fits {
+        return None;
+    }
+    Some([number(year)?, number(month)?, number(day)?])
+}
+
+fn number(part: &str) -> Option<u64> {
+    if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    part.parse().ok()
+}
+
+/// Dot-separated semver identifiers; prerelease ones lose leading zeros.
+fn identifiers(raw: &str, prerelease: bool) -> Option<Vec<String>> {
+    raw.split('.')
+        .map(|id| {
+            if id.is_empty() || !id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-') {
+                return None;
+            }
+            match number(id) {
+                Some(n) if prerelease => Some(n.to_string()),
+                _ => Some(id.to_string()),
+            }
+        })
+        .collect()
+}
+
+/// `["beta", "1"]` or `["beta1"]` → `b1`; empty for no prerelease.
+fn pep440_pre(pre: &[String]) -> Option<String> {
+    let (label, number) = match pre {
+        [] => return Some(String::new()),
+        [label] => (label.as_str(), None),
+        [label, n] => (label.as_str(), Some(self::number(n)?)),
+        _ => return None,
+    };
+    let (name, digits) = label.split_at(
+        label
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(label.len()),
+    );
+    let number = match (digits, number) {
+        ("", number) => number.unwrap_or(0),
+        (digits, None) => self::number(digits)?,
+        _ => return None,
+    };
+    let name = match name.to_ascii_lowercase().as_str() {
+        "a" | "alpha" => "a",
+        "b" | "beta" => "b",
+        "c" | "rc" | "pre" | "preview" => "rc",
+        _ => return None,
+    };
+    Some(format!("{name}{number}"))
+}
+
+/// Today's UTC date as `YYYYMMDD`, or that of `SOURCE_DATE_EPOCH` for
+/// reproducible builds.
+fn today() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        });
+    let (year, month, day) = civil_date(seconds / 86_400);
+    format!("{year:04}{month:02}{day:02}")
+}
+
+/// The proleptic Gregorian date `days` after 1970-01-01 (Howard Hinnant's
+/// `civil_from_days`).
+fn civil_date(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+/// The base URL baked into generated clients.
+///
+/// In YAML: `"https://api.example.com"` → `Url(..)`, `false` → `Disabled` (callers
+/// must always pass one). Absent → the spec's first server URL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BaseUrl {
+    Url(String),
+    Disabled,
+}
+
+impl BaseUrl {
+    /// Resolve with the spec's default: `None` → `default`, `Url(s)` → `Some(s)`, `Disabled` → `None`.
+    pub fn resolve<'a>(setting: Option<&'a Self>, default: Option<&'a str>) -> Option<&'a str> {
+        match setting {
+            None => default,
+            Some(BaseUrl::Url(s)) => Some(s.as_str()),
+            Some(BaseUrl::Disabled) => None,
+        }
+    }
+}
+
+impl Serialize for BaseUrl {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            BaseUrl::Url(url) => serializer.serialize_str(url),
+            BaseUrl::Disabled => serializer.serialize_bool(false),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for BaseUrl {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer).map_err(de::Error::custom)?;
+        match value {
+            serde_json::Value::String(s) => Ok(BaseUrl::Url(s)),
+            serde_json::Value::Bool(false) => Ok(BaseUrl::Disabled),
+            _ => Err(de::Error::custom(
+                "expected a URL string or `false` to require one at runtime",
+            )),
+        }
+    }
+}
+
 /// Top-level project configuration loaded from `.urmzd.oag.yaml`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct OagConfig {
     pub input: String,
     pub naming: NamingConfig,
+    pub docs: DocsConfig,
+    pub examples: ExamplesConfig,
+    pub extensions: ExtensionsConfig,
+    /// Prepend a section to `CHANGELOG.generated.md` describing how the
+    /// generated API surface changed since the last `oag generate`.
+    pub changelog: bool,
+    /// Optional IR passes, run in this order after the standard transform.
+    pub passes: Vec<IrPass>,
+    /// How paths that differ only by a trailing slash are treated.
+    pub trailing_slash: TrailingSlash,
+    /// What happens when a path template has a placeholder no path parameter
+    /// declares.
+    pub missing_path_params: MissingPathParams,
+    /// Let generators whose outputs overlap write different content to the
+    /// same file, the later one winning, instead of failing the run.
+    pub allow_overlap: bool,
+    /// How `deepObject` query parameters without `x-oag-query-object` are
+    /// flattened. Shared by every generator so clients and servers agree.
+    pub query_object: QueryObjectNotation,
+    /// Custom modules, each listing the operation names or path globs it
+    /// takes. Matched operations leave their tag modules for the first
+    /// module listing them; split layouts, routers and docs follow.
+    pub modules: IndexMap<String, Vec<String>>,
+    /// Per-operation settings, by operation name.
+    pub operations: IndexMap<String, OperationConfig>,
     pub generators: IndexMap<GeneratorId, GeneratorConfig>,
 }
 
@@ -62,17 +399,91 @@ impl Default for OagConfig {
         Self {
             input: "openapi.yaml".to_string(),
             naming: NamingConfig::default(),
+            docs: DocsConfig::default(),
+            examples: ExamplesConfig::default(),
+            extensions: ExtensionsConfig::default(),
+            changelog: false,
+            passes: Vec::new(),
+            trailing_slash: TrailingSlash::default(),
+            missing_path_params: MissingPathParams::default(),
+            allow_overlap: false,
+            query_object: QueryObjectNotation::default(),
+            modules: IndexMap::new(),
+            operations: IndexMap::new(),
             generators: IndexMap::new(),
         }
     }
 }
 
-/// A generator plugin identifier.
+/// Settings for one operation, under `operations.<name>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OperationConfig {
+    /// Whether the operation's success response is an event stream.
+    pub streaming: Streaming,
+}
+
+/// Whether an operation returns an event stream.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Streaming {
+    /// A stream when its success response has `text/event-stream` content.
+    #[default]
+    Auto,
+    /// A stream of the chosen response content's schema (JSON, else the first
+    /// media type), for long-polling endpoints.
+    Force,
+    /// Never a stream: JSON, else the first media type, wins over
+    /// `text/event-stream`.
+    Off,
+}
+
+impl Streaming {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Streaming::Auto => "auto",
+            Streaming::Force => "force",
+            Streaming::Off => "off",
+        }
+    }
+}
+
+/// How spec descriptions are carried into generated doc comments.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct DocsConfig {
+    /// Truncate descriptions longer than this many characters with `…`.
+    /// Operations with `externalDocs` link to them from the doc comment.
+    pub max_length: Option<usize>,
+}
+
+/// Which of a request or response body's examples generated tests use.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExamplesConfig {
+    /// Names of media type `examples` to use, most preferred first. Bodies
+    /// without any of them fall back to their single `example`, then to mocks
+    /// derived from the type.
+    pub prefer: Vec<String>,
+}
+
+/// Which vendor extensions are carried into the generated code.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ExtensionsConfig {
+    /// Operation extensions (e.g. `x-ratelimit-rpm`) copied, as JSON, into
+    /// `operationMeta` and each method's doc comment.
+    pub expose: Vec<String>,
+}
+
+/// A generator plugin identifier. New generators add variants.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
 pub enum GeneratorId {
     NodeClient,
     ReactSwrClient,
     FastapiServer,
+    JsonSchema,
 }
 
 impl GeneratorId {
@@ -81,6 +492,7 @@ impl GeneratorId {
             GeneratorId::NodeClient => "node-client",
             GeneratorId::ReactSwrClient => "react-swr-client",
             GeneratorId::FastapiServer => "fastapi-server",
+            GeneratorId::JsonSchema => "json-schema",
         }
     }
 }
@@ -91,6 +503,12 @@ impl fmt::Display for GeneratorId {
     }
 }
 
+impl Serialize for GeneratorId {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
 impl<'de> Deserialize<'de> for GeneratorId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -101,28 +519,130 @@ impl<'de> Deserialize<'de> for GeneratorId {
             "node-client" => Ok(GeneratorId::NodeClient),
             "react-swr-client" => Ok(GeneratorId::ReactSwrClient),
             "fastapi-server" => Ok(GeneratorId::FastapiServer),
+            "json-schema" => Ok(GeneratorId::JsonSchema),
             other => Err(de::Error::unknown_variant(
                 other,
-                &["node-client", "react-swr-client", "fastapi-server"],
+                &[
+                    "node-client",
+                    "react-swr-client",
+                    "fastapi-server",
+                    "json-schema",
+                ],
             )),
         }
     }
 }
 
-/// Configuration for a single generator.
-#[derive(Debug, Clone, Deserialize)]
+/// Configuration for a single generator: one entry under `generators` in the
+/// config file.
+///
+/// Embedders build one with [`GeneratorConfig::new`] and the `with_*`
+/// setters, which keep compiling as options are added:
+///
+/// ```
+/// use oag_core::config::{GeneratorConfig, OutputLayout};
+///
+/// let config = GeneratorConfig::new("out/client")
+///     .with_layout(OutputLayout::Bundled)
+///     .with_source_dir("")
+///     .with_pinned_parameter("X-Api-Version", "2024-01-01");
+/// assert_eq!(config.output, "out/client");
+/// assert_eq!(config.pinned_parameters["X-Api-Version"], "2024-01-01");
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct GeneratorConfig {
     pub output: String,
     pub layout: OutputLayout,
     pub split_by: Option<SplitBy>,
-    pub base_url: Option<String>,
+    /// In split layout, also split `types.ts` into `types/{group}.ts` files plus
+    /// `types/shared.ts` for schemas several groups use.
+    pub split_types: bool,
+    /// Default base URL for TypeScript clients; falls back to the spec's first server.
+    pub base_url: Option<BaseUrl>,
+    /// Base URL of each deployment of the API, by environment name, emitted
+    /// as `environments.ts` (TypeScript generators only). Empty → the spec's
+    /// servers, when it lists more than one.
+    pub environments: IndexMap<String, String>,
     pub no_jsdoc: Option<bool>,
+    /// Default SSE transport baked into the generated `sse.ts` (TypeScript generators only).
+    pub sse_transport: Option<SseTransport>,
+    /// Suffixes appended to generated React hook names (react-swr-client only).
+    pub hook_naming: HookNaming,
+    /// What SWR keys start with (react-swr-client only).
+    pub key_style: KeyStyle,
+    /// Which tagged SSE events `streaming.ts` folds into text (node-client only).
+    pub stream_accumulator: Option<StreamAccumulator>,
+    /// How the generated client handles bearer tokens (TypeScript generators only).
+    pub auth: AuthConfig,
+    /// Emit `routes.ts`, a typed table of every operation's method, path and
+    /// body/response types (node-client only).
+    pub route_table: bool,
+    /// Emit `streaming-helpers.ts`, with a `{op}ToWritable` helper per SSE
+    /// operation piping its events into a `WritableStream` (node-client only).
+    pub streaming_helpers: bool,
+    /// Emit `batch.ts`, with a `{op}Batched` helper per operation whose JSON
+    /// body is an array, sending the items in chunks (TypeScript generators
+    /// only; react-swr-client adds a `use{Op}Batched` hook).
+    pub batch_helpers: bool,
+    /// Emit `logging.ts`, a request/response logger with credential and
+    /// `writeOnly` body field redaction, enabled with `new ApiClient({ debug: true })`
+    /// (node-client only).
+    pub debug_logging: bool,
+    /// Emit a `fetchWithRetry` helper in the client, behind `ClientConfig.retry`
+    /// and each call's `retry` option, retrying failed requests with backoff
+    /// and honoring `Retry-After` on `429` and `503` (TypeScript generators only).
+    pub retry: bool,
+    /// Trim the client for small bundles: no JSDoc, no SSE runtime (or SSE
+    /// options) without SSE operations, no query or multipart handling
+    /// without operations that need it, and a barrel without the client's
+    /// helper functions (node-client only).
+    pub minimal_runtime: bool,
+    /// Emit Next.js route handlers under [`next_handlers_dir`](Self::next_handlers_dir)
+    /// that validate requests against the spec and forward them upstream
+    /// through the client (TypeScript generators only).
+    pub next_handlers: bool,
+    /// Directory, relative to the output root, for the `next_handlers` files.
+    /// Default `"next"`.
+    pub next_handlers_dir: String,
+    /// Emit `oag.operations.json` in the output root, a machine-readable index
+    /// of the generated operations and schemas; see [`crate::index`].
+    pub index_json: bool,
+    /// Prepended to every generated schema type name (and SSE event union),
+    /// e.g. `Api` for `ApiPet`, so the types don't clash with a codebase's
+    /// own. Wire names stay as they are.
+    pub type_prefix: Option<String>,
+    /// Whether schema examples are emitted as typed constants in
+    /// `examples.ts` (node-client only).
+    pub examples: ExamplesMode,
+    /// Emit a `contract/` suite that runs the node client against the FastAPI
+    /// stubs over HTTP. Set on either generator, it applies to both.
+    pub contract_tests: bool,
+    /// Emit `test-coverage.json` next to the generated tests, listing the
+    /// test cases that call each operation; see [`crate::test_report`].
+    pub test_report: bool,
+    /// Required header/query parameters (wire name → value) the generated code
+    /// always sends, dropped from method signatures, e.g. an API version header.
+    pub pinned_parameters: IndexMap<String, String>,
+    /// How client methods and hooks take query and header parameters
+    /// (TypeScript generators only).
+    pub params_style: ParamsStyle,
+    /// Whether TypeScript interfaces keep the spec's property names or use
+    /// camelCase ones mapped to and from the wire (TypeScript generators only).
+    pub property_naming: PropertyNaming,
     /// Subdirectory for generated source files. Default `"src"`.
     /// Empty string `""` places files at the output root.
     pub source_dir: String,
     /// Opaque scaffold config — each generator defines and parses its own struct.
     pub scaffold: Option<serde_json::Value>,
+    /// Filters applied to the emitted files before they are written.
+    pub files: FilesConfig,
+    /// Naming overrides for this generator only, layered over the global `naming`.
+    pub naming: Option<GeneratorNamingConfig>,
+    /// Emit only the schema types, no client, hooks or routes. Set by
+    /// `oag generate --types-only`, and for specs without operations.
+    #[serde(skip_deserializing)]
+    pub types_only: bool,
 }
 
 impl Default for GeneratorConfig {
@@ -131,17 +651,339 @@ impl Default for GeneratorConfig {
             output: "src/generated".to_string(),
             layout: OutputLayout::Modular,
             split_by: None,
+            split_types: false,
             base_url: None,
+            environments: IndexMap::new(),
             no_jsdoc: None,
+            sse_transport: None,
+            hook_naming: HookNaming::default(),
+            key_style: KeyStyle::default(),
+            stream_accumulator: None,
+            auth: AuthConfig::default(),
+            route_table: false,
+            streaming_helpers: false,
+            batch_helpers: false,
+            debug_logging: false,
+            retry: false,
+            minimal_runtime: false,
+            next_handlers: false,
+            next_handlers_dir: "next".to_string(),
+            index_json: false,
+            type_prefix: None,
+            examples: ExamplesMode::default(),
+            contract_tests: false,
+            test_report: false,
+            pinned_parameters: IndexMap::new(),
+            params_style: ParamsStyle::default(),
+            property_naming: PropertyNaming::default(),
             source_dir: "src".to_string(),
             scaffold: None,
+            files: FilesConfig::default(),
+            naming: None,
+            types_only: false,
+        }
+    }
+}
+
+impl GeneratorConfig {
+    /// The default config, writing to `output`.
+    pub fn new(output: impl Into<String>) -> Self {
+        Self {
+            output: output.into(),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_layout(mut self, layout: OutputLayout) -> Self {
+        self.layout = layout;
+        self
+    }
+
+    pub fn with_split_by(mut self, split_by: SplitBy) -> Self {
+        self.split_by = Some(split_by);
+        self
+    }
+
+    pub fn with_split_types(mut self, split_types: bool) -> Self {
+        self.split_types = split_types;
+        self
+    }
+
+    pub fn with_base_url(mut self, base_url: BaseUrl) -> Self {
+        self.base_url = Some(base_url);
+        self
+    }
+
+    /// Add an environment's base URL; see [`GeneratorConfig::environments`].
+    pub fn with_environment(mut self, name: impl Into<String>, url: impl Into<String>) -> Self {
+        self.environments.insert(name.into(), url.into());
+        self
+    }
+
+    pub fn with_no_jsdoc(mut self, no_jsdoc: bool) -> Self {
+        self.no_jsdoc = Some(no_jsdoc);
+        self
+    }
+
+    pub fn with_sse_transport(mut self, transport: SseTransport) -> Self {
+        self.sse_transport = Some(transport);
+        self
+    }
+
+    pub fn with_hook_naming(mut self, hook_naming: HookNaming) -> Self {
+        self.hook_naming = hook_naming;
+        self
+    }
+
+    pub fn with_key_style(mut self, key_style: KeyStyle) -> Self {
+        self.key_style = key_style;
+        self
+    }
+
+    pub fn with_stream_accumulator(mut self, accumulator: StreamAccumulator) -> Self {
+        self.stream_accumulator = Some(accumulator);
+        self
+    }
+
+    pub fn with_auth(mut self, auth: AuthConfig) -> Self {
+        self.auth = auth;
+        self
+    }
+
+    pub fn with_route_table(mut self, route_table: bool) -> Self {
+        self.route_table = route_table;
+        self
+    }
+
+    pub fn with_streaming_helpers(mut self, streaming_helpers: bool) -> Self {
+        self.streaming_helpers = streaming_helpers;
+        self
+    }
+
+    pub fn with_batch_helpers(mut self, batch_helpers: bool) -> Self {
+        self.batch_helpers = batch_helpers;
+        self
+    }
+
+    pub fn with_debug_logging(mut self, debug_logging: bool) -> Self {
+        self.debug_logging = debug_logging;
+        self
+    }
+
+    pub fn with_retry(mut self, retry: bool) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    pub fn with_minimal_runtime(mut self, minimal_runtime: bool) -> Self {
+        self.minimal_runtime = minimal_runtime;
+        self
+    }
+
+    pub fn with_next_handlers(mut self, next_handlers: bool) -> Self {
+        self.next_handlers = next_handlers;
+        self
+    }
+
+    pub fn with_next_handlers_dir(mut self, next_handlers_dir: impl Into<String>) -> Self {
+        self.next_handlers_dir = next_handlers_dir.into();
+        self
+    }
+
+    pub fn with_index_json(mut self, index_json: bool) -> Self {
+        self.index_json = index_json;
+        self
+    }
+
+    pub fn with_type_prefix(mut self, type_prefix: impl Into<String>) -> Self {
+        self.type_prefix = Some(type_prefix.into());
+        self
+    }
+
+    pub fn with_examples(mut self, examples: ExamplesMode) -> Self {
+        self.examples = examples;
+        self
+    }
+
+    pub fn with_contract_tests(mut self, contract_tests: bool) -> Self {
+        self.contract_tests = contract_tests;
+        self
+    }
+
+    pub fn with_test_report(mut self, test_report: bool) -> Self {
+        self.test_report = test_report;
+        self
+    }
+
+    /// Pin a required header or query parameter (by wire name) to `value`.
+    pub fn with_pinned_parameter(
+        mut self,
+        name: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Self {
+        self.pinned_parameters.insert(name.into(), value.into());
+        self
+    }
+
+    pub fn with_params_style(mut self, params_style: ParamsStyle) -> Self {
+        self.params_style = params_style;
+        self
+    }
+
+    pub fn with_property_naming(mut self, property_naming: PropertyNaming) -> Self {
+        self.property_naming = property_naming;
+        self
+    }
+
+    pub fn with_source_dir(mut self, source_dir: impl Into<String>) -> Self {
+        self.source_dir = source_dir.into();
+        self
+    }
+
+    /// The generator's `scaffold` section, as it would appear in the config
+    /// file; each generator parses its own keys.
+    pub fn with_scaffold(mut self, scaffold: serde_json::Value) -> Self {
+        self.scaffold = Some(scaffold);
+        self
+    }
+
+    pub fn with_files(mut self, files: FilesConfig) -> Self {
+        self.files = files;
+        self
+    }
+
+    pub fn with_naming(mut self, naming: GeneratorNamingConfig) -> Self {
+        self.naming = Some(naming);
+        self
+    }
+
+    pub fn with_types_only(mut self, types_only: bool) -> Self {
+        self.types_only = types_only;
+        self
+    }
+
+    /// The options this config sets to something other than their default, by
+    /// config key. `output`, `files`, `naming`, `index_json` and
+    /// `type_prefix` are left out: every generator honors them.
+    pub fn set_options(&self) -> Vec<&'static str> {
+        let default = Self::default();
+        [
+            ("layout", self.layout != default.layout),
+            ("split_by", self.split_by.is_some()),
+            ("split_types", self.split_types),
+            ("base_url", self.base_url.is_some()),
+            ("environments", !self.environments.is_empty()),
+            ("no_jsdoc", self.no_jsdoc == Some(true)),
+            ("sse_transport", self.sse_transport.is_some()),
+            ("hook_naming", self.hook_naming != default.hook_naming),
+            ("key_style", self.key_style != default.key_style),
+            ("stream_accumulator", self.stream_accumulator.is_some()),
+            ("auth", self.auth != default.auth),
+            ("route_table", self.route_table),
+            ("streaming_helpers", self.streaming_helpers),
+            ("batch_helpers", self.batch_helpers),
+            ("debug_logging", self.debug_logging),
+            ("retry", self.retry),
+            ("minimal_runtime", self.minimal_runtime),
+            ("next_handlers", self.next_handlers),
+            (
+                "next_handlers_dir",
+                self.next_handlers_dir != default.next_handlers_dir,
+            ),
+            ("examples", self.examples != default.examples),
+            ("contract_tests", self.contract_tests),
+            ("test_report", self.test_report),
+            ("pinned_parameters", !self.pinned_parameters.is_empty()),
+            ("params_style", self.params_style != default.params_style),
+            (
+                "property_naming",
+                self.property_naming != default.property_naming,
+            ),
+            ("source_dir", self.source_dir != default.source_dir),
+            ("scaffold", self.scaffold.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(key, set)| set.then_some(key))
+        .collect()
+    }
+
+    /// A warning for each set option (see [`set_options`](Self::set_options))
+    /// not in `supported`, the keys generator `id` reads.
+    pub fn unsupported_options(&self, id: GeneratorId, supported: &[&str]) -> Vec<ConfigWarning> {
+        self.set_options()
+            .into_iter()
+            .filter(|key| !supported.contains(key))
+            .map(|key| ConfigWarning::new(key, format!("not supported by {id}; ignored")))
+            .collect()
+    }
+
+    /// Warnings for `split_by` and `split_types` outside `layout: split`.
+    pub fn split_option_warnings(&self) -> Vec<ConfigWarning> {
+        if self.layout == OutputLayout::Split {
+            return Vec::new();
+        }
+        [
+            ("split_by", self.split_by.is_some()),
+            ("split_types", self.split_types),
+        ]
+        .into_iter()
+        .filter(|(_, set)| *set)
+        .map(|(key, _)| ConfigWarning::new(key, "only applies to `layout: split`; ignored"))
+        .collect()
+    }
+
+    /// Parse `scaffold` as the generator's scaffold struct `T`, which should
+    /// `deny_unknown_fields` so misspelled keys are caught. `false` and `null`
+    /// mean no scaffold and parse to `None`.
+    pub fn parse_scaffold<T: DeserializeOwned>(&self) -> Result<Option<T>, ConfigWarning> {
+        match &self.scaffold {
+            None | Some(serde_json::Value::Null | serde_json::Value::Bool(false)) => Ok(None),
+            Some(raw) => serde_json::from_value(raw.clone())
+                .map(Some)
+                .map_err(|e| ConfigWarning::new("scaffold", e.to_string())),
+        }
+    }
+}
+
+/// A generator option that will be ignored or can't be parsed, found by
+/// [`CodeGenerator::validate_config`](crate::CodeGenerator::validate_config)
+/// before anything is generated.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigWarning {
+    /// The option's config key, e.g. `split_by`.
+    pub option: String,
+    pub message: String,
+}
+
+impl ConfigWarning {
+    pub fn new(option: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            option: option.into(),
+            message: message.into(),
         }
     }
 }
 
-/// How generated files are laid out on disk.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+impl fmt::Display for ConfigWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.option, self.message)
+    }
+}
+
+/// Which emitted files to drop, e.g. `exclude: ["src/sse.ts"]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FilesConfig {
+    /// Glob patterns matched against emitted paths (relative to `output`).
+    pub exclude: Vec<String>,
+}
+
+/// How generated files are laid out on disk. Generators should reject layouts
+/// they don't know with a [`GeneratorError`](crate::GeneratorError) rather than
+/// guess.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
+#[non_exhaustive]
 pub enum OutputLayout {
     /// All files concatenated into one output file + scaffold.
     Bundled,
@@ -152,7 +994,7 @@ pub enum OutputLayout {
 }
 
 /// How to split operations into groups.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SplitBy {
     /// Group by operation (one file per operation).
@@ -163,14 +1005,134 @@ pub enum SplitBy {
     Route,
 }
 
+/// The delta pattern of a tagged SSE event union (Anthropic-style streams):
+/// one event carries a text delta, another ends the stream. Events are matched
+/// by the literal tag that discriminates the union's variants.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct StreamAccumulator {
+    /// Tag of the event carrying a delta, e.g. `content_block_delta`.
+    pub delta_event: String,
+    /// Dot-separated path to the string each delta event adds, e.g. `delta.text`.
+    pub delta_field: String,
+    /// Tag of the event ending the stream, e.g. `message_stop`.
+    pub done_event: String,
+}
+
+/// Bearer token handling in the generated client's `auth.ts`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AuthConfig {
+    /// Reuse a JWT from a `bearerToken` provider until this many seconds
+    /// before its `exp` claim, then ask the provider for a new one. Applies to
+    /// schemes with `bearerFormat: JWT`; unset, the provider is called for
+    /// every request.
+    pub refresh_before_expiry_s: Option<u64>,
+}
+
+/// Which transport the generated SSE runtime uses to open streams.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SseTransport {
+    /// `fetch` + `ReadableStream` — supports every HTTP method.
+    #[default]
+    Fetch,
+    /// WHATWG `EventSource` — GET only; other methods fall back to fetch.
+    EventSource,
+    /// Pick at runtime: fetch when streaming bodies are available, else `EventSource`.
+    Auto,
+}
+
+impl SseTransport {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SseTransport::Fetch => "fetch",
+            SseTransport::EventSource => "eventsource",
+            SseTransport::Auto => "auto",
+        }
+    }
+}
+
+/// How TypeScript client methods take an operation's query and header parameters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParamsStyle {
+    /// One argument per parameter: `listPets(limit?, status?)`.
+    #[default]
+    Positional,
+    /// A single `{Op}Params` object exported from `types.ts`: `listPets({ limit, status })`.
+    Object,
+}
+
+/// How TypeScript interfaces name schema properties.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PropertyNaming {
+    /// The spec's (wire) names, e.g. `created_at`, so values are typed exactly
+    /// as they're sent and received.
+    #[default]
+    Original,
+    /// camelCase names (`createdAt`). `codecs.ts` gets `serialize{Schema}` and
+    /// `deserialize{Schema}` functions mapping to and from the wire names, which
+    /// the client applies to request bodies, responses and stream events.
+    Camel,
+}
+
+/// How generated SWR hooks key their cache entries.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyStyle {
+    /// The operation name and a named params object: `["listPets", { limit, status }]`.
+    #[default]
+    Operation,
+    /// The path template and positional values: `["/pets", limit, status]`.
+    Path,
+}
+
+/// Whether schema examples become typed constants in `examples.ts`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExamplesMode {
+    /// `export const petExample = {...} satisfies Pet;`, so an example that
+    /// doesn't match its type fails to compile.
+    #[default]
+    Emit,
+    /// No `examples.ts`.
+    Skip,
+    /// The same constants cast with `as unknown as Pet`, for specs with
+    /// examples known not to match.
+    #[serde(alias = "emit-unchecked")]
+    EmitUnchecked,
+}
+
+/// Suffixes appended to React hook names, e.g. `useListPets` → `useListPetsQuery`.
+/// All empty by default, which keeps the plain `use{Operation}` names.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct HookNaming {
+    pub query_suffix: String,
+    pub mutation_suffix: String,
+    pub sse_suffix: String,
+}
+
 /// Naming strategy and aliases.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(default)]
 pub struct NamingConfig {
     pub strategy: NamingStrategy,
     /// Map from resolved operation name (operationId or route-derived) to custom alias.
+    /// Keys may contain `*` globs; see [`resolve_alias`](crate::transform::name_normalizer::resolve_alias).
     #[serde(default)]
     pub aliases: IndexMap<String, String>,
+    /// Map from component schema name to the name generated code uses for it,
+    /// e.g. `InlineResponse2001: PetListResponse`. Every reference follows.
+    pub schema_aliases: IndexMap<String, String>,
+    /// What to do when two component schema names normalize to the same type name.
+    pub on_collision: CollisionStrategy,
+    /// How route-derived operation names that clash are told apart.
+    pub fallback: FallbackNaming,
+    /// Module that operations without tags are grouped into. A real tag with
+    /// the same name pushes this one to `{name}_2`.
+    pub untagged_module: String,
 }
 
 impl Default for NamingConfig {
@@ -178,12 +1140,37 @@ impl Default for NamingConfig {
         Self {
             strategy: NamingStrategy::UseOperationId,
             aliases: IndexMap::new(),
+            schema_aliases: IndexMap::new(),
+            on_collision: CollisionStrategy::Error,
+            fallback: FallbackNaming::Route,
+            untagged_module: "default".to_string(),
         }
     }
 }
 
+impl NamingConfig {
+    /// This config with a generator's overrides applied: its `strategy` replaces
+    /// the global one and its `aliases` are added to (and win over) the global ones.
+    pub fn with_overrides(&self, overrides: &GeneratorNamingConfig) -> NamingConfig {
+        let mut naming = self.clone();
+        if let Some(strategy) = overrides.strategy {
+            naming.strategy = strategy;
+        }
+        naming.aliases.extend(overrides.aliases.clone());
+        naming
+    }
+}
+
+/// Per-generator naming overrides, e.g. aliasing an operation only in the TS client.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct GeneratorNamingConfig {
+    pub strategy: Option<NamingStrategy>,
+    pub aliases: IndexMap<String, String>,
+}
+
 /// How operation names are derived.
-#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum NamingStrategy {
     #[default]
@@ -191,6 +1178,83 @@ pub enum NamingStrategy {
     UseRouteBased,
 }
 
+/// How colliding schema names are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CollisionStrategy {
+    /// Fail the transform.
+    #[default]
+    Error,
+    /// Keep the first schema's name and suffix later ones (`UserProfile2`), with a warning.
+    Suffix,
+}
+
+/// How operations named from their route (no `operationId`, or
+/// `use_route_based`) are told apart when two derive the same name, e.g.
+/// `GET /pets/{id}` and `GET /pets/{id}/{version}` both giving `getPet`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FallbackNaming {
+    /// Keep the first operation's name and number later ones in spec order
+    /// (`getPet2`).
+    #[default]
+    Route,
+    /// Append a short hash of the method and path to every clashing name
+    /// (`getPet_1a2b3c`), so reordering or adding unrelated paths never
+    /// renames an operation.
+    RouteHash,
+}
+
+/// How paths that are the same route apart from a trailing slash or the names
+/// of their path parameters (`/pets` and `/pets/`, `/pets/{id}` and
+/// `/pets/{petId}`) are handled.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrailingSlash {
+    /// Treat them as one route: their operations are all generated, with the
+    /// paths as written, and a method both declare is an error.
+    #[default]
+    Merge,
+    /// Like `merge`, and also drop trailing slashes from generated paths.
+    Strip,
+    /// Generate every path as written, without checking for duplicates.
+    Keep,
+}
+
+/// What happens when a path template has a placeholder (`{petId}` in
+/// `/pets/{petId}`) that none of its operation's path parameters declare.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MissingPathParams {
+    /// Fail the transform.
+    #[default]
+    Error,
+    /// Declare it as a required string path parameter, with a warning.
+    Synthesize,
+}
+
+/// A built-in IR pass that can be enabled with `passes` in the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IrPass {
+    /// Drop schemas that no operation or webhook reaches.
+    PruneUnused,
+    /// Merge structurally identical schemas promoted from inline objects.
+    DedupeInline,
+    /// Turn `allOf` aliases over object schemas into one object schema.
+    FlattenIntersections,
+}
+
+impl IrPass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IrPass::PruneUnused => "prune_unused",
+            IrPass::DedupeInline => "dedupe_inline",
+            IrPass::FlattenIntersections => "flatten_intersections",
+        }
+    }
+}
+
 // --- Backward-compatible deserialization ---
 // Old format had: input, output, target, naming, output_options, client
 // New format has: input, naming, generators (map of GeneratorId -> GeneratorConfig)
@@ -275,6 +1339,28 @@ struct NewConfig {
     input: String,
     #[serde(default)]
     naming: NamingConfig,
+    #[serde(default)]
+    docs: DocsConfig,
+    #[serde(default)]
+    examples: ExamplesConfig,
+    #[serde(default)]
+    extensions: ExtensionsConfig,
+    #[serde(default)]
+    changelog: bool,
+    #[serde(default)]
+    passes: Vec<IrPass>,
+    #[serde(default)]
+    trailing_slash: TrailingSlash,
+    #[serde(default)]
+    missing_path_params: MissingPathParams,
+    #[serde(default)]
+    allow_overlap: bool,
+    #[serde(default)]
+    query_object: QueryObjectNotation,
+    #[serde(default)]
+    modules: IndexMap<String, Vec<String>>,
+    #[serde(default)]
+    operations: IndexMap<String, OperationConfig>,
     generators: IndexMap<GeneratorId, GeneratorConfig>,
 }
 
@@ -292,6 +1378,17 @@ impl<'de> Deserialize<'de> for OagConfig {
             Ok(OagConfig {
                 input: new_cfg.input,
                 naming: new_cfg.naming,
+                docs: new_cfg.docs,
+                examples: new_cfg.examples,
+                extensions: new_cfg.extensions,
+                changelog: new_cfg.changelog,
+                passes: new_cfg.passes,
+                trailing_slash: new_cfg.trailing_slash,
+                missing_path_params: new_cfg.missing_path_params,
+                allow_overlap: new_cfg.allow_overlap,
+                query_object: new_cfg.query_object,
+                modules: new_cfg.modules,
+                operations: new_cfg.operations,
                 generators: new_cfg.generators,
             })
         } else {
@@ -316,10 +1413,36 @@ fn convert_legacy(legacy: LegacyConfig) -> OagConfig {
         output,
         layout: OutputLayout::Modular,
         split_by: None,
-        base_url: legacy.client.base_url.clone(),
+        split_types: false,
+        base_url: legacy.client.base_url.clone().map(BaseUrl::Url),
+        environments: IndexMap::new(),
         no_jsdoc: Some(legacy.client.no_jsdoc),
+        sse_transport: None,
+        hook_naming: HookNaming::default(),
+        key_style: KeyStyle::default(),
+        stream_accumulator: None,
+        auth: AuthConfig::default(),
+        route_table: false,
+        streaming_helpers: false,
+        batch_helpers: false,
+        debug_logging: false,
+        retry: false,
+        minimal_runtime: false,
+        next_handlers: false,
+        next_handlers_dir: "next".to_string(),
+        index_json: false,
+        type_prefix: None,
+        examples: ExamplesMode::default(),
+        contract_tests: false,
+        test_report: false,
+        pinned_parameters: IndexMap::new(),
+        params_style: ParamsStyle::default(),
+        property_naming: PropertyNaming::default(),
         source_dir: "src".to_string(),
         scaffold: scaffold.clone(),
+        files: FilesConfig::default(),
+        naming: None,
+        types_only: false,
     };
 
     let mut generators = IndexMap::new();
@@ -357,6 +1480,17 @@ fn convert_legacy(legacy: LegacyConfig) -> OagConfig {
     OagConfig {
         input: legacy.input,
         naming: legacy.naming,
+        docs: DocsConfig::default(),
+        examples: ExamplesConfig::default(),
+        extensions: ExtensionsConfig::default(),
+        changelog: false,
+        passes: Vec::new(),
+        trailing_slash: TrailingSlash::default(),
+        missing_path_params: MissingPathParams::default(),
+        allow_overlap: false,
+        query_object: QueryObjectNotation::default(),
+        modules: IndexMap::new(),
+        operations: IndexMap::new(),
         generators,
     }
 }
@@ -366,18 +1500,106 @@ pub const CONFIG_FILE_NAME: &str = ".urmzd.oag.yaml";
 
 /// Load config from a YAML file. Returns `None` if the file doesn't exist.
 pub fn load_config(path: &Path) -> Result<Option<OagConfig>, String> {
+    load_config_profile(path, None)
+}
+
+/// Like [`load_config`], with `profile` applied; see [`parse_config_profile`].
+/// A profile but no file is an error.
+pub fn load_config_profile(
+    path: &Path,
+    profile: Option<&str>,
+) -> Result<Option<OagConfig>, String> {
     if !path.exists() {
-        return Ok(None);
+        return match profile {
+            Some(profile) => Err(format!(
+                "profile `{profile}` requested but {} doesn't exist",
+                path.display()
+            )),
+            None => Ok(None),
+        };
     }
     let content = fs::read_to_string(path)
         .map_err(|e| format!("failed to read config {}: {}", path.display(), e))?;
 
-    // Parse YAML to serde_json::Value first, then use our custom Deserialize impl
-    let yaml_value: serde_json::Value = serde_yaml_ng::from_str(&content)
-        .map_err(|e| format!("failed to parse config {}: {}", path.display(), e))?;
-    let config: OagConfig = serde_json::from_value(yaml_value)
-        .map_err(|e| format!("failed to parse config {}: {}", path.display(), e))?;
-    Ok(Some(config))
+    parse_config_profile(&content, profile)
+        .map(Some)
+        .map_err(|e| format!("failed to parse config {}: {}", path.display(), e))
+}
+
+/// Parse config YAML, expanding merge keys (`<<: *anchor`) the same way specs
+/// do; see [`crate::parse::prepare_yaml_value`]. `profiles` is ignored.
+pub fn parse_config(content: &str) -> Result<OagConfig, String> {
+    parse_config_profile(content, None)
+}
+
+/// Parse config YAML with a profile from its top-level `profiles` map merged
+/// over the root settings; `None` is the root itself.
+///
+/// Merging follows JSON Merge Patch (RFC 7396): mappings merge key by key, so a
+/// profile that sets `generators.node-client.output` keeps the rest of that
+/// generator's settings; `null` removes a key (`react-swr-client: null` drops
+/// the generator); anything else replaces the root's value.
+pub fn parse_config_profile(content: &str, profile: Option<&str>) -> Result<OagConfig, String> {
+    let mut value: serde_yaml_ng::Value =
+        serde_yaml_ng::from_str(content).map_err(|e| e.to_string())?;
+    crate::parse::prepare_yaml_value(&mut value).map_err(|e| e.to_string())?;
+    apply_profile(&mut value, profile)?;
+    serde_yaml_ng::from_value(value).map_err(|e| e.to_string())
+}
+
+/// Take `profiles` out of the root config and merge the chosen one back in.
+fn apply_profile(config: &mut serde_yaml_ng::Value, profile: Option<&str>) -> Result<(), String> {
+    let profiles = match config.as_mapping_mut() {
+        Some(root) => root.remove("profiles"),
+        None => None,
+    };
+    let Some(profile) = profile else {
+        return Ok(());
+    };
+
+    let profiles = match profiles {
+        None => serde_yaml_ng::Mapping::new(),
+        Some(serde_yaml_ng::Value::Mapping(profiles)) => profiles,
+        Some(_) => return Err("`profiles` must map profile names to settings".to_string()),
+    };
+    match profiles.get(profile) {
+        Some(patch @ serde_yaml_ng::Value::Mapping(_)) => {
+            merge_patch(config, patch.clone());
+            Ok(())
+        }
+        Some(_) => Err(format!("profile `{profile}` must be a mapping of settings")),
+        None if profiles.is_empty() => Err(format!(
+            "unknown profile `{profile}`; the config defines no `profiles`"
+        )),
+        None => Err(format!(
+            "unknown profile `{profile}`; expected one of: {}",
+            profiles
+                .keys()
+                .filter_map(|name| name.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )),
+    }
+}
+
+/// Merge `patch` into `target` as JSON Merge Patch does.
+fn merge_patch(target: &mut serde_yaml_ng::Value, patch: serde_yaml_ng::Value) {
+    use serde_yaml_ng::Value;
+
+    match (target, patch) {
+        (Value::Mapping(target), Value::Mapping(patch)) => {
+            for (key, value) in patch {
+                if value.is_null() {
+                    target.remove(&key);
+                } else if let Some(existing) = target.get_mut(&key) {
+                    merge_patch(existing, value);
+                } else {
+                    target.insert(key, value);
+                }
+            }
+        }
+        (target, patch) => *target = patch,
+    }
 }
 
 /// Generate the default config file content (new format).
@@ -395,6 +1617,18 @@ mod tests {
         assert_eq!(config.input, "openapi.yaml");
         assert_eq!(config.naming.strategy, NamingStrategy::UseOperationId);
         assert!(config.naming.aliases.is_empty());
+        assert_eq!(config.naming.on_collision, CollisionStrategy::Error);
+        assert_eq!(config.naming.fallback, FallbackNaming::Route);
+        assert_eq!(config.docs.max_length, None);
+        assert!(config.examples.prefer.is_empty());
+        assert!(config.extensions.expose.is_empty());
+        assert!(!config.changelog);
+        assert!(config.passes.is_empty());
+        assert_eq!(config.trailing_slash, TrailingSlash::Merge);
+        assert!(!config.allow_overlap);
+        assert_eq!(config.query_object, QueryObjectNotation::Bracket);
+        assert!(config.modules.is_empty());
+        assert!(config.operations.is_empty());
         assert!(config.generators.is_empty());
     }
 
@@ -407,41 +1641,298 @@ naming:
   strategy: use_route_based
   aliases:
     createChatCompletion: chat
+  on_collision: suffix
+  fallback: route_hash
+  untagged_module: misc
+
+docs:
+  max_length: 500
+
+examples:
+  prefer: [minimal, default]
+
+extensions:
+  expose: [x-ratelimit-rpm]
+
+changelog: true
+
+passes: [flatten_intersections, prune_unused]
+
+trailing_slash: strip
+
+missing_path_params: synthesize
+
+allow_overlap: true
+
+query_object: dot
+
+modules:
+  accounts: [getMe, "/users/*"]
+
+operations:
+  reportProgress:
+    streaming: off
+  pollEvents:
+    streaming: force
 
 generators:
   node-client:
     output: out/node
     layout: modular
     base_url: https://api.example.com
+    environments:
+      dev: http://localhost:8080
+      prod: https://api.example.com
+    route_table: true
+    streaming_helpers: true
+    batch_helpers: true
+    debug_logging: true
+    retry: true
+    minimal_runtime: true
+    next_handlers: true
+    next_handlers_dir: app/api
+    index_json: true
+    test_report: true
+    type_prefix: Api
+    examples: emit-unchecked
+    auth:
+      refresh_before_expiry_s: 60
     scaffold:
       package_name: "@myorg/client"
       formatter: biome
       bundler: tsdown
+    files:
+      exclude: ["src/sse.ts", "src/client.test.ts"]
+    naming:
+      strategy: use_operation_id
+      aliases:
+        listModels: models
   react-swr-client:
     output: out/react
     layout: split
     split_by: tag
+    split_types: true
+    sse_transport: eventsource
+    params_style: object
+    property_naming: camel
+    key_style: path
+    hook_naming:
+      query_suffix: Query
+      mutation_suffix: Mutation
 "#;
         let value: serde_json::Value = serde_yaml_ng::from_str(yaml).unwrap();
         let config: OagConfig = serde_json::from_value(value).unwrap();
         assert_eq!(config.input, "spec.yaml");
         assert_eq!(config.naming.strategy, NamingStrategy::UseRouteBased);
+        assert_eq!(config.naming.on_collision, CollisionStrategy::Suffix);
+        assert_eq!(config.naming.fallback, FallbackNaming::RouteHash);
+        assert_eq!(config.naming.untagged_module, "misc");
+        assert_eq!(config.docs.max_length, Some(500));
+        assert_eq!(config.examples.prefer, ["minimal", "default"]);
+        assert_eq!(config.extensions.expose, ["x-ratelimit-rpm"]);
+        assert!(config.changelog);
+        assert_eq!(
+            config.passes,
+            [IrPass::FlattenIntersections, IrPass::PruneUnused]
+        );
+        assert_eq!(config.trailing_slash, TrailingSlash::Strip);
+        assert_eq!(config.missing_path_params, MissingPathParams::Synthesize);
+        assert!(config.allow_overlap);
+        assert_eq!(config.query_object, QueryObjectNotation::Dot);
+        assert_eq!(config.modules["accounts"], ["getMe", "/users/*"]);
+        assert_eq!(
+            config.operations["reportProgress"].streaming,
+            Streaming::Off
+        );
+        assert_eq!(config.operations["pollEvents"].streaming, Streaming::Force);
         assert_eq!(config.generators.len(), 2);
 
         let node = &config.generators[&GeneratorId::NodeClient];
         assert_eq!(node.output, "out/node");
         assert_eq!(node.layout, OutputLayout::Modular);
-        assert_eq!(node.base_url, Some("https://api.example.com".to_string()));
+        assert_eq!(
+            node.base_url,
+            Some(BaseUrl::Url("https://api.example.com".to_string()))
+        );
         assert!(node.scaffold.is_some());
         let scaffold = node.scaffold.as_ref().unwrap();
         assert_eq!(scaffold["package_name"], "@myorg/client");
         assert_eq!(scaffold["formatter"], "biome");
         assert_eq!(scaffold["bundler"], "tsdown");
+        assert_eq!(node.files.exclude, ["src/sse.ts", "src/client.test.ts"]);
+        assert_eq!(node.environments["dev"], "http://localhost:8080");
+        assert_eq!(node.environments["prod"], "https://api.example.com");
+        assert!(node.route_table);
+        assert!(node.streaming_helpers);
+        assert!(node.batch_helpers);
+        assert!(node.debug_logging);
+        assert!(node.retry);
+        assert!(node.minimal_runtime);
+        assert!(node.next_handlers);
+        assert_eq!(node.next_handlers_dir, "app/api");
+        assert!(node.index_json);
+        assert!(node.test_report);
+        assert_eq!(node.type_prefix.as_deref(), Some("Api"));
+        assert_eq!(node.examples, ExamplesMode::EmitUnchecked);
+        assert_eq!(node.auth.refresh_before_expiry_s, Some(60));
+        let naming = config.naming.with_overrides(node.naming.as_ref().unwrap());
+        assert_eq!(naming.strategy, NamingStrategy::UseOperationId);
+        assert_eq!(naming.aliases["createChatCompletion"], "chat");
+        assert_eq!(naming.aliases["listModels"], "models");
 
         let react = &config.generators[&GeneratorId::ReactSwrClient];
         assert_eq!(react.output, "out/react");
         assert_eq!(react.layout, OutputLayout::Split);
         assert_eq!(react.split_by, Some(SplitBy::Tag));
+        assert!(react.split_types);
+        assert!(!node.split_types);
+        assert_eq!(react.sse_transport, Some(SseTransport::EventSource));
+        assert_eq!(node.sse_transport, None);
+        assert_eq!(react.params_style, ParamsStyle::Object);
+        assert_eq!(node.params_style, ParamsStyle::Positional);
+        assert_eq!(react.property_naming, PropertyNaming::Camel);
+        assert_eq!(node.property_naming, PropertyNaming::Original);
+        assert_eq!(react.key_style, KeyStyle::Path);
+        assert_eq!(node.key_style, KeyStyle::Operation);
+        assert_eq!(react.hook_naming.query_suffix, "Query");
+        assert_eq!(react.hook_naming.mutation_suffix, "Mutation");
+        assert_eq!(react.hook_naming.sse_suffix, "");
+        assert!(react.files.exclude.is_empty());
+        assert!(!react.route_table);
+        assert!(!react.index_json);
+        assert!(!react.test_report);
+        assert_eq!(react.type_prefix, None);
+        assert_eq!(react.auth, AuthConfig::default());
+        assert_eq!(react.naming, None);
+    }
+
+    #[test]
+    fn test_parse_merge_keys() {
+        let config =
+            parse_config(include_str!("../tests/fixtures/merge-keys-config.yaml")).unwrap();
+
+        let node = &config.generators[&GeneratorId::NodeClient];
+        assert_eq!(node.output, "out/node");
+        assert_eq!(node.layout, OutputLayout::Split);
+        assert_eq!(node.split_by, Some(SplitBy::Tag));
+        assert_eq!(node.base_url, Some(BaseUrl::Disabled));
+        let scaffold = node.scaffold.as_ref().unwrap();
+        assert_eq!(scaffold["formatter"], "biome");
+        assert_eq!(scaffold["test_runner"], "vitest");
+
+        // Keys beside `<<` override the merged ones, including nested merges.
+        let react = &config.generators[&GeneratorId::ReactSwrClient];
+        assert_eq!(react.layout, OutputLayout::Modular);
+        assert_eq!(react.split_by, Some(SplitBy::Tag));
+        let scaffold = react.scaffold.as_ref().unwrap();
+        assert_eq!(scaffold["formatter"], "biome");
+        assert_eq!(scaffold["swr_prefetch"], true);
+        assert!(scaffold.get("<<").is_none());
+
+        let server = &config.generators[&GeneratorId::FastapiServer];
+        let scaffold = server.scaffold.as_ref().unwrap();
+        assert_eq!(scaffold["formatter"], false);
+        assert_eq!(scaffold["test_runner"], "vitest");
+    }
+
+    #[test]
+    fn test_parse_rejects_scalar_merge_keys() {
+        let yaml = r#"
+generators:
+  node-client:
+    <<: out/node
+"#;
+        let err = parse_config(yaml).unwrap_err();
+        assert!(err.contains("`<<` at `generators.node-client`"), "{err}");
+    }
+
+    const PROFILES: &str = r#"
+input: openapi.yaml
+naming:
+  aliases:
+    listPets: allPets
+passes: [prune_unused]
+generators:
+  node-client:
+    output: out/node
+    layout: split
+  react-swr-client:
+    output: out/react
+profiles:
+  public:
+    input: public.yaml
+    generators:
+      node-client:
+        output: out/public
+      react-swr-client: null
+  admin:
+    passes: []
+    naming:
+      aliases:
+        deletePet: removePet
+"#;
+
+    #[test]
+    fn test_parse_profiles() {
+        let root = parse_config(PROFILES).unwrap();
+        assert_eq!(root.input, "openapi.yaml");
+        assert_eq!(root.generators.len(), 2);
+        assert_eq!(root.generators[&GeneratorId::NodeClient].output, "out/node");
+
+        // A profile overrides one generator's output and keeps its other settings.
+        let public = parse_config_profile(PROFILES, Some("public")).unwrap();
+        assert_eq!(public.input, "public.yaml");
+        assert_eq!(public.passes, [IrPass::PruneUnused]);
+        assert_eq!(public.naming.aliases["listPets"], "allPets");
+        assert_eq!(public.generators.len(), 1);
+        let node = &public.generators[&GeneratorId::NodeClient];
+        assert_eq!(node.output, "out/public");
+        assert_eq!(node.layout, OutputLayout::Split);
+
+        // Lists replace the root's; maps merge into it.
+        let admin = parse_config_profile(PROFILES, Some("admin")).unwrap();
+        assert_eq!(admin.input, "openapi.yaml");
+        assert!(admin.passes.is_empty());
+        assert_eq!(admin.naming.aliases["listPets"], "allPets");
+        assert_eq!(admin.naming.aliases["deletePet"], "removePet");
+        assert_eq!(admin.generators.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_unknown_profile() {
+        let err = parse_config_profile(PROFILES, Some("internal")).unwrap_err();
+        assert_eq!(
+            err,
+            "unknown profile `internal`; expected one of: public, admin"
+        );
+
+        let err = parse_config_profile("input: spec.yaml\n", Some("public")).unwrap_err();
+        assert!(err.contains("defines no `profiles`"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_layouts() {
+        let yaml = r#"
+input: spec.yaml
+generators:
+  node-client:
+    output: out/node
+    layout: split
+  react-swr-client:
+    output: out/react
+    layout: bundled
+"#;
+        let value: serde_json::Value = serde_yaml_ng::from_str(yaml).unwrap();
+        let config: OagConfig = serde_json::from_value(value).unwrap();
+        assert_eq!(
+            config.generators[&GeneratorId::NodeClient].layout,
+            OutputLayout::Split
+        );
+        assert_eq!(
+            config.generators[&GeneratorId::ReactSwrClient].layout,
+            OutputLayout::Bundled
+        );
+        assert!(serde_json::from_value::<OutputLayout>(serde_json::json!("single")).is_err());
     }
 
     #[test]
@@ -471,7 +1962,7 @@ client:
         assert_eq!(node_gen.output, "out");
         assert_eq!(
             node_gen.base_url,
-            Some("https://api.example.com".to_string())
+            Some(BaseUrl::Url("https://api.example.com".to_string()))
         );
         assert_eq!(node_gen.no_jsdoc, Some(true));
     }
@@ -542,6 +2033,100 @@ output_options:
         );
     }
 
+    #[test]
+    fn test_scaffold_version_modes() {
+        let version = |value: serde_json::Value| -> ScaffoldVersion {
+            serde_json::from_value(value).unwrap()
+        };
+        assert_eq!(version("spec".into()), ScaffoldVersion::Spec);
+        assert_eq!(version("spec+date".into()), ScaffoldVersion::SpecDate);
+        assert_eq!(
+            version("2.1.0".into()),
+            ScaffoldVersion::Literal("2.1.0".into())
+        );
+        assert_eq!(
+            version(serde_json::json!(2.1)),
+            ScaffoldVersion::Literal("2.1".into())
+        );
+        assert!(serde_json::from_value::<ScaffoldVersion>(serde_json::json!(true)).is_err());
+
+        let resolve = |version: ScaffoldVersion, spec: &str| {
+            version
+                .resolve(spec, "20240301")
+                .map(|v| (v.semver(), v.pep440()))
+        };
+        assert_eq!(
+            resolve(ScaffoldVersion::Spec, "1.0"),
+            Ok(("1.0.0".into(), Some("1.0.0".into())))
+        );
+        assert_eq!(
+            resolve(ScaffoldVersion::SpecDate, "1.0"),
+            Ok(("1.0.0-20240301".into(), Some("1.0.0.dev20240301".into())))
+        );
+        assert_eq!(
+            resolve(ScaffoldVersion::SpecDate, "2.0.0-beta.1"),
+            Ok((
+                "2.0.0-beta.1.20240301".into(),
+                Some("2.0.0b1.dev20240301".into())
+            ))
+        );
+        assert_eq!(
+            resolve(ScaffoldVersion::Literal("3".into()), "1.0"),
+            Ok(("3.0.0".into(), Some("3.0.0".into())))
+        );
+        assert_eq!(
+            resolve(ScaffoldVersion::Spec, "latest"),
+            Err("`info.version` `latest` isn't a version; using 0.0.0".into())
+        );
+        assert_eq!(
+            resolve(ScaffoldVersion::Literal("next".into()), "1.0"),
+            Err("`scaffold.version` `next` isn't a version; using 0.0.0".into())
+        );
+        assert_eq!(ScaffoldVersion::Spec.semver("latest"), "0.0.0");
+        assert_eq!(ScaffoldVersion::Spec.pep440("1.0.0-nightly"), "0.0.0");
+    }
+
+    #[test]
+    fn test_package_version_sanitization() {
+        let semver = |raw: &str| PackageVersion::parse(raw).map(|v| v.semver());
+        assert_eq!(semver("1").as_deref(), Some("1.0.0"));
+        assert_eq!(semver("1.2").as_deref(), Some("1.2.0"));
+        assert_eq!(semver(" v1.2.3 ").as_deref(), Some("1.2.3"));
+        assert_eq!(semver("01.02.003").as_deref(), Some("1.2.3"));
+        assert_eq!(semver("2023-06-01").as_deref(), Some("2023.6.1"));
+        assert_eq!(semver("1.0-rc.01").as_deref(), Some("1.0.0-rc.1"));
+        assert_eq!(
+            semver("1.0.0-alpha-2+Build.007").as_deref(),
+            Some("1.0.0-alpha-2+Build.007")
+        );
+        for invalid in [
+            "",
+            "latest",
+            "1.2.3.4",
+            "1..2",
+            "1.0-",
+            "1.0.0-beta..1",
+            "1.0+",
+            "1.0_1",
+        ] {
+            assert_eq!(semver(invalid), None, "{invalid:?}");
+        }
+
+        let pep440 = |raw: &str| PackageVersion::parse(raw).unwrap().pep440();
+        assert_eq!(pep440("1.0.0-rc1").as_deref(), Some("1.0.0rc1"));
+        assert_eq!(pep440("1.0.0-alpha").as_deref(), Some("1.0.0a0"));
+        assert_eq!(pep440("1.0.0+Build-7").as_deref(), Some("1.0.0+build.7"));
+        assert_eq!(pep440("1.0.0-nightly"), None);
+        assert_eq!(pep440("1.0.0-beta1.2"), None);
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(19_783), (2024, 3, 1));
+        assert_eq!(civil_date(11_016), (2000, 2, 29));
+    }
+
     #[test]
     fn test_tool_setting_deserialize() {
         let named: ToolSetting = serde_json::from_value(serde_json::json!("biome")).unwrap();
@@ -554,6 +2139,21 @@ output_options:
         assert!(err.is_err());
     }
 
+    #[test]
+    fn test_base_url_setting() {
+        let disabled: BaseUrl = serde_json::from_value(serde_json::json!(false)).unwrap();
+        assert_eq!(disabled, BaseUrl::Disabled);
+        assert!(serde_json::from_value::<BaseUrl>(serde_json::json!(true)).is_err());
+
+        let spec_default = Some("https://api.example.com/v1");
+        assert_eq!(BaseUrl::resolve(None, spec_default), spec_default);
+        assert_eq!(
+            BaseUrl::resolve(Some(&BaseUrl::Url("http://localhost".into())), spec_default),
+            Some("http://localhost")
+        );
+        assert_eq!(BaseUrl::resolve(Some(&disabled), spec_default), None);
+    }
+
     #[test]
     fn test_parse_minimal_config() {
         let yaml = "input: api.yaml\n";
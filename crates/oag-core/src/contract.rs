@@ -0,0 +1,178 @@
+//! Shared pieces of the generated contract tests (`contract_tests: true`).
+//!
+//! The node client's `contract/` suite calls the FastAPI stubs over HTTP, and
+//! the stubs answer from a table of canned responses. Both sides are built
+//! from the same IR, so they agree on which operations are covered
+//! ([`is_contract_operation`]) and on every request and response payload
+//! ([`sample_value`]) without reading each other's output.
+
+use serde_json::{Map, Value, json};
+
+use crate::ir::{IrOperation, IrReturnType, IrSchema, IrSpec, IrType};
+
+/// The placeholder mocks and samples use for a string `writeOnly` field
+/// (passwords, tokens), so generated tests never show a plausible secret.
+pub const REDACTED: &str = "<redacted>";
+
+/// How deep [`sample_value`] follows nested schemas before settling for an
+/// empty object or array, so recursive schemas terminate.
+const MAX_SAMPLE_DEPTH: usize = 8;
+
+/// Whether the contract suite covers `op`: a JSON (or bodiless) request with a
+/// JSON or empty response. Streams, plain-text and upload bodies, and text
+/// responses are left to the mocked client tests.
+pub fn is_contract_operation(op: &IrOperation) -> bool {
+    let json_body = op
+        .request_body
+        .as_ref()
+        .is_none_or(|body| body.content_type.ends_with("json"));
+    let json_response = match &op.return_type {
+        IrReturnType::Standard(resp) => !resp.is_text() && resp.response_type != IrType::Binary,
+        IrReturnType::Void => true,
+        IrReturnType::Sse(_) => false,
+    };
+    !op.is_webhook && json_body && json_response
+}
+
+/// The HTTP status the FastAPI stub for `op` answers with: `204` for
+/// operations without a response body, FastAPI's default `200` otherwise.
+pub fn contract_status(op: &IrOperation) -> u16 {
+    match op.return_type {
+        IrReturnType::Void => 204,
+        _ => 200,
+    }
+}
+
+/// A deterministic JSON value of `ir_type` that both a client and a server
+/// generated from `ir` accept: a top-level object gets every field, nested
+/// objects only their required ones, arrays one item, enums and unions their
+/// first member, and a discriminated union's variant its tag. String
+/// `writeOnly` fields are [`REDACTED`].
+pub fn sample_value(ir: &IrSpec, ir_type: &IrType) -> Value {
+    sample(ir, ir_type, 0, false)
+}
+
+/// [`sample_value`] as a server sends it back: without `writeOnly` fields,
+/// which the FastAPI models exclude from responses.
+pub fn sample_response_value(ir: &IrSpec, ir_type: &IrType) -> Value {
+    sample(ir, ir_type, 0, true)
+}
+
+fn sample(ir: &IrSpec, ir_type: &IrType, depth: usize, response: bool) -> Value {
+    match ir_type {
+        IrType::String | IrType::Binary | IrType::Any => json!("test"),
+        IrType::StringLiteral(s) => json!(s),
+        IrType::Number | IrType::Integer => json!(1),
+        IrType::Boolean => json!(true),
+        IrType::DateTime => json!("2024-01-01T00:00:00Z"),
+        IrType::Null | IrType::Void => Value::Null,
+        IrType::Array(_) if depth >= MAX_SAMPLE_DEPTH => json!([]),
+        IrType::Array(inner) => json!([sample(ir, inner, depth + 1, response)]),
+        IrType::Map(..) => json!({}),
+        IrType::Object(fields) => Value::Object(
+            fields
+                .iter()
+                .filter(|(_, _, required)| depth == 0 || *required)
+                .map(|(name, ty, _)| (name.clone(), sample(ir, ty, depth + 1, response)))
+                .collect(),
+        ),
+        IrType::Union(variants) => variants
+            .iter()
+            .find(|v| **v != IrType::Null)
+            .map_or(Value::Null, |v| sample(ir, v, depth, response)),
+        IrType::Intersection(parts) => {
+            let mut merged = Map::new();
+            for part in parts {
+                if let Value::Object(members) = sample(ir, part, depth, response) {
+                    merged.extend(members);
+                }
+            }
+            Value::Object(merged)
+        }
+        IrType::Ref(_) if depth > MAX_SAMPLE_DEPTH => json!({}),
+        IrType::Ref(name) => match ir.schemas.iter().find(|s| s.name().pascal_case == *name) {
+            Some(IrSchema::Object(obj)) => Value::Object(
+                obj.fields
+                    .iter()
+                    .filter(|f| depth == 0 || f.required)
+                    .filter(|f| !(response && f.write_only))
+                    .map(|f| {
+                        let value = match f.field_type {
+                            IrType::String if f.write_only => json!(REDACTED),
+                            _ => sample(ir, &f.field_type, depth + 1, response),
+                        };
+                        (f.original_name.clone(), value)
+                    })
+                    .collect(),
+            ),
+            Some(IrSchema::Enum(e)) => e.variants.first().map_or(json!("test"), |v| json!(v)),
+            Some(IrSchema::Alias(alias)) => sample(ir, &alias.target, depth, response),
+            Some(IrSchema::Union(union)) => {
+                let Some(variant) = union.variants.iter().find(|v| **v != IrType::Null) else {
+                    return Value::Null;
+                };
+                let mut value = sample(ir, variant, depth, response);
+                if let (Some(discriminator), IrType::Ref(variant_name), Value::Object(members)) =
+                    (&union.discriminator, variant, &mut value)
+                {
+                    let tag = discriminator
+                        .mapping
+                        .iter()
+                        .find(|(_, target)| target == variant_name)
+                        .map_or(variant_name, |(tag, _)| tag);
+                    members.insert(discriminator.property_name.clone(), json!(tag));
+                }
+                value
+            }
+            None => json!({}),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn petstore() -> IrSpec {
+        let spec =
+            crate::parse::from_yaml(include_str!("../tests/fixtures/petstore-polymorphic.yaml"))
+                .unwrap();
+        crate::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn discriminated_unions_carry_their_tag() {
+        let ir = petstore();
+        let pet = sample_value(&ir, &IrType::Ref("Pet".to_string()));
+        assert_eq!(pet["petType"], "cat");
+    }
+
+    #[test]
+    fn unions_skip_null_and_arrays_hold_one_item() {
+        let ir = petstore();
+        let value = sample_value(
+            &ir,
+            &IrType::Array(Box::new(IrType::Union(vec![
+                IrType::Null,
+                IrType::DateTime,
+            ]))),
+        );
+        assert_eq!(value, json!(["2024-01-01T00:00:00Z"]));
+    }
+
+    #[test]
+    fn write_only_fields_are_redacted_and_left_out_of_responses() {
+        let spec =
+            crate::parse::from_yaml(include_str!("../tests/fixtures/write-only.yaml")).unwrap();
+        let ir = crate::transform::transform(&spec).unwrap();
+        let user = IrType::Ref("User".to_string());
+
+        let request = sample_value(&ir, &user);
+        assert_eq!(request["password"], REDACTED);
+        assert_eq!(request["email"], "test");
+
+        let response = sample_response_value(&ir, &user);
+        assert!(response.get("password").is_none());
+        assert_eq!(response["email"], "test");
+    }
+}
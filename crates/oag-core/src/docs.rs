@@ -0,0 +1,339 @@
+//! Formatting of spec descriptions for generated doc comments.
+//!
+//! Descriptions are markdown written for API docs: long, multi-paragraph, with
+//! HTML, code fences and `\r\n` line endings. These helpers turn them into
+//! comment lines that read well in an editor and can't break out of the comment.
+
+/// Column doc comments wrap at, including indentation and the comment prefix.
+pub const DOC_WIDTH: usize = 100;
+
+/// Split a description into lines of at most `width` characters.
+///
+/// Paragraphs are re-flowed and separated by an empty line; list items,
+/// headings and block tags (`@minimum 0`) start their own line and fenced code
+/// blocks are kept verbatim.
+/// HTML tags are stripped. Words longer than `width` are not broken.
+pub fn doc_lines(text: &str, width: usize) -> Vec<String> {
+    let text = text.replace("\r\n", "\n").replace('\r', "\n");
+    let mut lines: Vec<String> = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut in_fence = false;
+
+    let flush = |paragraph: &mut Vec<String>, lines: &mut Vec<String>| {
+        if !paragraph.is_empty() {
+            lines.extend(wrap(&paragraph.join(" "), width));
+            paragraph.clear();
+        }
+    };
+
+    for raw in text.lines() {
+        let trimmed = raw.trim();
+        if trimmed.starts_with("```") {
+            flush(&mut paragraph, &mut lines);
+            in_fence = !in_fence;
+            lines.push(trimmed.to_string());
+            continue;
+        }
+        if in_fence {
+            lines.push(raw.trim_end().to_string());
+            continue;
+        }
+
+        let line = strip_html(trimmed);
+        let line = line.trim();
+        if line.is_empty() {
+            flush(&mut paragraph, &mut lines);
+            if lines.last().is_some_and(|l| !l.is_empty()) {
+                lines.push(String::new());
+            }
+        } else if starts_block(line) {
+            flush(&mut paragraph, &mut lines);
+            paragraph.push(line.to_string());
+        } else {
+            paragraph.push(line.to_string());
+        }
+    }
+    flush(&mut paragraph, &mut lines);
+
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines
+}
+
+/// Shorten `text` to at most `max_length` characters, cutting at a word
+/// boundary and ending with `…`. Returns `None` if it already fits.
+pub fn truncate_doc(text: &str, max_length: usize) -> Option<String> {
+    if text.chars().count() <= max_length {
+        return None;
+    }
+    let cut: String = text.chars().take(max_length.saturating_sub(1)).collect();
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(i) if i > 0 => &cut[..i],
+        _ => cut.as_str(),
+    };
+    Some(format!("{}…", cut.trim_end()))
+}
+
+/// Render a JSDoc block. The first line is unindented (the template places it);
+/// the rest are prefixed with `indent`. Short docs collapse to `/** text */`.
+pub fn jsdoc(text: &str, indent: &str, see: Option<&str>) -> String {
+    let mut lines = doc_lines(text, DOC_WIDTH.saturating_sub(indent.len() + 3));
+    if let Some(url) = see {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("@see {url}"));
+    }
+    let lines: Vec<String> = lines.iter().map(|l| escape_jsdoc(l)).collect();
+
+    if let [line] = lines.as_slice()
+        && indent.len() + line.len() + 7 <= DOC_WIDTH
+    {
+        return format!("/** {line} */");
+    }
+    format!("/**\n{}\n{indent} */", jsdoc_lines(&lines, indent))
+}
+
+/// Lines for the body of a JSDoc block that the template opens and closes
+/// itself, e.g. ` * {{ description | jsdoc_body }}`.
+pub fn jsdoc_body(text: &str, indent: &str) -> String {
+    let lines: Vec<String> = doc_lines(text, DOC_WIDTH.saturating_sub(indent.len() + 3))
+        .iter()
+        .map(|l| escape_jsdoc(l))
+        .collect();
+    let body = jsdoc_lines(&lines, indent);
+    body.strip_prefix(&format!("{indent} * "))
+        .unwrap_or(&body)
+        .to_string()
+}
+
+/// Render a Python docstring, including the quotes. Like [`jsdoc`], the first
+/// line is left for the template to indent.
+pub fn docstring(text: &str, indent: &str, see: Option<&str>) -> String {
+    let mut lines = doc_lines(text, DOC_WIDTH.saturating_sub(indent.len()));
+    if let Some(url) = see {
+        if !lines.is_empty() {
+            lines.push(String::new());
+        }
+        lines.push(format!("See: {url}"));
+    }
+    let lines: Vec<String> = lines.iter().map(|l| escape_docstring(l)).collect();
+
+    if let [line] = lines.as_slice()
+        && indent.len() + line.len() + 6 <= DOC_WIDTH
+    {
+        return format!("\"\"\"{line}\"\"\"");
+    }
+    let body: Vec<String> = lines
+        .iter()
+        .map(|l| {
+            if l.is_empty() {
+                String::new()
+            } else {
+                format!("{indent}{l}")
+            }
+        })
+        .collect();
+    format!("\"\"\"\n{}\n{indent}\"\"\"", body.join("\n"))
+}
+
+/// Render `#` line comments; the first line is left for the template to indent.
+pub fn line_comment(text: &str, indent: &str) -> String {
+    doc_lines(text, DOC_WIDTH.saturating_sub(indent.len() + 2))
+        .iter()
+        .map(|l| {
+            if l.is_empty() {
+                "#".to_string()
+            } else {
+                format!("# {l}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(&format!("\n{indent}"))
+}
+
+fn jsdoc_lines(lines: &[String], indent: &str) -> String {
+    lines
+        .iter()
+        .map(|l| {
+            if l.is_empty() {
+                format!("{indent} *")
+            } else {
+                format!("{indent} * {l}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// `*/` would close the comment early.
+fn escape_jsdoc(line: &str) -> String {
+    line.replace("*/", "*\\/")
+}
+
+/// Backslashes would start escape sequences, and `"""` (or a trailing `"`)
+/// would close the docstring early.
+fn escape_docstring(line: &str) -> String {
+    let escaped = line.replace('\\', "\\\\").replace("\"\"\"", "\\\"\\\"\\\"");
+    match escaped.strip_suffix('"') {
+        Some(rest) if !rest.ends_with('\\') => format!("{rest}\\\""),
+        _ => escaped,
+    }
+}
+
+/// Whether a line starts a markdown block that shouldn't be joined with the
+/// previous line: a list item or a heading.
+fn starts_block(line: &str) -> bool {
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return true;
+    }
+    if line.starts_with('#') {
+        return true;
+    }
+    if line
+        .strip_prefix('@')
+        .is_some_and(|tag| tag.starts_with(|c: char| c.is_ascii_alphabetic()))
+    {
+        return true;
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
+/// Drop HTML tags and comments, keeping their text. A `<` not followed by a
+/// letter, `/` or `!` (e.g. `a < b`) is left alone.
+fn strip_html(line: &str) -> String {
+    let mut out = String::with_capacity(line.len());
+    let mut rest = line;
+    while let Some(start) = rest.find('<') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let is_tag = after
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!');
+        match after.find('>') {
+            Some(end) if is_tag => rest = &after[end + 1..],
+            _ => {
+                out.push('<');
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        if !current.is_empty() && current.len() + 1 + word.len() > width {
+            lines.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(word);
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reflows_paragraphs_and_keeps_blocks() {
+        let text = "First paragraph\r\nspans two lines.\r\n\r\n- item one\n- item two\n\n```json\n{\"a\": 1}\n```";
+        assert_eq!(
+            doc_lines(text, 100),
+            [
+                "First paragraph spans two lines.",
+                "",
+                "- item one",
+                "- item two",
+                "",
+                "```json",
+                "{\"a\": 1}",
+                "```",
+            ]
+        );
+    }
+
+    #[test]
+    fn block_tags_start_their_own_line() {
+        assert_eq!(
+            doc_lines(
+                "Units.\n\n@format int32\n@minimum 0\nemail me@example.com",
+                100
+            ),
+            [
+                "Units.",
+                "",
+                "@format int32",
+                "@minimum 0 email me@example.com"
+            ]
+        );
+    }
+
+    #[test]
+    fn wraps_at_width() {
+        let lines = doc_lines("one two three four five", 9);
+        assert_eq!(lines, ["one two", "three", "four five"]);
+    }
+
+    #[test]
+    fn strips_html_tags_only() {
+        assert_eq!(
+            doc_lines("<p>Use <b>bold</b> if a < b</p><!-- note -->", 100),
+            ["Use bold if a < b"]
+        );
+    }
+
+    #[test]
+    fn truncates_at_word_boundary() {
+        assert_eq!(truncate_doc("short", 10), None);
+        assert_eq!(
+            truncate_doc("The quick brown fox jumps", 12).as_deref(),
+            Some("The quick…")
+        );
+    }
+
+    #[test]
+    fn renders_jsdoc() {
+        assert_eq!(
+            jsdoc("List pets */ now", "  ", None),
+            "/** List pets *\\/ now */"
+        );
+        assert_eq!(
+            jsdoc(
+                "List pets.\n\nPaged.",
+                "  ",
+                Some("https://docs.example.com")
+            ),
+            "/**\n   * List pets.\n   *\n   * Paged.\n   *\n   * @see https://docs.example.com\n   */"
+        );
+        assert_eq!(jsdoc_body("One.\n\nTwo.", ""), "One.\n *\n * Two.");
+    }
+
+    #[test]
+    fn renders_docstring() {
+        assert_eq!(
+            docstring(r#"Match \d+ "quoted""#, "    ", None),
+            r#""""Match \\d+ "quoted\"""""#
+        );
+        assert_eq!(
+            docstring("One.\n\nTwo.", "    ", None),
+            "\"\"\"\n    One.\n\n    Two.\n    \"\"\""
+        );
+        assert_eq!(
+            line_comment("One.\n\nTwo.", "    "),
+            "# One.\n    #\n    # Two."
+        );
+    }
+}
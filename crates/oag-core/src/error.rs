@@ -1,5 +1,13 @@
+#[cfg(feature = "generate")]
+use std::path::PathBuf;
+
 use thiserror::Error;
 
+#[cfg(feature = "generate")]
+use crate::GeneratorError;
+#[cfg(feature = "generate")]
+use crate::config::GeneratorId;
+
 #[derive(Debug, Error)]
 pub enum ParseError {
     #[error("failed to parse YAML: {0}")]
@@ -13,6 +21,9 @@ pub enum ParseError {
 
     #[error("missing required field: {0}")]
     MissingField(String),
+
+    #[error("invalid YAML merge key: {0}")]
+    InvalidMergeKey(String),
 }
 
 #[derive(Debug, Error)]
@@ -30,6 +41,7 @@ pub enum ResolveError {
     RefTargetNotFound(String),
 }
 
+#[cfg(feature = "transform")]
 #[derive(Debug, Error)]
 pub enum TransformError {
     #[error("parse error: {0}")]
@@ -38,6 +50,124 @@ pub enum TransformError {
     #[error("resolve error: {0}")]
     Resolve(#[from] ResolveError),
 
+    #[error(
+        "schemas `{first}` and `{second}` both normalize to `{name}`; rename one or set `naming.on_collision: suffix`"
+    )]
+    NameCollision {
+        name: String,
+        first: String,
+        second: String,
+    },
+
+    #[error(
+        "`naming.schema_aliases` renames `{schema}` to `{name}`, which `{existing}` already uses"
+    )]
+    SchemaAliasCollision {
+        schema: String,
+        name: String,
+        existing: String,
+    },
+
+    #[error("operations `{first}` and `{second}` are both named `{name}`; check `naming.aliases`")]
+    AliasCollision {
+        name: String,
+        first: String,
+        second: String,
+    },
+
+    #[error(
+        "`{method} {first}` and `{method} {second}` are the same route; remove one or set `trailing_slash: keep`"
+    )]
+    DuplicateRoute {
+        method: String,
+        first: String,
+        second: String,
+    },
+
+    #[error(
+        "`{route}` has no path parameter `{name}`; declare it or set `missing_path_params: synthesize`"
+    )]
+    UndeclaredPathParam { route: String, name: String },
+
+    #[error("`{operation_or_schema}` references unknown schema `{ref_name}`")]
+    BrokenRef {
+        operation_or_schema: String,
+        ref_name: String,
+    },
+
     #[error("transform failed: {0}")]
     Other(String),
 }
+
+#[cfg(feature = "transform")]
+#[derive(Debug, Error)]
+pub enum IrDumpError {
+    #[error("failed to read IR dump: {0}")]
+    Json(#[from] serde_json::Error),
+
+    #[error("not an oag IR dump; write one with `oag generate --emit-ir`")]
+    NotADump,
+
+    #[error(
+        "IR dump is from oag {found}, but this is oag {expected}; dump it again with `oag generate --emit-ir`"
+    )]
+    Version {
+        found: String,
+        expected: &'static str,
+    },
+}
+
+#[cfg(feature = "transform")]
+#[derive(Debug, Error)]
+pub enum FixtureError {
+    #[error(
+        "no operation with id {} in the spec",
+        .0.iter().map(|id| format!("`{id}`")).collect::<Vec<_>>().join(", ")
+    )]
+    UnknownOperations(Vec<String>),
+
+    #[error("reference target not found: {0}")]
+    MissingComponent(String),
+}
+
+#[cfg(feature = "generate")]
+#[derive(Debug, Error)]
+pub enum RunError {
+    #[error("failed to access {}: {source}", path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[error(transparent)]
+    Parse(#[from] ParseError),
+
+    #[error(transparent)]
+    Transform(#[from] TransformError),
+
+    #[error("no generator registered for {0}")]
+    UnregisteredGenerator(GeneratorId),
+
+    #[error("{id}: {source}")]
+    Generator {
+        id: GeneratorId,
+        source: GeneratorError,
+    },
+
+    #[error(
+        "generators would overwrite each other's files:\n{}\ngive them separate `output` directories or set `allow_overlap: true`",
+        overlaps.iter().map(|o| format!("  {o}")).collect::<Vec<_>>().join("\n")
+    )]
+    OutputOverlap { overlaps: Vec<OutputOverlap> },
+}
+
+/// A file two generators both write, with different content.
+#[cfg(feature = "generate")]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("{} (written by {first} and {second})", path.display())]
+pub struct OutputOverlap {
+    /// The file's path relative to the project root.
+    pub path: PathBuf,
+    pub first: GeneratorId,
+    pub second: GeneratorId,
+}
@@ -0,0 +1,526 @@
+//! Checks a spec's `example`/`examples` payloads against their schemas.
+//!
+//! Examples drift from the schemas they illustrate, and nothing else reads
+//! them. [`check_examples`] walks every example on a component schema,
+//! parameter, request body and response, and matches it structurally against
+//! the schema's [`IrType`]: JSON types, required fields, enum members and
+//! union variants. Formats, patterns and bounds are not checked.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::ir::{IrSchema, IrSpec, IrType};
+use crate::parse::media_type::MediaType;
+use crate::parse::operation::{Operation, PathItem};
+use crate::parse::parameter::ParameterOrRef;
+use crate::parse::request_body::RequestBodyOrRef;
+use crate::parse::response::ResponseOrRef;
+use crate::parse::schema::SchemaOrRef;
+use crate::parse::spec::OpenApiSpec;
+use crate::transform::{normalize_name, schema_or_ref_to_ir_type};
+
+/// An example that doesn't match its schema.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExampleMismatch {
+    /// Which example, e.g. `POST /pets request body (application/json) example \`broken\``.
+    pub location: String,
+    /// Where in the example, e.g. `$.tags[0]`; `$` is the whole payload.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for ExampleMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at `{}`: {}", self.location, self.path, self.message)
+    }
+}
+
+/// Every example in `spec` (as parsed, `$ref`s intact) that doesn't match its
+/// schema, with named schemas looked up in `ir`, the spec's transform.
+pub fn check_examples(spec: &OpenApiSpec, ir: &IrSpec) -> Vec<ExampleMismatch> {
+    let mut checker = Checker {
+        spec,
+        schemas: ir
+            .schemas
+            .iter()
+            .map(|s| (s.name().pascal_case.as_str(), s))
+            .collect(),
+        mismatches: Vec::new(),
+    };
+
+    if let Some(components) = &spec.components {
+        for (name, schema) in &components.schemas {
+            if let SchemaOrRef::Schema(schema) = schema
+                && let Some(example) = &schema.example
+            {
+                let ty = IrType::Ref(normalize_name(name).pascal_case);
+                checker.check(
+                    &format!("schema {name} example"),
+                    &ty,
+                    example,
+                    Direction::Either,
+                );
+            }
+        }
+    }
+    for (path, item) in &spec.paths {
+        for (method, op) in item.operations() {
+            checker.operation(&format!("{method} {path}"), item, op);
+        }
+    }
+    for (name, item) in &spec.webhooks {
+        for (method, op) in item.operations() {
+            checker.operation(&format!("webhook {name} ({method})"), item, op);
+        }
+    }
+
+    checker.mismatches
+}
+
+/// The component name a `#/components/{kind}/{name}` pointer names.
+fn component_name(ref_path: &str) -> &str {
+    ref_path.rsplit('/').next().unwrap_or(ref_path)
+}
+
+/// Which side of the wire a payload is on: `readOnly` fields may be left out
+/// of requests and `writeOnly` fields out of responses.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    Request,
+    Response,
+    Either,
+}
+
+struct Checker<'a> {
+    spec: &'a OpenApiSpec,
+    schemas: HashMap<&'a str, &'a IrSchema>,
+    mismatches: Vec<ExampleMismatch>,
+}
+
+impl Checker<'_> {
+    fn operation(&mut self, label: &str, item: &PathItem, op: &Operation) {
+        let components = self.spec.components.as_ref();
+
+        for param in item.parameters.iter().chain(&op.parameters) {
+            let param = match param {
+                ParameterOrRef::Parameter(param) => Some(param),
+                ParameterOrRef::Ref { ref_path } => {
+                    components.and_then(|c| match c.parameters.get(component_name(ref_path))? {
+                        ParameterOrRef::Parameter(param) => Some(param),
+                        ParameterOrRef::Ref { .. } => None,
+                    })
+                }
+            };
+            let Some(param) = param else { continue };
+            let location = format!("{label} parameter {}", param.name);
+            if let (Some(schema), Some(example)) = (&param.schema, &param.example) {
+                let ty = schema_or_ref_to_ir_type(schema);
+                self.check(
+                    &format!("{location} example"),
+                    &ty,
+                    example,
+                    Direction::Request,
+                );
+            }
+            if let Some(schema) = &param.schema {
+                let ty = schema_or_ref_to_ir_type(schema);
+                for (name, example) in &param.examples {
+                    if let Some(value) = example.get("value") {
+                        self.check(
+                            &format!("{location} example `{name}`"),
+                            &ty,
+                            value,
+                            Direction::Request,
+                        );
+                    }
+                }
+            }
+            self.content(&location, &param.content, Direction::Request);
+        }
+
+        let body = match &op.request_body {
+            Some(RequestBodyOrRef::RequestBody(body)) => Some(body),
+            Some(RequestBodyOrRef::Ref { ref_path }) => {
+                components.and_then(|c| match c.request_bodies.get(component_name(ref_path))? {
+                    RequestBodyOrRef::RequestBody(body) => Some(body),
+                    RequestBodyOrRef::Ref { .. } => None,
+                })
+            }
+            None => None,
+        };
+        if let Some(body) = body {
+            self.content(
+                &format!("{label} request body"),
+                &body.content,
+                Direction::Request,
+            );
+        }
+
+        for (status, response) in &op.responses {
+            let response = match response {
+                ResponseOrRef::Response(response) => Some(response),
+                ResponseOrRef::Ref { ref_path } => {
+                    components.and_then(|c| match c.responses.get(component_name(ref_path))? {
+                        ResponseOrRef::Response(response) => Some(response),
+                        ResponseOrRef::Ref { .. } => None,
+                    })
+                }
+            };
+            if let Some(response) = response {
+                self.content(
+                    &format!("{label} response {status}"),
+                    &response.content,
+                    Direction::Response,
+                );
+            }
+        }
+    }
+
+    fn content(
+        &mut self,
+        location: &str,
+        content: &IndexMap<String, MediaType>,
+        direction: Direction,
+    ) {
+        for (content_type, media_type) in content {
+            let Some(schema) = &media_type.schema else {
+                continue;
+            };
+            let ty = schema_or_ref_to_ir_type(schema);
+            let at = format!("{location} ({content_type})");
+            if let Some(example) = &media_type.example {
+                self.check(&format!("{at} example"), &ty, example, direction);
+            }
+            for (name, example) in &media_type.examples {
+                // An Example Object; `$ref` and `externalValue` ones aren't followed.
+                if let Some(value) = example.get("value") {
+                    self.check(&format!("{at} example `{name}`"), &ty, value, direction);
+                }
+            }
+        }
+    }
+
+    fn check(&mut self, location: &str, ty: &IrType, example: &Value, direction: Direction) {
+        let mut errors = Vec::new();
+        self.matches(ty, example, "$", direction, &mut errors);
+        self.mismatches
+            .extend(errors.into_iter().map(|(path, message)| ExampleMismatch {
+                location: location.to_string(),
+                path,
+                message,
+            }));
+    }
+
+    /// Push a `(path, message)` for every way `value` doesn't match `ty`.
+    fn matches(
+        &self,
+        ty: &IrType,
+        value: &Value,
+        path: &str,
+        direction: Direction,
+        errors: &mut Vec<(String, String)>,
+    ) {
+        let mut expected = |what: &str| {
+            errors.push((
+                path.to_string(),
+                format!("expected {what}, found {}", describe(value)),
+            ))
+        };
+        match ty {
+            IrType::Any | IrType::Void | IrType::Binary => {}
+            IrType::String | IrType::DateTime if !value.is_string() => expected("a string"),
+            IrType::StringLiteral(literal) if value.as_str() != Some(literal) => {
+                expected(&format!("\"{literal}\""))
+            }
+            IrType::Number if !value.is_number() => expected("a number"),
+            IrType::Integer if !value.is_i64() && !value.is_u64() => expected("an integer"),
+            IrType::Boolean if !value.is_boolean() => expected("a boolean"),
+            IrType::Null if !value.is_null() => expected("null"),
+            IrType::Array(inner) => match value.as_array() {
+                Some(items) => {
+                    for (i, item) in items.iter().enumerate() {
+                        self.matches(inner, item, &format!("{path}[{i}]"), direction, errors);
+                    }
+                }
+                None => expected("an array"),
+            },
+            IrType::Map(inner, _) => match value.as_object() {
+                Some(entries) => {
+                    for (key, item) in entries {
+                        self.matches(inner, item, &member(path, key), direction, errors);
+                    }
+                }
+                None => expected("an object"),
+            },
+            IrType::Object(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, field_type, required)| (name.as_str(), field_type, *required));
+                self.object(fields, value, path, direction, errors);
+            }
+            IrType::Union(variants) => {
+                let matched = variants.iter().any(|variant| {
+                    let mut variant_errors = Vec::new();
+                    self.matches(variant, value, path, direction, &mut variant_errors);
+                    variant_errors.is_empty()
+                });
+                if matched {
+                    return;
+                }
+                // Inline enums are unions of their values, so name them.
+                let literals: Option<Vec<&str>> = variants
+                    .iter()
+                    .map(|variant| match variant {
+                        IrType::StringLiteral(literal) => Some(literal.as_str()),
+                        _ => None,
+                    })
+                    .collect();
+                match literals {
+                    Some(literals) => expected(&one_of(&literals)),
+                    None => expected("a value matching one of the union's variants"),
+                }
+            }
+            IrType::Intersection(parts) => {
+                for part in parts {
+                    self.matches(part, value, path, direction, errors);
+                }
+            }
+            IrType::Ref(name) => match self.schemas.get(name.as_str()) {
+                Some(IrSchema::Object(obj)) => {
+                    let fields = obj.fields.iter().map(|field| {
+                        let optional = match direction {
+                            Direction::Request => field.read_only,
+                            Direction::Response => field.write_only,
+                            Direction::Either => field.read_only || field.write_only,
+                        };
+                        (
+                            field.original_name.as_str(),
+                            &field.field_type,
+                            field.required && !optional,
+                        )
+                    });
+                    self.object(fields, value, path, direction, errors);
+                }
+                // Only string enums keep their values in the IR.
+                Some(IrSchema::Enum(e))
+                    if !e.variants.is_empty()
+                        && !value
+                            .as_str()
+                            .is_some_and(|s| e.variants.iter().any(|v| v == s)) =>
+                {
+                    let variants: Vec<&str> = e.variants.iter().map(String::as_str).collect();
+                    expected(&one_of(&variants))
+                }
+                Some(IrSchema::Alias(alias)) => {
+                    self.matches(&alias.target, value, path, direction, errors)
+                }
+                Some(IrSchema::Union(union)) => {
+                    let variants = IrType::Union(union.variants.clone());
+                    self.matches(&variants, value, path, direction, errors)
+                }
+                // Broken refs are reported by the transform.
+                Some(IrSchema::Enum(_)) | None => {}
+            },
+            _ => {}
+        }
+    }
+
+    fn object<'f>(
+        &self,
+        fields: impl Iterator<Item = (&'f str, &'f IrType, bool)>,
+        value: &Value,
+        path: &str,
+        direction: Direction,
+        errors: &mut Vec<(String, String)>,
+    ) {
+        let Some(members) = value.as_object() else {
+            errors.push((
+                path.to_string(),
+                format!("expected an object, found {}", describe(value)),
+            ));
+            return;
+        };
+        for (name, field_type, required) in fields {
+            match members.get(name) {
+                Some(member_value) => self.matches(
+                    field_type,
+                    member_value,
+                    &member(path, name),
+                    direction,
+                    errors,
+                ),
+                None if required => {
+                    errors.push((path.to_string(), format!("missing required field `{name}`")))
+                }
+                None => {}
+            }
+        }
+    }
+}
+
+/// `one of "a", "b"`, naming the values an enum allows.
+fn one_of(values: &[&str]) -> String {
+    let quoted: Vec<String> = values
+        .iter()
+        .map(|value| Value::from(*value).to_string())
+        .collect();
+    format!("one of {}", quoted.join(", "))
+}
+
+/// `path.key`, or `path["key"]` when the key isn't an identifier.
+fn member(path: &str, key: &str) -> String {
+    let identifier = !key.is_empty()
+        && !key.starts_with(|c: char| c.is_ascii_digit())
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if identifier {
+        format!("{path}.{key}")
+    } else {
+        format!("{path}[{}]", Value::from(key))
+    }
+}
+
+/// What a JSON value is, for messages: `a string ("x")`, `an object`, ...
+fn describe(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_string(),
+        Value::Bool(b) => format!("a boolean ({b})"),
+        Value::Number(n) => format!("a number ({n})"),
+        Value::String(s) => format!("a string ({})", Value::from(s.as_str())),
+        Value::Array(_) => "an array".to_string(),
+        Value::Object(_) => "an object".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mismatches(yaml: &str) -> Vec<String> {
+        let spec = crate::parse::from_yaml(yaml).unwrap();
+        let ir = crate::transform::transform(&spec).unwrap();
+        check_examples(&spec, &ir)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn broken_examples_are_reported_with_their_paths() {
+        let found = mismatches(include_str!("../tests/fixtures/examples.yaml"));
+        assert_eq!(
+            found,
+            vec![
+                "POST /pets request body (application/json) example `broken` at `$`: missing required field `name`",
+                "POST /pets request body (application/json) example `broken` at `$.status`: expected one of \"available\", \"pending\", \"sold\", found a string (\"lost\")",
+                "POST /pets request body (application/json) example `broken` at `$.tags[1]`: expected a string, found a number (7)",
+            ]
+        );
+    }
+
+    #[test]
+    fn read_only_fields_are_optional_in_requests() {
+        let found = mismatches(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1" }
+paths:
+  /things:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/Thing" }
+            example: { name: "a" }
+      responses:
+        "200":
+          description: OK
+          content:
+            application/json:
+              schema: { $ref: "#/components/schemas/Thing" }
+              example: { name: "a" }
+components:
+  schemas:
+    Thing:
+      type: object
+      required: [id, name]
+      properties:
+        id: { type: integer, readOnly: true }
+        name: { type: string }
+"##,
+        );
+        assert_eq!(
+            found,
+            vec![
+                "POST /things response 200 (application/json) example at `$`: missing required field `id`"
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_and_parameter_examples_are_checked() {
+        let found = mismatches(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1" }
+paths:
+  /things:
+    get:
+      parameters:
+        - name: limit
+          in: query
+          schema: { type: integer }
+          example: ten
+      responses:
+        "204": { description: Empty }
+components:
+  schemas:
+    Tag:
+      type: object
+      properties:
+        label: { type: string }
+        "x-weight": { oneOf: [{ type: integer }, { type: "null" }] }
+        size: { type: string, enum: [small, large] }
+      example: { label: "a", "x-weight": 1.5, size: medium }
+"##,
+        );
+        assert_eq!(
+            found,
+            vec![
+                "schema Tag example at `$[\"x-weight\"]`: expected a value matching one of the union's variants, found a number (1.5)",
+                "schema Tag example at `$.size`: expected one of \"small\", \"large\", found a string (\"medium\")",
+                "GET /things parameter limit example at `$`: expected an integer, found a string (\"ten\")",
+            ]
+        );
+    }
+
+    #[test]
+    fn integer_enum_examples_are_not_checked_against_string_values() {
+        let found = mismatches(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1" }
+paths:
+  /logs:
+    get:
+      parameters:
+        - name: level
+          in: query
+          schema: { $ref: "#/components/schemas/Level" }
+          example: 2
+      responses:
+        "204": { description: Empty }
+components:
+  schemas:
+    Level:
+      type: integer
+      enum: [1, 2, 3]
+      example: 1
+"##,
+        );
+        assert!(found.is_empty(), "{found:?}");
+    }
+}
@@ -0,0 +1,145 @@
+use std::path::Path;
+
+use glob::Pattern;
+
+use crate::{GeneratedFile, GeneratorError};
+
+/// Drop emitted files whose path matches any of the `exclude` glob patterns.
+///
+/// Returns a warning for every pattern that matched nothing and for every
+/// kept file that still imports an excluded one.
+pub fn exclude_files(
+    files: &mut Vec<GeneratedFile>,
+    exclude: &[String],
+) -> Result<Vec<String>, GeneratorError> {
+    let patterns = exclude
+        .iter()
+        .map(|raw| {
+            Pattern::new(raw)
+                .map_err(|e| GeneratorError::Other(format!("invalid exclude pattern `{raw}`: {e}")))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut warnings = Vec::new();
+    let mut excluded = Vec::new();
+    for (raw, pattern) in exclude.iter().zip(&patterns) {
+        if !files.iter().any(|f| pattern.matches(&f.path)) {
+            warnings.push(format!(
+                "exclude pattern `{raw}` matched no generated files"
+            ));
+        }
+    }
+    files.retain(|f| {
+        let keep = !patterns.iter().any(|p| p.matches(&f.path));
+        if !keep {
+            excluded.push(f.path.clone());
+        }
+        keep
+    });
+
+    for path in &excluded {
+        for file in files.iter().filter(|f| imports(f, path)) {
+            warnings.push(format!(
+                "excluded `{path}` is still imported by `{}`",
+                file.path
+            ));
+        }
+    }
+
+    Ok(warnings)
+}
+
+/// Whether `file` imports the module at `target` from the same directory,
+/// judged by a plain search for TypeScript (`"./sse"`) or Python
+/// (`from sse import`) import syntax.
+fn imports(file: &GeneratedFile, target: &str) -> bool {
+    let (file_path, target_path) = (Path::new(&file.path), Path::new(target));
+    if file_path.parent() != target_path.parent() {
+        return false;
+    }
+    let Some(stem) = target_path.file_stem().and_then(|s| s.to_str()) else {
+        return false;
+    };
+    let content = &file.content;
+    content.contains(&format!("\"./{stem}\""))
+        || content.contains(&format!("\"./{stem}.js\""))
+        || content.contains(&format!("from {stem} import"))
+        || content.contains(&format!("from .{stem} import"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str, content: &str) -> GeneratedFile {
+        GeneratedFile {
+            path: path.to_string(),
+            content: content.to_string(),
+        }
+    }
+
+    fn node_files() -> Vec<GeneratedFile> {
+        vec![
+            file("src/types.ts", "export interface Pet {}\n"),
+            file("src/sse.ts", "export async function* streamSse() {}\n"),
+            file(
+                "src/client.ts",
+                "import type { Pet } from \"./types\";\nimport { streamSse } from \"./sse\";\n",
+            ),
+            file("src/index.ts", "export * from \"./types\";\n"),
+            file("package.json", "{}\n"),
+        ]
+    }
+
+    #[test]
+    fn test_exclude_sse() {
+        let mut files = node_files();
+        let warnings = exclude_files(&mut files, &["src/sse.ts".to_string()]).unwrap();
+
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "src/types.ts",
+                "src/client.ts",
+                "src/index.ts",
+                "package.json"
+            ]
+        );
+        assert_eq!(
+            warnings,
+            ["excluded `src/sse.ts` is still imported by `src/client.ts`"]
+        );
+    }
+
+    #[test]
+    fn test_exclude_glob_and_unmatched() {
+        let mut files = node_files();
+        let warnings = exclude_files(
+            &mut files,
+            &["**/*.json".to_string(), "src/client.test.ts".to_string()],
+        )
+        .unwrap();
+
+        assert!(files.iter().all(|f| f.path != "package.json"));
+        assert_eq!(files.len(), 4);
+        assert_eq!(
+            warnings,
+            ["exclude pattern `src/client.test.ts` matched no generated files"]
+        );
+    }
+
+    #[test]
+    fn test_python_import_detection() {
+        let routes = file("routes.py", "from sse import sse_response\n");
+        assert!(imports(&routes, "sse.py"));
+        assert!(!imports(&routes, "models.py"));
+        assert!(!imports(&routes, "src/sse.py"));
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let mut files = node_files();
+        assert!(exclude_files(&mut files, &["src/[".to_string()]).is_err());
+    }
+}
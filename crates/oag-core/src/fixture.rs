@@ -0,0 +1,234 @@
+//! Cut a spec down to a few operations, for bug reports and generator tests.
+//!
+//! [`extract_operations`] keeps the named operations and every component they
+//! reach through `$ref`s (or discriminator mappings and security
+//! requirements), so the result is a minimal spec that still parses and
+//! transforms the same way for those operations.
+
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::error::FixtureError;
+use crate::parse::components::Components;
+use crate::parse::operation::{Operation, PathItem};
+use crate::parse::spec::OpenApiSpec;
+
+/// A copy of `spec` with only the operations whose `operationId` is in
+/// `operation_ids` (from `paths` and `webhooks`), and only the components,
+/// security schemes and tags they use. `openapi`, `info`, `servers` and the
+/// top-level `security` are kept as they are.
+pub fn extract_operations(
+    spec: &OpenApiSpec,
+    operation_ids: &[String],
+) -> Result<OpenApiSpec, FixtureError> {
+    let paths = retain_operations(&spec.paths, operation_ids);
+    let webhooks = retain_operations(&spec.webhooks, operation_ids);
+    let kept: Vec<&Operation> = paths
+        .values()
+        .chain(webhooks.values())
+        .flat_map(|item| item.operations().map(|(_, op)| op))
+        .collect();
+
+    let missing: Vec<String> = operation_ids
+        .iter()
+        .filter(|id| !kept.iter().any(|op| op.operation_id.as_ref() == Some(id)))
+        .cloned()
+        .collect();
+    if !missing.is_empty() {
+        return Err(FixtureError::UnknownOperations(missing));
+    }
+
+    let source = spec.components.clone().unwrap_or_default();
+    let mut used = HashSet::new();
+    let mut queue = Vec::new();
+    collect_refs(&to_value(&paths), &mut queue);
+    collect_refs(&to_value(&webhooks), &mut queue);
+    while let Some(reference) = queue.pop() {
+        let Some((kind, name)) = component_ref(&reference) else {
+            continue;
+        };
+        if used.contains(&(kind.clone(), name.clone())) {
+            continue;
+        }
+        let component = match kind.as_str() {
+            "schemas" => source.schemas.get(&name).map(to_value),
+            "responses" => source.responses.get(&name).map(to_value),
+            "parameters" => source.parameters.get(&name).map(to_value),
+            "requestBodies" => source.request_bodies.get(&name).map(to_value),
+            "links" => source.links.get(&name).map(to_value),
+            _ => None,
+        }
+        .ok_or_else(|| FixtureError::MissingComponent(reference.clone()))?;
+        collect_refs(&component, &mut queue);
+        used.insert((kind, name));
+    }
+
+    let schemes: HashSet<&String> = kept
+        .iter()
+        .filter_map(|op| op.security.as_ref())
+        .chain(&spec.security)
+        .flatten()
+        .flat_map(|requirement| requirement.keys())
+        .collect();
+    let components = Components {
+        schemas: retain_used(&source.schemas, "schemas", &used),
+        responses: retain_used(&source.responses, "responses", &used),
+        parameters: retain_used(&source.parameters, "parameters", &used),
+        request_bodies: retain_used(&source.request_bodies, "requestBodies", &used),
+        links: retain_used(&source.links, "links", &used),
+        security_schemes: source
+            .security_schemes
+            .into_iter()
+            .filter(|(name, _)| schemes.contains(name))
+            .collect(),
+    };
+
+    let tag_names: HashSet<&String> = kept.iter().flat_map(|op| &op.tags).collect();
+    let tags = spec
+        .tags
+        .iter()
+        .filter(|tag| tag_names.contains(&tag.name))
+        .cloned()
+        .collect();
+    Ok(OpenApiSpec {
+        openapi: spec.openapi.clone(),
+        info: spec.info.clone(),
+        servers: spec.servers.clone(),
+        paths,
+        webhooks,
+        components: (components != Components::default()).then_some(components),
+        tags,
+        security: spec.security.clone(),
+    })
+}
+
+/// `items` with only the operations in `operation_ids`, dropping items left
+/// without any.
+fn retain_operations(
+    items: &IndexMap<String, PathItem>,
+    operation_ids: &[String],
+) -> IndexMap<String, PathItem> {
+    items
+        .iter()
+        .filter_map(|(key, item)| {
+            let mut item = item.clone();
+            for slot in [
+                &mut item.get,
+                &mut item.post,
+                &mut item.put,
+                &mut item.delete,
+                &mut item.patch,
+                &mut item.options,
+                &mut item.head,
+                &mut item.trace,
+            ] {
+                let keep = slot
+                    .as_ref()
+                    .and_then(|op| op.operation_id.as_ref())
+                    .is_some_and(|id| operation_ids.contains(id));
+                if !keep {
+                    *slot = None;
+                }
+            }
+            let has_operations = item.operations().next().is_some();
+            has_operations.then(|| (key.clone(), item))
+        })
+        .collect()
+}
+
+/// The entries of the component map `kind` that are in `used`, in spec order.
+fn retain_used<T: Clone>(
+    map: &IndexMap<String, T>,
+    kind: &str,
+    used: &HashSet<(String, String)>,
+) -> IndexMap<String, T> {
+    map.iter()
+        .filter(|(name, _)| used.contains(&(kind.to_string(), name.to_string())))
+        .map(|(name, value)| (name.clone(), value.clone()))
+        .collect()
+}
+
+/// Push every `$ref` in `value`, and every discriminator `mapping` target,
+/// onto `refs`.
+fn collect_refs(value: &Value, refs: &mut Vec<String>) {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                refs.push(reference.clone());
+            }
+            if let Some(Value::Object(mapping)) = map.get("mapping") {
+                refs.extend(
+                    mapping
+                        .values()
+                        .filter_map(|v| v.as_str().map(String::from)),
+                );
+            }
+            for value in map.values() {
+                collect_refs(value, refs);
+            }
+        }
+        Value::Array(items) => {
+            for value in items {
+                collect_refs(value, refs);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Split `#/components/{kind}/{name}` into its kind and (unescaped) name.
+fn component_ref(reference: &str) -> Option<(String, String)> {
+    let (kind, name) = reference.strip_prefix("#/components/")?.split_once('/')?;
+    Some((kind.to_string(), name.replace("~1", "/").replace("~0", "~")))
+}
+
+fn to_value(value: &impl Serialize) -> Value {
+    serde_json::to_value(value).expect("parsed specs always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse;
+
+    const ANTHROPIC: &str = include_str!("../tests/fixtures/anthropic-messages.yaml");
+
+    fn ids(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn keeps_only_what_the_operations_reach() {
+        let spec = parse::from_yaml(ANTHROPIC).unwrap();
+        let fixture = extract_operations(&spec, &ids(&["listModels", "getModel"])).unwrap();
+
+        assert_eq!(fixture.openapi, spec.openapi);
+        assert_eq!(fixture.info, spec.info);
+        let operations: Vec<&str> = fixture
+            .paths
+            .values()
+            .flat_map(|item| item.operations())
+            .filter_map(|(_, op)| op.operation_id.as_deref())
+            .collect();
+        assert_eq!(operations, ["listModels", "getModel"]);
+        let components = fixture.components.as_ref().unwrap();
+        let schemas: Vec<&str> = components.schemas.keys().map(String::as_str).collect();
+        assert_eq!(schemas, ["ModelInfo", "ModelListResponse"]);
+        assert!(components.parameters.contains_key("AnthropicVersion"));
+
+        // Round-trips through YAML and still transforms.
+        let reparsed = parse::from_yaml(&parse::to_yaml(&fixture).unwrap()).unwrap();
+        let ir = crate::transform::transform(&reparsed).unwrap();
+        assert_eq!(ir.operations.len(), 2);
+    }
+
+    #[test]
+    fn unknown_operations_are_an_error() {
+        let spec = parse::from_yaml(ANTHROPIC).unwrap();
+        let err = extract_operations(&spec, &ids(&["listModels", "nope"])).unwrap_err();
+        assert_eq!(err.to_string(), "no operation with id `nope` in the spec");
+    }
+}
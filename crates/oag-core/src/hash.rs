@@ -0,0 +1,54 @@
+//! Content hashing for incremental generation.
+//!
+//! [`hash_ir`] fingerprints an [`IrSpec`] so callers can tell whether a spec
+//! changed between runs without diffing the generated files.
+
+use sha2::{Digest, Sha256};
+
+use crate::ir::IrSpec;
+
+/// SHA-256 of the IR's canonical form: JSON with every object's keys sorted.
+///
+/// Equal IRs hash equally regardless of how they were built; the raw spec is
+/// included, so edits to fields the IR doesn't model still change the hash.
+pub fn hash_ir(spec: &IrSpec) -> [u8; 32] {
+    // `serde_json::Map` is a `BTreeMap`, so going through `Value` sorts the keys.
+    let canonical = serde_json::to_value(spec)
+        .expect("the IR always serializes")
+        .to_string();
+    sha256(canonical.as_bytes())
+}
+
+/// Lowercase hex encoding of a digest.
+pub fn to_hex(digest: &[u8]) -> String {
+    digest.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// SHA-256 of `data`.
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn petstore_ir() -> IrSpec {
+        let spec =
+            crate::parse::from_yaml(include_str!("../tests/fixtures/petstore-3.2.yaml")).unwrap();
+        crate::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn hash_ir_is_deterministic() {
+        assert_eq!(hash_ir(&petstore_ir()), hash_ir(&petstore_ir()));
+    }
+
+    #[test]
+    fn hash_ir_changes_with_the_ir() {
+        let ir = petstore_ir();
+        let mut renamed = ir.clone();
+        renamed.info.title.push_str(" v2");
+        assert_ne!(hash_ir(&ir), hash_ir(&renamed));
+    }
+}
@@ -0,0 +1,147 @@
+//! The operations index (`index_json: true`): `oag.operations.json`, a
+//! machine-readable list of the operations and schemas a generator's output
+//! exposes, for tools like API portals that shouldn't parse generated code.
+//!
+//! The index is built from the same IR as the code next to it, so names and
+//! types always match what was generated. Its format is versioned by
+//! [`FORMAT_VERSION`]: within a version, fields are only ever added, never
+//! renamed, removed or given a different meaning, and consumers should
+//! ignore fields they don't know. Array order follows the spec and isn't
+//! part of the format.
+
+use serde::Serialize;
+
+use crate::ir::diff::type_name;
+use crate::ir::{IrOperation, IrParameterLocation, IrReturnType, IrSchema, IrSpec};
+
+/// Where the index is written, relative to the generator's output directory.
+pub const INDEX_FILE: &str = "oag.operations.json";
+
+/// The `version` of the format [`operations_index`] writes.
+pub const FORMAT_VERSION: u32 = 1;
+
+/// The contents of `oag.operations.json` for `ir`: pretty-printed JSON with a
+/// trailing newline.
+pub fn operations_index(ir: &IrSpec) -> String {
+    let index = OperationsIndex {
+        version: FORMAT_VERSION,
+        operations: ir
+            .operations
+            .iter()
+            .enumerate()
+            .map(|(i, op)| IndexOperation::new(ir, i, op))
+            .collect(),
+        schemas: ir.schemas.iter().map(IndexSchema::new).collect(),
+    };
+    let mut json = serde_json::to_string_pretty(&index).expect("the index always serializes");
+    json.push('\n');
+    json
+}
+
+#[derive(Serialize)]
+struct OperationsIndex {
+    version: u32,
+    operations: Vec<IndexOperation>,
+    schemas: Vec<IndexSchema>,
+}
+
+#[derive(Serialize)]
+struct IndexOperation {
+    /// The generated method name.
+    name: String,
+    method: &'static str,
+    /// The path as written in the spec.
+    path: String,
+    /// The module (tag group) the operation is generated into.
+    module: Option<String>,
+    summary: Option<String>,
+    deprecated: bool,
+    request_body_type: Option<String>,
+    /// `standard`, `sse` or `void`.
+    return_kind: &'static str,
+    /// The response type, or each event's type for `sse`; `None` for `void`.
+    return_type: Option<String>,
+    parameters: Vec<IndexParameter>,
+}
+
+impl IndexOperation {
+    fn new(ir: &IrSpec, index: usize, op: &IrOperation) -> Self {
+        let (return_kind, return_type) = match &op.return_type {
+            IrReturnType::Standard(resp) => ("standard", Some(type_name(&resp.response_type))),
+            IrReturnType::Sse(sse) => (
+                "sse",
+                Some(
+                    sse.event_type_name
+                        .clone()
+                        .unwrap_or_else(|| type_name(&sse.event_type)),
+                ),
+            ),
+            IrReturnType::Void => ("void", None),
+        };
+        Self {
+            name: op.name.camel_case.clone(),
+            method: op.method.as_str(),
+            path: op.raw_path.clone(),
+            module: ir
+                .modules
+                .iter()
+                .find(|module| module.operations.contains(&index))
+                .map(|module| module.name.original.clone()),
+            summary: op.summary.clone(),
+            deprecated: op.deprecated,
+            request_body_type: op
+                .request_body
+                .as_ref()
+                .map(|body| type_name(&body.body_type)),
+            return_kind,
+            return_type,
+            parameters: op
+                .parameters
+                .iter()
+                .map(|param| IndexParameter {
+                    name: param.original_name.clone(),
+                    location: match param.location {
+                        IrParameterLocation::Path => "path",
+                        IrParameterLocation::Query => "query",
+                        IrParameterLocation::Header => "header",
+                        IrParameterLocation::Cookie => "cookie",
+                    },
+                    param_type: type_name(&param.param_type),
+                    required: param.required,
+                })
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct IndexParameter {
+    /// The wire name, as written in the spec.
+    name: String,
+    #[serde(rename = "in")]
+    location: &'static str,
+    #[serde(rename = "type")]
+    param_type: String,
+    required: bool,
+}
+
+#[derive(Serialize)]
+struct IndexSchema {
+    name: String,
+    /// `object`, `enum`, `alias` or `union`.
+    kind: &'static str,
+}
+
+impl IndexSchema {
+    fn new(schema: &IrSchema) -> Self {
+        Self {
+            name: schema.name().pascal_case.clone(),
+            kind: match schema {
+                IrSchema::Object(_) => "object",
+                IrSchema::Enum(_) => "enum",
+                IrSchema::Alias(_) => "alias",
+                IrSchema::Union(_) => "union",
+            },
+        }
+    }
+}
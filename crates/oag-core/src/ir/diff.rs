@@ -0,0 +1,1014 @@
+//! Comparison of two IRs of the same API.
+//!
+//! [`diff`] reports how the generated surface moves between two versions of a
+//! spec — operations, parameters, bodies, schemas, fields, enum variants — and
+//! whether each change breaks code written against the old client.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::Serialize;
+
+use super::operations::{IrOperation, IrParameter, IrParameterLocation, IrReturnType};
+use super::schemas::{IrMapKey, IrSchema, IrType};
+use super::types::IrSpec;
+
+/// Whether a change breaks code written against the old generated client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Breaking,
+    NonBreaking,
+}
+
+/// What a [`Change`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    OperationAdded,
+    OperationRemoved,
+    OperationRenamed,
+    OperationMoved,
+    ParameterAdded,
+    ParameterRemoved,
+    ParameterChanged,
+    BodyChanged,
+    ResponseChanged,
+    SchemaAdded,
+    SchemaRemoved,
+    SchemaChanged,
+    FieldAdded,
+    FieldRemoved,
+    FieldChanged,
+    EnumVariantAdded,
+    EnumVariantRemoved,
+}
+
+/// One difference between the old and the new IR.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub severity: Severity,
+    /// Where the change is, e.g. `GET /pets/{petId} (getPet)` or `Pet.name`.
+    pub location: String,
+    pub message: String,
+}
+
+/// Every change between two IRs, operations first, then schemas.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SpecDiff {
+    pub changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|c| c.severity == Severity::Breaking)
+    }
+
+    pub fn non_breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|c| c.severity == Severity::NonBreaking)
+    }
+
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        severity: Severity,
+        location: impl Into<String>,
+        message: impl Into<String>,
+    ) {
+        self.changes.push(Change {
+            kind,
+            severity,
+            location: location.into(),
+            message: message.into(),
+        });
+    }
+
+    /// A markdown changelog section headed `## {heading}`, with the changes
+    /// grouped under `Added`, `Removed` and `Changed`. Breaking changes are
+    /// flagged in bold.
+    pub fn to_markdown(&self, heading: &str) -> String {
+        let mut out = format!("## {heading}\n");
+        for (title, kinds) in [
+            (
+                "Added",
+                [ChangeKind::OperationAdded, ChangeKind::SchemaAdded],
+            ),
+            (
+                "Removed",
+                [ChangeKind::OperationRemoved, ChangeKind::SchemaRemoved],
+            ),
+        ] {
+            let changes: Vec<&Change> = self
+                .changes
+                .iter()
+                .filter(|c| kinds.contains(&c.kind))
+                .collect();
+            push_markdown_section(&mut out, title, &changes);
+        }
+        let changed: Vec<&Change> = self
+            .changes
+            .iter()
+            .filter(|c| {
+                !matches!(
+                    c.kind,
+                    ChangeKind::OperationAdded
+                        | ChangeKind::SchemaAdded
+                        | ChangeKind::OperationRemoved
+                        | ChangeKind::SchemaRemoved
+                )
+            })
+            .collect();
+        push_markdown_section(&mut out, "Changed", &changed);
+        out
+    }
+}
+
+fn push_markdown_section(out: &mut String, title: &str, changes: &[&Change]) {
+    if changes.is_empty() {
+        return;
+    }
+    out.push_str(&format!("\n### {title}\n\n"));
+    for change in changes {
+        let flag = match change.severity {
+            Severity::Breaking => "**Breaking:** ",
+            Severity::NonBreaking => "",
+        };
+        out.push_str(&format!(
+            "- {flag}`{}`: {}\n",
+            change.location, change.message
+        ));
+    }
+}
+
+impl fmt::Display for SpecDiff {
+    /// Breaking changes, then non-breaking ones, one per line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No changes to the generated surface.");
+        }
+        for (title, changes) in [
+            ("Breaking", self.breaking().collect::<Vec<_>>()),
+            ("Non-breaking", self.non_breaking().collect()),
+        ] {
+            if changes.is_empty() {
+                continue;
+            }
+            writeln!(f, "{title} changes ({}):", changes.len())?;
+            for change in changes {
+                writeln!(f, "  {}: {}", change.location, change.message)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compare `old` against `new`.
+///
+/// Operations are matched by method and path (ignoring path parameter names),
+/// then by name, so a renamed operation and a moved one are both recognized.
+/// Schemas and fields are matched by name. Since a schema can be sent as well
+/// as received, any change to an existing field's type or required-ness counts
+/// as breaking.
+pub fn diff(old: &IrSpec, new: &IrSpec) -> SpecDiff {
+    let mut diff = SpecDiff::default();
+    diff_operations(&mut diff, "", &old.operations, &new.operations);
+    diff_operations(&mut diff, "webhook ", &old.webhooks, &new.webhooks);
+    diff_schemas(&mut diff, &old.schemas, &new.schemas);
+    diff
+}
+
+/// `GET /pets/{}`: the method and path with parameter names blanked out.
+fn route_key(op: &IrOperation) -> String {
+    let mut key = format!("{} ", op.method.as_str());
+    let mut in_param = false;
+    for c in op.raw_path.chars() {
+        match c {
+            '{' => {
+                in_param = true;
+                key.push_str("{}");
+            }
+            '}' => in_param = false,
+            _ if !in_param => key.push(c),
+            _ => {}
+        }
+    }
+    key
+}
+
+fn op_location(prefix: &str, op: &IrOperation) -> String {
+    format!(
+        "{prefix}{} {} ({})",
+        op.method.as_str(),
+        op.raw_path,
+        op.name.original
+    )
+}
+
+fn diff_operations(diff: &mut SpecDiff, prefix: &str, old: &[IrOperation], new: &[IrOperation]) {
+    let mut matched_new = HashSet::new();
+    let mut pairs = Vec::new();
+    let mut removed = Vec::new();
+
+    for old_op in old {
+        let key = route_key(old_op);
+        let unmatched = |pred: &dyn Fn(&IrOperation) -> bool| {
+            (0..new.len()).find(|i| !matched_new.contains(i) && pred(&new[*i]))
+        };
+        let found = unmatched(&|n| route_key(n) == key)
+            .or_else(|| unmatched(&|n| n.name.original == old_op.name.original));
+        match found {
+            Some(i) => {
+                matched_new.insert(i);
+                pairs.push((old_op, &new[i]));
+            }
+            None => removed.push(old_op),
+        }
+    }
+
+    for op in removed {
+        diff.push(
+            ChangeKind::OperationRemoved,
+            Severity::Breaking,
+            op_location(prefix, op),
+            "operation removed",
+        );
+    }
+    for (i, op) in new.iter().enumerate() {
+        if !matched_new.contains(&i) {
+            diff.push(
+                ChangeKind::OperationAdded,
+                Severity::NonBreaking,
+                op_location(prefix, op),
+                "operation added",
+            );
+        }
+    }
+    for (old_op, new_op) in pairs {
+        diff_operation(diff, prefix, old_op, new_op);
+    }
+}
+
+fn diff_operation(diff: &mut SpecDiff, prefix: &str, old: &IrOperation, new: &IrOperation) {
+    let location = op_location(prefix, new);
+    if old.name.original != new.name.original {
+        diff.push(
+            ChangeKind::OperationRenamed,
+            Severity::Breaking,
+            &location,
+            format!(
+                "renamed from `{}` to `{}`",
+                old.name.original, new.name.original
+            ),
+        );
+    }
+    if route_key(old) != route_key(new) {
+        diff.push(
+            ChangeKind::OperationMoved,
+            Severity::NonBreaking,
+            &location,
+            format!("moved from {} {}", old.method.as_str(), old.raw_path),
+        );
+    }
+
+    // Path parameters are positional, so they match by position: renaming
+    // `{petId}` to `{id}` changes nothing for callers.
+    let path_index = |op: &IrOperation, param: &IrParameter| {
+        op.parameters
+            .iter()
+            .filter(|p| p.location == IrParameterLocation::Path)
+            .position(|p| std::ptr::eq(p, param))
+    };
+    let same_param = |new_param: &IrParameter, old_param: &IrParameter| {
+        new_param.location == old_param.location
+            && match new_param.location {
+                IrParameterLocation::Path => {
+                    path_index(new, new_param) == path_index(old, old_param)
+                }
+                _ => new_param.original_name == old_param.original_name,
+            }
+    };
+    for param in &old.parameters {
+        if !new.parameters.iter().any(|p| same_param(p, param)) {
+            diff.push(
+                ChangeKind::ParameterRemoved,
+                Severity::Breaking,
+                &location,
+                format!("{} removed", param_label(param)),
+            );
+        }
+    }
+    for param in &new.parameters {
+        match old.parameters.iter().find(|p| same_param(param, p)) {
+            None => diff.push(
+                ChangeKind::ParameterAdded,
+                if param.required {
+                    Severity::Breaking
+                } else {
+                    Severity::NonBreaking
+                },
+                &location,
+                format!(
+                    "{} {} added",
+                    if param.required {
+                        "required"
+                    } else {
+                        "optional"
+                    },
+                    param_label(param)
+                ),
+            ),
+            Some(old_param) => {
+                if old_param.param_type != param.param_type {
+                    diff.push(
+                        ChangeKind::ParameterChanged,
+                        Severity::Breaking,
+                        &location,
+                        format!(
+                            "{} type changed from {} to {}",
+                            param_label(param),
+                            type_name(&old_param.param_type),
+                            type_name(&param.param_type)
+                        ),
+                    );
+                }
+                if old_param.required != param.required {
+                    diff.push(
+                        ChangeKind::ParameterChanged,
+                        required_flip(param.required),
+                        &location,
+                        format!(
+                            "{} is now {}",
+                            param_label(param),
+                            required_word(param.required)
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    match (&old.request_body, &new.request_body) {
+        (None, None) => {}
+        (Some(_), None) => diff.push(
+            ChangeKind::BodyChanged,
+            Severity::Breaking,
+            &location,
+            "request body removed",
+        ),
+        (None, Some(body)) => diff.push(
+            ChangeKind::BodyChanged,
+            if body.required {
+                Severity::Breaking
+            } else {
+                Severity::NonBreaking
+            },
+            &location,
+            format!(
+                "{} request body added ({})",
+                required_word(body.required),
+                type_name(&body.body_type)
+            ),
+        ),
+        (Some(old_body), Some(body)) => {
+            if old_body.body_type != body.body_type {
+                diff.push(
+                    ChangeKind::BodyChanged,
+                    Severity::Breaking,
+                    &location,
+                    format!(
+                        "request body type changed from {} to {}",
+                        type_name(&old_body.body_type),
+                        type_name(&body.body_type)
+                    ),
+                );
+            }
+            if old_body.content_type != body.content_type {
+                diff.push(
+                    ChangeKind::BodyChanged,
+                    Severity::Breaking,
+                    &location,
+                    format!(
+                        "request body content type changed from `{}` to `{}`",
+                        old_body.content_type, body.content_type
+                    ),
+                );
+            }
+            if old_body.required != body.required {
+                diff.push(
+                    ChangeKind::BodyChanged,
+                    required_flip(body.required),
+                    &location,
+                    format!("request body is now {}", required_word(body.required)),
+                );
+            }
+        }
+    }
+
+    let (old_returns, new_returns) = (return_name(&old.return_type), return_name(&new.return_type));
+    if old_returns != new_returns {
+        diff.push(
+            ChangeKind::ResponseChanged,
+            Severity::Breaking,
+            &location,
+            format!("response changed from {old_returns} to {new_returns}"),
+        );
+    }
+}
+
+fn param_label(param: &IrParameter) -> String {
+    let location = match param.location {
+        IrParameterLocation::Path => "path",
+        IrParameterLocation::Query => "query",
+        IrParameterLocation::Header => "header",
+        IrParameterLocation::Cookie => "cookie",
+    };
+    format!("{location} parameter `{}`", param.original_name)
+}
+
+fn required_word(required: bool) -> &'static str {
+    if required { "required" } else { "optional" }
+}
+
+/// Making an input required breaks callers that omit it; relaxing it doesn't.
+fn required_flip(now_required: bool) -> Severity {
+    if now_required {
+        Severity::Breaking
+    } else {
+        Severity::NonBreaking
+    }
+}
+
+fn return_name(return_type: &IrReturnType) -> String {
+    match return_type {
+        IrReturnType::Standard(resp) => type_name(&resp.response_type),
+        IrReturnType::Sse(sse) => {
+            let events = sse
+                .event_type_name
+                .clone()
+                .unwrap_or_else(|| type_name(&sse.event_type));
+            match &sse.json_response {
+                Some(json) => format!("stream of {events} or {}", type_name(&json.response_type)),
+                None => format!("stream of {events}"),
+            }
+        }
+        IrReturnType::Void => "void".to_string(),
+    }
+}
+
+fn schema_kind(schema: &IrSchema) -> &'static str {
+    match schema {
+        IrSchema::Object(_) => "object",
+        IrSchema::Enum(_) => "enum",
+        IrSchema::Alias(_) => "alias",
+        IrSchema::Union(_) => "union",
+    }
+}
+
+fn diff_schemas(diff: &mut SpecDiff, old: &[IrSchema], new: &[IrSchema]) {
+    let find = |schemas: &[IrSchema], name: &str| {
+        schemas.iter().position(|s| s.name().pascal_case == name)
+    };
+
+    for schema in old {
+        if find(new, &schema.name().pascal_case).is_none() {
+            diff.push(
+                ChangeKind::SchemaRemoved,
+                Severity::Breaking,
+                &schema.name().pascal_case,
+                "schema removed",
+            );
+        }
+    }
+    for schema in new {
+        let name = &schema.name().pascal_case;
+        let Some(i) = find(old, name) else {
+            diff.push(
+                ChangeKind::SchemaAdded,
+                Severity::NonBreaking,
+                name,
+                "schema added",
+            );
+            continue;
+        };
+        match (&old[i], schema) {
+            (IrSchema::Object(old_obj), IrSchema::Object(obj)) => {
+                for field in &old_obj.fields {
+                    if !obj
+                        .fields
+                        .iter()
+                        .any(|f| f.original_name == field.original_name)
+                    {
+                        diff.push(
+                            ChangeKind::FieldRemoved,
+                            Severity::Breaking,
+                            format!("{name}.{}", field.original_name),
+                            "field removed",
+                        );
+                    }
+                }
+                for field in &obj.fields {
+                    let location = format!("{name}.{}", field.original_name);
+                    let Some(old_field) = old_obj
+                        .fields
+                        .iter()
+                        .find(|f| f.original_name == field.original_name)
+                    else {
+                        diff.push(
+                            ChangeKind::FieldAdded,
+                            if field.required {
+                                Severity::Breaking
+                            } else {
+                                Severity::NonBreaking
+                            },
+                            location,
+                            format!("{} field added", required_word(field.required)),
+                        );
+                        continue;
+                    };
+                    if old_field.field_type != field.field_type {
+                        diff.push(
+                            ChangeKind::FieldChanged,
+                            Severity::Breaking,
+                            &location,
+                            format!(
+                                "type changed from {} to {}",
+                                type_name(&old_field.field_type),
+                                type_name(&field.field_type)
+                            ),
+                        );
+                    }
+                    if old_field.required != field.required {
+                        diff.push(
+                            ChangeKind::FieldChanged,
+                            Severity::Breaking,
+                            &location,
+                            format!("now {}", required_word(field.required)),
+                        );
+                    }
+                    if !old_field.deprecated && field.deprecated {
+                        diff.push(
+                            ChangeKind::FieldChanged,
+                            Severity::NonBreaking,
+                            &location,
+                            "now deprecated",
+                        );
+                    }
+                }
+            }
+            (IrSchema::Enum(old_enum), IrSchema::Enum(new_enum)) => {
+                diff_variants(diff, name, &old_enum.variants, &new_enum.variants);
+            }
+            (IrSchema::Union(old_union), IrSchema::Union(union)) => {
+                let names = |variants: &[IrType]| -> Vec<String> {
+                    variants.iter().map(type_name).collect()
+                };
+                diff_variants(
+                    diff,
+                    name,
+                    &names(&old_union.variants),
+                    &names(&union.variants),
+                );
+            }
+            (IrSchema::Alias(old_alias), IrSchema::Alias(alias)) => {
+                if old_alias.target != alias.target {
+                    diff.push(
+                        ChangeKind::SchemaChanged,
+                        Severity::Breaking,
+                        name,
+                        format!(
+                            "type changed from {} to {}",
+                            type_name(&old_alias.target),
+                            type_name(&alias.target)
+                        ),
+                    );
+                }
+            }
+            (old_schema, schema) => diff.push(
+                ChangeKind::SchemaChanged,
+                Severity::Breaking,
+                name,
+                format!(
+                    "changed from {} to {}",
+                    schema_kind(old_schema),
+                    schema_kind(schema)
+                ),
+            ),
+        }
+    }
+}
+
+/// Removed variants break exhaustive handling of values sent to the API;
+/// added ones are non-breaking for client consumers.
+fn diff_variants(diff: &mut SpecDiff, name: &str, old: &[String], new: &[String]) {
+    for variant in old.iter().filter(|v| !new.contains(v)) {
+        diff.push(
+            ChangeKind::EnumVariantRemoved,
+            Severity::Breaking,
+            name,
+            format!("variant `{variant}` removed"),
+        );
+    }
+    for variant in new.iter().filter(|v| !old.contains(v)) {
+        diff.push(
+            ChangeKind::EnumVariantAdded,
+            Severity::NonBreaking,
+            name,
+            format!("variant `{variant}` added"),
+        );
+    }
+}
+
+/// A compact, language-neutral rendering of a type for change messages.
+pub fn type_name(ir_type: &IrType) -> String {
+    match ir_type {
+        IrType::String => "string".to_string(),
+        IrType::StringLiteral(s) => format!("\"{s}\""),
+        IrType::Number => "number".to_string(),
+        IrType::Integer => "integer".to_string(),
+        IrType::Boolean => "boolean".to_string(),
+        IrType::Null => "null".to_string(),
+        IrType::Array(item) => format!("{}[]", type_name(item)),
+        IrType::Object(fields) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|(name, ty, required)| {
+                    let optional = if *required { "" } else { "?" };
+                    format!("{name}{optional}: {}", type_name(ty))
+                })
+                .collect();
+            format!("{{ {} }}", fields.join(", "))
+        }
+        IrType::Map(value, key) => {
+            let key = match key {
+                Some(IrMapKey::Integer) => "integer",
+                _ => "string",
+            };
+            format!("map<{key}, {}>", type_name(value))
+        }
+        IrType::Ref(name) => name.clone(),
+        IrType::Union(variants) => variants
+            .iter()
+            .map(type_name)
+            .collect::<Vec<_>>()
+            .join(" | "),
+        IrType::Intersection(parts) => parts.iter().map(type_name).collect::<Vec<_>>().join(" & "),
+        IrType::Any => "any".to_string(),
+        IrType::Void => "void".to_string(),
+        IrType::DateTime => "date-time".to_string(),
+        IrType::Binary => "binary".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ir::{
+        HttpMethod, IrEnumSchema, IrField, IrInfo, IrObjectSchema, IrRequestBody, IrResponse,
+        NormalizedName,
+    };
+
+    fn name(s: &str) -> NormalizedName {
+        NormalizedName {
+            original: s.to_string(),
+            pascal_case: s[..1].to_uppercase() + &s[1..],
+            camel_case: s.to_string(),
+            snake_case: s.to_lowercase(),
+            screaming_snake: s.to_uppercase(),
+        }
+    }
+
+    fn spec(operations: Vec<IrOperation>, schemas: Vec<IrSchema>) -> IrSpec {
+        IrSpec {
+            info: IrInfo {
+                title: "Test".to_string(),
+                description: None,
+                version: "1".to_string(),
+            },
+            servers: vec![],
+            schemas,
+            operations,
+            webhooks: vec![],
+            modules: vec![],
+            diagnostics: vec![],
+            security: vec![],
+            raw_spec: None,
+        }
+    }
+
+    fn op(op_name: &str, method: HttpMethod, path: &str) -> IrOperation {
+        IrOperation {
+            name: name(op_name),
+            method,
+            raw_path: path.to_string(),
+            normalized_path: path.to_string(),
+            summary: None,
+            description: None,
+            external_docs: None,
+            tags: vec![],
+            parameters: vec![],
+            request_body: None,
+            return_type: IrReturnType::Void,
+            errors: vec![],
+            deprecated: false,
+            is_webhook: false,
+            source_location: None,
+            security: None,
+            cookie_auth: vec![],
+            bearer_auth: None,
+            links: vec![],
+            extensions: Default::default(),
+        }
+    }
+
+    fn query(param: &str, param_type: IrType, required: bool) -> IrParameter {
+        IrParameter {
+            name: name(param),
+            original_name: param.to_string(),
+            location: IrParameterLocation::Query,
+            param_type,
+            required,
+            description: None,
+            content_type: None,
+            pinned_value: None,
+            allow_reserved: false,
+            allow_empty_value: false,
+            example: None,
+            query_object: None,
+            query_array: None,
+        }
+    }
+
+    fn field(field: &str, field_type: IrType, required: bool) -> IrField {
+        IrField {
+            name: name(field),
+            original_name: field.to_string(),
+            field_type,
+            required,
+            description: None,
+            read_only: false,
+            write_only: false,
+            deprecated: false,
+            default: None,
+            number_hints: Default::default(),
+        }
+    }
+
+    fn object(schema: &str, fields: Vec<IrField>) -> IrSchema {
+        IrSchema::Object(IrObjectSchema {
+            name: name(schema),
+            description: None,
+            fields,
+            additional_properties: None,
+            unsupported_keywords: vec![],
+            example: None,
+        })
+    }
+
+    fn status(variants: &[&str]) -> IrSchema {
+        IrSchema::Enum(IrEnumSchema {
+            name: name("Status"),
+            description: None,
+            variants: variants.iter().map(|v| v.to_string()).collect(),
+            unsupported_keywords: vec![],
+            example: None,
+        })
+    }
+
+    fn messages(diff: &SpecDiff, severity: Severity) -> Vec<String> {
+        diff.changes
+            .iter()
+            .filter(|c| c.severity == severity)
+            .map(|c| format!("{}: {}", c.location, c.message))
+            .collect()
+    }
+
+    #[test]
+    fn identical_specs_have_no_changes() {
+        let s = spec(
+            vec![op("listPets", HttpMethod::Get, "/pets")],
+            vec![status(&["a"])],
+        );
+        let d = diff(&s, &s.clone());
+        assert!(d.is_empty());
+        assert_eq!(d.to_string(), "No changes to the generated surface.\n");
+    }
+
+    fn with_path_param(mut op: IrOperation, param: &str) -> IrOperation {
+        op.parameters.push(IrParameter {
+            location: IrParameterLocation::Path,
+            required: true,
+            ..query(param, IrType::String, true)
+        });
+        op
+    }
+
+    #[test]
+    fn operations_are_matched_by_route_then_name() {
+        let old = spec(
+            vec![
+                op("listPets", HttpMethod::Get, "/pets"),
+                with_path_param(op("getPet", HttpMethod::Get, "/pets/{petId}"), "petId"),
+                op("deletePet", HttpMethod::Delete, "/pets/{petId}"),
+                op("ping", HttpMethod::Get, "/ping"),
+            ],
+            vec![],
+        );
+        let new = spec(
+            vec![
+                op("findPets", HttpMethod::Get, "/pets"),
+                with_path_param(op("getPet", HttpMethod::Get, "/pets/{id}"), "id"),
+                op("deletePet", HttpMethod::Post, "/pets/{id}/delete"),
+                op("health", HttpMethod::Get, "/health"),
+            ],
+            vec![],
+        );
+        let d = diff(&old, &new);
+        assert_eq!(
+            messages(&d, Severity::Breaking),
+            [
+                "GET /ping (ping): operation removed",
+                "GET /pets (findPets): renamed from `listPets` to `findPets`",
+            ]
+        );
+        assert_eq!(
+            messages(&d, Severity::NonBreaking),
+            [
+                "GET /health (health): operation added",
+                "POST /pets/{id}/delete (deletePet): moved from DELETE /pets/{petId}",
+            ]
+        );
+    }
+
+    #[test]
+    fn parameter_and_body_changes() {
+        let mut old_op = op("listPets", HttpMethod::Post, "/pets");
+        old_op.parameters = vec![
+            query("limit", IrType::Integer, false),
+            query("status", IrType::String, false),
+            query("cursor", IrType::String, true),
+        ];
+        old_op.request_body = Some(IrRequestBody {
+            body_type: IrType::Ref("Filter".to_string()),
+            required: false,
+            content_type: "application/json".to_string(),
+            description: None,
+            encoding: None,
+            example: None,
+            examples: Default::default(),
+        });
+        old_op.return_type = IrReturnType::Standard(IrResponse {
+            response_type: IrType::Array(Box::new(IrType::Ref("Pet".to_string()))),
+            description: None,
+            content_type: None,
+            example: None,
+            examples: Default::default(),
+            empty_statuses: vec![],
+        });
+
+        let mut new_op = old_op.clone();
+        new_op.parameters = vec![
+            query("limit", IrType::String, false),
+            query("status", IrType::String, true),
+            query("cursor", IrType::String, false),
+            query("sort", IrType::String, false),
+        ];
+        new_op.request_body.as_mut().unwrap().required = true;
+        new_op.return_type = IrReturnType::Void;
+
+        let d = diff(&spec(vec![old_op], vec![]), &spec(vec![new_op], vec![]));
+        assert_eq!(
+            messages(&d, Severity::Breaking),
+            [
+                "POST /pets (listPets): query parameter `limit` type changed from integer to string",
+                "POST /pets (listPets): query parameter `status` is now required",
+                "POST /pets (listPets): request body is now required",
+                "POST /pets (listPets): response changed from Pet[] to void",
+            ]
+        );
+        assert_eq!(
+            messages(&d, Severity::NonBreaking),
+            [
+                "POST /pets (listPets): query parameter `cursor` is now optional",
+                "POST /pets (listPets): optional query parameter `sort` added",
+            ]
+        );
+    }
+
+    #[test]
+    fn schema_field_and_variant_changes() {
+        let old = spec(
+            vec![],
+            vec![
+                object(
+                    "Pet",
+                    vec![
+                        field("id", IrType::String, true),
+                        field("tag", IrType::String, false),
+                        field("age", IrType::Integer, false),
+                        field("name", IrType::String, false),
+                    ],
+                ),
+                status(&["available", "sold"]),
+                object("Legacy", vec![]),
+            ],
+        );
+        let new = spec(
+            vec![],
+            vec![
+                object(
+                    "Pet",
+                    vec![
+                        field("id", IrType::Integer, true),
+                        field("age", IrType::Integer, true),
+                        field("nickname", IrType::String, false),
+                        IrField {
+                            deprecated: true,
+                            ..field("name", IrType::String, false)
+                        },
+                    ],
+                ),
+                status(&["available", "pending"]),
+                object("Owner", vec![]),
+            ],
+        );
+        let d = diff(&old, &new);
+        assert_eq!(
+            messages(&d, Severity::Breaking),
+            [
+                "Legacy: schema removed",
+                "Pet.tag: field removed",
+                "Pet.id: type changed from string to integer",
+                "Pet.age: now required",
+                "Status: variant `sold` removed",
+            ]
+        );
+        assert_eq!(
+            messages(&d, Severity::NonBreaking),
+            [
+                "Pet.nickname: optional field added",
+                "Pet.name: now deprecated",
+                "Status: variant `pending` added",
+                "Owner: schema added",
+            ]
+        );
+        assert!(
+            d.to_string()
+                .starts_with("Breaking changes (5):\n  Legacy: schema removed\n")
+        );
+    }
+
+    #[test]
+    fn diff_serializes_kinds_and_severities() {
+        let old = spec(vec![], vec![status(&["a"])]);
+        let new = spec(vec![], vec![status(&["a", "b"])]);
+        let json = serde_json::to_value(diff(&old, &new)).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "changes": [{
+                    "kind": "enum_variant_added",
+                    "severity": "non_breaking",
+                    "location": "Status",
+                    "message": "variant `b` added",
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn markdown_groups_changes() {
+        let old = spec(
+            vec![
+                op("listPets", HttpMethod::Get, "/pets"),
+                op("ping", HttpMethod::Get, "/ping"),
+            ],
+            vec![status(&["a"])],
+        );
+        let new = spec(
+            vec![
+                op("listPets", HttpMethod::Get, "/pets"),
+                op("health", HttpMethod::Get, "/health"),
+            ],
+            vec![status(&["a", "b"])],
+        );
+        assert_eq!(
+            diff(&old, &new).to_markdown("2.0.0"),
+            "## 2.0.0\n\
+             \n### Added\n\n\
+             - `GET /health (health)`: operation added\n\
+             \n### Removed\n\n\
+             - **Breaking:** `GET /ping (ping)`: operation removed\n\
+             \n### Changed\n\n\
+             - `Status`: variant `b` added\n"
+        );
+    }
+}
@@ -0,0 +1,124 @@
+//! IR dumps (`oag generate --emit-ir`, `oag inspect --full`): the fully
+//! resolved [`IrSpec`], after every transform pass, in the form generators
+//! receive it, for debugging generator output without re-running the parser.
+//!
+//! A dump is wrapped in an envelope naming the oag version that wrote it.
+//! The IR changes shape between versions, so [`from_json`] refuses dumps from
+//! any other version instead of misreading them.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use super::IrSpec;
+use crate::error::IrDumpError;
+use crate::parse::spec::OpenApiSpec;
+
+/// The envelope's `format` marker.
+pub const FORMAT: &str = "oag-ir";
+
+/// The oag version writing (and the only one reading) dumps.
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// An [`IrSpec`] in its versioned envelope, as written by [`to_json`].
+#[derive(Debug, Serialize)]
+pub struct IrDump<'a> {
+    pub format: &'static str,
+    pub version: &'static str,
+    pub ir: &'a IrSpec,
+}
+
+impl<'a> IrDump<'a> {
+    pub fn new(ir: &'a IrSpec) -> Self {
+        Self {
+            format: FORMAT,
+            version: VERSION,
+            ir,
+        }
+    }
+}
+
+/// `ir` as a pretty-printed JSON dump, with a trailing newline.
+pub fn to_json(ir: &IrSpec) -> String {
+    let mut json = serde_json::to_string_pretty(&IrDump::new(ir)).expect("IR always serializes");
+    json.push('\n');
+    json
+}
+
+/// Read a dump written by [`to_json`], raw spec included, so generators see
+/// the same IR they would have been given when it was written.
+pub fn from_json(json: &str) -> Result<IrSpec, IrDumpError> {
+    let header: Header = serde_json::from_str(json)?;
+    if header.format.as_deref() != Some(FORMAT) {
+        return Err(IrDumpError::NotADump);
+    }
+    let version = header.version.unwrap_or_default();
+    if version != VERSION {
+        return Err(IrDumpError::Version {
+            found: version,
+            expected: VERSION,
+        });
+    }
+    let Envelope { ir } = serde_json::from_str(json)?;
+    Ok(IrSpec {
+        raw_spec: ir.raw_spec.map(Arc::new),
+        ..ir.ir
+    })
+}
+
+/// The envelope's fields, read before trusting the rest of the dump.
+#[derive(Deserialize)]
+struct Header {
+    format: Option<String>,
+    version: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Envelope {
+    ir: DumpedIr,
+}
+
+/// `IrSpec` skips `raw_spec` when deserializing; read it alongside.
+#[derive(Deserialize)]
+struct DumpedIr {
+    #[serde(flatten)]
+    ir: IrSpec,
+    raw_spec: Option<OpenApiSpec>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn petstore() -> IrSpec {
+        let spec = crate::parse::from_yaml(include_str!("../../tests/fixtures/petstore-3.2.yaml"))
+            .unwrap();
+        crate::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn round_trips_with_the_raw_spec() {
+        let ir = petstore();
+        let read = from_json(&to_json(&ir)).unwrap();
+        assert_eq!(read.raw_spec, ir.raw_spec);
+        assert_eq!(to_json(&read), to_json(&ir));
+    }
+
+    #[test]
+    fn rejects_other_versions() {
+        let json = to_json(&petstore()).replacen(
+            &format!("\"version\": \"{VERSION}\""),
+            "\"version\": \"0.0.1\"",
+            1,
+        );
+        let err = from_json(&json).unwrap_err();
+        assert!(matches!(err, IrDumpError::Version { ref found, .. } if found == "0.0.1"));
+        assert!(err.to_string().contains("--emit-ir"));
+    }
+
+    #[test]
+    fn rejects_bare_ir() {
+        let json = serde_json::to_string(&petstore()).unwrap();
+        assert!(matches!(from_json(&json), Err(IrDumpError::NotADump)));
+    }
+}
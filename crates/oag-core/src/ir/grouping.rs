@@ -1,3 +1,7 @@
+use std::collections::HashMap;
+
+use super::operations::{IrOperation, IrReturnType};
+use super::schemas::{IrSchema, IrType};
 use super::types::{IrSpec, NormalizedName};
 use crate::config::SplitBy;
 use crate::transform::name_normalizer::normalize_name;
@@ -47,7 +51,7 @@ fn group_by_route(ir: &IrSpec) -> Vec<OperationGroup> {
     let mut groups: IndexMap<String, Vec<usize>> = IndexMap::new();
 
     for (i, op) in ir.operations.iter().enumerate() {
-        let prefix = extract_path_prefix(&op.path);
+        let prefix = extract_path_prefix(&op.raw_path);
         groups.entry(prefix).or_default().push(i);
     }
 
@@ -60,6 +64,150 @@ fn group_by_route(ir: &IrSpec) -> Vec<OperationGroup> {
         .collect()
 }
 
+/// Where each schema's type goes when a split layout also splits its types.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaPartition {
+    /// Indices into `ir.schemas` owned by each group, parallel to the groups.
+    pub groups: Vec<Vec<usize>>,
+    /// Schemas reachable from several groups, or from none (e.g. only from
+    /// webhooks), plus everything those schemas reference.
+    pub shared: Vec<usize>,
+}
+
+/// Assign every schema to the one group whose operations reach it (following
+/// `$ref`s transitively), or to `shared`.
+///
+/// A group's schemas only reference their own group's schemas and shared ones;
+/// shared schemas only reference shared ones.
+pub fn partition_schemas(ir: &IrSpec, groups: &[OperationGroup]) -> SchemaPartition {
+    let index: HashMap<&str, usize> = ir
+        .schemas
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.name().pascal_case.as_str(), i))
+        .collect();
+    let deps: Vec<Vec<usize>> = ir
+        .schemas
+        .iter()
+        .map(|s| {
+            let mut refs = Vec::new();
+            schema_refs(s, &mut refs);
+            refs.iter().filter_map(|r| index.get(r).copied()).collect()
+        })
+        .collect();
+    let reach = |roots: Vec<usize>, reached: &mut Vec<bool>| {
+        let mut stack = roots;
+        while let Some(i) = stack.pop() {
+            if !reached[i] {
+                reached[i] = true;
+                stack.extend(&deps[i]);
+            }
+        }
+    };
+
+    let reached_by: Vec<Vec<bool>> = groups
+        .iter()
+        .map(|group| {
+            let mut refs = Vec::new();
+            for &op in &group.operation_indices {
+                operation_refs(&ir.operations[op], &mut refs);
+            }
+            let mut reached = vec![false; ir.schemas.len()];
+            reach(
+                refs.iter().filter_map(|r| index.get(r).copied()).collect(),
+                &mut reached,
+            );
+            reached
+        })
+        .collect();
+
+    // Schemas no group reaches are shared, and so is everything they reference.
+    let orphans = (0..ir.schemas.len())
+        .filter(|&i| reached_by.iter().all(|reached| !reached[i]))
+        .collect();
+    let mut shared_only = vec![false; ir.schemas.len()];
+    reach(orphans, &mut shared_only);
+
+    let mut partition = SchemaPartition {
+        groups: vec![Vec::new(); groups.len()],
+        shared: Vec::new(),
+    };
+    for i in 0..ir.schemas.len() {
+        let owners: Vec<usize> = (0..groups.len()).filter(|&g| reached_by[g][i]).collect();
+        match owners.as_slice() {
+            [g] if !shared_only[i] => partition.groups[*g].push(i),
+            _ => partition.shared.push(i),
+        }
+    }
+    partition
+}
+
+/// Every schema name an operation's parameters, body and responses reference.
+pub fn operation_refs<'a>(op: &'a IrOperation, out: &mut Vec<&'a str>) {
+    for param in &op.parameters {
+        type_refs(&param.param_type, out);
+    }
+    if let Some(body) = &op.request_body {
+        type_refs(&body.body_type, out);
+    }
+    match &op.return_type {
+        IrReturnType::Standard(resp) => type_refs(&resp.response_type, out),
+        IrReturnType::Sse(sse) => {
+            type_refs(&sse.event_type, out);
+            for variant in &sse.variants {
+                type_refs(variant, out);
+            }
+            if let Some(json) = &sse.json_response {
+                type_refs(&json.response_type, out);
+            }
+        }
+        IrReturnType::Void => {}
+    }
+    for error in &op.errors {
+        type_refs(&error.response_type, out);
+    }
+}
+
+/// Every schema name a schema's fields or variants reference.
+pub fn schema_refs<'a>(schema: &'a IrSchema, out: &mut Vec<&'a str>) {
+    match schema {
+        IrSchema::Object(obj) => {
+            for field in &obj.fields {
+                type_refs(&field.field_type, out);
+            }
+            if let Some(additional) = &obj.additional_properties {
+                type_refs(additional, out);
+            }
+        }
+        IrSchema::Alias(alias) => type_refs(&alias.target, out),
+        IrSchema::Union(union) => {
+            for variant in &union.variants {
+                type_refs(variant, out);
+            }
+        }
+        IrSchema::Enum(_) => {}
+    }
+}
+
+/// Every schema name referenced within a type.
+pub fn type_refs<'a>(ty: &'a IrType, out: &mut Vec<&'a str>) {
+    match ty {
+        IrType::Ref(name) => out.push(name),
+        IrType::Array(inner) | IrType::Map(inner, _) => type_refs(inner, out),
+        IrType::Object(fields) => {
+            for (_, field_type, _) in fields {
+                type_refs(field_type, out);
+            }
+        }
+        IrType::Union(types) | IrType::Intersection(types) => {
+            for t in types {
+                type_refs(t, out);
+            }
+        }
+        _ => {}
+    }
+}
+
 /// Extract the first meaningful path segment as a group name.
 /// e.g. "/pets/{petId}" → "pets", "/store/inventory" → "store"
 fn extract_path_prefix(path: &str) -> String {
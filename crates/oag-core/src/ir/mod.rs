@@ -1,9 +1,16 @@
+pub mod diff;
+pub mod dump;
 pub mod grouping;
 pub mod operations;
+pub mod order;
 pub mod schemas;
 pub mod types;
 
-pub use grouping::{OperationGroup, group_operations};
+pub use grouping::{
+    OperationGroup, SchemaPartition, group_operations, operation_refs, partition_schemas,
+    schema_refs, type_refs,
+};
 pub use operations::*;
+pub use order::{SchemaGroup, schema_order};
 pub use schemas::*;
 pub use types::{IrInfo, IrModule, IrServer, IrSpec, NormalizedName};
@@ -1,8 +1,13 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
 use super::schemas::IrType;
 use super::types::NormalizedName;
 
+pub use crate::parse::parameter::QueryObjectNotation;
+
 /// HTTP method.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum HttpMethod {
     Get,
     Post,
@@ -30,22 +35,201 @@ impl HttpMethod {
 }
 
 /// A fully resolved API operation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrOperation {
     pub name: NormalizedName,
     pub method: HttpMethod,
-    pub path: String,
+    /// The path exactly as written in the spec (e.g. `/pets/{pet_id}`).
+    pub raw_path: String,
+    /// The path with template parameters normalized to camelCase (e.g. `/pets/{petId}`).
+    pub normalized_path: String,
     pub summary: Option<String>,
     pub description: Option<String>,
+    /// URL of the operation's `externalDocs`.
+    pub external_docs: Option<String>,
     pub tags: Vec<String>,
     pub parameters: Vec<IrParameter>,
     pub request_body: Option<IrRequestBody>,
     pub return_type: IrReturnType,
+    /// The JSON error responses: `4XX`/`5XX` statuses and `default` when it
+    /// isn't the success response, in spec order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<IrErrorResponse>,
     pub deprecated: bool,
+    /// Whether this operation comes from the spec's `webhooks` section rather
+    /// than `paths`. For webhooks, `raw_path` holds the webhook name.
+    pub is_webhook: bool,
+    /// Where the operation is declared in the spec, for error messages.
+    pub source_location: Option<OperationSourceLocation>,
+    /// The operation's own `security`: `None` inherits the spec's default
+    /// ([`IrSpec::security`](super::IrSpec::security)), `Some(vec![])`
+    /// (`security: []`) explicitly makes it public.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security: Option<Vec<IrSecurityRequirement>>,
+    /// Cookie names of the `apiKey` security schemes in a cookie that the
+    /// operation accepts, from its own `security` or the spec's.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cookie_auth: Vec<String>,
+    /// The bearer (`type: http`, `scheme: bearer`) security scheme the
+    /// operation accepts, from its own `security` or the spec's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bearer_auth: Option<IrBearerAuth>,
+    /// Links declared on the operation's success responses that the
+    /// generators can follow.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<IrLink>,
+    /// The operation's vendor extensions listed in `extensions.expose`, by
+    /// name, in config order.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub extensions: IndexMap<String, serde_json::Value>,
+}
+
+/// A response link whose parameters all come from the response body.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IrLink {
+    /// The link's key under `links`.
+    pub name: String,
+    /// Name of the operation the link calls.
+    pub target_operation: NormalizedName,
+    /// How each of the target's parameters is filled in, in declaration order.
+    pub parameter_mapping: Vec<IrLinkParameter>,
+}
+
+/// One `$response.body#/...` entry of a link's `parameters`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IrLinkParameter {
+    /// The target parameter's name as written in the spec.
+    pub parameter: String,
+    /// JSON pointer into the response body, e.g. `/id`.
+    pub pointer: String,
+}
+
+/// The path and method an operation was declared under, plus its position in
+/// the spec file when the parser reports one.
+///
+/// Displays as `GET /pets`, or `GET /pets (line 12, column 5)` with a position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OperationSourceLocation {
+    /// The path as written in the spec, or the webhook name.
+    pub path: String,
+    /// The upper-case HTTP method.
+    pub method: String,
+    /// 1-based line of the operation. `serde_yaml_ng` doesn't expose spans,
+    /// so specs parsed through [`crate::parse`] leave this `None`.
+    pub line: Option<u32>,
+    /// 1-based column of the operation.
+    pub column: Option<u32>,
+}
+
+impl std::fmt::Display for OperationSourceLocation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.method, self.path)?;
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, " (line {line}, column {column})"),
+            (Some(line), None) => write!(f, " (line {line})"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A `type: http`, `scheme: bearer` security scheme.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IrBearerAuth {
+    /// The scheme's `bearerFormat` hint, e.g. `JWT`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+}
+
+impl IrBearerAuth {
+    /// Whether the tokens are JWTs (`bearerFormat: JWT`, any case), whose
+    /// `exp` claim the client can read.
+    pub fn is_jwt(&self) -> bool {
+        self.format
+            .as_deref()
+            .is_some_and(|f| f.eq_ignore_ascii_case("jwt"))
+    }
+}
+
+/// One alternative of a `security` list: scheme name → required scopes. Every
+/// scheme in it must be satisfied; an empty one makes credentials optional.
+pub type IrSecurityRequirement = IndexMap<String, Vec<String>>;
+
+/// Where an operation's security requirements come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IrAuth {
+    /// `security: []` on the operation: it takes no credentials, whatever the
+    /// spec's default.
+    Public,
+    /// No `security` of its own; the spec's default applies.
+    Inherited,
+    /// Its own non-empty `security`.
+    Operation,
+}
+
+impl IrOperation {
+    /// Whether the operation declares its own `security`, opts out with an
+    /// empty one, or inherits the spec's.
+    pub fn auth(&self) -> IrAuth {
+        match self.security.as_deref() {
+            None => IrAuth::Inherited,
+            Some([]) => IrAuth::Public,
+            Some(_) => IrAuth::Operation,
+        }
+    }
+
+    /// The security requirements a request must meet: the operation's own, or
+    /// `default` (the spec's, [`IrSpec::security`](super::IrSpec::security)).
+    /// Empty when the operation is public.
+    pub fn effective_security<'a>(
+        &'a self,
+        default: &'a [IrSecurityRequirement],
+    ) -> &'a [IrSecurityRequirement] {
+        self.security.as_deref().unwrap_or(default)
+    }
+
+    /// The names of the security schemes any of the effective requirements
+    /// use, in order of first appearance.
+    pub fn security_schemes<'a>(&'a self, default: &'a [IrSecurityRequirement]) -> Vec<&'a str> {
+        let mut schemes: Vec<&str> = Vec::new();
+        for scheme in self
+            .effective_security(default)
+            .iter()
+            .flat_map(|r| r.keys())
+        {
+            if !schemes.contains(&scheme.as_str()) {
+                schemes.push(scheme);
+            }
+        }
+        schemes
+    }
+
+    /// Doc text for the operation: the summary, then the description as further
+    /// paragraphs. A description that just repeats the summary is dropped.
+    pub fn doc(&self) -> Option<String> {
+        match (self.summary.as_deref(), self.description.as_deref()) {
+            (Some(summary), Some(description)) if description.starts_with(summary) => {
+                Some(description.to_string())
+            }
+            (Some(summary), Some(description)) => Some(format!("{summary}\n\n{description}")),
+            (summary, description) => summary.or(description).map(str::to_string),
+        }
+    }
+
+    /// How to refer to the operation in an error message: its source location
+    /// and name (`GET /pets (listPets)`), or just the name when the location
+    /// is unknown.
+    pub fn label(&self) -> String {
+        match &self.source_location {
+            Some(location) => format!("{location} ({})", self.name.original),
+            None => self.name.original.clone(),
+        }
+    }
 }
 
 /// What an operation returns.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)] // built once, matched everywhere
 pub enum IrReturnType {
     /// Standard JSON response.
     Standard(IrResponse),
@@ -56,7 +240,7 @@ pub enum IrReturnType {
 }
 
 /// SSE return type with event schema info.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrSseReturn {
     /// The type of each event yielded by the stream.
     pub event_type: IrType,
@@ -64,6 +248,10 @@ pub struct IrSseReturn {
     pub variants: Vec<IrType>,
     /// The union type name for the stream event (e.g., `CreateChatCompletionStreamEvent`).
     pub event_type_name: Option<String>,
+    /// Name of the client's stream method: the operation's own, or
+    /// `{name}Stream` for a dual endpoint (e.g. `createChatCompletionStream`).
+    /// Hooks are named after it too.
+    pub stream_name: NormalizedName,
     /// Whether the endpoint also has a JSON response (dual endpoint).
     pub also_has_json: bool,
     /// The JSON response type if this is a dual endpoint.
@@ -71,14 +259,55 @@ pub struct IrSseReturn {
 }
 
 /// A resolved response.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrResponse {
     pub response_type: IrType,
     pub description: Option<String>,
+    /// Media type of the response body, e.g. `application/json` or `text/plain`.
+    /// `None` when the IR is built by hand.
+    pub content_type: Option<String>,
+    /// The example generated tests use: the first of the config's preferred
+    /// `examples.prefer` names, else the media type's own `example`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    /// The media type's named `examples`, by name.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<String, serde_json::Value>,
+    /// Other success statuses declared without a body, e.g. `204` next to a
+    /// `200`; clients return `undefined` for them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub empty_statuses: Vec<String>,
+}
+
+/// A JSON error response of an operation.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IrErrorResponse {
+    /// The status as written in the spec: `404`, `4XX` or `default`.
+    pub status: String,
+    pub response_type: IrType,
+    pub description: Option<String>,
+}
+
+impl IrErrorResponse {
+    /// Whether this is the `default` response, the catch-all for statuses
+    /// not listed.
+    pub fn is_default(&self) -> bool {
+        self.status == "default"
+    }
+}
+
+impl IrResponse {
+    /// Whether the body is plain text, read as a string instead of parsed as JSON.
+    /// An event stream returned whole (`streaming: off`) is text too.
+    pub fn is_text(&self) -> bool {
+        self.content_type
+            .as_deref()
+            .is_some_and(|ct| is_text_media_type(ct) || ct == "text/event-stream")
+    }
 }
 
 /// A resolved path/query/header parameter.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrParameter {
     pub name: NormalizedName,
     pub original_name: String,
@@ -86,10 +315,92 @@ pub struct IrParameter {
     pub param_type: IrType,
     pub required: bool,
     pub description: Option<String>,
+    /// Media type the value is serialized as, for parameters declared with
+    /// `content` instead of `schema` (e.g. JSON-encoded query parameters).
+    pub content_type: Option<String>,
+    /// Constant from the generator's `pinned_parameters`: generated code
+    /// supplies it instead of asking callers for it.
+    pub pinned_value: Option<String>,
+    /// The spec's `allowReserved`: reserved characters are sent as-is instead
+    /// of percent-encoded.
+    #[serde(default)]
+    pub allow_reserved: bool,
+    /// The spec's `allowEmptyValue`: an empty value is sent as a bare key.
+    #[serde(default)]
+    pub allow_empty_value: bool,
+    /// The spec's `example`, else the value of its first `examples` entry;
+    /// generated tests use it instead of a placeholder.
+    #[serde(default)]
+    pub example: Option<serde_json::Value>,
+    /// For object-valued query parameters sent as free-form maps
+    /// (`deepObject` style or `x-oag-query-object`): how their keys are
+    /// flattened into the query string.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_object: Option<QueryObjectNotation>,
+    /// For array query parameters sent as one delimited value (`style` with
+    /// `explode: false`) instead of a repeated key: the delimiter's style.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query_array: Option<QueryArrayStyle>,
+}
+
+impl IrParameter {
+    /// The example as it appears in a URL path segment: a number, boolean,
+    /// or string of unreserved characters. `None` for anything that would
+    /// need encoding, so tests keep their placeholder.
+    pub fn path_example(&self) -> Option<String> {
+        match self.example.as_ref()? {
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            serde_json::Value::Bool(b) => Some(b.to_string()),
+            serde_json::Value::String(s)
+                if !s.is_empty()
+                    && s.chars()
+                        .all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c)) =>
+            {
+                Some(s.clone())
+            }
+            _ => None,
+        }
+    }
+}
+
+/// How an array query parameter's items are joined into one value, from its
+/// `style` when `explode` is off. Exploded arrays repeat the key instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryArrayStyle {
+    /// `ids=1,2,3` (`style: form`).
+    Form,
+    /// `ids=1%202%203` (`style: spaceDelimited`).
+    SpaceDelimited,
+    /// `ids=1|2|3` (`style: pipeDelimited`).
+    PipeDelimited,
+}
+
+impl QueryArrayStyle {
+    /// The style for a query parameter's `style` and `explode`, or `None` when
+    /// its array items are sent as repeated keys. `form` explodes unless told
+    /// otherwise; the delimited styles only if told to.
+    pub fn from_spec(style: Option<&str>, explode: Option<bool>) -> Option<Self> {
+        match (style.unwrap_or("form"), explode) {
+            ("form", Some(false)) => Some(QueryArrayStyle::Form),
+            ("spaceDelimited", None | Some(false)) => Some(QueryArrayStyle::SpaceDelimited),
+            ("pipeDelimited", None | Some(false)) => Some(QueryArrayStyle::PipeDelimited),
+            _ => None,
+        }
+    }
+
+    /// The text between items.
+    pub fn delimiter(&self) -> &'static str {
+        match self {
+            QueryArrayStyle::Form => ",",
+            QueryArrayStyle::SpaceDelimited => " ",
+            QueryArrayStyle::PipeDelimited => "|",
+        }
+    }
 }
 
 /// Parameter location.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum IrParameterLocation {
     Path,
     Query,
@@ -98,18 +409,70 @@ pub enum IrParameterLocation {
 }
 
 /// Encoding metadata for a single field in a multipart request body.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrFieldEncoding {
     pub field_name: String,
     pub content_type: Option<String>,
 }
 
 /// A resolved request body.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrRequestBody {
     pub body_type: IrType,
     pub required: bool,
     pub content_type: String,
     pub description: Option<String>,
     pub encoding: Option<Vec<IrFieldEncoding>>,
+    /// The example generated tests use: the first of the config's preferred
+    /// `examples.prefer` names, else the media type's own `example`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub example: Option<serde_json::Value>,
+    /// The media type's named `examples`, by name.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<String, serde_json::Value>,
+}
+
+impl IrRequestBody {
+    /// Whether the body is plain text, sent as a string without JSON encoding.
+    pub fn is_text(&self) -> bool {
+        is_text_media_type(&self.content_type)
+    }
+
+    /// Whether the body is JSON-encoded: `application/json` or a `+json` type.
+    pub fn is_json(&self) -> bool {
+        is_json_media_type(&self.content_type)
+    }
+
+    /// The patch document format the body's media type declares, if any.
+    pub fn patch_format(&self) -> Option<PatchFormat> {
+        match self.content_type.as_str() {
+            "application/merge-patch+json" => Some(PatchFormat::MergePatch),
+            "application/json-patch+json" => Some(PatchFormat::JsonPatch),
+            _ => None,
+        }
+    }
+}
+
+/// How a patch request body describes its changes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatchFormat {
+    /// `application/merge-patch+json` (RFC 7396): the resource with only the
+    /// changed fields set, at any depth.
+    MergePatch,
+    /// `application/json-patch+json` (RFC 6902): a list of `add`, `remove`,
+    /// `replace`, `move`, `copy` and `test` operations.
+    JsonPatch,
+}
+
+/// `text/*` media types other than `text/event-stream`, whose bodies are
+/// strings (`text/plain`, `text/csv`, ...).
+pub fn is_text_media_type(content_type: &str) -> bool {
+    content_type.starts_with("text/") && content_type != "text/event-stream"
+}
+
+/// `application/json` and structured `+json` media types
+/// (`application/merge-patch+json`, `application/problem+json`, ...).
+pub fn is_json_media_type(content_type: &str) -> bool {
+    content_type == "application/json"
+        || (content_type.starts_with("application/") && content_type.ends_with("+json"))
 }
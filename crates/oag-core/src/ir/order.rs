@@ -0,0 +1,214 @@
+use std::collections::HashMap;
+
+use super::grouping::schema_refs;
+use super::types::IrSpec;
+
+/// Schemas that have to be emitted together: one schema, or a cycle of
+/// schemas that reference each other.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SchemaGroup {
+    /// Indices into `IrSpec::schemas`, ascending.
+    pub schemas: Vec<usize>,
+    /// Whether the group references itself, so some reference in it has to
+    /// be a forward reference.
+    pub cyclic: bool,
+}
+
+/// `ir.schemas` in dependency order: every group comes after the groups its
+/// schemas reference. Otherwise the spec's order is kept, so the result is
+/// the same on every run.
+pub fn schema_order(ir: &IrSpec) -> Vec<SchemaGroup> {
+    let index: HashMap<&str, usize> = ir
+        .schemas
+        .iter()
+        .enumerate()
+        .map(|(i, s)| (s.name().pascal_case.as_str(), i))
+        .collect();
+    let edges: Vec<Vec<usize>> = ir
+        .schemas
+        .iter()
+        .map(|schema| {
+            let mut refs = Vec::new();
+            schema_refs(schema, &mut refs);
+            refs.into_iter()
+                .filter_map(|r| index.get(r).copied())
+                .collect()
+        })
+        .collect();
+
+    let mut tarjan = Tarjan {
+        edges: &edges,
+        index: vec![None; edges.len()],
+        low: vec![0; edges.len()],
+        on_stack: vec![false; edges.len()],
+        stack: Vec::new(),
+        next: 0,
+        groups: Vec::new(),
+    };
+    for i in 0..edges.len() {
+        if tarjan.index[i].is_none() {
+            tarjan.visit(i);
+        }
+    }
+    tarjan.groups
+}
+
+/// Tarjan's strongly connected components. A component is finished only after
+/// everything it reaches, so groups come out dependencies first.
+struct Tarjan<'a> {
+    edges: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    groups: Vec<SchemaGroup>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, v: usize) {
+        self.index[v] = Some(self.next);
+        self.low[v] = self.next;
+        self.next += 1;
+        self.stack.push(v);
+        self.on_stack[v] = true;
+
+        for &w in &self.edges[v] {
+            match self.index[w] {
+                None => {
+                    self.visit(w);
+                    self.low[v] = self.low[v].min(self.low[w]);
+                }
+                Some(index) if self.on_stack[w] => self.low[v] = self.low[v].min(index),
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low[v]) == self.index[v] {
+            let mut schemas = Vec::new();
+            while let Some(w) = self.stack.pop() {
+                self.on_stack[w] = false;
+                schemas.push(w);
+                if w == v {
+                    break;
+                }
+            }
+            schemas.sort_unstable();
+            let cyclic = schemas.len() > 1 || self.edges[v].contains(&v);
+            self.groups.push(SchemaGroup { schemas, cyclic });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(yaml: &str) -> Vec<(Vec<String>, bool)> {
+        let spec = crate::parse::from_yaml(yaml).unwrap();
+        let ir = crate::transform::transform(&spec).unwrap();
+        schema_order(&ir)
+            .into_iter()
+            .map(|group| {
+                let names = group
+                    .schemas
+                    .iter()
+                    .map(|&i| ir.schemas[i].name().pascal_case.clone())
+                    .collect();
+                (names, group.cyclic)
+            })
+            .collect()
+    }
+
+    fn single(name: &str) -> (Vec<String>, bool) {
+        (vec![name.to_string()], false)
+    }
+
+    #[test]
+    fn chain_puts_dependencies_first() {
+        let groups = order(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths: {}
+components:
+  schemas:
+    A: { type: array, items: { $ref: "#/components/schemas/B" } }
+    B: { type: array, items: { $ref: "#/components/schemas/C" } }
+    C: { type: string }
+    D: { type: integer }
+"##,
+        );
+        assert_eq!(
+            groups,
+            vec![single("C"), single("B"), single("A"), single("D")]
+        );
+    }
+
+    #[test]
+    fn diamond_emits_the_shared_schema_once() {
+        let groups = order(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths: {}
+components:
+  schemas:
+    Top:
+      oneOf:
+        - $ref: "#/components/schemas/Left"
+        - $ref: "#/components/schemas/Right"
+    Left:
+      type: object
+      properties: { base: { $ref: "#/components/schemas/Base" } }
+    Right:
+      type: object
+      properties: { base: { $ref: "#/components/schemas/Base" } }
+    Base: { type: object, properties: { id: { type: string } } }
+"##,
+        );
+        assert_eq!(
+            groups,
+            vec![
+                single("Base"),
+                single("Left"),
+                single("Right"),
+                single("Top")
+            ]
+        );
+    }
+
+    #[test]
+    fn cycles_are_grouped_and_flagged() {
+        let groups = order(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths: {}
+components:
+  schemas:
+    Forest: { type: array, items: { $ref: "#/components/schemas/Tree" } }
+    Tree:
+      oneOf:
+        - $ref: "#/components/schemas/Node"
+        - $ref: "#/components/schemas/Leaf"
+    Node:
+      type: object
+      properties: { children: { $ref: "#/components/schemas/Forest" } }
+    Leaf: { type: object, properties: { value: { type: string } } }
+    List:
+      type: object
+      properties: { next: { $ref: "#/components/schemas/List" } }
+"##,
+        );
+        let cycle = vec!["Forest".to_string(), "Tree".to_string(), "Node".to_string()];
+        assert_eq!(
+            groups,
+            vec![
+                single("Leaf"),
+                (cycle, true),
+                (vec!["List".to_string()], true)
+            ]
+        );
+    }
+}
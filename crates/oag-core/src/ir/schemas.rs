@@ -1,7 +1,9 @@
+use serde::{Deserialize, Serialize};
+
 use super::types::NormalizedName;
 
 /// A resolved schema in the IR.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum IrSchema {
     Object(IrObjectSchema),
     Enum(IrEnumSchema),
@@ -18,19 +20,43 @@ impl IrSchema {
             IrSchema::Union(u) => &u.name,
         }
     }
+
+    /// Schema keywords the IR can only approximate (`not`, `if`, `then`, `else`).
+    pub fn unsupported_keywords(&self) -> &[String] {
+        match self {
+            IrSchema::Object(o) => &o.unsupported_keywords,
+            IrSchema::Enum(e) => &e.unsupported_keywords,
+            IrSchema::Alias(a) => &a.unsupported_keywords,
+            IrSchema::Union(u) => &u.unsupported_keywords,
+        }
+    }
+    /// The spec's `example` for this schema.
+    pub fn example(&self) -> Option<&serde_json::Value> {
+        match self {
+            IrSchema::Object(o) => o.example.as_ref(),
+            IrSchema::Enum(e) => e.example.as_ref(),
+            IrSchema::Alias(a) => a.example.as_ref(),
+            IrSchema::Union(u) => u.example.as_ref(),
+        }
+    }
 }
 
 /// An object schema with typed fields.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrObjectSchema {
     pub name: NormalizedName,
     pub description: Option<String>,
     pub fields: Vec<IrField>,
     pub additional_properties: Option<IrType>,
+    /// Keywords from the source schema that were approximated or dropped.
+    pub unsupported_keywords: Vec<String>,
+    /// The spec's `example` for this schema, if it has one.
+    #[serde(default)]
+    pub example: Option<serde_json::Value>,
 }
 
 /// A field on an object schema.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrField {
     pub name: NormalizedName,
     pub original_name: String,
@@ -39,42 +65,148 @@ pub struct IrField {
     pub description: Option<String>,
     pub read_only: bool,
     pub write_only: bool,
+    /// `deprecated: true` on the property's schema.
+    #[serde(default)]
+    pub deprecated: bool,
+    /// The property schema's `default`. It doesn't make a required field
+    /// optional.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    /// A numeric property's `format` and bounds, which its type drops.
+    #[serde(default, skip_serializing_if = "IrNumberHints::is_empty")]
+    pub number_hints: IrNumberHints,
+}
+
+/// The `format` (`int32`, `double`) and bounds of a numeric schema, kept for
+/// documentation. Exclusive bounds are in their 3.1 numeric form.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct IrNumberHints {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maximum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusive_minimum: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclusive_maximum: Option<f64>,
+}
+
+impl IrNumberHints {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// The hints as JSDoc-style tags, e.g. `["@format int32", "@minimum 0"]`.
+    pub fn tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .format
+            .iter()
+            .map(|format| format!("@format {format}"))
+            .collect();
+        for (tag, bound) in [
+            ("minimum", self.minimum),
+            ("exclusiveMinimum", self.exclusive_minimum),
+            ("maximum", self.maximum),
+            ("exclusiveMaximum", self.exclusive_maximum),
+        ] {
+            // `f64` prints whole numbers without a fraction: `0`, not `0.0`.
+            if let Some(value) = bound {
+                tags.push(format!("@{tag} {value}"));
+            }
+        }
+        tags
+    }
 }
 
 /// A string enum schema.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrEnumSchema {
     pub name: NormalizedName,
     pub description: Option<String>,
     pub variants: Vec<String>,
+    /// Keywords from the source schema that were approximated or dropped.
+    pub unsupported_keywords: Vec<String>,
+    /// The spec's `example` for this schema, if it has one.
+    #[serde(default)]
+    pub example: Option<serde_json::Value>,
 }
 
 /// A type alias (e.g., `type Foo = string`).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrAliasSchema {
     pub name: NormalizedName,
     pub description: Option<String>,
     pub target: IrType,
+    /// Keywords from the source schema that were approximated or dropped.
+    pub unsupported_keywords: Vec<String>,
+    /// The spec's `example` for this schema, if it has one.
+    #[serde(default)]
+    pub example: Option<serde_json::Value>,
 }
 
 /// A union type (oneOf / anyOf).
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrUnionSchema {
     pub name: NormalizedName,
     pub description: Option<String>,
     pub variants: Vec<IrType>,
     pub discriminator: Option<IrDiscriminator>,
+    /// Whether the spec wrote `oneOf` or `anyOf`, for generators that
+    /// validate: a `oneOf` value must match exactly one variant.
+    #[serde(default)]
+    pub kind: UnionKind,
+    /// Keywords from the source schema that were approximated or dropped.
+    pub unsupported_keywords: Vec<String>,
+    /// The spec's `example` for this schema, if it has one.
+    #[serde(default)]
+    pub example: Option<serde_json::Value>,
+}
+
+/// The keyword a union schema was declared with.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnionKind {
+    #[default]
+    OneOf,
+    AnyOf,
+}
+
+impl UnionKind {
+    /// The spec keyword, `oneOf` or `anyOf`.
+    pub fn keyword(self) -> &'static str {
+        match self {
+            UnionKind::OneOf => "oneOf",
+            UnionKind::AnyOf => "anyOf",
+        }
+    }
 }
 
 /// Discriminator for union types.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrDiscriminator {
     pub property_name: String,
     pub mapping: Vec<(String, String)>,
 }
 
+/// Key constraint of an `IrType::Map`, taken from the schema's `propertyNames`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum IrMapKey {
+    /// Integer keys (still strings on the wire): `type: integer`, an integer
+    /// `format`, or a digits-only `pattern`.
+    Integer,
+    /// String keys matching a regular expression.
+    Pattern(String),
+}
+
 /// A resolved type reference.
-#[derive(Debug, Clone, PartialEq)]
+///
+/// More kinds of type will be added as the IR models more of JSON Schema, so
+/// matches outside this crate need a fallback arm, typically the same as for
+/// [`IrType::Any`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum IrType {
     String,
     StringLiteral(String),
@@ -84,7 +216,7 @@ pub enum IrType {
     Null,
     Array(Box<IrType>),
     Object(Vec<(String, IrType, bool)>), // inline object: (name, type, required)
-    Map(Box<IrType>),                    // Record<string, T>
+    Map(Box<IrType>, Option<IrMapKey>),  // Record<string, T>, keys constrained by `propertyNames`
     Ref(String),                         // reference to a named schema (PascalCase)
     Union(Vec<IrType>),
     Intersection(Vec<IrType>),
@@ -93,3 +225,14 @@ pub enum IrType {
     DateTime,
     Binary,
 }
+
+impl IrType {
+    /// Whether the type admits `null`, on its own or as a union variant.
+    pub fn is_nullable(&self) -> bool {
+        match self {
+            IrType::Null => true,
+            IrType::Union(variants) => variants.contains(&IrType::Null),
+            _ => false,
+        }
+    }
+}
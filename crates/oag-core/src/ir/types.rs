@@ -1,17 +1,131 @@
+use std::borrow::Cow;
 use std::fmt;
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::parse::spec::OpenApiSpec;
 
 /// A fully resolved, generator-ready intermediate representation of an OpenAPI spec.
-#[derive(Debug, Clone)]
+///
+/// Built by [`transform`](crate::transform::transform) from a parsed spec;
+/// every `$ref` is resolved, names are normalized, and inline object schemas
+/// are promoted to named [`IrSchema`]s that types refer to with
+/// [`IrType::Ref`].
+///
+/// ```
+/// use oag_core::ir::{IrReturnType, IrType};
+///
+/// let spec = oag_core::parse::from_yaml(
+///     r##"
+/// openapi: "3.1.0"
+/// info: { title: Pets, version: "1.0" }
+/// paths:
+///   /pets/{petId}:
+///     get:
+///       operationId: getPet
+///       parameters:
+///         - { name: petId, in: path, required: true, schema: { type: string } }
+///       responses:
+///         "200":
+///           description: The pet
+///           content:
+///             application/json:
+///               schema: { $ref: "#/components/schemas/Pet" }
+/// components:
+///   schemas:
+///     Pet:
+///       type: object
+///       properties:
+///         name: { type: string }
+/// "##,
+/// )?;
+/// let ir = oag_core::transform::transform(&spec)?;
+/// assert_eq!(ir.schemas[0].name().pascal_case, "Pet");
+/// let IrReturnType::Standard(response) = &ir.operations[0].return_type else {
+///     panic!("expected a JSON response");
+/// };
+/// assert_eq!(response.response_type, IrType::Ref("Pet".into()));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrSpec {
     pub info: IrInfo,
     pub servers: Vec<IrServer>,
     pub schemas: Vec<IrSchema>,
     pub operations: Vec<IrOperation>,
+    /// Operations declared under `webhooks`, kept apart from `operations` so
+    /// client and route generators don't treat them as callable endpoints.
+    pub webhooks: Vec<IrOperation>,
     pub modules: Vec<IrModule>,
+    /// Non-fatal notes from the transform, such as renamed colliding schemas.
+    pub diagnostics: Vec<String>,
+    /// The spec's default `security`, which operations without their own
+    /// inherit. Empty when the spec requires no authentication.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security: Vec<IrSecurityRequirement>,
+    /// The parsed spec the IR was built from, for fields the IR doesn't model
+    /// (extensions, raw descriptions). `None` when the IR is built by hand.
+    #[serde(serialize_with = "serialize_raw_spec", skip_deserializing)]
+    pub raw_spec: Option<Arc<OpenApiSpec>>,
+}
+
+fn serialize_raw_spec<S: Serializer>(
+    spec: &Option<Arc<OpenApiSpec>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    spec.as_deref().serialize(serializer)
+}
+
+impl IrSpec {
+    /// The first server's URL with its variables set to their defaults, used as
+    /// the generated client's base URL when none is configured.
+    pub fn default_base_url(&self) -> Option<&str> {
+        self.servers.first().map(|s| s.default_url.as_str())
+    }
+
+    /// This IR with `pins` (wire name → constant) set as the `pinned_value` of
+    /// every required header or query parameter they name. Header names match
+    /// case-insensitively. Borrows `self` when nothing is pinned.
+    pub fn with_pinned_parameters(&self, pins: &IndexMap<String, String>) -> Cow<'_, IrSpec> {
+        let pin = |param: &IrParameter| -> Option<&String> {
+            if !param.required {
+                return None;
+            }
+            match param.location {
+                IrParameterLocation::Header => pins
+                    .iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(&param.original_name))
+                    .map(|(_, value)| value),
+                IrParameterLocation::Query => pins.get(&param.original_name),
+                _ => None,
+            }
+        };
+        let pinned = |ops: &[IrOperation]| {
+            ops.iter()
+                .flat_map(|op| &op.parameters)
+                .any(|p| pin(p).is_some())
+        };
+        if !pinned(&self.operations) && !pinned(&self.webhooks) {
+            return Cow::Borrowed(self);
+        }
+
+        let mut ir = self.clone();
+        for param in ir
+            .operations
+            .iter_mut()
+            .chain(ir.webhooks.iter_mut())
+            .flat_map(|op| op.parameters.iter_mut())
+        {
+            param.pinned_value = pin(param).cloned();
+        }
+        Cow::Owned(ir)
+    }
 }
 
 /// API metadata.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrInfo {
     pub title: String,
     pub description: Option<String>,
@@ -19,21 +133,25 @@ pub struct IrInfo {
 }
 
 /// A server URL.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrServer {
     pub url: String,
+    /// `url` with each `{variable}` replaced by its default.
+    pub default_url: String,
     pub description: Option<String>,
 }
 
 /// A module groups operations by tag.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IrModule {
     pub name: NormalizedName,
+    /// The description of the spec's top-level tag of the same name.
+    pub description: Option<String>,
     pub operations: Vec<usize>, // indices into IrSpec.operations
 }
 
 /// A name with multiple casing variants pre-computed.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct NormalizedName {
     pub original: String,
     pub pascal_case: String,
@@ -51,3 +169,37 @@ impl fmt::Display for NormalizedName {
 // Re-export schema and operation types for convenience
 pub use super::operations::*;
 pub use super::schemas::*;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anthropic_ir() -> IrSpec {
+        let spec =
+            crate::parse::from_yaml(include_str!("../../tests/fixtures/anthropic-messages.yaml"))
+                .unwrap();
+        crate::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn pinning_sets_matching_required_headers() {
+        let ir = anthropic_ir();
+        let pins = IndexMap::from([("Anthropic-Version".to_string(), "2023-06-01".to_string())]);
+        let pinned = ir.with_pinned_parameters(&pins);
+        assert!(matches!(pinned, Cow::Owned(_)));
+        let version = pinned
+            .operations
+            .iter()
+            .flat_map(|op| &op.parameters)
+            .find(|p| p.original_name == "anthropic-version")
+            .unwrap();
+        assert_eq!(version.pinned_value.as_deref(), Some("2023-06-01"));
+    }
+
+    #[test]
+    fn pinning_unknown_names_borrows() {
+        let ir = anthropic_ir();
+        let pins = IndexMap::from([("x-unknown".to_string(), "1".to_string())]);
+        assert!(matches!(ir.with_pinned_parameters(&pins), Cow::Borrowed(_)));
+    }
+}
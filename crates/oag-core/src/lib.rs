@@ -1,57 +1,283 @@
+//! Core of the `oag` OpenAPI generator: parsing specs, transforming them into
+//! a generator-ready intermediate representation ([`ir::IrSpec`]), and the
+//! [`CodeGenerator`] trait that turns the IR into files.
+//!
+//! A spec goes through three steps: [`parse`] it, [`transform`] it into the
+//! IR, then hand the IR and a [`config::GeneratorConfig`] to a generator. The
+//! `oag` CLI does the same through [`run`], which also reads the config file
+//! and writes the files out.
+//!
+//! ```
+//! # #[cfg(feature = "generate")] {
+//! use oag_core::config::{GeneratorConfig, GeneratorId};
+//! use oag_core::ir::IrSpec;
+//! use oag_core::{CodeGenerator, GeneratedFile, GeneratorError};
+//!
+//! /// Lists every operation's method and path.
+//! struct RouteList;
+//!
+//! impl CodeGenerator for RouteList {
+//!     fn id(&self) -> GeneratorId {
+//!         GeneratorId::JsonSchema
+//!     }
+//!
+//!     fn generate(
+//!         &self,
+//!         ir: &IrSpec,
+//!         _config: &GeneratorConfig,
+//!     ) -> Result<Vec<GeneratedFile>, GeneratorError> {
+//!         let content: String = ir
+//!             .operations
+//!             .iter()
+//!             .map(|op| format!("{} {}\n", op.method.as_str(), op.raw_path))
+//!             .collect();
+//!         Ok(vec![GeneratedFile::new("routes.txt", content)])
+//!     }
+//! }
+//!
+//! let spec = oag_core::parse::from_yaml(
+//!     r#"
+//! openapi: "3.1.0"
+//! info: { title: Pets, version: "1.0" }
+//! paths:
+//!   /pets:
+//!     get:
+//!       operationId: listPets
+//!       responses:
+//!         "204": { description: Listed }
+//! "#,
+//! )?;
+//! let ir = oag_core::transform::transform(&spec)?;
+//! let files = RouteList.generate(&ir, &GeneratorConfig::new("out"))?;
+//! assert_eq!(files[0].path, "routes.txt");
+//! assert_eq!(files[0].content, "GET /pets\n");
+//! # }
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+//!
+//! # Features
+//!
+//! Each feature includes the one before it, and `generate` is on by default:
+//!
+//! - `parse`: [`parse`] and its errors, for build scripts that only read and
+//!   check a spec. Depends on serde and the YAML/JSON parsers alone.
+//! - `transform`: the IR, [`transform`], the config, and the IR-based helpers.
+//! - `generate`: [`CodeGenerator`], [`GeneratedFile`], output filtering and
+//!   normalization, and [`run`].
+
+#[cfg(feature = "transform")]
 pub mod config;
+#[cfg(feature = "transform")]
+pub mod contract;
+#[cfg(feature = "transform")]
+pub mod docs;
+#[cfg(feature = "parse")]
 pub mod error;
+#[cfg(feature = "transform")]
+pub mod examples;
+#[cfg(feature = "generate")]
+pub mod filter;
+#[cfg(feature = "transform")]
+pub mod fixture;
+#[cfg(feature = "transform")]
+pub mod hash;
+#[cfg(feature = "transform")]
+pub mod index;
+#[cfg(feature = "transform")]
 pub mod ir;
+#[cfg(feature = "parse")]
 pub mod parse;
+#[cfg(feature = "generate")]
+pub mod postprocess;
+#[cfg(feature = "transform")]
+pub mod report;
+#[cfg(feature = "generate")]
+pub mod run;
+#[cfg(feature = "transform")]
+pub mod test_report;
+#[cfg(feature = "transform")]
 pub mod transform;
 
+#[cfg(feature = "generate")]
+use std::path::{Component, Path, PathBuf};
+
+#[cfg(feature = "generate")]
 use thiserror::Error;
 
+#[cfg(feature = "generate")]
 /// A generated file with path and content.
 #[derive(Debug, Clone)]
 pub struct GeneratedFile {
+    /// Path relative to the generator's output directory, always `/`-separated
+    /// (`src/types.ts`) regardless of platform.
     pub path: String,
+    /// The file's text. Line endings and trailing whitespace are normalized
+    /// before it's written; see [`CodeGenerator::normalization`].
     pub content: String,
 }
 
-/// Normalize whitespace in generated code:
-/// - Collapse 3+ consecutive newlines into 2 (max one blank line)
-/// - Ensure trailing newline
-pub fn normalize_generated(content: &str) -> String {
-    let mut result = String::with_capacity(content.len());
-    let mut newline_count = 0;
-    for ch in content.chars() {
-        if ch == '\n' {
-            newline_count += 1;
-            if newline_count <= 2 {
-                result.push(ch);
+#[cfg(feature = "generate")]
+impl GeneratedFile {
+    pub fn new(path: impl Into<String>, content: impl Into<String>) -> Self {
+        Self {
+            path: path.into(),
+            content: content.into(),
+        }
+    }
+
+    /// Where this file goes under `base`, with the path rebuilt using the
+    /// platform's separator.
+    pub fn to_platform_path(&self, base: &Path) -> PathBuf {
+        let mut path = base.to_path_buf();
+        for component in Path::new(&self.path).components() {
+            if let Component::Normal(_) | Component::ParentDir = component {
+                path.push(component);
             }
-        } else {
-            newline_count = 0;
-            result.push(ch);
         }
+        path
+    }
+
+    /// Check that [`path`](Self::path) stays inside the output directory:
+    /// it must be relative and have no `..` component.
+    ///
+    /// ```
+    /// use oag_core::GeneratedFile;
+    ///
+    /// assert!(GeneratedFile::new("src/index.ts", "").check_path().is_ok());
+    /// assert!(GeneratedFile::new("../../.bashrc", "").check_path().is_err());
+    /// assert!(GeneratedFile::new("/etc/passwd", "").check_path().is_err());
+    /// ```
+    pub fn check_path(&self) -> Result<(), GeneratorError> {
+        check_relative_path(&self.path)
     }
-    if !result.ends_with('\n') {
-        result.push('\n');
+
+    /// The whitespace policy this file's extension calls for.
+    pub fn normalization(&self) -> postprocess::Normalization {
+        postprocess::Normalization::for_path(&self.path)
     }
-    result
 }
 
-/// Unified error type for code generators.
+#[cfg(feature = "generate")]
+/// Check that `path`, a `/`-separated path like a [`GeneratedFile::path`] or
+/// a `source_dir`, can't name anything outside the directory it's joined to.
+/// `\` counts as a separator too, since it is one on Windows.
+pub fn check_relative_path(path: &str) -> Result<(), GeneratorError> {
+    let unsafe_path = |reason| {
+        Err(GeneratorError::UnsafePath {
+            path: path.to_string(),
+            reason,
+        })
+    };
+    let bytes = path.as_bytes();
+    if path.starts_with(['/', '\\'])
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+        || Path::new(path).is_absolute()
+    {
+        return unsafe_path("absolute paths aren't allowed");
+    }
+    if path.split(['/', '\\']).any(|component| component == "..") {
+        return unsafe_path("`..` components aren't allowed");
+    }
+    Ok(())
+}
+
+#[cfg(feature = "generate")]
+/// Unified error type for code generators. The message is shown to the user
+/// as is, so it should say what in the spec or config caused it:
+///
+/// ```
+/// use oag_core::GeneratorError;
+///
+/// let err = GeneratorError::Other("`stream_accumulator.event` is empty".into());
+/// assert_eq!(err.to_string(), "generation failed: `stream_accumulator.event` is empty");
+/// ```
 #[derive(Debug, Error)]
 pub enum GeneratorError {
+    /// A template failed to render, e.g. `minijinja::Error::to_string()`.
     #[error("template render failed: {0}")]
     Render(String),
 
+    /// A file path that would be written outside the output directory.
+    #[error("refusing to write `{path}`: {reason}")]
+    UnsafePath { path: String, reason: &'static str },
+
+    /// Anything else, such as config values the generator can't honor.
     #[error("generation failed: {0}")]
     Other(String),
 }
 
-/// Trait for code generators that produce files from an IR spec.
+#[cfg(feature = "generate")]
+/// Trait for code generators that produce files from an IR spec. See the
+/// [crate docs](crate) for a complete generator run in memory.
 pub trait CodeGenerator {
+    /// Which `generators` entry in the config this generator handles.
     fn id(&self) -> config::GeneratorId;
+
+    /// The files for `ir`, with paths relative to `config.output`.
     fn generate(
         &self,
         ir: &ir::IrSpec,
         config: &config::GeneratorConfig,
     ) -> Result<Vec<GeneratedFile>, GeneratorError>;
+
+    /// Check `config` for options this generator ignores or can't parse, such
+    /// as `split_by` on a generator without split layout or a misspelled
+    /// `scaffold` key. Called before generating; the default finds nothing.
+    fn validate_config(&self, _config: &config::GeneratorConfig) -> Vec<config::ConfigWarning> {
+        Vec::new()
+    }
+
+    /// The whitespace policy applied to `file` before it's written; the
+    /// default goes by its extension.
+    fn normalization(&self, file: &GeneratedFile) -> postprocess::Normalization {
+        file.normalization()
+    }
+}
+
+#[cfg(all(test, feature = "generate"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_platform_path() {
+        let file = |path: &str| GeneratedFile {
+            path: path.to_string(),
+            content: String::new(),
+        };
+        let expected = Path::new("out").join("src").join("types").join("shared.ts");
+        assert_eq!(
+            file("src/types/shared.ts").to_platform_path(Path::new("out")),
+            expected
+        );
+        assert_eq!(
+            file("./src//types/shared.ts").to_platform_path(Path::new("out")),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_check_relative_path() {
+        for path in [
+            "index.ts",
+            "src/types/shared.ts",
+            "./src",
+            "",
+            "a..b/c",
+            "...",
+        ] {
+            assert!(check_relative_path(path).is_ok(), "{path}");
+        }
+        for path in ["/etc/passwd", "\\\\server\\share", "C:\\temp", "c:/temp"] {
+            let err = check_relative_path(path).unwrap_err().to_string();
+            assert!(err.contains("absolute paths"), "{path}: {err}");
+        }
+        for path in ["..", "../out", "src/../../x", "src\\..\\..\\x", "src/.."] {
+            let err = check_relative_path(path).unwrap_err().to_string();
+            assert!(err.contains("`..` components"), "{path}: {err}");
+        }
+        assert_eq!(
+            check_relative_path("../../x").unwrap_err().to_string(),
+            "refusing to write `../../x`: `..` components aren't allowed"
+        );
+    }
 }
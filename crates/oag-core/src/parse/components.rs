@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 
 use super::parameter::ParameterOrRef;
 use super::request_body::RequestBodyOrRef;
-use super::response::ResponseOrRef;
+use super::response::{LinkOrRef, ResponseOrRef};
 use super::schema::SchemaOrRef;
 use super::security::SecurityScheme;
 
@@ -32,4 +32,7 @@ pub struct Components {
         skip_serializing_if = "IndexMap::is_empty"
     )]
     pub security_schemes: IndexMap<String, SecurityScheme>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub links: IndexMap<String, LinkOrRef>,
 }
@@ -41,3 +41,14 @@ pub struct MediaType {
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub examples: IndexMap<String, serde_json::Value>,
 }
+
+impl MediaType {
+    /// The `value` of each named Example Object in `examples`, by name.
+    /// `$ref` and `externalValue` examples aren't followed.
+    pub fn named_examples(&self) -> IndexMap<String, serde_json::Value> {
+        self.examples
+            .iter()
+            .filter_map(|(name, e)| Some((name.clone(), e.get("value")?.clone())))
+            .collect()
+    }
+}
@@ -11,15 +11,141 @@ pub mod server;
 pub mod spec;
 
 use crate::error::ParseError;
+use serde_yaml_ng::Value;
 use spec::OpenApiSpec;
 
-/// Parse an OpenAPI spec from YAML.
+/// Parse an OpenAPI spec from YAML. Merge keys (`<<: *anchor`) are expanded
+/// first; see [`expand_merge_keys`].
 pub fn from_yaml(input: &str) -> Result<OpenApiSpec, ParseError> {
-    let spec: OpenApiSpec = serde_yaml_ng::from_str(input)?;
+    from_yaml_value(serde_yaml_ng::from_str(input)?)
+}
+
+/// Parse an OpenAPI spec from an already loaded YAML document, e.g. one an
+/// editor keeps in memory, without serializing it back to text.
+pub fn from_yaml_value(mut value: Value) -> Result<OpenApiSpec, ParseError> {
+    prepare_yaml_value(&mut value)?;
+    let spec: OpenApiSpec = serde_yaml_ng::from_value(value)?;
     validate_version(&spec)?;
     Ok(spec)
 }
 
+/// Ready a loaded YAML document for deserializing: scalar mapping keys
+/// (`200:`, `true:`) become strings, as they would parsing from text, then
+/// merge keys are expanded; see [`expand_merge_keys`].
+///
+/// Specs and config files both go through this, so `<<` behaves the same in
+/// each instead of reaching serde as a literal `"<<"` key.
+pub fn prepare_yaml_value(value: &mut Value) -> Result<(), ParseError> {
+    stringify_keys(value);
+    expand_merge_keys(value)
+}
+
+/// Turn number and boolean mapping keys into strings, throughout `value`.
+fn stringify_keys(value: &mut Value) {
+    match value {
+        Value::Mapping(mapping) => {
+            let scalar_keys = mapping
+                .keys()
+                .any(|k| matches!(k, Value::Number(_) | Value::Bool(_)));
+            if scalar_keys {
+                *mapping = std::mem::take(mapping)
+                    .into_iter()
+                    .map(|(key, value)| match key {
+                        Value::Number(n) => (Value::String(n.to_string()), value),
+                        Value::Bool(b) => (Value::String(b.to_string()), value),
+                        key => (key, value),
+                    })
+                    .collect();
+            }
+            mapping.values_mut().for_each(stringify_keys);
+        }
+        Value::Sequence(sequence) => sequence.iter_mut().for_each(stringify_keys),
+        Value::Tagged(tagged) => stringify_keys(&mut tagged.value),
+        _ => {}
+    }
+}
+
+/// Expand YAML merge keys throughout `value`, per <https://yaml.org/type/merge.html>.
+///
+/// A `<<` entry holds a mapping or a list of mappings — usually aliases,
+/// `<<: *base` or `<<: [*a, *b]`, but inline mappings work too. Their entries
+/// are spliced in where the `<<` was, skipping keys the containing mapping
+/// sets itself; in a list, earlier mappings win over later ones. Merged
+/// mappings may use `<<` themselves. Anything else is an error naming where
+/// the `<<` is.
+pub fn expand_merge_keys(value: &mut Value) -> Result<(), ParseError> {
+    expand(value, "")
+}
+
+fn expand(value: &mut Value, path: &str) -> Result<(), ParseError> {
+    let child = |key: &Value| match key.as_str() {
+        Some(key) if path.is_empty() => key.to_string(),
+        Some(key) => format!("{path}.{key}"),
+        None => format!("{path}.?"),
+    };
+    match value {
+        Value::Mapping(mapping) if mapping.contains_key("<<") => {
+            let entries = std::mem::take(mapping);
+            for (key, mut value) in entries.clone() {
+                if key.as_str() != Some("<<") {
+                    expand(&mut value, &child(&key))?;
+                    mapping.insert(key, value);
+                    continue;
+                }
+                let at = if path.is_empty() {
+                    "the top level"
+                } else {
+                    path
+                };
+                let sources = match value {
+                    Value::Sequence(sources) => sources,
+                    source => vec![source],
+                };
+                for mut source in sources {
+                    expand(&mut source, path)?;
+                    let Value::Mapping(source) = source else {
+                        return Err(ParseError::InvalidMergeKey(format!(
+                            "`<<` at `{at}` must be a mapping or a list of mappings \
+                             (`<<: *anchor`, `<<: [*a, *b]`), found {}",
+                            kind(&source)
+                        )));
+                    };
+                    for (key, value) in source {
+                        if !entries.contains_key(&key) && !mapping.contains_key(&key) {
+                            mapping.insert(key, value);
+                        }
+                    }
+                }
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (key, value) in mapping.iter_mut() {
+                expand(value, &child(key))?;
+            }
+        }
+        Value::Sequence(sequence) => {
+            for (i, value) in sequence.iter_mut().enumerate() {
+                expand(value, &format!("{path}[{i}]"))?;
+            }
+        }
+        Value::Tagged(tagged) => expand(&mut tagged.value, path)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn kind(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Sequence(_) => "a list",
+        Value::Mapping(_) => "a mapping",
+        Value::Tagged(_) => "a tagged value",
+    }
+}
+
 /// Parse an OpenAPI spec from JSON.
 pub fn from_json(input: &str) -> Result<OpenApiSpec, ParseError> {
     let spec: OpenApiSpec = serde_json::from_str(input)?;
@@ -27,6 +153,47 @@ pub fn from_json(input: &str) -> Result<OpenApiSpec, ParseError> {
     Ok(spec)
 }
 
+/// Parse an OpenAPI spec from an already loaded JSON document.
+pub fn from_json_value(value: serde_json::Value) -> Result<OpenApiSpec, ParseError> {
+    let spec: OpenApiSpec = serde_json::from_value(value)?;
+    validate_version(&spec)?;
+    Ok(spec)
+}
+
+/// Serialize a spec back to YAML.
+pub fn to_yaml(spec: &OpenApiSpec) -> Result<String, ParseError> {
+    Ok(serde_yaml_ng::to_string(spec)?)
+}
+
+/// Serialize a spec back to pretty-printed JSON.
+pub fn to_json(spec: &OpenApiSpec) -> Result<String, ParseError> {
+    Ok(serde_json::to_string_pretty(spec)?)
+}
+
+/// Return a canonical copy of the spec for stable diffs: `paths`, `webhooks` and
+/// every `components` map are sorted by key, and an empty `components` object is
+/// dropped. Other empty arrays and maps are already omitted on serialization.
+pub fn normalize_spec(spec: &OpenApiSpec) -> OpenApiSpec {
+    let mut spec = spec.clone();
+    spec.paths.sort_keys();
+    spec.webhooks.sort_keys();
+    if let Some(components) = &mut spec.components {
+        components.schemas.sort_keys();
+        components.responses.sort_keys();
+        components.parameters.sort_keys();
+        components.request_bodies.sort_keys();
+        components.security_schemes.sort_keys();
+    }
+    if spec
+        .components
+        .as_ref()
+        .is_some_and(|c| *c == components::Components::default())
+    {
+        spec.components = None;
+    }
+    spec
+}
+
 fn validate_version(spec: &OpenApiSpec) -> Result<(), ParseError> {
     if !spec.openapi.starts_with("3.") {
         return Err(ParseError::UnsupportedVersion(spec.openapi.clone()));
@@ -5,6 +5,7 @@ use super::parameter::ParameterOrRef;
 use super::request_body::RequestBodyOrRef;
 use super::response::ResponseOrRef;
 use super::security::SecurityRequirement;
+use super::spec::ExternalDocs;
 
 /// An API operation.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -18,6 +19,9 @@ pub struct Operation {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 
+    #[serde(rename = "externalDocs", skip_serializing_if = "Option::is_none")]
+    pub external_docs: Option<ExternalDocs>,
+
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub tags: Vec<String>,
 
@@ -35,6 +39,14 @@ pub struct Operation {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub security: Option<Vec<SecurityRequirement>>,
+
+    /// Callbacks keyed by name, kept raw: no generator emits them yet.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub callbacks: IndexMap<String, serde_json::Value>,
+
+    /// Any other fields, such as `x-` vendor extensions, kept raw.
+    #[serde(flatten)]
+    pub extensions: IndexMap<String, serde_json::Value>,
 }
 
 /// A path item, containing operations keyed by HTTP method.
@@ -66,3 +78,22 @@ pub struct PathItem {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub trace: Option<Operation>,
 }
+
+impl PathItem {
+    /// The operations this item declares, with their upper-case HTTP method,
+    /// in the order `get`, `post`, `put`, `delete`, `patch`, `options`, `head`, `trace`.
+    pub fn operations(&self) -> impl Iterator<Item = (&'static str, &Operation)> {
+        [
+            ("GET", &self.get),
+            ("POST", &self.post),
+            ("PUT", &self.put),
+            ("DELETE", &self.delete),
+            ("PATCH", &self.patch),
+            ("OPTIONS", &self.options),
+            ("HEAD", &self.head),
+            ("TRACE", &self.trace),
+        ]
+        .into_iter()
+        .filter_map(|(method, op)| Some((method, op.as_ref()?)))
+    }
+}
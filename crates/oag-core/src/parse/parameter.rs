@@ -1,5 +1,8 @@
+use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
+use super::media_type::MediaType;
+
 use super::schema::SchemaOrRef;
 
 /// Parameter location.
@@ -32,17 +35,46 @@ pub struct Parameter {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub schema: Option<SchemaOrRef>,
 
+    /// Alternative to `schema` for complex serialized values: a single media
+    /// type (e.g. `application/json`) whose schema describes the decoded value.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub content: IndexMap<String, MediaType>,
+
+    /// Whether a query parameter may be sent with an empty value (`?flag=`).
+    #[serde(rename = "allowEmptyValue", skip_serializing_if = "Option::is_none")]
+    pub allow_empty_value: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub style: Option<String>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub explode: Option<bool>,
 
+    /// Whether reserved characters (`/`, `?`, ...) may appear unencoded in
+    /// the value. Only meaningful for query parameters in the spec, but
+    /// generators honor it wherever the value is interpolated.
+    #[serde(rename = "allowReserved", skip_serializing_if = "Option::is_none")]
+    pub allow_reserved: Option<bool>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub example: Option<serde_json::Value>,
+
+    /// Named Example Objects; the payload is each one's `value`.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub examples: IndexMap<String, serde_json::Value>,
+
+    /// `x-oag-query-object`: send this object query parameter as a flattened
+    /// map in `bracket` or `dot` notation, overriding the configured default.
+    #[serde(
+        rename = "x-oag-query-object",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub query_object: Option<QueryObjectNotation>,
 }
 
 /// A reference or inline parameter.
+#[allow(clippy::large_enum_variant)] // parsed once, matched everywhere
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ParameterOrRef {
@@ -52,3 +84,23 @@ pub enum ParameterOrRef {
     },
     Parameter(Parameter),
 }
+
+/// How an object query parameter's keys are spelled in the query string.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum QueryObjectNotation {
+    /// `filter[name]=x&filter[age]=3`, as `deepObject` specifies.
+    #[default]
+    Bracket,
+    /// `filter.name=x&filter.age=3`.
+    Dot,
+}
+
+impl QueryObjectNotation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueryObjectNotation::Bracket => "bracket",
+            QueryObjectNotation::Dot => "dot",
+        }
+    }
+}
@@ -1,11 +1,14 @@
+use std::fmt;
+
 use indexmap::IndexMap;
+use serde::Serialize;
 
 use super::components::Components;
 use super::media_type::MediaType;
 use super::operation::{Operation, PathItem};
 use super::parameter::{Parameter, ParameterOrRef};
 use super::request_body::{RequestBody, RequestBodyOrRef};
-use super::response::{Response, ResponseOrRef};
+use super::response::{Link, LinkOrRef, Response, ResponseOrRef};
 use super::schema::{Schema, SchemaOrRef};
 use super::spec::OpenApiSpec;
 use crate::error::ResolveError;
@@ -14,44 +17,85 @@ use crate::error::ResolveError;
 /// with no remaining references. Detects circular references.
 pub struct RefResolver<'a> {
     components: Option<&'a Components>,
+    /// Set by [`unresolved_refs`](Self::unresolved_refs): failed lookups are
+    /// recorded here, leaving the `$ref` in place, instead of returned.
+    findings: Option<Vec<RefFinding>>,
+}
+
+/// A `$ref` into the components that doesn't resolve.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RefFinding {
+    /// The components section the reference is looked up in, e.g. `parameters`.
+    pub section: String,
+    /// The reference as written, e.g. `#/components/parameters/Limit`.
+    #[serde(rename = "ref")]
+    pub ref_path: String,
+    pub message: String,
+}
+
+impl fmt::Display for RefFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` ({}): {}",
+            self.ref_path, self.section, self.message
+        )
+    }
 }
 
 impl<'a> RefResolver<'a> {
     pub fn new(spec: &'a OpenApiSpec) -> Self {
         Self {
             components: spec.components.as_ref(),
+            findings: None,
         }
     }
 
-    /// Resolve the entire spec in place, returning a spec with no `$ref` nodes.
+    /// Every `$ref` to a shared parameter, request body, response or link in
+    /// `spec` that doesn't resolve, where [`resolve_spec`](Self::resolve_spec)
+    /// stops at the first.
+    pub fn unresolved_refs(&mut self, spec: &OpenApiSpec) -> Vec<RefFinding> {
+        self.findings = Some(Vec::new());
+        // Lookups record their errors instead of returning them.
+        let _ = self.resolve_spec(spec);
+        self.findings.take().unwrap_or_default()
+    }
+
+    /// Resolve the entire spec, returning a copy with no `$ref` nodes.
     pub fn resolve_spec(&mut self, spec: &OpenApiSpec) -> Result<OpenApiSpec, ResolveError> {
         let mut resolved = spec.clone();
+        self.resolve_spec_in_place(&mut resolved)?;
+        Ok(resolved)
+    }
 
+    /// Resolve every `$ref` in `spec` in place, against the components this
+    /// resolver was created with. `spec` is normally a copy of that spec.
+    pub fn resolve_spec_in_place(&mut self, spec: &mut OpenApiSpec) -> Result<(), ResolveError> {
         // Resolve all paths
-        for (_path, item) in &mut resolved.paths {
+        for item in spec.paths.values_mut() {
+            self.resolve_path_item(item)?;
+        }
+
+        // Resolve webhooks (same shape as paths)
+        for item in spec.webhooks.values_mut() {
             self.resolve_path_item(item)?;
         }
 
         // Resolve component schemas
-        if let Some(ref mut components) = resolved.components {
-            let schema_names: Vec<String> = components.schemas.keys().cloned().collect();
-            for name in schema_names {
-                let schema = components.schemas.get(&name).unwrap().clone();
-                let resolved_schema = self.resolve_schema_or_ref(&schema)?;
-                components.schemas.insert(name, resolved_schema);
+        if let Some(ref mut components) = spec.components {
+            for schema in components.schemas.values_mut() {
+                self.resolve_schema_or_ref_in_place(schema)?;
             }
         }
 
-        Ok(resolved)
+        Ok(())
     }
 
     fn resolve_path_item(&mut self, item: &mut PathItem) -> Result<(), ResolveError> {
         // Resolve path-level parameters
-        let mut resolved_params = Vec::new();
-        for p in &item.parameters {
-            resolved_params.push(self.resolve_parameter_or_ref(p)?);
+        for p in &mut item.parameters {
+            self.resolve_parameter_or_ref(p)?;
         }
-        item.parameters = resolved_params;
 
         // Resolve each operation
         macro_rules! resolve_op {
@@ -73,202 +117,263 @@ impl<'a> RefResolver<'a> {
     }
 
     fn resolve_operation(&mut self, op: &mut Operation) -> Result<(), ResolveError> {
-        // Resolve parameters
-        let mut resolved_params = Vec::new();
-        for p in &op.parameters {
-            resolved_params.push(self.resolve_parameter_or_ref(p)?);
+        for p in &mut op.parameters {
+            self.resolve_parameter_or_ref(p)?;
         }
-        op.parameters = resolved_params;
-
-        // Resolve request body
-        if let Some(ref body) = op.request_body {
-            let resolved = self.resolve_request_body_or_ref(body)?;
-            op.request_body = Some(resolved);
+        if let Some(ref mut body) = op.request_body {
+            self.resolve_request_body_or_ref(body)?;
         }
-
-        // Resolve responses
-        let mut resolved_responses = IndexMap::new();
-        for (status, resp) in &op.responses {
-            resolved_responses.insert(status.clone(), self.resolve_response_or_ref(resp)?);
+        for resp in op.responses.values_mut() {
+            self.resolve_response_or_ref(resp)?;
         }
-        op.responses = resolved_responses;
-
         Ok(())
     }
 
+    /// Resolve a schema, returning a copy whose nested schemas are resolved.
     pub fn resolve_schema_or_ref(
         &mut self,
         schema_or_ref: &SchemaOrRef,
     ) -> Result<SchemaOrRef, ResolveError> {
+        let mut resolved = schema_or_ref.clone();
+        self.resolve_schema_or_ref_in_place(&mut resolved)?;
+        Ok(resolved)
+    }
+
+    fn resolve_schema_or_ref_in_place(
+        &mut self,
+        schema_or_ref: &mut SchemaOrRef,
+    ) -> Result<(), ResolveError> {
         match schema_or_ref {
-            SchemaOrRef::Ref { .. } => {
-                // Preserve schema $ref pointers. Component schemas are resolved
-                // separately when iterating components.schemas. This allows
-                // schema_or_ref_to_ir_type to produce IrType::Ref(name) instead
-                // of inlining the full schema.
-                Ok(schema_or_ref.clone())
-            }
-            SchemaOrRef::Schema(schema) => {
-                let resolved = self.resolve_schema(schema)?;
-                Ok(SchemaOrRef::Schema(Box::new(resolved)))
-            }
+            // Preserve schema $ref pointers. Component schemas are resolved
+            // separately when iterating components.schemas. This allows
+            // schema_or_ref_to_ir_type to produce IrType::Ref(name) instead
+            // of inlining the full schema.
+            SchemaOrRef::Ref { .. } => Ok(()),
+            SchemaOrRef::Schema(schema) => self.resolve_schema(schema),
         }
     }
 
-    fn resolve_schema(&mut self, schema: &Schema) -> Result<Schema, ResolveError> {
-        let mut resolved = schema.clone();
+    fn resolve_schema(&mut self, schema: &mut Schema) -> Result<(), ResolveError> {
+        schema.normalize_exclusive_bounds();
 
-        // Resolve properties
-        let mut resolved_props = IndexMap::new();
-        for (name, prop) in &schema.properties {
-            resolved_props.insert(name.clone(), self.resolve_schema_or_ref(prop)?);
+        for prop in schema.properties.values_mut() {
+            self.resolve_schema_or_ref_in_place(prop)?;
+        }
+        if let Some(ref mut items) = schema.items {
+            self.resolve_schema_or_ref_in_place(items)?;
+        }
+        for sub in schema
+            .all_of
+            .iter_mut()
+            .chain(&mut schema.one_of)
+            .chain(&mut schema.any_of)
+        {
+            self.resolve_schema_or_ref_in_place(sub)?;
         }
-        resolved.properties = resolved_props;
 
-        // Resolve items
-        if let Some(ref items) = schema.items {
-            resolved.items = Some(Box::new(self.resolve_schema_or_ref(items)?));
+        // Resolve propertyNames / not / if / then / else
+        for sub in [
+            &mut schema.property_names,
+            &mut schema.not,
+            &mut schema.if_schema,
+            &mut schema.then_schema,
+            &mut schema.else_schema,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            self.resolve_schema_or_ref_in_place(sub)?;
         }
 
-        // Resolve allOf, oneOf, anyOf
-        resolved.all_of = schema
-            .all_of
-            .iter()
-            .map(|s| self.resolve_schema_or_ref(s))
-            .collect::<Result<Vec<_>, _>>()?;
-        resolved.one_of = schema
-            .one_of
-            .iter()
-            .map(|s| self.resolve_schema_or_ref(s))
-            .collect::<Result<Vec<_>, _>>()?;
-        resolved.any_of = schema
-            .any_of
-            .iter()
-            .map(|s| self.resolve_schema_or_ref(s))
-            .collect::<Result<Vec<_>, _>>()?;
-
-        // Resolve additionalProperties
-        if let Some(super::schema::AdditionalProperties::Schema(ref s)) =
+        if let Some(super::schema::AdditionalProperties::Schema(ref mut s)) =
             schema.additional_properties
         {
-            resolved.additional_properties = Some(super::schema::AdditionalProperties::Schema(
-                Box::new(self.resolve_schema_or_ref(s)?),
-            ));
+            self.resolve_schema_or_ref_in_place(s)?;
         }
 
-        Ok(resolved)
+        Ok(())
     }
 
-    fn resolve_parameter_or_ref(
-        &mut self,
-        param: &ParameterOrRef,
-    ) -> Result<ParameterOrRef, ResolveError> {
-        match param {
-            ParameterOrRef::Ref { ref_path } => {
-                let resolved = self.lookup_parameter(ref_path)?;
-                Ok(ParameterOrRef::Parameter(resolved))
-            }
-            ParameterOrRef::Parameter(p) => {
-                let mut resolved = p.clone();
-                if let Some(ref s) = p.schema {
-                    resolved.schema = Some(self.resolve_schema_or_ref(s)?);
-                }
-                Ok(ParameterOrRef::Parameter(resolved))
+    fn resolve_parameter_or_ref(&mut self, param: &mut ParameterOrRef) -> Result<(), ResolveError> {
+        if let ParameterOrRef::Ref { ref_path } = param
+            && let Some(found) = self.record(ref_path, "parameters", Self::lookup_parameter)?
+        {
+            *param = ParameterOrRef::Parameter(found);
+        }
+        if let ParameterOrRef::Parameter(p) = param {
+            if let Some(ref mut s) = p.schema {
+                self.resolve_schema_or_ref_in_place(s)?;
             }
+            self.resolve_media_types(&mut p.content)?;
         }
+        Ok(())
     }
 
     fn resolve_request_body_or_ref(
         &mut self,
-        body: &RequestBodyOrRef,
-    ) -> Result<RequestBodyOrRef, ResolveError> {
-        match body {
-            RequestBodyOrRef::Ref { ref_path } => {
-                let resolved = self.lookup_request_body(ref_path)?;
-                let mut rb = resolved;
-                self.resolve_media_types(&mut rb.content)?;
-                Ok(RequestBodyOrRef::RequestBody(rb))
-            }
-            RequestBodyOrRef::RequestBody(rb) => {
-                let mut resolved = rb.clone();
-                self.resolve_media_types(&mut resolved.content)?;
-                Ok(RequestBodyOrRef::RequestBody(resolved))
-            }
+        body: &mut RequestBodyOrRef,
+    ) -> Result<(), ResolveError> {
+        if let RequestBodyOrRef::Ref { ref_path } = body
+            && let Some(found) =
+                self.record(ref_path, "requestBodies", Self::lookup_request_body)?
+        {
+            *body = RequestBodyOrRef::RequestBody(found);
         }
+        if let RequestBodyOrRef::RequestBody(rb) = body {
+            self.resolve_media_types(&mut rb.content)?;
+        }
+        Ok(())
     }
 
-    fn resolve_response_or_ref(
-        &mut self,
-        resp: &ResponseOrRef,
-    ) -> Result<ResponseOrRef, ResolveError> {
-        match resp {
-            ResponseOrRef::Ref { ref_path } => {
-                let resolved = self.lookup_response(ref_path)?;
-                let mut r = resolved;
-                self.resolve_media_types(&mut r.content)?;
-                Ok(ResponseOrRef::Response(r))
-            }
-            ResponseOrRef::Response(r) => {
-                let mut resolved = r.clone();
-                self.resolve_media_types(&mut resolved.content)?;
-                Ok(ResponseOrRef::Response(resolved))
+    fn resolve_response_or_ref(&mut self, resp: &mut ResponseOrRef) -> Result<(), ResolveError> {
+        if let ResponseOrRef::Ref { ref_path } = resp
+            && let Some(found) = self.record(ref_path, "responses", Self::lookup_response)?
+        {
+            *resp = ResponseOrRef::Response(found);
+        }
+        if let ResponseOrRef::Response(r) = resp {
+            self.resolve_media_types(&mut r.content)?;
+            for link in r.links.values_mut() {
+                if let LinkOrRef::Ref { ref_path } = link
+                    && let Some(found) = self.record(ref_path, "links", Self::lookup_link)?
+                {
+                    *link = LinkOrRef::Link(found);
+                }
             }
         }
+        Ok(())
     }
 
     fn resolve_media_types(
         &mut self,
         content: &mut IndexMap<String, MediaType>,
     ) -> Result<(), ResolveError> {
-        let keys: Vec<String> = content.keys().cloned().collect();
-        for key in keys {
-            let mt = content.get(&key).unwrap().clone();
-            let mut resolved_mt = mt;
-            if let Some(ref s) = resolved_mt.schema {
-                resolved_mt.schema = Some(self.resolve_schema_or_ref(s)?);
+        for mt in content.values_mut() {
+            for s in [&mut mt.schema, &mut mt.item_schema].into_iter().flatten() {
+                self.resolve_schema_or_ref_in_place(s)?;
             }
-            if let Some(ref s) = resolved_mt.item_schema {
-                resolved_mt.item_schema = Some(self.resolve_schema_or_ref(s)?);
-            }
-            content.insert(key, resolved_mt);
         }
         Ok(())
     }
 
     // Lookup helpers
 
+    /// Run `lookup` for `ref_path`, turning its error into a finding when
+    /// collecting them; `None` leaves the `$ref` as it is.
+    fn record<T>(
+        &mut self,
+        ref_path: &str,
+        section: &str,
+        lookup: impl FnOnce(&Self, &str) -> Result<T, ResolveError>,
+    ) -> Result<Option<T>, ResolveError> {
+        match lookup(self, ref_path) {
+            Ok(found) => Ok(Some(found)),
+            Err(error) => match self.findings {
+                Some(ref mut findings) => {
+                    findings.push(RefFinding {
+                        section: section.to_string(),
+                        ref_path: ref_path.to_string(),
+                        message: error.to_string(),
+                    });
+                    Ok(None)
+                }
+                None => Err(error),
+            },
+        }
+    }
+
     fn lookup_parameter(&self, ref_path: &str) -> Result<Parameter, ResolveError> {
-        let name = parse_ref_name(ref_path, "parameters")?;
-        self.components
-            .and_then(|c| c.parameters.get(name))
-            .and_then(|p| match p {
-                ParameterOrRef::Parameter(param) => Some(param.clone()),
-                _ => None,
-            })
-            .ok_or_else(|| ResolveError::RefTargetNotFound(ref_path.to_string()))
+        self.follow(
+            ref_path,
+            "parameters",
+            |c| &c.parameters,
+            |p| match p {
+                ParameterOrRef::Parameter(param) => Entry::Object(param),
+                ParameterOrRef::Ref { ref_path } => Entry::Ref(ref_path),
+            },
+        )
     }
 
     fn lookup_request_body(&self, ref_path: &str) -> Result<RequestBody, ResolveError> {
-        let name = parse_ref_name(ref_path, "requestBodies")?;
-        self.components
-            .and_then(|c| c.request_bodies.get(name))
-            .and_then(|rb| match rb {
-                RequestBodyOrRef::RequestBody(body) => Some(body.clone()),
-                _ => None,
-            })
-            .ok_or_else(|| ResolveError::RefTargetNotFound(ref_path.to_string()))
+        self.follow(
+            ref_path,
+            "requestBodies",
+            |c| &c.request_bodies,
+            |rb| match rb {
+                RequestBodyOrRef::RequestBody(body) => Entry::Object(body),
+                RequestBodyOrRef::Ref { ref_path } => Entry::Ref(ref_path),
+            },
+        )
     }
 
     fn lookup_response(&self, ref_path: &str) -> Result<Response, ResolveError> {
-        let name = parse_ref_name(ref_path, "responses")?;
-        self.components
-            .and_then(|c| c.responses.get(name))
-            .and_then(|r| match r {
-                ResponseOrRef::Response(resp) => Some(resp.clone()),
-                _ => None,
-            })
-            .ok_or_else(|| ResolveError::RefTargetNotFound(ref_path.to_string()))
+        self.follow(
+            ref_path,
+            "responses",
+            |c| &c.responses,
+            |r| match r {
+                ResponseOrRef::Response(resp) => Entry::Object(resp),
+                ResponseOrRef::Ref { ref_path } => Entry::Ref(ref_path),
+            },
+        )
+    }
+
+    fn lookup_link(&self, ref_path: &str) -> Result<Link, ResolveError> {
+        self.follow(
+            ref_path,
+            "links",
+            |c| &c.links,
+            |l| match l {
+                LinkOrRef::Link(link) => Entry::Object(link),
+                LinkOrRef::Ref { ref_path } => Entry::Ref(ref_path),
+            },
+        )
     }
+
+    /// Look `ref_path` up in `section` of the components, following entries
+    /// that are themselves `$ref`s (`A -> B -> C`) to the object at the end of
+    /// the chain. A chain that comes back on itself, or is longer than
+    /// [`MAX_REF_DEPTH`], is an error.
+    fn follow<E: 'a, T: Clone + 'a>(
+        &self,
+        ref_path: &str,
+        section: &str,
+        entries: impl Fn(&'a Components) -> &'a IndexMap<String, E>,
+        entry: impl Fn(&'a E) -> Entry<'a, T>,
+    ) -> Result<T, ResolveError> {
+        let mut visited: Vec<&str> = Vec::new();
+        let mut current = ref_path;
+        loop {
+            if visited.contains(&current) {
+                visited.push(current);
+                return Err(ResolveError::CircularRef(visited.join(" -> ")));
+            }
+            if visited.len() == MAX_REF_DEPTH {
+                return Err(ResolveError::UnresolvedRef(format!(
+                    "{ref_path} (more than {MAX_REF_DEPTH} `$ref` hops)"
+                )));
+            }
+            visited.push(current);
+            let name = parse_ref_name(current, section)?;
+            let found = self
+                .components
+                .and_then(|c| entries(c).get(name))
+                .ok_or_else(|| ResolveError::RefTargetNotFound(current.to_string()))?;
+            match entry(found) {
+                Entry::Object(object) => return Ok(object.clone()),
+                Entry::Ref(next) => current = next,
+            }
+        }
+    }
+}
+
+/// Most `$ref` hops followed from one reference to a component.
+const MAX_REF_DEPTH: usize = 32;
+
+/// A component entry: the object itself, or a `$ref` to another entry.
+enum Entry<'e, T> {
+    Object(&'e T),
+    Ref(&'e str),
 }
 
 /// Parse a `$ref` path like `#/components/schemas/Foo` and extract the name.
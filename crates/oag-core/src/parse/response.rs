@@ -13,9 +13,13 @@ pub struct Response {
 
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub headers: IndexMap<String, serde_json::Value>,
+
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub links: IndexMap<String, LinkOrRef>,
 }
 
 /// A reference or inline response.
+#[allow(clippy::large_enum_variant)] // parsed once, matched everywhere
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ResponseOrRef {
@@ -25,3 +29,36 @@ pub enum ResponseOrRef {
     },
     Response(Response),
 }
+
+/// A design-time link from a response to another operation.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct Link {
+    #[serde(rename = "operationId", skip_serializing_if = "Option::is_none")]
+    pub operation_id: Option<String>,
+
+    #[serde(rename = "operationRef", skip_serializing_if = "Option::is_none")]
+    pub operation_ref: Option<String>,
+
+    /// Target parameter name (optionally `path.`/`query.`-qualified) to a
+    /// runtime expression or constant.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub parameters: IndexMap<String, serde_json::Value>,
+
+    #[serde(rename = "requestBody", skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<serde_json::Value>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A reference or inline link.
+#[allow(clippy::large_enum_variant)] // parsed once, matched everywhere
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum LinkOrRef {
+    Ref {
+        #[serde(rename = "$ref")]
+        ref_path: String,
+    },
+    Link(Link),
+}
@@ -1,3 +1,5 @@
+use std::collections::HashSet;
+
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 
@@ -76,6 +78,9 @@ pub struct Schema {
     )]
     pub additional_properties: Option<AdditionalProperties>,
 
+    #[serde(rename = "propertyNames", skip_serializing_if = "Option::is_none")]
+    pub property_names: Option<Box<SchemaOrRef>>,
+
     // Array items
     #[serde(skip_serializing_if = "Option::is_none")]
     pub items: Option<Box<SchemaOrRef>>,
@@ -93,6 +98,20 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub discriminator: Option<Discriminator>,
 
+    // Negation and conditionals — parsed so they round-trip, only approximated
+    // by the transform
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub not: Option<Box<SchemaOrRef>>,
+
+    #[serde(rename = "if", skip_serializing_if = "Option::is_none")]
+    pub if_schema: Option<Box<SchemaOrRef>>,
+
+    #[serde(rename = "then", skip_serializing_if = "Option::is_none")]
+    pub then_schema: Option<Box<SchemaOrRef>>,
+
+    #[serde(rename = "else", skip_serializing_if = "Option::is_none")]
+    pub else_schema: Option<Box<SchemaOrRef>>,
+
     // Enum values
     #[serde(rename = "enum", default, skip_serializing_if = "Vec::is_empty")]
     pub enum_values: Vec<serde_json::Value>,
@@ -103,9 +122,9 @@ pub struct Schema {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub maximum: Option<f64>,
     #[serde(rename = "exclusiveMinimum", skip_serializing_if = "Option::is_none")]
-    pub exclusive_minimum: Option<f64>,
+    pub exclusive_minimum: Option<ExclusiveBound>,
     #[serde(rename = "exclusiveMaximum", skip_serializing_if = "Option::is_none")]
-    pub exclusive_maximum: Option<f64>,
+    pub exclusive_maximum: Option<ExclusiveBound>,
 
     // String constraints
     #[serde(rename = "minLength", skip_serializing_if = "Option::is_none")]
@@ -129,6 +148,9 @@ pub struct Schema {
     #[serde(rename = "writeOnly", skip_serializing_if = "Option::is_none")]
     pub write_only: Option<bool>,
 
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecated: Option<bool>,
+
     // Const
     #[serde(rename = "const", skip_serializing_if = "Option::is_none")]
     pub const_value: Option<serde_json::Value>,
@@ -138,6 +160,109 @@ pub struct Schema {
     pub example: Option<serde_json::Value>,
 }
 
+impl Schema {
+    /// Rewrite 3.0 boolean `exclusiveMinimum`/`exclusiveMaximum` flags into the 3.1
+    /// numeric form: `minimum: 0, exclusiveMinimum: true` becomes
+    /// `exclusiveMinimum: 0`. A `false` flag, or one without a bound, is dropped.
+    pub fn normalize_exclusive_bounds(&mut self) {
+        fn normalize(bound: &mut Option<f64>, exclusive: &mut Option<ExclusiveBound>) {
+            if let Some(ExclusiveBound::Bool(flag)) = *exclusive {
+                *exclusive = match (flag, bound.take()) {
+                    (true, Some(value)) => Some(ExclusiveBound::Number(value)),
+                    (_, value) => {
+                        *bound = value;
+                        None
+                    }
+                };
+            }
+        }
+        normalize(&mut self.minimum, &mut self.exclusive_minimum);
+        normalize(&mut self.maximum, &mut self.exclusive_maximum);
+    }
+
+    /// The keywords on this schema, or any schema nested in it, that generated
+    /// types can't express (`not`, `if`, `then`, `else`), in that order.
+    pub fn unsupported_keywords(&self) -> Vec<&'static str> {
+        let mut found = [false; 4];
+        self.collect_unsupported(&mut found);
+        ["not", "if", "then", "else"]
+            .into_iter()
+            .zip(found)
+            .filter_map(|(keyword, present)| present.then_some(keyword))
+            .collect()
+    }
+
+    fn collect_unsupported(&self, found: &mut [bool; 4]) {
+        let conditionals = [
+            &self.not,
+            &self.if_schema,
+            &self.then_schema,
+            &self.else_schema,
+        ];
+        for (i, keyword) in conditionals.iter().enumerate() {
+            found[i] |= keyword.is_some();
+        }
+        for schema in self.subschemas() {
+            schema.collect_unsupported(found);
+        }
+    }
+
+    /// The string `enum` values listed more than once on this schema, or any
+    /// schema nested in it, each once and in the order they repeat.
+    pub fn duplicate_enum_values(&self) -> Vec<String> {
+        let mut duplicates = Vec::new();
+        self.collect_duplicate_enum_values(&mut duplicates);
+        duplicates
+    }
+
+    fn collect_duplicate_enum_values(&self, duplicates: &mut Vec<String>) {
+        let mut seen = HashSet::new();
+        for value in self.enum_values.iter().filter_map(|v| v.as_str()) {
+            if !seen.insert(value) && !duplicates.iter().any(|d| d == value) {
+                duplicates.push(value.to_string());
+            }
+        }
+        for schema in self.subschemas() {
+            schema.collect_duplicate_enum_values(duplicates);
+        }
+    }
+
+    /// The inline schemas directly nested in this one.
+    fn subschemas(&self) -> impl Iterator<Item = &Schema> {
+        let additional = match &self.additional_properties {
+            Some(AdditionalProperties::Schema(s)) => Some(s.as_ref()),
+            _ => None,
+        };
+        let conditionals = [
+            &self.not,
+            &self.if_schema,
+            &self.then_schema,
+            &self.else_schema,
+        ];
+        self.properties
+            .values()
+            .chain(self.items.as_deref())
+            .chain(&self.all_of)
+            .chain(&self.one_of)
+            .chain(&self.any_of)
+            .chain(additional)
+            .chain(conditionals.into_iter().flatten().map(|s| s.as_ref()))
+            .filter_map(|sub| match sub {
+                SchemaOrRef::Schema(schema) => Some(schema.as_ref()),
+                SchemaOrRef::Ref { .. } => None,
+            })
+    }
+}
+
+/// `exclusiveMinimum`/`exclusiveMaximum`: a number in OpenAPI 3.1, or in 3.0 a
+/// boolean that makes `minimum`/`maximum` exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExclusiveBound {
+    Number(f64),
+    Bool(bool),
+}
+
 /// `additionalProperties` can be a boolean or a schema.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
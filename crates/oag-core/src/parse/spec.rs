@@ -45,6 +45,14 @@ pub struct License {
     pub url: Option<String>,
 }
 
+/// A link to additional documentation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExternalDocs {
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
 /// Tag definition.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Tag {
@@ -66,6 +74,11 @@ pub struct OpenApiSpec {
     #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
     pub paths: IndexMap<String, PathItem>,
 
+    /// Incoming requests the API may send to consumers (OpenAPI 3.1+), keyed by
+    /// webhook name. Each entry has the same shape as a path item.
+    #[serde(default, skip_serializing_if = "IndexMap::is_empty")]
+    pub webhooks: IndexMap<String, PathItem>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub components: Option<Components>,
 
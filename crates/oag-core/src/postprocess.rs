@@ -0,0 +1,277 @@
+//! Whitespace normalization of generated files.
+//!
+//! Templates leave stray blank lines behind (conditional blocks, loops), so
+//! every file gets a [`Normalization`] policy before it's written: picked from
+//! its extension by [`GeneratedFile::normalization`], or by the generator that
+//! emitted it through [`CodeGenerator::normalization`](crate::CodeGenerator::normalization).
+
+use crate::{CodeGenerator, GeneratedFile};
+
+/// How a generated file's whitespace is cleaned up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Normalization {
+    /// LF line endings, at most one blank line in a row, a trailing newline.
+    Collapse,
+    /// Written exactly as emitted, e.g. YAML whose block scalars keep their
+    /// blank lines.
+    AsIs,
+    /// LF line endings and PEP 8 blank lines: two before top-level `def`s
+    /// and `class`es, one before methods, none after a decorator or a block
+    /// opener or inside brackets, at most two elsewhere. Triple-quoted
+    /// strings are left alone.
+    PythonPep8,
+}
+
+impl Normalization {
+    /// The default policy for a file at `path`: `AsIs` for YAML, `PythonPep8`
+    /// for Python, `Collapse` for everything else.
+    pub fn for_path(path: &str) -> Self {
+        let extension = path.rsplit_once('.').map_or("", |(_, ext)| ext);
+        match extension {
+            "yaml" | "yml" => Normalization::AsIs,
+            "py" => Normalization::PythonPep8,
+            _ => Normalization::Collapse,
+        }
+    }
+
+    /// `content` with this policy applied.
+    pub fn apply(self, content: &str) -> String {
+        match self {
+            Normalization::Collapse => collapse_blank_lines(content),
+            Normalization::AsIs => content.to_string(),
+            Normalization::PythonPep8 => python_blank_lines(content),
+        }
+    }
+}
+
+/// Apply each file's policy, as declared by `generator`, in place.
+pub fn normalize_files(generator: &dyn CodeGenerator, files: &mut [GeneratedFile]) {
+    for file in files {
+        file.content = generator.normalization(file).apply(&file.content);
+    }
+}
+
+/// Convert CRLF line endings to LF so generated files are POSIX text
+/// regardless of how templates were checked out.
+pub fn normalize_line_endings(content: &str) -> String {
+    content.replace("\r\n", "\n")
+}
+
+/// Normalize whitespace in generated code:
+/// - Convert CRLF line endings to LF
+/// - Collapse 3+ consecutive newlines into 2 (max one blank line)
+/// - Ensure trailing newline
+pub fn collapse_blank_lines(content: &str) -> String {
+    let content = normalize_line_endings(content);
+    let mut result = String::with_capacity(content.len());
+    let mut newline_count = 0;
+    for ch in content.chars() {
+        if ch == '\n' {
+            newline_count += 1;
+            if newline_count <= 2 {
+                result.push(ch);
+            }
+        } else {
+            newline_count = 0;
+            result.push(ch);
+        }
+    }
+    if !result.ends_with('\n') {
+        result.push('\n');
+    }
+    result
+}
+
+/// Rewrite every run of blank lines in Python source to the PEP 8 count for
+/// what follows it. Ends the file with exactly one newline.
+fn python_blank_lines(content: &str) -> String {
+    let content = normalize_line_endings(content);
+    let mut lines: Vec<&str> = Vec::new();
+    let mut blanks = 0;
+    let mut in_string = false;
+    // Indentation of each enclosing block opener, and whether it's a class.
+    let mut blocks: Vec<(usize, bool)> = Vec::new();
+
+    for line in content.lines() {
+        if line.trim().is_empty() {
+            blanks += 1;
+            continue;
+        }
+        let keep = match lines.last() {
+            _ if in_string => blanks,
+            Some(previous) => {
+                let indent = line.len() - line.trim_start().len();
+                blocks.retain(|&(opener, _)| opener < indent);
+                let in_class = blocks.last().is_some_and(|&(_, class)| class);
+                python_blanks_between(previous, line, blanks, in_class)
+            }
+            None => 0,
+        };
+        lines.extend(std::iter::repeat_n("", keep));
+        lines.push(line);
+        blanks = 0;
+
+        if !in_string && line.trim_end().ends_with(':') && !line.trim_start().starts_with('#') {
+            let indent = line.len() - line.trim_start().len();
+            blocks.push((indent, line.trim_start().starts_with("class ")));
+        }
+        if line.matches("\"\"\"").count() % 2 == 1 {
+            in_string = !in_string;
+        }
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+/// How many of the `blanks` blank lines between `previous` and `line` PEP 8
+/// keeps. `in_class` is whether `line` sits directly in a class body.
+fn python_blanks_between(previous: &str, line: &str, blanks: usize, in_class: bool) -> usize {
+    let trimmed = line.trim_start();
+    let indented = trimmed.len() < line.len();
+    let previous_trimmed = previous.trim();
+    let continues = trimmed.starts_with([')', ']', '}'])
+        || ["else:", "elif ", "except", "finally:"]
+            .iter()
+            .any(|keyword| trimmed.starts_with(keyword));
+    let opens = previous_trimmed.ends_with([':', '(', '[', '{', ',']);
+    let after_decorator = previous_trimmed.starts_with('@');
+    let after_comment = previous_trimmed.starts_with('#');
+    let defines = ["def ", "async def ", "class ", "@"]
+        .iter()
+        .any(|keyword| trimmed.starts_with(keyword));
+
+    if continues || opens || after_decorator {
+        0
+    } else if defines && !after_comment {
+        if indented { 1 } else { 2 }
+    } else if indented {
+        if in_class { 0 } else { blanks.min(1) }
+    } else if previous.starts_with(char::is_whitespace) {
+        // The first statement after a top-level block.
+        2
+    } else if trimmed.starts_with('#') {
+        blanks.min(2)
+    } else {
+        blanks.min(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_line_endings_mixed() {
+        let input = "a\r\nb\nc\r\n\r\nd";
+        assert_eq!(normalize_line_endings(input), "a\nb\nc\n\nd");
+    }
+
+    #[test]
+    fn test_collapse_converts_crlf() {
+        let input = "a\r\n\r\n\r\n\r\nb\nc";
+        assert_eq!(Normalization::Collapse.apply(input), "a\n\nb\nc\n");
+    }
+
+    #[test]
+    fn test_policy_by_extension() {
+        assert_eq!(
+            Normalization::for_path("src/client.ts"),
+            Normalization::Collapse
+        );
+        assert_eq!(
+            Normalization::for_path("routes.py"),
+            Normalization::PythonPep8
+        );
+        assert_eq!(Normalization::for_path("openapi.yaml"), Normalization::AsIs);
+        assert_eq!(
+            Normalization::for_path(".github/ci.yml"),
+            Normalization::AsIs
+        );
+        assert_eq!(Normalization::for_path("Makefile"), Normalization::Collapse);
+    }
+
+    #[test]
+    fn test_yaml_passes_through_untouched() {
+        let yaml = "info:\r\n  description: |\n    one\n\n\n    two\n\n\n\npaths: {}";
+        let file = GeneratedFile {
+            path: "openapi.yaml".to_string(),
+            content: yaml.to_string(),
+        };
+        assert_eq!(file.normalization().apply(&file.content), yaml);
+    }
+
+    #[test]
+    fn test_python_blank_lines() {
+        let input = "\
+from fastapi import APIRouter
+
+
+from models import Pet
+router = APIRouter()
+
+
+
+@router.get(\"/pets\")
+
+async def list_pets(
+
+
+    limit: int,
+
+) -> list[str]:
+
+    \"\"\"List pets.
+
+
+
+    Paragraphs stay apart.
+    \"\"\"
+    raise NotImplementedError
+class Pet:
+
+    name: str
+
+    kind: str
+    def describe(self) -> str:
+
+
+        return self.name
+PETS = []
+
+
+";
+        let expected = "\
+from fastapi import APIRouter
+
+from models import Pet
+router = APIRouter()
+
+
+@router.get(\"/pets\")
+async def list_pets(
+    limit: int,
+) -> list[str]:
+    \"\"\"List pets.
+
+
+
+    Paragraphs stay apart.
+    \"\"\"
+    raise NotImplementedError
+
+
+class Pet:
+    name: str
+    kind: str
+
+    def describe(self) -> str:
+        return self.name
+
+
+PETS = []
+";
+        assert_eq!(Normalization::PythonPep8.apply(input), expected);
+    }
+}
@@ -0,0 +1,476 @@
+//! Feature coverage of the transform over a spec.
+//!
+//! [`feature_report`] lists the spec constructs the generators handle only
+//! partially — `anyOf` nuance, `readOnly`/`writeOnly`, callbacks, non-JSON
+//! bodies — next to the ones they fully support, so a spec author can see what
+//! the generated code flattens before relying on it.
+
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::ir::is_text_media_type;
+use crate::parse::media_type::MediaType;
+use crate::parse::operation::Operation;
+use crate::parse::request_body::RequestBodyOrRef;
+use crate::parse::response::ResponseOrRef;
+use crate::parse::schema::{AdditionalProperties, Schema, SchemaOrRef};
+use crate::parse::spec::OpenApiSpec;
+
+/// A category of spec construct the report tracks, in report order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Feature {
+    OneOf,
+    AnyOf,
+    ReadWriteOnly,
+    ConditionalKeywords,
+    RequestBodies,
+    Responses,
+    Webhooks,
+    Callbacks,
+    Diagnostics,
+}
+
+impl Feature {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Feature::OneOf => "oneOf",
+            Feature::AnyOf => "anyOf",
+            Feature::ReadWriteOnly => "readOnly/writeOnly",
+            Feature::ConditionalKeywords => "not/if/then/else",
+            Feature::RequestBodies => "request bodies",
+            Feature::Responses => "responses",
+            Feature::Webhooks => "webhooks",
+            Feature::Callbacks => "callbacks",
+            Feature::Diagnostics => "transform diagnostics",
+        }
+    }
+}
+
+/// How faithfully the generated code represents a spec element.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Support {
+    Supported,
+    /// Generated, but with less precision than the spec expresses.
+    Approximated,
+    /// Not represented in the generated code at all.
+    Dropped,
+}
+
+impl Support {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Support::Supported => "supported",
+            Support::Approximated => "approximated",
+            Support::Dropped => "dropped",
+        }
+    }
+}
+
+/// One spec element of a tracked category.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureUse {
+    pub feature: Feature,
+    pub support: Support,
+    /// Where the element is, e.g. `Message.content` or `POST /messages request body`.
+    pub location: String,
+    /// What the generators do instead, for approximated and dropped elements.
+    pub note: Option<String>,
+}
+
+/// Every tracked element of a spec, grouped by category.
+#[derive(Debug, Clone, Default)]
+pub struct FeatureReport {
+    pub uses: Vec<FeatureUse>,
+}
+
+impl FeatureReport {
+    /// Supported, approximated and dropped counts for `feature`.
+    pub fn counts(&self, feature: Feature) -> [usize; 3] {
+        let mut counts = [0; 3];
+        for u in self.uses.iter().filter(|u| u.feature == feature) {
+            counts[u.support as usize] += 1;
+        }
+        counts
+    }
+
+    /// True when nothing was approximated or dropped.
+    pub fn is_lossless(&self) -> bool {
+        self.uses.iter().all(|u| u.support == Support::Supported)
+    }
+}
+
+impl fmt::Display for FeatureReport {
+    /// One summary line per category that occurs in the spec, followed by the
+    /// location of each approximated or dropped element.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut features: Vec<Feature> = self.uses.iter().map(|u| u.feature).collect();
+        features.sort();
+        features.dedup();
+        if features.is_empty() {
+            return writeln!(f, "No tracked features used.");
+        }
+
+        for feature in features {
+            let [supported, approximated, dropped] = self.counts(feature);
+            writeln!(
+                f,
+                "{}: {supported} supported, {approximated} approximated, {dropped} dropped",
+                feature.label()
+            )?;
+            for u in self
+                .uses
+                .iter()
+                .filter(|u| u.feature == feature && u.support != Support::Supported)
+            {
+                write!(f, "  {} {}", u.support.as_str(), u.location)?;
+                match &u.note {
+                    Some(note) => writeln!(f, " — {note}")?,
+                    None => writeln!(f)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Build the report for `spec` (as parsed, `$ref`s intact) and the
+/// `diagnostics` its transform produced.
+///
+/// Diagnostics about conditional keywords are left out: the schema walk
+/// already reports those, with finer locations.
+pub fn feature_report(spec: &OpenApiSpec, diagnostics: &[String]) -> FeatureReport {
+    let mut report = Collector {
+        spec,
+        uses: Vec::new(),
+    };
+
+    if let Some(components) = &spec.components {
+        for (name, schema) in &components.schemas {
+            report.schema(name, schema);
+        }
+    }
+    for (path, item) in &spec.paths {
+        for (method, op) in item.operations() {
+            report.operation(&format!("{method} {path}"), op);
+        }
+    }
+    for (name, item) in &spec.webhooks {
+        for (method, op) in item.operations() {
+            let location = format!("webhook {name} ({method})");
+            report.add(Feature::Webhooks, Support::Supported, &location, None);
+            report.operation(&location, op);
+        }
+    }
+
+    for diagnostic in diagnostics {
+        if diagnostic.ends_with("which generated types only approximate") {
+            continue;
+        }
+        let support = if diagnostic.ends_with("typed as `Any`") {
+            Support::Dropped
+        } else {
+            Support::Approximated
+        };
+        report.add(Feature::Diagnostics, support, diagnostic, None);
+    }
+
+    FeatureReport { uses: report.uses }
+}
+
+/// The component name a `#/components/{kind}/{name}` pointer names.
+fn component_name(ref_path: &str) -> &str {
+    ref_path.rsplit('/').next().unwrap_or(ref_path)
+}
+
+struct Collector<'a> {
+    spec: &'a OpenApiSpec,
+    uses: Vec<FeatureUse>,
+}
+
+impl Collector<'_> {
+    fn add(&mut self, feature: Feature, support: Support, location: &str, note: Option<String>) {
+        self.uses.push(FeatureUse {
+            feature,
+            support,
+            location: location.to_string(),
+            note,
+        });
+    }
+
+    fn operation(&mut self, label: &str, op: &Operation) {
+        for name in op.callbacks.keys() {
+            self.add(
+                Feature::Callbacks,
+                Support::Dropped,
+                &format!("{label} callback {name}"),
+                Some("no generator emits callback requests or handlers".to_string()),
+            );
+        }
+
+        let components = self.spec.components.as_ref();
+        let body = match &op.request_body {
+            Some(RequestBodyOrRef::RequestBody(body)) => Some(body),
+            Some(RequestBodyOrRef::Ref { ref_path }) => {
+                components.and_then(|c| match c.request_bodies.get(component_name(ref_path))? {
+                    RequestBodyOrRef::RequestBody(body) => Some(body),
+                    RequestBodyOrRef::Ref { .. } => None,
+                })
+            }
+            None => None,
+        };
+        if let Some(body) = body {
+            self.request_body(&format!("{label} request body"), &body.content);
+        }
+
+        // The same success response the transform picks.
+        let response = ["200", "201", "2XX", "default"]
+            .into_iter()
+            .find_map(|status| op.responses.get(status));
+        let response = match response {
+            Some(ResponseOrRef::Response(response)) => Some(response),
+            Some(ResponseOrRef::Ref { ref_path }) => {
+                components.and_then(|c| match c.responses.get(component_name(ref_path))? {
+                    ResponseOrRef::Response(response) => Some(response),
+                    ResponseOrRef::Ref { .. } => None,
+                })
+            }
+            None => None,
+        };
+        if let Some(response) = response {
+            self.response(&format!("{label} response"), &response.content);
+        }
+    }
+
+    fn request_body(&mut self, location: &str, content: &IndexMap<String, MediaType>) {
+        let Some((chosen, _)) = content
+            .get_key_value("application/json")
+            .or_else(|| content.first())
+        else {
+            return;
+        };
+        match chosen.as_str() {
+            ct if matches!(ct, "application/json" | "multipart/form-data")
+                || is_text_media_type(ct) =>
+            {
+                self.add(
+                    Feature::RequestBodies,
+                    Support::Supported,
+                    &format!("{location} ({chosen})"),
+                    None,
+                )
+            }
+            _ => self.add(
+                Feature::RequestBodies,
+                Support::Approximated,
+                &format!("{location} ({chosen})"),
+                Some("sent as given, without serialization".to_string()),
+            ),
+        }
+        for content_type in content.keys().filter(|ct| *ct != chosen) {
+            self.add(
+                Feature::RequestBodies,
+                Support::Dropped,
+                &format!("{location} ({content_type})"),
+                Some(format!("only `{chosen}` is generated")),
+            );
+        }
+        for (content_type, media_type) in content {
+            if let Some(schema) = &media_type.schema {
+                self.schema(&format!("{location} ({content_type})"), schema);
+            }
+        }
+    }
+
+    fn response(&mut self, location: &str, content: &IndexMap<String, MediaType>) {
+        let streamed = ["text/event-stream", "application/json"]
+            .into_iter()
+            .filter(|ct| content.contains_key(*ct))
+            .collect::<Vec<_>>();
+        let chosen: Vec<&str> = if streamed.is_empty() {
+            content.keys().take(1).map(String::as_str).collect()
+        } else {
+            streamed
+        };
+
+        for content_type in content.keys() {
+            let at = format!("{location} ({content_type})");
+            if !chosen.contains(&content_type.as_str()) {
+                self.add(
+                    Feature::Responses,
+                    Support::Dropped,
+                    &at,
+                    Some(format!("only `{}` is generated", chosen.join("`, `"))),
+                );
+            } else if content_type == "application/json"
+                || content_type == "text/event-stream"
+                || is_text_media_type(content_type)
+            {
+                self.add(Feature::Responses, Support::Supported, &at, None);
+            } else {
+                self.add(
+                    Feature::Responses,
+                    Support::Approximated,
+                    &at,
+                    Some("decoded as JSON, falling back to text".to_string()),
+                );
+            }
+        }
+        for (content_type, media_type) in content {
+            let at = format!("{location} ({content_type})");
+            for schema in media_type.schema.iter().chain(&media_type.item_schema) {
+                self.schema(&at, schema);
+            }
+        }
+    }
+
+    fn schema(&mut self, location: &str, schema: &SchemaOrRef) {
+        if let SchemaOrRef::Schema(schema) = schema {
+            self.inline_schema(location, schema);
+        }
+    }
+
+    fn inline_schema(&mut self, location: &str, schema: &Schema) {
+        if !schema.one_of.is_empty() {
+            self.add(Feature::OneOf, Support::Supported, location, None);
+        }
+        if !schema.any_of.is_empty() {
+            self.add(
+                Feature::AnyOf,
+                Support::Approximated,
+                location,
+                Some("generated as a plain union, like `oneOf`".to_string()),
+            );
+        }
+        let conditionals = [
+            ("not", &schema.not),
+            ("if", &schema.if_schema),
+            ("then", &schema.then_schema),
+            ("else", &schema.else_schema),
+        ];
+        let keywords: Vec<&str> = conditionals
+            .iter()
+            .filter(|(_, s)| s.is_some())
+            .map(|(keyword, _)| *keyword)
+            .collect();
+        if !keywords.is_empty() {
+            self.add(
+                Feature::ConditionalKeywords,
+                Support::Approximated,
+                location,
+                Some(format!("`{}` not enforced", keywords.join("`, `"))),
+            );
+        }
+
+        for (name, property) in &schema.properties {
+            let at = format!("{location}.{name}");
+            if let SchemaOrRef::Schema(property) = property {
+                if property.read_only == Some(true) {
+                    self.add(
+                        Feature::ReadWriteOnly,
+                        Support::Dropped,
+                        &at,
+                        Some("`readOnly` ignored; the field is also in request types".to_string()),
+                    );
+                }
+                if property.write_only == Some(true) {
+                    self.add(
+                        Feature::ReadWriteOnly,
+                        Support::Approximated,
+                        &at,
+                        Some(
+                            "`writeOnly` excluded from FastAPI responses; TypeScript response types still have the field"
+                                .to_string(),
+                        ),
+                    );
+                }
+            }
+            self.schema(&at, property);
+        }
+
+        if let Some(items) = &schema.items {
+            self.schema(&format!("{location}[]"), items);
+        }
+        if let Some(AdditionalProperties::Schema(additional)) = &schema.additional_properties {
+            self.schema(&format!("{location}.*"), additional);
+        }
+        let compositions = [
+            ("allOf", &schema.all_of),
+            ("oneOf", &schema.one_of),
+            ("anyOf", &schema.any_of),
+        ];
+        for (keyword, schemas) in compositions {
+            for (i, sub) in schemas.iter().enumerate() {
+                self.schema(&format!("{location}.{keyword}[{i}]"), sub);
+            }
+        }
+        for (keyword, sub) in conditionals {
+            if let Some(sub) = sub {
+                self.schema(&format!("{location}.{keyword}"), sub);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(yaml: &str) -> FeatureReport {
+        let spec = crate::parse::from_yaml(yaml).unwrap();
+        let ir = crate::transform::transform(&spec).unwrap();
+        feature_report(&spec, &ir.diagnostics)
+    }
+
+    #[test]
+    fn callbacks_and_extra_media_types_are_dropped() {
+        let report = report(
+            r#"
+openapi: "3.1.0"
+info: { title: Hooks, version: "1" }
+paths:
+  /subscriptions:
+    post:
+      operationId: subscribe
+      requestBody:
+        content:
+          application/json:
+            schema: { type: object, properties: { url: { type: string, writeOnly: true } } }
+          application/xml:
+            schema: { type: object }
+      responses:
+        "201":
+          description: Created
+          content:
+            application/xml:
+              schema: { type: string }
+      callbacks:
+        onEvent:
+          "{$request.body#/url}":
+            post:
+              responses:
+                "200": { description: OK }
+"#,
+        );
+        assert!(!report.is_lossless());
+        assert_eq!(report.counts(Feature::Callbacks), [0, 0, 1]);
+        assert_eq!(report.counts(Feature::RequestBodies), [1, 0, 1]);
+        assert_eq!(report.counts(Feature::Responses), [0, 1, 0]);
+        assert_eq!(report.counts(Feature::ReadWriteOnly), [0, 1, 0]);
+        assert!(report.to_string().contains(
+            "  dropped POST /subscriptions request body (application/xml) — only `application/json` is generated\n"
+        ));
+    }
+
+    #[test]
+    fn broken_ref_diagnostics_are_dropped() {
+        let spec =
+            crate::parse::from_yaml(include_str!("../tests/fixtures/petstore-3.2.yaml")).unwrap();
+        let diagnostics = vec![
+            "`Pet.owner` references unknown schema `Owner`; typed as `Any`".to_string(),
+            "schema `Pet` uses `not`, which generated types only approximate".to_string(),
+        ];
+        let report = feature_report(&spec, &diagnostics);
+        assert_eq!(report.counts(Feature::Diagnostics), [0, 0, 1]);
+    }
+}
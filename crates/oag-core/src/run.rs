@@ -0,0 +1,809 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::config::{ConfigWarning, GeneratorConfig, GeneratorId, NamingConfig, OagConfig};
+use crate::error::TransformError;
+use crate::error::{OutputOverlap, RunError};
+use crate::filter::exclude_files;
+use crate::hash::{hash_ir, sha256, to_hex};
+use crate::index;
+use crate::ir::{IrSpec, diff};
+use crate::parse::{self, spec::OpenApiSpec};
+use crate::postprocess;
+use crate::transform::{self, TransformOptions};
+use crate::{CodeGenerator, GeneratedFile, GeneratorError};
+
+/// The "do not edit" notice written to every output directory as `README.md`.
+pub const GENERATED_README: &str = r#"# Generated Code — Do Not Edit
+
+This directory is **auto-generated** by [oag](https://github.com/urmzd/openapi-generator).
+Any manual changes will be overwritten the next time `oag generate` is run.
+
+To regenerate, run:
+```
+oag generate
+```
+
+To customize the generated output, edit your `.urmzd.oag.yaml` configuration file.
+"#;
+
+/// Directory, under the project root, holding state between `oag generate` runs.
+pub const CACHE_DIR: &str = ".oag-cache";
+
+/// File in [`CACHE_DIR`] recording the fingerprint of the last generated run.
+pub const IR_HASH_FILE: &str = "ir.sha256";
+
+/// File in [`CACHE_DIR`] recording what the last run was generated from and
+/// the files it wrote, so an unchanged run can skip parsing the spec.
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// File in [`CACHE_DIR`] holding the last generated IR, diffed against the
+/// next run's to write the changelog.
+pub const IR_SNAPSHOT_FILE: &str = "ir.json";
+
+/// Changelog written under the project root when `changelog` is enabled.
+pub const CHANGELOG_FILE: &str = "CHANGELOG.generated.md";
+
+/// Where the OpenAPI spec comes from.
+#[derive(Debug, Clone)]
+pub enum SpecSource {
+    /// A spec file on disk — `.json` files are parsed as JSON, anything else as YAML.
+    Path(PathBuf),
+    /// YAML text.
+    Yaml(String),
+    /// JSON text.
+    Json(String),
+    /// An already parsed spec.
+    Parsed(Box<OpenApiSpec>),
+}
+
+impl SpecSource {
+    /// Read (if needed) and parse the spec.
+    pub fn parse(self) -> Result<OpenApiSpec, RunError> {
+        match self {
+            SpecSource::Path(path) => {
+                let content = fs::read_to_string(&path).map_err(|source| RunError::Io {
+                    path: path.clone(),
+                    source,
+                })?;
+                let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("yaml");
+                match ext {
+                    "json" => Ok(parse::from_json(&content)?),
+                    _ => Ok(parse::from_yaml(&content)?),
+                }
+            }
+            SpecSource::Yaml(content) => Ok(parse::from_yaml(&content)?),
+            SpecSource::Json(content) => Ok(parse::from_json(&content)?),
+            SpecSource::Parsed(spec) => Ok(*spec),
+        }
+    }
+}
+
+/// The generators [`generate`] can dispatch to, keyed by their id.
+#[derive(Default)]
+pub struct GeneratorRegistry {
+    generators: IndexMap<GeneratorId, Box<dyn CodeGenerator>>,
+}
+
+impl GeneratorRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a generator under its `id()`, replacing any previous one.
+    pub fn register(&mut self, generator: impl CodeGenerator + 'static) -> &mut Self {
+        self.generators.insert(generator.id(), Box::new(generator));
+        self
+    }
+
+    pub fn get(&self, id: GeneratorId) -> Option<&dyn CodeGenerator> {
+        self.generators.get(&id).map(|g| g.as_ref())
+    }
+}
+
+/// Files produced by one configured generator.
+#[derive(Debug, Clone)]
+pub struct GeneratorOutput {
+    pub id: GeneratorId,
+    /// The generator's `output` directory from the config.
+    pub output: PathBuf,
+    /// Generated files, paths relative to `output`. Includes `README.md`.
+    pub files: Vec<GeneratedFile>,
+    /// Non-fatal diagnostics, e.g. from `files.exclude`.
+    pub warnings: Vec<String>,
+    /// Whether the generator ran in types-only mode, forced or because the
+    /// spec has no operations.
+    pub types_only: bool,
+}
+
+/// Everything produced by a [`generate`] run, in config order.
+#[derive(Debug, Clone)]
+pub struct GenerationResult {
+    pub outputs: Vec<GeneratorOutput>,
+    /// Diagnostics from building the IR, shared by every generator.
+    pub warnings: Vec<String>,
+}
+
+/// Parse the spec and build the IR using the config's naming options.
+pub fn load_ir(config: &OagConfig, spec_source: SpecSource) -> Result<IrSpec, RunError> {
+    let spec = spec_source.parse()?;
+    Ok(transform::transform_owned(
+        spec,
+        &transform_options(config, &config.naming),
+    )?)
+}
+
+fn transform_options(config: &OagConfig, naming: &NamingConfig) -> TransformOptions {
+    TransformOptions {
+        naming_strategy: naming.strategy,
+        aliases: naming.aliases.clone(),
+        schema_aliases: naming.schema_aliases.clone(),
+        on_collision: naming.on_collision,
+        fallback_naming: naming.fallback,
+        doc_max_length: config.docs.max_length,
+        untagged_module: Some(naming.untagged_module.clone()),
+        modules: config.modules.clone(),
+        passes: config.passes.clone(),
+        trailing_slash: config.trailing_slash,
+        missing_path_params: config.missing_path_params,
+        example_preference: config.examples.prefer.clone(),
+        exposed_extensions: config.extensions.expose.clone(),
+        query_object: config.query_object,
+        streaming: config
+            .operations
+            .iter()
+            .map(|(name, operation)| (name.clone(), operation.streaming))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+/// Every configured generator's [`CodeGenerator::validate_config`] warnings,
+/// in config order.
+pub fn validate_config(
+    config: &OagConfig,
+    registry: &GeneratorRegistry,
+) -> Result<Vec<(GeneratorId, ConfigWarning)>, RunError> {
+    let mut warnings = Vec::new();
+    for (id, gen_config) in &config.generators {
+        let generator = registry
+            .get(*id)
+            .ok_or(RunError::UnregisteredGenerator(*id))?;
+        warnings.extend(
+            generator
+                .validate_config(gen_config)
+                .into_iter()
+                .map(|warning| (*id, warning)),
+        );
+    }
+    Ok(warnings)
+}
+
+/// Run every generator in `config` against the spec, entirely in memory.
+pub fn generate(
+    config: &OagConfig,
+    registry: &GeneratorRegistry,
+    spec_source: SpecSource,
+) -> Result<GenerationResult, RunError> {
+    let ir = load_ir(config, spec_source)?;
+    generate_from_ir(config, registry, &ir)
+}
+
+/// Like [`generate`], for callers that already hold an IR.
+///
+/// Generators with their own `naming` overrides get an IR rebuilt from
+/// `ir.raw_spec` with the overlaid naming options, and those with a
+/// `type_prefix` get it applied to a copy. Unless `allow_overlap` is
+/// set, generators writing different content to the same file are an error.
+pub fn generate_from_ir(
+    config: &OagConfig,
+    registry: &GeneratorRegistry,
+    ir: &IrSpec,
+) -> Result<GenerationResult, RunError> {
+    let mut outputs = Vec::new();
+    // `contract_tests` on either side of the contract turns on both halves.
+    let contract_tests = config.generators.values().any(|g| g.contract_tests);
+    // A spec of only schemas has nothing to build a client or routes from.
+    let no_operations = ir.operations.is_empty() && ir.webhooks.is_empty();
+
+    for (id, gen_config) in &config.generators {
+        let generator = registry
+            .get(*id)
+            .ok_or(RunError::UnregisteredGenerator(*id))?;
+        let adjusted_config;
+        let gen_config = if (contract_tests && !gen_config.contract_tests)
+            || (no_operations && !gen_config.types_only)
+        {
+            adjusted_config = GeneratorConfig {
+                contract_tests: contract_tests || gen_config.contract_tests,
+                types_only: no_operations || gen_config.types_only,
+                ..gen_config.clone()
+            };
+            &adjusted_config
+        } else {
+            gen_config
+        };
+        let renamed_ir = match &gen_config.naming {
+            Some(overrides) => {
+                let spec = ir.raw_spec.clone().ok_or_else(|| {
+                    TransformError::Other(format!(
+                        "{id}: `naming` overrides need the IR's raw spec"
+                    ))
+                })?;
+                let naming = config.naming.with_overrides(overrides);
+                Some(transform::transform_owned(
+                    spec,
+                    &transform_options(config, &naming),
+                )?)
+            }
+            None => None,
+        };
+        let renamed_ir = match gen_config.type_prefix.as_deref() {
+            Some(prefix) => {
+                let mut prefixed = renamed_ir.unwrap_or_else(|| ir.clone());
+                transform::passes::prefix_type_names(&mut prefixed, prefix);
+                Some(prefixed)
+            }
+            None => renamed_ir,
+        };
+        let mut files = generator
+            .generate(renamed_ir.as_ref().unwrap_or(ir), gen_config)
+            .and_then(|files| {
+                files.iter().try_for_each(GeneratedFile::check_path)?;
+                Ok(files)
+            })
+            .map_err(|source| RunError::Generator { id: *id, source })?;
+        files.push(GeneratedFile {
+            path: "README.md".to_string(),
+            content: GENERATED_README.to_string(),
+        });
+        if gen_config.index_json {
+            files.push(GeneratedFile::new(
+                index::INDEX_FILE,
+                index::operations_index(renamed_ir.as_ref().unwrap_or(ir)),
+            ));
+        }
+        postprocess::normalize_files(generator, &mut files);
+
+        let warnings = exclude_files(&mut files, &gen_config.files.exclude)
+            .map_err(|source| RunError::Generator { id: *id, source })?;
+
+        outputs.push(GeneratorOutput {
+            id: *id,
+            output: PathBuf::from(&gen_config.output),
+            files,
+            warnings,
+            types_only: gen_config.types_only,
+        });
+    }
+
+    if !config.allow_overlap {
+        let overlaps = find_overlaps(&outputs);
+        if !overlaps.is_empty() {
+            return Err(RunError::OutputOverlap { overlaps });
+        }
+    }
+
+    Ok(GenerationResult {
+        outputs,
+        warnings: ir.diagnostics.clone(),
+    })
+}
+
+/// Files that more than one generator writes, with different content, in
+/// the order they're written. Identical files (like the generated
+/// `README.md`) don't conflict.
+fn find_overlaps(outputs: &[GeneratorOutput]) -> Vec<OutputOverlap> {
+    let mut seen: IndexMap<PathBuf, (GeneratorId, &str)> = IndexMap::new();
+    let mut overlaps = Vec::new();
+    for output in outputs {
+        for file in &output.files {
+            let path = lexical_path(&output.output.join(&file.path));
+            match seen.get(&path) {
+                Some(&(first, content)) if first != output.id && content != file.content => {
+                    overlaps.push(OutputOverlap {
+                        path,
+                        first,
+                        second: output.id,
+                    });
+                }
+                Some(_) => {}
+                None => {
+                    seen.insert(path, (output.id, &file.content));
+                }
+            }
+        }
+    }
+    overlaps
+}
+
+/// `path` with `.` dropped and `..` applied, so `out/./a` and `out/b/../a`
+/// name the same file.
+fn lexical_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir if normalized.file_name().is_some() => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Write every generator's files under `root`. Returns the written paths.
+pub fn write_output(result: &GenerationResult, root: &Path) -> Result<Vec<PathBuf>, RunError> {
+    let mut written = Vec::new();
+    for output in &result.outputs {
+        written.extend(write_generator_output(output, root)?);
+    }
+    Ok(written)
+}
+
+/// Write one generator's files to `root/{output}`. Returns the written paths.
+///
+/// Fails without writing a file whose path isn't relative or contains `..`,
+/// or that resolves outside the output directory through a symlink.
+pub fn write_generator_output(
+    output: &GeneratorOutput,
+    root: &Path,
+) -> Result<Vec<PathBuf>, RunError> {
+    let base = root.join(&output.output);
+    let unsafe_path = |source| RunError::Generator {
+        id: output.id,
+        source,
+    };
+    for file in &output.files {
+        file.check_path().map_err(unsafe_path)?;
+    }
+    fs::create_dir_all(&base).map_err(|source| RunError::Io {
+        path: base.clone(),
+        source,
+    })?;
+    let canonical_base = canonicalize(&base)?;
+    let mut written = Vec::new();
+
+    for file in &output.files {
+        let path = file.to_platform_path(&base);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|source| RunError::Io {
+                path: parent.to_path_buf(),
+                source,
+            })?;
+            let resolved = match fs::symlink_metadata(&path) {
+                Ok(meta) if meta.file_type().is_symlink() => canonicalize(&path),
+                _ => canonicalize(parent),
+            };
+            if !resolved.is_ok_and(|resolved| resolved.starts_with(&canonical_base)) {
+                return Err(unsafe_path(GeneratorError::UnsafePath {
+                    path: file.path.clone(),
+                    reason: "it resolves outside the output directory",
+                }));
+            }
+        }
+        fs::write(&path, &file.content).map_err(|source| RunError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+fn canonicalize(path: &Path) -> Result<PathBuf, RunError> {
+    path.canonicalize().map_err(|source| RunError::Io {
+        path: path.to_path_buf(),
+        source,
+    })
+}
+
+/// Hex fingerprint of a run: [`hash_ir`] of the IR, salted with the config and
+/// the oag version so that changing either also regenerates.
+pub fn generation_hash(config: &OagConfig, ir: &IrSpec) -> String {
+    let mut input = hash_ir(ir).to_vec();
+    input.extend_from_slice(format!("{}\n", env!("CARGO_PKG_VERSION")).as_bytes());
+    input.extend(config_json(config));
+    to_hex(&sha256(&input))
+}
+
+/// `config` serialized as JSON, the stable form its hashes are taken of.
+fn config_json(config: &OagConfig) -> Vec<u8> {
+    serde_json::to_vec(config).expect("the config always serializes")
+}
+
+/// Whether the last run under `root` recorded `hash` and every file its
+/// [`Manifest`] lists is still on disk unchanged, so generating again would
+/// change nothing. Without a manifest the outputs can't be checked, so the
+/// run isn't skipped.
+pub fn is_up_to_date(root: &Path, hash: &str) -> bool {
+    let cached = fs::read_to_string(root.join(CACHE_DIR).join(IR_HASH_FILE));
+    cached.is_ok_and(|cached| cached.trim() == hash)
+        && read_manifest(root).is_some_and(|manifest| outputs_unchanged(root, &manifest))
+}
+
+/// Record `hash` as the last generated run under `root`.
+pub fn write_cached_hash(root: &Path, hash: &str) -> Result<(), RunError> {
+    let dir = root.join(CACHE_DIR);
+    fs::create_dir_all(&dir).map_err(|source| RunError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+    let path = dir.join(IR_HASH_FILE);
+    fs::write(&path, format!("{hash}\n")).map_err(|source| RunError::Io { path, source })
+}
+
+/// What a run is generated from, hashed: the spec's text, the effective config
+/// and the oag version.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunInputs {
+    pub oag_version: String,
+    pub spec_hash: String,
+    pub config_hash: String,
+}
+
+/// The contents of [`MANIFEST_FILE`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    #[serde(flatten)]
+    pub inputs: RunInputs,
+    /// Every file the run wrote, relative to the project root, with the hex
+    /// SHA-256 of its content after formatting.
+    pub files: IndexMap<String, String>,
+    /// Names from the spec, for shell completion.
+    #[serde(default)]
+    pub summary: SpecSummary,
+}
+
+/// Names from a generated spec, recorded in the [`Manifest`] so `oag`'s shell
+/// completion can offer them without parsing the spec.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpecSummary {
+    /// Component schema names, as generated.
+    pub schemas: Vec<String>,
+}
+
+impl SpecSummary {
+    pub fn of(ir: &IrSpec) -> Self {
+        Self {
+            schemas: ir
+                .schemas
+                .iter()
+                .map(|schema| schema.name().pascal_case.clone())
+                .collect(),
+        }
+    }
+}
+
+/// The [`RunInputs`] of generating `spec_source` with `config`, reading the
+/// spec file if needed. `None` for an already parsed spec, which has no text
+/// to hash.
+pub fn run_inputs(
+    config: &OagConfig,
+    spec_source: &SpecSource,
+) -> Result<Option<RunInputs>, RunError> {
+    let spec_hash = match spec_source {
+        SpecSource::Path(path) => sha256(&fs::read(path).map_err(|source| RunError::Io {
+            path: path.clone(),
+            source,
+        })?),
+        SpecSource::Yaml(content) | SpecSource::Json(content) => sha256(content.as_bytes()),
+        SpecSource::Parsed(_) => return Ok(None),
+    };
+    Ok(Some(RunInputs {
+        oag_version: env!("CARGO_PKG_VERSION").to_string(),
+        spec_hash: to_hex(&spec_hash),
+        config_hash: to_hex(&sha256(&config_json(config))),
+    }))
+}
+
+/// The manifest of a run of `ir` from `inputs` that wrote `files` (paths under
+/// `root`, as returned by [`write_output`]), hashing each file as it is now on
+/// disk.
+pub fn manifest_for(
+    inputs: RunInputs,
+    ir: &IrSpec,
+    root: &Path,
+    files: &[PathBuf],
+) -> Result<Manifest, RunError> {
+    let mut hashes = IndexMap::new();
+    for path in files {
+        let content = fs::read(path).map_err(|source| RunError::Io {
+            path: path.clone(),
+            source,
+        })?;
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        hashes.insert(
+            relative.to_string_lossy().into_owned(),
+            to_hex(&sha256(&content)),
+        );
+    }
+    Ok(Manifest {
+        inputs,
+        files: hashes,
+        summary: SpecSummary::of(ir),
+    })
+}
+
+/// The manifest recorded by the last run under `root`, if it can be read.
+pub fn read_manifest(root: &Path) -> Option<Manifest> {
+    let content = fs::read_to_string(root.join(CACHE_DIR).join(MANIFEST_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Record `manifest` as the last run under `root`.
+pub fn write_manifest(root: &Path, manifest: &Manifest) -> Result<(), RunError> {
+    let dir = root.join(CACHE_DIR);
+    fs::create_dir_all(&dir).map_err(|source| RunError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+    let path = dir.join(MANIFEST_FILE);
+    let json = serde_json::to_string_pretty(manifest).expect("manifests always serialize");
+    fs::write(&path, json + "\n").map_err(|source| RunError::Io { path, source })
+}
+
+/// Project files generators scaffold at the root of their output directory,
+/// which a host project generated into may already have.
+pub const PROJECT_FILES: &[&str] = &[
+    "package.json",
+    "tsconfig.json",
+    "biome.json",
+    "tsdown.config.ts",
+    ".gitignore",
+    "pyproject.toml",
+    "ruff.toml",
+    "README.md",
+];
+
+/// The [`PROJECT_FILES`] of `output` that would replace a file already under
+/// `root` that no earlier run wrote, i.e. one the host project owns. Files in
+/// `written`, those this run already wrote for another generator sharing the
+/// directory, are oag's too.
+pub fn overwritten_project_files(
+    output: &GeneratorOutput,
+    root: &Path,
+    written: &[PathBuf],
+) -> Vec<PathBuf> {
+    let previous = read_manifest(root).map(|manifest| manifest.files);
+    let base = root.join(&output.output);
+    output
+        .files
+        .iter()
+        .filter(|file| PROJECT_FILES.contains(&file.path.as_str()))
+        .map(|file| file.to_platform_path(&base))
+        .filter(|path| path.is_file() && !written.contains(path))
+        .filter(|path| {
+            let relative = path.strip_prefix(root).unwrap_or(path);
+            !previous
+                .as_ref()
+                .is_some_and(|files| files.contains_key(relative.to_string_lossy().as_ref()))
+        })
+        .collect()
+}
+
+/// Whether the last run under `root` was generated from `inputs` and every
+/// file it wrote is still on disk unchanged, so the run can be skipped
+/// without even parsing the spec.
+pub fn is_manifest_current(root: &Path, inputs: &RunInputs) -> bool {
+    read_manifest(root)
+        .is_some_and(|manifest| manifest.inputs == *inputs && outputs_unchanged(root, &manifest))
+}
+
+/// Whether `manifest` lists files and each one under `root` still has the
+/// content it was recorded with.
+fn outputs_unchanged(root: &Path, manifest: &Manifest) -> bool {
+    !manifest.files.is_empty()
+        && manifest.files.iter().all(|(path, hash)| {
+            fs::read(root.join(path)).is_ok_and(|content| to_hex(&sha256(&content)) == *hash)
+        })
+}
+
+/// Diff `ir` against the IR recorded by the last run under `root` and prepend
+/// the changes to [`CHANGELOG_FILE`], then record `ir` for the next run.
+///
+/// Returns the changelog's path when a section was added. Nothing is written
+/// to the changelog on the first run, or when the API surface is unchanged.
+pub fn update_changelog(root: &Path, ir: &IrSpec) -> Result<Option<PathBuf>, RunError> {
+    let dir = root.join(CACHE_DIR);
+    let snapshot_path = dir.join(IR_SNAPSHOT_FILE);
+    // A snapshot from an older oag may not parse; treat it as a first run.
+    let previous: Option<IrSpec> = fs::read_to_string(&snapshot_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok());
+
+    let mut updated = None;
+    if let Some(previous) = previous {
+        let changes = diff::diff(&previous, ir);
+        if !changes.is_empty() {
+            let heading = if previous.info.version == ir.info.version {
+                ir.info.version.clone()
+            } else {
+                format!("{} (from {})", ir.info.version, previous.info.version)
+            };
+            let path = root.join(CHANGELOG_FILE);
+            let existing = fs::read_to_string(&path).unwrap_or_default();
+            let rest = existing
+                .strip_prefix("# Changelog\n")
+                .unwrap_or(&existing)
+                .trim_start();
+            let mut content = format!("# Changelog\n\n{}", changes.to_markdown(&heading));
+            if !rest.is_empty() {
+                content.push('\n');
+                content.push_str(rest);
+            }
+            fs::write(&path, content).map_err(|source| RunError::Io {
+                path: path.clone(),
+                source,
+            })?;
+            updated = Some(path);
+        }
+    }
+
+    fs::create_dir_all(&dir).map_err(|source| RunError::Io {
+        path: dir.clone(),
+        source,
+    })?;
+    let snapshot = IrSpec {
+        raw_spec: None,
+        ..ir.clone()
+    };
+    let content = serde_json::to_string(&snapshot).expect("IR always serializes");
+    fs::write(&snapshot_path, content).map_err(|source| RunError::Io {
+        path: snapshot_path,
+        source,
+    })?;
+    Ok(updated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC: &str = "openapi: 3.1.0\ninfo:\n  title: Pets\n  version: 1.0.0\npaths: {}\n";
+
+    fn inputs(config: &OagConfig, spec: &str) -> RunInputs {
+        run_inputs(config, &SpecSource::Yaml(spec.to_string()))
+            .unwrap()
+            .unwrap()
+    }
+
+    #[test]
+    fn run_inputs_change_with_the_spec_and_config() {
+        let config = OagConfig::default();
+        let base = inputs(&config, SPEC);
+        assert_eq!(base, inputs(&config, SPEC));
+        assert_eq!(base.oag_version, env!("CARGO_PKG_VERSION"));
+        assert_ne!(
+            base.spec_hash,
+            inputs(&config, &format!("{SPEC}\n")).spec_hash
+        );
+
+        let changed = OagConfig {
+            allow_overlap: true,
+            ..OagConfig::default()
+        };
+        let other = inputs(&changed, SPEC);
+        assert_eq!(base.spec_hash, other.spec_hash);
+        assert_ne!(base.config_hash, other.config_hash);
+
+        // `--types-only` isn't read from the file, but changes the output all the same.
+        let mut node = OagConfig::default();
+        node.generators
+            .insert(GeneratorId::NodeClient, GeneratorConfig::new("out"));
+        let mut types_only = node.clone();
+        types_only.generators[&GeneratorId::NodeClient].types_only = true;
+        assert_ne!(
+            inputs(&node, SPEC).config_hash,
+            inputs(&types_only, SPEC).config_hash
+        );
+
+        let parsed = SpecSource::Parsed(Box::new(parse::from_yaml(SPEC).unwrap()));
+        assert_eq!(run_inputs(&config, &parsed).unwrap(), None);
+    }
+
+    #[test]
+    fn manifest_is_current_until_an_input_or_output_changes() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let file = root.join("out/client.ts");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "export {};\n").unwrap();
+
+        let config = OagConfig::default();
+        let run = inputs(&config, SPEC);
+        assert!(!is_manifest_current(root, &run));
+
+        let ir = transform::transform(&parse::from_yaml(SPEC).unwrap()).unwrap();
+        let manifest = manifest_for(run.clone(), &ir, root, std::slice::from_ref(&file)).unwrap();
+        assert_eq!(
+            manifest.files.keys().collect::<Vec<_>>(),
+            [&Path::new("out")
+                .join("client.ts")
+                .to_string_lossy()
+                .into_owned()]
+        );
+        write_manifest(root, &manifest).unwrap();
+        assert_eq!(read_manifest(root), Some(manifest));
+        assert!(is_manifest_current(root, &run));
+
+        assert!(!is_manifest_current(
+            root,
+            &inputs(&config, "openapi: 3.2.0\n")
+        ));
+        fs::write(&file, "export const edited = 1;\n").unwrap();
+        assert!(!is_manifest_current(root, &run));
+        fs::remove_file(&file).unwrap();
+        assert!(!is_manifest_current(root, &run));
+    }
+
+    #[test]
+    fn up_to_date_needs_the_hash_and_unchanged_outputs() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let file = root.join("out/client.ts");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "export {};\n").unwrap();
+        write_cached_hash(root, "abc").unwrap();
+        // No manifest: the outputs can't be vouched for.
+        assert!(!is_up_to_date(root, "abc"));
+
+        let ir = transform::transform(&parse::from_yaml(SPEC).unwrap()).unwrap();
+        let run = inputs(&OagConfig::default(), SPEC);
+        let manifest = manifest_for(run, &ir, root, std::slice::from_ref(&file)).unwrap();
+        write_manifest(root, &manifest).unwrap();
+        assert!(is_up_to_date(root, "abc"));
+        assert!(!is_up_to_date(root, "def"));
+
+        fs::write(&file, "export const edited = 1;\n").unwrap();
+        assert!(!is_up_to_date(root, "abc"));
+        fs::remove_file(&file).unwrap();
+        assert!(!is_up_to_date(root, "abc"));
+    }
+
+    #[test]
+    fn project_files_a_run_did_not_write_are_reported() {
+        let tmp = tempfile::tempdir().unwrap();
+        let root = tmp.path();
+        let output = GeneratorOutput {
+            id: GeneratorId::NodeClient,
+            output: PathBuf::from("app"),
+            files: vec![
+                GeneratedFile::new("package.json", "{}"),
+                GeneratedFile::new("tsconfig.json", "{}"),
+                GeneratedFile::new("src/package.json", "{}"),
+            ],
+            warnings: vec![],
+            types_only: false,
+        };
+        assert!(overwritten_project_files(&output, root, &[]).is_empty());
+
+        let app = root.join("app");
+        fs::create_dir_all(app.join("src")).unwrap();
+        for path in ["package.json", "tsconfig.json", "src/package.json"] {
+            fs::write(app.join(path), "{}").unwrap();
+        }
+        assert_eq!(
+            overwritten_project_files(&output, root, &[]),
+            [app.join("package.json"), app.join("tsconfig.json")]
+        );
+
+        // Files an earlier run wrote are oag's to replace.
+        let ir = transform::transform(&parse::from_yaml(SPEC).unwrap()).unwrap();
+        let run = inputs(&OagConfig::default(), SPEC);
+        let manifest = manifest_for(run, &ir, root, &[app.join("tsconfig.json")]).unwrap();
+        write_manifest(root, &manifest).unwrap();
+        assert_eq!(
+            overwritten_project_files(&output, root, &[]),
+            [app.join("package.json")]
+        );
+
+        // So are files this run wrote for a generator sharing the directory.
+        assert!(overwritten_project_files(&output, root, &[app.join("package.json")]).is_empty());
+    }
+}
@@ -0,0 +1,118 @@
+//! The test coverage report (`test_report: true`): `test-coverage.json`,
+//! written next to a generator's tests, mapping every operation to the
+//! generated test cases that call it.
+//!
+//! Generators fill the report from the same per-operation contexts their
+//! test templates render, so an operation whose tests were skipped or lost
+//! to a name collision shows up with no tests instead of going unnoticed.
+//! Operations are keyed by their generated method name (as in
+//! [`crate::index`]), and test cases are named the way their runner reports
+//! them: `client.test.ts > ApiClient > listPets` for vitest,
+//! `test_routes.py::test_list_pets_route_exists` for pytest.
+
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::ir::{IrOperation, IrSpec};
+
+/// The report's file name, in the directory of the tests it covers.
+pub const REPORT_FILE: &str = "test-coverage.json";
+
+/// The generated tests targeting each operation.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestCoverage {
+    /// Test case names by operation name, in spec order; `[]` for an
+    /// operation no test calls.
+    pub operations: IndexMap<String, Vec<String>>,
+}
+
+impl TestCoverage {
+    /// A report listing every operation in `ir`, none of them tested yet.
+    pub fn new(ir: &IrSpec) -> Self {
+        Self {
+            operations: ir
+                .operations
+                .iter()
+                .map(|op| (op.name.camel_case.clone(), Vec::new()))
+                .collect(),
+        }
+    }
+
+    /// Record `test` as a test of `op`.
+    pub fn add(&mut self, op: &IrOperation, test: impl Into<String>) {
+        self.operations
+            .entry(op.name.camel_case.clone())
+            .or_default()
+            .push(test.into());
+    }
+
+    /// Add the tests `other` records, e.g. those of another test file.
+    pub fn merge(&mut self, other: TestCoverage) {
+        for (operation, tests) in other.operations {
+            self.operations.entry(operation).or_default().extend(tests);
+        }
+    }
+
+    /// The operations no generated test calls, in spec order.
+    pub fn untested(&self) -> Vec<&str> {
+        self.operations
+            .iter()
+            .filter(|(_, tests)| tests.is_empty())
+            .map(|(operation, _)| operation.as_str())
+            .collect()
+    }
+
+    /// The contents of `test-coverage.json`: pretty-printed JSON with a
+    /// trailing newline.
+    pub fn to_json(&self) -> String {
+        let mut json = serde_json::to_string_pretty(self).expect("the report always serializes");
+        json.push('\n');
+        json
+    }
+
+    /// Read a report written by [`to_json`](Self::to_json).
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untested_operations_stay_in_the_report() {
+        let spec = crate::parse::from_yaml(
+            r#"
+openapi: "3.1.0"
+info: { title: Pets, version: "1.0" }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        "204": { description: Listed }
+    post:
+      operationId: createPet
+      responses:
+        "204": { description: Created }
+"#,
+        )
+        .unwrap();
+        let ir = crate::transform::transform(&spec).unwrap();
+        let mut coverage = TestCoverage::new(&ir);
+        coverage.add(&ir.operations[0], "client.test.ts > ApiClient > listPets");
+        let mut hooks = TestCoverage::new(&ir);
+        hooks.add(
+            &ir.operations[0],
+            "hooks.test.tsx > hooks exports > useListPets",
+        );
+        coverage.merge(hooks);
+
+        assert_eq!(coverage.untested(), ["createPet"]);
+        assert_eq!(coverage.operations["listPets"].len(), 2);
+        let json = coverage.to_json();
+        assert!(json.contains("\"createPet\": []"), "{json}");
+        assert_eq!(TestCoverage::from_json(&json).unwrap(), coverage);
+    }
+}
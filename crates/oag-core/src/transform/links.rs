@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use crate::ir::{IrLink, IrLinkParameter, IrOperation, IrParameterLocation};
+use crate::parse::response::{LinkOrRef, ResponseOrRef};
+use crate::parse::spec::OpenApiSpec;
+
+/// Fill in [`IrOperation::links`] from the `links` of each path operation's
+/// success responses.
+///
+/// Only links that name their target by `operationId` and fill every one of
+/// its parameters from `$response.body#/...` expressions are kept. Anything
+/// else (other runtime expressions, constants, `operationRef`, `requestBody`,
+/// unknown targets) is skipped with a diagnostic. `operation_path` maps a spec
+/// path to the path operations were generated under.
+pub fn resolve_links<'a>(
+    spec: &'a OpenApiSpec,
+    operation_path: impl Fn(&'a str) -> &'a str,
+    operations: &mut [IrOperation],
+) -> Vec<String> {
+    let index: HashMap<(&str, &str), usize> = operations
+        .iter()
+        .enumerate()
+        .filter(|(_, op)| !op.is_webhook)
+        .filter_map(|(i, op)| {
+            let location = op.source_location.as_ref()?;
+            Some(((location.path.as_str(), location.method.as_str()), i))
+        })
+        .collect();
+    let by_operation_id: HashMap<&str, usize> = spec
+        .paths
+        .iter()
+        .flat_map(|(path, item)| {
+            let path = operation_path(path);
+            item.operations()
+                .map(move |(method, op)| (path, method, op))
+        })
+        .filter_map(|(path, method, op)| {
+            Some((op.operation_id.as_deref()?, *index.get(&(path, method))?))
+        })
+        .collect();
+
+    let mut diagnostics = Vec::new();
+    let mut resolved = Vec::new();
+    for (path, item) in &spec.paths {
+        for (method, op) in item.operations() {
+            let Some(&source) = index.get(&(operation_path(path), method)) else {
+                continue;
+            };
+            let links = op
+                .responses
+                .iter()
+                .filter(|(status, _)| status.starts_with('2'))
+                .filter_map(|(_, response)| match response {
+                    ResponseOrRef::Response(r) => Some(&r.links),
+                    ResponseOrRef::Ref { .. } => None,
+                })
+                .flatten();
+            for (name, link) in links {
+                let LinkOrRef::Link(link) = link else {
+                    continue;
+                };
+                let label = format!("link `{name}` of {}", operations[source].label());
+                let target = match &link.operation_id {
+                    Some(id) => match by_operation_id.get(id.as_str()) {
+                        Some(&target) => target,
+                        None => {
+                            diagnostics
+                                .push(format!("{label} targets unknown operation `{id}`, skipped"));
+                            continue;
+                        }
+                    },
+                    None => {
+                        diagnostics.push(format!(
+                            "{label} uses `operationRef`, which isn't supported, skipped"
+                        ));
+                        continue;
+                    }
+                };
+                match link_parameters(&link.parameters, &operations[target]) {
+                    Ok(_) if link.request_body.is_some() => diagnostics.push(format!(
+                        "{label} sets `requestBody`, which isn't supported, skipped"
+                    )),
+                    Ok(parameter_mapping) => resolved.push((
+                        source,
+                        IrLink {
+                            name: name.clone(),
+                            target_operation: operations[target].name.clone(),
+                            parameter_mapping,
+                        },
+                    )),
+                    Err(reason) => diagnostics.push(format!("{label} {reason}, skipped")),
+                }
+            }
+        }
+    }
+
+    for (source, link) in resolved {
+        operations[source].links.push(link);
+    }
+    diagnostics
+}
+
+/// The pointer each of `target`'s parameters is read from, in the target's
+/// parameter order, or why the link can't be followed.
+fn link_parameters(
+    parameters: &indexmap::IndexMap<String, serde_json::Value>,
+    target: &IrOperation,
+) -> Result<Vec<IrLinkParameter>, String> {
+    let mut pointers: HashMap<(Option<IrParameterLocation>, &str), String> = HashMap::new();
+    for (key, value) in parameters {
+        let pointer = value
+            .as_str()
+            .and_then(|expr| expr.strip_prefix("$response.body#"))
+            .filter(|pointer| pointer.is_empty() || pointer.starts_with('/'))
+            .ok_or_else(|| {
+                format!(
+                    "maps `{key}` from {value}; only `$response.body#/...` expressions are supported"
+                )
+            })?;
+        pointers.insert(qualified_parameter(key), pointer.to_string());
+    }
+
+    let mut mapping = Vec::new();
+    let mut used = 0;
+    for param in &target.parameters {
+        let pointer = pointers
+            .get(&(Some(param.location), param.original_name.as_str()))
+            .or_else(|| pointers.get(&(None, param.original_name.as_str())));
+        match pointer {
+            Some(pointer) => {
+                used += 1;
+                mapping.push(IrLinkParameter {
+                    parameter: param.original_name.clone(),
+                    pointer: pointer.clone(),
+                });
+            }
+            None if param.required && param.pinned_value.is_none() => {
+                return Err(format!(
+                    "doesn't map required parameter `{}` of `{}`",
+                    param.original_name, target.name.camel_case
+                ));
+            }
+            None => {}
+        }
+    }
+    if used < pointers.len() {
+        return Err(format!(
+            "maps parameters `{}` doesn't declare",
+            target.name.camel_case
+        ));
+    }
+    if target
+        .request_body
+        .as_ref()
+        .is_some_and(|body| body.required)
+    {
+        return Err(format!(
+            "targets `{}`, which requires a request body",
+            target.name.camel_case
+        ));
+    }
+    Ok(mapping)
+}
+
+/// Split a link parameter key like `path.petId` into its location and name.
+fn qualified_parameter(key: &str) -> (Option<IrParameterLocation>, &str) {
+    let locations = [
+        ("path.", IrParameterLocation::Path),
+        ("query.", IrParameterLocation::Query),
+        ("header.", IrParameterLocation::Header),
+        ("cookie.", IrParameterLocation::Cookie),
+    ];
+    locations
+        .into_iter()
+        .find_map(|(prefix, location)| Some((Some(location), key.strip_prefix(prefix)?)))
+        .unwrap_or((None, key))
+}
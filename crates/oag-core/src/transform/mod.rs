@@ -1,7 +1,15 @@
+pub mod links;
+pub mod name_collisions;
 pub mod name_normalizer;
+pub mod passes;
 pub mod promote_inline;
 pub mod schema_resolver;
+pub mod shared_components;
 pub mod spec_to_ir;
 pub mod sse_detector;
+pub mod validate;
 
-pub use spec_to_ir::{TransformOptions, transform, transform_with_options};
+pub use name_normalizer::normalize_name;
+pub use passes::PostPass;
+pub use schema_resolver::schema_or_ref_to_ir_type;
+pub use spec_to_ir::{TransformOptions, transform, transform_owned, transform_with_options};
@@ -0,0 +1,250 @@
+use std::collections::{HashMap, HashSet};
+
+use indexmap::IndexMap;
+
+use crate::config::CollisionStrategy;
+use crate::error::TransformError;
+use crate::parse::schema::SchemaOrRef;
+use crate::parse::spec::OpenApiSpec;
+
+use super::name_normalizer::normalize_name;
+
+const SCHEMA_REF_PREFIX: &str = "#/components/schemas/";
+
+/// Names the generated code already uses for other types: TypeScript globals
+/// and utility types, and what the Python modules import from `typing`,
+/// pydantic and FastAPI. A schema with one of these names would shadow it.
+pub const RESERVED_TYPE_NAMES: &[&str] = &[
+    // TypeScript
+    "AbortSignal",
+    "Array",
+    "Blob",
+    "Boolean",
+    "Date",
+    "Error",
+    "EventSource",
+    "File",
+    "FormData",
+    "Headers",
+    "JSON",
+    "Map",
+    "Number",
+    "Object",
+    "Omit",
+    "Partial",
+    "Promise",
+    "ReadableStream",
+    "Record",
+    "Request",
+    "RequestInit",
+    "Response",
+    "Set",
+    "String",
+    "URL",
+    "URLSearchParams",
+    // Python
+    "Annotated",
+    "Any",
+    "BaseModel",
+    "Depends",
+    "Enum",
+    "Field",
+    "Header",
+    "Literal",
+    "Path",
+    "Query",
+    "Required",
+    "Session",
+    "StreamingResponse",
+    "TypedDict",
+];
+
+/// Detect component schemas whose names normalize to the same PascalCase name
+/// (`user-profile` and `UserProfile`), which would otherwise emit duplicate types.
+///
+/// With [`CollisionStrategy::Error`] the first collision is returned as an error.
+/// With [`CollisionStrategy::Suffix`] every later schema in spec order is renamed
+/// to `{Name}2`, `{Name}3`, ... and all `$ref`s to it are rewritten; one diagnostic
+/// per rename is returned.
+///
+/// Regardless of the strategy, a schema named after one of the
+/// [`RESERVED_TYPE_NAMES`] is renamed to `{Name}Schema`, with a diagnostic.
+pub fn resolve_schema_name_collisions(
+    spec: &mut OpenApiSpec,
+    strategy: CollisionStrategy,
+) -> Result<Vec<String>, TransformError> {
+    let Some(components) = &spec.components else {
+        return Ok(vec![]);
+    };
+
+    let mut used: HashSet<String> = components
+        .schemas
+        .keys()
+        .map(|name| normalize_name(name).pascal_case)
+        .collect();
+    let mut owners: HashMap<String, &str> = HashMap::new();
+    let mut renames: Vec<(String, String)> = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for name in components.schemas.keys() {
+        let pascal = normalize_name(name).pascal_case;
+        if RESERVED_TYPE_NAMES.contains(&pascal.as_str()) {
+            let schema = format!("{pascal}Schema");
+            let renamed = if used.insert(schema.clone()) {
+                schema
+            } else {
+                numbered_name(&mut used, &schema)
+            };
+            diagnostics.push(format!(
+                "schema `{name}` would shadow the built-in `{pascal}`; renamed to `{renamed}`"
+            ));
+            renames.push((name.clone(), renamed));
+            continue;
+        }
+        let Some(first) = owners.get(&pascal) else {
+            owners.insert(pascal, name);
+            continue;
+        };
+        match strategy {
+            CollisionStrategy::Error => {
+                return Err(TransformError::NameCollision {
+                    first: first.to_string(),
+                    second: name.clone(),
+                    name: pascal,
+                });
+            }
+            CollisionStrategy::Suffix => {
+                let renamed = numbered_name(&mut used, &pascal);
+                diagnostics.push(format!(
+                    "schema `{name}` collides with `{first}` as `{pascal}`; renamed to `{renamed}`"
+                ));
+                renames.push((name.clone(), renamed));
+            }
+        }
+    }
+
+    if !renames.is_empty() {
+        rename_schemas(spec, &renames)?;
+    }
+    Ok(diagnostics)
+}
+
+/// Rename component schemas as `naming.schema_aliases` asks, rewriting every
+/// reference to them. Keys match a schema's name in the spec or its generated
+/// name (`inline_response_200_1` or `InlineResponse2001`); keys that match
+/// nothing produce a diagnostic.
+///
+/// A schema can't take a name another schema already normalizes to, even one
+/// that is itself renamed, nor the same name as another alias.
+pub fn apply_schema_aliases(
+    spec: &mut OpenApiSpec,
+    aliases: &IndexMap<String, String>,
+) -> Result<Vec<String>, TransformError> {
+    let Some(components) = spec.components.as_ref().filter(|_| !aliases.is_empty()) else {
+        return Ok(vec![]);
+    };
+
+    let mut owners: HashMap<String, &str> = components
+        .schemas
+        .keys()
+        .map(|name| (normalize_name(name).pascal_case, name.as_str()))
+        .collect();
+    let mut renames: Vec<(String, String)> = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    for (from, to) in aliases {
+        let schema = components
+            .schemas
+            .get_key_value(from)
+            .map(|(name, _)| name)
+            .or_else(|| {
+                components
+                    .schemas
+                    .keys()
+                    .find(|name| normalize_name(name).pascal_case == *from)
+            });
+        let Some(schema) = schema else {
+            diagnostics.push(format!(
+                "`naming.schema_aliases` entry `{from}` matches no schema"
+            ));
+            continue;
+        };
+
+        let name = normalize_name(to).pascal_case;
+        match owners.get(&name) {
+            Some(existing) if *existing == schema => {}
+            Some(existing) => {
+                return Err(TransformError::SchemaAliasCollision {
+                    schema: schema.clone(),
+                    name,
+                    existing: existing.to_string(),
+                });
+            }
+            None => {
+                owners.insert(name, schema);
+            }
+        }
+        renames.push((schema.clone(), to.clone()));
+    }
+
+    if !renames.is_empty() {
+        rename_schemas(spec, &renames)?;
+    }
+    Ok(diagnostics)
+}
+
+/// The first of `{name}2`, `{name}3`, ... not in `used`, which it's then added to.
+fn numbered_name(used: &mut HashSet<String>, name: &str) -> String {
+    let mut i = 2;
+    loop {
+        let candidate = format!("{name}{i}");
+        if used.insert(candidate.clone()) {
+            return candidate;
+        }
+        i += 1;
+    }
+}
+
+/// Rename component schemas and rewrite every reference to them (`$ref`s and
+/// discriminator mappings). `$ref`s are rewritten on the JSON text so that map
+/// ordering is preserved; mappings to a bare schema name (`cat: Cat`) only on
+/// component schemas, the only ones that keep a discriminator.
+fn rename_schemas(
+    spec: &mut OpenApiSpec,
+    renames: &[(String, String)],
+) -> Result<(), TransformError> {
+    let to_json = |s: &str| serde_json::to_string(s).expect("strings always serialize");
+    let mut json = serde_json::to_string(&*spec)
+        .map_err(|e| TransformError::Other(format!("failed to rename schemas: {e}")))?;
+    for (from, to) in renames {
+        json = json.replace(
+            &to_json(&format!("{SCHEMA_REF_PREFIX}{from}")),
+            &to_json(&format!("{SCHEMA_REF_PREFIX}{to}")),
+        );
+    }
+    *spec = serde_json::from_str(&json)
+        .map_err(|e| TransformError::Other(format!("failed to rename schemas: {e}")))?;
+
+    if let Some(components) = &mut spec.components {
+        let renamed = |name: String| {
+            renames
+                .iter()
+                .find(|(from, _)| *from == name)
+                .map_or(name, |(_, to)| to.clone())
+        };
+        components.schemas = std::mem::take(&mut components.schemas)
+            .into_iter()
+            .map(|(name, mut schema)| {
+                if let SchemaOrRef::Schema(schema) = &mut schema
+                    && let Some(discriminator) = &mut schema.discriminator
+                {
+                    for target in discriminator.mapping.values_mut() {
+                        *target = renamed(std::mem::take(target));
+                    }
+                }
+                (renamed(name), schema)
+            })
+            .collect();
+    }
+    Ok(())
+}
@@ -1,4 +1,5 @@
 use heck::{ToLowerCamelCase, ToPascalCase, ToShoutySnakeCase, ToSnakeCase};
+use indexmap::IndexMap;
 
 use crate::ir::NormalizedName;
 
@@ -16,6 +17,93 @@ pub fn normalize_name(name: &str) -> NormalizedName {
     }
 }
 
+/// Look up the alias for an operation name.
+///
+/// An exact key wins; otherwise keys containing `*` are tried in map order.
+/// Each `*` matches any run of characters, and `$1`, `$2`, ... in the value
+/// insert what they matched. An empty value keeps just the matched text, so
+/// `api_v2_*: ""` strips the prefix.
+///
+/// Examples:
+/// - `api_v2_*: ""` maps `api_v2_listPets` → `listPets`
+/// - `*_internal: "$1"` maps `getUser_internal` → `getUser`
+pub fn resolve_alias(name: &str, aliases: &IndexMap<String, String>) -> Option<String> {
+    if let Some(alias) = aliases.get(name) {
+        return Some(alias.clone());
+    }
+    aliases
+        .iter()
+        .filter(|(pattern, _)| pattern.contains('*'))
+        .find_map(|(pattern, alias)| {
+            let captures = glob_captures(pattern, name)?;
+            if alias.is_empty() {
+                return Some(captures.concat());
+            }
+            // Replace higher indices first so `$1` doesn't eat the start of `$10`.
+            let mut resolved = alias.clone();
+            for (i, capture) in captures.iter().enumerate().rev() {
+                resolved = resolved.replace(&format!("${}", i + 1), capture);
+            }
+            Some(resolved)
+        })
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any run of characters.
+pub fn glob_matches(pattern: &str, name: &str) -> bool {
+    if pattern.contains('*') {
+        glob_captures(pattern, name).is_some()
+    } else {
+        pattern == name
+    }
+}
+
+/// Match `name` against a pattern where `*` matches any (possibly empty) run of
+/// characters, returning the text each `*` matched.
+fn glob_captures<'a>(pattern: &str, name: &'a str) -> Option<Vec<&'a str>> {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, rest) = parts.split_first()?;
+    let mut remaining = name.strip_prefix(first)?;
+    let mut captures = Vec::new();
+    for (i, part) in rest.iter().enumerate() {
+        let end = if i == rest.len() - 1 {
+            // The last literal must end the name.
+            remaining.strip_suffix(part)?.len()
+        } else {
+            remaining.find(part)?
+        };
+        captures.push(&remaining[..end]);
+        remaining = &remaining[end + part.len()..];
+    }
+    Some(captures)
+}
+
+/// Normalize path template parameters to camelCase, leaving the rest of the
+/// path verbatim.
+///
+/// Examples:
+/// - `/pets/{pet_id}` → `/pets/{petId}`
+/// - `/pets/{PET_ID}/toys` → `/pets/{petId}/toys`
+/// - `/files/{file-name}.json` → `/files/{fileName}.json`
+pub fn normalize_path(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut rest = path;
+
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let end = start + len;
+        result.push_str(&rest[..start]);
+        result.push('{');
+        result.push_str(&normalize_name(&rest[start + 1..end]).camel_case);
+        result.push('}');
+        rest = &rest[end + 1..];
+    }
+
+    result.push_str(rest);
+    result
+}
+
 /// Derive a camelCase operation name from HTTP method + path.
 ///
 /// Examples:
@@ -95,6 +183,75 @@ fn singularize(word: &str) -> String {
     }
 }
 
+/// Words TypeScript won't accept as a binding or `export * as` name.
+pub const TS_RESERVED_WORDS: &[&str] = &[
+    "arguments",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "debugger",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "enum",
+    "eval",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "implements",
+    "import",
+    "in",
+    "instanceof",
+    "interface",
+    "let",
+    "new",
+    "null",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    "yield",
+];
+
+/// Python keywords, which can't name a function, parameter or module.
+pub const PYTHON_RESERVED_WORDS: &[&str] = &[
+    "False", "None", "True", "and", "as", "assert", "async", "await", "break", "class", "continue",
+    "def", "del", "elif", "else", "except", "finally", "for", "from", "global", "if", "import",
+    "in", "is", "lambda", "nonlocal", "not", "or", "pass", "raise", "return", "try", "while",
+    "with", "yield",
+];
+
+/// `name` with a trailing `_` if it is one of `reserved`, e.g. `default` → `default_`.
+pub fn escape_reserved(name: &str, reserved: &[&str]) -> String {
+    if reserved.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
 /// Sanitize a string to be a valid identifier.
 fn sanitize_identifier(name: &str) -> String {
     let mut result = String::with_capacity(name.len());
@@ -210,6 +367,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_route_to_name_ignores_trailing_slash() {
+        assert_eq!(
+            route_to_name("GET", "/pets/"),
+            route_to_name("GET", "/pets")
+        );
+        assert_eq!(
+            route_to_name("GET", "/pets/{petId}/"),
+            route_to_name("GET", "/pets/{petId}")
+        );
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(normalize_path("/pets/{petId}"), "/pets/{petId}");
+        assert_eq!(normalize_path("/pets/{pet_id}"), "/pets/{petId}");
+        assert_eq!(
+            normalize_path("/pets/{PET_ID}/toys/{toy-id}"),
+            "/pets/{petId}/toys/{toyId}"
+        );
+        assert_eq!(normalize_path("/pets"), "/pets");
+        assert_eq!(normalize_path("/broken/{id"), "/broken/{id");
+    }
+
+    #[test]
+    fn test_escape_reserved() {
+        assert_eq!(escape_reserved("default", TS_RESERVED_WORDS), "default_");
+        assert_eq!(escape_reserved("pets", TS_RESERVED_WORDS), "pets");
+        assert_eq!(escape_reserved("import", PYTHON_RESERVED_WORDS), "import_");
+        assert_eq!(escape_reserved("default", PYTHON_RESERVED_WORDS), "default");
+    }
+
+    #[test]
+    fn test_glob_matches() {
+        assert!(glob_matches("getPet", "getPet"));
+        assert!(!glob_matches("getPet", "getPets"));
+        assert!(glob_matches("/store/*", "/store/inventory"));
+        assert!(glob_matches("/store/*", "/store/orders/{orderId}"));
+        assert!(!glob_matches("/store/*", "/stores"));
+        assert!(glob_matches("*Pet", "deletePet"));
+    }
+
+    #[test]
+    fn test_resolve_alias() {
+        let aliases: IndexMap<String, String> = [
+            ("createChatCompletion", "chat"),
+            ("api_v2_*", ""),
+            ("*_internal", "$1"),
+            ("*_v*_beta", "$1V$2"),
+            ("*", "unused"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+
+        assert_eq!(
+            resolve_alias("createChatCompletion", &aliases).as_deref(),
+            Some("chat")
+        );
+        assert_eq!(
+            resolve_alias("api_v2_listPets", &aliases).as_deref(),
+            Some("listPets")
+        );
+        assert_eq!(
+            resolve_alias("getUser_internal", &aliases).as_deref(),
+            Some("getUser")
+        );
+        assert_eq!(
+            resolve_alias("search_v3_beta", &aliases).as_deref(),
+            Some("searchV3")
+        );
+        // Patterns are tried in map order, so the catch-all only sees the rest.
+        assert_eq!(
+            resolve_alias("listPets", &aliases).as_deref(),
+            Some("unused")
+        );
+        assert_eq!(resolve_alias("listPets", &IndexMap::new()), None);
+    }
+
     #[test]
     fn test_singularize_glasses() {
         assert_eq!(singularize("glasses"), "glass");
@@ -0,0 +1,634 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use crate::config::IrPass;
+use crate::ir::grouping::{operation_refs, schema_refs};
+use crate::ir::{IrField, IrObjectSchema, IrReturnType, IrSchema, IrSpec, IrType};
+
+use super::name_normalizer::normalize_name;
+
+/// A project-specific pass over the finished IR, e.g. rewriting a vendor
+/// extension's envelopes into the project's own shape. Set it in
+/// `TransformOptions::post_passes`.
+#[derive(Clone)]
+pub struct PostPass(Arc<dyn Fn(&mut IrSpec) + Send + Sync>);
+
+impl PostPass {
+    pub fn new(pass: impl Fn(&mut IrSpec) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(pass))
+    }
+}
+
+impl fmt::Debug for PostPass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PostPass(..)")
+    }
+}
+
+/// Run the built-in `passes`, then the `post_passes`, each in the given order.
+pub fn run_passes(ir: &mut IrSpec, passes: &[IrPass], post_passes: &[PostPass]) {
+    for pass in passes {
+        log::debug!("running IR pass `{}`", pass.as_str());
+        run_pass(*pass, ir);
+    }
+    for (i, pass) in post_passes.iter().enumerate() {
+        log::debug!("running IR post pass {} of {}", i + 1, post_passes.len());
+        (pass.0)(ir);
+    }
+}
+
+/// Run one built-in pass.
+pub fn run_pass(pass: IrPass, ir: &mut IrSpec) {
+    match pass {
+        IrPass::PruneUnused => prune_unused(ir),
+        IrPass::DedupeInline => dedupe_inline(ir),
+        IrPass::FlattenIntersections => flatten_intersections(ir),
+    }
+}
+
+/// Drop every schema that no operation or webhook reaches, directly or
+/// through other schemas.
+pub fn prune_unused(ir: &mut IrSpec) {
+    let reached = {
+        let index: HashMap<&str, usize> = ir
+            .schemas
+            .iter()
+            .enumerate()
+            .map(|(i, s)| (s.name().pascal_case.as_str(), i))
+            .collect();
+        let mut refs = Vec::new();
+        for op in ir.operations.iter().chain(&ir.webhooks) {
+            operation_refs(op, &mut refs);
+        }
+        let mut reached = vec![false; ir.schemas.len()];
+        let mut stack: Vec<usize> = refs.iter().filter_map(|r| index.get(r).copied()).collect();
+        while let Some(i) = stack.pop() {
+            if !reached[i] {
+                reached[i] = true;
+                let mut deps = Vec::new();
+                schema_refs(&ir.schemas[i], &mut deps);
+                stack.extend(deps.iter().filter_map(|r| index.get(r).copied()));
+            }
+        }
+        reached
+    };
+
+    let mut reached = reached.into_iter();
+    ir.schemas.retain(|_| reached.next().unwrap_or(true));
+}
+
+/// Prepend `prefix` to every schema's generated names and to the SSE event
+/// unions' (`Api` turns `Pet` into `ApiPet` and `petSchema`-style derived
+/// names into `apiPetSchema`), rewriting every reference and discriminator
+/// mapping. `original`, the spec's name, is kept.
+pub fn prefix_type_names(ir: &mut IrSpec, prefix: &str) {
+    if prefix.is_empty() {
+        return;
+    }
+    let prefixed = |name: &str| format!("{prefix}{name}");
+    let renames: HashMap<String, String> = ir
+        .schemas
+        .iter()
+        .map(|s| {
+            let name = &s.name().pascal_case;
+            (name.clone(), prefixed(name))
+        })
+        .collect();
+
+    for schema in &mut ir.schemas {
+        let name = match schema {
+            IrSchema::Object(o) => &mut o.name,
+            IrSchema::Enum(e) => &mut e.name,
+            IrSchema::Alias(a) => &mut a.name,
+            IrSchema::Union(u) => {
+                if let Some(discriminator) = &mut u.discriminator {
+                    for (_, target) in &mut discriminator.mapping {
+                        if let Some(renamed) = renames.get(target) {
+                            *target = renamed.clone();
+                        }
+                    }
+                }
+                &mut u.name
+            }
+        };
+        let pascal_case = prefixed(&name.pascal_case);
+        let normalized = normalize_name(&pascal_case);
+        name.camel_case = normalized.camel_case;
+        name.snake_case = normalized.snake_case;
+        name.screaming_snake = normalized.screaming_snake;
+        name.pascal_case = pascal_case;
+    }
+    for op in ir.operations.iter_mut().chain(&mut ir.webhooks) {
+        if let IrReturnType::Sse(sse) = &mut op.return_type
+            && let Some(name) = &mut sse.event_type_name
+        {
+            *name = prefixed(name);
+        }
+    }
+    for_each_type_mut(ir, &mut |ty| rename_refs(ty, &renames));
+}
+
+/// Merge object schemas promoted from inline objects that have the same
+/// fields, keeping the first one's name and pointing refs to the others at
+/// it. Repeats until nothing merges, so parents that only differed in which
+/// duplicate they referenced merge too.
+///
+/// Component schemas are never merged. They are told apart by
+/// `IrSpec::raw_spec`, so without it nothing is.
+pub fn dedupe_inline(ir: &mut IrSpec) {
+    let Some(spec) = ir.raw_spec.clone() else {
+        return;
+    };
+    let components: HashSet<String> = spec
+        .components
+        .iter()
+        .flat_map(|c| c.schemas.keys())
+        .map(|name| normalize_name(name).pascal_case)
+        .collect();
+
+    loop {
+        let mut kept: Vec<&IrObjectSchema> = Vec::new();
+        let mut renames: HashMap<String, String> = HashMap::new();
+        for schema in &ir.schemas {
+            let IrSchema::Object(obj) = schema else {
+                continue;
+            };
+            if components.contains(&obj.name.pascal_case) {
+                continue;
+            }
+            match kept.iter().find(|k| same_shape(k, obj)) {
+                Some(first) => {
+                    renames.insert(obj.name.pascal_case.clone(), first.name.pascal_case.clone());
+                }
+                None => kept.push(obj),
+            }
+        }
+        if renames.is_empty() {
+            return;
+        }
+
+        ir.schemas
+            .retain(|s| !renames.contains_key(&s.name().pascal_case));
+        for_each_type_mut(ir, &mut |ty| rename_refs(ty, &renames));
+    }
+}
+
+fn same_shape(a: &IrObjectSchema, b: &IrObjectSchema) -> bool {
+    a.description == b.description
+        && a.additional_properties == b.additional_properties
+        && a.fields.len() == b.fields.len()
+        && a.fields.iter().zip(&b.fields).all(|(x, y)| {
+            x.original_name == y.original_name
+                && x.field_type == y.field_type
+                && x.required == y.required
+                && x.description == y.description
+                && x.read_only == y.read_only
+                && x.write_only == y.write_only
+                && x.deprecated == y.deprecated
+        })
+}
+
+fn rename_refs(ty: &mut IrType, renames: &HashMap<String, String>) {
+    match ty {
+        IrType::Ref(name) => {
+            if let Some(new_name) = renames.get(name) {
+                *name = new_name.clone();
+            }
+        }
+        IrType::Array(inner) | IrType::Map(inner, _) => rename_refs(inner, renames),
+        IrType::Object(fields) => {
+            for (_, field_type, _) in fields {
+                rename_refs(field_type, renames);
+            }
+        }
+        IrType::Union(types) | IrType::Intersection(types) => {
+            for t in types {
+                rename_refs(t, renames);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replace each alias of an `allOf` intersection whose parts are all object
+/// schemas (or inline objects) by one object schema with their fields. A field
+/// in several parts is taken from the last one; if the parts disagree on its
+/// type or whether it is required, that is noted in `ir.diagnostics`. Repeats
+/// until nothing changes, so aliases built on flattened aliases flatten too.
+/// Intersections with other parts are left alone.
+pub fn flatten_intersections(ir: &mut IrSpec) {
+    loop {
+        let mut changed = false;
+        for i in 0..ir.schemas.len() {
+            let IrSchema::Alias(alias) = &ir.schemas[i] else {
+                continue;
+            };
+            let IrType::Intersection(parts) = &alias.target else {
+                continue;
+            };
+            let mut conflicts = Vec::new();
+            let Some(fields) = merged_fields(&ir.schemas, parts, &mut conflicts) else {
+                continue;
+            };
+            for field in conflicts {
+                ir.diagnostics.push(format!(
+                    "schema `{}` gets field `{field}` from `allOf` parts that disagree on it; the last part wins",
+                    alias.name.original,
+                ));
+            }
+            ir.schemas[i] = IrSchema::Object(IrObjectSchema {
+                name: alias.name.clone(),
+                description: alias.description.clone(),
+                fields,
+                additional_properties: None,
+                unsupported_keywords: alias.unsupported_keywords.clone(),
+                example: alias.example.clone(),
+            });
+            changed = true;
+        }
+        if !changed {
+            return;
+        }
+    }
+}
+
+/// The fields of all `parts`, or `None` if one is not an object without
+/// additional properties. Fields that later parts redefine differently are
+/// added to `conflicts`.
+fn merged_fields(
+    schemas: &[IrSchema],
+    parts: &[IrType],
+    conflicts: &mut Vec<String>,
+) -> Option<Vec<IrField>> {
+    let mut merged: Vec<IrField> = Vec::new();
+    for part in parts {
+        let fields: Vec<IrField> = match part {
+            IrType::Ref(name) => match schemas.iter().find(|s| s.name().pascal_case == *name) {
+                Some(IrSchema::Object(obj)) if obj.additional_properties.is_none() => {
+                    obj.fields.clone()
+                }
+                _ => return None,
+            },
+            IrType::Object(fields) => fields
+                .iter()
+                .map(|(field_name, field_type, required)| IrField {
+                    name: normalize_name(field_name),
+                    original_name: field_name.clone(),
+                    field_type: field_type.clone(),
+                    required: *required,
+                    description: None,
+                    read_only: false,
+                    write_only: false,
+                    deprecated: false,
+                    default: None,
+                    number_hints: Default::default(),
+                })
+                .collect(),
+            _ => return None,
+        };
+        for field in fields {
+            match merged
+                .iter_mut()
+                .find(|f| f.original_name == field.original_name)
+            {
+                Some(existing) => {
+                    if existing.field_type != field.field_type
+                        || existing.required != field.required
+                    {
+                        conflicts.push(field.original_name.clone());
+                    }
+                    *existing = field;
+                }
+                None => merged.push(field),
+            }
+        }
+    }
+    Some(merged)
+}
+
+/// Call `f` on every top-level type in schemas, operations and webhooks.
+fn for_each_type_mut(ir: &mut IrSpec, f: &mut impl FnMut(&mut IrType)) {
+    for schema in &mut ir.schemas {
+        match schema {
+            IrSchema::Object(obj) => {
+                for field in &mut obj.fields {
+                    f(&mut field.field_type);
+                }
+                if let Some(additional) = &mut obj.additional_properties {
+                    f(additional);
+                }
+            }
+            IrSchema::Alias(alias) => f(&mut alias.target),
+            IrSchema::Union(union) => {
+                for variant in &mut union.variants {
+                    f(variant);
+                }
+            }
+            IrSchema::Enum(_) => {}
+        }
+    }
+    for op in ir.operations.iter_mut().chain(&mut ir.webhooks) {
+        match &mut op.return_type {
+            IrReturnType::Standard(resp) => f(&mut resp.response_type),
+            IrReturnType::Sse(sse) => {
+                f(&mut sse.event_type);
+                for variant in &mut sse.variants {
+                    f(variant);
+                }
+                if let Some(json) = &mut sse.json_response {
+                    f(&mut json.response_type);
+                }
+            }
+            IrReturnType::Void => {}
+        }
+        for error in &mut op.errors {
+            f(&mut error.response_type);
+        }
+        if let Some(body) = &mut op.request_body {
+            f(&mut body.body_type);
+        }
+        for param in &mut op.parameters {
+            f(&mut param.param_type);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transform(yaml: &str) -> IrSpec {
+        let spec = crate::parse::from_yaml(yaml).unwrap();
+        crate::transform::transform(&spec).unwrap()
+    }
+
+    fn schema_names(ir: &IrSpec) -> Vec<&str> {
+        ir.schemas
+            .iter()
+            .map(|s| s.name().pascal_case.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn prune_unused_keeps_what_operations_reach() {
+        let mut ir = transform(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        "200":
+          description: OK
+          content:
+            application/json:
+              schema: { type: array, items: { $ref: "#/components/schemas/Pet" } }
+webhooks:
+  petAdopted:
+    post:
+      requestBody:
+        content:
+          application/json:
+            schema: { $ref: "#/components/schemas/Adoption" }
+      responses:
+        "200": { description: OK }
+components:
+  schemas:
+    Pet:
+      type: object
+      properties:
+        owner: { $ref: "#/components/schemas/Owner" }
+    Owner:
+      type: object
+      properties:
+        name: { type: string }
+    Adoption:
+      type: object
+      properties:
+        at: { type: string }
+    Legacy:
+      type: object
+      properties:
+        pet: { $ref: "#/components/schemas/Pet" }
+"##,
+        );
+        prune_unused(&mut ir);
+        assert_eq!(schema_names(&ir), ["Pet", "Owner", "Adoption"]);
+    }
+
+    #[test]
+    fn dedupe_inline_merges_identical_promoted_objects() {
+        let mut ir = transform(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths:
+  /pets:
+    post:
+      operationId: createPet
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name: { type: string }
+                tag: { type: object, properties: { label: { type: string } } }
+      responses:
+        "204": { description: Created }
+    put:
+      operationId: replacePet
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: object
+              required: [name]
+              properties:
+                name: { type: string }
+                tag: { type: object, properties: { label: { type: string } } }
+      responses:
+        "204": { description: Replaced }
+components:
+  schemas:
+    Tag:
+      type: object
+      properties:
+        label: { type: string }
+"##,
+        );
+        assert_eq!(
+            schema_names(&ir),
+            [
+                "Tag",
+                "CreatePetBodyTag",
+                "CreatePetBody",
+                "ReplacePetBodyTag",
+                "ReplacePetBody"
+            ]
+        );
+
+        dedupe_inline(&mut ir);
+        // `Tag` is a component, so it stays even though it has the same shape.
+        assert_eq!(
+            schema_names(&ir),
+            ["Tag", "CreatePetBodyTag", "CreatePetBody"]
+        );
+        let body = ir.operations[1].request_body.as_ref().unwrap();
+        assert_eq!(body.body_type, IrType::Ref("CreatePetBody".to_string()));
+    }
+
+    #[test]
+    fn prefix_type_names_renames_schemas_and_references() {
+        let mut ir = transform(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        "200":
+          description: Pets
+          content:
+            application/json:
+              schema: { type: array, items: { $ref: "#/components/schemas/Pet" } }
+components:
+  schemas:
+    Pet:
+      oneOf:
+        - $ref: "#/components/schemas/Cat"
+      discriminator:
+        propertyName: kind
+        mapping: { cat: "#/components/schemas/Cat" }
+    Cat:
+      type: object
+      properties:
+        kind: { type: string }
+"##,
+        );
+        prefix_type_names(&mut ir, "Api");
+
+        assert_eq!(schema_names(&ir), ["ApiPet", "ApiCat"]);
+        let IrSchema::Union(pet) = &ir.schemas[0] else {
+            panic!("expected a union");
+        };
+        assert_eq!(pet.name.original, "Pet");
+        assert_eq!(pet.name.camel_case, "apiPet");
+        assert_eq!(pet.variants, [IrType::Ref("ApiCat".to_string())]);
+        let mapping = &pet.discriminator.as_ref().unwrap().mapping;
+        assert_eq!(mapping, &[("cat".to_string(), "ApiCat".to_string())]);
+        let IrReturnType::Standard(resp) = &ir.operations[0].return_type else {
+            panic!("expected a JSON response");
+        };
+        assert_eq!(
+            resp.response_type,
+            IrType::Array(Box::new(IrType::Ref("ApiPet".to_string())))
+        );
+    }
+
+    #[test]
+    fn flatten_intersections_merges_object_parts() {
+        let mut ir = transform(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths: {}
+components:
+  schemas:
+    Named:
+      type: object
+      properties:
+        name: { type: string }
+    Pet:
+      allOf:
+        - $ref: "#/components/schemas/Named"
+        - type: object
+          required: [tag]
+          properties:
+            tag: { type: string }
+    Dog:
+      allOf:
+        - $ref: "#/components/schemas/Pet"
+        - type: object
+          properties:
+            breed: { type: string }
+    Loose:
+      allOf:
+        - $ref: "#/components/schemas/Pet"
+        - type: object
+          properties:
+            tag: { type: integer }
+    Labelled:
+      allOf:
+        - $ref: "#/components/schemas/Named"
+        - $ref: "#/components/schemas/Tags"
+    Tags:
+      type: object
+      additionalProperties: { type: string }
+"##,
+        );
+        flatten_intersections(&mut ir);
+
+        let fields = |name: &str| match ir.schemas.iter().find(|s| s.name().pascal_case == name) {
+            Some(IrSchema::Object(obj)) => obj
+                .fields
+                .iter()
+                .map(|f| (f.original_name.as_str(), f.required))
+                .collect::<Vec<_>>(),
+            other => panic!("{name} is not an object: {other:?}"),
+        };
+        assert_eq!(fields("Pet"), [("name", false), ("tag", true)]);
+        assert_eq!(
+            fields("Dog"),
+            [("name", false), ("tag", true), ("breed", false)]
+        );
+        // The later part's `tag` wins, optionality included, and the clash is reported.
+        assert_eq!(fields("Loose"), [("name", false), ("tag", false)]);
+        assert_eq!(
+            ir.diagnostics,
+            [
+                "schema `Loose` gets field `tag` from `allOf` parts that disagree on it; the last part wins"
+            ]
+        );
+        // A part with additional properties can't be merged into plain fields.
+        assert!(matches!(
+            ir.schemas
+                .iter()
+                .find(|s| s.name().pascal_case == "Labelled"),
+            Some(IrSchema::Alias(_))
+        ));
+    }
+
+    #[test]
+    fn passes_run_in_order() {
+        let order = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let post = |label: &'static str| {
+            let order = order.clone();
+            PostPass::new(move |ir: &mut IrSpec| {
+                order.lock().unwrap().push((label, ir.schemas.len()));
+            })
+        };
+        let mut ir = transform(
+            r##"
+openapi: "3.1.0"
+info: { title: Test, version: "1.0" }
+paths: {}
+components:
+  schemas:
+    Unused: { type: string }
+"##,
+        );
+        run_passes(
+            &mut ir,
+            &[IrPass::PruneUnused],
+            &[post("first"), post("second")],
+        );
+        assert_eq!(*order.lock().unwrap(), [("first", 0), ("second", 0)]);
+    }
+}
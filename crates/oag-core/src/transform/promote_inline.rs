@@ -37,7 +37,7 @@ pub fn promote_inline_objects(ir: &mut IrSpec) {
     }
 
     // Phase 2: Walk operations and promote inline objects in return types, request bodies, and parameters
-    for op in &mut ir.operations {
+    for op in ir.operations.iter_mut().chain(ir.webhooks.iter_mut()) {
         let op_pascal = op.name.pascal_case.clone();
 
         // Return type
@@ -70,6 +70,22 @@ pub fn promote_inline_objects(ir: &mut IrSpec) {
             crate::ir::IrReturnType::Void => {}
         }
 
+        // Error responses
+        for error in &mut op.errors {
+            let status = if error.is_default() {
+                "Default"
+            } else {
+                error.status.as_str()
+            };
+            let ctx = format!("{}{}Error", op_pascal, status);
+            promote_type(
+                &ctx,
+                &mut error.response_type,
+                &mut new_schemas,
+                &mut used_names,
+            );
+        }
+
         // Request body
         if let Some(ref mut body) = op.request_body {
             let ctx = format!("{}Body", op_pascal);
@@ -114,6 +130,9 @@ fn promote_type(
                     description: None,
                     read_only: false,
                     write_only: false,
+                    deprecated: false,
+                    default: None,
+                    number_hints: Default::default(),
                 })
                 .collect();
 
@@ -129,6 +148,8 @@ fn promote_type(
                 description: None,
                 fields: ir_fields,
                 additional_properties: None,
+                unsupported_keywords: vec![],
+                example: None,
             }));
 
             *ir_type = IrType::Ref(name);
@@ -137,13 +158,13 @@ fn promote_type(
             let item_ctx = format!("{}Item", context_name);
             promote_type(&item_ctx, inner, new_schemas, used_names);
         }
-        IrType::Map(inner) => {
+        IrType::Map(inner, _) => {
             let value_ctx = format!("{}Value", context_name);
             promote_type(&value_ctx, inner, new_schemas, used_names);
         }
         IrType::Union(variants) => {
-            for (i, variant) in variants.iter_mut().enumerate() {
-                let variant_ctx = format!("{}Variant{}", context_name, i + 1);
+            let contexts = variant_contexts(context_name, variants);
+            for (variant, variant_ctx) in variants.iter_mut().zip(contexts) {
                 promote_type(&variant_ctx, variant, new_schemas, used_names);
             }
         }
@@ -157,6 +178,46 @@ fn promote_type(
     }
 }
 
+/// Name each union variant after its content so names survive variant insertion
+/// and reordering: `{Context}{Label}Variant`, where the label is the first string
+/// literal field (a discriminator such as `type: "text"`) or else the first
+/// required field. Variants without a label, or whose label is shared with
+/// another variant, fall back to `{Context}Variant{position}`. A variant's
+/// `title` can't serve as a label: inline objects reach the IR as bare field
+/// lists, without the schema's title.
+fn variant_contexts(context_name: &str, variants: &[IrType]) -> Vec<String> {
+    let labels: Vec<Option<String>> = variants.iter().map(variant_label).collect();
+    labels
+        .iter()
+        .enumerate()
+        .map(|(i, label)| match label {
+            Some(l) if labels.iter().filter(|o| o.as_ref() == Some(l)).count() == 1 => {
+                format!("{}{}Variant", context_name, l.to_pascal_case())
+            }
+            _ => format!("{}Variant{}", context_name, i + 1),
+        })
+        .collect()
+}
+
+fn variant_label(variant: &IrType) -> Option<String> {
+    let IrType::Object(fields) = variant else {
+        return None;
+    };
+    fields
+        .iter()
+        .find_map(|(_, ty, _)| match ty {
+            IrType::StringLiteral(value) => Some(value.clone()),
+            _ => None,
+        })
+        .or_else(|| {
+            fields
+                .iter()
+                .find(|(_, _, required)| *required)
+                .map(|(name, _, _)| name.clone())
+        })
+        .filter(|label| !label.to_pascal_case().is_empty())
+}
+
 /// Generate a unique PascalCase name, appending numeric suffixes if needed.
 fn unique_name(base: &str, used_names: &mut HashSet<String>) -> String {
     let pascal = base.to_pascal_case();
@@ -200,11 +261,20 @@ mod tests {
                     description: None,
                     read_only: false,
                     write_only: false,
+                    deprecated: false,
+                    default: None,
+                    number_hints: Default::default(),
                 }],
                 additional_properties: None,
+                unsupported_keywords: vec![],
+                example: None,
             })],
             operations: vec![],
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
             modules: vec![],
+            raw_spec: None,
         }
     }
 
@@ -245,9 +315,11 @@ mod tests {
             operations: vec![IrOperation {
                 name: normalize_name("getPet"),
                 method: HttpMethod::Get,
-                path: "/pet".to_string(),
+                raw_path: "/pet".to_string(),
+                normalized_path: "/pet".to_string(),
                 summary: None,
                 description: None,
+                external_docs: None,
                 tags: vec![],
                 parameters: vec![],
                 request_body: None,
@@ -257,10 +329,26 @@ mod tests {
                         ("name".to_string(), IrType::String, true),
                     ]),
                     description: None,
+                    content_type: None,
+                    example: None,
+                    examples: Default::default(),
+                    empty_statuses: vec![],
                 }),
+                errors: vec![],
                 deprecated: false,
+                is_webhook: false,
+                source_location: None,
+                security: None,
+                cookie_auth: vec![],
+                bearer_auth: None,
+                links: vec![],
+                extensions: Default::default(),
             }],
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
             modules: vec![],
+            raw_spec: None,
         };
 
         promote_inline_objects(&mut ir);
@@ -299,11 +387,20 @@ mod tests {
                     description: None,
                     read_only: false,
                     write_only: false,
+                    deprecated: false,
+                    default: None,
+                    number_hints: Default::default(),
                 }],
                 additional_properties: None,
+                unsupported_keywords: vec![],
+                example: None,
             })],
             operations: vec![],
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
             modules: vec![],
+            raw_spec: None,
         };
 
         promote_inline_objects(&mut ir);
@@ -341,11 +438,20 @@ mod tests {
                     description: None,
                     read_only: false,
                     write_only: false,
+                    deprecated: false,
+                    default: None,
+                    number_hints: Default::default(),
                 }],
                 additional_properties: None,
+                unsupported_keywords: vec![],
+                example: None,
             })],
             operations: vec![],
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
             modules: vec![],
+            raw_spec: None,
         };
 
         promote_inline_objects(&mut ir);
@@ -375,6 +481,8 @@ mod tests {
                     description: None,
                     fields: vec![],
                     additional_properties: None,
+                    unsupported_keywords: vec![],
+                    example: None,
                 }),
                 // Pet schema with inline owner field that would normally be "PetOwner"
                 IrSchema::Object(IrObjectSchema {
@@ -392,12 +500,21 @@ mod tests {
                         description: None,
                         read_only: false,
                         write_only: false,
+                        deprecated: false,
+                        default: None,
+                        number_hints: Default::default(),
                     }],
                     additional_properties: None,
+                    unsupported_keywords: vec![],
+                    example: None,
                 }),
             ],
             operations: vec![],
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
             modules: vec![],
+            raw_spec: None,
         };
 
         promote_inline_objects(&mut ir);
@@ -423,9 +540,11 @@ mod tests {
             operations: vec![IrOperation {
                 name: normalize_name("createPet"),
                 method: HttpMethod::Post,
-                path: "/pet".to_string(),
+                raw_path: "/pet".to_string(),
+                normalized_path: "/pet".to_string(),
                 summary: None,
                 description: None,
+                external_docs: None,
                 tags: vec![],
                 parameters: vec![],
                 request_body: Some(IrRequestBody {
@@ -434,11 +553,25 @@ mod tests {
                     content_type: "application/json".to_string(),
                     description: None,
                     encoding: None,
+                    example: None,
+                    examples: Default::default(),
                 }),
                 return_type: IrReturnType::Void,
+                errors: vec![],
                 deprecated: false,
+                is_webhook: false,
+                source_location: None,
+                security: None,
+                cookie_auth: vec![],
+                bearer_auth: None,
+                links: vec![],
+                extensions: Default::default(),
             }],
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
             modules: vec![],
+            raw_spec: None,
         };
 
         promote_inline_objects(&mut ir);
@@ -451,4 +584,127 @@ mod tests {
         }
         assert_eq!(ir.schemas.len(), 1);
     }
+
+    fn spec_with_union_field(variants: Vec<IrType>) -> IrSpec {
+        IrSpec {
+            info: IrInfo {
+                title: "Test".to_string(),
+                description: None,
+                version: "1.0".to_string(),
+            },
+            servers: vec![],
+            schemas: vec![IrSchema::Object(IrObjectSchema {
+                name: normalize_name("Message"),
+                description: None,
+                fields: vec![IrField {
+                    name: normalize_name("content"),
+                    original_name: "content".to_string(),
+                    field_type: IrType::Union(variants),
+                    required: true,
+                    description: None,
+                    read_only: false,
+                    write_only: false,
+                    deprecated: false,
+                    default: None,
+                    number_hints: Default::default(),
+                }],
+                additional_properties: None,
+                unsupported_keywords: vec![],
+                example: None,
+            })],
+            operations: vec![],
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
+            modules: vec![],
+            raw_spec: None,
+        }
+    }
+
+    fn tagged(tag: &str) -> IrType {
+        IrType::Object(vec![
+            (
+                "type".to_string(),
+                IrType::StringLiteral(tag.to_string()),
+                true,
+            ),
+            ("value".to_string(), IrType::String, true),
+        ])
+    }
+
+    fn union_refs(ir: &IrSpec) -> Vec<String> {
+        let message = match &ir.schemas[0] {
+            IrSchema::Object(o) => o,
+            _ => panic!("expected object"),
+        };
+        match &message.fields[0].field_type {
+            IrType::Union(variants) => variants
+                .iter()
+                .map(|v| match v {
+                    IrType::Ref(n) => n.clone(),
+                    other => panic!("expected ref, got {other:?}"),
+                })
+                .collect(),
+            _ => panic!("expected union"),
+        }
+    }
+
+    #[test]
+    fn names_union_variants_from_literal_field() {
+        let mut ir = spec_with_union_field(vec![tagged("text"), tagged("image")]);
+        promote_inline_objects(&mut ir);
+        assert_eq!(
+            union_refs(&ir),
+            vec!["MessageContentTextVariant", "MessageContentImageVariant"]
+        );
+    }
+
+    #[test]
+    fn names_union_variants_from_first_required_field() {
+        let mut ir = spec_with_union_field(vec![
+            IrType::Object(vec![("url".to_string(), IrType::String, true)]),
+            IrType::Object(vec![
+                ("note".to_string(), IrType::String, false),
+                ("data".to_string(), IrType::String, true),
+            ]),
+        ]);
+        promote_inline_objects(&mut ir);
+        assert_eq!(
+            union_refs(&ir),
+            vec!["MessageContentUrlVariant", "MessageContentDataVariant"]
+        );
+    }
+
+    #[test]
+    fn falls_back_to_positional_variant_names() {
+        // Same label twice, and no label at all.
+        let mut ir = spec_with_union_field(vec![
+            IrType::Object(vec![("id".to_string(), IrType::String, true)]),
+            IrType::Object(vec![("id".to_string(), IrType::Integer, true)]),
+            IrType::Object(vec![("note".to_string(), IrType::String, false)]),
+        ]);
+        promote_inline_objects(&mut ir);
+        assert_eq!(
+            union_refs(&ir),
+            vec![
+                "MessageContentVariant1",
+                "MessageContentVariant2",
+                "MessageContentVariant3"
+            ]
+        );
+    }
+
+    #[test]
+    fn inserting_a_variant_keeps_existing_names() {
+        let mut before = spec_with_union_field(vec![tagged("text"), tagged("image")]);
+        let mut after =
+            spec_with_union_field(vec![tagged("tool_use"), tagged("text"), tagged("image")]);
+        promote_inline_objects(&mut before);
+        promote_inline_objects(&mut after);
+
+        let before = union_refs(&before);
+        let after = union_refs(&after);
+        assert_eq!(after[0], "MessageContentToolUseVariant");
+        assert_eq!(&after[1..], before.as_slice());
+    }
 }
@@ -2,10 +2,12 @@ use indexmap::IndexMap;
 
 use crate::error::TransformError;
 use crate::ir::{
-    IrAliasSchema, IrDiscriminator, IrEnumSchema, IrField, IrObjectSchema, IrSchema, IrType,
-    IrUnionSchema,
+    IrAliasSchema, IrDiscriminator, IrEnumSchema, IrField, IrMapKey, IrNumberHints, IrObjectSchema,
+    IrSchema, IrType, IrUnionSchema, UnionKind,
+};
+use crate::parse::schema::{
+    AdditionalProperties, ExclusiveBound, Schema, SchemaOrRef, SchemaType, TypeSet,
 };
-use crate::parse::schema::{AdditionalProperties, Schema, SchemaOrRef, SchemaType, TypeSet};
 
 use super::name_normalizer::normalize_name;
 
@@ -20,16 +22,43 @@ pub fn schema_or_ref_to_ir_type(schema_or_ref: &SchemaOrRef) -> IrType {
     }
 }
 
+/// Normalize the variants of a union that admits `null`: nested nullable
+/// unions are flattened and a single `Null` is moved to the end, so
+/// `anyOf: [{type: "null"}, {$ref: Pet}]` reads as `Pet | null`.
+fn coalesce_nullable(variants: Vec<IrType>) -> Vec<IrType> {
+    if !variants.iter().any(IrType::is_nullable) {
+        return variants;
+    }
+    let mut out: Vec<IrType> = Vec::with_capacity(variants.len());
+    for variant in variants {
+        let variant = match variant {
+            IrType::Null => continue,
+            IrType::Union(inner) if inner.contains(&IrType::Null) => {
+                out.extend(inner.into_iter().filter(|t| *t != IrType::Null));
+                continue;
+            }
+            other => other,
+        };
+        out.push(variant);
+    }
+    out.push(IrType::Null);
+    out
+}
+
 /// Convert a parsed `Schema` to an `IrType`.
 pub fn schema_to_ir_type(schema: &Schema) -> IrType {
+    if let Some(branch) = conditional_branch(schema) {
+        return schema_or_ref_to_ir_type(branch);
+    }
+
     // Handle composition first
     if !schema.one_of.is_empty() {
         let variants: Vec<IrType> = schema.one_of.iter().map(schema_or_ref_to_ir_type).collect();
-        return IrType::Union(variants);
+        return IrType::Union(coalesce_nullable(variants));
     }
     if !schema.any_of.is_empty() {
         let variants: Vec<IrType> = schema.any_of.iter().map(schema_or_ref_to_ir_type).collect();
-        return IrType::Union(variants);
+        return IrType::Union(coalesce_nullable(variants));
     }
     if !schema.all_of.is_empty() {
         if schema.all_of.len() == 1 {
@@ -65,11 +94,7 @@ pub fn schema_to_ir_type(schema: &Schema) -> IrType {
 
     // Handle enum
     if !schema.enum_values.is_empty() {
-        let string_variants: Vec<String> = schema
-            .enum_values
-            .iter()
-            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-            .collect();
+        let string_variants = string_variants(schema);
         if string_variants.len() == 1 {
             return IrType::StringLiteral(string_variants.into_iter().next().unwrap());
         }
@@ -164,9 +189,11 @@ fn resolve_object_type(schema: &Schema) -> IrType {
     if schema.properties.is_empty() {
         match &schema.additional_properties {
             Some(AdditionalProperties::Schema(s)) => {
-                IrType::Map(Box::new(schema_or_ref_to_ir_type(s)))
+                IrType::Map(Box::new(schema_or_ref_to_ir_type(s)), map_key(schema))
+            }
+            Some(AdditionalProperties::Bool(true)) => {
+                IrType::Map(Box::new(IrType::Any), map_key(schema))
             }
-            Some(AdditionalProperties::Bool(true)) => IrType::Map(Box::new(IrType::Any)),
             Some(AdditionalProperties::Bool(false)) | None => IrType::Any,
         }
     } else {
@@ -182,6 +209,26 @@ fn resolve_object_type(schema: &Schema) -> IrType {
     }
 }
 
+/// Key constraint of a map schema from its `propertyNames`, if it narrows the
+/// keys beyond plain strings.
+fn map_key(schema: &Schema) -> Option<IrMapKey> {
+    let SchemaOrRef::Schema(names) = schema.property_names.as_deref()? else {
+        return None;
+    };
+    let integer_type = matches!(
+        names.schema_type,
+        Some(TypeSet::Single(SchemaType::Integer))
+    );
+    let integer_format = matches!(names.format.as_deref(), Some("int32" | "int64"));
+    if integer_type || integer_format {
+        return Some(IrMapKey::Integer);
+    }
+    match names.pattern.as_deref()? {
+        "^[0-9]+$" | "^\\d+$" | "^-?[0-9]+$" | "^-?\\d+$" => Some(IrMapKey::Integer),
+        pattern => Some(IrMapKey::Pattern(pattern.to_string())),
+    }
+}
+
 /// Convert a named component schema to an `IrSchema`.
 pub fn schema_or_ref_to_ir_schema(
     name: &str,
@@ -194,38 +241,89 @@ pub fn schema_or_ref_to_ir_schema(
                 name: normalize_name(name),
                 description: None,
                 target: IrType::Ref(normalize_name(target).pascal_case),
+                unsupported_keywords: vec![],
+                example: None,
             }))
         }
         SchemaOrRef::Schema(schema) => schema_to_ir_schema(name, schema),
     }
 }
 
-/// Convert a named `Schema` to an `IrSchema`.
+/// Convert a named `Schema` to an `IrSchema`, recording any keywords it can't
+/// represent in `unsupported_keywords` and keeping its `example`.
 pub fn schema_to_ir_schema(name: &str, schema: &Schema) -> Result<IrSchema, TransformError> {
+    let mut ir_schema = match conditional_branch(schema) {
+        Some(branch) => schema_or_ref_to_ir_schema(name, branch)?,
+        None => build_ir_schema(name, schema)?,
+    };
+    let keywords = schema
+        .unsupported_keywords()
+        .into_iter()
+        .map(String::from)
+        .collect();
+    let example = schema.example.clone();
+    match &mut ir_schema {
+        IrSchema::Object(o) => (o.unsupported_keywords, o.example) = (keywords, example),
+        IrSchema::Enum(e) => (e.unsupported_keywords, e.example) = (keywords, example),
+        IrSchema::Alias(a) => (a.unsupported_keywords, a.example) = (keywords, example),
+        IrSchema::Union(u) => (u.unsupported_keywords, u.example) = (keywords, example),
+    }
+    Ok(ir_schema)
+}
+
+/// For a schema that is only a conditional (`if`/`then`/`else` with no type or
+/// properties of its own), the `then` branch is the closest static type.
+/// `not` never narrows the base type and is ignored.
+fn conditional_branch(schema: &Schema) -> Option<&SchemaOrRef> {
+    let has_own_shape = schema.schema_type.is_some()
+        || !schema.properties.is_empty()
+        || schema.items.is_some()
+        || !schema.all_of.is_empty()
+        || !schema.one_of.is_empty()
+        || !schema.any_of.is_empty()
+        || !schema.enum_values.is_empty()
+        || schema.const_value.is_some();
+    if has_own_shape {
+        return None;
+    }
+    schema.then_schema.as_deref()
+}
+
+/// The string values of `schema.enum`, without repeats: the first occurrence
+/// of each is kept, so `["a", "b", "a"]` gives `a | b`.
+fn string_variants(schema: &Schema) -> Vec<String> {
+    let mut variants: Vec<String> = Vec::new();
+    for value in schema.enum_values.iter().filter_map(|v| v.as_str()) {
+        if !variants.iter().any(|v| v == value) {
+            variants.push(value.to_string());
+        }
+    }
+    variants
+}
+
+fn build_ir_schema(name: &str, schema: &Schema) -> Result<IrSchema, TransformError> {
     let normalized = normalize_name(name);
 
     // Check for enum
     if !schema.enum_values.is_empty() {
-        let variants: Vec<String> = schema
-            .enum_values
-            .iter()
-            .filter_map(|v| v.as_str().map(|s| s.to_string()))
-            .collect();
         return Ok(IrSchema::Enum(IrEnumSchema {
             name: normalized,
             description: schema.description.clone(),
-            variants,
+            variants: string_variants(schema),
+            unsupported_keywords: vec![],
+            example: None,
         }));
     }
 
     // Check for oneOf / anyOf (union)
     if !schema.one_of.is_empty() || !schema.any_of.is_empty() {
-        let variants_src = if !schema.one_of.is_empty() {
-            &schema.one_of
+        let (variants_src, kind) = if !schema.one_of.is_empty() {
+            (&schema.one_of, UnionKind::OneOf)
         } else {
-            &schema.any_of
+            (&schema.any_of, UnionKind::AnyOf)
         };
-        let variants: Vec<IrType> = variants_src.iter().map(schema_or_ref_to_ir_type).collect();
+        let variants =
+            coalesce_nullable(variants_src.iter().map(schema_or_ref_to_ir_type).collect());
         let discriminator = schema.discriminator.as_ref().map(|d| IrDiscriminator {
             property_name: d.property_name.clone(),
             mapping: d
@@ -237,11 +335,25 @@ pub fn schema_to_ir_schema(name: &str, schema: &Schema) -> Result<IrSchema, Tran
                 })
                 .collect(),
         });
+        // `anyOf: [X, {type: "null"}]` is 3.1's spelling of a nullable X, not a
+        // structural union: keep it as an alias so generators emit `X | null`.
+        if discriminator.is_none() && matches!(variants.as_slice(), [_, IrType::Null]) {
+            return Ok(IrSchema::Alias(IrAliasSchema {
+                name: normalized,
+                description: schema.description.clone(),
+                target: IrType::Union(variants),
+                unsupported_keywords: vec![],
+                example: None,
+            }));
+        }
         return Ok(IrSchema::Union(IrUnionSchema {
             name: normalized,
             description: schema.description.clone(),
             variants,
             discriminator,
+            kind,
+            unsupported_keywords: vec![],
+            example: None,
         }));
     }
 
@@ -285,6 +397,8 @@ pub fn schema_to_ir_schema(name: &str, schema: &Schema) -> Result<IrSchema, Tran
                 name: normalized,
                 description: schema.description.clone(),
                 target: IrType::Intersection(parts),
+                unsupported_keywords: vec![],
+                example: None,
             }));
         }
         // No refs — safe to flatten merge as before
@@ -294,6 +408,8 @@ pub fn schema_to_ir_schema(name: &str, schema: &Schema) -> Result<IrSchema, Tran
             description: schema.description.clone(),
             fields: merged,
             additional_properties: None,
+            unsupported_keywords: vec![],
+            example: None,
         }));
     }
 
@@ -315,6 +431,8 @@ pub fn schema_to_ir_schema(name: &str, schema: &Schema) -> Result<IrSchema, Tran
                 description: schema.description.clone(),
                 fields,
                 additional_properties: additional,
+                unsupported_keywords: vec![],
+                example: None,
             }))
         }
         _ => {
@@ -324,6 +442,8 @@ pub fn schema_to_ir_schema(name: &str, schema: &Schema) -> Result<IrSchema, Tran
                 name: normalized,
                 description: schema.description.clone(),
                 target,
+                unsupported_keywords: vec![],
+                example: None,
             }))
         }
     }
@@ -333,13 +453,17 @@ fn build_fields(properties: &IndexMap<String, SchemaOrRef>, required: &[String])
     properties
         .iter()
         .map(|(name, prop)| {
-            let (description, read_only, write_only) = match prop {
+            let (description, read_only, write_only, deprecated, default, number_hints) = match prop
+            {
                 SchemaOrRef::Schema(s) => (
                     s.description.clone(),
                     s.read_only.unwrap_or(false),
                     s.write_only.unwrap_or(false),
+                    s.deprecated.unwrap_or(false),
+                    s.default_value.clone(),
+                    number_hints(s),
                 ),
-                _ => (None, false, false),
+                _ => (None, false, false, false, None, IrNumberHints::default()),
             };
             IrField {
                 name: normalize_name(name),
@@ -349,11 +473,39 @@ fn build_fields(properties: &IndexMap<String, SchemaOrRef>, required: &[String])
                 description,
                 read_only,
                 write_only,
+                deprecated,
+                default,
+                number_hints,
             }
         })
         .collect()
 }
 
+/// The `format` and bounds of a numeric (or nullable numeric) `schema`; none
+/// for any other.
+fn number_hints(schema: &Schema) -> IrNumberHints {
+    let numeric = |t: &SchemaType| matches!(t, SchemaType::Integer | SchemaType::Number);
+    let is_numeric = match &schema.schema_type {
+        Some(TypeSet::Single(t)) => numeric(t),
+        Some(TypeSet::Multiple(types)) => types.iter().any(numeric),
+        None => false,
+    };
+    if !is_numeric {
+        return IrNumberHints::default();
+    }
+    let exclusive = |bound: &Option<ExclusiveBound>| match bound {
+        Some(ExclusiveBound::Number(value)) => Some(*value),
+        _ => None,
+    };
+    IrNumberHints {
+        format: schema.format.clone(),
+        minimum: schema.minimum,
+        maximum: schema.maximum,
+        exclusive_minimum: exclusive(&schema.exclusive_minimum),
+        exclusive_maximum: exclusive(&schema.exclusive_maximum),
+    }
+}
+
 fn merge_all_of(
     all_of: &[SchemaOrRef],
     extra_properties: &IndexMap<String, SchemaOrRef>,
@@ -0,0 +1,128 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+
+use crate::ir::IrType;
+use crate::parse::media_type::MediaType;
+use crate::parse::parameter::ParameterOrRef;
+use crate::parse::request_body::RequestBodyOrRef;
+use crate::parse::response::ResponseOrRef;
+use crate::parse::schema::SchemaOrRef;
+use crate::parse::spec::OpenApiSpec;
+
+use super::name_normalizer::normalize_name;
+use super::schema_resolver::schema_to_ir_type;
+
+/// Move the inline object schemas of shared `components.responses`,
+/// `components.requestBodies` and `components.parameters` into
+/// `components.schemas`, named after the component, and point the component
+/// at them with a `$ref`.
+///
+/// Resolving refs copies a shared response into every operation that uses it;
+/// without this each copy's inline object would be promoted to its own
+/// `{Operation}Response` schema. Run before ref resolution, so every copy
+/// carries the `$ref` instead. A name another schema already has gets the
+/// component kind appended (`PetResponse`), then a number.
+///
+/// Returns whether anything was hoisted.
+pub fn hoist_component_schemas(spec: &mut OpenApiSpec) -> bool {
+    let Some(components) = &mut spec.components else {
+        return false;
+    };
+
+    let mut hoister = Hoister {
+        used: components
+            .schemas
+            .keys()
+            .map(|name| normalize_name(name).pascal_case)
+            .collect(),
+        hoisted: Vec::new(),
+    };
+    for (name, response) in &mut components.responses {
+        if let ResponseOrRef::Response(response) = response {
+            hoister.hoist(name, "Response", content_schemas(&mut response.content));
+        }
+    }
+    for (name, body) in &mut components.request_bodies {
+        if let RequestBodyOrRef::RequestBody(body) = body {
+            hoister.hoist(name, "Body", content_schemas(&mut body.content));
+        }
+    }
+    for (name, parameter) in &mut components.parameters {
+        if let ParameterOrRef::Parameter(parameter) = parameter {
+            let schemas = parameter
+                .schema
+                .iter_mut()
+                .chain(content_schemas(&mut parameter.content));
+            hoister.hoist(name, "Param", schemas);
+        }
+    }
+
+    let changed = !hoister.hoisted.is_empty();
+    components.schemas.extend(hoister.hoisted);
+    changed
+}
+
+/// The schema of every media type in `content`.
+fn content_schemas(
+    content: &mut IndexMap<String, MediaType>,
+) -> impl Iterator<Item = &mut SchemaOrRef> {
+    content.values_mut().filter_map(|mt| mt.schema.as_mut())
+}
+
+struct Hoister {
+    /// PascalCase names already taken by component schemas.
+    used: HashSet<String>,
+    /// New component schemas, in the order they were hoisted.
+    hoisted: Vec<(String, SchemaOrRef)>,
+}
+
+impl Hoister {
+    /// Hoist each of one component's schemas that is an inline object with
+    /// properties. Identical schemas (the same object as JSON and XML) share
+    /// one name.
+    fn hoist<'a>(
+        &mut self,
+        component: &str,
+        kind: &str,
+        schemas: impl Iterator<Item = &'a mut SchemaOrRef>,
+    ) {
+        let mut names: Vec<(SchemaOrRef, String)> = Vec::new();
+        for schema in schemas {
+            let SchemaOrRef::Schema(inline) = schema else {
+                continue;
+            };
+            if !matches!(schema_to_ir_type(inline), IrType::Object(fields) if !fields.is_empty()) {
+                continue;
+            }
+
+            let name = match names.iter().find(|(hoisted, _)| hoisted == schema) {
+                Some((_, name)) => name.clone(),
+                None => {
+                    let name = self.unique_name(component, kind);
+                    self.hoisted.push((name.clone(), schema.clone()));
+                    names.push((schema.clone(), name.clone()));
+                    name
+                }
+            };
+            *schema = SchemaOrRef::Ref {
+                ref_path: format!("#/components/schemas/{name}"),
+            };
+        }
+    }
+
+    /// `component` in PascalCase, else with `kind` appended, else numbered.
+    fn unique_name(&mut self, component: &str, kind: &str) -> String {
+        let name = normalize_name(component).pascal_case;
+        let with_kind = if name.ends_with(kind) {
+            name.clone()
+        } else {
+            format!("{name}{kind}")
+        };
+        [name, with_kind.clone()]
+            .into_iter()
+            .chain((2..).map(|i| format!("{with_kind}{i}")))
+            .find(|candidate| self.used.insert(candidate.clone()))
+            .expect("the numbered names never run out")
+    }
+}
@@ -1,50 +1,337 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use indexmap::IndexMap;
 
-use crate::config::NamingStrategy;
+use crate::config::{
+    CollisionStrategy, FallbackNaming, IrPass, MissingPathParams, NamingStrategy, Streaming,
+    TrailingSlash,
+};
+use crate::docs::truncate_doc;
 use crate::error::TransformError;
+use crate::hash::{sha256, to_hex};
 use crate::ir::*;
 use crate::parse::operation::{Operation, PathItem};
 use crate::parse::parameter::{ParameterLocation, ParameterOrRef};
 use crate::parse::ref_resolve::RefResolver;
 use crate::parse::request_body::RequestBodyOrRef;
-use crate::parse::spec::OpenApiSpec;
+use crate::parse::schema::SchemaOrRef;
+use crate::parse::security::{ApiKeyLocation, SecurityRequirement, SecuritySchemeType};
+use crate::parse::spec::{OpenApiSpec, Tag};
 
-use super::name_normalizer::{normalize_name, route_to_name};
+use super::links::resolve_links;
+use super::name_collisions::{apply_schema_aliases, resolve_schema_name_collisions};
+use super::name_normalizer::{
+    glob_matches, normalize_name, normalize_path, resolve_alias, route_to_name,
+};
+use super::passes::{PostPass, run_passes};
 use super::promote_inline::promote_inline_objects;
 use super::schema_resolver::{schema_or_ref_to_ir_schema, schema_or_ref_to_ir_type};
-use super::sse_detector::detect_return_type;
+use super::shared_components::hoist_component_schemas;
+use super::sse_detector::{detect_error_responses, detect_return_type, media_examples};
+use super::validate::resolve_broken_refs;
 
-/// Options controlling how the transform phase resolves operation names.
+/// Options for [`transform_with_options`]: how operations and schemas are
+/// named, and which optional passes run over the result.
+///
+/// [`TransformOptions::default`] is what [`transform`] uses. Build other
+/// options with the `with_*` setters rather than a struct literal, so code
+/// keeps compiling as options are added:
+///
+/// ```
+/// use oag_core::config::{CollisionStrategy, IrPass};
+/// use oag_core::transform::TransformOptions;
+///
+/// let options = TransformOptions::new()
+///     .with_alias("listPets", "pets")
+///     .with_on_collision(CollisionStrategy::Suffix)
+///     .with_pass(IrPass::PruneUnused);
+/// assert_eq!(options.aliases["listPets"], "pets");
+/// ```
 #[derive(Debug, Clone, Default)]
 pub struct TransformOptions {
+    /// Whether operations are named from their `operationId` or their route.
     pub naming_strategy: NamingStrategy,
+    /// Operation renames, from the derived name to the one generated. Keys
+    /// may use `*` wildcards; an exact key wins over a pattern.
     pub aliases: IndexMap<String, String>,
+    /// Component schema renames, applied before anything references the names.
+    pub schema_aliases: IndexMap<String, String>,
+    /// What happens when two component schemas normalize to the same name.
+    pub on_collision: CollisionStrategy,
+    /// How clashing route-derived operation names are told apart.
+    pub fallback_naming: FallbackNaming,
+    /// Fail on `$ref`s to schemas that don't exist instead of typing them as `Any`.
+    pub strict: bool,
+    /// Truncate descriptions longer than this many characters.
+    pub doc_max_length: Option<usize>,
+    /// Module for operations without tags; `default` when unset.
+    pub untagged_module: Option<String>,
+    /// Custom modules, each listing the operations it takes: operation names,
+    /// or paths when starting with `/`, either may use `*` wildcards. An
+    /// operation joins the first module matching it instead of its tag modules.
+    pub modules: IndexMap<String, Vec<String>>,
+    /// How paths that differ only by a trailing slash are treated.
+    pub trailing_slash: TrailingSlash,
+    /// What happens to path template placeholders no path parameter declares.
+    pub missing_path_params: MissingPathParams,
+    /// Named media type examples to use for bodies, most preferred first.
+    pub example_preference: Vec<String>,
+    /// Operation extensions copied into [`IrOperation::extensions`].
+    pub exposed_extensions: Vec<String>,
+    /// Notation for `deepObject` query parameters without `x-oag-query-object`.
+    pub query_object: QueryObjectNotation,
+    /// Built-in passes run, in order, after the standard phases.
+    pub passes: Vec<IrPass>,
+    /// Custom passes run, in order, after `passes`.
+    pub post_passes: Vec<PostPass>,
+    /// Overrides of SSE detection, by operation name (as generated, or as in
+    /// the spec). Operations not listed are [`Streaming::Auto`].
+    pub streaming: IndexMap<String, Streaming>,
+}
+
+impl TransformOptions {
+    /// The default options, to chain `with_*` setters onto.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_naming_strategy(mut self, strategy: NamingStrategy) -> Self {
+        self.naming_strategy = strategy;
+        self
+    }
+
+    /// Add an operation rename; see [`TransformOptions::aliases`].
+    pub fn with_alias(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.aliases.insert(from.into(), to.into());
+        self
+    }
+
+    /// Add a component schema rename; see [`TransformOptions::schema_aliases`].
+    pub fn with_schema_alias(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.schema_aliases.insert(from.into(), to.into());
+        self
+    }
+
+    pub fn with_on_collision(mut self, strategy: CollisionStrategy) -> Self {
+        self.on_collision = strategy;
+        self
+    }
+
+    pub fn with_fallback_naming(mut self, fallback: FallbackNaming) -> Self {
+        self.fallback_naming = fallback;
+        self
+    }
+
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    pub fn with_doc_max_length(mut self, max_length: usize) -> Self {
+        self.doc_max_length = Some(max_length);
+        self
+    }
+
+    pub fn with_untagged_module(mut self, module: impl Into<String>) -> Self {
+        self.untagged_module = Some(module.into());
+        self
+    }
+
+    /// Add operations to a custom module; see [`TransformOptions::modules`].
+    pub fn with_module<I, S>(mut self, module: impl Into<String>, patterns: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.modules
+            .entry(module.into())
+            .or_default()
+            .extend(patterns.into_iter().map(Into::into));
+        self
+    }
+
+    pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    pub fn with_missing_path_params(mut self, missing: MissingPathParams) -> Self {
+        self.missing_path_params = missing;
+        self
+    }
+
+    /// Add a named media type example, after those already preferred.
+    pub fn with_example_preference(mut self, name: impl Into<String>) -> Self {
+        self.example_preference.push(name.into());
+        self
+    }
+
+    /// Add an operation extension to copy into [`IrOperation::extensions`].
+    pub fn with_exposed_extension(mut self, name: impl Into<String>) -> Self {
+        self.exposed_extensions.push(name.into());
+        self
+    }
+
+    pub fn with_query_object(mut self, notation: QueryObjectNotation) -> Self {
+        self.query_object = notation;
+        self
+    }
+
+    /// Add a built-in pass, run after those already added.
+    pub fn with_pass(mut self, pass: IrPass) -> Self {
+        self.passes.push(pass);
+        self
+    }
+
+    /// Add a custom pass, run after the built-in ones and those already added.
+    pub fn with_post_pass(mut self, pass: PostPass) -> Self {
+        self.post_passes.push(pass);
+        self
+    }
+
+    /// Override SSE detection for an operation; see [`TransformOptions::streaming`].
+    pub fn with_streaming(mut self, operation: impl Into<String>, streaming: Streaming) -> Self {
+        self.streaming.insert(operation.into(), streaming);
+        self
+    }
+
+    /// The SSE detection override for the operation named `name`.
+    fn streaming_for(&self, name: &NormalizedName) -> Streaming {
+        self.streaming
+            .get(&name.original)
+            .or_else(|| self.streaming.get(&name.camel_case))
+            .copied()
+            .unwrap_or_default()
+    }
 }
 
 /// Transform a parsed OpenAPI spec into the fully resolved IR.
+///
+/// ```
+/// let spec = oag_core::parse::from_yaml(
+///     r#"
+/// openapi: "3.1.0"
+/// info: { title: Pets, version: "1.0" }
+/// paths:
+///   /pets:
+///     get:
+///       operationId: listPets
+///       responses:
+///         "200":
+///           description: The pets
+///           content:
+///             application/json:
+///               schema: { type: array, items: { type: string } }
+/// "#,
+/// )?;
+/// let ir = oag_core::transform::transform(&spec)?;
+/// assert_eq!(ir.operations[0].name.camel_case, "listPets");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
 pub fn transform(spec: &OpenApiSpec) -> Result<IrSpec, TransformError> {
     transform_with_options(spec, &TransformOptions::default())
 }
 
-/// Transform with explicit naming options.
+/// Transform with explicit options.
+///
+/// ```
+/// use oag_core::transform::{TransformOptions, transform_with_options};
+///
+/// let spec = oag_core::parse::from_yaml(
+///     r#"
+/// openapi: "3.1.0"
+/// info: { title: Pets, version: "1.0" }
+/// paths:
+///   /pets:
+///     get:
+///       operationId: listPets
+///       responses:
+///         "204": { description: Listed }
+/// "#,
+/// )?;
+/// let options = TransformOptions::new().with_alias("listPets", "allPets");
+/// let ir = transform_with_options(&spec, &options)?;
+/// assert_eq!(ir.operations[0].name.camel_case, "allPets");
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
 pub fn transform_with_options(
     spec: &OpenApiSpec,
     options: &TransformOptions,
 ) -> Result<IrSpec, TransformError> {
-    // Phase 1: Resolve all $ref pointers
-    let mut resolver = RefResolver::new(spec);
-    let resolved = resolver.resolve_spec(spec)?;
+    transform_owned(spec.clone(), options)
+}
+
+/// Like [`transform_with_options`], but takes the spec by value (or an `Arc`
+/// of it, e.g. another IR's `raw_spec`): it becomes `IrSpec::raw_spec` as is,
+/// so the only copy made is the one refs are resolved into.
+pub fn transform_owned(
+    spec: impl Into<Arc<OpenApiSpec>>,
+    options: &TransformOptions,
+) -> Result<IrSpec, TransformError> {
+    let spec: Arc<OpenApiSpec> = spec.into();
+
+    // Phase 1: Name the inline schemas of shared responses, request bodies and
+    // parameters, then resolve all $ref pointers
+    let mut resolved = OpenApiSpec::clone(&spec);
+    if hoist_component_schemas(&mut resolved) {
+        let hoisted = resolved.clone();
+        RefResolver::new(&hoisted).resolve_spec_in_place(&mut resolved)?;
+    } else {
+        RefResolver::new(&spec).resolve_spec_in_place(&mut resolved)?;
+    }
 
-    // Phase 2: Convert component schemas to IR schemas
+    // Phase 2: Convert component schemas to IR schemas, after applying the
+    // configured renames and making sure no two normalize to the same name
+    let mut diagnostics = apply_schema_aliases(&mut resolved, &options.schema_aliases)?;
+    diagnostics.extend(resolve_schema_name_collisions(
+        &mut resolved,
+        options.on_collision,
+    )?);
+    diagnostics.extend(duplicate_enum_values(&resolved));
     let schemas = resolve_schemas(&resolved)?;
+    for schema in &schemas {
+        let keywords = schema.unsupported_keywords();
+        if !keywords.is_empty() {
+            diagnostics.push(format!(
+                "schema `{}` uses `{}`, which generated types only approximate",
+                schema.name().original,
+                keywords.join("`, `"),
+            ));
+        }
+    }
 
-    // Phase 3: Convert operations
-    let operations = resolve_operations(&resolved, options)?;
+    // Phase 3: Convert operations (paths and webhooks), after checking that no
+    // two paths are the same route, and resolve the links between them
+    diagnostics.extend(check_routes(&resolved, options.trailing_slash)?);
+    let mut aliased = HashSet::new();
+    let mut operations = resolve_operations(&resolved, options, &mut aliased, &mut diagnostics)?;
+    for name in options.streaming.keys() {
+        if !operations
+            .iter()
+            .any(|op| op.name.original == *name || op.name.camel_case == *name)
+        {
+            diagnostics.push(format!("`operations.{name}` matches no operation"));
+        }
+    }
+    diagnostics.extend(resolve_links(
+        &resolved,
+        |path| operation_path(path, options),
+        &mut operations,
+    ));
+    let (webhooks, operations): (Vec<_>, Vec<_>) =
+        operations.into_iter().partition(|op| op.is_webhook);
+    check_alias_collisions(&operations, &aliased)?;
+    check_alias_collisions(&webhooks, &aliased)?;
 
-    // Phase 4: Group operations into modules by tag
-    let modules = group_into_modules(&operations);
+    // Phase 4: Group operations into the configured modules, then by tag
+    let modules = group_into_modules(
+        &operations,
+        &spec.tags,
+        options.untagged_module.as_deref().unwrap_or("default"),
+        &options.modules,
+        &mut diagnostics,
+    );
 
     // Phase 5: Build IR info and servers
     let info = IrInfo {
@@ -58,6 +345,12 @@ pub fn transform_with_options(
         .iter()
         .map(|s| IrServer {
             url: s.url.clone(),
+            default_url: s
+                .variables
+                .iter()
+                .fold(s.url.clone(), |url, (name, variable)| {
+                    url.replace(&format!("{{{name}}}"), &variable.default)
+                }),
             description: s.description.clone(),
         })
         .collect();
@@ -67,15 +360,65 @@ pub fn transform_with_options(
         servers,
         schemas,
         operations,
+        webhooks,
         modules,
+        diagnostics,
+        security: resolved.security.clone().unwrap_or_default(),
+        raw_spec: Some(spec),
     };
 
     // Phase 6: Promote inline objects to named schemas
     promote_inline_objects(&mut ir);
 
+    // Phase 7: Reject or stub out references to schemas that don't exist
+    resolve_broken_refs(&mut ir, options.strict)?;
+
+    if let Some(max_length) = options.doc_max_length {
+        truncate_descriptions(&mut ir, max_length);
+    }
+
+    // Phase 8: Optional and custom passes
+    run_passes(&mut ir, &options.passes, &options.post_passes);
+
     Ok(ir)
 }
 
+/// Shorten every schema, field, operation, parameter and request body
+/// description to `max_length`.
+fn truncate_descriptions(ir: &mut IrSpec, max_length: usize) {
+    let truncate = |description: &mut Option<String>| {
+        if let Some(short) = description
+            .as_deref()
+            .and_then(|d| truncate_doc(d, max_length))
+        {
+            *description = Some(short);
+        }
+    };
+
+    for schema in &mut ir.schemas {
+        match schema {
+            IrSchema::Object(obj) => {
+                truncate(&mut obj.description);
+                for field in &mut obj.fields {
+                    truncate(&mut field.description);
+                }
+            }
+            IrSchema::Enum(e) => truncate(&mut e.description),
+            IrSchema::Alias(a) => truncate(&mut a.description),
+            IrSchema::Union(u) => truncate(&mut u.description),
+        }
+    }
+    for op in ir.operations.iter_mut().chain(&mut ir.webhooks) {
+        truncate(&mut op.description);
+        for param in &mut op.parameters {
+            truncate(&mut param.description);
+        }
+        if let Some(body) = &mut op.request_body {
+            truncate(&mut body.description);
+        }
+    }
+}
+
 fn resolve_schemas(spec: &OpenApiSpec) -> Result<Vec<IrSchema>, TransformError> {
     let mut schemas = Vec::new();
     if let Some(ref components) = spec.components {
@@ -87,31 +430,259 @@ fn resolve_schemas(spec: &OpenApiSpec) -> Result<Vec<IrSchema>, TransformError>
     Ok(schemas)
 }
 
+/// Warn about component schemas that list an `enum` value more than once; all
+/// but the first occurrence are dropped.
+fn duplicate_enum_values(spec: &OpenApiSpec) -> Vec<String> {
+    let Some(components) = &spec.components else {
+        return Vec::new();
+    };
+    components
+        .schemas
+        .iter()
+        .filter_map(|(name, schema_or_ref)| match schema_or_ref {
+            SchemaOrRef::Schema(schema) => Some((name, schema.duplicate_enum_values())),
+            SchemaOrRef::Ref { .. } => None,
+        })
+        .filter(|(_, duplicates)| !duplicates.is_empty())
+        .map(|(name, duplicates)| {
+            let values: Vec<String> = duplicates.iter().map(|v| format!("{v:?}")).collect();
+            format!(
+                "schema `{name}` lists enum values {} more than once; the repeats are dropped",
+                values.join(", "),
+            )
+        })
+        .collect()
+}
+
+/// `path` without its trailing slash, unless it's the root `/`.
+fn strip_trailing_slash(path: &str) -> &str {
+    match path.strip_suffix('/') {
+        Some(stripped) if !stripped.is_empty() => stripped,
+        _ => path,
+    }
+}
+
+/// Warn about path items that declare no operations, and unless
+/// `trailing_slash` is `keep`, fail if two paths that are the same route (up to
+/// a trailing slash and path parameter names) declare the same method.
+fn check_routes(
+    spec: &OpenApiSpec,
+    trailing_slash: TrailingSlash,
+) -> Result<Vec<String>, TransformError> {
+    let mut diagnostics = Vec::new();
+    let mut routes: HashMap<(String, &str), &str> = HashMap::new();
+    for (path, item) in &spec.paths {
+        if item.operations().next().is_none() {
+            diagnostics.push(format!(
+                "path `{path}` declares no operations and generates nothing"
+            ));
+        }
+        if trailing_slash == TrailingSlash::Keep {
+            continue;
+        }
+
+        let route = route_template(strip_trailing_slash(path));
+        for (method, _) in item.operations() {
+            if let Some(first) = routes.insert((route.clone(), method), path) {
+                return Err(TransformError::DuplicateRoute {
+                    method: method.to_string(),
+                    first: first.to_string(),
+                    second: path.clone(),
+                });
+            }
+        }
+    }
+    Ok(diagnostics)
+}
+
+/// `path` with every parameter name erased: `/pets/{petId}` → `/pets/{}`.
+fn route_template(path: &str) -> String {
+    let mut template = String::with_capacity(path.len());
+    let mut in_param = false;
+    for c in path.chars() {
+        match c {
+            '{' => {
+                in_param = true;
+                template.push(c);
+            }
+            '}' => {
+                in_param = false;
+                template.push(c);
+            }
+            _ if in_param => {}
+            _ => template.push(c),
+        }
+    }
+    template
+}
+
+/// Fail if an alias gives an operation the same name as another operation.
+/// Duplicate names that come straight from the spec are left alone.
+fn check_alias_collisions(
+    operations: &[IrOperation],
+    aliased: &HashSet<String>,
+) -> Result<(), TransformError> {
+    if aliased.is_empty() {
+        return Ok(());
+    }
+    let mut seen: HashMap<&str, &IrOperation> = HashMap::new();
+    for op in operations {
+        let name = op.name.camel_case.as_str();
+        if let Some(first) = seen.insert(name, op)
+            && aliased.contains(name)
+        {
+            let label = |op: &IrOperation| format!("{} {}", op.method.as_str(), op.raw_path);
+            return Err(TransformError::AliasCollision {
+                name: name.to_string(),
+                first: label(first),
+                second: label(op),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Convert every path and webhook operation. The camelCase names of operations
+/// renamed by an alias are added to `aliased`, and path parameter warnings to
+/// `diagnostics`.
 fn resolve_operations(
     spec: &OpenApiSpec,
     options: &TransformOptions,
+    aliased: &mut HashSet<String>,
+    diagnostics: &mut Vec<String>,
 ) -> Result<Vec<IrOperation>, TransformError> {
     let mut operations = Vec::new();
+    let auth = AuthSchemes::new(spec);
+    let fallbacks = fallback_names(spec, options);
 
     for (path, path_item) in &spec.paths {
-        let path_params = resolve_parameters(&path_item.parameters);
-        collect_operations(path, path_item, &path_params, options, &mut operations)?;
+        collect_operations(
+            operation_path(path, options),
+            path_item,
+            false,
+            &auth,
+            options,
+            &fallbacks,
+            &mut operations,
+            aliased,
+            diagnostics,
+        )?;
+    }
+
+    for (name, path_item) in &spec.webhooks {
+        collect_operations(
+            name,
+            path_item,
+            true,
+            &auth,
+            options,
+            &fallbacks,
+            &mut operations,
+            aliased,
+            diagnostics,
+        )?;
     }
 
     Ok(operations)
 }
 
+/// The path an operation is generated with, after `trailing_slash`.
+fn operation_path<'a>(path: &'a str, options: &TransformOptions) -> &'a str {
+    match options.trailing_slash {
+        TrailingSlash::Strip => strip_trailing_slash(path),
+        TrailingSlash::Merge | TrailingSlash::Keep => path,
+    }
+}
+
+/// Route-derived operation names, keyed by (is webhook, path, method), told
+/// apart per `fallback_naming` where two operations would share a name.
+/// Names from an `operationId` are never changed, but count as taken.
+fn fallback_names<'a>(
+    spec: &'a OpenApiSpec,
+    options: &TransformOptions,
+) -> HashMap<(bool, &'a str, &'static str), String> {
+    let routes = spec
+        .paths
+        .iter()
+        .map(|(path, item)| (false, operation_path(path, options), item))
+        .chain(
+            spec.webhooks
+                .iter()
+                .map(|(name, item)| (true, name.as_str(), item)),
+        );
+
+    // Every operation's name before disambiguation, grouped by the name the
+    // generated code would use.
+    let mut groups: IndexMap<String, Vec<(bool, &str, &'static str, bool)>> = IndexMap::new();
+    for (is_webhook, path, item) in routes {
+        for (method, op) in item.operations() {
+            let (name, derived) = match (options.naming_strategy, &op.operation_id) {
+                (NamingStrategy::UseOperationId, Some(id)) => (id.clone(), false),
+                _ => (route_to_name(method, path), true),
+            };
+            groups
+                .entry(normalize_name(&name).camel_case)
+                .or_default()
+                .push((is_webhook, path, method, derived));
+        }
+    }
+
+    let mut names = HashMap::new();
+    for (name, members) in groups {
+        let clashes = members.len() > 1;
+        for (position, (is_webhook, path, method, derived)) in members.into_iter().enumerate() {
+            if !derived {
+                continue;
+            }
+            let name = match options.fallback_naming {
+                _ if !clashes => name.clone(),
+                FallbackNaming::Route if position == 0 => name.clone(),
+                FallbackNaming::Route => format!("{name}{}", position + 1),
+                FallbackNaming::RouteHash => {
+                    let digest = sha256(format!("{method} {path}").as_bytes());
+                    format!("{name}_{}", &to_hex(&digest)[..6])
+                }
+            };
+            names.insert((is_webhook, path, method), name);
+        }
+    }
+    names
+}
+
+#[allow(clippy::too_many_arguments)]
 fn collect_operations(
     path: &str,
     item: &PathItem,
-    path_params: &[IrParameter],
+    is_webhook: bool,
+    auth: &AuthSchemes,
     options: &TransformOptions,
+    fallbacks: &HashMap<(bool, &str, &'static str), String>,
     out: &mut Vec<IrOperation>,
+    aliased: &mut HashSet<String>,
+    diagnostics: &mut Vec<String>,
 ) -> Result<(), TransformError> {
+    let path_params = resolve_parameters(&item.parameters, options);
     macro_rules! add_op {
         ($method:expr, $op:expr) => {
             if let Some(ref op) = $op {
-                let ir_op = build_operation($method, path, op, path_params, options)?;
+                let fallback = fallbacks.get(&(is_webhook, path, $method.as_str()));
+                let mut ir_op =
+                    build_operation($method, path, op, &path_params, fallback, options, aliased)?;
+                ir_op.is_webhook = is_webhook;
+                let streaming = options.streaming_for(&ir_op.name);
+                if streaming != Streaming::Auto {
+                    diagnostics.push(format!(
+                        "operation `{}`: `streaming: {}` overrides SSE detection",
+                        ir_op.name.original,
+                        streaming.as_str(),
+                    ));
+                }
+                if !is_webhook {
+                    check_path_params(&mut ir_op, options.missing_path_params, diagnostics)?;
+                }
+                ir_op.security = op.security.clone();
+                ir_op.cookie_auth = auth.cookies(op);
+                ir_op.bearer_auth = auth.bearer(op);
                 out.push(ir_op);
             }
         };
@@ -134,48 +705,236 @@ fn build_operation(
     path: &str,
     op: &Operation,
     path_params: &[IrParameter],
+    fallback: Option<&String>,
     options: &TransformOptions,
+    aliased: &mut HashSet<String>,
 ) -> Result<IrOperation, TransformError> {
-    // Derive the raw operation name based on naming strategy
-    let raw_name = match options.naming_strategy {
-        NamingStrategy::UseOperationId => {
-            op.operation_id.clone().unwrap_or_else(|| {
-                // Fallback: route-based even in operationId mode when no operationId
-                route_to_name(method.as_str(), path)
-            })
-        }
-        NamingStrategy::UseRouteBased => route_to_name(method.as_str(), path),
+    // The operationId, else the route-derived name from `fallback_names`
+    let raw_name = match (options.naming_strategy, &op.operation_id, fallback) {
+        (NamingStrategy::UseOperationId, Some(id), _) => id.clone(),
+        (_, _, Some(name)) => name.clone(),
+        _ => route_to_name(method.as_str(), path),
     };
 
-    // Apply aliases: if the raw name matches an alias key, use the alias value
-    let name = if let Some(alias) = options.aliases.get(&raw_name) {
-        alias.clone()
-    } else {
-        raw_name.clone()
+    // Apply aliases: an exact key match, else the first matching `*` pattern
+    let name = match resolve_alias(&raw_name, &options.aliases) {
+        Some(alias) => {
+            let name = normalize_name(&alias);
+            aliased.insert(name.camel_case.clone());
+            name
+        }
+        None => normalize_name(&raw_name),
     };
 
     let mut parameters = path_params.to_vec();
-    parameters.extend(resolve_parameters(&op.parameters));
+    parameters.extend(resolve_parameters(&op.parameters, options));
 
-    let request_body = op.request_body.as_ref().and_then(resolve_request_body);
+    let request_body = op
+        .request_body
+        .as_ref()
+        .and_then(|body| resolve_request_body(body, &options.example_preference));
 
-    let return_type = detect_return_type(&name, &op.responses);
+    let return_type = detect_return_type(
+        &name,
+        &op.responses,
+        &options.example_preference,
+        options.streaming_for(&name),
+    );
+    let errors = detect_error_responses(&op.responses);
 
     Ok(IrOperation {
-        name: normalize_name(&name),
+        name,
         method,
-        path: path.to_string(),
+        raw_path: path.to_string(),
+        normalized_path: normalize_path(path),
         summary: op.summary.clone(),
         description: op.description.clone(),
+        external_docs: op.external_docs.as_ref().map(|d| d.url.clone()),
         tags: op.tags.clone(),
         parameters,
         request_body,
         return_type,
+        errors,
         deprecated: op.deprecated.unwrap_or(false),
+        is_webhook: false,
+        source_location: Some(OperationSourceLocation {
+            path: path.to_string(),
+            method: method.as_str().to_string(),
+            line: None,
+            column: None,
+        }),
+        security: None,
+        cookie_auth: Vec::new(),
+        bearer_auth: None,
+        links: Vec::new(),
+        extensions: options
+            .exposed_extensions
+            .iter()
+            .filter_map(|name| Some((name.clone(), op.extensions.get(name)?.clone())))
+            .collect(),
     })
 }
 
-fn resolve_parameters(params: &[ParameterOrRef]) -> Vec<IrParameter> {
+/// Check `op`'s path template against its path parameters. A placeholder no
+/// parameter declares fails the transform, or with `synthesize` is declared as
+/// a required string; a parameter whose name matches a placeholder only once
+/// normalized (`pet_id` for `{petId}`) and one that matches none are warned
+/// about.
+fn check_path_params(
+    op: &mut IrOperation,
+    missing: MissingPathParams,
+    diagnostics: &mut Vec<String>,
+) -> Result<(), TransformError> {
+    let route = format!("{} {}", op.method.as_str(), op.raw_path);
+    let placeholders = path_placeholders(&op.raw_path);
+    let declared: Vec<&IrParameter> = op
+        .parameters
+        .iter()
+        .filter(|p| p.location == IrParameterLocation::Path)
+        .collect();
+
+    let mut synthesized = Vec::new();
+    for placeholder in &placeholders {
+        if declared.iter().any(|p| p.original_name == *placeholder) {
+            continue;
+        }
+        let normalized = normalize_name(placeholder);
+        if let Some(param) = declared
+            .iter()
+            .find(|p| p.name.camel_case == normalized.camel_case)
+        {
+            diagnostics.push(format!(
+                "`{route}` declares path parameter `{}` for `{{{placeholder}}}`; rename it `{placeholder}`",
+                param.original_name
+            ));
+            continue;
+        }
+        match missing {
+            MissingPathParams::Error => {
+                return Err(TransformError::UndeclaredPathParam {
+                    route,
+                    name: placeholder.to_string(),
+                });
+            }
+            MissingPathParams::Synthesize => {
+                diagnostics.push(format!(
+                    "`{route}` declares no path parameter `{placeholder}`; generating it as a required string"
+                ));
+                synthesized.push(IrParameter {
+                    name: normalized,
+                    original_name: placeholder.to_string(),
+                    location: IrParameterLocation::Path,
+                    param_type: IrType::String,
+                    required: true,
+                    description: None,
+                    content_type: None,
+                    pinned_value: None,
+                    allow_reserved: false,
+                    allow_empty_value: false,
+                    example: None,
+                    query_object: None,
+                    query_array: None,
+                });
+            }
+        }
+    }
+
+    for param in &declared {
+        let used = placeholders
+            .iter()
+            .any(|p| normalize_name(p).camel_case == param.name.camel_case);
+        if !used {
+            diagnostics.push(format!(
+                "`{route}` declares path parameter `{}`, which its path doesn't use",
+                param.original_name
+            ));
+        }
+    }
+
+    op.parameters.splice(0..0, synthesized);
+    Ok(())
+}
+
+/// The placeholder names in a path template: `/pets/{petId}` → `["petId"]`.
+fn path_placeholders(path: &str) -> Vec<&str> {
+    path.split('{')
+        .skip(1)
+        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+/// The spec's cookie-located `apiKey` and bearer security schemes and its
+/// default `security`, for finding how each operation authenticates.
+struct AuthSchemes<'a> {
+    /// Scheme name → cookie name.
+    cookies: HashMap<&'a str, &'a str>,
+    /// Scheme name → `bearerFormat`.
+    bearer: HashMap<&'a str, Option<&'a str>>,
+    default: &'a [SecurityRequirement],
+}
+
+impl<'a> AuthSchemes<'a> {
+    fn new(spec: &'a OpenApiSpec) -> Self {
+        let schemes = || spec.components.iter().flat_map(|c| &c.security_schemes);
+        let cookies = schemes()
+            .filter(|(_, scheme)| {
+                scheme.scheme_type == SecuritySchemeType::ApiKey
+                    && scheme.location == Some(ApiKeyLocation::Cookie)
+            })
+            .filter_map(|(name, scheme)| Some((name.as_str(), scheme.name.as_deref()?)))
+            .collect();
+        let bearer = schemes()
+            .filter(|(_, scheme)| {
+                scheme.scheme_type == SecuritySchemeType::Http
+                    && scheme
+                        .scheme
+                        .as_deref()
+                        .is_some_and(|s| s.eq_ignore_ascii_case("bearer"))
+            })
+            .map(|(name, scheme)| (name.as_str(), scheme.bearer_format.as_deref()))
+            .collect();
+        Self {
+            cookies,
+            bearer,
+            default: spec.security.as_deref().unwrap_or_default(),
+        }
+    }
+
+    /// The scheme names of the operation's security requirements. An
+    /// operation's own `security`, even an empty one, replaces the default.
+    fn schemes<'o>(&'o self, op: &'o Operation) -> impl Iterator<Item = &'o str> {
+        let requirements = op.security.as_deref().unwrap_or(self.default);
+        requirements
+            .iter()
+            .flat_map(|r| r.keys())
+            .map(String::as_str)
+    }
+
+    /// Cookie names used by any of the operation's security requirements.
+    fn cookies(&self, op: &Operation) -> Vec<String> {
+        let mut cookies: Vec<String> = Vec::new();
+        for scheme in self.schemes(op) {
+            if let Some(&cookie) = self.cookies.get(scheme)
+                && !cookies.iter().any(|c| c == cookie)
+            {
+                cookies.push(cookie.to_string());
+            }
+        }
+        cookies
+    }
+
+    /// The first bearer scheme any of the operation's security requirements
+    /// use.
+    fn bearer(&self, op: &Operation) -> Option<IrBearerAuth> {
+        self.schemes(op)
+            .find_map(|scheme| self.bearer.get(scheme))
+            .map(|format| IrBearerAuth {
+                format: format.map(str::to_string),
+            })
+    }
+}
+
+fn resolve_parameters(params: &[ParameterOrRef], options: &TransformOptions) -> Vec<IrParameter> {
     params
         .iter()
         .filter_map(|p| match p {
@@ -186,11 +945,25 @@ fn resolve_parameters(params: &[ParameterOrRef]) -> Vec<IrParameter> {
                     ParameterLocation::Header => IrParameterLocation::Header,
                     ParameterLocation::Cookie => IrParameterLocation::Cookie,
                 };
+                // `content` holds a single media type whose schema types the value.
+                let content = param.content.first().filter(|_| param.schema.is_none());
                 let param_type = param
                     .schema
                     .as_ref()
+                    .or_else(|| content.and_then(|(_, mt)| mt.schema.as_ref()))
                     .map(schema_or_ref_to_ir_type)
                     .unwrap_or(IrType::Any);
+                // Free-form query maps: the extension picks the notation,
+                // plain `deepObject` uses the configured one.
+                let query_object = param
+                    .query_object
+                    .or((param.style.as_deref() == Some("deepObject"))
+                        .then_some(options.query_object))
+                    .filter(|_| location == IrParameterLocation::Query);
+                let query_array = matches!(param_type, IrType::Array(_))
+                    .then(|| QueryArrayStyle::from_spec(param.style.as_deref(), param.explode))
+                    .flatten()
+                    .filter(|_| location == IrParameterLocation::Query && content.is_none());
                 Some(IrParameter {
                     name: normalize_name(&param.name),
                     original_name: param.name.clone(),
@@ -198,6 +971,19 @@ fn resolve_parameters(params: &[ParameterOrRef]) -> Vec<IrParameter> {
                     param_type,
                     required: param.required,
                     description: param.description.clone(),
+                    content_type: content.map(|(content_type, _)| content_type.clone()),
+                    pinned_value: None,
+                    allow_reserved: param.allow_reserved.unwrap_or(false),
+                    allow_empty_value: param.allow_empty_value.unwrap_or(false),
+                    example: param.example.clone().or_else(|| {
+                        // An Example Object; `$ref` and `externalValue` ones aren't followed.
+                        param
+                            .examples
+                            .values()
+                            .find_map(|e| e.get("value").cloned())
+                    }),
+                    query_object,
+                    query_array,
                 })
             }
             ParameterOrRef::Ref { .. } => None, // Should already be resolved
@@ -205,20 +991,23 @@ fn resolve_parameters(params: &[ParameterOrRef]) -> Vec<IrParameter> {
         .collect()
 }
 
-fn resolve_request_body(body: &RequestBodyOrRef) -> Option<IrRequestBody> {
+fn resolve_request_body(body: &RequestBodyOrRef, prefer: &[String]) -> Option<IrRequestBody> {
     match body {
         RequestBodyOrRef::RequestBody(rb) => {
-            // Prefer application/json, fall back to first content type
+            // Prefer application/json, then another JSON type (a merge or JSON
+            // patch), falling back to the first content type
             let (content_type, mt) = rb
                 .content
                 .get_key_value("application/json")
+                .or_else(|| rb.content.iter().find(|(ct, _)| is_json_media_type(ct)))
                 .or_else(|| rb.content.first())?;
 
-            let body_type = mt
-                .schema
-                .as_ref()
-                .map(schema_or_ref_to_ir_type)
-                .unwrap_or(IrType::Any);
+            // Plain text is sent as a string whatever its schema says.
+            let body_type = match &mt.schema {
+                _ if is_text_media_type(content_type) => IrType::String,
+                Some(schema) => schema_or_ref_to_ir_type(schema),
+                None => IrType::Any,
+            };
 
             let encoding = if mt.encoding.is_empty() {
                 None
@@ -234,34 +1023,96 @@ fn resolve_request_body(body: &RequestBodyOrRef) -> Option<IrRequestBody> {
                 )
             };
 
+            let (example, examples) = media_examples(mt, prefer);
             Some(IrRequestBody {
                 body_type,
                 required: rb.required,
                 content_type: content_type.clone(),
                 description: rb.description.clone(),
                 encoding,
+                example,
+                examples,
             })
         }
         RequestBodyOrRef::Ref { .. } => None, // Should already be resolved
     }
 }
 
-fn group_into_modules(operations: &[IrOperation]) -> Vec<IrModule> {
+fn group_into_modules(
+    operations: &[IrOperation],
+    tags: &[Tag],
+    untagged_module: &str,
+    custom_modules: &IndexMap<String, Vec<String>>,
+    diagnostics: &mut Vec<String>,
+) -> Vec<IrModule> {
     let mut tag_groups: HashMap<String, Vec<usize>> = HashMap::new();
 
+    // Untagged operations get their own module, suffixed if a real tag or a
+    // custom module normalizes to the same name.
+    let tag_names: HashSet<String> = operations
+        .iter()
+        .flat_map(|op| &op.tags)
+        .chain(custom_modules.keys())
+        .map(|tag| normalize_name(tag).snake_case)
+        .collect();
+    let mut untagged = untagged_module.to_string();
+    let mut suffix = 2;
+    while tag_names.contains(&normalize_name(&untagged).snake_case) {
+        untagged = format!("{untagged_module}_{suffix}");
+        suffix += 1;
+    }
+
+    let mut assigned = HashSet::new();
+    for (module, patterns) in custom_modules {
+        let ops: Vec<usize> = operations
+            .iter()
+            .enumerate()
+            .filter(|(i, op)| {
+                !assigned.contains(i)
+                    && patterns.iter().any(|pattern| {
+                        if pattern.starts_with('/') {
+                            glob_matches(pattern, &op.raw_path)
+                        } else {
+                            glob_matches(pattern, &op.name.original)
+                                || glob_matches(pattern, &op.name.camel_case)
+                        }
+                    })
+            })
+            .map(|(i, _)| i)
+            .collect();
+        if ops.is_empty() {
+            diagnostics.push(format!("module `{module}` matches no operations"));
+            continue;
+        }
+        assigned.extend(ops.iter().copied());
+        tag_groups.insert(module.clone(), ops);
+    }
+
     for (i, op) in operations.iter().enumerate() {
+        if assigned.contains(&i) {
+            continue;
+        }
         if op.tags.is_empty() {
-            tag_groups.entry("default".to_string()).or_default().push(i);
+            tag_groups.entry(untagged.clone()).or_default().push(i);
         } else {
             for tag in &op.tags {
                 tag_groups.entry(tag.clone()).or_default().push(i);
             }
         }
     }
+    // A tag sharing a custom module's name adds its leftovers after the
+    // module's own operations; keep spec order.
+    for ops in tag_groups.values_mut() {
+        ops.sort_unstable();
+    }
 
     let mut modules: Vec<IrModule> = tag_groups
         .into_iter()
         .map(|(name, ops)| IrModule {
+            description: tags
+                .iter()
+                .find(|tag| tag.name == name)
+                .and_then(|tag| tag.description.clone()),
             name: normalize_name(&name),
             operations: ops,
         })
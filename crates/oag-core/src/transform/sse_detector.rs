@@ -1,18 +1,27 @@
-use crate::ir::{IrResponse, IrReturnType, IrSseReturn, IrType};
+use crate::config::Streaming;
+use crate::ir::{
+    IrErrorResponse, IrResponse, IrReturnType, IrSseReturn, IrType, NormalizedName,
+    is_text_media_type,
+};
 use crate::parse::media_type::MediaType;
 use crate::parse::response::ResponseOrRef;
 use crate::parse::schema::SchemaOrRef;
 
-use super::name_normalizer::normalize_name;
 use super::schema_resolver::schema_or_ref_to_ir_type;
 
 use indexmap::IndexMap;
 
 /// Detect whether an operation's responses include SSE streaming.
-/// Returns the appropriate `IrReturnType`.
+/// Returns the appropriate `IrReturnType`, an SSE one named after `name`, the
+/// operation's name once aliased (see [`derive_sse_names`]). JSON responses
+/// carry their examples, the one to use picked per [`media_examples`].
+/// `streaming` overrides the detection: `Off` ignores `text/event-stream`
+/// content, and `Force` streams the JSON (else first) content's schema.
 pub fn detect_return_type(
-    operation_id: &str,
+    name: &NormalizedName,
     responses: &IndexMap<String, ResponseOrRef>,
+    prefer: &[String],
+    streaming: Streaming,
 ) -> IrReturnType {
     let success_response = find_success_response(responses);
     let Some(response) = success_response else {
@@ -28,15 +37,25 @@ pub fn detect_return_type(
         return IrReturnType::Void;
     }
 
-    let sse = content.get("text/event-stream");
+    let sse = match streaming {
+        Streaming::Off => None,
+        Streaming::Auto | Streaming::Force => content.get("text/event-stream"),
+    };
     let json = content.get("application/json");
 
     match (sse, json) {
         (Some(sse_mt), json_mt) => {
             // SSE endpoint (possibly dual)
-            let sse_return = build_sse_return(operation_id, sse_mt, json_mt);
+            let sse_return = build_sse_return(name, sse_mt, json_mt, prefer);
             IrReturnType::Sse(sse_return)
         }
+        (None, json_mt) if streaming == Streaming::Force => {
+            // Stream items of the chosen content's schema, e.g. a long poll
+            let Some(mt) = json_mt.or_else(|| content.values().next()) else {
+                return IrReturnType::Void;
+            };
+            IrReturnType::Sse(build_sse_return(name, mt, None, prefer))
+        }
         (None, Some(json_mt)) => {
             // Standard JSON response
             let response_type = match &json_mt.schema {
@@ -47,21 +66,34 @@ pub fn detect_return_type(
                 ResponseOrRef::Response(r) => Some(r.description.clone()),
                 _ => None,
             };
+            let (example, examples) = media_examples(json_mt, prefer);
             IrReturnType::Standard(IrResponse {
                 response_type,
                 description,
+                content_type: Some("application/json".to_string()),
+                example,
+                examples,
+                empty_statuses: empty_success_statuses(responses, response),
             })
         }
         (None, None) => {
             // Try any other content type
-            if let Some((_ct, mt)) = content.first() {
+            if let Some((ct, mt)) = content.first() {
+                // Plain text is returned as a string whatever its schema says,
+                // and so is an event stream read whole (`streaming: off`).
                 let response_type = match &mt.schema {
+                    _ if is_text_media_type(ct) || ct == "text/event-stream" => IrType::String,
                     Some(s) => schema_or_ref_to_ir_type(s),
                     None => IrType::Any,
                 };
+                let (example, examples) = media_examples(mt, prefer);
                 IrReturnType::Standard(IrResponse {
                     response_type,
                     description: None,
+                    content_type: Some(ct.clone()),
+                    example,
+                    examples,
+                    empty_statuses: empty_success_statuses(responses, response),
                 })
             } else {
                 IrReturnType::Void
@@ -70,19 +102,62 @@ pub fn detect_return_type(
     }
 }
 
+/// An operation's JSON error responses: `4XX`/`5XX` statuses, plus `default`
+/// as the catch-all unless it's the success response (no 2xx is declared).
+pub fn detect_error_responses(responses: &IndexMap<String, ResponseOrRef>) -> Vec<IrErrorResponse> {
+    let success = find_success_response(responses);
+    responses
+        .iter()
+        .filter(|(status, response)| match status.as_str() {
+            "default" => !success.is_some_and(|s| std::ptr::eq(*response, s)),
+            status => status.starts_with('4') || status.starts_with('5'),
+        })
+        .filter_map(|(status, response)| {
+            let ResponseOrRef::Response(r) = response else {
+                return None;
+            };
+            let schema = r.content.get("application/json")?.schema.as_ref()?;
+            Some(IrErrorResponse {
+                status: status.clone(),
+                response_type: schema_or_ref_to_ir_type(schema),
+                description: Some(r.description.clone()),
+            })
+        })
+        .collect()
+}
+
+/// A media type's example to use and its named `examples`: the first name in
+/// `prefer` it has, else its single `example`.
+pub fn media_examples(
+    mt: &MediaType,
+    prefer: &[String],
+) -> (
+    Option<serde_json::Value>,
+    IndexMap<String, serde_json::Value>,
+) {
+    let examples = mt.named_examples();
+    let example = prefer
+        .iter()
+        .find_map(|name| examples.get(name))
+        .or(mt.example.as_ref())
+        .cloned();
+    (example, examples)
+}
+
 fn build_sse_return(
-    operation_id: &str,
+    name: &NormalizedName,
     sse_mt: &MediaType,
     json_mt: Option<&MediaType>,
+    prefer: &[String],
 ) -> IrSseReturn {
     // Extract event type from itemSchema (OpenAPI 3.2)
-    let (event_type, variants, event_type_name) = match &sse_mt.item_schema {
-        Some(item_schema) => extract_event_info(operation_id, item_schema),
+    let (event_type, variants) = match &sse_mt.item_schema {
+        Some(item_schema) => extract_event_info(item_schema),
         None => {
             // Fallback: try the schema field
             match &sse_mt.schema {
-                Some(s) => (schema_or_ref_to_ir_type(s), vec![], None),
-                None => (IrType::Any, vec![], None),
+                Some(s) => (schema_or_ref_to_ir_type(s), vec![]),
+                None => (IrType::Any, vec![]),
             }
         }
     };
@@ -92,51 +167,92 @@ fn build_sse_return(
             Some(s) => schema_or_ref_to_ir_type(s),
             None => IrType::Any,
         };
+        let (example, examples) = media_examples(mt, prefer);
         IrResponse {
             response_type,
             description: None,
+            content_type: Some("application/json".to_string()),
+            example,
+            examples,
+            empty_statuses: vec![],
         }
     });
 
-    IrSseReturn {
+    let mut sse = IrSseReturn {
         event_type,
         variants,
-        event_type_name,
+        event_type_name: None,
+        stream_name: name.clone(),
         also_has_json: json_response.is_some(),
         json_response,
-    }
+    };
+    derive_sse_names(name, &mut sse);
+    sse
 }
 
-fn extract_event_info(
-    operation_id: &str,
-    item_schema: &SchemaOrRef,
-) -> (IrType, Vec<IrType>, Option<String>) {
-    match item_schema {
-        SchemaOrRef::Ref { .. } => {
-            let ir_type = schema_or_ref_to_ir_type(item_schema);
-            (ir_type, vec![], None)
+/// Set the names generated code derives from an SSE operation's `name`: the
+/// stream method, `{name}Stream` for a dual endpoint, and for a `oneOf`
+/// itemSchema the `{Name}StreamEvent` union. Emitters read these instead of
+/// deriving their own, so types, client and hooks agree under any alias.
+pub fn derive_sse_names(name: &NormalizedName, sse: &mut IrSseReturn) {
+    sse.stream_name = if sse.also_has_json {
+        NormalizedName {
+            original: format!("{}Stream", name.original),
+            pascal_case: format!("{}Stream", name.pascal_case),
+            camel_case: format!("{}Stream", name.camel_case),
+            snake_case: format!("{}_stream", name.snake_case),
+            screaming_snake: format!("{}_STREAM", name.screaming_snake),
         }
+    } else {
+        name.clone()
+    };
+    sse.event_type_name =
+        (!sse.variants.is_empty()).then(|| format!("{}StreamEvent", name.pascal_case));
+}
+
+fn extract_event_info(item_schema: &SchemaOrRef) -> (IrType, Vec<IrType>) {
+    match item_schema {
+        SchemaOrRef::Ref { .. } => (schema_or_ref_to_ir_type(item_schema), vec![]),
         SchemaOrRef::Schema(schema) => {
             if !schema.one_of.is_empty() {
                 // Union of event types
                 let variants: Vec<IrType> =
                     schema.one_of.iter().map(schema_or_ref_to_ir_type).collect();
-                let event_name = format!("{}StreamEvent", normalize_name(operation_id).pascal_case);
-                let event_type = IrType::Union(variants.clone());
-                (event_type, variants, Some(event_name))
+                (IrType::Union(variants.clone()), variants)
             } else {
-                let ir_type = schema_or_ref_to_ir_type(item_schema);
-                (ir_type, vec![], None)
+                (schema_or_ref_to_ir_type(item_schema), vec![])
             }
         }
     }
 }
 
+/// The 2xx statuses besides `chosen` that are declared without a body.
+fn empty_success_statuses(
+    responses: &IndexMap<String, ResponseOrRef>,
+    chosen: &ResponseOrRef,
+) -> Vec<String> {
+    responses
+        .iter()
+        .filter(|(status, response)| status.starts_with('2') && !std::ptr::eq(*response, chosen))
+        .filter(
+            |(_, response)| matches!(response, ResponseOrRef::Response(r) if r.content.is_empty()),
+        )
+        .map(|(status, _)| status.clone())
+        .collect()
+}
+
 fn find_success_response(responses: &IndexMap<String, ResponseOrRef>) -> Option<&ResponseOrRef> {
-    // Try 200, 201, 2XX, default
+    // Try 200, 201, 2XX, then `default` when no 2xx status is declared at all
+    // (with one, `default` is the catch-all error).
     responses
         .get("200")
         .or_else(|| responses.get("201"))
         .or_else(|| responses.get("2XX"))
-        .or_else(|| responses.get("default"))
+        .or_else(|| {
+            if responses.keys().any(|status| status.starts_with('2')) {
+                None
+            } else {
+                responses.get("default")
+            }
+        })
 }
@@ -0,0 +1,110 @@
+use std::collections::HashSet;
+
+use crate::error::TransformError;
+use crate::ir::{IrReturnType, IrSchema, IrSpec, IrType};
+
+/// Find every `IrType::Ref` in the IR that names no schema in `ir.schemas`.
+///
+/// `$ref`s to component schemas are kept as names rather than inlined, so a typo,
+/// a schema that hasn't been written yet, or a pointer outside
+/// `#/components/schemas` would otherwise reach generators as a dangling type name.
+/// With `strict` the first broken ref is an error; otherwise each one is replaced
+/// by `IrType::Any` and reported in `ir.diagnostics`.
+pub fn resolve_broken_refs(ir: &mut IrSpec, strict: bool) -> Result<(), TransformError> {
+    let mut checker = RefChecker {
+        known: ir
+            .schemas
+            .iter()
+            .map(|s| s.name().pascal_case.clone())
+            .collect(),
+        strict,
+        diagnostics: Vec::new(),
+    };
+
+    for schema in &mut ir.schemas {
+        let name = schema.name().pascal_case.clone();
+        match schema {
+            IrSchema::Object(obj) => {
+                for field in &mut obj.fields {
+                    checker.check(
+                        &format!("{name}.{}", field.original_name),
+                        &mut field.field_type,
+                    )?;
+                }
+                if let Some(additional) = &mut obj.additional_properties {
+                    checker.check(&name, additional)?;
+                }
+            }
+            IrSchema::Alias(alias) => checker.check(&name, &mut alias.target)?,
+            IrSchema::Union(union) => {
+                for variant in &mut union.variants {
+                    checker.check(&name, variant)?;
+                }
+            }
+            IrSchema::Enum(_) => {}
+        }
+    }
+
+    for op in ir.operations.iter_mut().chain(&mut ir.webhooks) {
+        let location = op.label();
+        match &mut op.return_type {
+            IrReturnType::Standard(resp) => checker.check(&location, &mut resp.response_type)?,
+            IrReturnType::Sse(sse) => {
+                checker.check(&location, &mut sse.event_type)?;
+                for variant in &mut sse.variants {
+                    checker.check(&location, variant)?;
+                }
+                if let Some(json) = &mut sse.json_response {
+                    checker.check(&location, &mut json.response_type)?;
+                }
+            }
+            IrReturnType::Void => {}
+        }
+        for error in &mut op.errors {
+            checker.check(&location, &mut error.response_type)?;
+        }
+        if let Some(body) = &mut op.request_body {
+            checker.check(&location, &mut body.body_type)?;
+        }
+        for param in &mut op.parameters {
+            checker.check(&location, &mut param.param_type)?;
+        }
+    }
+
+    ir.diagnostics.extend(checker.diagnostics);
+    Ok(())
+}
+
+struct RefChecker {
+    known: HashSet<String>,
+    strict: bool,
+    diagnostics: Vec<String>,
+}
+
+impl RefChecker {
+    fn check(&mut self, location: &str, ty: &mut IrType) -> Result<(), TransformError> {
+        match ty {
+            IrType::Ref(name) if !self.known.contains(name.as_str()) => {
+                if self.strict {
+                    return Err(TransformError::BrokenRef {
+                        operation_or_schema: location.to_string(),
+                        ref_name: name.clone(),
+                    });
+                }
+                self.diagnostics.push(format!(
+                    "`{location}` references unknown schema `{name}`; typed as `Any`"
+                ));
+                *ty = IrType::Any;
+                Ok(())
+            }
+            IrType::Array(inner) | IrType::Map(inner, _) => self.check(location, inner),
+            IrType::Object(fields) => fields
+                .iter_mut()
+                .try_for_each(|(_, field_type, _)| self.check(location, field_type)),
+            IrType::Union(types) | IrType::Intersection(types) => {
+                types.iter_mut().try_for_each(|t| self.check(location, t))
+            }
+            _ => Ok(()),
+        }
+    }
+}
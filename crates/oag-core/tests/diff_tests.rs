@@ -0,0 +1,50 @@
+use oag_core::ir::IrSpec;
+use oag_core::ir::diff::{ChangeKind, Severity, diff};
+use oag_core::{parse, transform};
+
+const PETSTORE: &str = include_str!("fixtures/petstore-3.2.yaml");
+
+fn ir(yaml: &str) -> IrSpec {
+    transform::transform(&parse::from_yaml(yaml).unwrap()).unwrap()
+}
+
+/// Petstore after an upstream bump: an operation renamed, one removed, a
+/// parameter retyped, a field made required and an enum variant swapped.
+fn bumped_petstore() -> String {
+    let (head, tail) = PETSTORE.split_once("  /store/inventory:").unwrap();
+    let (_, components) = tail.split_once("components:").unwrap();
+    format!("{head}components:{components}")
+        .replace("operationId: listPets", "operationId: findPets")
+        .replace("type: integer\n            maximum: 100", "type: string")
+        .replace("required: [name]\n", "required: [name, status]\n")
+        .replace(
+            "[available, pending, sold]",
+            "[available, pending, adopted]",
+        )
+}
+
+#[test]
+fn petstore_diff() {
+    let d = diff(&ir(PETSTORE), &ir(&bumped_petstore()));
+    insta::assert_snapshot!(d.to_string());
+}
+
+#[test]
+fn petstore_diff_kinds() {
+    let d = diff(&ir(PETSTORE), &ir(&bumped_petstore()));
+    let breaking: Vec<ChangeKind> = d.breaking().map(|c| c.kind).collect();
+    assert!(breaking.contains(&ChangeKind::OperationRemoved));
+    assert!(breaking.contains(&ChangeKind::OperationRenamed));
+    assert!(breaking.contains(&ChangeKind::ParameterChanged));
+    assert!(breaking.contains(&ChangeKind::EnumVariantRemoved));
+    assert!(
+        d.changes
+            .iter()
+            .any(|c| c.kind == ChangeKind::EnumVariantAdded && c.severity == Severity::NonBreaking)
+    );
+}
+
+#[test]
+fn unchanged_petstore_has_no_diff() {
+    assert!(diff(&ir(PETSTORE), &ir(PETSTORE)).is_empty());
+}
@@ -0,0 +1,30 @@
+use oag_core::index::operations_index;
+use oag_core::{parse, transform};
+
+const PETSTORE: &str = include_str!("fixtures/petstore-3.2.yaml");
+const SSE_CHAT: &str = include_str!("fixtures/sse-chat.yaml");
+
+#[test]
+fn petstore_operations_index() {
+    let spec = parse::from_yaml(PETSTORE).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    insta::assert_snapshot!(operations_index(&ir));
+}
+
+#[test]
+fn streams_are_indexed_by_event_type() {
+    let spec = parse::from_yaml(SSE_CHAT).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let index: serde_json::Value = serde_json::from_str(&operations_index(&ir)).unwrap();
+    assert_eq!(index["version"], 1);
+    let streams: Vec<_> = index["operations"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|op| op["return_kind"] == "sse")
+        .collect();
+    assert!(!streams.is_empty());
+    for op in streams {
+        assert!(op["return_type"].is_string(), "{op}");
+    }
+}
@@ -1,4 +1,12 @@
-use oag_core::ir::{IrParameterLocation, IrReturnType, IrSchema, IrType};
+use oag_core::config::{
+    CollisionStrategy, FallbackNaming, IrPass, MissingPathParams, Streaming, TrailingSlash,
+};
+use oag_core::error::TransformError;
+use oag_core::hash::hash_ir;
+use oag_core::ir::{
+    IrAuth, IrMapKey, IrParameterLocation, IrReturnType, IrSchema, IrSpec, IrType, PatchFormat,
+    QueryArrayStyle, QueryObjectNotation, UnionKind,
+};
 use oag_core::parse;
 use oag_core::transform;
 
@@ -7,6 +15,36 @@ const PETSTORE: &str = include_str!("fixtures/petstore-3.2.yaml");
 const MIXED: &str = include_str!("fixtures/mixed-endpoints.yaml");
 const ANTHROPIC: &str = include_str!("fixtures/anthropic-messages.yaml");
 const PETSTORE_POLY: &str = include_str!("fixtures/petstore-polymorphic.yaml");
+const NAME_COLLISIONS: &str = include_str!("fixtures/name-collisions.yaml");
+const CONDITIONAL: &str = include_str!("fixtures/conditional-schemas.yaml");
+const JSON_QUERY: &str = include_str!("fixtures/json-query-params.yaml");
+const MAP_KEYS: &str = include_str!("fixtures/map-keys.yaml");
+const MARKDOWN_DOCS: &str = include_str!("fixtures/markdown-docs.yaml");
+const SCHEMA_ALIASES: &str = include_str!("fixtures/schema-aliases.yaml");
+const SHARED_COMPONENTS: &str = include_str!("fixtures/shared-components.yaml");
+const TRAILING_SLASHES: &str = include_str!("fixtures/trailing-slashes.yaml");
+const UNTAGGED: &str = include_str!("fixtures/untagged-operations.yaml");
+const RESERVED_WORDS: &str = include_str!("fixtures/reserved-words.yaml");
+const PLAIN_TEXT: &str = include_str!("fixtures/plain-text.yaml");
+const NULLABLE_UNIONS: &str = include_str!("fixtures/nullable-unions.yaml");
+const COOKIE_AUTH: &str = include_str!("fixtures/cookie-auth.yaml");
+const BEARER_AUTH: &str = include_str!("fixtures/bearer-auth.yaml");
+const DEPRECATED_FIELDS: &str = include_str!("fixtures/deprecated-fields.yaml");
+const ROUTE_NAMES: &str = include_str!("fixtures/route-names.yaml");
+const PARAMETER_EXAMPLES: &str = include_str!("fixtures/parameter-examples.yaml");
+const SCHEMA_EXAMPLES: &str = include_str!("fixtures/schema-examples.yaml");
+const LINKS: &str = include_str!("fixtures/links.yaml");
+const BODY_EXAMPLES: &str = include_str!("fixtures/body-examples.yaml");
+const RATE_LIMITS: &str = include_str!("fixtures/rate-limits.yaml");
+const REF_CHAINS: &str = include_str!("fixtures/ref-chains.yaml");
+const EMPTY_SUCCESS: &str = include_str!("fixtures/empty-success.yaml");
+const PATHOLOGICAL_ENUMS: &str = include_str!("fixtures/pathological-enums.yaml");
+const QUERY_OBJECTS: &str = include_str!("fixtures/query-objects.yaml");
+const QUERY_ARRAYS: &str = include_str!("fixtures/query-arrays.yaml");
+const DEFAULT_ERRORS: &str = include_str!("fixtures/default-errors.yaml");
+const PATCH_BODIES: &str = include_str!("fixtures/patch-bodies.yaml");
+const PATH_PARAMS: &str = include_str!("fixtures/path-params.yaml");
+const FIELD_DEFAULTS: &str = include_str!("fixtures/field-defaults.yaml");
 
 #[test]
 fn transform_sse_chat() {
@@ -14,6 +52,11 @@ fn transform_sse_chat() {
     let ir = transform::transform(&spec).unwrap();
 
     assert_eq!(ir.info.title, "AI Chat API");
+    let raw = ir
+        .raw_spec
+        .as_ref()
+        .expect("transform should keep the raw spec");
+    assert_eq!(raw.info.title, "AI Chat API");
     assert!(!ir.schemas.is_empty());
     assert!(!ir.operations.is_empty());
 
@@ -52,6 +95,99 @@ fn transform_sse_chat() {
     }
 }
 
+#[test]
+fn transform_names_sse_streams_after_aliases() {
+    let spec = parse::from_yaml(SSE_CHAT).unwrap();
+    let options = transform::TransformOptions::new().with_alias("createChatCompletion", "chat");
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+
+    let sse = |name: &str| match &ir
+        .operations
+        .iter()
+        .find(|op| op.name.camel_case == name)
+        .unwrap_or_else(|| panic!("should have {name}"))
+        .return_type
+    {
+        IrReturnType::Sse(sse) => sse,
+        _ => panic!("expected SSE return type for {name}"),
+    };
+
+    // The dual endpoint's stream method and event union follow the alias.
+    let chat = sse("chat");
+    assert_eq!(chat.stream_name.camel_case, "chatStream");
+    assert_eq!(chat.stream_name.pascal_case, "ChatStream");
+    assert_eq!(chat.stream_name.snake_case, "chat_stream");
+    assert_eq!(chat.event_type_name.as_deref(), Some("ChatStreamEvent"));
+
+    // A stream-only endpoint's stream method is the operation itself.
+    let stream = sse("createChatCompletionStream");
+    assert_eq!(stream.stream_name.camel_case, "createChatCompletionStream");
+    assert_eq!(
+        stream.event_type_name.as_deref(),
+        Some("CreateChatCompletionStreamStreamEvent")
+    );
+}
+
+#[test]
+fn transform_overrides_sse_detection_per_operation() {
+    let spec = parse::from_yaml(SSE_CHAT).unwrap();
+    let options = transform::TransformOptions::new()
+        .with_streaming("createChatCompletion", Streaming::Off)
+        .with_streaming("createChatCompletionStream", Streaming::Off)
+        .with_streaming("listModels", Streaming::Force)
+        .with_streaming("deleteModel", Streaming::Force);
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    let return_type = |name: &str| {
+        &ir.operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .unwrap_or_else(|| panic!("should have {name}"))
+            .return_type
+    };
+
+    // `off`: the JSON response wins over the event stream
+    match return_type("createChatCompletion") {
+        IrReturnType::Standard(resp) => {
+            assert_eq!(resp.content_type.as_deref(), Some("application/json"));
+            assert_eq!(
+                resp.response_type,
+                IrType::Ref("ChatCompletionResponse".into())
+            );
+        }
+        other => panic!("expected a standard response, got {other:?}"),
+    }
+    // ... and without one, the stream is read whole as text
+    match return_type("createChatCompletionStream") {
+        IrReturnType::Standard(resp) => {
+            assert_eq!(resp.content_type.as_deref(), Some("text/event-stream"));
+            assert_eq!(resp.response_type, IrType::String);
+            assert!(resp.is_text());
+        }
+        other => panic!("expected a standard response, got {other:?}"),
+    }
+
+    // `force`: the JSON schema becomes the event type
+    match return_type("listModels") {
+        IrReturnType::Sse(sse) => {
+            assert_eq!(sse.event_type, IrType::Ref("ModelList".into()));
+            assert!(!sse.also_has_json);
+            assert_eq!(sse.stream_name.camel_case, "listModels");
+        }
+        other => panic!("expected an SSE return type, got {other:?}"),
+    }
+
+    assert!(ir.diagnostics.contains(
+        &"operation `listModels`: `streaming: force` overrides SSE detection".to_string()
+    ));
+    assert!(ir.diagnostics.contains(
+        &"operation `createChatCompletion`: `streaming: off` overrides SSE detection".to_string()
+    ));
+    assert!(
+        ir.diagnostics
+            .contains(&"`operations.deleteModel` matches no operation".to_string())
+    );
+}
+
 #[test]
 fn transform_petstore() {
     let spec = parse::from_yaml(PETSTORE).unwrap();
@@ -126,6 +262,31 @@ fn transform_mixed() {
     }
 }
 
+#[test]
+fn transform_mixed_webhooks() {
+    let spec = parse::from_yaml(MIXED).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    // Webhooks stay out of the callable operations
+    assert_eq!(ir.operations.len(), 5);
+    assert!(ir.operations.iter().all(|op| !op.is_webhook));
+
+    assert_eq!(ir.webhooks.len(), 2);
+    let created = &ir.webhooks[0];
+    assert!(created.is_webhook);
+    assert_eq!(created.name.camel_case, "onItemCreated");
+    assert_eq!(created.raw_path, "itemCreated");
+    assert!(matches!(created.return_type, IrReturnType::Void));
+
+    // Inline webhook payloads are promoted like any other request body
+    let deleted = &ir.webhooks[1];
+    let body = deleted
+        .request_body
+        .as_ref()
+        .expect("should have a payload");
+    assert!(matches!(&body.body_type, IrType::Ref(name) if name == "OnItemDeletedBody"));
+}
+
 #[test]
 fn transform_modules_grouping() {
     let spec = parse::from_yaml(SSE_CHAT).unwrap();
@@ -142,6 +303,7 @@ fn transform_modules_grouping() {
         .find(|m| m.name.original == "chat")
         .expect("should have chat module");
     assert!(!chat_module.operations.is_empty());
+    assert_eq!(chat_module.description.as_deref(), Some("Chat operations"));
 
     let models_module = ir
         .modules
@@ -151,6 +313,160 @@ fn transform_modules_grouping() {
     assert!(!models_module.operations.is_empty());
 }
 
+#[test]
+fn transform_untagged_module_avoids_real_tags() {
+    let spec = parse::from_yaml(UNTAGGED).unwrap();
+    let module_ops = |ir: &oag_core::ir::IrSpec| -> Vec<(String, Vec<String>)> {
+        ir.modules
+            .iter()
+            .map(|m| {
+                let ops = m
+                    .operations
+                    .iter()
+                    .map(|&i| ir.operations[i].name.camel_case.clone())
+                    .collect();
+                (m.name.original.clone(), ops)
+            })
+            .collect()
+    };
+
+    // The spec has a real `default` tag, so untagged operations move aside.
+    let ir = transform::transform(&spec).unwrap();
+    assert_eq!(
+        module_ops(&ir),
+        vec![
+            ("default".to_string(), vec!["getSettings".to_string()]),
+            ("default_2".to_string(), vec!["getHealth".to_string()]),
+            ("types".to_string(), vec!["import".to_string()]),
+        ]
+    );
+
+    let options = transform::TransformOptions {
+        untagged_module: Some("misc".to_string()),
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    let names: Vec<String> = module_ops(&ir).into_iter().map(|(name, _)| name).collect();
+    assert_eq!(names, vec!["default", "misc", "types"]);
+}
+
+#[test]
+fn transform_custom_modules_take_precedence_over_tags() {
+    let spec = parse::from_yaml(PETSTORE).unwrap();
+    let options = transform::TransformOptions::new()
+        .with_module("admin", ["deletePet", "/store/*"])
+        .with_module("unused", ["noSuchOperation"]);
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+
+    let modules: Vec<(&str, Vec<&str>)> = ir
+        .modules
+        .iter()
+        .map(|m| {
+            let ops = m
+                .operations
+                .iter()
+                .map(|&i| ir.operations[i].name.camel_case.as_str())
+                .collect();
+            (m.name.original.as_str(), ops)
+        })
+        .collect();
+    assert_eq!(
+        modules,
+        vec![
+            ("admin", vec!["deletePet", "getInventory"]),
+            ("pets", vec!["listPets", "createPet", "getPet", "updatePet"]),
+        ]
+    );
+    assert!(
+        ir.diagnostics
+            .contains(&"module `unused` matches no operations".to_string())
+    );
+}
+
+#[test]
+fn transform_runs_passes_after_the_standard_phases() {
+    let spec = parse::from_yaml(PETSTORE).unwrap();
+    let options = transform::TransformOptions {
+        passes: vec![IrPass::PruneUnused],
+        post_passes: vec![
+            transform::PostPass::new(|ir| ir.diagnostics.push("first".to_string())),
+            transform::PostPass::new(|ir| {
+                let note = format!("second after {}", ir.diagnostics.len());
+                ir.diagnostics.push(note);
+            }),
+        ],
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    let n = ir.diagnostics.len();
+    assert_eq!(ir.diagnostics[n - 2], "first");
+    assert_eq!(ir.diagnostics[n - 1], format!("second after {}", n - 1));
+}
+
+#[test]
+fn flatten_intersections_spells_out_inherited_fields() {
+    let spec = parse::from_yaml(PETSTORE_POLY).unwrap();
+    let options = transform::TransformOptions {
+        passes: vec![IrPass::FlattenIntersections],
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+
+    let ext_err = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "ExtendedErrorModel")
+        .unwrap();
+    let IrSchema::Object(obj) = ext_err else {
+        panic!("ExtendedErrorModel should be flattened into an Object");
+    };
+    let fields: Vec<(&str, bool)> = obj
+        .fields
+        .iter()
+        .map(|f| (f.original_name.as_str(), f.required))
+        .collect();
+    assert_eq!(
+        fields,
+        [("message", true), ("code", true), ("rootCause", true)]
+    );
+    assert!(ir.diagnostics.is_empty());
+
+    // Without the pass the alias form stays.
+    let ir = transform::transform(&spec).unwrap();
+    assert!(
+        ir.schemas
+            .iter()
+            .any(|s| matches!(s, IrSchema::Alias(a) if a.name.pascal_case == "ExtendedErrorModel"))
+    );
+}
+
+#[test]
+fn transform_records_operation_source_location() {
+    let spec = parse::from_yaml(PETSTORE).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    let op = ir
+        .operations
+        .iter()
+        .find(|op| op.name.original == "getPet")
+        .unwrap();
+    let location = op.source_location.as_ref().unwrap();
+    assert_eq!(location.path, "/pets/{petId}");
+    assert_eq!(location.method, "GET");
+    assert_eq!(location.to_string(), "GET /pets/{petId}");
+    assert_eq!(op.label(), "GET /pets/{petId} (getPet)");
+
+    let positioned = oag_core::ir::OperationSourceLocation {
+        line: Some(12),
+        column: Some(5),
+        ..location.clone()
+    };
+    assert_eq!(
+        positioned.to_string(),
+        "GET /pets/{petId} (line 12, column 5)"
+    );
+}
+
 #[test]
 fn transform_request_body() {
     let spec = parse::from_yaml(SSE_CHAT).unwrap();
@@ -537,3 +853,1356 @@ fn transform_petstore_polymorphic() {
         .expect("should have getPet");
     assert_eq!(get_op.parameters.len(), 1); // petId
 }
+
+#[test]
+fn transform_exports_schema_mapping() {
+    use oag_core::transform::{normalize_name, schema_or_ref_to_ir_type};
+
+    let spec = parse::from_yaml(PETSTORE).unwrap();
+    let components = spec.components.as_ref().unwrap();
+
+    let category = schema_or_ref_to_ir_type(&components.schemas["Category"]);
+    assert!(matches!(category, IrType::Object(ref fields) if fields.len() == 2));
+    assert_eq!(normalize_name("pet_status").pascal_case, "PetStatus");
+}
+
+#[test]
+fn transform_rejects_colliding_schema_names() {
+    let spec = parse::from_yaml(NAME_COLLISIONS).unwrap();
+    match transform::transform(&spec) {
+        Err(TransformError::NameCollision {
+            name,
+            first,
+            second,
+        }) => {
+            assert_eq!(name, "UserProfile");
+            assert_eq!(first, "UserProfile");
+            assert_eq!(second, "user-profile");
+        }
+        other => panic!("expected a name collision, got {other:?}"),
+    }
+}
+
+#[test]
+fn transform_suffixes_colliding_schema_names() {
+    let spec = parse::from_yaml(NAME_COLLISIONS).unwrap();
+    let options = transform::TransformOptions {
+        on_collision: CollisionStrategy::Suffix,
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+
+    let names: Vec<&str> = ir
+        .schemas
+        .iter()
+        .map(|s| s.name().pascal_case.as_str())
+        .collect();
+    // The promoted `User.profile` object can't take either component's name.
+    assert_eq!(
+        names,
+        vec!["UserProfile", "UserProfile2", "User", "UserProfile3"]
+    );
+    assert_eq!(ir.diagnostics.len(), 1);
+    assert!(ir.diagnostics[0].contains("renamed to `UserProfile2`"));
+
+    let response_ref = |op_name: &str| {
+        let op = ir
+            .operations
+            .iter()
+            .find(|op| op.name.camel_case == op_name)
+            .unwrap();
+        match &op.return_type {
+            IrReturnType::Standard(resp) => resp.response_type.clone(),
+            _ => panic!("expected standard return"),
+        }
+    };
+    assert!(matches!(response_ref("getProfile"), IrType::Ref(n) if n == "UserProfile"));
+    assert!(matches!(response_ref("getPrivateProfile"), IrType::Ref(n) if n == "UserProfile2"));
+}
+
+#[test]
+fn transform_names_shared_component_schemas_once() {
+    let spec = parse::from_yaml(SHARED_COMPONENTS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    let names: Vec<&str> = ir
+        .schemas
+        .iter()
+        .map(|s| s.name().pascal_case.as_str())
+        .collect();
+    // The request body `Pet` can't take the schema's name.
+    assert_eq!(
+        names,
+        ["Pet", "PetPage", "ErrorResponse", "PetBody", "PageFilter"]
+    );
+
+    let op = |name: &str| {
+        ir.operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .unwrap()
+    };
+    for name in ["listPets", "listOwnerPets"] {
+        match &op(name).return_type {
+            IrReturnType::Standard(resp) => {
+                assert_eq!(resp.response_type, IrType::Ref("PetPage".into()))
+            }
+            other => panic!("expected standard return, got {other:?}"),
+        }
+        let page = op(name)
+            .parameters
+            .iter()
+            .find(|p| p.original_name == "page");
+        assert_eq!(page.unwrap().param_type, IrType::Ref("PageFilter".into()));
+    }
+    for name in ["createPet", "replacePet"] {
+        let body = op(name).request_body.as_ref().unwrap();
+        assert_eq!(body.body_type, IrType::Ref("PetBody".into()));
+    }
+}
+
+fn routes(ir: &oag_core::ir::IrSpec) -> Vec<(String, &str)> {
+    ir.operations
+        .iter()
+        .map(|op| (op.name.camel_case.clone(), op.raw_path.as_str()))
+        .collect()
+}
+
+#[test]
+fn transform_merges_paths_differing_by_a_trailing_slash() {
+    let spec = parse::from_yaml(TRAILING_SLASHES).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    assert_eq!(
+        routes(&ir),
+        [
+            ("listPets".to_string(), "/pets"),
+            ("createPet".to_string(), "/pets/"),
+            ("getPet".to_string(), "/pets/{id}/"),
+        ]
+    );
+    assert_eq!(
+        ir.diagnostics,
+        ["path `/pets/{petId}` declares no operations and generates nothing"]
+    );
+
+    let options = transform::TransformOptions {
+        trailing_slash: TrailingSlash::Strip,
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    assert_eq!(
+        routes(&ir),
+        [
+            ("listPets".to_string(), "/pets"),
+            ("createPet".to_string(), "/pets"),
+            ("getPet".to_string(), "/pets/{id}"),
+        ]
+    );
+}
+
+#[test]
+fn transform_rejects_a_method_declared_on_two_paths_of_one_route() {
+    let yaml = r#"
+openapi: 3.1.0
+info: { title: Test, version: "1.0" }
+paths:
+  /pets/{petId}:
+    parameters: [{ name: petId, in: path, required: true, schema: { type: string } }]
+    get:
+      responses: { "200": { description: OK } }
+  /pets/{id}/:
+    parameters: [{ name: id, in: path, required: true, schema: { type: string } }]
+    delete:
+      responses: { "204": { description: Deleted } }
+    get:
+      responses: { "200": { description: OK } }
+"#;
+    let spec = parse::from_yaml(yaml).unwrap();
+    match transform::transform(&spec) {
+        Err(TransformError::DuplicateRoute {
+            method,
+            first,
+            second,
+        }) => {
+            assert_eq!(method, "GET");
+            assert_eq!(first, "/pets/{petId}");
+            assert_eq!(second, "/pets/{id}/");
+        }
+        other => panic!("expected a duplicate route, got {other:?}"),
+    }
+
+    // `keep` generates both, the second with a numbered derived name.
+    let options = transform::TransformOptions {
+        trailing_slash: TrailingSlash::Keep,
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    assert_eq!(
+        routes(&ir),
+        [
+            ("getPet".to_string(), "/pets/{petId}"),
+            ("getPet2".to_string(), "/pets/{id}/"),
+            ("deletePet".to_string(), "/pets/{id}/"),
+        ]
+    );
+}
+
+fn schema_aliases(aliases: &[(&str, &str)]) -> transform::TransformOptions {
+    transform::TransformOptions {
+        schema_aliases: aliases
+            .iter()
+            .map(|(from, to)| (from.to_string(), to.to_string()))
+            .collect(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn transform_applies_schema_aliases() {
+    let spec = parse::from_yaml(SCHEMA_ALIASES).unwrap();
+    // One key is the generated name, the other the spec's.
+    let options = schema_aliases(&[
+        ("InlineResponse2001", "PetListResponse"),
+        ("InlineResponse400", "SearchError"),
+    ]);
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    assert!(ir.diagnostics.is_empty(), "{:?}", ir.diagnostics);
+
+    let names: Vec<&str> = ir
+        .schemas
+        .iter()
+        .map(|s| s.name().pascal_case.as_str())
+        .collect();
+    assert_eq!(names, ["PetListResponse", "SearchError", "SearchResult"]);
+
+    let op = |name: &str| {
+        ir.operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .unwrap()
+    };
+    match &op("listPets").return_type {
+        IrReturnType::Standard(resp) => {
+            assert!(matches!(&resp.response_type, IrType::Ref(n) if n == "PetListResponse"))
+        }
+        other => panic!("expected standard return, got {other:?}"),
+    }
+    match &op("watchPets").return_type {
+        IrReturnType::Sse(sse) => assert_eq!(
+            sse.variants,
+            [
+                IrType::Ref("PetListResponse".into()),
+                IrType::Ref("SearchError".into())
+            ]
+        ),
+        other => panic!("expected SSE return, got {other:?}"),
+    }
+
+    let Some(IrSchema::Union(union)) = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "SearchResult")
+    else {
+        panic!("expected SearchResult to be a union");
+    };
+    assert_eq!(
+        union.variants,
+        [
+            IrType::Ref("PetListResponse".into()),
+            IrType::Ref("SearchError".into())
+        ]
+    );
+    let mapping = &union.discriminator.as_ref().unwrap().mapping;
+    assert_eq!(
+        mapping,
+        &[
+            ("page".to_string(), "PetListResponse".to_string()),
+            ("error".to_string(), "SearchError".to_string())
+        ]
+    );
+
+    let generated = format!("{:?}{:?}", ir.schemas, ir.operations);
+    assert!(!generated.contains("InlineResponse"));
+    assert!(!generated.contains("inline_response"));
+}
+
+#[test]
+fn transform_rejects_schema_aliases_onto_existing_names() {
+    let spec = parse::from_yaml(SCHEMA_ALIASES).unwrap();
+    let options = schema_aliases(&[("InlineResponse400", "search-result")]);
+    match transform::transform_with_options(&spec, &options) {
+        Err(TransformError::SchemaAliasCollision {
+            schema,
+            name,
+            existing,
+        }) => {
+            assert_eq!(schema, "InlineResponse400");
+            assert_eq!(name, "SearchResult");
+            assert_eq!(existing, "SearchResult");
+        }
+        other => panic!("expected a schema alias collision, got {other:?}"),
+    }
+
+    let options = schema_aliases(&[
+        ("inline_response_200_1", "Page"),
+        ("InlineResponse400", "Page"),
+    ]);
+    assert!(matches!(
+        transform::transform_with_options(&spec, &options),
+        Err(TransformError::SchemaAliasCollision { existing, .. }) if existing == "inline_response_200_1"
+    ));
+}
+
+#[test]
+fn transform_warns_about_schema_aliases_matching_nothing() {
+    let spec = parse::from_yaml(SCHEMA_ALIASES).unwrap();
+    let options = schema_aliases(&[("InlineResponse2002", "PetListResponse")]);
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    assert_eq!(
+        ir.diagnostics,
+        ["`naming.schema_aliases` entry `InlineResponse2002` matches no schema"]
+    );
+}
+
+#[test]
+fn transform_renames_schemas_shadowing_builtins() {
+    let spec = parse::from_yaml(RESERVED_WORDS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    let names: Vec<&str> = ir
+        .schemas
+        .iter()
+        .map(|s| s.name().pascal_case.as_str())
+        .collect();
+    assert_eq!(names, vec!["RecordSchema"]);
+    assert_eq!(ir.diagnostics.len(), 1);
+    assert!(ir.diagnostics[0].contains("renamed to `RecordSchema`"));
+    match &ir.operations[0].return_type {
+        IrReturnType::Standard(resp) => {
+            assert_eq!(resp.response_type, IrType::Ref("RecordSchema".to_string()));
+        }
+        other => panic!("expected a standard response, got {other:?}"),
+    }
+}
+
+#[test]
+fn transform_rejects_alias_collisions() {
+    let spec = parse::from_yaml(PETSTORE).unwrap();
+    let options = transform::TransformOptions {
+        aliases: [("getPet".to_string(), "listPets".to_string())].into(),
+        ..Default::default()
+    };
+    match transform::transform_with_options(&spec, &options) {
+        Err(TransformError::AliasCollision {
+            name,
+            first,
+            second,
+        }) => {
+            assert_eq!(name, "listPets");
+            assert_eq!(first, "GET /pets");
+            assert_eq!(second, "GET /pets/{petId}");
+        }
+        other => panic!("expected an alias collision, got {other:?}"),
+    }
+}
+
+#[test]
+fn transform_handles_broken_schema_refs() {
+    let spec = parse::from_yaml(
+        r##"
+openapi: 3.1.0
+info:
+  title: Dangling
+  version: "1"
+paths:
+  /pets:
+    get:
+      operationId: listPets
+      responses:
+        "200":
+          description: ok
+          content:
+            application/json:
+              schema:
+                type: array
+                items:
+                  $ref: "#/components/schemas/Pet"
+components:
+  schemas:
+    Owner:
+      type: object
+      properties:
+        name:
+          type: string
+"##,
+    )
+    .unwrap();
+
+    let options = transform::TransformOptions {
+        strict: true,
+        ..Default::default()
+    };
+    match transform::transform_with_options(&spec, &options) {
+        Err(TransformError::BrokenRef {
+            operation_or_schema,
+            ref_name,
+        }) => {
+            assert_eq!(ref_name, "Pet");
+            assert_eq!(operation_or_schema, "GET /pets (listPets)");
+        }
+        other => panic!("expected a broken ref error, got {other:?}"),
+    }
+
+    // Without `strict` the ref is typed as `Any` and reported.
+    let ir = transform::transform(&spec).unwrap();
+    match &ir.operations[0].return_type {
+        IrReturnType::Standard(resp) => {
+            assert_eq!(resp.response_type, IrType::Array(Box::new(IrType::Any)));
+        }
+        other => panic!("expected a standard response, got {other:?}"),
+    }
+    assert_eq!(ir.diagnostics.len(), 1);
+    assert!(ir.diagnostics[0].contains("unknown schema `Pet`"));
+}
+
+#[test]
+fn transform_conditional_schema_keeps_base_type_with_diagnostic() {
+    let spec = parse::from_yaml(CONDITIONAL).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    assert_eq!(ir.diagnostics.len(), 1);
+    assert!(ir.diagnostics[0].contains("`Shipment`"));
+    assert!(ir.diagnostics[0].contains("`if`, `then`, `else`"));
+
+    let shipment = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "Shipment")
+        .unwrap();
+    assert_eq!(shipment.unsupported_keywords(), ["if", "then", "else"]);
+    match shipment {
+        IrSchema::Object(obj) => {
+            let fields: Vec<_> = obj
+                .fields
+                .iter()
+                .map(|f| f.original_name.as_str())
+                .collect();
+            assert_eq!(fields, ["country", "postalCode"]);
+        }
+        _ => panic!("expected the base object schema"),
+    }
+}
+
+#[test]
+fn transform_drops_repeated_enum_values_with_diagnostic() {
+    let spec = parse::from_yaml(PATHOLOGICAL_ENUMS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    let mode = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "Mode")
+        .unwrap();
+    match mode {
+        IrSchema::Enum(e) => assert_eq!(e.variants, ["a", "A", "", "none", "1", "-"]),
+        _ => panic!("expected an enum schema"),
+    }
+
+    let flag = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "Flag")
+        .unwrap();
+    match flag {
+        IrSchema::Object(obj) => {
+            let kind = obj
+                .fields
+                .iter()
+                .find(|f| f.original_name == "kind")
+                .unwrap();
+            assert_eq!(
+                kind.field_type,
+                IrType::Union(vec![
+                    IrType::StringLiteral("on".to_string()),
+                    IrType::StringLiteral("off".to_string()),
+                ])
+            );
+        }
+        _ => panic!("expected an object schema"),
+    }
+
+    assert_eq!(
+        ir.diagnostics,
+        [
+            "schema `Mode` lists enum values \"a\", \"A\", \"\" more than once; the repeats are dropped",
+            "schema `Flag` lists enum values \"on\" more than once; the repeats are dropped",
+        ]
+    );
+}
+
+#[test]
+fn transform_approximates_bare_conditional_and_not() {
+    let spec = parse::from_yaml(
+        r#"
+openapi: 3.1.0
+info:
+  title: Approximations
+  version: "1"
+paths: {}
+components:
+  schemas:
+    Address:
+      if:
+        properties:
+          kind:
+            const: po_box
+      then:
+        type: object
+        properties:
+          box:
+            type: string
+    NotEmpty:
+      type: string
+      not:
+        const: ""
+"#,
+    )
+    .unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    assert_eq!(ir.diagnostics.len(), 2);
+
+    // A schema that is only a conditional takes the shape of its `then` branch.
+    match &ir.schemas[0] {
+        IrSchema::Object(obj) => {
+            assert_eq!(obj.name.pascal_case, "Address");
+            assert_eq!(obj.fields[0].original_name, "box");
+            assert_eq!(obj.unsupported_keywords, ["if", "then"]);
+        }
+        other => panic!("expected object, got {other:?}"),
+    }
+    // `not` is dropped, leaving the base type.
+    match &ir.schemas[1] {
+        IrSchema::Alias(alias) => {
+            assert_eq!(alias.target, IrType::String);
+            assert_eq!(alias.unsupported_keywords, ["not"]);
+        }
+        other => panic!("expected alias, got {other:?}"),
+    }
+}
+
+#[test]
+fn transform_content_parameter_uses_media_type_schema() {
+    let spec = parse::from_yaml(JSON_QUERY).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let op = &ir.operations[0];
+
+    let filter = &op.parameters[0];
+    assert_eq!(filter.location, IrParameterLocation::Query);
+    assert_eq!(filter.param_type, IrType::Ref("ItemFilter".to_string()));
+    assert_eq!(filter.content_type.as_deref(), Some("application/json"));
+
+    let limit = &op.parameters[1];
+    assert_eq!(limit.param_type, IrType::Integer);
+    assert_eq!(limit.content_type, None);
+}
+
+#[test]
+fn transform_map_key_constraints_from_property_names() {
+    let spec = parse::from_yaml(MAP_KEYS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let alias_target = |name: &str| match ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == name)
+        .unwrap()
+    {
+        IrSchema::Alias(alias) => alias.target.clone(),
+        other => panic!("expected alias, got {other:?}"),
+    };
+
+    // A digits-only pattern means integer keys.
+    assert_eq!(
+        alias_target("ScoresByYear"),
+        IrType::Map(Box::new(IrType::Number), Some(IrMapKey::Integer))
+    );
+    // Any other pattern is kept as a constraint on string keys.
+    assert_eq!(
+        alias_target("Labels"),
+        IrType::Map(
+            Box::new(IrType::String),
+            Some(IrMapKey::Pattern("^[a-z][a-z0-9-]*$".to_string()))
+        )
+    );
+
+    // An integer format on the key schema also means integer keys.
+    match ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "Warehouse")
+        .unwrap()
+    {
+        IrSchema::Object(obj) => assert_eq!(
+            obj.fields[0].field_type,
+            IrType::Map(Box::new(IrType::Integer), Some(IrMapKey::Integer))
+        ),
+        other => panic!("expected object, got {other:?}"),
+    }
+}
+
+#[test]
+fn transform_operation_doc_and_truncation() {
+    let spec = parse::from_yaml(MARKDOWN_DOCS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let op = &ir.operations[0];
+    assert_eq!(
+        op.external_docs.as_deref(),
+        Some("https://docs.example.com/widgets")
+    );
+    let doc = op.doc().unwrap();
+    assert!(doc.starts_with("List widgets\n\nReturns widgets visible"));
+
+    let options = transform::TransformOptions {
+        doc_max_length: Some(40),
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    assert_eq!(
+        ir.operations[0].description.as_deref(),
+        Some("Returns widgets visible to the caller,…")
+    );
+    assert_eq!(ir.operations[0].summary.as_deref(), Some("List widgets"));
+}
+
+#[test]
+fn transform_substitutes_server_variable_defaults() {
+    let spec = parse::from_yaml(
+        r#"
+openapi: 3.1.0
+info:
+  title: Servers
+  version: "1"
+servers:
+  - url: https://{region}.api.example.com/{version}
+    variables:
+      region:
+        default: eu
+        enum: [eu, us]
+      version:
+        default: v2
+  - url: /v1
+paths: {}
+"#,
+    )
+    .unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    assert_eq!(
+        ir.servers[0].url,
+        "https://{region}.api.example.com/{version}"
+    );
+    assert_eq!(ir.servers[0].default_url, "https://eu.api.example.com/v2");
+    assert_eq!(ir.servers[1].default_url, "/v1");
+    assert_eq!(ir.default_base_url(), Some("https://eu.api.example.com/v2"));
+}
+
+#[test]
+fn transform_plain_text_bodies_are_strings() {
+    let spec = parse::from_yaml(PLAIN_TEXT).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let op = |name: &str| {
+        ir.operations
+            .iter()
+            .find(|op| op.name.original == name)
+            .unwrap()
+    };
+
+    let body = op("ingestLogs").request_body.as_ref().unwrap();
+    assert_eq!(body.content_type, "text/plain");
+    assert!(body.is_text());
+    assert_eq!(body.body_type, IrType::String);
+    let IrReturnType::Standard(json) = &op("ingestLogs").return_type else {
+        panic!("ingestLogs should return JSON");
+    };
+    assert!(!json.is_text());
+
+    for name in ["tailLogs", "exportLogs"] {
+        let IrReturnType::Standard(text) = &op(name).return_type else {
+            panic!("{name} should return text");
+        };
+        assert!(text.is_text());
+        assert_eq!(text.response_type, IrType::String);
+    }
+}
+
+#[test]
+fn transform_coalesces_nullable_unions() {
+    let spec = parse::from_yaml(NULLABLE_UNIONS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let nullable_pet = IrType::Union(vec![IrType::Ref("Pet".into()), IrType::Null]);
+
+    let schema = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "NullablePet")
+        .unwrap();
+    let IrSchema::Alias(alias) = schema else {
+        panic!("NullablePet should be an alias, got {schema:?}");
+    };
+    assert_eq!(alias.target, nullable_pet);
+    assert!(alias.description.is_some());
+
+    let IrSchema::Object(adoption) = ir
+        .schemas
+        .iter()
+        .find(|s| s.name().pascal_case == "Adoption")
+        .unwrap()
+    else {
+        panic!("Adoption should be an object");
+    };
+    let field = |name: &str| {
+        &adoption
+            .fields
+            .iter()
+            .find(|f| f.original_name == name)
+            .unwrap()
+            .field_type
+    };
+    assert_eq!(*field("pet"), IrType::Ref("NullablePet".into()));
+    // `null` listed first still ends up last.
+    assert_eq!(*field("previousPet"), nullable_pet);
+    assert_eq!(
+        *field("tags"),
+        IrType::Union(vec![IrType::Array(Box::new(IrType::String)), IrType::Null])
+    );
+}
+
+#[test]
+fn transform_owned_matches_borrowed() {
+    let spec = parse::from_yaml(ANTHROPIC).unwrap();
+    let options = transform::TransformOptions::default();
+    let borrowed = transform::transform_with_options(&spec, &options).unwrap();
+    let owned = transform::transform_owned(spec, &options).unwrap();
+    assert_eq!(hash_ir(&owned), hash_ir(&borrowed));
+
+    // An IR's raw spec can be transformed again without copying it.
+    let raw = owned.raw_spec.clone().unwrap();
+    let again = transform::transform_owned(std::sync::Arc::clone(&raw), &options).unwrap();
+    assert!(std::sync::Arc::ptr_eq(
+        again.raw_spec.as_ref().unwrap(),
+        &raw
+    ));
+    assert_eq!(hash_ir(&again), hash_ir(&borrowed));
+}
+
+#[test]
+fn transform_cookie_auth() {
+    let spec = parse::from_yaml(COOKIE_AUTH).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let cookies = |name: &str| {
+        ir.operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .unwrap()
+            .cookie_auth
+            .clone()
+    };
+
+    // Inherited from the spec's `security`, or any alternative that uses it.
+    assert_eq!(cookies("getMe"), ["sid"]);
+    assert_eq!(cookies("streamEvents"), ["sid"]);
+    assert_eq!(cookies("health"), ["sid"]);
+    // Overridden with no auth or another scheme.
+    assert!(cookies("login").is_empty());
+    assert!(cookies("status").is_empty());
+}
+
+#[test]
+fn transform_bearer_auth() {
+    let spec = parse::from_yaml(BEARER_AUTH).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let bearer = |name: &str| {
+        ir.operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .unwrap()
+            .bearer_auth
+            .clone()
+    };
+
+    let jwt = bearer("getMe").unwrap();
+    assert_eq!(jwt.format.as_deref(), Some("JWT"));
+    assert!(jwt.is_jwt());
+    assert_eq!(bearer("streamEvents"), Some(jwt));
+    // `scheme` is case-insensitive; no `bearerFormat` means opaque tokens.
+    let opaque = bearer("listKeys").unwrap();
+    assert_eq!(opaque.format, None);
+    assert!(!opaque.is_jwt());
+    assert_eq!(bearer("createToken"), None);
+
+    let spec = parse::from_yaml(COOKIE_AUTH).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let op = |name: &str| ir.operations.iter().find(|op| op.name.camel_case == name);
+    assert!(op("status").unwrap().bearer_auth.is_some());
+    assert!(op("getMe").unwrap().bearer_auth.is_none());
+}
+
+#[test]
+fn transform_operation_security() {
+    let spec = parse::from_yaml(COOKIE_AUTH).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let op = |name: &str| {
+        ir.operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .unwrap()
+    };
+
+    assert_eq!(ir.security.len(), 1);
+    assert!(ir.security[0].contains_key("session"));
+
+    // No `security` of its own: the spec's applies.
+    assert_eq!(op("getMe").security, None);
+    assert_eq!(op("getMe").auth(), IrAuth::Inherited);
+    assert_eq!(op("getMe").security_schemes(&ir.security), ["session"]);
+    // `security: []` opts out, whatever the spec's default.
+    assert_eq!(op("login").security, Some(vec![]));
+    assert_eq!(op("login").auth(), IrAuth::Public);
+    assert!(op("login").effective_security(&ir.security).is_empty());
+    // Its own requirements replace the spec's.
+    assert_eq!(op("status").auth(), IrAuth::Operation);
+    assert_eq!(op("status").security_schemes(&ir.security), ["bearer"]);
+    assert_eq!(
+        op("health").security_schemes(&ir.security),
+        ["bearer", "session"]
+    );
+}
+
+#[test]
+fn transform_deprecated_fields() {
+    let spec = parse::from_yaml(DEPRECATED_FIELDS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let IrSchema::Object(account) = &ir.schemas[0] else {
+        panic!("expected an object schema");
+    };
+    let deprecated: Vec<&str> = account
+        .fields
+        .iter()
+        .filter(|f| f.deprecated)
+        .map(|f| f.original_name.as_str())
+        .collect();
+    assert_eq!(deprecated, ["legacyId", "nickname"]);
+}
+
+/// `(path, operation name)` for every operation in `ROUTE_NAMES`, sorted by
+/// path, with the spec's paths in their written order or reversed.
+fn route_names(fallback: FallbackNaming, reversed: bool) -> Vec<(String, String)> {
+    let mut spec = parse::from_yaml(ROUTE_NAMES).unwrap();
+    if reversed {
+        spec.paths.reverse();
+    }
+    let options = transform::TransformOptions {
+        fallback_naming: fallback,
+        aliases: [("getPet_*".to_string(), "getPetAt$1".to_string())].into(),
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    let mut names: Vec<_> = ir
+        .operations
+        .iter()
+        .map(|op| (op.raw_path.clone(), op.name.camel_case.clone()))
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn route_fallback_numbers_clashing_names_in_spec_order() {
+    let written = route_names(FallbackNaming::Route, false);
+    assert_eq!(
+        written,
+        [
+            ("/owners".to_string(), "listOwners".to_string()),
+            ("/pets/{petId}".to_string(), "getPet".to_string()),
+            ("/pets/{petId}/{version}".to_string(), "getPet2".to_string()),
+            (
+                "/stores/{storeId}/pets/{petId}".to_string(),
+                "getStoresPet".to_string()
+            ),
+        ]
+    );
+    assert_ne!(written, route_names(FallbackNaming::Route, true));
+}
+
+#[test]
+fn route_hash_fallback_names_survive_reordering() {
+    let written = route_names(FallbackNaming::RouteHash, false);
+    assert_eq!(written, route_names(FallbackNaming::RouteHash, true));
+
+    let names: Vec<&str> = written.iter().map(|(_, name)| name.as_str()).collect();
+    assert_eq!(names[0], "listOwners");
+    assert_eq!(names[3], "getStoresPet");
+    // Both clashing names get a hash, and aliases match the hashed names.
+    for name in &names[1..3] {
+        assert!(name.starts_with("getPetAt"), "{name}");
+        assert_eq!(name.len(), "getPetAt".len() + 6, "{name}");
+    }
+    assert_ne!(names[1], names[2]);
+}
+
+#[test]
+fn parameters_carry_their_examples() {
+    let spec = parse::from_yaml(PARAMETER_EXAMPLES).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let params = &ir.operations[0].parameters;
+
+    assert_eq!(params[0].example, Some(serde_json::json!(42)));
+    assert_eq!(params[0].path_example().as_deref(), Some("42"));
+    // The first of `examples` when there's no `example`.
+    assert_eq!(params[1].example, Some(serde_json::json!("name,status")));
+    // A string that would need encoding isn't used in paths.
+    assert_eq!(ir.operations[1].parameters[0].path_example(), None);
+}
+
+#[test]
+fn schemas_carry_their_examples() {
+    let spec = parse::from_yaml(SCHEMA_EXAMPLES).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let example = |name: &str| {
+        ir.schemas
+            .iter()
+            .find(|s| s.name().pascal_case == name)
+            .unwrap()
+            .example()
+            .cloned()
+    };
+
+    assert_eq!(
+        example("Pet"),
+        Some(serde_json::json!({ "id": 1, "name": "Rex", "status": "available" }))
+    );
+    assert_eq!(example("PetStatus"), None);
+    // Kept as written, whether or not it matches the schema.
+    assert_eq!(
+        example("Order"),
+        Some(serde_json::json!({ "id": 7, "quantity": "two" }))
+    );
+}
+
+#[test]
+fn links_follow_response_body_fields() {
+    let spec = parse::from_yaml(LINKS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let create = &ir.operations[0];
+
+    assert_eq!(create.links.len(), 1);
+    let link = &create.links[0];
+    assert_eq!(link.name, "GetPet");
+    assert_eq!(link.target_operation.camel_case, "getPet");
+    assert_eq!(link.parameter_mapping.len(), 1);
+    assert_eq!(link.parameter_mapping[0].parameter, "petId");
+    assert_eq!(link.parameter_mapping[0].pointer, "/id");
+    assert!(ir.operations[1].links.is_empty());
+}
+
+#[test]
+fn links_with_unsupported_expressions_are_skipped() {
+    let spec = parse::from_yaml(LINKS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    assert_eq!(ir.diagnostics.len(), 1);
+    let diagnostic = &ir.diagnostics[0];
+    assert!(diagnostic.starts_with("link `GetPetByLocation` of POST /pets (createPet)"));
+    assert!(diagnostic.contains("$response.header.Location"));
+    assert!(diagnostic.ends_with("skipped"));
+}
+
+#[test]
+fn body_examples_follow_the_preferred_names() {
+    let spec = parse::from_yaml(BODY_EXAMPLES).unwrap();
+    let transform = |prefer: &[&str]| {
+        let options = transform::TransformOptions {
+            example_preference: prefer.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        transform::transform_with_options(&spec, &options).unwrap()
+    };
+    let body_example = |ir: &oag_core::ir::IrSpec| {
+        ir.operations[0]
+            .request_body
+            .as_ref()
+            .unwrap()
+            .example
+            .clone()
+    };
+
+    let ir = transform(&["minimal", "default"]);
+    let body = ir.operations[0].request_body.as_ref().unwrap();
+    assert_eq!(
+        body.examples.keys().collect::<Vec<_>>(),
+        ["full", "minimal"]
+    );
+    assert_eq!(body.example, Some(serde_json::json!({ "name": "Rex" })));
+    assert_eq!(
+        body_example(&transform(&["full", "minimal"])),
+        Some(serde_json::json!({ "name": "Rex", "tag": "dog" }))
+    );
+
+    // No preferred name: the single `example`, then nothing.
+    let ir = transform(&[]);
+    assert_eq!(
+        body_example(&ir),
+        Some(serde_json::json!({ "name": "Single" }))
+    );
+    let IrReturnType::Standard(created) = &ir.operations[0].return_type else {
+        panic!("expected a JSON response");
+    };
+    assert_eq!(
+        created.example,
+        Some(serde_json::json!({ "id": 7, "name": "Rex" }))
+    );
+    let IrReturnType::Standard(pet) = &transform(&["minimal"]).operations[1].return_type else {
+        panic!("expected a JSON response");
+    };
+    assert_eq!(pet.example, None);
+    assert_eq!(pet.examples.len(), 1);
+}
+
+#[test]
+fn exposed_extensions_are_copied_onto_operations() {
+    let spec = parse::from_yaml(RATE_LIMITS).unwrap();
+    let options = transform::TransformOptions {
+        exposed_extensions: vec!["x-ratelimit-rpm".into(), "x-ratelimit-burst".into()],
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+
+    let create = &ir.operations[0].extensions;
+    assert_eq!(
+        create.keys().collect::<Vec<_>>(),
+        ["x-ratelimit-rpm", "x-ratelimit-burst"]
+    );
+    assert_eq!(create["x-ratelimit-rpm"], serde_json::json!(60));
+    assert_eq!(
+        create["x-ratelimit-burst"],
+        serde_json::json!({ "limit": 10, "window": "1s" })
+    );
+    assert_eq!(
+        ir.operations[1].extensions["x-ratelimit-rpm"],
+        serde_json::json!(600)
+    );
+    assert!(ir.operations[2].extensions.is_empty());
+
+    // Nothing is exposed unless listed.
+    let ir = transform::transform(&spec).unwrap();
+    assert!(ir.operations.iter().all(|op| op.extensions.is_empty()));
+}
+
+#[test]
+fn union_kind_follows_the_spec_keyword() {
+    let spec = parse::from_yaml(ANTHROPIC).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let kind = |name: &str| match ir.schemas.iter().find(|s| s.name().pascal_case == name) {
+        Some(IrSchema::Union(u)) => u.kind,
+        _ => panic!("{name} should be a union"),
+    };
+
+    assert_eq!(kind("ContentBlock"), UnionKind::OneOf);
+    assert_eq!(kind("ToolResultContent"), UnionKind::AnyOf);
+}
+
+#[test]
+fn ref_chains_transform_to_their_targets() {
+    let spec = parse::from_yaml(REF_CHAINS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let pet_alias = IrType::Ref("PetAlias".to_string());
+
+    let get = &ir.operations[0];
+    assert_eq!(get.parameters[0].original_name, "petId");
+    assert_eq!(get.parameters[0].location, IrParameterLocation::Path);
+    let IrReturnType::Standard(response) = &get.return_type else {
+        panic!("expected a JSON response");
+    };
+    assert_eq!(response.response_type, pet_alias);
+
+    let body = ir.operations[1].request_body.as_ref().unwrap();
+    assert_eq!(body.body_type, pet_alias);
+    assert!(body.required);
+
+    // Each schema in `PetAlias -> PetRecord -> PetEntity -> Pet` aliases the next.
+    let target = |name: &str| match ir.schemas.iter().find(|s| s.name().pascal_case == name) {
+        Some(IrSchema::Alias(alias)) => alias.target.clone(),
+        _ => panic!("{name} should be an alias"),
+    };
+    assert_eq!(target("PetAlias"), IrType::Ref("PetRecord".to_string()));
+    assert_eq!(target("PetRecord"), IrType::Ref("PetEntity".to_string()));
+    assert_eq!(target("PetEntity"), IrType::Ref("Pet".to_string()));
+}
+
+#[test]
+fn bodiless_success_statuses_are_recorded() {
+    let spec = parse::from_yaml(EMPTY_SUCCESS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let response = |index: usize| match &ir.operations[index].return_type {
+        IrReturnType::Standard(response) => response.clone(),
+        other => panic!("expected a JSON response, got {other:?}"),
+    };
+
+    let get = response(0);
+    assert_eq!(get.response_type, IrType::Ref("Pet".to_string()));
+    assert_eq!(get.empty_statuses, vec!["204".to_string()]);
+    assert!(response(1).empty_statuses.is_empty());
+}
+
+#[test]
+fn default_response_is_the_catch_all_error() {
+    let spec = parse::from_yaml(DEFAULT_ERRORS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let errors = |name: &str| -> Vec<(String, IrType)> {
+        let op = ir
+            .operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .unwrap();
+        op.errors
+            .iter()
+            .map(|e| (e.status.clone(), e.response_type.clone()))
+            .collect()
+    };
+
+    assert_eq!(
+        errors("getPet"),
+        vec![
+            ("404".to_string(), IrType::Ref("NotFound".to_string())),
+            ("default".to_string(), IrType::Ref("ErrorModel".to_string())),
+        ]
+    );
+    assert_eq!(
+        errors("deletePet"),
+        vec![("default".to_string(), IrType::Ref("ErrorModel".to_string()))]
+    );
+
+    // With no 2xx, `default` is the success response, not an error.
+    let health = ir
+        .operations
+        .iter()
+        .find(|op| op.name.camel_case == "getHealth")
+        .unwrap();
+    assert!(health.errors.is_empty());
+    assert!(matches!(
+        &health.return_type,
+        IrReturnType::Standard(r) if r.response_type == IrType::Ref("Health".to_string())
+    ));
+}
+
+#[test]
+fn query_object_notation_comes_from_extension_or_default() {
+    let spec = parse::from_yaml(QUERY_OBJECTS).unwrap();
+    let notations = |ir: &IrSpec| -> Vec<(String, Option<QueryObjectNotation>)> {
+        ir.operations
+            .iter()
+            .flat_map(|op| &op.parameters)
+            .map(|p| (p.original_name.clone(), p.query_object))
+            .collect()
+    };
+
+    let ir = transform::transform(&spec).unwrap();
+    assert_eq!(
+        notations(&ir),
+        [
+            ("filter".to_string(), Some(QueryObjectNotation::Bracket)),
+            ("limit".to_string(), None),
+            ("where".to_string(), Some(QueryObjectNotation::Dot)),
+        ]
+    );
+
+    // The default only applies to `deepObject` parameters without the extension.
+    let options = transform::TransformOptions {
+        query_object: QueryObjectNotation::Dot,
+        ..Default::default()
+    };
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    assert_eq!(notations(&ir)[0].1, Some(QueryObjectNotation::Dot));
+    assert_eq!(notations(&ir)[2].1, Some(QueryObjectNotation::Dot));
+}
+
+#[test]
+fn query_array_style_comes_from_style_and_explode() {
+    let spec = parse::from_yaml(QUERY_ARRAYS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let styles: Vec<(&str, Option<QueryArrayStyle>)> = ir.operations[0]
+        .parameters
+        .iter()
+        .map(|p| (p.original_name.as_str(), p.query_array))
+        .collect();
+    assert_eq!(
+        styles,
+        [
+            ("ids", Some(QueryArrayStyle::Form)),
+            ("tags", Some(QueryArrayStyle::SpaceDelimited)),
+            ("status", Some(QueryArrayStyle::PipeDelimited)),
+            ("breed", None),
+            ("bornAfter", None),
+            ("vaccinated", None),
+        ]
+    );
+    assert_eq!(
+        QueryArrayStyle::from_spec(Some("pipeDelimited"), Some(true)),
+        None
+    );
+}
+
+#[test]
+fn patch_bodies_keep_their_media_type() {
+    let spec = parse::from_yaml(PATCH_BODIES).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let body = |name: &str| {
+        ir.operations
+            .iter()
+            .find(|op| op.name.camel_case == name)
+            .and_then(|op| op.request_body.clone())
+            .unwrap()
+    };
+
+    let replace = body("replacePet");
+    assert_eq!(replace.content_type, "application/json");
+    assert_eq!(replace.patch_format(), None);
+
+    let merge = body("updatePet");
+    assert_eq!(merge.content_type, "application/merge-patch+json");
+    assert_eq!(merge.patch_format(), Some(PatchFormat::MergePatch));
+    assert_eq!(merge.body_type, IrType::Ref("Pet".to_string()));
+    assert!(merge.is_json());
+
+    let patch = body("patchPet");
+    assert_eq!(patch.content_type, "application/json-patch+json");
+    assert_eq!(patch.patch_format(), Some(PatchFormat::JsonPatch));
+    assert!(patch.is_json());
+}
+
+#[test]
+fn json_media_types_are_preferred_over_other_request_bodies() {
+    let spec = parse::from_yaml(
+        r#"
+openapi: "3.1.0"
+info: { title: Pets, version: "1.0" }
+paths:
+  /pets/{petId}:
+    patch:
+      operationId: updatePet
+      parameters:
+        - { name: petId, in: path, required: true, schema: { type: string } }
+      requestBody:
+        content:
+          application/x-www-form-urlencoded:
+            schema: { type: object, properties: { name: { type: string } } }
+          application/merge-patch+json:
+            schema: { type: object, properties: { name: { type: string } } }
+      responses:
+        "204": { description: Updated }
+"#,
+    )
+    .unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let body = ir.operations[0].request_body.as_ref().unwrap();
+    assert_eq!(body.content_type, "application/merge-patch+json");
+}
+
+#[test]
+fn path_placeholders_without_parameters_fail_the_transform() {
+    let spec = parse::from_yaml(PATH_PARAMS).unwrap();
+    match transform::transform(&spec) {
+        Err(TransformError::UndeclaredPathParam { route, name }) => {
+            assert_eq!(route, "GET /stores/{storeId}/pets/{petId}");
+            assert_eq!(name, "storeId");
+        }
+        other => panic!("expected an undeclared path parameter, got {other:?}"),
+    }
+}
+
+#[test]
+fn path_placeholders_without_parameters_can_be_synthesized() {
+    let spec = parse::from_yaml(PATH_PARAMS).unwrap();
+    let options =
+        transform::TransformOptions::new().with_missing_path_params(MissingPathParams::Synthesize);
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+
+    let store_pet = ir
+        .operations
+        .iter()
+        .find(|op| op.name.camel_case == "getStorePet")
+        .unwrap();
+    let params: Vec<(&str, &IrType, bool)> = store_pet
+        .parameters
+        .iter()
+        .map(|p| (p.original_name.as_str(), &p.param_type, p.required))
+        .collect();
+    assert_eq!(
+        params,
+        [
+            ("storeId", &IrType::String, true),
+            ("petId", &IrType::Integer, true),
+        ]
+    );
+    assert!(ir.diagnostics.contains(
+        &"`GET /stores/{storeId}/pets/{petId}` declares no path parameter `storeId`; generating it as a required string".to_string()
+    ));
+}
+
+#[test]
+fn mismatched_and_unused_path_parameters_are_warned_about() {
+    let spec = parse::from_yaml(PATH_PARAMS).unwrap();
+    let options =
+        transform::TransformOptions::new().with_missing_path_params(MissingPathParams::Synthesize);
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+
+    // `pet_id` still fills `{petId}`: both normalize to the same name.
+    let get_pet = &ir.operations[0];
+    assert_eq!(get_pet.normalized_path, "/pets/{petId}");
+    assert_eq!(get_pet.parameters[0].name.camel_case, "petId");
+    assert!(ir.diagnostics.contains(
+        &"`GET /pets/{petId}` declares path parameter `pet_id` for `{petId}`; rename it `petId`"
+            .to_string()
+    ));
+    assert!(ir.diagnostics.contains(
+        &"`GET /pets/{petId}/toys` declares path parameter `ownerId`, which its path doesn't use"
+            .to_string()
+    ));
+    assert_eq!(
+        ir.diagnostics
+            .iter()
+            .filter(|d| d.contains("path parameter"))
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn field_defaults_are_kept_without_changing_required() {
+    let spec = parse::from_yaml(FIELD_DEFAULTS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let options = ir
+        .schemas
+        .iter()
+        .find_map(|s| match s {
+            IrSchema::Object(obj) if obj.name.pascal_case == "SearchOptions" => Some(obj),
+            _ => None,
+        })
+        .unwrap();
+    let field = |name: &str| {
+        options
+            .fields
+            .iter()
+            .find(|f| f.original_name == name)
+            .unwrap()
+    };
+
+    assert!(field("pageSize").required);
+    assert_eq!(field("pageSize").default, Some(serde_json::json!(50)));
+    assert!(!field("limit").required);
+    assert_eq!(field("limit").default, Some(serde_json::json!(20)));
+    assert_eq!(field("tags").default, Some(serde_json::json!(["public"])));
+    assert_eq!(
+        field("visibility").default,
+        Some(serde_json::json!("public"))
+    );
+    assert_eq!(field("cursor").default, None);
+}
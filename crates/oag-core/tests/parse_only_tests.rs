@@ -0,0 +1,39 @@
+//! Parsing with only the `parse` feature, as a build script depending on
+//! `oag-core` with `default-features = false, features = ["parse"]` would.
+//! CI runs this file that way:
+//! `cargo test -p oag-core --no-default-features --features parse --test parse_only_tests`.
+
+use oag_core::error::ParseError;
+use oag_core::parse;
+use oag_core::parse::ref_resolve::RefResolver;
+
+const PETSTORE: &str = include_str!("fixtures/petstore-3.2.yaml");
+
+#[test]
+fn parse_and_resolve_without_transform() {
+    let spec = parse::from_yaml(PETSTORE).expect("should parse petstore-3.2.yaml");
+    assert_eq!(spec.info.title, "Petstore");
+    assert!(spec.paths.contains_key("/pets"));
+
+    let resolved = RefResolver::new(&spec)
+        .resolve_spec(&spec)
+        .expect("refs should resolve");
+    assert_eq!(resolved.paths.len(), spec.paths.len());
+}
+
+#[test]
+fn json_specs_parse_without_transform() {
+    let spec = parse::from_json(
+        r#"{"openapi": "3.1.0", "info": {"title": "Pets", "version": "1.0"}, "paths": {}}"#,
+    )
+    .expect("should parse a JSON spec");
+    assert_eq!(spec.info.title, "Pets");
+}
+
+#[test]
+fn unsupported_versions_are_rejected_without_transform() {
+    let err =
+        parse::from_yaml("openapi: \"2.0\"\ninfo: { title: Old, version: \"1\" }\npaths: {}\n")
+            .unwrap_err();
+    assert!(matches!(err, ParseError::UnsupportedVersion(_)), "{err}");
+}
@@ -1,10 +1,21 @@
+use oag_core::error::ParseError;
 use oag_core::parse;
+use oag_core::parse::ref_resolve::RefResolver;
+use oag_core::parse::schema::{ExclusiveBound, Schema, SchemaOrRef};
+use oag_core::transform;
 
 const SSE_CHAT: &str = include_str!("fixtures/sse-chat.yaml");
 const PETSTORE: &str = include_str!("fixtures/petstore-3.2.yaml");
 const MIXED: &str = include_str!("fixtures/mixed-endpoints.yaml");
 const ANTHROPIC: &str = include_str!("fixtures/anthropic-messages.yaml");
 const PETSTORE_POLY: &str = include_str!("fixtures/petstore-polymorphic.yaml");
+const NUMERIC_BOUNDS: &str = include_str!("fixtures/numeric-bounds-3.0.yaml");
+const CONDITIONAL: &str = include_str!("fixtures/conditional-schemas.yaml");
+const JSON_QUERY: &str = include_str!("fixtures/json-query-params.yaml");
+const MERGE_KEYS: &str = include_str!("fixtures/merge-keys.yaml");
+const MERGE_KEYS_EXPANDED: &str = include_str!("fixtures/merge-keys-expanded.yaml");
+const TRAILING_SLASHES: &str = include_str!("fixtures/trailing-slashes.yaml");
+const REF_CHAINS: &str = include_str!("fixtures/ref-chains.yaml");
 
 #[test]
 fn parse_sse_chat_yaml() {
@@ -52,6 +63,8 @@ fn parse_mixed_31_yaml() {
     let spec = parse::from_yaml(MIXED).expect("should parse 3.1 spec");
     assert_eq!(spec.openapi, "3.1.0");
     assert_eq!(spec.paths.len(), 3);
+    assert_eq!(spec.webhooks.len(), 2);
+    assert!(spec.webhooks["itemCreated"].post.is_some());
 }
 
 #[test]
@@ -236,3 +249,291 @@ fn parse_anthropic_sse_item_schema() {
         _ => panic!("expected inline response"),
     }
 }
+
+#[test]
+fn parse_expands_merge_keys() {
+    let merged = parse::from_yaml(MERGE_KEYS).unwrap();
+    let expanded = parse::from_yaml(MERGE_KEYS_EXPANDED).unwrap();
+    assert_eq!(merged, expanded);
+
+    let merged = transform::transform(&merged).unwrap();
+    let expanded = transform::transform(&expanded).unwrap();
+    assert_eq!(
+        format!("{:?}", merged.schemas),
+        format!("{:?}", expanded.schemas)
+    );
+    assert_eq!(
+        format!("{:?}", merged.operations),
+        format!("{:?}", expanded.operations)
+    );
+}
+
+#[test]
+fn parse_keeps_trailing_slash_and_operationless_paths() {
+    let spec = parse::from_yaml(TRAILING_SLASHES).unwrap();
+    let paths: Vec<&str> = spec.paths.keys().map(String::as_str).collect();
+    assert_eq!(paths, ["/pets", "/pets/", "/pets/{petId}", "/pets/{id}/"]);
+
+    let item = &spec.paths["/pets/{petId}"];
+    assert_eq!(item.operations().count(), 0);
+    assert_eq!(item.parameters.len(), 1);
+}
+
+#[test]
+fn parse_rejects_scalar_merge_keys() {
+    let yaml = r#"
+openapi: 3.1.0
+info:
+  title: Test
+  version: "1.0"
+  <<: 1
+paths: {}
+"#;
+    match parse::from_yaml(yaml) {
+        Err(ParseError::InvalidMergeKey(message)) => assert_eq!(
+            message,
+            "`<<` at `info` must be a mapping or a list of mappings \
+             (`<<: *anchor`, `<<: [*a, *b]`), found a number"
+        ),
+        other => panic!("expected an invalid merge key error, got {other:?}"),
+    }
+}
+
+#[test]
+fn parse_names_where_an_invalid_merge_key_is() {
+    let yaml = r#"
+openapi: 3.1.0
+info: { title: Test, version: "1.0" }
+x-errors: &errors [400, 500]
+paths:
+  /pets:
+    get:
+      responses:
+        <<: [{ "404": { description: Missing } }, *errors]
+"#;
+    match parse::from_yaml(yaml) {
+        Err(ParseError::InvalidMergeKey(message)) => {
+            assert!(message.starts_with("`<<` at `paths./pets.get.responses`"));
+            assert!(message.ends_with("found a list"));
+        }
+        other => panic!("expected an invalid merge key error, got {other:?}"),
+    }
+}
+
+#[test]
+fn spec_round_trips_through_yaml_and_json() {
+    let spec = parse::from_yaml(ANTHROPIC).unwrap();
+
+    let yaml = parse::to_yaml(&spec).expect("should serialize to YAML");
+    assert_eq!(parse::from_yaml(&yaml).unwrap(), spec);
+
+    let json = parse::to_json(&spec).expect("should serialize to JSON");
+    assert_eq!(parse::from_json(&json).unwrap(), spec);
+}
+
+#[test]
+fn normalize_spec_sorts_paths_and_schemas() {
+    let spec = parse::from_yaml(PETSTORE_POLY).unwrap();
+    let normalized = parse::normalize_spec(&spec);
+
+    let paths: Vec<&String> = normalized.paths.keys().collect();
+    let mut sorted = paths.clone();
+    sorted.sort();
+    assert_eq!(paths, sorted);
+
+    let schemas: Vec<&String> = normalized
+        .components
+        .as_ref()
+        .unwrap()
+        .schemas
+        .keys()
+        .collect();
+    let mut sorted = schemas.clone();
+    sorted.sort();
+    assert_eq!(schemas, sorted);
+    assert_eq!(
+        normalized.components.as_ref().unwrap().schemas.len(),
+        spec.components.as_ref().unwrap().schemas.len()
+    );
+
+    // Normalizing is idempotent.
+    assert_eq!(parse::normalize_spec(&normalized), normalized);
+}
+
+#[test]
+fn normalize_spec_drops_empty_components() {
+    let spec = parse::from_yaml(
+        "openapi: 3.1.0\ninfo:\n  title: Empty\n  version: '1'\ncomponents: {}\npaths: {}\n",
+    )
+    .unwrap();
+    assert!(spec.components.is_some());
+    let normalized = parse::normalize_spec(&spec);
+    assert!(normalized.components.is_none());
+    assert!(!parse::to_yaml(&normalized).unwrap().contains("components"));
+}
+
+fn reading_property(spec: &parse::spec::OpenApiSpec, name: &str) -> Schema {
+    let reading = &spec.components.as_ref().unwrap().schemas["Reading"];
+    let SchemaOrRef::Schema(reading) = reading else {
+        panic!("expected inline Reading schema");
+    };
+    match &reading.properties[name] {
+        SchemaOrRef::Schema(s) => (**s).clone(),
+        _ => panic!("expected inline property"),
+    }
+}
+
+#[test]
+fn parse_exclusive_bounds_both_forms() {
+    let spec = parse::from_yaml(NUMERIC_BOUNDS).expect("should parse 3.0 boolean bounds");
+    assert_eq!(spec.openapi, "3.0.3");
+
+    let value = reading_property(&spec, "value");
+    assert_eq!(value.minimum, Some(0.0));
+    assert_eq!(value.exclusive_minimum, Some(ExclusiveBound::Bool(true)));
+    assert_eq!(value.exclusive_maximum, Some(ExclusiveBound::Bool(false)));
+
+    let ratio = reading_property(&spec, "ratio");
+    assert_eq!(ratio.exclusive_minimum, Some(ExclusiveBound::Number(0.0)));
+    assert_eq!(ratio.exclusive_maximum, Some(ExclusiveBound::Number(1.0)));
+}
+
+#[test]
+fn resolve_normalizes_exclusive_bounds_to_31() {
+    let spec = parse::from_yaml(NUMERIC_BOUNDS).unwrap();
+    let resolved = RefResolver::new(&spec).resolve_spec(&spec).unwrap();
+
+    let value = reading_property(&resolved, "value");
+    assert_eq!(value.minimum, None);
+    assert_eq!(value.exclusive_minimum, Some(ExclusiveBound::Number(0.0)));
+    assert_eq!(value.maximum, Some(100.0));
+    assert_eq!(value.exclusive_maximum, None);
+
+    let ratio = reading_property(&resolved, "ratio");
+    assert_eq!(ratio.exclusive_minimum, Some(ExclusiveBound::Number(0.0)));
+
+    let param = &resolved.paths["/readings"].get.as_ref().unwrap().parameters[0];
+    let parse::parameter::ParameterOrRef::Parameter(param) = param else {
+        panic!("expected inline parameter");
+    };
+    let Some(SchemaOrRef::Schema(schema)) = &param.schema else {
+        panic!("expected inline parameter schema");
+    };
+    assert_eq!(schema.exclusive_minimum, Some(ExclusiveBound::Number(0.0)));
+}
+
+#[test]
+fn resolve_follows_ref_chains_between_components() {
+    use parse::parameter::ParameterOrRef;
+    use parse::request_body::RequestBodyOrRef;
+    use parse::response::ResponseOrRef;
+
+    let spec = parse::from_yaml(REF_CHAINS).unwrap();
+    let resolved = RefResolver::new(&spec).resolve_spec(&spec).unwrap();
+    let item = &resolved.paths["/pets/{petId}"];
+
+    let ParameterOrRef::Parameter(param) = &item.parameters[0] else {
+        panic!("expected the parameter to resolve");
+    };
+    assert_eq!(param.name, "petId");
+
+    let get = item.get.as_ref().unwrap();
+    let descriptions: Vec<&str> = get
+        .responses
+        .values()
+        .map(|response| match response {
+            ResponseOrRef::Response(r) => r.description.as_str(),
+            ResponseOrRef::Ref { ref_path } => panic!("{ref_path} left unresolved"),
+        })
+        .collect();
+    // `PetFound` is one hop from `PetResponse`, `NotFound` two from `Error`.
+    assert_eq!(descriptions, ["A pet", "An error"]);
+
+    let put = item.put.as_ref().unwrap();
+    let Some(RequestBodyOrRef::RequestBody(body)) = &put.request_body else {
+        panic!("expected the request body to resolve");
+    };
+    assert!(body.required);
+}
+
+#[test]
+fn resolve_rejects_circular_component_refs() {
+    let spec = parse::from_yaml(
+        r##"
+openapi: 3.1.0
+info: { title: Loop, version: "1" }
+paths:
+  /a:
+    get:
+      operationId: getA
+      responses:
+        "200": { $ref: "#/components/responses/A" }
+components:
+  responses:
+    A: { $ref: "#/components/responses/B" }
+    B: { $ref: "#/components/responses/A" }
+"##,
+    )
+    .unwrap();
+    let err = RefResolver::new(&spec).resolve_spec(&spec).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "circular reference detected: #/components/responses/A -> #/components/responses/B -> #/components/responses/A"
+    );
+}
+
+#[test]
+fn parse_conditional_keywords() {
+    let spec = parse::from_yaml(CONDITIONAL).expect("should parse if/then/else");
+    let SchemaOrRef::Schema(shipment) = &spec.components.as_ref().unwrap().schemas["Shipment"]
+    else {
+        panic!("expected inline Shipment schema");
+    };
+    assert!(shipment.if_schema.is_some());
+    assert!(shipment.then_schema.is_some());
+    assert!(shipment.else_schema.is_some());
+    assert!(shipment.not.is_none());
+    assert_eq!(shipment.unsupported_keywords(), ["if", "then", "else"]);
+
+    // Round-trips under the JSON Schema keyword names.
+    let yaml = parse::to_yaml(&spec).unwrap();
+    assert!(yaml.contains("if:") && yaml.contains("then:") && yaml.contains("else:"));
+}
+
+#[test]
+fn parse_parameter_content() {
+    let spec = parse::from_yaml(JSON_QUERY).unwrap();
+    let op = spec.paths["/items"].get.as_ref().unwrap();
+    let parse::parameter::ParameterOrRef::Parameter(filter) = &op.parameters[0] else {
+        panic!("expected inline parameter");
+    };
+    assert!(filter.schema.is_none());
+    let json = filter
+        .content
+        .get("application/json")
+        .expect("filter should declare JSON content");
+    assert!(matches!(
+        &json.schema,
+        Some(SchemaOrRef::Ref { ref_path }) if ref_path == "#/components/schemas/ItemFilter"
+    ));
+}
+
+#[test]
+fn parse_from_loaded_documents() {
+    let value: serde_yaml_ng::Value = serde_yaml_ng::from_str(MERGE_KEYS).unwrap();
+    let from_value = parse::from_yaml_value(value).unwrap();
+    let from_text = parse::from_yaml(MERGE_KEYS).unwrap();
+    assert_eq!(
+        parse::to_json(&from_value).unwrap(),
+        parse::to_json(&from_text).unwrap()
+    );
+
+    let json: serde_json::Value =
+        serde_json::from_str(&parse::to_json(&from_text).unwrap()).unwrap();
+    let from_json = parse::from_json_value(json).unwrap();
+    assert_eq!(from_json.info.title, from_text.info.title);
+
+    let not_openapi =
+        serde_json::json!({ "openapi": "2.0", "info": { "title": "t", "version": "1" } });
+    assert!(parse::from_json_value(not_openapi).is_err());
+}
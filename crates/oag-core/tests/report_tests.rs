@@ -0,0 +1,23 @@
+use oag_core::report::{Feature, feature_report};
+use oag_core::{parse, transform};
+
+const ANTHROPIC: &str = include_str!("fixtures/anthropic-messages.yaml");
+const CONDITIONAL: &str = include_str!("fixtures/conditional-schemas.yaml");
+
+#[test]
+fn anthropic_feature_report() {
+    let spec = parse::from_yaml(ANTHROPIC).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    insta::assert_snapshot!(feature_report(&spec, &ir.diagnostics).to_string());
+}
+
+#[test]
+fn conditional_keywords_are_reported_once() {
+    let spec = parse::from_yaml(CONDITIONAL).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    assert!(!ir.diagnostics.is_empty());
+    let report = feature_report(&spec, &ir.diagnostics);
+    let [_, approximated, _] = report.counts(Feature::ConditionalKeywords);
+    assert!(approximated > 0);
+    assert_eq!(report.counts(Feature::Diagnostics), [0, 0, 0]);
+}
@@ -1,4 +1,88 @@
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::ir::IrSpec;
+use oag_core::transform::normalize_name;
+
+use super::python_str;
+
 /// Emit `main.py` — FastAPI app entry point.
-pub fn emit_app() -> String {
-    include_str!("../../templates/app.py.j2").to_string()
+///
+/// With `webhooks`, the `/webhooks` router from `webhooks.py` is mounted too.
+/// With `factory`, the app is built by `app.create_app` instead.
+pub fn emit_app(webhooks: bool, factory: bool) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template("app.py.j2", include_str!("../../templates/app.py.j2"))
+        .expect("template should be valid");
+    let tmpl = env.get_template("app.py.j2").unwrap();
+    tmpl.render(context! { webhooks => webhooks, factory => factory })
+        .expect("render should succeed")
+}
+
+/// Emit `app.py` — a `Settings` class and the `create_app(settings)` factory
+/// that mounts the routers under `settings.base_path` — plus a `__main__.py`
+/// serving it with uvicorn (`app_factory: true`).
+///
+/// `Settings` gets an optional `{scheme}_secret` for every security scheme,
+/// and the app's tags metadata lists each module with its tag description.
+pub fn emit_app_factory(ir: &IrSpec) -> Vec<GeneratedFile> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_filter("comment", super::comment_filter);
+    env.set_keep_trailing_newline(true);
+    env.add_template(
+        "app_factory.py.j2",
+        include_str!("../../templates/app_factory.py.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("app_factory.py.j2").unwrap();
+
+    let secrets: Vec<minijinja::Value> = ir
+        .raw_spec
+        .as_ref()
+        .and_then(|spec| spec.components.as_ref())
+        .into_iter()
+        .flat_map(|components| &components.security_schemes)
+        .map(|(name, scheme)| {
+            let mut comment = format!("Secret for the `{name}` security scheme.");
+            if let Some(description) = &scheme.description {
+                comment = format!("{comment}\n\n{description}");
+            }
+            context! {
+                name => format!("{}_secret", normalize_name(name).snake_case),
+                comment => comment,
+            }
+        })
+        .collect();
+    let tags: Vec<minijinja::Value> = ir
+        .modules
+        .iter()
+        .map(|module| {
+            context! {
+                name => python_str(&module.name.original),
+                description => module.description.as_deref().map(python_str),
+            }
+        })
+        .collect();
+
+    let app = tmpl
+        .render(context! {
+            title => python_str(&ir.info.title),
+            version => python_str(&ir.info.version),
+            webhooks => !ir.webhooks.is_empty(),
+            secrets => secrets,
+            tags => tags,
+        })
+        .expect("render should succeed");
+
+    vec![
+        GeneratedFile {
+            path: "app.py".to_string(),
+            content: app,
+        },
+        GeneratedFile {
+            path: "__main__.py".to_string(),
+            content: include_str!("../../templates/__main__.py.j2").to_string(),
+        },
+    ]
 }
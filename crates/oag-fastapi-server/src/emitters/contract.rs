@@ -0,0 +1,70 @@
+use oag_core::GeneratedFile;
+use oag_core::contract::{contract_status, is_contract_operation, sample_value};
+use oag_core::ir::{IrReturnType, IrSpec};
+use serde_json::json;
+
+/// Emit the server half of the contract suite: `contract/mocks.json`, the
+/// response each covered route answers with, `contract/server.py`, the app
+/// with its stubs answering from it, and `contract/run.py`, which starts the
+/// server and runs the node client's `contract/` suite against it.
+pub fn emit_contract(ir: &IrSpec) -> Vec<GeneratedFile> {
+    let mocks: Vec<serde_json::Value> = ir
+        .operations
+        .iter()
+        .filter(|op| is_contract_operation(op))
+        .map(|op| {
+            let mut mock = json!({
+                "method": op.method.as_str(),
+                "path": op.raw_path,
+                "status": contract_status(op),
+            });
+            if let IrReturnType::Standard(resp) = &op.return_type {
+                mock["body"] = sample_value(ir, &resp.response_type);
+            }
+            mock
+        })
+        .collect();
+    let mut mocks = serde_json::to_string_pretty(&mocks).expect("JSON values always serialize");
+    mocks.push('\n');
+
+    vec![
+        GeneratedFile {
+            path: "contract/mocks.json".to_string(),
+            content: mocks,
+        },
+        GeneratedFile {
+            path: "contract/server.py".to_string(),
+            content: include_str!("../../templates/contract_server.py.j2").to_string(),
+        },
+        GeneratedFile {
+            path: "contract/run.py".to_string(),
+            content: include_str!("../../templates/contract_run.py.j2").to_string(),
+        },
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mocks_cover_json_operations() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let files = emit_contract(&ir);
+        let mocks: Vec<serde_json::Value> = serde_json::from_str(&files[0].content).unwrap();
+
+        let delete = mocks.iter().find(|m| m["method"] == "DELETE").unwrap();
+        assert_eq!(delete["status"], 204);
+        assert!(delete.get("body").is_none());
+        let get = mocks
+            .iter()
+            .find(|m| m["method"] == "GET" && m["path"] == "/pets/{petId}")
+            .unwrap();
+        assert_eq!(get["status"], 200);
+        assert!(get["body"].is_object());
+    }
+}
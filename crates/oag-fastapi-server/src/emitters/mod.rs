@@ -1,6 +1,57 @@
 pub mod app;
+pub mod contract;
+pub mod db;
 pub mod models;
 pub mod routes;
 pub mod scaffold;
 pub mod sse;
 pub mod tests;
+pub mod webhooks;
+
+/// A double-quoted Python string literal (JSON escapes are valid Python).
+fn python_str(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+/// A Python literal for a scalar JSON value; `None` for arrays and objects.
+fn python_scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(python_str(s)),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(true) => Some("True".to_string()),
+        serde_json::Value::Bool(false) => Some("False".to_string()),
+        _ => None,
+    }
+}
+
+/// A Python literal for any JSON value, e.g. a request body example.
+fn python_literal(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => "None".to_string(),
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(python_literal).collect();
+            format!("[{}]", items.join(", "))
+        }
+        serde_json::Value::Object(members) => {
+            let members: Vec<String> = members
+                .iter()
+                .map(|(key, value)| format!("{}: {}", python_str(key), python_literal(value)))
+                .collect();
+            format!("{{{}}}", members.join(", "))
+        }
+        scalar => python_scalar(scalar).expect("every other value is a scalar"),
+    }
+}
+
+/// Template filter rendering a description as a docstring, quotes included:
+/// `{{ op.doc | docstring("    ") }}` with the docstring's indentation and an
+/// optional URL to link to.
+fn docstring_filter(value: String, indent: Option<String>, see: Option<String>) -> String {
+    oag_core::docs::docstring(&value, indent.as_deref().unwrap_or(""), see.as_deref())
+}
+
+/// Template filter rendering a description as `#` comments:
+/// `{{ field.description | comment("    ") }}`.
+fn comment_filter(value: String, indent: Option<String>) -> String {
+    oag_core::docs::line_comment(&value, indent.as_deref().unwrap_or(""))
+}
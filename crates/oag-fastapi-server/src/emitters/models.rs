@@ -1,33 +1,369 @@
+use std::collections::{HashMap, HashSet};
+
 use minijinja::{Environment, context};
-use oag_core::ir::{IrObjectSchema, IrSchema, IrSpec};
+use oag_core::ir::{
+    IrField, IrObjectSchema, IrReturnType, IrSchema, IrSpec, IrType, IrUnionSchema, UnionKind,
+    schema_order, schema_refs, type_refs,
+};
+use oag_core::transform::name_normalizer::{PYTHON_RESERVED_WORDS, escape_reserved};
+use oag_core::transform::normalize_name;
+use oag_core::transform::passes::flatten_intersections;
 
-use crate::type_mapper::{ir_type_to_python, ir_type_to_python_field};
+use super::python_literal;
+use super::scaffold::{AllOfStyle, ResponseStyle};
+use crate::type_mapper::{
+    ir_type_to_python, ir_type_to_python_field, ir_type_to_python_forward, uses_literal,
+};
 
 /// Emit `models.py` — Pydantic v2 BaseModel classes from IrSchema.
-pub fn emit_models(ir: &IrSpec) -> String {
+///
+/// With [`ResponseStyle::TypedDict`], object schemas that no request body or
+/// parameter reaches (and that don't allow additional properties) become
+/// `TypedDict`s instead.
+///
+/// `allOf` schemas over object schemas are flattened into one model. With
+/// [`AllOfStyle::Inherit`] they subclass the schemas they reference instead,
+/// unless that is cyclic or the parts disagree on a field; those stay flat,
+/// with a comment saying why.
+///
+/// Schemas are emitted after the schemas they reference (see [`schema_order`]).
+/// In a reference cycle, aliases and unions quote the names defined after them.
+pub fn emit_models(ir: &IrSpec, response_style: ResponseStyle, allof_style: AllOfStyle) -> String {
     let mut env = Environment::new();
+    env.add_filter("comment", super::comment_filter);
     env.add_template("models.py.j2", include_str!("../../templates/models.py.j2"))
         .expect("template should be valid");
     let tmpl = env.get_template("models.py.j2").unwrap();
 
-    let schemas: Vec<_> = ir.schemas.iter().map(schema_to_ctx).collect();
+    let mut flat = ir.clone();
+    flatten_intersections(&mut flat);
+    let subclasses: HashMap<&str, Result<Subclass, String>> = match allof_style {
+        AllOfStyle::Flatten => HashMap::new(),
+        AllOfStyle::Inherit => ir
+            .schemas
+            .iter()
+            .filter_map(|schema| match schema {
+                IrSchema::Alias(alias) => match &alias.target {
+                    IrType::Intersection(parts) => Some((
+                        alias.name.pascal_case.as_str(),
+                        subclass(ir, &flat, &alias.name.pascal_case, parts),
+                    )),
+                    _ => None,
+                },
+                _ => None,
+            })
+            .collect(),
+    };
+
+    let inputs = match response_style {
+        ResponseStyle::BaseModel => None,
+        ResponseStyle::TypedDict => {
+            let mut inputs = input_schemas(&flat);
+            // A `BaseModel` can't subclass a `TypedDict`.
+            inputs.extend(
+                subclasses
+                    .values()
+                    .flatten()
+                    .flat_map(|sub| sub.bases.iter().map(String::as_str)),
+            );
+            Some(inputs)
+        }
+    };
+    let schema_ctx = |schema: &IrSchema, forward: &HashSet<&str>| {
+        let name = schema.name().pascal_case.as_str();
+        match subclasses.get(name) {
+            Some(Ok(sub)) => {
+                let obj = IrObjectSchema {
+                    name: schema.name().clone(),
+                    description: match schema {
+                        IrSchema::Object(obj) => obj.description.clone(),
+                        IrSchema::Alias(alias) => alias.description.clone(),
+                        _ => None,
+                    },
+                    fields: sub.fields.clone(),
+                    additional_properties: None,
+                    unsupported_keywords: vec![],
+                    example: None,
+                };
+                object_to_ctx(&obj, &sub.bases)
+            }
+            Some(Err(reason)) => {
+                log::warn!("`{name}` is flattened instead of inherited: {reason}");
+                context! {
+                    note => format!("Flattened instead of inherited: {reason}."),
+                    ..schema_to_ctx(schema, false, forward)
+                }
+            }
+            None => {
+                let typed_dict = inputs.as_ref().is_some_and(|inputs| !inputs.contains(name));
+                schema_to_ctx(schema, typed_dict, forward)
+            }
+        }
+    };
+
+    // `ir` still has the `allOf` refs, so base classes count as dependencies.
+    let mut schemas = Vec::with_capacity(flat.schemas.len());
+    let mut has_union = false;
+    let mut has_annotated = false;
+    for group in schema_order(ir) {
+        let members = group_order(&flat, &subclasses, &group.schemas);
+        let mut forward: HashSet<&str> = match group.cyclic {
+            true => members
+                .iter()
+                .map(|&i| flat.schemas[i].name().pascal_case.as_str())
+                .collect(),
+            false => HashSet::new(),
+        };
+        for i in members {
+            let schema = &flat.schemas[i];
+            let ctx = schema_ctx(schema, &forward);
+            has_annotated |= matches!(schema, IrSchema::Union(u) if union_mode(u).is_some());
+            has_union |= ctx
+                .get_attr("target")
+                .ok()
+                .and_then(|t| t.as_str().map(|t| t.contains("Union[")))
+                .unwrap_or(false);
+            schemas.push(ctx);
+            forward.remove(schema.name().pascal_case.as_str());
+        }
+    }
+    let has_typed_dicts = schemas
+        .iter()
+        .any(|s| s.get_attr("kind").ok().as_ref().and_then(|k| k.as_str()) == Some("typed_dict"));
+    let has_literal = flat.schemas.iter().any(|schema| match schema {
+        IrSchema::Object(obj) => obj.fields.iter().any(|f| uses_literal(&f.field_type)),
+        IrSchema::Alias(alias) => uses_literal(&alias.target),
+        IrSchema::Union(union) => union.variants.iter().any(uses_literal),
+        IrSchema::Enum(_) => false,
+    });
+    let has_unset_fields = schemas.iter().any(|s| {
+        s.get_attr("fields")
+            .ok()
+            .and_then(|f| f.try_iter().ok())
+            .is_some_and(|mut fields| {
+                fields.any(|f| f.get_attr("unset_none").is_ok_and(|u| u.is_true()))
+            })
+    });
+    let sse_event_types = collect_sse_event_types(ir);
 
     tmpl.render(context! {
         schemas => schemas,
+        sse_event_types => sse_event_types,
+        has_typed_dicts => has_typed_dicts,
+        has_literal => has_literal,
+        has_unset_fields => has_unset_fields,
+        has_union => has_union,
+        has_annotated => has_annotated,
     })
     .expect("render should succeed")
 }
 
-fn schema_to_ctx(schema: &IrSchema) -> minijinja::Value {
+/// Names of the schemas a request body or parameter reaches, directly or
+/// through other schemas. These stay `BaseModel`s so FastAPI validates them.
+fn input_schemas(ir: &IrSpec) -> HashSet<&str> {
+    let mut stack = Vec::new();
+    for op in ir.operations.iter().chain(&ir.webhooks) {
+        if let Some(body) = &op.request_body {
+            type_refs(&body.body_type, &mut stack);
+        }
+        for param in &op.parameters {
+            type_refs(&param.param_type, &mut stack);
+        }
+    }
+
+    let mut inputs = HashSet::new();
+    while let Some(name) = stack.pop() {
+        if inputs.insert(name)
+            && let Some(schema) = ir.schemas.iter().find(|s| s.name().pascal_case == name)
+        {
+            schema_refs(schema, &mut stack);
+        }
+    }
+    inputs
+}
+
+/// Type aliases for SSE event unions synthesized by the transform (e.g.
+/// `CreateMessageStreamEvent`) that have no component schema of their own.
+fn collect_sse_event_types(ir: &IrSpec) -> Vec<minijinja::Value> {
+    let schema_names: HashSet<&str> = ir
+        .schemas
+        .iter()
+        .map(|s| s.name().pascal_case.as_str())
+        .collect();
+    let mut seen = HashSet::new();
+    let mut event_types = Vec::new();
+    for op in &ir.operations {
+        if let IrReturnType::Sse(sse) = &op.return_type
+            && let Some(ref event_name) = sse.event_type_name
+        {
+            if schema_names.contains(event_name.as_str()) || !seen.insert(event_name.clone()) {
+                continue;
+            }
+            let target = if sse.variants.is_empty() {
+                ir_type_to_python(&sse.event_type)
+            } else {
+                let variants: Vec<String> = sse.variants.iter().map(ir_type_to_python).collect();
+                variants.join(" | ")
+            };
+            event_types.push(context! {
+                name => event_name.clone(),
+                target => target,
+            });
+        }
+    }
+    event_types
+}
+
+/// An `allOf` schema as a subclass: the schemas it references are the bases
+/// and its inline objects' fields are its own.
+struct Subclass {
+    bases: Vec<String>,
+    fields: Vec<IrField>,
+}
+
+/// How the alias `name` of an intersection of `parts` subclasses its bases, or
+/// why it can't. `flat` is the IR with intersections flattened, so a base that
+/// is itself an `allOf` is seen with all of its fields.
+fn subclass(ir: &IrSpec, flat: &IrSpec, name: &str, parts: &[IrType]) -> Result<Subclass, String> {
+    let mut bases = Vec::new();
+    let mut fields = Vec::new();
+    for part in parts {
+        match part {
+            IrType::Ref(base) => bases.push(base.clone()),
+            IrType::Object(inline) => {
+                fields.extend(
+                    inline
+                        .iter()
+                        .map(|(field_name, field_type, required)| IrField {
+                            name: normalize_name(field_name),
+                            original_name: field_name.clone(),
+                            field_type: field_type.clone(),
+                            required: *required,
+                            description: None,
+                            read_only: false,
+                            write_only: false,
+                            deprecated: false,
+                            default: None,
+                            number_hints: Default::default(),
+                        }),
+                );
+            }
+            other => return Err(format!("`{}` is not an object", ir_type_to_python(other))),
+        }
+    }
+    if bases.is_empty() {
+        return Err("it extends no schema".to_string());
+    }
+    if extends_itself(ir, name, &bases) {
+        return Err("it extends itself".to_string());
+    }
+
+    let mut seen: Vec<&IrField> = Vec::new();
+    let base_fields = bases.iter().map(|base| {
+        match flat.schemas.iter().find(|s| s.name().pascal_case == *base) {
+            Some(IrSchema::Object(obj)) => Ok(&obj.fields),
+            _ => Err(format!("`{base}` is not an object schema")),
+        }
+    });
+    for part_fields in base_fields.chain(std::iter::once(Ok(&fields))) {
+        for field in part_fields? {
+            if let Some(other) = seen.iter().find(|f| f.original_name == field.original_name) {
+                if other.field_type != field.field_type || other.required != field.required {
+                    return Err(format!(
+                        "its parts disagree on field `{}`",
+                        field.original_name
+                    ));
+                }
+            } else {
+                seen.push(field);
+            }
+        }
+    }
+    Ok(Subclass { bases, fields })
+}
+
+/// Whether following `allOf` refs from `bases` leads back to `name`.
+fn extends_itself(ir: &IrSpec, name: &str, bases: &[String]) -> bool {
+    let mut stack: Vec<&str> = bases.iter().map(String::as_str).collect();
+    let mut seen = HashSet::new();
+    while let Some(base) = stack.pop() {
+        if base == name {
+            return true;
+        }
+        if !seen.insert(base) {
+            continue;
+        }
+        if let Some(IrSchema::Alias(alias)) =
+            ir.schemas.iter().find(|s| s.name().pascal_case == base)
+            && let IrType::Intersection(parts) = &alias.target
+        {
+            stack.extend(parts.iter().filter_map(|part| match part {
+                IrType::Ref(r) => Some(r.as_str()),
+                _ => None,
+            }));
+        }
+    }
+    false
+}
+
+/// The schemas of a group from [`schema_order`] in the order they're emitted:
+/// classes first, with base classes before their subclasses, then aliases and
+/// unions. Class annotations are evaluated lazily; the rest aren't.
+fn group_order(
+    flat: &IrSpec,
+    subclasses: &HashMap<&str, Result<Subclass, String>>,
+    group: &[usize],
+) -> Vec<usize> {
+    fn visit(
+        i: usize,
+        flat: &IrSpec,
+        subclasses: &HashMap<&str, Result<Subclass, String>>,
+        group: &[usize],
+        order: &mut Vec<usize>,
+    ) {
+        if order.contains(&i) {
+            return;
+        }
+        if let Some(Ok(sub)) = subclasses.get(flat.schemas[i].name().pascal_case.as_str()) {
+            for base in &sub.bases {
+                if let Some(&b) = group
+                    .iter()
+                    .find(|&&b| flat.schemas[b].name().pascal_case == *base)
+                {
+                    visit(b, flat, subclasses, group, order);
+                }
+            }
+        }
+        order.push(i);
+    }
+
+    let (classes, rest): (Vec<usize>, Vec<usize>) = group
+        .iter()
+        .partition(|&&i| !matches!(flat.schemas[i], IrSchema::Alias(_) | IrSchema::Union(_)));
+    let mut order = Vec::with_capacity(group.len());
+    for i in classes {
+        visit(i, flat, subclasses, group, &mut order);
+    }
+    order.extend(rest);
+    order
+}
+
+/// `forward` holds the schemas not defined yet where this one is, which alias
+/// and union targets have to quote.
+fn schema_to_ctx(schema: &IrSchema, typed_dict: bool, forward: &HashSet<&str>) -> minijinja::Value {
     match schema {
-        IrSchema::Object(obj) => object_to_ctx(obj),
+        IrSchema::Object(obj) if typed_dict && obj.additional_properties.is_none() => {
+            typed_dict_to_ctx(obj)
+        }
+        IrSchema::Object(obj) => object_to_ctx(obj, &["BaseModel".to_string()]),
         IrSchema::Enum(e) => {
             let variants: Vec<minijinja::Value> = e
                 .variants
                 .iter()
-                .map(|v| {
+                .zip(enum_member_names(&e.variants))
+                .map(|(v, name)| {
                     context! {
-                        name => heck::AsUpperCamelCase(v).to_string(),
+                        name => name,
                         value => v.clone(),
                     }
                 })
@@ -44,33 +380,97 @@ fn schema_to_ctx(schema: &IrSchema) -> minijinja::Value {
                 kind => "alias",
                 name => a.name.pascal_case.clone(),
                 description => a.description.clone(),
-                target => ir_type_to_python(&a.target),
+                target => ir_type_to_python_forward(&a.target, forward),
+            }
+        }
+        IrSchema::Union(u)
+            if u.variants
+                .iter()
+                .any(|v| matches!(v, IrType::Ref(name) if forward.contains(name.as_str()))) =>
+        {
+            let target = ir_type_to_python_forward(&IrType::Union(u.variants.clone()), forward);
+            context! {
+                kind => "alias",
+                name => u.name.pascal_case.clone(),
+                description => u.description.clone(),
+                target => match union_mode(u) {
+                    Some(mode) => format!("Annotated[{target}, Field(union_mode=\"{mode}\")]"),
+                    None => target,
+                },
             }
         }
         IrSchema::Union(u) => {
-            let variants: Vec<String> = u.variants.iter().map(ir_type_to_python).collect();
+            let variants: Vec<String> = u
+                .variants
+                .iter()
+                .map(|v| ir_type_to_python_forward(v, forward))
+                .collect();
             context! {
                 kind => "union",
                 name => u.name.pascal_case.clone(),
                 description => u.description.clone(),
                 variants => variants,
+                union_mode => union_mode(u),
             }
         }
     }
 }
 
-fn object_to_ctx(obj: &IrObjectSchema) -> minijinja::Value {
+/// The Pydantic `union_mode` for `union`: `anyOf` takes the first variant
+/// that validates, left to right; `oneOf` keeps the default smart mode, which
+/// picks the best match.
+fn union_mode(union: &IrUnionSchema) -> Option<&'static str> {
+    (union.kind == UnionKind::AnyOf).then_some("left_to_right")
+}
+
+/// A Pydantic model subclassing `bases`.
+fn object_to_ctx(obj: &IrObjectSchema, bases: &[String]) -> minijinja::Value {
     let fields: Vec<minijinja::Value> = obj
         .fields
         .iter()
         .map(|f| {
+            let name = escape_reserved(&f.name.snake_case, PYTHON_RESERVED_WORDS);
+            // The annotation alone, for fields whose default goes in `Field(...)`.
+            let annotation = ir_type_to_python(&f.field_type);
+            let default = field_default(f);
+            // A default for a model or enum is written as JSON; validating it
+            // builds the instance instead of keeping the raw value.
+            let validate_default = default.is_some() && references_schema(&f.field_type);
+            // `Field(...)` arguments, for fields that need an alias, are
+            // deprecated, are write-only and so left out of responses, or
+            // have a default to validate.
+            let mut field_args = Vec::new();
+            if !f.required {
+                let unset = match f.field_type.is_nullable() {
+                    true => "None",
+                    false => "UNSET",
+                };
+                field_args.push(format!("default={}", default.as_deref().unwrap_or(unset)));
+            }
+            if validate_default {
+                field_args.push("validate_default=True".to_string());
+            }
+            if name != f.original_name {
+                field_args.push(format!("alias=\"{}\"", f.original_name));
+            }
+            if f.deprecated {
+                field_args.push("deprecated=True".to_string());
+            }
+            if f.write_only {
+                field_args.push("exclude=True".to_string());
+            }
+            let needs_field =
+                name != f.original_name || f.deprecated || f.write_only || validate_default;
             context! {
-                name => f.name.snake_case.clone(),
+                annotation => annotation,
+                field_args => needs_field.then(|| field_args.join(", ")),
+                name => name,
                 original_name => f.original_name.clone(),
-                type_str => ir_type_to_python_field(&f.field_type, f.required),
+                type_str => ir_type_to_python_field(&f.field_type, f.required, default.as_deref()),
                 required => f.required,
                 description => f.description.clone(),
-                needs_alias => f.name.snake_case != f.original_name,
+                write_only => f.write_only,
+                unset_none => !f.required && default.is_none() && !f.field_type.is_nullable(),
             }
         })
         .collect();
@@ -80,8 +480,99 @@ fn object_to_ctx(obj: &IrObjectSchema) -> minijinja::Value {
     context! {
         kind => "object",
         name => obj.name.pascal_case.clone(),
+        bases => bases,
         description => obj.description.clone(),
         fields => fields,
         has_additional_properties => has_additional_properties,
     }
 }
+
+/// An optional field's spec `default` as a Python literal. A `null` default
+/// is the same as none.
+fn field_default(field: &IrField) -> Option<String> {
+    field
+        .default
+        .as_ref()
+        .filter(|default| !field.required && !default.is_null())
+        .map(python_literal)
+}
+
+/// Whether `ty` refers to a generated model or enum anywhere inside it.
+fn references_schema(ty: &IrType) -> bool {
+    match ty {
+        IrType::Ref(_) => true,
+        IrType::Array(inner) | IrType::Map(inner, _) => references_schema(inner),
+        IrType::Object(fields) => fields.iter().any(|(_, ty, _)| references_schema(ty)),
+        IrType::Union(variants) | IrType::Intersection(variants) => {
+            variants.iter().any(references_schema)
+        }
+        _ => false,
+    }
+}
+
+/// A response-only object as a `TypedDict` keyed by the wire names, without
+/// its `writeOnly` fields. Keys that aren't Python identifiers need the
+/// functional `TypedDict("Name", {...})` form.
+fn typed_dict_to_ctx(obj: &IrObjectSchema) -> minijinja::Value {
+    let returned: Vec<&IrField> = obj.fields.iter().filter(|f| !f.write_only).collect();
+    let fields: Vec<minijinja::Value> = returned
+        .iter()
+        .map(|f| {
+            let type_str = ir_type_to_python(&f.field_type);
+            // The functional form's values are evaluated, so forward refs go in quotes.
+            let type_literal = format!("'{}'", type_str.replace('\\', "\\\\").replace('\'', "\\'"));
+            context! {
+                key => f.original_name.clone(),
+                type_literal => type_literal,
+                type_str => type_str,
+                required => f.required,
+                description => f.description.clone(),
+                deprecated => f.deprecated,
+            }
+        })
+        .collect();
+
+    context! {
+        kind => "typed_dict",
+        name => obj.name.pascal_case.clone(),
+        description => obj.description.clone(),
+        fields => fields,
+        functional => !returned.iter().all(|f| is_python_identifier(&f.original_name)),
+        total => returned.iter().all(|f| f.required),
+    }
+}
+
+/// Python member names for enum values: UpperCamelCase, `EMPTY` for `""`,
+/// prefixed with `V` when they'd start with a digit and escaped when they're a
+/// keyword. Values that still name the same member (`a` and `A`) are numbered
+/// in spec order: `A`, `A_2`.
+fn enum_member_names(variants: &[String]) -> Vec<String> {
+    let mut used = HashSet::new();
+    variants
+        .iter()
+        .map(|value| {
+            let base = match heck::AsUpperCamelCase(value).to_string() {
+                _ if value.is_empty() => "EMPTY".to_string(),
+                name if name.is_empty() => "VALUE".to_string(),
+                name if name.starts_with(|c: char| c.is_ascii_digit()) => format!("V{name}"),
+                name => escape_reserved(&name, PYTHON_RESERVED_WORDS),
+            };
+            let mut name = base.clone();
+            let mut n = 2;
+            while !used.insert(name.clone()) {
+                name = format!("{base}_{n}");
+                n += 1;
+            }
+            name
+        })
+        .collect()
+}
+
+fn is_python_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+        && !PYTHON_RESERVED_WORDS.contains(&name)
+}
@@ -1,17 +1,19 @@
 use minijinja::{Environment, context};
-use oag_core::ir::{HttpMethod, IrOperation, IrParameterLocation, IrReturnType, IrSpec, IrType};
+use oag_core::ir::{
+    HttpMethod, IrOperation, IrParameter, IrParameterLocation, IrReturnType, IrSpec, IrType,
+};
+use oag_core::transform::name_normalizer::{PYTHON_RESERVED_WORDS, escape_reserved};
 
-use crate::type_mapper::ir_type_to_python;
+use crate::type_mapper::{ir_type_to_python, uses_literal};
 
-/// Escape triple-quote sequences that would prematurely close Python docstrings.
-fn escape_docstring(value: String) -> String {
-    value.replace("\"\"\"", "\\\"\\\"\\\"")
-}
+use super::python_scalar;
 
 /// Emit `routes.py` — FastAPI router with stub endpoints.
-pub fn emit_routes(ir: &IrSpec) -> String {
+///
+/// With `db`, every handler receives a `db: Session = Depends(get_db)` parameter.
+pub fn emit_routes(ir: &IrSpec, db: bool) -> String {
     let mut env = Environment::new();
-    env.add_filter("escape_docstring", escape_docstring);
+    env.add_filter("docstring", super::docstring_filter);
     env.add_template("routes.py.j2", include_str!("../../templates/routes.py.j2"))
         .expect("template should be valid");
     let tmpl = env.get_template("routes.py.j2").unwrap();
@@ -23,16 +25,74 @@ pub fn emit_routes(ir: &IrSpec) -> String {
         .collect();
 
     let model_imports = collect_model_imports(ir);
+    let has_kind = |kind: &str| {
+        operations.iter().any(|op| {
+            op.get_attr("kind")
+                .ok()
+                .is_some_and(|v| v.as_str() == Some(kind))
+        })
+    };
+    let has_dual = has_kind("dual");
+    let uses_body = ir
+        .operations
+        .iter()
+        .filter_map(|op| op.request_body.as_ref())
+        .any(|body| body.is_text());
+    let uses_plain_text = ir
+        .operations
+        .iter()
+        .any(|op| matches!(&op.return_type, IrReturnType::Standard(resp) if resp.is_text()));
+    let uses_literal = ir.operations.iter().any(|op| {
+        let returns = match &op.return_type {
+            IrReturnType::Standard(resp) => vec![&resp.response_type],
+            IrReturnType::Sse(sse) => {
+                let mut types = vec![&sse.event_type];
+                types.extend(sse.json_response.as_ref().map(|r| &r.response_type));
+                types
+            }
+            IrReturnType::Void => vec![],
+        };
+        op.parameters
+            .iter()
+            .filter(|p| p.content_type.is_none())
+            .map(|p| &p.param_type)
+            .chain(op.request_body.as_ref().map(|b| &b.body_type))
+            .chain(returns)
+            .any(uses_literal)
+    });
+    let has_sse = has_dual || has_kind("sse");
+    // Dual routes read `Accept`; pinned headers get a `Header(...)` default.
+    let uses_header = has_dual
+        || ir
+            .operations
+            .iter()
+            .flat_map(|op| &op.parameters)
+            .any(|p| p.pinned_value.is_some() && p.location == IrParameterLocation::Header);
+
+    let has_query_objects = ir
+        .operations
+        .iter()
+        .flat_map(|op| &op.parameters)
+        .any(|p| p.query_object.is_some() && p.pinned_value.is_none());
 
     tmpl.render(context! {
         operations => operations,
         model_imports => model_imports,
+        has_sse => has_sse,
+        has_query_objects => has_query_objects,
+        has_dual => has_dual,
+        uses_header => uses_header,
+        uses_body => uses_body,
+        uses_plain_text => uses_plain_text,
+        uses_literal => uses_literal,
+        db => db,
     })
     .expect("render should succeed")
 }
 
 fn build_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
     let mut results = Vec::new();
+    let name = escape_reserved(&op.name.snake_case, PYTHON_RESERVED_WORDS);
 
     let http_method = match op.method {
         HttpMethod::Get => "get",
@@ -45,40 +105,60 @@ fn build_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
 
     // Convert OpenAPI path params {param} to FastAPI path params {param}
     // (they use the same syntax so no conversion needed)
-    let path = op.path.clone();
+    let path = op.raw_path.clone();
+
+    let (params, pinned_params, has_body, body_type, body_param_name) = build_params(op);
+    // Plain-text bodies are declared with `Body(media_type=...)` so FastAPI
+    // documents them as text rather than JSON.
+    let body_media_type = op
+        .request_body
+        .as_ref()
+        .filter(|body| body.is_text())
+        .map(|body| body.content_type.clone());
 
-    let (params, has_body, body_type, body_param_name) = build_params(op);
+    let responses = error_responses(op);
 
     match &op.return_type {
         IrReturnType::Standard(resp) => {
             let return_type = ir_type_to_python(&resp.response_type);
             results.push(context! {
                 kind => "standard",
-                name => op.name.snake_case.clone(),
+                name => name.clone(),
                 http_method => http_method,
                 path => path,
                 params => params,
+                pinned_params => pinned_params,
                 has_body => has_body,
                 body_type => body_type,
                 body_param_name => body_param_name,
+                body_media_type => body_media_type,
                 return_type => return_type,
+                text_response => resp.is_text(),
                 summary => op.summary.clone(),
+                doc => op.doc().unwrap_or_default(),
+                external_docs => op.external_docs.clone(),
                 description => op.description.clone(),
+                responses => responses.clone(),
             });
         }
         IrReturnType::Void => {
             results.push(context! {
                 kind => "void",
-                name => op.name.snake_case.clone(),
+                name => name.clone(),
                 http_method => http_method,
                 path => path,
                 params => params,
+                pinned_params => pinned_params,
                 has_body => has_body,
                 body_type => body_type,
                 body_param_name => body_param_name,
+                body_media_type => body_media_type,
                 return_type => "None",
                 summary => op.summary.clone(),
+                doc => op.doc().unwrap_or_default(),
+                external_docs => op.external_docs.clone(),
                 description => op.description.clone(),
+                responses => responses.clone(),
             });
         }
         IrReturnType::Sse(sse) => {
@@ -87,35 +167,49 @@ fn build_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
             } else {
                 ir_type_to_python(&sse.event_type)
             };
-            results.push(context! {
-                kind => "sse",
-                name => op.name.snake_case.clone(),
-                http_method => http_method,
-                path => path,
-                params => params,
-                has_body => has_body,
-                body_type => body_type,
-                body_param_name => body_param_name,
-                event_type => event_type,
-                summary => op.summary.clone(),
-                description => op.description.clone(),
-            });
 
-            // Also generate JSON endpoint if dual
+            // Dual endpoints share one route: the JSON handler delegates to the
+            // `_stream` variant when the client sends `Accept: text/event-stream`.
             if let Some(ref json_resp) = sse.json_response {
                 let return_type = ir_type_to_python(&json_resp.response_type);
                 results.push(context! {
-                    kind => "standard",
-                    name => op.name.snake_case.clone(),
+                    kind => "dual",
+                    name => name.clone(),
+                    stream_name => sse.stream_name.snake_case.clone(),
                     http_method => http_method,
                     path => path,
                     params => params,
+                        pinned_params => pinned_params,
                     has_body => has_body,
                     body_type => body_type,
                     body_param_name => body_param_name,
+                    body_media_type => body_media_type,
                     return_type => return_type,
+                    event_type => event_type,
+                    summary => op.summary.clone(),
+                    doc => op.doc().unwrap_or_default(),
+                    external_docs => op.external_docs.clone(),
+                    description => op.description.clone(),
+                    responses => responses.clone(),
+                });
+            } else {
+                results.push(context! {
+                    kind => "sse",
+                    name => name.clone(),
+                    http_method => http_method,
+                    path => path,
+                    params => params,
+                        pinned_params => pinned_params,
+                    has_body => has_body,
+                    body_type => body_type,
+                    body_param_name => body_param_name,
+                    body_media_type => body_media_type,
+                    event_type => event_type,
                     summary => op.summary.clone(),
-                    description => format!("{} (JSON response)", op.description.as_deref().unwrap_or("")),
+                    doc => op.doc().unwrap_or_default(),
+                    external_docs => op.external_docs.clone(),
+                    description => op.description.clone(),
+                    responses => responses.clone(),
                 });
             }
         }
@@ -124,24 +218,83 @@ fn build_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
     results
 }
 
-fn build_params(op: &IrOperation) -> (Vec<minijinja::Value>, bool, String, String) {
+/// The decorator's `responses=` documenting the operation's JSON error
+/// responses, e.g. `{404: {"model": NotFound}, "default": {"model": Error}}`,
+/// or `None` when it declares none.
+fn error_responses(op: &IrOperation) -> Option<String> {
+    if op.errors.is_empty() {
+        return None;
+    }
+    let entries: Vec<String> = op
+        .errors
+        .iter()
+        .map(|error| {
+            // FastAPI keys numeric statuses by int; `4XX` and `default` stay strings.
+            let key = if error.status.bytes().all(|b| b.is_ascii_digit()) {
+                error.status.clone()
+            } else {
+                format!("\"{}\"", error.status)
+            };
+            format!(
+                "{key}: {{\"model\": {}}}",
+                ir_type_to_python(&error.response_type)
+            )
+        })
+        .collect();
+    Some(format!("{{{}}}", entries.join(", ")))
+}
+
+/// The operation's parameters, with pinned ones split out: they go after the
+/// body (they have defaults) and are left out of calls between handlers.
+fn build_params(
+    op: &IrOperation,
+) -> (
+    Vec<minijinja::Value>,
+    Vec<minijinja::Value>,
+    bool,
+    String,
+    String,
+) {
     let mut params = Vec::new();
+    let mut pinned_params = Vec::new();
 
     for param in &op.parameters {
-        let py_type = ir_type_to_python(&param.param_type);
+        if let Some(value) = &param.pinned_value {
+            let marker = match param.location {
+                IrParameterLocation::Header => "Header",
+                _ => "Query",
+            };
+            pinned_params.push(context! {
+                name => param_ident(param),
+                type_str => ir_type_to_python(&param.param_type),
+                marker => marker,
+                default => serde_json::to_string(value).expect("strings always serialize"),
+                original_name => param.original_name.clone(),
+            });
+            continue;
+        }
+        // `content`-encoded parameters arrive as the raw serialized string.
+        let py_type = if param.content_type.is_some() {
+            "str".to_string()
+        } else {
+            ir_type_to_python(&param.param_type)
+        };
         let location = match param.location {
             IrParameterLocation::Path => "path",
             IrParameterLocation::Query => "query",
             IrParameterLocation::Header => "header",
             IrParameterLocation::Cookie => "cookie",
         };
+        let name = param_ident(param);
         params.push(context! {
-            name => param.name.snake_case.clone(),
+            path_alias => name != param.original_name,
+            name => name,
             original_name => param.original_name.clone(),
             type_str => py_type,
             location => location,
             required => param.required,
-            needs_alias => param.name.snake_case != param.original_name,
+            query_kwargs => query_kwargs(param),
+            query_object => param.query_object.map(|n| n.as_str()),
         });
     }
 
@@ -153,7 +306,34 @@ fn build_params(op: &IrOperation) -> (Vec<minijinja::Value>, bool, String, Strin
         .unwrap_or_default();
     let body_param_name = "body".to_string();
 
-    (params, has_body, body_type, body_param_name)
+    (params, pinned_params, has_body, body_type, body_param_name)
+}
+
+/// The Python name for a parameter: its snake_case name, with a trailing `_` if
+/// that's a keyword (`class` → `class_`). The wire name goes in an alias.
+fn param_ident(param: &IrParameter) -> String {
+    escape_reserved(&param.name.snake_case, PYTHON_RESERVED_WORDS)
+}
+
+/// Extra `Query(...)` arguments: the alias when the Python name differs, the
+/// spec's example for the OpenAPI docs, and a description of the encoding
+/// for `content`-encoded parameters.
+fn query_kwargs(param: &IrParameter) -> String {
+    let mut kwargs = Vec::new();
+    if param_ident(param) != param.original_name {
+        kwargs.push(format!("alias=\"{}\"", param.original_name));
+    }
+    if let Some(example) = param.example.as_ref().and_then(python_scalar) {
+        kwargs.push(format!("examples=[{example}]"));
+    }
+    if let Some(content_type) = &param.content_type {
+        kwargs.push(format!(
+            "description=\"{} value encoded as {}\"",
+            ir_type_to_python(&param.param_type),
+            content_type
+        ));
+    }
+    kwargs.join(", ")
 }
 
 fn collect_model_imports(ir: &IrSpec) -> Vec<String> {
@@ -179,9 +359,12 @@ fn collect_model_imports(ir: &IrSpec) -> Vec<String> {
         if let Some(ref body) = op.request_body {
             collect_refs(&body.body_type, &mut imports);
         }
-        for param in &op.parameters {
+        for param in op.parameters.iter().filter(|p| p.content_type.is_none()) {
             collect_refs(&param.param_type, &mut imports);
         }
+        for error in &op.errors {
+            collect_refs(&error.response_type, &mut imports);
+        }
     }
 
     let mut sorted: Vec<String> = imports.into_iter().collect();
@@ -189,12 +372,12 @@ fn collect_model_imports(ir: &IrSpec) -> Vec<String> {
     sorted
 }
 
-fn collect_refs(ir_type: &IrType, imports: &mut std::collections::HashSet<String>) {
+pub(crate) fn collect_refs(ir_type: &IrType, imports: &mut std::collections::HashSet<String>) {
     match ir_type {
         IrType::Ref(name) => {
             imports.insert(name.clone());
         }
-        IrType::Array(inner) | IrType::Map(inner) => collect_refs(inner, imports),
+        IrType::Array(inner) | IrType::Map(inner, _) => collect_refs(inner, imports),
         IrType::Union(variants) | IrType::Intersection(variants) => {
             for v in variants {
                 collect_refs(v, imports);
@@ -203,3 +386,139 @@ fn collect_refs(ir_type: &IrType, imports: &mut std::collections::HashSet<String
         _ => {}
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_content_query_param_is_a_string() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/json-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains(
+            "filter: str | None = Query(default=None, description=\"ItemFilter value encoded as application/json\"),"
+        ));
+        assert!(content.contains("limit: int | None = Query(default=None),"));
+        assert!(!content.contains("ItemFilter,"));
+    }
+
+    #[test]
+    fn test_query_param_example_is_documented() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/parameter-examples.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains("fields: str = Query(examples=[\"name,status\"]),"));
+    }
+
+    #[test]
+    fn test_error_responses_are_documented() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/default-errors.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains(
+            "@router.get(\"/pets/{petId}\", responses={404: {\"model\": NotFound}, \"default\": {\"model\": ErrorModel}})"
+        ));
+        assert!(content.contains(
+            "@router.delete(\"/pets/{petId}\", status_code=204, responses={\"default\": {\"model\": ErrorModel}})"
+        ));
+        // `default` is the success response when there is no 2xx.
+        assert!(content.contains("@router.get(\"/health\")\n"));
+        assert!(content.contains("    NotFound,\n"));
+    }
+
+    #[test]
+    fn test_dual_route_branches_on_the_event_stream_media_type() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains("from sse import sse_response, wants_event_stream"));
+        assert!(content.contains("    if wants_event_stream(accept):\n"));
+        assert!(!content.contains("\"text/event-stream\" in accept"));
+    }
+
+    #[test]
+    fn test_reserved_operation_name_is_escaped() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/untagged-operations.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains("async def import_("));
+        assert!(!content.contains("def import("));
+    }
+
+    #[test]
+    fn test_pinned_header_gets_a_default() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let pins = [("anthropic-version".to_string(), "2023-06-01".to_string())];
+        let ir = ir.with_pinned_parameters(&pins.into_iter().collect());
+        let content = emit_routes(&ir, false);
+        let pinned =
+            "    anthropic_version: str = Header(\"2023-06-01\", alias=\"anthropic-version\"),";
+        // Defaulted, so it follows the body.
+        let body = content.find("    body: CountTokensRequest,").unwrap();
+        assert!(content[body..].contains(pinned));
+        assert!(content.contains("from fastapi import APIRouter, Header, Path, Query"));
+    }
+
+    #[test]
+    fn test_enum_query_params() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains(
+            "    status: Literal[\"available\", \"pending\", \"sold\"] | None = Query(default=None),"
+        ));
+        assert!(content.contains("from typing import Annotated, Literal\n"));
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains("    status: ThingStatus | None = Query(default=None),"));
+        assert!(content.contains("    ThingStatus,\n"));
+        assert!(content.contains("from typing import Annotated\n"));
+    }
+
+    #[test]
+    fn test_query_objects_use_dependency() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/query-objects.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, false);
+        assert!(content.contains("def query_object(name: str, notation: str, type_: Any"));
+        assert!(content.contains(
+            "    filter: PetFilter | None = Depends(query_object(\"filter\", \"bracket\", PetFilter)),"
+        ));
+        assert!(content.contains(
+            "    where: ListOwnersWhere = Depends(query_object(\"where\", \"dot\", ListOwnersWhere, required=True)),"
+        ));
+        assert!(content.contains("from fastapi import APIRouter, Depends, Path, Query, Request\n"));
+        assert!(content.contains("from pydantic import TypeAdapter, ValidationError\n"));
+    }
+}
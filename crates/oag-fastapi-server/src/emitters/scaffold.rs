@@ -1,19 +1,108 @@
 use minijinja::{Environment, context};
 use oag_core::GeneratedFile;
-use oag_core::config::ToolSetting;
-use serde::Deserialize;
+use oag_core::config::{ScaffoldVersion, ToolSetting};
+use serde::de;
+use serde::{Deserialize, Deserializer};
 
 /// FastAPI-specific scaffold configuration, parsed from the opaque `serde_json::Value`.
 #[derive(Debug, Clone, Default, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct FastapiScaffoldConfig {
     pub package_name: Option<String>,
     pub formatter: Option<ToolSetting>,
     pub test_runner: Option<ToolSetting>,
+    /// Also generate `test_routes_hypothesis.py` — property-based tests that post
+    /// random schema-valid bodies (needs the pytest test runner).
+    pub hypothesis_tests: bool,
+    /// Database scaffold. `true` enables it with defaults, `false` or absent disables it.
+    #[serde(deserialize_with = "deserialize_db")]
+    pub db: Option<DbConfig>,
+    /// How response-only object schemas are emitted in `models.py`.
+    pub response_style: ResponseStyle,
+    /// How `allOf` schemas that extend other schemas are emitted in `models.py`.
+    pub allof_style: AllOfStyle,
+    /// Also generate `app.py` — a pydantic-settings `Settings` class and a
+    /// `create_app(settings)` factory with CORS and `/healthz` — and a
+    /// `__main__.py` that serves it.
+    pub app_factory: bool,
+    /// Also generate `client/sse.py` — sync and async iterators over an httpx
+    /// streaming response, yielding each event validated against its model —
+    /// when an operation streams.
+    pub sse_client: bool,
+    /// The `pyproject.toml` version (default: `0.1.0`).
+    pub version: Option<ScaffoldVersion>,
+}
+
+/// Class style for object schemas that are only ever returned, never accepted
+/// as input. Input models stay Pydantic `BaseModel`s so they're validated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ResponseStyle {
+    #[default]
+    BaseModel,
+    /// `TypedDict`, which skips model construction at runtime.
+    TypedDict,
+}
+
+/// Model style for an `allOf` of schema refs and inline objects.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AllOfStyle {
+    /// One model with every part's fields spelled out.
+    #[default]
+    Flatten,
+    /// A subclass of each referenced model (`class Extended(Base)`) adding
+    /// the inline fields, so `isinstance` checks against the bases work.
+    Inherit,
+}
+
+/// Database scaffold options (SQLAlchemy session wiring + Alembic).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct DbConfig {
+    pub engine: DbEngine,
+    /// Generate `alembic.ini` and `alembic/` migration stubs. Default `true`.
+    pub alembic: bool,
+    /// Also generate SQLAlchemy model classes in `db_models.py`.
+    pub db_model: bool,
+}
+
+impl Default for DbConfig {
+    fn default() -> Self {
+        Self {
+            engine: DbEngine::Sqlite,
+            alembic: true,
+            db_model: false,
+        }
+    }
+}
+
+/// Database engine the scaffold targets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DbEngine {
+    Postgresql,
+    #[default]
+    Sqlite,
+}
+
+fn deserialize_db<'de, D>(deserializer: D) -> Result<Option<DbConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = serde_json::Value::deserialize(deserializer).map_err(de::Error::custom)?;
+    match value {
+        serde_json::Value::Null | serde_json::Value::Bool(false) => Ok(None),
+        serde_json::Value::Bool(true) => Ok(Some(DbConfig::default())),
+        other => serde_json::from_value(other)
+            .map(Some)
+            .map_err(de::Error::custom),
+    }
 }
 
 /// Emit scaffold files for the FastAPI server (pyproject.toml, optionally ruff.toml).
-pub fn emit_scaffold(config: &FastapiScaffoldConfig) -> Vec<GeneratedFile> {
+/// `spec_version` is the spec's `info.version`, for `version: spec`.
+pub fn emit_scaffold(config: &FastapiScaffoldConfig, spec_version: &str) -> Vec<GeneratedFile> {
     let mut files = Vec::new();
 
     let name = config.package_name.as_deref().unwrap_or("generated-server");
@@ -32,7 +121,21 @@ pub fn emit_scaffold(config: &FastapiScaffoldConfig) -> Vec<GeneratedFile> {
     files.push(GeneratedFile {
         path: "pyproject.toml".to_string(),
         content: tmpl
-            .render(context! { name => name, pytest => pytest, ruff => ruff })
+            .render(context! {
+                name => name,
+                version => config.version.as_ref().map_or_else(
+                    || "0.1.0".to_string(),
+                    |version| version.pep440(spec_version),
+                ),
+                pytest => pytest,
+                hypothesis => pytest && config.hypothesis_tests,
+                ruff => ruff,
+                db => config.db.is_some(),
+                alembic => config.db.as_ref().is_some_and(|db| db.alembic),
+                postgresql => config.db.as_ref().is_some_and(|db| db.engine == DbEngine::Postgresql),
+                settings => config.app_factory,
+                httpx => config.sse_client,
+            })
             .expect("render should succeed"),
     });
 
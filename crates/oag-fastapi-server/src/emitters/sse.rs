@@ -1,4 +1,74 @@
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::ir::{IrReturnType, IrSpec};
+
 /// Emit `sse.py` — SSE helper using FastAPI's built-in StreamingResponse.
 pub fn emit_sse() -> String {
     include_str!("../../templates/sse.py.j2").to_string()
 }
+
+/// Emit `client/sse.py` — the runtime a Python client consumes the spec's
+/// streams with: `SSEStream` and `AsyncSSEStream` iterate an httpx streaming
+/// response's events, validated against an event type. Empty when no
+/// operation streams.
+pub fn emit_sse_client(ir: &IrSpec) -> Vec<GeneratedFile> {
+    if !has_sse(ir) {
+        return Vec::new();
+    }
+    vec![
+        GeneratedFile::new("client/__init__.py", ""),
+        GeneratedFile::new(
+            "client/sse.py",
+            include_str!("../../templates/client_sse.py.j2"),
+        ),
+    ]
+}
+
+/// Emit `test_client_sse.py` — pytest coverage of `client/sse.py` feeding
+/// canned byte streams through it, sync and async, plus an empty stream of
+/// each of the spec's event unions. `None` when no operation streams.
+pub fn emit_sse_client_tests(ir: &IrSpec) -> Option<GeneratedFile> {
+    if !has_sse(ir) {
+        return None;
+    }
+    let mut event_types: Vec<&str> = ir
+        .operations
+        .iter()
+        .filter_map(|op| match &op.return_type {
+            IrReturnType::Sse(sse) => sse.event_type_name.as_deref(),
+            _ => None,
+        })
+        .collect();
+    event_types.sort_unstable();
+    event_types.dedup();
+    let event_types: Vec<minijinja::Value> = event_types
+        .into_iter()
+        .map(|name| {
+            context! {
+                name => name,
+                snake => heck::AsSnakeCase(name).to_string(),
+            }
+        })
+        .collect();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_template(
+        "test_client_sse.py.j2",
+        include_str!("../../templates/test_client_sse.py.j2"),
+    )
+    .expect("template should be valid");
+    let content = env
+        .get_template("test_client_sse.py.j2")
+        .unwrap()
+        .render(context! { event_types => event_types })
+        .expect("render should succeed");
+    Some(GeneratedFile::new("test_client_sse.py", content))
+}
+
+fn has_sse(ir: &IrSpec) -> bool {
+    ir.operations
+        .iter()
+        .any(|op| matches!(op.return_type, IrReturnType::Sse(_)))
+}
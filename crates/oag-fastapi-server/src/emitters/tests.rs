@@ -1,19 +1,115 @@
 use minijinja::{Environment, context};
 use oag_core::GeneratedFile;
-use oag_core::ir::{HttpMethod, IrOperation, IrParameterLocation, IrReturnType, IrSpec, IrType};
+use oag_core::ir::{
+    HttpMethod, IrObjectSchema, IrOperation, IrParameterLocation, IrReturnType, IrSchema, IrSpec,
+    IrType,
+};
+use oag_core::test_report::TestCoverage;
+
+use super::{python_literal, python_scalar, python_str};
 
 /// Emit `conftest.py` + `test_routes.py` for pytest.
-pub fn emit_tests(ir: &IrSpec) -> Vec<GeneratedFile> {
-    vec![
+///
+/// With `hypothesis`, also emit `test_routes_hypothesis.py`, which fuzzes every
+/// endpoint whose request body is a named model. With `app_factory`, the test
+/// client's app is built by `create_app` with test settings.
+pub fn emit_tests(ir: &IrSpec, hypothesis: bool, app_factory: bool) -> Vec<GeneratedFile> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_template(
+        "conftest.py.j2",
+        include_str!("../../templates/conftest.py.j2"),
+    )
+    .expect("template should be valid");
+    let conftest = env
+        .get_template("conftest.py.j2")
+        .unwrap()
+        .render(context! { factory => app_factory })
+        .expect("render should succeed");
+
+    let mut files = vec![
         GeneratedFile {
             path: "conftest.py".to_string(),
-            content: include_str!("../../templates/conftest.py.j2").to_string(),
+            content: conftest,
         },
         GeneratedFile {
             path: "test_routes.py".to_string(),
             content: emit_test_routes(ir),
         },
-    ]
+    ];
+
+    if hypothesis && let Some(content) = emit_hypothesis_tests(ir) {
+        files.push(GeneratedFile {
+            path: "test_routes_hypothesis.py".to_string(),
+            content,
+        });
+    }
+
+    files
+}
+
+/// Property-based tests driven by `hypothesis-jsonschema`. `None` when no
+/// operation takes a model body.
+fn emit_hypothesis_tests(ir: &IrSpec) -> Option<String> {
+    let operations: Vec<minijinja::Value> = ir
+        .operations
+        .iter()
+        .filter_map(|op| {
+            let body_model = match op.request_body.as_ref().map(|b| &b.body_type) {
+                Some(IrType::Ref(name)) => name.clone(),
+                _ => return None,
+            };
+            let query: Vec<String> = op
+                .parameters
+                .iter()
+                .filter(|p| p.location == IrParameterLocation::Query && p.required)
+                .map(|p| {
+                    let value = p
+                        .example
+                        .as_ref()
+                        .and_then(python_scalar)
+                        .unwrap_or_else(|| mock_value_python(&p.param_type));
+                    format!("\"{}\": {value}", p.original_name)
+                })
+                .collect();
+            Some(context! {
+                name => op.name.snake_case.clone(),
+                http_method => http_method(&op.method),
+                path => op.raw_path.clone(),
+                test_path => build_test_path(&op.raw_path, op),
+                body_model => body_model,
+                query => (!query.is_empty()).then(|| format!("{{{}}}", query.join(", "))),
+            })
+        })
+        .collect();
+
+    if operations.is_empty() {
+        return None;
+    }
+
+    let model_imports: Vec<String> = operations
+        .iter()
+        .filter_map(|op| op.get_attr("body_model").ok()?.as_str().map(String::from))
+        .collect::<std::collections::BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "test_routes_hypothesis.py.j2",
+        include_str!("../../templates/test_routes_hypothesis.py.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("test_routes_hypothesis.py.j2").unwrap();
+    Some(
+        tmpl.render(context! {
+            operations => operations,
+            model_imports => model_imports,
+        })
+        .expect("render should succeed"),
+    )
 }
 
 fn emit_test_routes(ir: &IrSpec) -> String {
@@ -42,36 +138,82 @@ fn emit_test_routes(ir: &IrSpec) -> String {
     let operations: Vec<minijinja::Value> = ir
         .operations
         .iter()
-        .flat_map(build_test_operation_contexts)
+        .flat_map(|op| build_test_operation_contexts(ir, op))
+        .collect();
+
+    // One wire-name round trip per object model sent as a request body.
+    let round_trips: Vec<minijinja::Value> = model_imports
+        .iter()
+        .filter(|name| object_schema(ir, name).is_some())
+        .map(|name| {
+            context! {
+                name => heck::AsSnakeCase(name).to_string(),
+                model => name.clone(),
+                payload => mock_payload_python(ir, &IrType::Ref(name.clone())),
+            }
+        })
         .collect();
 
     tmpl.render(context! {
         operations => operations,
         model_imports => model_imports,
+        round_trips => round_trips,
     })
     .expect("render should succeed")
 }
 
-fn build_test_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
+/// The `test_routes.py` tests calling each operation (`test_report: true`),
+/// built from the same contexts as the tests themselves.
+pub fn route_test_coverage(ir: &IrSpec) -> TestCoverage {
+    let mut coverage = TestCoverage::new(ir);
+    for op in &ir.operations {
+        for ctx in build_test_operation_contexts(ir, op) {
+            for test in route_test_names(&ctx) {
+                coverage.add(op, format!("test_routes.py::{test}"));
+            }
+        }
+    }
+    coverage
+}
+
+/// The test functions `test_routes.py.j2` renders for one operation context.
+fn route_test_names(ctx: &minijinja::Value) -> Vec<String> {
+    let attr = |name| ctx.get_attr(name).unwrap_or_default();
+    let name = attr("name");
+    let kind = attr("kind");
+    let mut tests = vec![format!("test_{name}_route_exists")];
+    if matches!(kind.as_str(), Some("standard" | "void")) {
+        tests.push(format!("test_{name}_not_implemented"));
+    }
+    if attr("has_body").is_true() && attr("text_body").is_none() {
+        tests.push(format!("test_{name}_validates_input"));
+    }
+    tests
+}
+
+fn build_test_operation_contexts(ir: &IrSpec, op: &IrOperation) -> Vec<minijinja::Value> {
     let mut results = Vec::new();
 
-    let http_method = match op.method {
-        HttpMethod::Get => "get",
-        HttpMethod::Post => "post",
-        HttpMethod::Put => "put",
-        HttpMethod::Delete => "delete",
-        HttpMethod::Patch => "patch",
-        _ => "get",
-    };
+    let http_method = http_method(&op.method);
 
     // Replace path params with placeholder values for test URLs
-    let test_path = build_test_path(&op.path, op);
+    let test_path = build_test_path(&op.raw_path, op);
     let has_body = op.request_body.is_some();
+    // The body's preferred example, else a payload built from its type.
     let mock_body = op
         .request_body
         .as_ref()
-        .map(|b| mock_value_python(&b.body_type))
+        .map(|b| match &b.example {
+            Some(example) => python_literal(example),
+            None => mock_payload_python(ir, &b.body_type),
+        })
         .unwrap_or_else(|| "{}".to_string());
+    // Plain-text bodies are posted as raw content with their media type.
+    let text_body = op
+        .request_body
+        .as_ref()
+        .filter(|b| b.is_text())
+        .map(|b| b.content_type.clone());
 
     match &op.return_type {
         IrReturnType::Standard(_) => {
@@ -79,10 +221,11 @@ fn build_test_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
                 kind => "standard",
                 name => op.name.snake_case.clone(),
                 http_method => http_method,
-                path => op.path.clone(),
+                path => op.raw_path.clone(),
                 test_path => test_path,
                 has_body => has_body,
                 mock_body => mock_body,
+                text_body => text_body,
             });
         }
         IrReturnType::Void => {
@@ -90,48 +233,58 @@ fn build_test_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
                 kind => "void",
                 name => op.name.snake_case.clone(),
                 http_method => http_method,
-                path => op.path.clone(),
+                path => op.raw_path.clone(),
                 test_path => test_path,
                 has_body => has_body,
                 mock_body => mock_body,
+                text_body => text_body,
             });
         }
         IrReturnType::Sse(sse) => {
+            // Dual endpoints are a single route whose default (JSON) branch is
+            // a stub, so they are tested like standard endpoints.
+            let kind = if sse.json_response.is_some() {
+                "standard"
+            } else {
+                "sse"
+            };
             results.push(context! {
-                kind => "sse",
+                kind => kind,
                 name => op.name.snake_case.clone(),
                 http_method => http_method,
-                path => op.path.clone(),
+                path => op.raw_path.clone(),
                 test_path => test_path,
                 has_body => has_body,
                 mock_body => mock_body,
+                text_body => text_body,
             });
-
-            // Also test the JSON endpoint if dual
-            if sse.json_response.is_some() {
-                results.push(context! {
-                    kind => "standard",
-                    name => op.name.snake_case.clone(),
-                    http_method => http_method,
-                    path => op.path.clone(),
-                    test_path => test_path,
-                    has_body => has_body,
-                    mock_body => mock_body,
-                });
-            }
         }
     }
 
     results
 }
 
-/// Replace `{param}` placeholders in the path with test values.
+fn http_method(method: &HttpMethod) -> &'static str {
+    match method {
+        HttpMethod::Get => "get",
+        HttpMethod::Post => "post",
+        HttpMethod::Put => "put",
+        HttpMethod::Delete => "delete",
+        HttpMethod::Patch => "patch",
+        _ => "get",
+    }
+}
+
+/// Replace `{param}` placeholders in the path with each parameter's example,
+/// else a test value.
 fn build_test_path(path: &str, op: &IrOperation) -> String {
     let mut result = path.to_string();
     for param in &op.parameters {
         if param.location == IrParameterLocation::Path {
             let placeholder = format!("{{{}}}", param.original_name);
-            let test_value = mock_path_value(&param.param_type);
+            let test_value = param
+                .path_example()
+                .unwrap_or_else(|| mock_path_value(&param.param_type));
             result = result.replace(&placeholder, &test_value);
         }
     }
@@ -158,7 +311,7 @@ fn mock_value_python(ir_type: &IrType) -> String {
         IrType::Null | IrType::Void => "None".to_string(),
         IrType::Array(_) => "[]".to_string(),
         IrType::Ref(name) => format!("{}.model_construct()", name),
-        IrType::Object(_) | IrType::Map(_) | IrType::Any => "{}".to_string(),
+        IrType::Object(_) | IrType::Map(..) | IrType::Any => "{}".to_string(),
         IrType::Binary => "b\"test\"".to_string(),
         IrType::Union(variants) | IrType::Intersection(variants) => {
             if let Some(first) = variants.first() {
@@ -167,10 +320,73 @@ fn mock_value_python(ir_type: &IrType) -> String {
                 "{}".to_string()
             }
         }
+        _ => "{}".to_string(),
     }
 }
 
+/// Nesting depth past which referenced models are mocked as `{}`.
+const MAX_PAYLOAD_DEPTH: usize = 8;
+
+/// Generate a JSON-ready Python literal for a request body. Models become
+/// dicts keyed by the spec's property names, so the payload goes through the
+/// same aliases as a real client's request.
+fn mock_payload_python(ir: &IrSpec, ir_type: &IrType) -> String {
+    payload_value(ir, ir_type, 0)
+}
+
+/// The top-level model gets every field; nested models only their required ones.
+fn payload_value(ir: &IrSpec, ir_type: &IrType, depth: usize) -> String {
+    match ir_type {
+        IrType::Ref(_) if depth > MAX_PAYLOAD_DEPTH => "{}".to_string(),
+        IrType::Ref(name) => match ir.schemas.iter().find(|s| s.name().pascal_case == *name) {
+            Some(IrSchema::Object(obj)) => {
+                let entries: Vec<String> = obj
+                    .fields
+                    .iter()
+                    .filter(|f| depth == 0 || f.required)
+                    .map(|f| {
+                        format!(
+                            "{}: {}",
+                            python_str(&f.original_name),
+                            payload_value(ir, &f.field_type, depth + 1)
+                        )
+                    })
+                    .collect();
+                format!("{{{}}}", entries.join(", "))
+            }
+            Some(IrSchema::Enum(e)) => e
+                .variants
+                .first()
+                .map(|v| python_str(v))
+                .unwrap_or_else(|| "\"test\"".to_string()),
+            Some(IrSchema::Alias(a)) => payload_value(ir, &a.target, depth),
+            Some(IrSchema::Union(u)) => u
+                .variants
+                .first()
+                .map(|v| payload_value(ir, v, depth))
+                .unwrap_or_else(|| "{}".to_string()),
+            None => "{}".to_string(),
+        },
+        IrType::StringLiteral(s) => python_str(s),
+        IrType::DateTime => "\"2024-01-01T00:00:00Z\"".to_string(),
+        IrType::Binary => "\"test\"".to_string(),
+        IrType::Union(variants) | IrType::Intersection(variants) => variants
+            .first()
+            .map(|v| payload_value(ir, v, depth))
+            .unwrap_or_else(|| "{}".to_string()),
+        _ => mock_value_python(ir_type),
+    }
+}
+
+fn object_schema<'a>(ir: &'a IrSpec, name: &str) -> Option<&'a IrObjectSchema> {
+    ir.schemas.iter().find_map(|s| match s {
+        IrSchema::Object(obj) if obj.name.pascal_case == name => Some(obj),
+        _ => None,
+    })
+}
+
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use super::*;
 
@@ -194,4 +410,118 @@ mod tests {
             "Pet.model_construct()"
         );
     }
+
+    #[test]
+    fn test_payloads_use_wire_names() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/camel-case-bodies.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        assert_eq!(
+            mock_payload_python(&ir, &IrType::Ref("NewOrder".to_string())),
+            "{\"customerId\": \"test\", \"shippingAddress\": {\"streetLine\": \"test\", \"postalCode\": \"test\"}, \"giftMessage\": \"test\", \"placedAt\": \"2024-01-01T00:00:00Z\", \"deliveryWindow\": \"morning\"}"
+        );
+
+        let content = emit_test_routes(&ir);
+        assert!(content.contains("json={\"customerId\": \"test\""));
+        assert!(!content.contains("model_construct()"));
+        assert!(content.contains("async def test_new_order_round_trips_wire_names():"));
+        assert!(content.contains("async def echo_body(body: NewOrder) -> NewOrder:"));
+        assert!(content.contains("assert response.json() == payload"));
+    }
+
+    #[test]
+    fn test_parameter_examples_replace_mocks() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/parameter-examples.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        let content = emit_test_routes(&ir);
+        assert!(content.contains("await client.get(\"/pets/42\")"));
+        assert!(content.contains("await client.get(\"/owners/test\")"));
+    }
+
+    #[test]
+    fn test_preferred_body_example_is_posted() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/body-examples.yaml"
+        ))
+        .unwrap();
+        let options = oag_core::transform::TransformOptions {
+            example_preference: vec!["full".to_string()],
+            ..Default::default()
+        };
+        let ir = oag_core::transform::transform_with_options(&spec, &options).unwrap();
+        let content = emit_test_routes(&ir);
+        assert!(content.contains("json={\"name\": \"Rex\", \"tag\": \"dog\"}"));
+
+        // Without a preferred name, the single `example`.
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert!(emit_test_routes(&ir).contains("json={\"name\": \"Single\"}"));
+    }
+
+    #[test]
+    fn test_emit_hypothesis_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        let without: Vec<String> = emit_tests(&ir, false, false)
+            .into_iter()
+            .map(|f| f.path)
+            .collect();
+        assert_eq!(without, ["conftest.py", "test_routes.py"]);
+
+        let files = emit_tests(&ir, true, false);
+        let hypothesis = files
+            .iter()
+            .find(|f| f.path == "test_routes_hypothesis.py")
+            .expect("hypothesis tests should be emitted");
+        assert!(hypothesis.content.contains("from models import NewPet\n"));
+        assert!(
+            hypothesis
+                .content
+                .contains("@given(payload=from_schema(NewPet.model_json_schema()))")
+        );
+        assert!(
+            hypothesis
+                .content
+                .contains("send(\"PUT\", \"/pets/test\", json=payload)")
+        );
+    }
+
+    #[test]
+    fn test_route_coverage_names_rendered_tests() {
+        for fixture in [
+            include_str!("../../../oag-core/tests/fixtures/stream-name-collision.yaml"),
+            include_str!("../../../oag-core/tests/fixtures/camel-case-bodies.yaml"),
+            include_str!("../../../oag-core/tests/fixtures/plain-text.yaml"),
+        ] {
+            let ir = oag_core::transform::transform(&oag_core::parse::from_yaml(fixture).unwrap())
+                .unwrap();
+            let coverage = route_test_coverage(&ir);
+            let content = emit_test_routes(&ir);
+
+            assert!(coverage.untested().is_empty(), "{coverage:?}");
+            let tests: Vec<&str> = coverage
+                .operations
+                .values()
+                .flatten()
+                .map(|test| test.strip_prefix("test_routes.py::").unwrap())
+                .collect();
+            // Every operation test is listed; the rest check models and 404s.
+            let defined = content.matches("async def test_").count();
+            let round_trips = content.matches("_round_trips_wire_names(").count();
+            assert_eq!(tests.len(), defined - round_trips - 1, "{content}");
+            for test in tests {
+                assert!(content.contains(&format!("async def {test}(")), "{test}");
+            }
+        }
+    }
 }
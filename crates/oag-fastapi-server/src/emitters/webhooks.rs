@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use minijinja::{Environment, context};
+use oag_core::ir::{HttpMethod, IrReturnType, IrSpec};
+use oag_core::transform::name_normalizer::{PYTHON_RESERVED_WORDS, escape_reserved};
+
+use crate::emitters::routes::collect_refs;
+use crate::type_mapper::ir_type_to_python;
+
+/// Emit `webhooks.py` — a router mounted at `/webhooks` with one stub per
+/// webhook, served at `/webhooks/{name}`.
+pub fn emit_webhooks(ir: &IrSpec) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_filter("docstring", super::docstring_filter);
+    env.add_template(
+        "webhooks.py.j2",
+        include_str!("../../templates/webhooks.py.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("webhooks.py.j2").unwrap();
+
+    let mut imports = HashSet::new();
+    let webhooks: Vec<minijinja::Value> = ir
+        .webhooks
+        .iter()
+        .map(|op| {
+            let http_method = match op.method {
+                HttpMethod::Get => "get",
+                HttpMethod::Put => "put",
+                HttpMethod::Delete => "delete",
+                HttpMethod::Patch => "patch",
+                _ => "post",
+            };
+            let return_type = match &op.return_type {
+                IrReturnType::Standard(resp) => {
+                    collect_refs(&resp.response_type, &mut imports);
+                    ir_type_to_python(&resp.response_type)
+                }
+                IrReturnType::Sse(_) | IrReturnType::Void => "None".to_string(),
+            };
+            let payload_type = op.request_body.as_ref().map(|b| {
+                collect_refs(&b.body_type, &mut imports);
+                ir_type_to_python(&b.body_type)
+            });
+            context! {
+                key => op.raw_path.clone(),
+                name => escape_reserved(&op.name.snake_case, PYTHON_RESERVED_WORDS),
+                http_method => http_method,
+                payload_type => payload_type,
+                return_type => return_type,
+                doc => op.doc(),
+            }
+        })
+        .collect();
+
+    let mut model_imports: Vec<String> = imports.into_iter().collect();
+    model_imports.sort();
+
+    tmpl.render(context! {
+        webhooks => webhooks,
+        model_imports => model_imports,
+    })
+    .expect("render should succeed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_webhooks() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/mixed-endpoints.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_webhooks(&ir);
+        assert!(content.contains("router = APIRouter(prefix=\"/webhooks\")"));
+        assert!(content.contains("@router.post(\"/itemCreated\", status_code=204)"));
+        assert!(content.contains("async def on_item_created(\n    payload: Item,\n) -> None:"));
+        assert!(content.contains("@router.post(\"/itemDeleted\")"));
+        assert!(content.contains(") -> OnItemDeletedResponse:"));
+    }
+}
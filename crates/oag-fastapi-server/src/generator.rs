@@ -1,5 +1,7 @@
-use oag_core::config::{GeneratorConfig, GeneratorId, ToolSetting};
+use oag_core::config::{ConfigWarning, GeneratorConfig, GeneratorId, ToolSetting};
 use oag_core::ir::IrSpec;
+use oag_core::postprocess::Normalization;
+use oag_core::test_report::REPORT_FILE;
 use oag_core::{CodeGenerator, GeneratedFile, GeneratorError};
 
 use crate::emitters;
@@ -8,6 +10,14 @@ use crate::emitters::scaffold::FastapiScaffoldConfig;
 /// FastAPI server stub generator.
 pub struct FastapiServerGenerator;
 
+/// The options fastapi-server reads, besides `output`, `files` and `naming`.
+const SUPPORTED_OPTIONS: &[&str] = &[
+    "contract_tests",
+    "test_report",
+    "pinned_parameters",
+    "scaffold",
+];
+
 impl CodeGenerator for FastapiServerGenerator {
     fn id(&self) -> GeneratorId {
         GeneratorId::FastapiServer
@@ -18,14 +28,44 @@ impl CodeGenerator for FastapiServerGenerator {
         ir: &IrSpec,
         config: &GeneratorConfig,
     ) -> Result<Vec<GeneratedFile>, GeneratorError> {
+        let ir = &*ir.with_pinned_parameters(&config.pinned_parameters);
+        let scaffold: Option<FastapiScaffoldConfig> = config
+            .scaffold
+            .as_ref()
+            .map(|raw| serde_json::from_value(raw.clone()))
+            .transpose()
+            .map_err(|e| GeneratorError::Other(format!("invalid scaffold config: {e}")))?;
+        let db = scaffold.as_ref().and_then(|s| s.db.as_ref());
+        let response_style = scaffold
+            .as_ref()
+            .map(|s| s.response_style)
+            .unwrap_or_default();
+        let allof_style = scaffold.as_ref().map(|s| s.allof_style).unwrap_or_default();
+        let app_factory = scaffold.as_ref().is_some_and(|s| s.app_factory);
+        let sse_client = scaffold.as_ref().is_some_and(|s| s.sse_client);
+
+        if config.types_only {
+            let mut files = vec![GeneratedFile {
+                path: "models.py".to_string(),
+                content: emitters::models::emit_models(ir, response_style, allof_style),
+            }];
+            if let Some(ref scaffold) = scaffold {
+                files.extend(emitters::scaffold::emit_scaffold(
+                    scaffold,
+                    &ir.info.version,
+                ));
+            }
+            return Ok(files);
+        }
+
         let mut files = vec![
             GeneratedFile {
                 path: "models.py".to_string(),
-                content: emitters::models::emit_models(ir),
+                content: emitters::models::emit_models(ir, response_style, allof_style),
             },
             GeneratedFile {
                 path: "routes.py".to_string(),
-                content: emitters::routes::emit_routes(ir),
+                content: emitters::routes::emit_routes(ir, db.is_some()),
             },
             GeneratedFile {
                 path: "sse.py".to_string(),
@@ -33,25 +73,144 @@ impl CodeGenerator for FastapiServerGenerator {
             },
             GeneratedFile {
                 path: "main.py".to_string(),
-                content: emitters::app::emit_app(),
-            },
-            GeneratedFile {
-                path: "__init__.py".to_string(),
-                content: String::new(),
+                content: emitters::app::emit_app(!ir.webhooks.is_empty(), app_factory),
             },
         ];
+        files.extend(package_markers());
+
+        if !ir.webhooks.is_empty() {
+            files.push(GeneratedFile {
+                path: "webhooks.py".to_string(),
+                content: emitters::webhooks::emit_webhooks(ir),
+            });
+        }
+
+        if app_factory {
+            files.extend(emitters::app::emit_app_factory(ir));
+        }
+
+        if sse_client {
+            files.extend(emitters::sse::emit_sse_client(ir));
+        }
 
         // Add scaffold (pyproject.toml, optionally ruff.toml)
-        if let Some(ref raw) = config.scaffold {
-            let scaffold: FastapiScaffoldConfig = serde_json::from_value(raw.clone())
-                .map_err(|e| GeneratorError::Other(format!("invalid scaffold config: {e}")))?;
-            files.extend(emitters::scaffold::emit_scaffold(&scaffold));
+        if let Some(ref scaffold) = scaffold {
+            files.extend(emitters::scaffold::emit_scaffold(
+                scaffold,
+                &ir.info.version,
+            ));
 
             if ToolSetting::resolve(scaffold.test_runner.as_ref(), "pytest").is_some() {
-                files.extend(emitters::tests::emit_tests(ir));
+                files.extend(emitters::tests::emit_tests(
+                    ir,
+                    scaffold.hypothesis_tests,
+                    scaffold.app_factory,
+                ));
+                if config.test_report {
+                    files.push(GeneratedFile::new(
+                        REPORT_FILE,
+                        emitters::tests::route_test_coverage(ir).to_json(),
+                    ));
+                }
+                if sse_client {
+                    files.extend(emitters::sse::emit_sse_client_tests(ir));
+                }
             }
         }
 
+        if let Some(db) = db {
+            files.extend(emitters::db::emit_db(ir, db));
+        }
+
+        if config.contract_tests {
+            files.extend(emitters::contract::emit_contract(ir));
+        }
+
         Ok(files)
     }
+
+    /// Alembic's `script.py.mako` renders to Python, so it gets the Python policy.
+    fn normalization(&self, file: &GeneratedFile) -> Normalization {
+        if file.path.ends_with(".py.mako") {
+            Normalization::PythonPep8
+        } else {
+            file.normalization()
+        }
+    }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Vec<ConfigWarning> {
+        let mut warnings = config.unsupported_options(self.id(), SUPPORTED_OPTIONS);
+        if let Err(warning) = config.parse_scaffold::<FastapiScaffoldConfig>() {
+            warnings.push(warning);
+        }
+        warnings
+    }
+}
+
+/// `__init__.py`, so the output imports as a package, and `py.typed`, so type
+/// checkers read its annotations when it's installed as one.
+fn package_markers() -> [GeneratedFile; 2] {
+    ["__init__.py", "py.typed"].map(|path| GeneratedFile::new(path, ""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oag_core::config::{OutputLayout, SplitBy};
+
+    #[test]
+    fn validate_config_flags_client_options_and_scaffold_typos() {
+        let config = GeneratorConfig {
+            layout: OutputLayout::Split,
+            split_by: Some(SplitBy::Operation),
+            no_jsdoc: Some(true),
+            scaffold: Some(serde_json::json!({ "db": true, "hypothesis": true })),
+            ..Default::default()
+        };
+        let warnings = FastapiServerGenerator.validate_config(&config);
+        let options: Vec<&str> = warnings.iter().map(|w| w.option.as_str()).collect();
+        assert_eq!(options, ["layout", "split_by", "no_jsdoc", "scaffold"]);
+        assert_eq!(
+            warnings[1].to_string(),
+            "`split_by`: not supported by fastapi-server; ignored"
+        );
+        assert!(warnings[3].message.contains("unknown field `hypothesis`"));
+    }
+
+    #[test]
+    fn scaffold_version_sets_the_project_version() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let version = |scaffold: serde_json::Value| {
+            let config = GeneratorConfig {
+                scaffold: Some(scaffold),
+                ..Default::default()
+            };
+            let files = FastapiServerGenerator.generate(&ir, &config).unwrap();
+            let pyproject = &files.iter().find(|f| f.path == "pyproject.toml").unwrap();
+            pyproject
+                .content
+                .lines()
+                .find_map(|line| line.strip_prefix("version = "))
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(version(serde_json::json!({})), "\"0.1.0\"");
+        assert_eq!(
+            version(serde_json::json!({ "version": "spec" })),
+            "\"1.0.0\""
+        );
+        assert_eq!(
+            version(serde_json::json!({ "version": "2.0-rc.1" })),
+            "\"2.0.0rc1\""
+        );
+        assert_eq!(
+            version(serde_json::json!({ "version": "1.0.0-nightly" })),
+            "\"0.0.0\""
+        );
+    }
 }
@@ -1,7 +1,16 @@
-use oag_core::ir::IrType;
+use std::collections::HashSet;
+
+use oag_core::ir::{IrMapKey, IrType};
 
 /// Map an `IrType` to its Python type string representation.
 pub fn ir_type_to_python(ir_type: &IrType) -> String {
+    ir_type_to_python_forward(ir_type, &HashSet::new())
+}
+
+/// Like [`ir_type_to_python`], for a type evaluated before the `forward`
+/// schemas are defined: refs to them are quoted, and unions holding one are
+/// spelled `Union[...]` since `"A" | B` doesn't evaluate.
+pub fn ir_type_to_python_forward(ir_type: &IrType, forward: &HashSet<&str>) -> String {
     match ir_type {
         IrType::String => "str".to_string(),
         IrType::StringLiteral(s) => format!("Literal[\"{s}\"]"),
@@ -13,14 +22,18 @@ pub fn ir_type_to_python(ir_type: &IrType) -> String {
         IrType::Binary => "bytes".to_string(),
         IrType::Any => "Any".to_string(),
         IrType::Void => "None".to_string(),
+        IrType::Ref(name) if forward.contains(name.as_str()) => format!("\"{name}\""),
         IrType::Ref(name) => name.clone(),
         IrType::Array(inner) => {
-            let inner_py = ir_type_to_python(inner);
+            let inner_py = ir_type_to_python_forward(inner, forward);
             format!("list[{inner_py}]")
         }
-        IrType::Map(value_type) => {
-            let value_py = ir_type_to_python(value_type);
-            format!("dict[str, {value_py}]")
+        IrType::Map(value_type, key) => {
+            let value_py = ir_type_to_python_forward(value_type, forward);
+            match key {
+                Some(IrMapKey::Integer) => format!("dict[int, {value_py}]"),
+                _ => format!("dict[str, {value_py}]"),
+            }
         }
         IrType::Object(fields) => {
             if fields.is_empty() {
@@ -30,29 +43,78 @@ pub fn ir_type_to_python(ir_type: &IrType) -> String {
             "dict[str, Any]".to_string()
         }
         IrType::Union(variants) => {
-            let variant_strs: Vec<String> = variants.iter().map(ir_type_to_python).collect();
-            variant_strs.join(" | ")
+            // String literals collapse into one `Literal[...]` where the first one was.
+            let literals: Vec<String> = variants
+                .iter()
+                .filter_map(|v| match v {
+                    IrType::StringLiteral(s) => Some(format!("\"{s}\"")),
+                    _ => None,
+                })
+                .collect();
+            let mut variant_strs = Vec::new();
+            let mut literal_done = false;
+            for variant in variants {
+                if !matches!(variant, IrType::StringLiteral(_)) {
+                    variant_strs.push(ir_type_to_python_forward(variant, forward));
+                } else if !literal_done {
+                    variant_strs.push(format!("Literal[{}]", literals.join(", ")));
+                    literal_done = true;
+                }
+            }
+            let has_forward = variants
+                .iter()
+                .any(|v| matches!(v, IrType::Ref(name) if forward.contains(name.as_str())));
+            if has_forward {
+                format!("Union[{}]", variant_strs.join(", "))
+            } else {
+                variant_strs.join(" | ")
+            }
         }
         IrType::Intersection(parts) => {
             // Python doesn't have a native intersection type; use the first part as a fallback
             if parts.len() == 1 {
-                ir_type_to_python(&parts[0])
+                ir_type_to_python_forward(&parts[0], forward)
             } else {
                 // Multiple inheritance: tuple of base classes
-                let part_strs: Vec<String> = parts.iter().map(ir_type_to_python).collect();
+                let part_strs: Vec<String> = parts
+                    .iter()
+                    .map(|part| ir_type_to_python_forward(part, forward))
+                    .collect();
                 part_strs.join(", ")
             }
         }
+        // Types added to the IR later stay usable, if loosely typed.
+        _ => "Any".to_string(),
+    }
+}
+
+/// Whether the Python type of `ir_type` uses `typing.Literal`.
+pub fn uses_literal(ir_type: &IrType) -> bool {
+    match ir_type {
+        IrType::StringLiteral(_) => true,
+        IrType::Array(inner) | IrType::Map(inner, _) => uses_literal(inner),
+        IrType::Union(variants) | IrType::Intersection(variants) => {
+            variants.iter().any(uses_literal)
+        }
+        _ => false,
     }
 }
 
-/// Map an `IrType` to a Python type that's Optional if not required.
-pub fn ir_type_to_python_field(ir_type: &IrType, required: bool) -> String {
+/// Map an `IrType` to a Python field annotation and default. `default` is an
+/// optional field's spec `default` as a Python literal; without one the field
+/// defaults to `None`, but only a nullable field accepts `None` as a value.
+/// Others default to `models.py`'s `UNSET`, `None` typed `Any`, which type
+/// checkers accept for any annotation. Required fields stay required whatever
+/// their default.
+pub fn ir_type_to_python_field(ir_type: &IrType, required: bool, default: Option<&str>) -> String {
     let base = ir_type_to_python(ir_type);
-    if required {
-        base
-    } else {
-        format!("{base} | None = None")
+    match (required, default) {
+        (true, _) => base,
+        (false, Some(default)) => format!("{base} = {default}"),
+        // Already `T | None`; don't repeat the `None`.
+        (false, None) if ir_type.is_nullable() => format!("{base} = None"),
+        // `None` only marks the field unset; validation still rejects it.
+        (false, None) => format!("{base} = UNSET"),
     }
 }
 
@@ -82,9 +144,16 @@ mod tests {
     #[test]
     fn test_map() {
         assert_eq!(
-            ir_type_to_python(&IrType::Map(Box::new(IrType::String))),
+            ir_type_to_python(&IrType::Map(Box::new(IrType::String), None)),
             "dict[str, str]"
         );
+        assert_eq!(
+            ir_type_to_python(&IrType::Map(
+                Box::new(IrType::String),
+                Some(IrMapKey::Integer)
+            )),
+            "dict[int, str]"
+        );
     }
 
     #[test]
@@ -100,12 +169,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_forward_refs() {
+        let forward = HashSet::from(["Node"]);
+        let tree = IrType::Union(vec![IrType::Ref("Node".into()), IrType::Ref("Leaf".into())]);
+        assert_eq!(
+            ir_type_to_python_forward(&tree, &forward),
+            "Union[\"Node\", Leaf]"
+        );
+        assert_eq!(
+            ir_type_to_python_forward(&IrType::Array(Box::new(tree.clone())), &HashSet::new()),
+            "list[Node | Leaf]"
+        );
+        assert_eq!(
+            ir_type_to_python_forward(
+                &IrType::Array(Box::new(IrType::Ref("Node".into()))),
+                &forward
+            ),
+            "list[\"Node\"]"
+        );
+    }
+
+    #[test]
+    fn test_string_literal_union() {
+        let status = IrType::Union(vec![
+            IrType::StringLiteral("available".into()),
+            IrType::StringLiteral("pending".into()),
+            IrType::StringLiteral("sold".into()),
+        ]);
+        assert_eq!(
+            ir_type_to_python(&status),
+            "Literal[\"available\", \"pending\", \"sold\"]"
+        );
+        assert!(uses_literal(&IrType::Array(Box::new(status))));
+
+        let mixed = IrType::Union(vec![
+            IrType::Integer,
+            IrType::StringLiteral("auto".into()),
+            IrType::StringLiteral("none".into()),
+            IrType::Null,
+        ]);
+        assert_eq!(
+            ir_type_to_python(&mixed),
+            "int | Literal[\"auto\", \"none\"] | None"
+        );
+        assert!(!uses_literal(&IrType::Ref("PetStatus".into())));
+    }
+
     #[test]
     fn test_optional_field() {
-        assert_eq!(ir_type_to_python_field(&IrType::String, true), "str");
+        assert_eq!(ir_type_to_python_field(&IrType::String, true, None), "str");
+        assert_eq!(
+            ir_type_to_python_field(&IrType::String, false, None),
+            "str = UNSET"
+        );
+        let nullable = IrType::Union(vec![IrType::Ref("Pet".into()), IrType::Null]);
+        assert_eq!(
+            ir_type_to_python_field(&nullable, false, None),
+            "Pet | None = None"
+        );
+    }
+
+    #[test]
+    fn test_defaulted_field() {
+        assert_eq!(
+            ir_type_to_python_field(&IrType::Integer, false, Some("20")),
+            "int = 20"
+        );
+        // A default doesn't make a required field optional.
         assert_eq!(
-            ir_type_to_python_field(&IrType::String, false),
-            "str | None = None"
+            ir_type_to_python_field(&IrType::Integer, true, Some("20")),
+            "int"
         );
     }
 }
@@ -0,0 +1,40 @@
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, GeneratedFile, parse, transform};
+use oag_fastapi_server::FastapiServerGenerator;
+
+const MARKDOWN_DOCS: &str = include_str!("../../oag-core/tests/fixtures/markdown-docs.yaml");
+
+fn generate() -> Vec<GeneratedFile> {
+    let spec = parse::from_yaml(MARKDOWN_DOCS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    FastapiServerGenerator
+        .generate(&ir, &GeneratorConfig::default())
+        .unwrap()
+}
+
+fn file<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+    &files
+        .iter()
+        .find(|f| f.path == path)
+        .unwrap_or_else(|| panic!("{path} should be generated"))
+        .content
+}
+
+#[test]
+fn route_docstring_is_wrapped_and_escaped() {
+    let files = generate();
+    let routes = file(&files, "routes.py");
+    let start = routes.find("    \"\"\"\n    List widgets").unwrap();
+    let body = start + "    \"\"\"".len();
+    let end = body + routes[body..].find("\"\"\"").unwrap() + 3;
+    insta::assert_snapshot!(&routes[start..end]);
+}
+
+#[test]
+fn model_comments_keep_paragraphs() {
+    let files = generate();
+    let models = file(&files, "models.py");
+    let start = models.find("# A configurable widget.").unwrap();
+    let end = models.find("    id: str").unwrap();
+    insta::assert_snapshot!(&models[start..end]);
+}
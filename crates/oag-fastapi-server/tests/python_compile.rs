@@ -0,0 +1,628 @@
+use std::fs;
+use std::process::Command;
+
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, GeneratedFile, parse, postprocess, transform};
+use oag_fastapi_server::FastapiServerGenerator;
+
+const ANTHROPIC: &str = include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml");
+const MIXED: &str = include_str!("../../oag-core/tests/fixtures/mixed-endpoints.yaml");
+const MARKDOWN_DOCS: &str = include_str!("../../oag-core/tests/fixtures/markdown-docs.yaml");
+const RESERVED_WORDS: &str = include_str!("../../oag-core/tests/fixtures/reserved-words.yaml");
+const CAMEL_CASE: &str = include_str!("../../oag-core/tests/fixtures/camel-case-bodies.yaml");
+const PLAIN_TEXT: &str = include_str!("../../oag-core/tests/fixtures/plain-text.yaml");
+const NULLABLE_UNIONS: &str = include_str!("../../oag-core/tests/fixtures/nullable-unions.yaml");
+const PETSTORE_POLY: &str = include_str!("../../oag-core/tests/fixtures/petstore-polymorphic.yaml");
+const DEPRECATED_FIELDS: &str =
+    include_str!("../../oag-core/tests/fixtures/deprecated-fields.yaml");
+const WRITE_ONLY: &str = include_str!("../../oag-core/tests/fixtures/write-only.yaml");
+const FIELD_DEFAULTS: &str = include_str!("../../oag-core/tests/fixtures/field-defaults.yaml");
+const PATHOLOGICAL_ENUMS: &str =
+    include_str!("../../oag-core/tests/fixtures/pathological-enums.yaml");
+
+fn generate(yaml: &str) -> Vec<GeneratedFile> {
+    generate_with(yaml, &GeneratorConfig::default())
+}
+
+fn generate_with(yaml: &str, config: &GeneratorConfig) -> Vec<GeneratedFile> {
+    let spec = parse::from_yaml(yaml).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let mut files = FastapiServerGenerator.generate(&ir, config).unwrap();
+    postprocess::normalize_files(&FastapiServerGenerator, &mut files);
+    files
+}
+
+fn file<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+    &files
+        .iter()
+        .find(|f| f.path == path)
+        .unwrap_or_else(|| panic!("{path} should be generated"))
+        .content
+}
+
+/// Names of top-level `async def` functions in a Python module.
+fn function_names(source: &str) -> Vec<&str> {
+    source
+        .lines()
+        .filter_map(|line| line.strip_prefix("async def "))
+        .filter_map(|rest| rest.split('(').next())
+        .collect()
+}
+
+fn assert_unique(names: &[&str], path: &str) {
+    let mut seen = std::collections::HashSet::new();
+    for name in names {
+        assert!(seen.insert(*name), "{path} defines `{name}` more than once");
+    }
+}
+
+/// Names imported by `from models import (...)` in a generated module.
+fn model_imports(source: &str) -> Vec<String> {
+    let start = source
+        .find("from models import (")
+        .expect("module should import models");
+    let block = &source[start..];
+    let end = block.find(')').unwrap();
+    block["from models import (".len()..end]
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+#[test]
+fn anthropic_sse_event_union_is_defined() {
+    let files = generate(ANTHROPIC);
+    let models = file(&files, "models.py");
+    let routes = file(&files, "routes.py");
+
+    assert!(models.contains("\nCreateMessageStreamEvent = "));
+    assert!(routes.contains("AsyncIterator[CreateMessageStreamEvent]"));
+
+    for name in model_imports(routes) {
+        let defined = models.contains(&format!("\nclass {name}("))
+            || models.contains(&format!("\n{name} = "));
+        assert!(
+            defined,
+            "routes.py imports `{name}` which models.py does not define"
+        );
+    }
+}
+
+#[test]
+fn anyof_unions_validate_left_to_right() {
+    let files = generate(ANTHROPIC);
+    let models = file(&files, "models.py");
+
+    assert!(models.contains("from typing import Annotated, Any"));
+    assert!(models.contains(
+        "\nToolResultContent = Annotated[TextBlock | ImageBlock, Field(union_mode=\"left_to_right\")]\n"
+    ));
+    // `oneOf` keeps Pydantic's default smart mode.
+    assert!(
+        models
+            .contains("\nContentBlock = TextBlock | ImageBlock | ToolUseBlock | ToolResultBlock\n")
+    );
+}
+
+#[test]
+fn anthropic_dual_endpoint_names_are_unique() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "test_runner": "pytest" })),
+        ..Default::default()
+    };
+    let files = generate_with(ANTHROPIC, &config);
+    let routes = file(&files, "routes.py");
+    let tests = file(&files, "test_routes.py");
+
+    let route_names = function_names(routes);
+    assert!(route_names.contains(&"create_message"));
+    assert!(route_names.contains(&"create_message_stream"));
+    assert_unique(&route_names, "routes.py");
+    assert_unique(&function_names(tests), "test_routes.py");
+
+    // Both variants live on one route; the stream is picked by the Accept header.
+    assert_eq!(routes.matches("@router.post(\"/v1/messages\"").count(), 1);
+    assert!(routes.contains("if wants_event_stream(accept):"));
+}
+
+fn assert_compiles(files: &[GeneratedFile]) {
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    for file in files {
+        let dest = dir.join(&file.path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&dest, &file.content).unwrap();
+    }
+
+    let compile = Command::new("python3")
+        .args(["-m", "compileall", "-q", "."])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run python3 -m compileall");
+    if !compile.status.success() {
+        panic!(
+            "compileall failed:\nstdout: {}\nstderr: {}",
+            String::from_utf8_lossy(&compile.stdout),
+            String::from_utf8_lossy(&compile.stderr),
+        );
+    }
+}
+
+#[test]
+fn generated_python_anthropic_compiles() {
+    assert_compiles(&generate(ANTHROPIC));
+}
+
+#[test]
+fn generated_python_anthropic_pinned_version_compiles() {
+    let config = GeneratorConfig {
+        pinned_parameters: [("anthropic-version".to_string(), "2023-06-01".to_string())]
+            .into_iter()
+            .collect(),
+        scaffold: Some(serde_json::json!({ "test_runner": "pytest" })),
+        ..Default::default()
+    };
+    assert_compiles(&generate_with(ANTHROPIC, &config));
+}
+
+#[test]
+fn generated_python_markdown_docs_compiles() {
+    assert_compiles(&generate(MARKDOWN_DOCS));
+}
+
+#[test]
+fn mixed_webhooks_router_is_mounted() {
+    let files = generate(MIXED);
+    let main = file(&files, "main.py");
+    let webhooks = file(&files, "webhooks.py");
+
+    assert!(main.contains("from webhooks import router as webhooks_router"));
+    assert!(main.contains("app.include_router(webhooks_router)"));
+    assert!(webhooks.contains("async def on_item_deleted("));
+    for name in model_imports(webhooks) {
+        assert!(
+            file(&files, "models.py").contains(&format!("\nclass {name}(")),
+            "webhooks.py imports `{name}` which models.py does not define"
+        );
+    }
+}
+
+#[test]
+fn routes_keep_two_blank_lines_between_functions() {
+    let files = generate(ANTHROPIC);
+    let routes = file(&files, "routes.py");
+    assert!(!routes.contains("\n\n\n\n"));
+    assert!(!routes.contains("(\n\n"));
+    let decorators = routes.matches("\n@router.").count();
+    assert!(decorators > 1);
+    assert_eq!(routes.matches("\n\n\n@router.").count(), decorators);
+    assert!(routes.ends_with("raise NotImplementedError\n"));
+    assert_compiles(&files);
+}
+
+#[test]
+fn typed_dict_response_style_compiles() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "response_style": "typed_dict" })),
+        ..Default::default()
+    };
+    let files = generate_with(ANTHROPIC, &config);
+    let models = file(&files, "models.py");
+    assert!(models.contains("from typing_extensions import Required, TypedDict"));
+    // Request bodies stay validated models; response-only shapes become TypedDicts.
+    assert!(models.contains("class CreateMessageRequest(BaseModel):"));
+    assert!(models.contains("class MessageResponse(TypedDict, total=False):"));
+    assert!(models.contains("    id: Required[str]"));
+    assert_compiles(&files);
+}
+
+#[test]
+fn reserved_words_are_escaped_and_compile() {
+    let files = generate(RESERVED_WORDS);
+    let models = file(&files, "models.py");
+    assert!(models.contains("class RecordSchema(BaseModel):"));
+    assert!(models.contains("    class_: str = Field(alias=\"class\")"));
+    assert!(models.contains("    def_: str = Field(default=UNSET, alias=\"def\")"));
+
+    let routes = file(&files, "routes.py");
+    assert!(routes.contains("    import_: Annotated[str, Path(alias=\"import\")],"));
+    assert!(routes.contains("    class_: str = Query(alias=\"class\"),"));
+    assert_compiles(&files);
+}
+
+#[test]
+fn pathological_enum_members_are_unique_and_compile() {
+    let files = generate(PATHOLOGICAL_ENUMS);
+    let models = file(&files, "models.py");
+    assert!(models.contains(
+        "class Mode(str, Enum):\n    A = \"a\"\n    A_2 = \"A\"\n    EMPTY = \"\"\n    None_ = \"none\"\n    V1 = \"1\"\n    VALUE = \"-\"\n"
+    ));
+    assert_compiles(&files);
+}
+
+#[test]
+fn camel_case_round_trip_test_compiles() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "test_runner": "pytest" })),
+        ..Default::default()
+    };
+    let files = generate_with(CAMEL_CASE, &config);
+    let tests = file(&files, "test_routes.py");
+
+    assert!(tests.contains("async def test_new_order_round_trips_wire_names():"));
+    assert!(tests.contains("payload = {\"customerId\": \"test\", \"shippingAddress\": {"));
+    assert_unique(&function_names(tests), "test_routes.py");
+    assert_compiles(&files);
+}
+
+#[test]
+fn plain_text_bodies_and_responses_compile() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "test_runner": "pytest" })),
+        ..Default::default()
+    };
+    let files = generate_with(PLAIN_TEXT, &config);
+    let routes = file(&files, "routes.py");
+    assert!(routes.contains("from fastapi import APIRouter, Body, Path, Query"));
+    assert!(routes.contains("from fastapi.responses import PlainTextResponse, StreamingResponse"));
+    assert!(routes.contains("    body: str = Body(..., media_type=\"text/plain\"),"));
+    assert!(routes.contains("@router.get(\"/logs/{streamId}\", response_class=PlainTextResponse)"));
+    assert!(
+        routes
+            .contains("@router.get(\"/logs/{streamId}/export\", response_class=PlainTextResponse)")
+    );
+
+    let tests = file(&files, "test_routes.py");
+    assert!(tests.contains(
+        "await client.post(\"/logs\", content=\"test\", headers={\"content-type\": \"text/plain\"})"
+    ));
+    assert!(!tests.contains("async def test_ingest_logs_validates_input("));
+    assert_compiles(&files);
+}
+
+#[test]
+fn nullable_unions_become_optional_and_compile() {
+    let files = generate(NULLABLE_UNIONS);
+    let models = file(&files, "models.py");
+    assert!(models.contains("\nNullablePet = Pet | None\n"));
+    assert!(models.contains("    pet: NullablePet\n"));
+    assert!(
+        models
+            .contains("    previous_pet: Pet | None = Field(default=None, alias=\"previousPet\")")
+    );
+    assert!(models.contains("    tags: list[str] | None = None\n"));
+    assert!(!models.contains("| None | None"));
+    assert_compiles(&files);
+}
+
+#[test]
+fn allof_is_flattened_by_default() {
+    let files = generate(PETSTORE_POLY);
+    let models = file(&files, "models.py");
+    let ext = models
+        .split("class ExtendedErrorModel(BaseModel):")
+        .nth(1)
+        .unwrap();
+    let ext = &ext[..ext.find("model_config").unwrap()];
+    assert!(ext.contains("message: str"));
+    assert!(ext.contains("code: int"));
+    assert!(ext.contains("root_cause: str = Field(alias=\"rootCause\")"));
+    assert_compiles(&files);
+}
+
+#[test]
+fn deprecated_fields_carry_field_metadata() {
+    let files = generate(DEPRECATED_FIELDS);
+    let models = file(&files, "models.py");
+    assert!(
+        models
+            .contains("legacy_id: int = Field(default=UNSET, alias=\"legacyId\", deprecated=True)")
+    );
+    assert!(models.contains("nickname: str = Field(default=UNSET, deprecated=True)"));
+    assert!(models.contains("    email: str = UNSET\n"));
+    assert_compiles(&files);
+}
+
+#[test]
+fn write_only_fields_stay_out_of_responses() {
+    let files = generate(WRITE_ONLY);
+    let models = file(&files, "models.py");
+    assert!(models.contains(
+        "    # At least 12 characters.\n    # Write-only: accepted in requests, never returned in responses.\n    password: str = Field(exclude=True)\n"
+    ));
+    assert!(models.contains(
+        "refresh_token: str = Field(default=UNSET, alias=\"refreshToken\", exclude=True)"
+    ));
+    assert_compiles(&files);
+
+    // Response-only shapes drop them entirely.
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "response_style": "typed_dict" })),
+        ..Default::default()
+    };
+    let files = generate_with(WRITE_ONLY, &config);
+    let models = file(&files, "models.py");
+    assert!(models.contains("class AuthToken(TypedDict):\n    userId: str\n"));
+    assert!(!models.contains("refreshToken"));
+    assert_compiles(&files);
+
+    // The contract mocks carry a placeholder, never a plausible secret.
+    let config = GeneratorConfig {
+        contract_tests: true,
+        ..Default::default()
+    };
+    let files = generate_with(WRITE_ONLY, &config);
+    assert!(file(&files, "contract/mocks.json").contains("\"password\": \"<redacted>\""));
+}
+
+#[test]
+fn field_defaults_follow_required_and_nullable() {
+    let files = generate(FIELD_DEFAULTS);
+    let models = file(&files, "models.py");
+    for line in [
+        // Required with a default: still required.
+        "    page_size: int = Field(alias=\"pageSize\")\n",
+        "    limit: int = 20\n",
+        "    sort: Literal[\"relevance\", \"newest\"] = \"relevance\"\n",
+        "    tags: list[str] = [\"public\"]\n",
+        "    visibility: Visibility = Field(default=\"public\", validate_default=True)\n",
+        // Optional, not nullable: unset is `None`, but `None` isn't accepted.
+        "    cursor: str = UNSET\n",
+        "    note: str | None = None\n",
+        "    since: str | None = None\n",
+    ] {
+        assert!(models.contains(line), "models.py should contain {line:?}");
+    }
+    assert!(models.contains("from typing import Any, Literal\n"));
+    assert!(models.contains("\nUNSET: Any = None\n"));
+    assert_compiles(&files);
+}
+
+#[test]
+#[ignore = "needs Python with pydantic"]
+fn field_defaults_serialize_unset_default_and_explicit_none() {
+    let tmp = tempfile::tempdir().unwrap();
+    fs::write(
+        tmp.path().join("models.py"),
+        file(&generate(FIELD_DEFAULTS), "models.py"),
+    )
+    .unwrap();
+
+    let check = r#"
+import pydantic
+from models import SearchOptions, Visibility
+
+unset = SearchOptions(query="cats", pageSize=10)
+assert (unset.limit, unset.sort, unset.tags) == (20, "relevance", ["public"])
+assert unset.visibility is Visibility.Public
+assert unset.cursor is None
+assert unset.model_dump(by_alias=True, exclude_unset=True) == {"query": "cats", "pageSize": 10}
+dumped = unset.model_dump(mode="json", by_alias=True)
+assert dumped["limit"] == 20 and dumped["visibility"] == "public", dumped
+
+explicit = SearchOptions(query="cats", pageSize=10, limit=5, note=None)
+assert explicit.model_dump(by_alias=True, exclude_unset=True) == {
+    "query": "cats", "pageSize": 10, "limit": 5, "note": None,
+}
+
+invalid = [{"query": "cats"}, {"query": "cats", "pageSize": 10, "cursor": None},
+           {"query": "cats", "pageSize": 10, "limit": None}]
+for fields in invalid:
+    try:
+        SearchOptions(**fields)
+    except pydantic.ValidationError:
+        continue
+    raise AssertionError(f"{fields} should be rejected")
+"#;
+    let run = Command::new("python3")
+        .args(["-W", "error", "-c", check])
+        .current_dir(tmp.path())
+        .output()
+        .expect("failed to run python3");
+    assert!(
+        run.status.success(),
+        "field default checks failed:\n{}",
+        String::from_utf8_lossy(&run.stderr)
+    );
+}
+
+#[test]
+fn app_factory_builds_the_app_from_settings() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "app_factory": true, "formatter": false })),
+        ..Default::default()
+    };
+    let files = generate_with(ANTHROPIC, &config);
+    let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+    for path in ["app.py", "__main__.py", "main.py", "conftest.py"] {
+        assert!(paths.contains(&path), "{path} should be generated");
+    }
+
+    let app = file(&files, "app.py");
+    assert!(app.contains("class Settings(BaseSettings):"));
+    assert!(app.contains("    api_key_auth_secret: SecretStr | None = None"));
+    assert!(app.contains("    bearer_auth_secret: SecretStr | None = None"));
+    assert!(app.contains(r#"{"name": "messages", "description": "Create and manage messages"},"#));
+    assert!(app.contains("def create_app(settings: Settings) -> FastAPI:"));
+    assert!(app.contains("app.include_router(router, prefix=settings.base_path)"));
+    assert!(app.contains("@app.get(\"/healthz\", include_in_schema=False)"));
+    assert!(file(&files, "main.py").contains("app = create_app(Settings())"));
+    assert!(file(&files, "conftest.py").contains("create_app(Settings(base_path=\"\""));
+    assert!(file(&files, "pyproject.toml").contains("\"pydantic-settings>=2.4\""));
+    assert_compiles(&files);
+
+    let without = generate(ANTHROPIC);
+    assert!(!without.iter().any(|f| f.path == "app.py"));
+}
+
+#[test]
+#[ignore = "needs Python with FastAPI and pydantic-settings"]
+fn app_factory_module_imports() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "app_factory": true })),
+        ..Default::default()
+    };
+    let tmp = tempfile::tempdir().unwrap();
+    for file in generate_with(ANTHROPIC, &config) {
+        fs::write(tmp.path().join(&file.path), &file.content).unwrap();
+    }
+
+    let import = Command::new("python3")
+        .args([
+            "-c",
+            "from app import Settings, create_app; create_app(Settings(base_path='/v1'))",
+        ])
+        .current_dir(tmp.path())
+        .output()
+        .expect("failed to run python3");
+    assert!(
+        import.status.success(),
+        "importing app.py failed:\n{}",
+        String::from_utf8_lossy(&import.stderr)
+    );
+}
+
+#[test]
+fn sse_client_runtime_is_emitted_with_its_tests() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "sse_client": true, "test_runner": "pytest" })),
+        ..Default::default()
+    };
+    let files = generate_with(ANTHROPIC, &config);
+    let runtime = file(&files, "client/sse.py");
+    assert!(runtime.contains("class SSEStream(Generic[T]):"));
+    assert!(runtime.contains("class AsyncSSEStream(Generic[T]):"));
+    assert!(runtime.contains("if event.data == DONE:"));
+    assert!(file(&files, "client/__init__.py").trim().is_empty());
+    let pyproject = file(&files, "pyproject.toml");
+    let dependencies = &pyproject[..pyproject.find("[dependency-groups]").unwrap()];
+    assert!(dependencies.contains("\"httpx>=0.27\","));
+
+    let tests = file(&files, "test_client_sse.py");
+    assert!(
+        tests.contains(
+            "from .client.sse import AsyncSSEStream, ServerSentEvent, SSEStream, iter_sse"
+        )
+    );
+    assert!(tests.contains("from .models import CreateMessageStreamEvent\n"));
+    assert!(tests.contains("def test_create_message_stream_event_stream_ends_at_done():"));
+    assert_unique(&function_names(tests), "test_client_sse.py");
+    assert_compiles(&files);
+
+    // Nothing to consume without a stream.
+    let files = generate_with(PLAIN_TEXT, &config);
+    assert!(!files.iter().any(|f| f.path.starts_with("client/")));
+    assert!(!files.iter().any(|f| f.path == "test_client_sse.py"));
+}
+
+#[test]
+#[ignore = "needs Python with pydantic, httpx, pytest and pytest-asyncio"]
+fn sse_client_tests_pass() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "sse_client": true, "test_runner": "pytest" })),
+        ..Default::default()
+    };
+    let tmp = tempfile::tempdir().unwrap();
+    let package = tmp.path().join("server");
+    for file in generate_with(ANTHROPIC, &config) {
+        let dest = package.join(&file.path);
+        fs::create_dir_all(dest.parent().unwrap()).unwrap();
+        fs::write(dest, &file.content).unwrap();
+    }
+
+    let run = Command::new("python3")
+        .args([
+            "-m",
+            "pytest",
+            "-q",
+            "-p",
+            "no:cacheprovider",
+            "-o",
+            "asyncio_mode=auto",
+            "server/test_client_sse.py",
+        ])
+        .current_dir(tmp.path())
+        .output()
+        .expect("failed to run pytest");
+    assert!(
+        run.status.success(),
+        "test_client_sse.py failed:\n{}",
+        String::from_utf8_lossy(&run.stdout)
+    );
+}
+
+#[test]
+fn allof_inherit_subclasses_the_referenced_model() {
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "allof_style": "inherit" })),
+        ..Default::default()
+    };
+    let files = generate_with(PETSTORE_POLY, &config);
+    let models = file(&files, "models.py");
+    let ext = models
+        .split("class ExtendedErrorModel(ErrorModel):")
+        .nth(1)
+        .unwrap();
+    let ext = &ext[..ext.find("model_config").unwrap()];
+    // Only the extension's own fields; the rest are inherited.
+    assert!(ext.contains("root_cause: str = Field(alias=\"rootCause\")"));
+    assert!(!ext.contains("message"));
+    assert!(
+        models.find("class ErrorModel(BaseModel):").unwrap()
+            < models
+                .find("class ExtendedErrorModel(ErrorModel):")
+                .unwrap()
+    );
+    assert_compiles(&files);
+}
+
+#[test]
+fn schemas_are_emitted_after_what_they_reference() {
+    let files = generate(PETSTORE_POLY);
+    let models = file(&files, "models.py");
+    let pet = models.find("\nPet = ").unwrap();
+    assert!(models.find("class Cat(").unwrap() < pet);
+    assert!(models.find("class Dog(").unwrap() < pet);
+    assert_compiles(&files);
+}
+
+#[test]
+fn cyclic_aliases_quote_forward_refs() {
+    let files = generate(
+        r##"
+openapi: "3.1.0"
+info: { title: Trees, version: "1.0" }
+paths:
+  /forest:
+    get:
+      operationId: getForest
+      responses:
+        "200":
+          description: OK
+          content:
+            application/json:
+              schema: { $ref: "#/components/schemas/Forest" }
+components:
+  schemas:
+    Forest: { type: array, items: { $ref: "#/components/schemas/Tree" } }
+    Tree:
+      oneOf:
+        - $ref: "#/components/schemas/Node"
+        - $ref: "#/components/schemas/Leaf"
+    Node:
+      type: object
+      required: [children]
+      properties: { children: { $ref: "#/components/schemas/Forest" } }
+    Leaf: { type: object, properties: { value: { type: string } } }
+"##,
+    );
+    let models = file(&files, "models.py");
+    // Node's annotations are lazy, so it goes first; the aliases follow it.
+    assert!(models.contains("Forest = list[\"Tree\"]"));
+    assert!(models.contains("Tree = Node | Leaf"));
+    assert!(models.find("class Node(").unwrap() < models.find("Forest = ").unwrap());
+    assert_compiles(&files);
+}
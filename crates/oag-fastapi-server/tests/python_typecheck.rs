@@ -0,0 +1,85 @@
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, parse, postprocess, transform};
+use oag_fastapi_server::FastapiServerGenerator;
+
+const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
+const ANTHROPIC: &str = include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml");
+
+/// Write the generated server into a temp dir, byte-compile it, and type-check
+/// its modules with `mypy --strict`, or pyright when mypy isn't installed.
+/// Both run against whatever FastAPI and pydantic the current Python has.
+fn typecheck(yaml: &str) {
+    let spec = parse::from_yaml(yaml).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    let mut files = FastapiServerGenerator
+        .generate(&ir, &GeneratorConfig::default())
+        .unwrap();
+    postprocess::normalize_files(&FastapiServerGenerator, &mut files);
+    assert!(files.iter().any(|f| f.path == "__init__.py"));
+    assert!(files.iter().any(|f| f.path == "py.typed"));
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+    for file in &files {
+        let dest = dir.join(&file.path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&dest, &file.content).unwrap();
+    }
+
+    let compile = run(dir, "python3", &["-m", "compileall", "-q", "."]);
+    assert_success("python3 -m compileall", &compile);
+
+    // Modules are checked by their file name, as the generated absolute
+    // imports (`from models import ...`) expect.
+    let modules: Vec<&str> = files
+        .iter()
+        .map(|f| f.path.as_str())
+        .filter(|path| path.ends_with(".py") && *path != "__init__.py" && !path.contains('/'))
+        .collect();
+
+    let mypy = run(dir, "python3", &["-m", "mypy", "--version"]);
+    if mypy.status.success() {
+        let mut args = vec!["-m", "mypy", "--strict", "--explicit-package-bases"];
+        args.extend(&modules);
+        assert_success("mypy --strict", &run(dir, "python3", &args));
+    } else {
+        let pyright = run(dir, "pyright", &modules);
+        assert_success("pyright", &pyright);
+    }
+}
+
+fn run(dir: &Path, program: &str, args: &[&str]) -> Output {
+    Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap_or_else(|e| panic!("failed to run {program}: {e}"))
+}
+
+fn assert_success(what: &str, output: &Output) {
+    assert!(
+        output.status.success(),
+        "{what} failed:\n{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+#[ignore = "needs Python with FastAPI, pydantic and mypy (or pyright)"]
+fn generated_python_petstore_typechecks() {
+    typecheck(PETSTORE);
+}
+
+#[test]
+#[ignore = "needs Python with FastAPI, pydantic and mypy (or pyright)"]
+fn generated_python_anthropic_typechecks() {
+    typecheck(ANTHROPIC);
+}
@@ -0,0 +1,79 @@
+use oag_core::config::{ConfigWarning, GeneratorConfig, GeneratorId};
+use oag_core::ir::IrSpec;
+use oag_core::{CodeGenerator, GeneratedFile, GeneratorError};
+use serde::Serialize;
+
+use crate::schema::{Json, schema_document, schema_file_name};
+
+/// JSON Schema generator: one draft 2020-12 document per component schema.
+pub struct JsonSchemaGenerator;
+
+impl CodeGenerator for JsonSchemaGenerator {
+    fn id(&self) -> GeneratorId {
+        GeneratorId::JsonSchema
+    }
+
+    fn generate(
+        &self,
+        ir: &IrSpec,
+        _config: &GeneratorConfig,
+    ) -> Result<Vec<GeneratedFile>, GeneratorError> {
+        let mut files = Vec::with_capacity(ir.schemas.len() + 1);
+        let mut index = Vec::with_capacity(ir.schemas.len());
+        for schema in &ir.schemas {
+            let name = &schema.name().pascal_case;
+            let path = format!("schemas/{}", schema_file_name(name));
+            index.push(Json::object([
+                ("name", name.as_str().into()),
+                ("path", path.as_str().into()),
+            ]));
+            files.push(GeneratedFile {
+                path,
+                content: to_json(&schema_document(schema)),
+            });
+        }
+
+        files.push(GeneratedFile {
+            path: "index.json".to_string(),
+            content: to_json(&Json::object([
+                ("title", ir.info.title.as_str().into()),
+                ("version", ir.info.version.as_str().into()),
+                ("schemas", Json::Array(index)),
+            ])),
+        });
+        Ok(files)
+    }
+
+    /// Only `output`, `files` and `naming` apply to schema documents.
+    fn validate_config(&self, config: &GeneratorConfig) -> Vec<ConfigWarning> {
+        config.unsupported_options(self.id(), &[])
+    }
+}
+
+/// Pretty-printed, with the trailing newline formatters expect.
+fn to_json(value: &impl Serialize) -> String {
+    let mut json = serde_json::to_string_pretty(value).expect("JSON values always serialize");
+    json.push('\n');
+    json
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validate_config_flags_code_options() {
+        let config = GeneratorConfig {
+            output: "schemas".to_string(),
+            route_table: true,
+            scaffold: Some(serde_json::json!({ "package_name": "schemas" })),
+            ..Default::default()
+        };
+        let options: Vec<String> = JsonSchemaGenerator
+            .validate_config(&config)
+            .into_iter()
+            .map(|w| w.option)
+            .collect();
+        assert_eq!(options, ["route_table", "scaffold"]);
+    }
+}
@@ -0,0 +1,4 @@
+pub mod generator;
+pub mod schema;
+
+pub use generator::JsonSchemaGenerator;
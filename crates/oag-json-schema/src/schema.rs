@@ -0,0 +1,293 @@
+use indexmap::IndexMap;
+use oag_core::ir::{IrMapKey, IrSchema, IrType};
+use serde::Serialize;
+use serde_json::Value;
+
+/// The dialect every emitted document declares.
+pub const DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// A JSON value whose objects keep their insertion order — `serde_json::Map`
+/// sorts its keys — so documents read `$schema`, `title`, `type`, then
+/// `properties` in the spec's order.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum Json {
+    Scalar(Value),
+    Array(Vec<Json>),
+    Object(IndexMap<String, Json>),
+}
+
+impl Json {
+    pub fn object<'a>(members: impl IntoIterator<Item = (&'a str, Json)>) -> Self {
+        Json::Object(
+            members
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    pub fn insert(&mut self, key: &str, value: impl Into<Json>) {
+        if let Json::Object(members) = self {
+            members.insert(key.to_string(), value.into());
+        }
+    }
+}
+
+impl<T: Into<Value>> From<T> for Json {
+    fn from(value: T) -> Self {
+        Json::Scalar(value.into())
+    }
+}
+
+/// The file a schema is written to, relative to the `schemas/` directory;
+/// also its `$id` and what `$ref`s to it point at.
+pub fn schema_file_name(name: &str) -> String {
+    format!("{name}.schema.json")
+}
+
+/// A standalone draft 2020-12 document for `schema`. References to other
+/// schemas are `$ref`s to their sibling files.
+pub fn schema_document(schema: &IrSchema) -> Json {
+    let name = &schema.name().pascal_case;
+    let mut doc = Json::object([
+        ("$schema", DIALECT.into()),
+        ("$id", schema_file_name(name).into()),
+        ("title", name.as_str().into()),
+    ]);
+
+    let (description, body) = match schema {
+        IrSchema::Object(obj) => {
+            let mut properties = IndexMap::new();
+            for field in &obj.fields {
+                let mut property = ir_type_to_json_schema(&field.field_type);
+                if let Some(description) = &field.description {
+                    property.insert("description", description.as_str());
+                }
+                if field.read_only {
+                    property.insert("readOnly", true);
+                }
+                if field.write_only {
+                    property.insert("writeOnly", true);
+                }
+                if field.deprecated {
+                    property.insert("deprecated", true);
+                }
+                properties.insert(field.original_name.clone(), property);
+            }
+            let required: Vec<&str> = obj
+                .fields
+                .iter()
+                .filter(|f| f.required)
+                .map(|f| f.original_name.as_str())
+                .collect();
+
+            let mut body = Json::object([
+                ("type", "object".into()),
+                ("properties", Json::Object(properties)),
+            ]);
+            if !required.is_empty() {
+                body.insert("required", required);
+            }
+            if let Some(additional) = &obj.additional_properties {
+                body.insert("additionalProperties", ir_type_to_json_schema(additional));
+            }
+            (&obj.description, body)
+        }
+        IrSchema::Enum(e) => (
+            &e.description,
+            Json::object([
+                ("type", "string".into()),
+                ("enum", e.variants.clone().into()),
+            ]),
+        ),
+        IrSchema::Alias(alias) => (&alias.description, ir_type_to_json_schema(&alias.target)),
+        IrSchema::Union(union) => {
+            let variants = union.variants.iter().map(ir_type_to_json_schema).collect();
+            let mut body = Json::object([("oneOf", Json::Array(variants))]);
+            // OpenAPI's `discriminator`, passed through for tools that read it;
+            // JSON Schema validators ignore unknown keywords.
+            if let Some(discriminator) = &union.discriminator {
+                let mut value =
+                    Json::object([("propertyName", discriminator.property_name.as_str().into())]);
+                if !discriminator.mapping.is_empty() {
+                    let mapping = discriminator
+                        .mapping
+                        .iter()
+                        .map(|(tag, name)| (tag.as_str(), schema_file_name(name).into()));
+                    value.insert("mapping", Json::object(mapping));
+                }
+                body.insert("discriminator", value);
+            }
+            (&union.description, body)
+        }
+    };
+
+    if let Some(description) = description {
+        doc.insert("description", description.as_str());
+    }
+    if let (Json::Object(doc), Json::Object(body)) = (&mut doc, body) {
+        doc.extend(body);
+    }
+    doc
+}
+
+/// The JSON Schema for an IR type.
+pub fn ir_type_to_json_schema(ir_type: &IrType) -> Json {
+    let typed = |ty: &str| Json::object([("type", ty.into())]);
+    match ir_type {
+        IrType::String => typed("string"),
+        IrType::StringLiteral(s) => Json::object([("const", s.as_str().into())]),
+        IrType::Number => typed("number"),
+        IrType::Integer => typed("integer"),
+        IrType::Boolean => typed("boolean"),
+        IrType::Null => typed("null"),
+        IrType::DateTime => {
+            Json::object([("type", "string".into()), ("format", "date-time".into())])
+        }
+        IrType::Binary => Json::object([
+            ("type", "string".into()),
+            ("contentMediaType", "application/octet-stream".into()),
+        ]),
+        IrType::Any | IrType::Void => Json::object([]),
+        IrType::Ref(name) => Json::object([("$ref", schema_file_name(name).into())]),
+        IrType::Array(inner) => Json::object([
+            ("type", "array".into()),
+            ("items", ir_type_to_json_schema(inner)),
+        ]),
+        IrType::Map(value_type, key) => {
+            let mut schema = Json::object([
+                ("type", "object".into()),
+                ("additionalProperties", ir_type_to_json_schema(value_type)),
+            ]);
+            let pattern = match key {
+                Some(IrMapKey::Integer) => Some("^-?[0-9]+$"),
+                Some(IrMapKey::Pattern(pattern)) => Some(pattern.as_str()),
+                None => None,
+            };
+            if let Some(pattern) = pattern {
+                schema.insert("propertyNames", Json::object([("pattern", pattern.into())]));
+            }
+            schema
+        }
+        IrType::Object(fields) => {
+            let properties = fields
+                .iter()
+                .map(|(name, ty, _)| (name.as_str(), ir_type_to_json_schema(ty)));
+            let required: Vec<&str> = fields
+                .iter()
+                .filter(|(_, _, required)| *required)
+                .map(|(name, _, _)| name.as_str())
+                .collect();
+            let mut schema = Json::object([
+                ("type", "object".into()),
+                ("properties", Json::object(properties)),
+            ]);
+            if !required.is_empty() {
+                schema.insert("required", required);
+            }
+            schema
+        }
+        IrType::Union(variants) => Json::object([(
+            "anyOf",
+            Json::Array(variants.iter().map(ir_type_to_json_schema).collect()),
+        )]),
+        IrType::Intersection(parts) => Json::object([(
+            "allOf",
+            Json::Array(parts.iter().map(ir_type_to_json_schema).collect()),
+        )]),
+        // Types added to the IR later accept any value until handled.
+        _ => Json::object([]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    fn documents(yaml: &str) -> Vec<Value> {
+        let spec = oag_core::parse::from_yaml(yaml).unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        ir.schemas
+            .iter()
+            .map(|s| serde_json::to_value(schema_document(s)).unwrap())
+            .collect()
+    }
+
+    fn schema_value(ir_type: &IrType) -> Value {
+        serde_json::to_value(ir_type_to_json_schema(ir_type)).unwrap()
+    }
+
+    fn document<'a>(docs: &'a [Value], title: &str) -> &'a Value {
+        docs.iter().find(|d| d["title"] == title).unwrap()
+    }
+
+    #[test]
+    fn objects_enums_and_refs() {
+        let docs = documents(include_str!("../../oag-core/tests/fixtures/examples.yaml"));
+
+        assert_eq!(
+            document(&docs, "PetStatus"),
+            &json!({
+                "$schema": DIALECT,
+                "$id": "PetStatus.schema.json",
+                "title": "PetStatus",
+                "type": "string",
+                "enum": ["available", "pending", "sold"],
+            })
+        );
+        assert_eq!(
+            document(&docs, "NewPet"),
+            &json!({
+                "$schema": DIALECT,
+                "$id": "NewPet.schema.json",
+                "title": "NewPet",
+                "type": "object",
+                "properties": {
+                    "name": { "type": "string" },
+                    "status": { "$ref": "PetStatus.schema.json" },
+                    "tags": { "type": "array", "items": { "type": "string" } },
+                },
+                "required": ["name"],
+            })
+        );
+    }
+
+    #[test]
+    fn unions_pass_the_discriminator_through() {
+        let docs = documents(include_str!(
+            "../../oag-core/tests/fixtures/petstore-polymorphic.yaml"
+        ));
+        let pet = document(&docs, "Pet");
+        assert_eq!(
+            pet["oneOf"],
+            json!([{ "$ref": "Cat.schema.json" }, { "$ref": "Dog.schema.json" }])
+        );
+        assert_eq!(pet["discriminator"]["propertyName"], "petType");
+        assert_eq!(pet["discriminator"]["mapping"]["cat"], "Cat.schema.json");
+    }
+
+    #[test]
+    fn maps_and_nullable_types() {
+        assert_eq!(
+            schema_value(&IrType::Map(
+                Box::new(IrType::Integer),
+                Some(IrMapKey::Integer)
+            )),
+            json!({
+                "type": "object",
+                "additionalProperties": { "type": "integer" },
+                "propertyNames": { "pattern": "^-?[0-9]+$" },
+            })
+        );
+        assert_eq!(
+            schema_value(&IrType::Union(vec![
+                IrType::Ref("Pet".into()),
+                IrType::Null
+            ])),
+            json!({ "anyOf": [{ "$ref": "Pet.schema.json" }, { "type": "null" }] })
+        );
+    }
+}
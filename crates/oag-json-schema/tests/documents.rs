@@ -0,0 +1,101 @@
+use std::collections::HashSet;
+
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, GeneratedFile, parse, transform};
+use oag_json_schema::JsonSchemaGenerator;
+use oag_json_schema::schema::DIALECT;
+use serde_json::Value;
+
+const FIXTURES: &[&str] = &[
+    include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml"),
+    include_str!("../../oag-core/tests/fixtures/petstore-polymorphic.yaml"),
+    include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml"),
+    include_str!("../../oag-core/tests/fixtures/map-keys.yaml"),
+    include_str!("../../oag-core/tests/fixtures/nullable-unions.yaml"),
+];
+
+fn generate(yaml: &str) -> Vec<GeneratedFile> {
+    let ir = transform::transform(&parse::from_yaml(yaml).unwrap()).unwrap();
+    JsonSchemaGenerator
+        .generate(&ir, &GeneratorConfig::default())
+        .unwrap()
+}
+
+/// Every `$ref` in `value`.
+fn refs<'a>(value: &'a Value, out: &mut Vec<&'a str>) {
+    match value {
+        Value::Object(members) => {
+            for (key, member) in members {
+                match (key.as_str(), member) {
+                    ("$ref", Value::String(target)) => out.push(target),
+                    _ => refs(member, out),
+                }
+            }
+        }
+        Value::Array(items) => items.iter().for_each(|item| refs(item, out)),
+        _ => {}
+    }
+}
+
+#[test]
+fn documents_parse_back_and_link_to_each_other() {
+    for yaml in FIXTURES {
+        let files = generate(yaml);
+        let (index, schemas): (Vec<_>, Vec<_>) = files.iter().partition(|f| f.path == "index.json");
+
+        let ids: HashSet<&str> = schemas
+            .iter()
+            .map(|f| f.path.strip_prefix("schemas/").unwrap())
+            .collect();
+        for file in &schemas {
+            let doc: Value = serde_json::from_str(&file.content).unwrap();
+            assert_eq!(doc["$schema"], DIALECT, "{}", file.path);
+            assert_eq!(doc["$id"], file.path["schemas/".len()..], "{}", file.path);
+            assert!(doc["title"].is_string(), "{}", file.path);
+            assert!(
+                ["type", "oneOf", "anyOf", "allOf", "$ref", "const"]
+                    .iter()
+                    .any(|k| doc.get(k).is_some())
+                    || doc.as_object().unwrap().len() == 3,
+                "{} has no schema keywords",
+                file.path
+            );
+
+            let mut targets = Vec::new();
+            refs(&doc, &mut targets);
+            for target in targets {
+                assert!(
+                    ids.contains(target),
+                    "{} links to missing {target}",
+                    file.path
+                );
+            }
+        }
+
+        let index: Value = serde_json::from_str(&index[0].content).unwrap();
+        let listed: Vec<&str> = index["schemas"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|s| s["path"].as_str().unwrap())
+            .collect();
+        let emitted: Vec<&str> = schemas.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(listed, emitted);
+    }
+}
+
+#[test]
+fn properties_keep_the_spec_order() {
+    let files = generate(FIXTURES[0]);
+    let pet = files
+        .iter()
+        .find(|f| f.path == "schemas/Pet.schema.json")
+        .unwrap();
+    assert!(pet.content.starts_with(
+        "{\n  \"$schema\": \"https://json-schema.org/draft/2020-12/schema\",\n  \"$id\": \"Pet.schema.json\",\n  \"title\": \"Pet\",\n"
+    ));
+    // Spec order, not alphabetical.
+    let tag = pet.content.find("\"tag\": {").unwrap();
+    let status = pet.content.find("\"status\": {").unwrap();
+    assert!(tag < status);
+}
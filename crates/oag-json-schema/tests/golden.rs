@@ -0,0 +1,86 @@
+//! Golden files: every file the generator emits for the main fixtures, one
+//! snapshot per file under `snapshots/golden/{fixture}/`. Review changes with
+//! `cargo insta review` (see CONTRIBUTING.md).
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, GeneratedFile, parse, postprocess, transform};
+use oag_json_schema::JsonSchemaGenerator;
+
+const FIXTURES: &[(&str, &str)] = &[
+    (
+        "petstore",
+        include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml"),
+    ),
+    (
+        "sse-chat",
+        include_str!("../../oag-core/tests/fixtures/sse-chat.yaml"),
+    ),
+    (
+        "anthropic",
+        include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml"),
+    ),
+    (
+        "petstore-polymorphic",
+        include_str!("../../oag-core/tests/fixtures/petstore-polymorphic.yaml"),
+    ),
+];
+
+/// The files as written, with default options.
+fn generate(yaml: &str) -> Vec<GeneratedFile> {
+    let ir = transform::transform(&parse::from_yaml(yaml).unwrap()).unwrap();
+    let mut files = JsonSchemaGenerator
+        .generate(&ir, &GeneratorConfig::default())
+        .unwrap();
+    postprocess::normalize_files(&JsonSchemaGenerator, &mut files);
+    files
+}
+
+/// `schemas/Pet.schema.json` → `schemas__Pet.schema.json`.
+fn snapshot_name(path: &str) -> String {
+    path.replace('/', "__")
+}
+
+#[test]
+fn generated_files_match_their_snapshots() {
+    for (fixture, yaml) in FIXTURES {
+        let files = generate(yaml);
+        let contents = |files: &[GeneratedFile]| -> Vec<(String, String)> {
+            files
+                .iter()
+                .map(|f| (f.path.clone(), f.content.clone()))
+                .collect()
+        };
+        assert_eq!(
+            contents(&files),
+            contents(&generate(yaml)),
+            "{fixture}: output isn't deterministic"
+        );
+
+        let dir = format!("snapshots/golden/{fixture}");
+        let mut settings = insta::Settings::clone_current();
+        settings.set_snapshot_path(&dir);
+        settings.set_prepend_module_to_snapshot(false);
+        settings.set_omit_expression(true);
+        settings.bind(|| {
+            for file in &files {
+                insta::assert_snapshot!(snapshot_name(&file.path), file.content);
+            }
+        });
+
+        // A file the generator stopped emitting leaves a stale snapshot.
+        let expected: BTreeSet<String> = files
+            .iter()
+            .map(|f| format!("{}.snap", snapshot_name(&f.path)))
+            .collect();
+        let stored: BTreeSet<String> = fs::read_dir(Path::new("tests").join(&dir))
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+            .filter(|name| name.ends_with(".snap"))
+            .collect();
+        assert_eq!(stored, expected, "{fixture}: stale or missing snapshots");
+    }
+}
@@ -0,0 +1,153 @@
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::config::AuthConfig;
+use oag_core::ir::IrSpec;
+
+use super::bundled::{CLIENT_SECTION, strip_auto_generated_header};
+use super::source_path;
+
+/// Whether any operation is secured by a bearer scheme, so the client needs
+/// `auth.ts`.
+pub fn has_bearer_auth(ir: &IrSpec) -> bool {
+    ir.operations.iter().any(|op| op.bearer_auth.is_some())
+}
+
+/// Emit `auth.ts` — `BearerToken` and the `BearerAuth` token source the
+/// client sends `Authorization` headers from. JWT expiry parsing is only
+/// included when `auth.refresh_before_expiry_s` is set and a bearer scheme
+/// declares `bearerFormat: JWT`.
+pub fn emit_auth(ir: &IrSpec, auth: &AuthConfig) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template("auth.ts.j2", include_str!("../../templates/auth.ts.j2"))
+        .expect("template should be valid");
+    let tmpl = env.get_template("auth.ts.j2").unwrap();
+    tmpl.render(context! {
+        refresh_before_expiry_s => refresh_before_expiry(ir, auth),
+    })
+    .expect("render should succeed")
+}
+
+/// Emit `auth.test.ts` — vitest coverage of `BearerAuth`, including its JWT
+/// refresh when `auth.ts` has one. `module` is where `auth.ts`'s exports are
+/// imported from: `./auth`, or `./index` in a bundle.
+pub fn emit_auth_tests(ir: &IrSpec, auth: &AuthConfig, module: &str) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "auth.test.ts.j2",
+        include_str!("../../templates/auth.test.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("auth.test.ts.j2").unwrap();
+    tmpl.render(context! {
+        refresh => refresh_before_expiry(ir, auth).is_some(),
+        module => module,
+    })
+    .expect("render should succeed")
+}
+
+/// `auth.refresh_before_expiry_s`, when a bearer scheme issues JWTs.
+fn refresh_before_expiry(ir: &IrSpec, auth: &AuthConfig) -> Option<u64> {
+    let has_jwt = ir
+        .operations
+        .iter()
+        .filter_map(|op| op.bearer_auth.as_ref())
+        .any(|bearer| bearer.is_jwt());
+    auth.refresh_before_expiry_s.filter(|_| has_jwt)
+}
+
+/// Add `auth.ts` to `files` when the client needs it. A `bundled` index
+/// (`index.ts`, `index.tsx`) gets it inlined ahead of the client instead.
+pub fn add_auth_module(
+    files: &mut Vec<GeneratedFile>,
+    ir: &IrSpec,
+    auth: &AuthConfig,
+    source_dir: &str,
+    index: &str,
+    bundled: bool,
+) {
+    if !has_bearer_auth(ir) {
+        return;
+    }
+    let content = emit_auth(ir, auth);
+    if !bundled {
+        files.push(GeneratedFile {
+            path: source_path(source_dir, "auth.ts"),
+            content,
+        });
+        return;
+    }
+    let index_path = source_path(source_dir, index);
+    let Some(index) = files.iter_mut().find(|f| f.path == index_path) else {
+        return;
+    };
+    let section = format!(
+        "// === Auth ===\n\n{}\n",
+        strip_auto_generated_header(&content)
+    );
+    match index.content.find(CLIENT_SECTION) {
+        Some(at) => index.content.insert_str(at, &section),
+        None => index.content.push_str(&section),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bearer_ir() -> IrSpec {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/bearer-auth.yaml"
+        ))
+        .unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn test_jwt_refresh_needs_config_and_a_jwt_scheme() {
+        let ir = bearer_ir();
+        assert!(has_bearer_auth(&ir));
+
+        let plain = emit_auth(&ir, &AuthConfig::default());
+        assert!(plain.contains("export type BearerToken = string | (() => Promise<string>);"));
+        assert!(plain.contains("    return source();"));
+        assert!(!plain.contains("jwtExpiry"));
+
+        let refresh = AuthConfig {
+            refresh_before_expiry_s: Some(45),
+        };
+        let content = emit_auth(&ir, &refresh);
+        assert!(content.contains("export const REFRESH_BEFORE_EXPIRY_S = 45;"));
+        assert!(content.contains("export function jwtExpiry(token: string): number | undefined {"));
+        assert!(content.contains("this.pending = this.refresh(source)"));
+
+        // Without `bearerFormat: JWT` there's no `exp` to read.
+        let mut opaque = ir.clone();
+        for op in &mut opaque.operations {
+            if let Some(bearer) = &mut op.bearer_auth {
+                bearer.format = None;
+            }
+        }
+        assert!(!emit_auth(&opaque, &refresh).contains("jwtExpiry"));
+    }
+
+    #[test]
+    fn test_auth_tests_cover_the_emitted_runtime() {
+        let ir = bearer_ir();
+        let refresh = AuthConfig {
+            refresh_before_expiry_s: Some(45),
+        };
+        let content = emit_auth_tests(&ir, &refresh, "./auth");
+        assert!(content.contains(
+            "import { BearerAuth, jwtExpiry, REFRESH_BEFORE_EXPIRY_S } from \"./auth\";"
+        ));
+        assert!(content.contains("function unsignedJwt("));
+        assert!(content.contains("it(\"shares one refresh between concurrent requests\""));
+
+        let content = emit_auth_tests(&ir, &AuthConfig::default(), "./index");
+        assert!(content.contains("import { BearerAuth } from \"./index\";"));
+        assert!(!content.contains("jwtExpiry"));
+        assert!(content.contains("it(\"asks a provider for every token\""));
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::HashSet;
+
+use minijinja::{Environment, context};
+use oag_core::GeneratorError;
+use oag_core::config::ParamsStyle;
+use oag_core::ir::{HttpMethod, IrReturnType, IrSpec, IrType};
+
+use crate::emitters::client::{collect_types_from_ir_type, method_arguments};
+use crate::emitters::streaming::{HelperModules, add_imports, import_contexts};
+use crate::type_mapper::{ir_type_to_ts, response_ts_type};
+
+/// A client method whose JSON body is an array of items, as its
+/// `{method}Batched` helper calls it.
+pub struct BatchMethod {
+    /// The operation's PascalCase name, e.g. `CreatePets`.
+    pub name: String,
+    /// The client method, e.g. `createPets`.
+    pub method: String,
+    /// The type of one item of the body, e.g. `NewPet`.
+    pub item_type: String,
+    /// What the method resolves to for one chunk.
+    pub response_type: String,
+    /// The method's parameters before `options`, the body as `items` and
+    /// optional ones as `T | undefined` so they can precede the helper's
+    /// `options`.
+    pub parts: Vec<String>,
+    /// The argument names, in order, the body as `chunk`.
+    pub args: Vec<String>,
+    /// Types from the types module the helper's signature uses.
+    pub types: Vec<String>,
+}
+
+/// The client methods that send an array as their JSON body, once per method
+/// name. GET and SSE operations, and JSON Patch or merge patch bodies, are
+/// left out.
+pub fn batch_methods(ir: &IrSpec, params_style: ParamsStyle) -> Vec<BatchMethod> {
+    let mut seen = HashSet::new();
+    ir.operations
+        .iter()
+        .filter(|op| op.method != HttpMethod::Get)
+        .filter_map(|op| {
+            let body = op.request_body.as_ref()?;
+            let IrType::Array(item) = &body.body_type else {
+                return None;
+            };
+            if !body.is_json() || body.patch_format().is_some() {
+                return None;
+            }
+            let (response_type, mut types) = match &op.return_type {
+                IrReturnType::Standard(resp) => {
+                    let mut types = HashSet::new();
+                    collect_types_from_ir_type(&resp.response_type, &mut types);
+                    (response_ts_type(resp), types)
+                }
+                IrReturnType::Void => ("void".to_string(), HashSet::new()),
+                IrReturnType::Sse(_) => return None,
+            };
+            let method = op.name.camel_case.clone();
+            if !seen.insert(method.clone()) {
+                return None;
+            }
+            let arguments = method_arguments(op, params_style);
+            types.extend(arguments.types);
+            let (parts, args) = arguments
+                .parts
+                .iter()
+                .map(|part| match part.split_once(": ") {
+                    Some(("body" | "body?", ty)) => (format!("items: {ty}"), "chunk".to_string()),
+                    Some((name, ty)) => match name.strip_suffix('?') {
+                        Some(name) => (format!("{name}: {ty} | undefined"), name.to_string()),
+                        None => (part.clone(), name.to_string()),
+                    },
+                    None => (part.clone(), part.clone()),
+                })
+                .unzip();
+            let mut types: Vec<String> = types.into_iter().collect();
+            types.sort();
+            Some(BatchMethod {
+                name: op.name.pascal_case.clone(),
+                method,
+                item_type: ir_type_to_ts(item),
+                response_type,
+                parts,
+                args,
+                types,
+            })
+        })
+        .collect()
+}
+
+/// Emit `batch.ts`: `runBatched`, which sends items through a call in chunks
+/// with limited concurrency and collects each chunk's result or error, and a
+/// `{method}Batched` helper for each client method taking an array body.
+/// `None` when there are no such methods.
+pub fn emit_batch_helpers(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    modules: &HelperModules,
+) -> Result<Option<String>, GeneratorError> {
+    let methods = batch_methods(ir, params_style);
+    if methods.is_empty() {
+        return Ok(None);
+    }
+
+    let mut imports = Vec::new();
+    add_imports(
+        &mut imports,
+        modules.client,
+        ["ApiClient".to_string(), "RequestOptions".to_string()],
+    );
+    add_imports(
+        &mut imports,
+        modules.types,
+        methods.iter().flat_map(|m| m.types.iter().cloned()),
+    );
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template("batch.ts.j2", include_str!("../../templates/batch.ts.j2"))
+        .expect("template should be valid");
+    let tmpl = env.get_template("batch.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            imports => import_contexts(imports),
+            helpers => methods
+                .iter()
+                .map(|m| context! {
+                    method => m.method,
+                    item_type => m.item_type,
+                    response_type => m.response_type,
+                    parts => m.parts,
+                    args => m.args.iter().map(String::as_str).chain(["options"]).collect::<Vec<_>>().join(", "),
+                })
+                .collect::<Vec<_>>(),
+        })
+        .map_err(|e| GeneratorError::Render(e.to_string()))?;
+    Ok(Some(content))
+}
+
+/// Emit `batch.test.ts`: chunking, concurrency limits and error collection of
+/// `runBatched`, and each `{method}Batched` against a stubbed client. `None`
+/// when there is no `batch.ts`.
+pub fn emit_batch_tests(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    modules: &HelperModules,
+) -> Result<Option<String>, GeneratorError> {
+    let methods = batch_methods(ir, params_style);
+    if methods.is_empty() {
+        return Ok(None);
+    }
+
+    let mut imports = Vec::new();
+    add_imports(&mut imports, modules.client, ["ApiClient".to_string()]);
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "batch.test.ts.j2",
+        include_str!("../../templates/batch.test.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("batch.test.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            imports => import_contexts(imports),
+            helpers => methods
+                .iter()
+                .map(|m| {
+                    let test_args: Vec<&str> = m
+                        .args
+                        .iter()
+                        .map(|arg| if arg == "chunk" { "items" } else { "undefined as never" })
+                        .collect();
+                    context! {
+                        method => m.method,
+                        test_args => test_args.join(", "),
+                        chunk_index => m.args.iter().position(|arg| arg == "chunk"),
+                    }
+                })
+                .collect::<Vec<_>>(),
+        })
+        .map_err(|e| GeneratorError::Render(e.to_string()))?;
+    Ok(Some(content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch_endpoints() -> IrSpec {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/batch-endpoints.yaml"
+        ))
+        .unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    fn modular() -> HelperModules<'static> {
+        HelperModules {
+            client: "./client",
+            sse: "./sse",
+            types: "./types",
+        }
+    }
+
+    #[test]
+    fn only_array_bodies_get_batched_helpers() {
+        let methods = batch_methods(&batch_endpoints(), ParamsStyle::Positional);
+        let names: Vec<&str> = methods.iter().map(|m| m.method.as_str()).collect();
+        assert_eq!(names, ["createPets", "addStoreTags"]);
+        assert_eq!(methods[0].item_type, "NewPet");
+        assert_eq!(methods[0].response_type, "BatchSummary");
+        assert_eq!(methods[1].item_type, "string");
+        assert_eq!(methods[1].response_type, "void");
+    }
+
+    #[test]
+    fn batched_helpers_forward_the_method_arguments() {
+        let content = emit_batch_helpers(&batch_endpoints(), ParamsStyle::Positional, &modular())
+            .unwrap()
+            .unwrap();
+        assert!(content.contains("import type { ApiClient, RequestOptions } from \"./client\";"));
+        assert!(content.contains("import type { BatchSummary, NewPet } from \"./types\";"));
+        assert!(content.contains("export async function runBatched<T, R>("));
+        assert!(content.contains(
+            "export function createPetsBatched(\n  client: ApiClient,\n  items: NewPet[],\n  options?: BatchOptions & RequestOptions,\n): Promise<BatchResult<NewPet, BatchSummary>> {\n  return runBatched(items, (chunk) => client.createPets(chunk, options), options);"
+        ));
+        assert!(content.contains(
+            "  storeId: string,\n  items: string[],\n  dryRun: boolean | undefined,\n  options?: BatchOptions & RequestOptions,\n): Promise<BatchResult<string, void>> {"
+        ));
+        assert!(content.contains("client.addStoreTags(storeId, chunk, dryRun, options)"));
+
+        let tests = emit_batch_tests(&batch_endpoints(), ParamsStyle::Positional, &modular())
+            .unwrap()
+            .unwrap();
+        assert!(tests.contains(
+            "import { createPetsBatched, addStoreTagsBatched, chunkItems, runBatched } from \"./batch\";"
+        ));
+        assert!(tests.contains(
+            "await addStoreTagsBatched(client, undefined as never, items, undefined as never, options);"
+        ));
+        assert!(tests.contains("args[1])).toEqual([[0, 1], [2, 3], [4]]);"));
+    }
+
+    #[test]
+    fn object_params_precede_the_items() {
+        let content = emit_batch_helpers(&batch_endpoints(), ParamsStyle::Object, &modular())
+            .unwrap()
+            .unwrap();
+        assert!(content.contains(
+            "  storeId: string,\n  items: string[],\n  params: AddStoreTagsParams | undefined,\n"
+        ));
+        assert!(content.contains("client.addStoreTags(storeId, chunk, params, options)"));
+    }
+
+    #[test]
+    fn specs_without_array_bodies_have_no_batch_module() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert!(
+            emit_batch_helpers(&ir, ParamsStyle::Positional, &modular())
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -1,13 +1,46 @@
+use oag_core::config::{ParamsStyle, PropertyNaming, SseTransport};
 use oag_core::ir::IrSpec;
 
 use crate::emitters;
 
 /// Emit a single `index.ts` file that bundles types + sse + client together.
 /// Strips relative imports between modules since everything is inlined.
-pub fn emit_bundled(ir: &IrSpec, no_jsdoc: bool) -> String {
-    let types_content = emitters::types::emit_types(ir);
-    let sse_content = emitters::sse::emit_sse();
-    let client_content = emitters::client::emit_client(ir, no_jsdoc);
+///
+/// With `sse_package`, the SSE runtime is imported from and re-exported from
+/// that package instead of inlined. `property_naming` and `debug_logging` are
+/// passed on to [`emit_client`](emitters::client::emit_client); the codecs
+/// are added by [`add_codecs_module`](emitters::codecs::add_codecs_module).
+/// With `minimal_runtime`, a spec without streams gets no SSE runtime at all.
+/// With `retry`, the client gets `fetchWithRetry`.
+#[allow(clippy::too_many_arguments)]
+pub fn emit_bundled(
+    ir: &IrSpec,
+    no_jsdoc: bool,
+    sse_transport: SseTransport,
+    default_base_url: Option<&str>,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    sse_package: Option<&str>,
+    debug_logging: bool,
+    minimal_runtime: bool,
+    retry: bool,
+) -> String {
+    let types_content = emitters::types::emit_types(
+        ir,
+        params_style,
+        property_naming,
+        emitters::types::needs_retry_config(ir, retry, minimal_runtime),
+    );
+    let mut client_content = emitters::client::emit_client(
+        ir,
+        no_jsdoc,
+        default_base_url,
+        params_style,
+        property_naming,
+        debug_logging,
+        minimal_runtime,
+        retry,
+    );
 
     let mut output = String::new();
     output.push_str("// Auto-generated by oag — do not edit (bundled)\n\n");
@@ -17,21 +50,42 @@ pub fn emit_bundled(ir: &IrSpec, no_jsdoc: bool) -> String {
     output.push_str(&strip_auto_generated_header(&types_content));
     output.push('\n');
 
-    // Append SSE runtime (strip header)
-    output.push_str("// === SSE Runtime ===\n\n");
-    output.push_str(&strip_auto_generated_header(&sse_content));
-    output.push('\n');
+    // Append SSE runtime (strip header and its `./types` import)
+    if emitters::sse::needs_runtime(ir, minimal_runtime) {
+        output.push_str("// === SSE Runtime ===\n\n");
+        match sse_package {
+            Some(package) => {
+                output.push_str(&format!("{SSE_EXPORTS} from \"{package}\";\n"));
+                client_content = emitters::sse::import_sse_from(&client_content, package);
+            }
+            None => {
+                let sse_content = emitters::sse::emit_sse(sse_transport);
+                output.push_str(&strip_relative_imports(&strip_auto_generated_header(
+                    &sse_content,
+                )));
+            }
+        }
+        output.push('\n');
+    }
 
     // Append client (strip imports from ./types and ./sse since they're inlined)
-    output.push_str("// === Client ===\n\n");
+    output.push_str(CLIENT_SECTION);
+    output.push_str("\n\n");
     let client_stripped = strip_relative_imports(&strip_auto_generated_header(&client_content));
     output.push_str(&client_stripped);
 
     output
 }
 
+/// Where the client starts in a bundled `index.ts`; modules the client
+/// imports are inlined ahead of it.
+pub const CLIENT_SECTION: &str = "// === Client ===";
+
+/// The SSE runtime's public API, as `index.ts` re-exports it.
+const SSE_EXPORTS: &str = "export { type EventSourceFactory, type EventSourceLike, type SSEOptions, type SSETransport, SSEError, SSETimeoutError, streamSse }";
+
 /// Remove the "// Auto-generated by oag — do not edit" header line.
-fn strip_auto_generated_header(content: &str) -> String {
+pub fn strip_auto_generated_header(content: &str) -> String {
     content
         .lines()
         .filter(|line| !line.starts_with("// Auto-generated by oag"))
@@ -39,15 +93,14 @@ fn strip_auto_generated_header(content: &str) -> String {
         .join("\n")
 }
 
-/// Remove import lines that reference relative modules (./types, ./sse, ./client).
-fn strip_relative_imports(content: &str) -> String {
+/// Remove import lines that reference relative modules (`./types`, `./client`, ...):
+/// in a bundle they all point back into the same file.
+pub fn strip_relative_imports(content: &str) -> String {
     let lines: Vec<&str> = content.lines().collect();
     let mut result = Vec::new();
     let mut i = 0;
 
-    let is_relative = |s: &str| {
-        s.contains("\"./types\"") || s.contains("\"./sse\"") || s.contains("\"./client\"")
-    };
+    let is_relative = |s: &str| s.contains("from \"./");
 
     while i < lines.len() {
         let trimmed = lines[i].trim();
@@ -1,33 +1,82 @@
 use std::collections::HashSet;
 
 use minijinja::{Environment, context};
-use oag_core::ir::{IrOperation, IrParameterLocation, IrReturnType, IrSpec, IrType};
+use oag_core::config::{ParamsStyle, PropertyNaming};
+use oag_core::ir::{
+    IrLink, IrOperation, IrParameter, IrParameterLocation, IrRequestBody, IrResponse, IrReturnType,
+    IrSpec, IrType, PatchFormat,
+};
+use oag_core::transform::name_normalizer::{TS_RESERVED_WORDS, escape_reserved};
 
-use crate::type_mapper::ir_type_to_ts;
+use crate::emitters::codecs::{Codecs, codec_identifiers};
+use crate::emitters::types::member_access;
+use crate::type_mapper::{ir_type_to_ts, request_body_ts_type, response_ts_type, ts_property_key};
 
-/// Escape `*/` sequences that would prematurely close JSDoc comment blocks.
-fn escape_jsdoc(value: String) -> String {
-    value.replace("*/", "*\\/")
-}
+/// What `client.ts` offers the package root; the rest are the client's
+/// internals.
+pub const EXPORTS: &[&str] = &[
+    "ApiError",
+    "type ApiErrorBodies",
+    "type ApiResponse",
+    "type ClientConfig",
+    "type OperationError",
+    "type OperationMeta",
+    "type RequestOptions",
+    "type UploadOptions",
+    "type UploadProgress",
+    "ApiClient",
+    "joinUrl",
+    "operationMeta",
+    "serializeQueryValue",
+];
+
+/// The helper functions among [`EXPORTS`], which a `minimal_runtime` barrel
+/// leaves out.
+pub const HELPER_EXPORTS: &[&str] = &["joinUrl", "serializeQueryValue"];
 
 /// Emit `client.ts` — the API client class with REST and SSE methods.
-pub fn emit_client(ir: &IrSpec, _no_jsdoc: bool) -> String {
+///
+/// With a `default_base_url`, `ClientConfig.baseUrl` becomes optional. A relative
+/// default (`/v1`) is resolved against the page origin, so it only works in browsers.
+/// With `no_jsdoc`, methods don't carry the spec's operation docs. With
+/// `ParamsStyle::Object`, query and header parameters are taken as one `{Op}Params`.
+/// The spec's response links become `client.follow` helpers. With
+/// `debug_logging`, `ClientConfig.debug` logs requests through `logging.ts`.
+/// With `PropertyNaming::Camel`, bodies, parameter objects, responses and
+/// stream events go through the `codecs.ts` functions between the typed and
+/// wire forms. With `minimal_runtime`, the SSE options, query serialization
+/// and multipart encoding are left out when no operation needs them. With
+/// `retry`, requests go through `fetchWithRetry` when `ClientConfig.retry` or
+/// the call's `retry` is set, taking the `RetryConfig` from `types.ts`.
+#[allow(clippy::too_many_arguments)]
+pub fn emit_client(
+    ir: &IrSpec,
+    no_jsdoc: bool,
+    default_base_url: Option<&str>,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    debug_logging: bool,
+    minimal_runtime: bool,
+    retry: bool,
+) -> String {
     let mut env = Environment::new();
     env.set_trim_blocks(true);
-    env.add_filter("escape_jsdoc", escape_jsdoc);
+    env.add_filter("jsdoc", super::jsdoc_filter);
     env.add_template("client.ts.j2", include_str!("../../templates/client.ts.j2"))
         .expect("template should be valid");
     let tmpl = env.get_template("client.ts.j2").unwrap();
 
+    let codecs = Codecs::new(ir, property_naming);
+
     // Build and deduplicate operations, tracking which source ops survived.
     let mut seen_methods = HashSet::new();
     let mut used_op_indices = HashSet::new();
-    let operations: Vec<minijinja::Value> = ir
+    let methods: Vec<(usize, minijinja::Value)> = ir
         .operations
         .iter()
         .enumerate()
         .flat_map(|(idx, op)| {
-            build_operation_contexts(op)
+            build_operation_contexts(op, params_style, &codecs)
                 .into_iter()
                 .map(move |ctx| (idx, ctx))
         })
@@ -48,17 +97,51 @@ pub fn emit_client(ir: &IrSpec, _no_jsdoc: bool) -> String {
                 None => true,
             }
         })
-        .map(|(_, ctx)| ctx)
         .collect();
+    let error_bodies: Vec<minijinja::Value> = methods
+        .iter()
+        .filter_map(|(idx, ctx)| {
+            let method = ctx.get_attr("method_name").ok()?;
+            Some(context! {
+                method => method,
+                body => error_body_type(&ir.operations[*idx]),
+            })
+        })
+        .collect();
+    let operations: Vec<minijinja::Value> = methods.into_iter().map(|(_, ctx)| ctx).collect();
 
     // Only collect types from operations that contributed surviving methods.
-    let imported_types = collect_imported_types(
+    let used_ops = || {
         ir.operations
             .iter()
             .enumerate()
             .filter(|(i, _)| used_op_indices.contains(i))
-            .map(|(_, op)| op),
-    );
+            .map(|(_, op)| op)
+    };
+    let mut imported_types = collect_imported_types(used_ops());
+    let mut error_types = HashSet::new();
+    for error in used_ops().flat_map(|op| &op.errors) {
+        collect_types_from_ir_type(&error.response_type, &mut error_types);
+    }
+    if !error_types.is_empty() {
+        imported_types.extend(error_types);
+        imported_types.sort();
+        imported_types.dedup();
+    }
+    if params_style == ParamsStyle::Object {
+        imported_types.extend(
+            used_ops()
+                .filter(|op| !params_object_members(op).is_empty())
+                .map(params_interface_name),
+        );
+        imported_types.sort();
+        imported_types.dedup();
+    }
+    if retry {
+        imported_types.push("RetryConfig".to_string());
+        imported_types.sort();
+        imported_types.dedup();
+    }
 
     let has_sse = operations.iter().any(|op| {
         op.get_attr("kind")
@@ -66,25 +149,333 @@ pub fn emit_client(ir: &IrSpec, _no_jsdoc: bool) -> String {
             .is_some_and(|v| v.as_str() == Some("sse"))
     });
 
+    let has_cookie_auth = used_ops().any(|op| !op.cookie_auth.is_empty());
+    let has_bearer_auth = used_ops().any(|op| op.bearer_auth.is_some());
+    let has_uploads = operations
+        .iter()
+        .any(|op| op.get_attr("upload").is_ok_and(|v| v.is_true()));
+    let has_required_params = operations
+        .iter()
+        .any(|op| op.get_attr("required_params").is_ok_and(|v| !v.is_none()));
+    let has_reserved_path_params = used_ops().any(|op| {
+        op.parameters
+            .iter()
+            .any(|p| p.location == IrParameterLocation::Path && p.allow_reserved)
+    });
+    let has_query_objects = used_ops().any(|op| {
+        op.parameters
+            .iter()
+            .any(|p| p.query_object.is_some() && p.pinned_value.is_none())
+    });
+    let uses = |attr: &str| {
+        operations
+            .iter()
+            .any(|op| op.get_attr(attr).is_ok_and(|v| v.is_true()))
+    };
+    let query_support = !minimal_runtime || uses("has_query_params");
+    let multipart_support = !minimal_runtime || uses("is_multipart");
+    let follow = follow_helpers(ir, &seen_methods, params_style);
+    let used_codecs: Vec<String> = used_ops()
+        .flat_map(|op| codecs.operation_codecs(op))
+        .collect();
+    let codec_imports = codec_identifiers(&used_codecs);
+
     tmpl.render(context! {
         title => ir.info.title.clone(),
+        pinned_parameters => pinned_parameters(ir),
         imported_types => imported_types,
         operations => operations,
+        error_bodies => error_bodies,
         has_sse => has_sse,
-        no_jsdoc => _no_jsdoc,
+        sse_options => has_sse || !minimal_runtime,
+        query_support => query_support,
+        multipart_support => multipart_support,
+        has_cookie_auth => has_cookie_auth,
+        has_bearer_auth => has_bearer_auth,
+        has_uploads => has_uploads,
+        has_reserved_path_params => has_reserved_path_params,
+        has_query_objects => has_query_objects,
+        has_required_params => has_required_params,
+        follow => follow,
+        has_codecs => !codecs.is_empty(),
+        codec_imports => codec_imports,
+        debug_logging => debug_logging,
+        retry => retry,
+        no_jsdoc => no_jsdoc,
+        default_base_url => default_base_url
+            .map(|url| serde_json::to_string(url).expect("strings always serialize")),
+        default_base_url_relative => default_base_url.is_some_and(|url| !url.contains("://")),
     })
     .expect("render should succeed")
 }
 
-fn build_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
+/// The `body` type of the `ApiError` an operation throws: the union of its
+/// declared error responses (`4XX`, `5XX`, `default`), else `unknown`.
+fn error_body_type(op: &IrOperation) -> String {
+    let mut types: Vec<String> = Vec::new();
+    for error in &op.errors {
+        let ts = ir_type_to_ts(&error.response_type);
+        if !types.contains(&ts) {
+            types.push(ts);
+        }
+    }
+    if types.is_empty() {
+        "unknown".to_string()
+    } else {
+        types.join(" | ")
+    }
+}
+
+/// One `client.follow` helper per response link, e.g. `getPetFromCreatePet`,
+/// which takes the source operation's response and calls the target with the
+/// linked fields. Links that take nothing from the response, or are from or to
+/// methods the client doesn't have as plain JSON or void calls, are left out.
+fn follow_helpers(
+    ir: &IrSpec,
+    methods: &HashSet<String>,
+    params_style: ParamsStyle,
+) -> Vec<minijinja::Value> {
+    let mut names = HashSet::new();
+    let mut helpers = Vec::new();
+    for source in &ir.operations {
+        let IrReturnType::Standard(resp) = &source.return_type else {
+            continue;
+        };
+        if resp.is_text() || !methods.contains(&source.name.camel_case) {
+            continue;
+        }
+        for link in source
+            .links
+            .iter()
+            .filter(|l| !l.parameter_mapping.is_empty())
+        {
+            let Some(target) = ir.operations.iter().find(|op| {
+                op.name == link.target_operation
+                    && matches!(
+                        op.return_type,
+                        IrReturnType::Standard(_) | IrReturnType::Void
+                    )
+            }) else {
+                continue;
+            };
+            let name = format!("{}From{}", target.name.camel_case, source.name.pascal_case);
+            if !methods.contains(&target.name.camel_case) || !names.insert(name.clone()) {
+                continue;
+            }
+            let fields = link
+                .parameter_mapping
+                .iter()
+                .map(|p| format!("`{}`", p.parameter))
+                .collect::<Vec<_>>()
+                .join(", ");
+            helpers.push(context! {
+                name => name,
+                doc => format!(
+                    "The `{}` link: calls `{}` with {fields} from a `{}` response.",
+                    link.name, target.name.camel_case, source.name.camel_case
+                ),
+                response_type => ir_type_to_ts(&resp.response_type),
+                method => target.name.camel_case.clone(),
+                args => follow_args(target, link, params_style).join(", "),
+            });
+        }
+    }
+    helpers
+}
+
+/// The arguments a follow helper passes to `target`, in the order of its
+/// method's parameters, with unlinked optional ones `undefined`.
+fn follow_args(target: &IrOperation, link: &IrLink, params_style: ParamsStyle) -> Vec<String> {
+    let linked = |param: &IrParameter| {
+        link.parameter_mapping
+            .iter()
+            .find(|p| p.parameter == param.original_name)
+            .map(|p| response_access(&p.pointer))
+    };
+    let params_object =
+        params_style == ParamsStyle::Object && !params_object_members(target).is_empty();
+    let mut required = Vec::new();
+    let mut optional = Vec::new();
+    for param in &target.parameters {
+        let is_argument = param.location == IrParameterLocation::Path
+            || (matches!(
+                param.location,
+                IrParameterLocation::Query | IrParameterLocation::Header
+            ) && param.pinned_value.is_none()
+                && !params_object);
+        if !is_argument {
+            continue;
+        }
+        let value = linked(param).unwrap_or_else(|| "undefined".to_string());
+        if param.required || param.location == IrParameterLocation::Path {
+            required.push(value);
+        } else {
+            optional.push(value);
+        }
+    }
+    if params_object {
+        let members: Vec<String> = params_object_members(target)
+            .into_iter()
+            .filter_map(|param| {
+                Some(format!(
+                    "{}: {}",
+                    ts_property_key(&param.name.camel_case),
+                    linked(param)?
+                ))
+            })
+            .collect();
+        let object = if members.is_empty() {
+            "undefined".to_string()
+        } else {
+            format!("{{ {} }}", members.join(", "))
+        };
+        if params_object_required(target) {
+            required.push(object);
+        } else {
+            optional.push(object);
+        }
+    }
+    if target.request_body.is_some() {
+        optional.push("undefined".to_string());
+    }
+    required.extend(optional);
+    required.push("options".to_string());
+    required
+}
+
+/// `response` followed by the member accesses of a JSON pointer into it:
+/// `/owner/id` → `response.owner.id`.
+fn response_access(pointer: &str) -> String {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|segment| segment.replace("~1", "/").replace("~0", "~"))
+        .fold("response".to_string(), |expr, segment| {
+            expr + &member_access(&segment)
+        })
+}
+
+/// `"wire-name": "value"` entries of `PINNED_PARAMETERS`, one per pinned name.
+fn pinned_parameters(ir: &IrSpec) -> Vec<String> {
+    let json = |s: &str| serde_json::to_string(s).expect("strings always serialize");
+    let mut seen = HashSet::new();
+    ir.operations
+        .iter()
+        .flat_map(|op| &op.parameters)
+        .filter_map(|p| Some((p.original_name.as_str(), p.pinned_value.as_deref()?)))
+        .filter(|(name, _)| seen.insert(*name))
+        .map(|(name, value)| format!("{}: {}", json(name), json(value)))
+        .collect()
+}
+
+/// The `operationMeta` entry for the client method `method_name` of `op`, as a
+/// TypeScript object literal.
+pub fn operation_meta(op: &IrOperation, method_name: &str) -> String {
+    let json = |s: &str| serde_json::to_string(s).expect("strings always serialize");
+    let extensions = if op.extensions.is_empty() {
+        String::new()
+    } else {
+        let entries: Vec<String> = op
+            .extensions
+            .iter()
+            .map(|(name, value)| format!("{}: {value}", json(name)))
+            .collect();
+        format!(", extensions: {{ {} }}", entries.join(", "))
+    };
+    format!(
+        "{{ name: {}, method: {}, path: {}, tags: [{}]{extensions} }}",
+        json(method_name),
+        json(op.method.as_str()),
+        json(&op.normalized_path),
+        op.tags
+            .iter()
+            .map(|t| json(t))
+            .collect::<Vec<_>>()
+            .join(", "),
+    )
+}
+
+/// A method's doc comment: the operation's summary and description, a
+/// `@remarks` line per exposed extension, then `@param` tags for the documented
+/// arguments and `@returns` with the response's description.
+fn method_doc(op: &IrOperation, params_style: ParamsStyle, returns: Option<&str>) -> String {
+    let remarks = op
+        .extensions
+        .iter()
+        .map(|(name, value)| format!("@remarks {name}: {value}"));
+    let mut tags = param_tags(op, params_style);
+    if let Some(body) = &op.request_body {
+        tags.extend(doc_tag("@param body -", body.description.as_deref()));
+    }
+    tags.extend(doc_tag("@returns", returns));
+    op.doc()
+        .into_iter()
+        .chain(remarks)
+        .chain((!tags.is_empty()).then(|| tags.join("\n")))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// `@param` tags for the documented parameters a method takes as arguments, in
+/// signature order: path parameters, and query and header parameters unless
+/// `params_style` gathers them into a params object (whose members carry their
+/// own docs).
+pub fn param_tags(op: &IrOperation, params_style: ParamsStyle) -> Vec<String> {
+    let params_object =
+        params_style == ParamsStyle::Object && !params_object_members(op).is_empty();
+    let (required, optional): (Vec<&IrParameter>, Vec<&IrParameter>) = op
+        .parameters
+        .iter()
+        .filter(|p| match p.location {
+            IrParameterLocation::Path => true,
+            IrParameterLocation::Query | IrParameterLocation::Header => {
+                p.pinned_value.is_none() && !params_object
+            }
+            IrParameterLocation::Cookie => false,
+        })
+        .partition(|p| p.required || p.location == IrParameterLocation::Path);
+    required
+        .into_iter()
+        .chain(optional)
+        .filter_map(|p| {
+            doc_tag(
+                &format!("@param {} -", param_ident(p)),
+                p.description.as_deref(),
+            )
+        })
+        .collect()
+}
+
+/// A JSDoc block tag with `description` on the same line, e.g.
+/// `@param petId - The pet to fetch`, or `None` without a description. The
+/// description is collapsed to one paragraph so it stays part of the tag.
+pub fn doc_tag(tag: &str, description: Option<&str>) -> Option<String> {
+    let description = description?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    (!description.is_empty()).then(|| format!("{tag} {description}"))
+}
+
+fn build_operation_contexts(
+    op: &IrOperation,
+    params_style: ParamsStyle,
+    codecs: &Codecs,
+) -> Vec<minijinja::Value> {
     let mut results = Vec::new();
 
     match &op.return_type {
         IrReturnType::Standard(resp) => {
-            results.push(build_standard_op(op, &ir_type_to_ts(&resp.response_type)));
+            results.push(build_standard_op(
+                op,
+                &response_ts_type(resp),
+                resp,
+                params_style,
+                codecs,
+            ));
         }
         IrReturnType::Void => {
-            results.push(build_void_op(op));
+            results.push(build_void_op(op, params_style, codecs));
         }
         IrReturnType::Sse(sse) => {
             let return_type = if let Some(ref name) = sse.event_type_name {
@@ -92,17 +483,22 @@ fn build_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
             } else {
                 ir_type_to_ts(&sse.event_type)
             };
-            let sse_name = if sse.also_has_json {
-                format!("{}Stream", op.name.camel_case)
-            } else {
-                op.name.camel_case.clone()
-            };
-            results.push(build_sse_op(op, &return_type, &sse_name));
+            let sse_name = sse.stream_name.camel_case.clone();
+            results.push(build_sse_op(
+                op,
+                &return_type,
+                &sse_name,
+                params_style,
+                codecs,
+            ));
 
             if let Some(ref json_resp) = sse.json_response {
                 results.push(build_standard_op(
                     op,
-                    &ir_type_to_ts(&json_resp.response_type),
+                    &response_ts_type(json_resp),
+                    json_resp,
+                    params_style,
+                    codecs,
                 ));
             }
         }
@@ -117,58 +513,114 @@ fn is_multipart_op(op: &IrOperation) -> bool {
         .is_some_and(|b| b.content_type == "multipart/form-data")
 }
 
-fn build_standard_op(op: &IrOperation, return_type: &str) -> minijinja::Value {
-    let result = build_params(op);
+/// Whether `op` uploads a file: its body is multipart or binary. These take
+/// `UploadOptions`, which can report upload progress.
+pub fn is_upload_op(op: &IrOperation) -> bool {
+    is_multipart_op(op)
+        || op
+            .request_body
+            .as_ref()
+            .is_some_and(|b| b.body_type == IrType::Binary)
+}
+
+/// The method signature, with `UploadOptions` for upload operations.
+fn signature(op: &IrOperation, mut parts: Vec<String>) -> String {
+    if is_upload_op(op)
+        && let Some(last) = parts.last_mut()
+    {
+        *last = "options?: UploadOptions".to_string();
+    }
+    parts.join(", ")
+}
+
+/// A REST method, sending `resp`'s media type as `Accept`. A text response's
+/// body is returned as read, not parsed as JSON.
+fn build_standard_op(
+    op: &IrOperation,
+    return_type: &str,
+    resp: &IrResponse,
+    params_style: ParamsStyle,
+    codecs: &Codecs,
+) -> minijinja::Value {
+    let result = build_params(op, params_style, codecs);
+    let decode = if resp.is_text() {
+        None
+    } else {
+        codecs.value_decoder(&resp.response_type)
+    };
 
     context! {
         kind => "standard",
         method_name => op.name.camel_case.clone(),
+        meta => operation_meta(op, &op.name.camel_case),
         http_method => op.method.as_str(),
-        path => op.path.clone(),
-        params_signature => result.parts.join(", "),
+        path => op.normalized_path.clone(),
+        params_signature => signature(op, result.parts),
         return_type => return_type,
+        text_response => resp.is_text(),
+        decode => decode,
+        encode => codecs.body_encoder(op),
+        accept => resp.content_type.as_deref().unwrap_or("application/json"),
         path_params => result.path_params,
         query_params_obj => result.query_params_obj,
         header_params_obj => result.header_params_obj,
         has_body => result.has_body,
         body_content_type => result.body_content_type.clone(),
         is_multipart => is_multipart_op(op),
+        upload => is_upload_op(op),
+        cookie_auth => !op.cookie_auth.is_empty(),
+        bearer_auth => op.bearer_auth.is_some(),
         has_path_params => result.has_path_params,
         has_query_params => result.has_query_params,
         has_header_params => result.has_header_params,
-        summary => op.summary.clone(),
-        description => op.description.clone(),
+        required_params => result.required_params,
+        allow_empty => result.allow_empty,
+        doc => method_doc(op, params_style, resp.description.as_deref()),
+        external_docs => op.external_docs.clone(),
         deprecated => op.deprecated,
     }
 }
 
-fn build_void_op(op: &IrOperation) -> minijinja::Value {
-    let result = build_params(op);
+fn build_void_op(op: &IrOperation, params_style: ParamsStyle, codecs: &Codecs) -> minijinja::Value {
+    let result = build_params(op, params_style, codecs);
 
     context! {
         kind => "void",
         method_name => op.name.camel_case.clone(),
+        meta => operation_meta(op, &op.name.camel_case),
         http_method => op.method.as_str(),
-        path => op.path.clone(),
-        params_signature => result.parts.join(", "),
+        path => op.normalized_path.clone(),
+        params_signature => signature(op, result.parts),
         return_type => "void",
+        encode => codecs.body_encoder(op),
         path_params => result.path_params,
         query_params_obj => result.query_params_obj,
         header_params_obj => result.header_params_obj,
         has_body => result.has_body,
         body_content_type => result.body_content_type.clone(),
         is_multipart => is_multipart_op(op),
+        upload => is_upload_op(op),
+        cookie_auth => !op.cookie_auth.is_empty(),
+        bearer_auth => op.bearer_auth.is_some(),
         has_path_params => result.has_path_params,
         has_query_params => result.has_query_params,
         has_header_params => result.has_header_params,
-        summary => op.summary.clone(),
-        description => op.description.clone(),
+        required_params => result.required_params,
+        allow_empty => result.allow_empty,
+        doc => method_doc(op, params_style, None),
+        external_docs => op.external_docs.clone(),
         deprecated => op.deprecated,
     }
 }
 
-fn build_sse_op(op: &IrOperation, return_type: &str, method_name: &str) -> minijinja::Value {
-    let mut result = build_params_raw(op);
+fn build_sse_op(
+    op: &IrOperation,
+    return_type: &str,
+    method_name: &str,
+    params_style: ParamsStyle,
+    codecs: &Codecs,
+) -> minijinja::Value {
+    let mut result = build_params_raw(op, params_style, Some(codecs));
 
     // For SSE, use SSEOptions instead of RequestOptions
     if let Some(last) = result.parts.last_mut()
@@ -181,8 +633,9 @@ fn build_sse_op(op: &IrOperation, return_type: &str, method_name: &str) -> minij
     context! {
         kind => "sse",
         method_name => method_name,
+        meta => operation_meta(op, method_name),
         http_method => op.method.as_str(),
-        path => op.path.clone(),
+        path => op.normalized_path.clone(),
         params_signature => params_sig,
         return_type => return_type,
         path_params => result.path_params,
@@ -190,18 +643,90 @@ fn build_sse_op(op: &IrOperation, return_type: &str, method_name: &str) -> minij
         header_params_obj => result.header_params_obj,
         has_body => result.has_body,
         body_content_type => result.body_content_type.clone(),
+        body_json => op.request_body.as_ref().is_none_or(|b| b.is_json()),
+        encode => codecs.body_encoder(op),
+        decode => codecs.event_decoder(op),
         is_multipart => is_multipart_op(op),
+        cookie_auth => !op.cookie_auth.is_empty(),
+        bearer_auth => op.bearer_auth.is_some(),
         has_path_params => result.has_path_params,
         has_query_params => result.has_query_params,
         has_header_params => result.has_header_params,
-        summary => op.summary.clone(),
-        description => op.description.clone(),
+        required_params => result.required_params,
+        allow_empty => result.allow_empty,
+        doc => method_doc(op, params_style, None),
+        external_docs => op.external_docs.clone(),
         deprecated => op.deprecated,
     }
 }
 
+/// The variable name for a parameter: its camelCase name, with a trailing `_`
+/// if that's a reserved word (`new` → `new_`). The wire name is unaffected.
+pub fn param_ident(param: &IrParameter) -> String {
+    escape_reserved(&param.name.camel_case, TS_RESERVED_WORDS)
+}
+
+/// The query and header parameters a method takes as one `{Op}Params` object
+/// with `ParamsStyle::Object`: all but path and pinned parameters, in spec order.
+pub fn params_object_members(op: &IrOperation) -> Vec<&IrParameter> {
+    op.parameters
+        .iter()
+        .filter(|p| {
+            matches!(
+                p.location,
+                IrParameterLocation::Query | IrParameterLocation::Header
+            ) && p.pinned_value.is_none()
+        })
+        .collect()
+}
+
+/// The name of an operation's params object interface, e.g. `ListPetsParams`.
+pub fn params_interface_name(op: &IrOperation) -> String {
+    format!("{}Params", op.name.pascal_case)
+}
+
+/// Whether callers must pass the params object, i.e. it has a required member.
+pub fn params_object_required(op: &IrOperation) -> bool {
+    params_object_members(op).iter().any(|p| p.required)
+}
+
+/// How a params object member is read in generated code: `params.limit`, or
+/// `params?.limit` when the object itself is optional.
+pub fn params_member_access(param: &IrParameter, object_required: bool) -> String {
+    let access = member_access(&param.name.camel_case);
+    match (object_required, access.starts_with('.')) {
+        (true, _) => format!("params{access}"),
+        (false, true) => format!("params?{access}"),
+        (false, false) => format!("params?.{access}"),
+    }
+}
+
+/// The expression sent for a query or header parameter, read from `object` (a
+/// params object member access) or else the parameter's own argument. Parameters
+/// declared with JSON `content` are serialized with `JSON.stringify`;
+/// URL-encoding happens when the query string is built. An `encode`r maps the
+/// value to its wire form first.
+fn param_value(param: &IrParameter, object: Option<String>, encode: Option<String>) -> String {
+    if param.pinned_value.is_some() {
+        return format!("this.pinnedParameters[\"{}\"]", param.original_name);
+    }
+    let name = object.unwrap_or_else(|| param_ident(param));
+    let name = match encode {
+        Some(encode) => format!("{encode}({name} as never)"),
+        None => name,
+    };
+    match param.content_type.as_deref() {
+        Some(ct) if ct.ends_with("json") => {
+            format!("{name} === undefined ? undefined : JSON.stringify({name})")
+        }
+        _ => name,
+    }
+}
+
 struct ParamsResult {
     parts: Vec<String>,
+    /// Schema types the argument declarations in `parts` refer to.
+    argument_types: HashSet<String>,
     path_params: Vec<minijinja::Value>,
     query_params_obj: String,
     header_params_obj: String,
@@ -210,55 +735,123 @@ struct ParamsResult {
     has_path_params: bool,
     has_query_params: bool,
     has_header_params: bool,
+    /// `"wire": value` pairs of the required query and header parameters
+    /// callers pass in, checked at runtime.
+    required_params: Option<String>,
+    /// Wire names of query parameters with `allowEmptyValue`, as a TS array.
+    allow_empty: Option<String>,
 }
 
-fn build_params(op: &IrOperation) -> ParamsResult {
-    build_params_raw(op)
+fn build_params(op: &IrOperation, params_style: ParamsStyle, codecs: &Codecs) -> ParamsResult {
+    build_params_raw(op, params_style, Some(codecs))
 }
 
-fn build_params_raw(op: &IrOperation) -> ParamsResult {
+/// The parameters of `op`'s method. Without `codecs`, only the declarations in
+/// `parts` and `argument_types` are meant to be used.
+fn build_params_raw(
+    op: &IrOperation,
+    params_style: ParamsStyle,
+    codecs: Option<&Codecs>,
+) -> ParamsResult {
     let mut required_parts = Vec::new();
     let mut optional_parts = Vec::new();
     let mut path_params = Vec::new();
     let mut query_parts = Vec::new();
     let mut header_parts = Vec::new();
+    let mut required_checks = Vec::new();
+    let mut allow_empty = Vec::new();
+    let mut argument_types = HashSet::new();
+
+    // With the object style, query and header parameters become one argument.
+    let params_object =
+        params_style == ParamsStyle::Object && !params_object_members(op).is_empty();
+    let object_required = params_object_required(op);
+    let value = |param: &IrParameter| {
+        param_value(
+            param,
+            params_object.then(|| params_member_access(param, object_required)),
+            codecs.and_then(|codecs| codecs.value_encoder(&param.param_type)),
+        )
+    };
 
     for param in &op.parameters {
         let ts_type = ir_type_to_ts(&param.param_type);
+        // Pinned parameters are read from `this.pinnedParameters`, not passed in.
+        let is_argument = matches!(
+            param.location,
+            IrParameterLocation::Query | IrParameterLocation::Header
+        ) && param.pinned_value.is_none()
+            && !params_object;
+        if matches!(
+            param.location,
+            IrParameterLocation::Query | IrParameterLocation::Header
+        ) && param.pinned_value.is_none()
+            && param.required
+        {
+            required_checks.push(format!("\"{}\": {}", param.original_name, value(param)));
+        }
+        if param.location == IrParameterLocation::Query && param.allow_empty_value {
+            allow_empty.push(
+                serde_json::to_string(&param.original_name).expect("strings always serialize"),
+            );
+        }
+        if is_argument || param.location == IrParameterLocation::Path {
+            collect_types_from_ir_type(&param.param_type, &mut argument_types);
+        }
+        if is_argument {
+            if param.required {
+                required_parts.push(format!("{}: {}", param_ident(param), ts_type));
+            } else {
+                optional_parts.push(format!("{}?: {}", param_ident(param), ts_type));
+            }
+        }
         match param.location {
             IrParameterLocation::Path => {
-                required_parts.push(format!("{}: {}", param.name.camel_case, ts_type));
+                required_parts.push(format!("{}: {}", param_ident(param), ts_type));
                 path_params.push(context! {
                     name => param.name.camel_case.clone(),
+                    ident => param_ident(param),
                     original_name => param.original_name.clone(),
+                    encode => if param.allow_reserved { "encodeReserved" } else { "encodeURIComponent" },
                 });
             }
-            IrParameterLocation::Query => {
-                if param.required {
-                    required_parts.push(format!("{}: {}", param.name.camel_case, ts_type));
-                } else {
-                    optional_parts.push(format!("{}?: {}", param.name.camel_case, ts_type));
-                }
-                query_parts.push(format!(
-                    "\"{}\": {}",
-                    param.original_name, param.name.camel_case
-                ));
-            }
+            IrParameterLocation::Query => match param.query_object {
+                // Spread into `name[key]` / `name.key` entries.
+                Some(notation) if param.pinned_value.is_none() => query_parts.push(format!(
+                    "...flattenQueryObject(\"{}\", {}, \"{}\")",
+                    param.original_name,
+                    value(param),
+                    notation.as_str()
+                )),
+                // Joined into one value; other arrays repeat the key.
+                _ => match param.query_array {
+                    Some(style) if param.pinned_value.is_none() => query_parts.push(format!(
+                        "\"{}\": serializeQueryValue({}, {})",
+                        param.original_name,
+                        value(param),
+                        serde_json::to_string(style.delimiter()).expect("strings always serialize")
+                    )),
+                    _ => query_parts.push(format!("\"{}\": {}", param.original_name, value(param))),
+                },
+            },
             IrParameterLocation::Header => {
-                if param.required {
-                    required_parts.push(format!("{}: {}", param.name.camel_case, ts_type));
-                } else {
-                    optional_parts.push(format!("{}?: {}", param.name.camel_case, ts_type));
-                }
-                header_parts.push(format!(
-                    "\"{}\": {}",
-                    param.original_name, param.name.camel_case
-                ));
+                header_parts.push(format!("\"{}\": {}", param.original_name, value(param)));
             }
             _ => {}
         }
     }
 
+    // After the path parameters, before the body.
+    if params_object {
+        let interface = params_interface_name(op);
+        argument_types.insert(interface.clone());
+        if object_required {
+            required_parts.push(format!("params: {interface}"));
+        } else {
+            optional_parts.push(format!("params?: {interface}"));
+        }
+    }
+
     let has_body = op.request_body.is_some();
     let body_content_type = op
         .request_body
@@ -267,7 +860,8 @@ fn build_params_raw(op: &IrOperation) -> ParamsResult {
         .unwrap_or_else(|| "application/json".to_string());
 
     if let Some(ref body) = op.request_body {
-        let ts_type = ir_type_to_ts(&body.body_type);
+        let ts_type = request_body_ts_type(body);
+        collect_types_from_request_body(body, &mut argument_types);
         if body.required {
             required_parts.push(format!("body: {ts_type}"));
         } else {
@@ -288,6 +882,7 @@ fn build_params_raw(op: &IrOperation) -> ParamsResult {
 
     ParamsResult {
         parts,
+        argument_types,
         path_params,
         query_params_obj,
         header_params_obj,
@@ -296,17 +891,41 @@ fn build_params_raw(op: &IrOperation) -> ParamsResult {
         has_path_params,
         has_query_params,
         has_header_params,
+        required_params: (!required_checks.is_empty()).then(|| required_checks.join(", ")),
+        allow_empty: (!allow_empty.is_empty()).then(|| format!("[{}]", allow_empty.join(", "))),
     }
 }
 
-fn collect_imported_types<'a>(ops: impl Iterator<Item = &'a IrOperation>) -> Vec<String> {
+/// A client method's parameters before its trailing `options`, for code that
+/// calls the method on a caller's behalf.
+pub(crate) struct MethodArguments {
+    /// The declarations, e.g. `petId: string` or `limit?: number`.
+    pub parts: Vec<String>,
+    /// Names from the types module the declarations use, sorted.
+    pub types: Vec<String>,
+}
+
+pub(crate) fn method_arguments(op: &IrOperation, params_style: ParamsStyle) -> MethodArguments {
+    let mut result = build_params_raw(op, params_style, None);
+    result.parts.pop();
+    let mut types: Vec<String> = result.argument_types.into_iter().collect();
+    types.sort();
+    MethodArguments {
+        parts: result.parts,
+        types,
+    }
+}
+
+pub(crate) fn collect_imported_types<'a>(
+    ops: impl Iterator<Item = &'a IrOperation>,
+) -> Vec<String> {
     let mut types = HashSet::new();
 
     for op in ops {
         collect_types_from_return(&op.return_type, &mut types);
 
         if let Some(ref body) = op.request_body {
-            collect_types_from_ir_type(&body.body_type, &mut types);
+            collect_types_from_request_body(body, &mut types);
         }
 
         for param in &op.parameters {
@@ -340,12 +959,12 @@ fn collect_types_from_return(ret: &IrReturnType, types: &mut HashSet<String>) {
     }
 }
 
-fn collect_types_from_ir_type(ir_type: &IrType, types: &mut HashSet<String>) {
+pub(crate) fn collect_types_from_ir_type(ir_type: &IrType, types: &mut HashSet<String>) {
     match ir_type {
         IrType::Ref(name) => {
             types.insert(name.clone());
         }
-        IrType::Array(inner) | IrType::Map(inner) => collect_types_from_ir_type(inner, types),
+        IrType::Array(inner) | IrType::Map(inner, _) => collect_types_from_ir_type(inner, types),
         IrType::Union(variants) | IrType::Intersection(variants) => {
             for v in variants {
                 collect_types_from_ir_type(v, types);
@@ -359,3 +978,908 @@ fn collect_types_from_ir_type(ir_type: &IrType, types: &mut HashSet<String>) {
         _ => {}
     }
 }
+
+/// The types-module names [`request_body_ts_type`] uses for `body`.
+pub fn collect_types_from_request_body(body: &IrRequestBody, types: &mut HashSet<String>) {
+    match body.patch_format() {
+        Some(PatchFormat::MergePatch) => {
+            types.insert("DeepPartial".to_string());
+            collect_types_from_ir_type(&body.body_type, types);
+        }
+        Some(PatchFormat::JsonPatch) => {
+            types.insert("JsonPatchOp".to_string());
+        }
+        None => collect_types_from_ir_type(&body.body_type, types),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plain_text_is_sent_and_returned_as_string() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/plain-text.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains(
+            "async ingestLogs(body: string, options?: RequestOptions): Promise<IngestResult>"
+        ));
+        assert!(content.contains("      contentType: \"text/plain\",\n"));
+        assert!(content.contains(
+            "async tailLogs(streamId: string, options?: RequestOptions): Promise<string>"
+        ));
+        assert!(content.contains(
+            "async exportLogs(streamId: string, options?: RequestOptions): Promise<string>"
+        ));
+        // Both text methods and their `Raw` variants skip JSON parsing.
+        assert_eq!(content.matches("responseType: \"text\",").count(), 4);
+    }
+
+    #[test]
+    fn test_bodiless_success_status_widens_return_type() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/empty-success.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains(
+            "async getPet(petId: string, options?: RequestOptions): Promise<Pet | undefined>"
+        ));
+        assert!(content.contains("Promise<ApiResponse<Pet | undefined>>"));
+        assert!(content.contains(
+            "async replacePet(petId: string, body: Pet, options?: RequestOptions): Promise<Pet>"
+        ));
+    }
+
+    #[test]
+    fn test_error_responses_type_the_api_error_body() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/default-errors.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("export class ApiError<TBody = unknown> extends Error {"));
+        assert!(content.contains("public readonly body?: TBody,"));
+        assert!(content.contains(
+            "export interface ApiErrorBodies {\n  getPet: NotFound | ErrorModel;\n  deletePet: ErrorModel;\n  getHealth: unknown;\n}"
+        ));
+        assert!(content.contains(
+            "export type OperationError<K extends keyof ApiErrorBodies> = ApiError<ApiErrorBodies[K]>;"
+        ));
+        assert!(
+            content.contains("import type {\n  ErrorModel,\n  Health,\n  NotFound,\n  Pet,\n}")
+        );
+
+        // Methods without error responses throw `ApiError<unknown>`.
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/empty-success.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("getPet: unknown;"));
+    }
+
+    #[test]
+    fn test_query_objects_are_flattened() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/query-objects.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("function flattenQueryObject("));
+        assert!(content.contains("async listPets(filter?: PetFilter, limit?: number,"));
+        assert!(content.contains(
+            "query: { ...flattenQueryObject(\"filter\", filter, \"bracket\"), \"limit\": limit },"
+        ));
+        assert!(content.contains("query: { ...flattenQueryObject(\"where\", where, \"dot\") },"));
+
+        // Only emitted when a parameter needs it.
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/json-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(!content.contains("flattenQueryObject"));
+    }
+
+    #[test]
+    fn test_delimited_query_arrays_are_joined() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/query-arrays.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains(
+            "export function serializeQueryValue(value: unknown, delimiter?: string): string | string[] | undefined {"
+        ));
+        assert!(content.contains(
+            "query: { \"ids\": serializeQueryValue(ids, \",\"), \"tags\": serializeQueryValue(tags, \" \"), \"status\": serializeQueryValue(status, \"|\"), \"breed\": breed, \"bornAfter\": bornAfter, \"vaccinated\": vaccinated },"
+        ));
+    }
+
+    #[test]
+    fn test_json_content_query_param_is_stringified() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/json-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("filter?: ItemFilter"));
+        assert!(content.contains(
+            "\"filter\": filter === undefined ? undefined : JSON.stringify(filter), \"limit\": limit"
+        ));
+    }
+
+    #[test]
+    fn test_reserved_parameter_names_are_escaped() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/reserved-words.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains(
+            "async getRecord(import_: string, class_: string, def?: string, type?: string, new_?: boolean, options?: RequestOptions): Promise<RecordSchema>"
+        ));
+        assert!(
+            content.contains("path.replace(\"{import}\", encodeURIComponent(String(import_)))")
+        );
+        assert!(content.contains("query: { \"class\": class_, \"def\": def, \"type\": type }"));
+    }
+
+    #[test]
+    fn test_default_base_url_from_servers() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        let content = emit_client(
+            &ir,
+            false,
+            ir.default_base_url(),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(
+            content.contains("export const DEFAULT_BASE_URL = \"https://api.example.com/v1\";")
+        );
+        assert!(content.contains("  baseUrl?: string;"));
+        assert!(content.contains("constructor(config: ClientConfig = {})"));
+        assert!(content.contains("this.baseUrl = config.baseUrl ?? DEFAULT_BASE_URL;"));
+        assert!(!content.contains("resolveDefaultBaseUrl"));
+
+        let content = emit_client(
+            &ir,
+            false,
+            Some("/v1"),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("export const DEFAULT_BASE_URL = \"/v1\";"));
+        assert!(content.contains("this.baseUrl = config.baseUrl ?? resolveDefaultBaseUrl();"));
+        assert!(content.contains("is relative; pass an absolute baseUrl in ClientConfig"));
+
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(!content.contains("DEFAULT_BASE_URL"));
+        assert!(content.contains("  baseUrl: string;"));
+        assert!(content.contains("constructor(config: ClientConfig) {"));
+    }
+
+    #[test]
+    fn test_base_url_per_call() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        assert!(
+            content.contains("export function joinUrl(baseUrl: string, path: string): string {")
+        );
+        assert!(content.contains("  baseUrl?: string | undefined;\n}"));
+        // Requests and streams both join against the call's base URL first.
+        assert_eq!(
+            content
+                .matches("joinUrl(options?.baseUrl ?? this.baseUrl, path)")
+                .count(),
+            1 + content.matches("yield* streamSse").count()
+        );
+        assert!(content.contains("this.baseUrl = config.baseUrl;"));
+        assert!(content.contains("this.headers = Object.freeze({ ...config.headers });"));
+    }
+
+    #[test]
+    fn test_pinned_header_is_sent_but_not_taken() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let pins = [("anthropic-version".to_string(), "2023-06-01".to_string())];
+        let ir = ir.with_pinned_parameters(&pins.into_iter().collect());
+
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains(
+            "async createMessage(body: CreateMessageRequest, options?: RequestOptions): Promise<MessageResponse>"
+        ));
+        assert!(!content.contains("anthropicVersion"));
+        assert!(content.contains(
+            "Object.entries({ \"anthropic-version\": this.pinnedParameters[\"anthropic-version\"] })"
+        ));
+        assert!(content.contains(
+            "export const PINNED_PARAMETERS = {\n  \"anthropic-version\": \"2023-06-01\",\n};"
+        ));
+        assert!(content.contains("pinnedParameters?: Partial<typeof PINNED_PARAMETERS>;"));
+        assert!(content.contains(
+            "this.pinnedParameters = Object.freeze({ ...PINNED_PARAMETERS, ...config.pinnedParameters });"
+        ));
+    }
+
+    #[test]
+    fn test_cookie_auth() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/cookie-auth.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        assert!(content.contains("  cookies?: Record<string, string>;\n  /**"));
+        assert!(content.contains("this.cookies = Object.freeze({ ...config.cookies });"));
+        assert!(
+            content.contains("...(cookieAuth ? { credentials: cookieAuth.credentials } : {}),")
+        );
+        // getMe (+Raw) and health (+Raw); login opts out and status only takes a bearer.
+        assert_eq!(content.matches("      cookieAuth: true,\n").count(), 4);
+        let login = content.split("async login(").nth(1).unwrap();
+        let login = &login[..login.find("async loginRaw(").unwrap()];
+        assert!(!login.contains("cookieAuth"));
+        // Streams carry the same cookie and credentials.
+        assert!(content.contains("const _auth = this.cookieAuth();"));
+        assert!(content.contains("credentials: _auth.credentials,"));
+        assert!(
+            content
+                .contains("headers: { ..._auth.headers, ...this.headers, ...options?.headers },")
+        );
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(!content.contains("cookie"));
+        assert!(!content.contains("credentials"));
+    }
+
+    #[test]
+    fn test_bearer_auth() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/bearer-auth.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        assert!(content.contains("import { BearerAuth, type BearerToken } from \"./auth\";"));
+        assert!(content.contains("  bearerToken?: BearerToken;\n}"));
+        assert!(content.contains(
+            "this.bearerAuth = config.bearerToken === undefined ? undefined : new BearerAuth(config.bearerToken);"
+        ));
+        assert!(content.contains(
+            "const bearerHeaders = options?.bearerAuth ? await this.bearerHeaders() : undefined;"
+        ));
+        // getMe, deleteMe and listKeys (+Raw each); createToken opts out.
+        assert_eq!(content.matches("      bearerAuth: true,\n").count(), 6);
+        let create = content.split("async createToken(").nth(1).unwrap();
+        let create = &create[..create.find("async createTokenRaw(").unwrap()];
+        assert!(!create.contains("bearerAuth"));
+        // Streams ask for the token before opening.
+        assert!(content.contains("const _bearer = await this.bearerHeaders();"));
+        assert!(content.contains("headers: { ..._bearer, ...this.headers, ...options?.headers },"));
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(!content.contains("bearer"));
+        assert!(!content.contains("./auth"));
+    }
+
+    #[test]
+    fn test_operation_meta() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        assert!(content.contains("  getModel: { name: \"getModel\", method: \"GET\", path: \"/v1/models/{modelId}\", tags: [\"models\"] },\n"));
+        assert!(content.contains("tags: [\"tokens\", \"messages\"] },"));
+        assert!(content.contains("requestInterceptor({ ...req, operation })"));
+        assert!(content.contains("this.request<ModelInfo>(operationMeta.getModel, path, {"));
+        assert!(content.contains("this.rawRequest<ModelInfo>(operationMeta.getModel, path, {"));
+        // The stream and the JSON variant of one operation each get an entry.
+        assert!(content.contains("this.interceptorFor(operationMeta.createMessageStream)"));
+        assert!(content.contains("  createMessage: { name: \"createMessage\","));
+    }
+
+    #[test]
+    fn test_upload_progress() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/file-uploads.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        // Multipart and binary bodies take `UploadOptions`; the JSON read doesn't.
+        assert!(content.contains("async uploadPhoto(petId: string, body: UploadPhotoBody, overwrite?: boolean, options?: UploadOptions): Promise<Photo>"));
+        assert!(content.contains("async putFileRaw(name: string, xChecksum: string, body: Blob, options?: UploadOptions): Promise<ApiResponse<void>>"));
+        assert!(content.contains("async listPhotos(petId: string, options?: RequestOptions)"));
+        assert!(content.contains("function xhrFetch("));
+        assert!(content.contains("? xhrFetch(url, init, onUploadProgress)"));
+        // putFile lists its options one by one, for the checksum header.
+        assert_eq!(
+            content
+                .matches("      onUploadProgress: options?.onUploadProgress,\n")
+                .count(),
+            2
+        );
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("export interface UploadOptions extends RequestOptions {"));
+        assert!(!content.contains("xhrFetch"));
+        assert!(!content.contains("options?: UploadOptions"));
+    }
+
+    #[test]
+    fn test_params_object_style() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Object,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("  ListThingsParams,\n"));
+        assert!(content.contains(
+            "async listThings(params?: ListThingsParams, options?: RequestOptions): Promise<Thing[]>"
+        ));
+        assert!(content.contains("Object.entries({ \"X-Request-Id\": params?.xRequestId })"));
+        assert!(content.contains("query: { \"limit\": params?.limit, \"offset\": params?.offset,"));
+        // A required body stays ahead of an optional params object.
+        assert!(content.contains(
+            "async createThing(body: NewThing, params?: CreateThingParams, options?: RequestOptions)"
+        ));
+        assert!(content.contains(
+            "async updateThing(thingId: string, params: UpdateThingParams, body?: NewThing, options?: RequestOptions)"
+        ));
+        assert!(content.contains("query: { \"reason\": params.reason }"));
+        assert!(content.contains("async deleteThing(thingId: string, options?: RequestOptions)"));
+
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(!content.contains("ListThingsParams"));
+        assert!(
+            content.contains(
+                "async listThings(limit?: number, offset?: number, status?: ThingStatus,"
+            )
+        );
+    }
+
+    #[test]
+    fn test_follow_helpers_for_response_links() {
+        let spec =
+            oag_core::parse::from_yaml(include_str!("../../../oag-core/tests/fixtures/links.yaml"))
+                .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("  readonly follow = {\n"));
+        assert!(content.contains(
+            "    getPetFromCreatePet: (response: Pet, options?: RequestOptions) =>\n      this.getPet(response.id, options),\n"
+        ));
+        // The link with an unsupported expression gets no helper.
+        assert_eq!(content.matches("From").count(), 1);
+        assert_eq!(
+            response_access("/owner/pet~1id"),
+            "response.owner[\"pet/id\"]"
+        );
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/parameter-examples.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert!(
+            !emit_client(
+                &ir,
+                false,
+                None,
+                ParamsStyle::Positional,
+                PropertyNaming::Original,
+                false,
+                false,
+                false
+            )
+            .contains("follow")
+        );
+    }
+
+    #[test]
+    fn test_allow_reserved_path_params_keep_reserved_characters() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/reserved-path-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        assert!(content.contains("function encodeReserved(value: string): string {"));
+        assert!(content.contains("path.replace(\"{filePath}\", encodeReserved(String(filePath)))"));
+        assert!(content.contains("path.replace(\"{petId}\", encodeURIComponent(String(petId)))"));
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/trailing-slashes.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert!(
+            !emit_client(
+                &ir,
+                false,
+                None,
+                ParamsStyle::Positional,
+                PropertyNaming::Original,
+                false,
+                false,
+                false
+            )
+            .contains("encodeReserved")
+        );
+    }
+
+    #[test]
+    fn test_required_and_empty_query_params_at_runtime() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains(
+            "function assertRequired(method: string, params: Record<string, unknown>): void {"
+        ));
+        assert!(content.contains(
+            "    assertRequired(\"listModels\", { \"anthropic-version\": anthropicVersion });\n"
+        ));
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(content.contains("    assertRequired(\"searchThings\", { \"q\": q });\n"));
+        assert!(content.contains("      allowEmpty: [\"q\"],\n"));
+        assert_eq!(content.matches("allowEmpty: [").count(), 2);
+    }
+
+    #[test]
+    fn test_exposed_extensions_in_meta_and_docs() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/rate-limits.yaml"
+        ))
+        .unwrap();
+        let options = oag_core::transform::TransformOptions {
+            exposed_extensions: vec!["x-ratelimit-rpm".into(), "x-ratelimit-burst".into()],
+            ..Default::default()
+        };
+        let ir = oag_core::transform::transform_with_options(&spec, &options).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        assert!(content.contains(
+            "  createMessage: { name: \"createMessage\", method: \"POST\", path: \"/messages\", tags: [], extensions: { \"x-ratelimit-rpm\": 60, \"x-ratelimit-burst\": {\"limit\":10,\"window\":\"1s\"} } },"
+        ));
+        assert!(content.contains("extensions: { \"x-ratelimit-rpm\": 600 } },"));
+        assert!(content.contains(
+            "  /**\n   * Send a message\n   *\n   * @remarks x-ratelimit-rpm: 60\n   *\n   * @remarks x-ratelimit-burst: {\"limit\":10,\"window\":\"1s\"}\n   */\n  async createMessage("
+        ));
+        assert!(content.contains(
+            "  /**\n   * @remarks x-ratelimit-rpm: 600\n   *\n   * @returns The models\n   */\n  async listModels("
+        ));
+        assert!(!content.contains("x-internal-owner"));
+        assert!(content.contains(
+            "  health: { name: \"health\", method: \"GET\", path: \"/health\", tags: [] },"
+        ));
+    }
+
+    #[test]
+    fn test_method_docs_describe_arguments_and_result() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/documented-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let emit = |params_style| {
+            emit_client(
+                &ir,
+                false,
+                None,
+                params_style,
+                PropertyNaming::Original,
+                false,
+                false,
+                false,
+            )
+        };
+
+        let content = emit(ParamsStyle::Positional);
+        assert!(content.contains(
+            "  /**\n   * Update a pet\n   *\n   * @param petId - The id of the pet to update\n   * @param body - The pet's new name and tag\n   * @returns The updated pet\n   */\n  async updatePet("
+        ));
+
+        // Query and header parameters are documented on the params object.
+        let content = emit(ParamsStyle::Object);
+        assert!(content.contains(
+            "   * @param petId - The id of the pet to fetch\n   * @returns The pet\n   */\n  async getPet("
+        ));
+    }
+
+    #[test]
+    fn test_etag_cache_applies_to_get_requests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+
+        assert!(content.contains("export class MemoryCacheStore implements CacheStore {"));
+        assert!(content.contains("  cache?: { store?: CacheStore };"));
+        assert!(content.contains(
+            "const cacheStore = operation.method === \"GET\" ? this.cacheStore : undefined;"
+        ));
+        assert!(content.contains("...(cached ? { \"If-None-Match\": cached.etag } : {}),"));
+        assert!(content.contains(
+            "return { ...response, ok: true, data: cached.data as T, fromCache: true };"
+        ));
+    }
+
+    #[test]
+    fn test_patch_bodies_are_typed_by_media_type() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/patch-bodies.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(
+            content
+                .contains("async replacePet(petId: string, body: Pet, options?: RequestOptions)")
+        );
+        assert!(content.contains(
+            "async updatePet(petId: string, body: DeepPartial<Pet>, options?: RequestOptions)"
+        ));
+        assert!(content.contains(
+            "async patchPet(petId: string, body: JsonPatchOp[], options?: RequestOptions)"
+        ));
+        assert!(content.contains("      contentType: \"application/merge-patch+json\",\n"));
+        assert!(content.contains("  DeepPartial,\n  JsonPatchOp,\n  Pet,\n} from \"./types\";"));
+        // `+json` bodies are serialized like `application/json` ones.
+        assert!(content.contains("} else if (isJsonMediaType(contentType)) {"));
+    }
+
+    #[test]
+    fn test_retry_helper_only_with_retry() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let emit = |retry| {
+            emit_client(
+                &ir,
+                false,
+                None,
+                ParamsStyle::Positional,
+                PropertyNaming::Original,
+                false,
+                false,
+                retry,
+            )
+        };
+
+        let content = emit(false);
+        assert!(!content.contains("fetchWithRetry"));
+        assert!(!content.contains("RetryConfig"));
+        assert!(!content.contains("retry?:"));
+
+        let content = emit(true);
+        assert!(content.contains("async function fetchWithRetry("));
+        assert!(content.contains("  RetryConfig,\n"));
+        assert!(content.contains("  retry?: RetryConfig | false | undefined;\n"));
+        assert!(!content.contains("export interface RetryConfig"));
+    }
+}
@@ -0,0 +1,802 @@
+use std::collections::{BTreeSet, HashSet};
+
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::config::PropertyNaming;
+use oag_core::ir::{
+    IrDiscriminator, IrObjectSchema, IrOperation, IrReturnType, IrSchema, IrSpec, IrType,
+    PatchFormat,
+};
+use serde_json::Value;
+
+use super::bundled::{CLIENT_SECTION, strip_auto_generated_header, strip_relative_imports};
+use super::source_path;
+use super::types::{event_discriminator, sse_event_types};
+use crate::type_mapper::ts_property_key;
+
+/// The property names `obj`'s interface declares, in field order: the wire
+/// names, or their camelCase forms with `PropertyNaming::Camel`. Fields whose
+/// camelCase names would clash keep their wire names.
+pub fn property_names(obj: &IrObjectSchema, naming: PropertyNaming) -> Vec<String> {
+    let mut names: Vec<String> = obj
+        .fields
+        .iter()
+        .map(|f| match naming {
+            PropertyNaming::Camel if !f.name.camel_case.is_empty() => f.name.camel_case.clone(),
+            _ => f.original_name.clone(),
+        })
+        .collect();
+    // Renamed fields that clash go back to their wire names, which can expose
+    // another clash.
+    loop {
+        let clashes: Vec<usize> = (0..names.len())
+            .filter(|&i| {
+                names[i] != obj.fields[i].original_name
+                    && names.iter().filter(|name| **name == names[i]).count() > 1
+            })
+            .collect();
+        if clashes.is_empty() {
+            return names;
+        }
+        for i in clashes {
+            names[i] = obj.fields[i].original_name.clone();
+        }
+    }
+}
+
+/// The name `obj`'s interface declares for its wire property `wire`.
+pub fn property_name(obj: &IrObjectSchema, wire: &str, naming: PropertyNaming) -> String {
+    match obj.fields.iter().position(|f| f.original_name == wire) {
+        Some(i) => property_names(obj, naming).swap_remove(i),
+        None => wire.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    /// Typed form to wire form.
+    Serialize,
+    /// Wire form to typed form.
+    Deserialize,
+}
+
+impl Direction {
+    fn prefix(self) -> &'static str {
+        match self {
+            Direction::Serialize => "serialize",
+            Direction::Deserialize => "deserialize",
+        }
+    }
+}
+
+/// A codec as TypeScript: a function expression, or the keys of an object to
+/// rename and the codecs of its values, by (source) key.
+enum Codec {
+    Function(String),
+    Keys {
+        names: Vec<(String, String)>,
+        values: Vec<(String, String)>,
+    },
+}
+
+impl Codec {
+    /// The codec as a function expression.
+    fn function(self) -> String {
+        match self {
+            Codec::Function(function) => function,
+            Codec::Keys { names, values } => format!(
+                "objectOf({}, {})",
+                ts_object(&names, true),
+                ts_object(&values, false)
+            ),
+        }
+    }
+
+    /// The codec applied to `value`.
+    fn apply(self) -> String {
+        match self {
+            Codec::Function(function) => format!("{function}(value as never)"),
+            Codec::Keys { names, values } if values.is_empty() => {
+                format!("mapKeys(value, {})", ts_object(&names, true))
+            }
+            Codec::Keys { names, values } => format!(
+                "mapKeys(value, {}, {})",
+                ts_object(&names, true),
+                ts_object(&values, false)
+            ),
+        }
+    }
+}
+
+/// `{ key: value, ... }`, with the values as string literals when `quote`.
+fn ts_object(entries: &[(String, String)], quote: bool) -> String {
+    if entries.is_empty() {
+        return "{}".to_string();
+    }
+    let entries: Vec<String> = entries
+        .iter()
+        .map(|(key, value)| {
+            let value = if quote {
+                serde_json::to_string(value).expect("strings always serialize")
+            } else {
+                value.clone()
+            };
+            format!("{}: {value}", ts_property_key(key))
+        })
+        .collect();
+    format!("{{ {} }}", entries.join(", "))
+}
+
+/// The schemas whose typed form differs from their wire form, so they need
+/// codecs, and the TypeScript expressions of those codecs. There are none with
+/// `PropertyNaming::Original`: the types are the wire form.
+///
+/// Inline object types keep their wire names, and values of untagged unions
+/// only map the keys their variants agree on.
+pub struct Codecs<'a> {
+    ir: &'a IrSpec,
+    naming: PropertyNaming,
+    /// Schemas with codecs, by name.
+    schemas: HashSet<&'a str>,
+    /// SSE event union types with decoders, by name.
+    events: HashSet<&'a str>,
+}
+
+impl<'a> Codecs<'a> {
+    pub fn new(ir: &'a IrSpec, naming: PropertyNaming) -> Self {
+        let mut codecs = Codecs {
+            ir,
+            naming,
+            schemas: HashSet::new(),
+            events: HashSet::new(),
+        };
+        if naming == PropertyNaming::Original {
+            return codecs;
+        }
+        // A schema needs a codec when it renames a property or holds a value
+        // that needs one, so add schemas until no more do.
+        loop {
+            let added: Vec<&'a str> = ir
+                .schemas
+                .iter()
+                .filter(|schema| !codecs.schemas.contains(schema.name().pascal_case.as_str()))
+                .filter(|schema| {
+                    codecs
+                        .schema_codec(schema, Direction::Deserialize)
+                        .is_some()
+                })
+                .map(|schema| schema.name().pascal_case.as_str())
+                .collect();
+            if added.is_empty() {
+                break;
+            }
+            codecs.schemas.extend(added);
+        }
+        codecs.events = sse_event_types(ir, &ir.operations)
+            .into_iter()
+            .filter(|event| {
+                codecs
+                    .union_codec(event.variants, None, Direction::Deserialize)
+                    .is_some()
+            })
+            .map(|event| event.name)
+            .collect();
+        codecs
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.schemas.is_empty() && self.events.is_empty()
+    }
+
+    /// The codec functions `codecs.ts` exports, in schema order.
+    pub fn exports(&self) -> Vec<String> {
+        let mut exports = Vec::new();
+        for schema in &self.ir.schemas {
+            let name = &schema.name().pascal_case;
+            if self.schemas.contains(name.as_str()) {
+                exports.push(format!("serialize{name}"));
+                exports.push(format!("deserialize{name}"));
+            }
+        }
+        for event in sse_event_types(self.ir, &self.ir.operations) {
+            if self.events.contains(event.name) {
+                exports.push(format!("deserialize{}", event.name));
+            }
+        }
+        exports
+    }
+
+    /// The serializer of `op`'s request body, e.g. `serializePet`. JSON Patch
+    /// documents and bodies sent as is (text, binary) have none.
+    pub fn body_encoder(&self, op: &IrOperation) -> Option<String> {
+        let body = op.request_body.as_ref()?;
+        if !(body.is_json() || body.content_type == "multipart/form-data")
+            || body.patch_format() == Some(PatchFormat::JsonPatch)
+        {
+            return None;
+        }
+        Some(
+            self.type_codec(&body.body_type, Direction::Serialize)?
+                .function(),
+        )
+    }
+
+    /// The serializer of a parameter value of type `ty`.
+    pub fn value_encoder(&self, ty: &IrType) -> Option<String> {
+        Some(self.type_codec(ty, Direction::Serialize)?.function())
+    }
+
+    /// The deserializer of a JSON response body of type `ty`, e.g.
+    /// `arrayOf(deserializePet)`.
+    pub fn value_decoder(&self, ty: &IrType) -> Option<String> {
+        Some(self.type_codec(ty, Direction::Deserialize)?.function())
+    }
+
+    /// The deserializer of each event of `op`'s SSE stream.
+    pub fn event_decoder(&self, op: &IrOperation) -> Option<String> {
+        let IrReturnType::Sse(sse) = &op.return_type else {
+            return None;
+        };
+        match &sse.event_type_name {
+            Some(name) if self.events.contains(name.as_str()) => Some(format!("deserialize{name}")),
+            _ => self.value_decoder(&sse.event_type),
+        }
+    }
+
+    /// Every codec expression `op`'s client methods use.
+    pub fn operation_codecs(&self, op: &IrOperation) -> Vec<String> {
+        let mut codecs: Vec<String> = op
+            .parameters
+            .iter()
+            .filter(|p| p.pinned_value.is_none())
+            .filter_map(|p| self.value_encoder(&p.param_type))
+            .collect();
+        codecs.extend(self.body_encoder(op));
+        match &op.return_type {
+            IrReturnType::Standard(resp) if !resp.is_text() => {
+                codecs.extend(self.value_decoder(&resp.response_type));
+            }
+            IrReturnType::Sse(sse) => {
+                codecs.extend(self.event_decoder(op));
+                if let Some(resp) = sse.json_response.as_ref().filter(|r| !r.is_text()) {
+                    codecs.extend(self.value_decoder(&resp.response_type));
+                }
+            }
+            _ => {}
+        }
+        codecs
+    }
+
+    /// `value`, in the wire form of `ty`, with its keys renamed to the typed
+    /// property names, e.g. to show a spec example as its generated type.
+    pub fn typed_value(&self, ty: &IrType, value: &Value) -> Value {
+        if self.naming == PropertyNaming::Original {
+            return value.clone();
+        }
+        match (ty, value) {
+            (IrType::Array(item), Value::Array(items)) => Value::Array(
+                items
+                    .iter()
+                    .map(|item_value| self.typed_value(item, item_value))
+                    .collect(),
+            ),
+            (IrType::Map(item, _), Value::Object(entries)) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, v)| (key.clone(), self.typed_value(item, v)))
+                    .collect(),
+            ),
+            (IrType::Object(fields), Value::Object(entries)) => Value::Object(
+                entries
+                    .iter()
+                    .map(|(key, v)| {
+                        let v = match fields.iter().find(|(name, _, _)| name == key) {
+                            Some((_, field_type, _)) => self.typed_value(field_type, v),
+                            None => v.clone(),
+                        };
+                        (key.clone(), v)
+                    })
+                    .collect(),
+            ),
+            (IrType::Union(variants), _) => match self.variant_of(variants, None, value) {
+                Some(variant) => self.typed_value(&variant, value),
+                None => value.clone(),
+            },
+            (IrType::Ref(name), _) => match self.schema(name) {
+                Some(IrSchema::Object(obj)) => self.typed_object(obj, value),
+                Some(IrSchema::Alias(alias)) => self.typed_value(&alias.target, value),
+                Some(IrSchema::Union(union)) => {
+                    match self.variant_of(&union.variants, union.discriminator.as_ref(), value) {
+                        Some(variant) => self.typed_value(&variant, value),
+                        None => value.clone(),
+                    }
+                }
+                _ => value.clone(),
+            },
+            _ => value.clone(),
+        }
+    }
+
+    /// The typed names along the wire property path `path` into a `ty`, e.g.
+    /// `["delta", "stopReason"]` for `delta.stop_reason`. Segments past a value
+    /// that isn't an object schema stay as written.
+    pub fn typed_path(&self, ty: &IrType, path: &[&str]) -> Vec<String> {
+        let mut object = self.object(ty);
+        path.iter()
+            .map(|segment| match object {
+                Some(obj) => {
+                    let field = obj.fields.iter().find(|f| f.original_name == *segment);
+                    object = field.and_then(|f| self.object(&f.field_type));
+                    property_name(obj, segment, self.naming)
+                }
+                None => segment.to_string(),
+            })
+            .collect()
+    }
+
+    /// The name the interface of `ty`, an object schema, declares for its
+    /// wire property `wire`.
+    pub fn typed_property(&self, ty: &IrType, wire: &str) -> String {
+        match self.object(ty) {
+            Some(obj) => property_name(obj, wire, self.naming),
+            None => wire.to_string(),
+        }
+    }
+
+    fn schema(&self, name: &str) -> Option<&'a IrSchema> {
+        self.ir
+            .schemas
+            .iter()
+            .find(|schema| schema.name().pascal_case == name)
+    }
+
+    fn object(&self, ty: &IrType) -> Option<&'a IrObjectSchema> {
+        match ty {
+            IrType::Ref(name) => match self.schema(name)? {
+                IrSchema::Object(obj) => Some(obj),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn typed_object(&self, obj: &IrObjectSchema, value: &Value) -> Value {
+        let Value::Object(entries) = value else {
+            return value.clone();
+        };
+        let names = property_names(obj, self.naming);
+        Value::Object(
+            entries
+                .iter()
+                .map(
+                    |(key, v)| match obj.fields.iter().position(|f| f.original_name == *key) {
+                        Some(i) => (
+                            names[i].clone(),
+                            self.typed_value(&obj.fields[i].field_type, v),
+                        ),
+                        None => match &obj.additional_properties {
+                            Some(item) => (key.clone(), self.typed_value(item, v)),
+                            None => (key.clone(), v.clone()),
+                        },
+                    },
+                )
+                .collect(),
+        )
+    }
+
+    /// The variant of a union a wire `value` is: the one its discriminator
+    /// maps it to, else the first object schema it fits.
+    fn variant_of(
+        &self,
+        variants: &[IrType],
+        discriminator: Option<&IrDiscriminator>,
+        value: &Value,
+    ) -> Option<IrType> {
+        let Value::Object(entries) = value else {
+            return variants
+                .iter()
+                .find(|v| matches!((v, value), (IrType::Array(_), Value::Array(_))))
+                .cloned();
+        };
+        if let Some(d) = discriminator
+            && let Some(Value::String(tag)) = entries.get(&d.property_name)
+            && let Some((_, name)) = d.mapping.iter().find(|(t, _)| t == tag)
+        {
+            return Some(IrType::Ref(name.clone()));
+        }
+        let fits = |obj: &IrObjectSchema| {
+            entries.keys().all(|key| {
+                obj.additional_properties.is_some()
+                    || obj.fields.iter().any(|f| f.original_name == *key)
+            }) && obj.fields.iter().all(|f| match &f.field_type {
+                IrType::StringLiteral(tag) => entries
+                    .get(&f.original_name)
+                    .is_none_or(|v| v.as_str() == Some(tag)),
+                _ => true,
+            })
+        };
+        variants
+            .iter()
+            .find(|v| self.object(v).is_some_and(fits))
+            .cloned()
+    }
+
+    fn schema_codec(&self, schema: &IrSchema, direction: Direction) -> Option<Codec> {
+        match schema {
+            IrSchema::Object(obj) => self.object_codec(obj, direction),
+            IrSchema::Alias(alias) => self.type_codec(&alias.target, direction),
+            IrSchema::Union(union) => {
+                self.union_codec(&union.variants, union.discriminator.as_ref(), direction)
+            }
+            IrSchema::Enum(_) => None,
+        }
+    }
+
+    fn object_codec(&self, obj: &IrObjectSchema, direction: Direction) -> Option<Codec> {
+        if obj.fields.is_empty() {
+            let codec = self.type_codec(obj.additional_properties.as_ref()?, direction)?;
+            return Some(Codec::Function(format!("recordOf({})", codec.function())));
+        }
+        let mut names = Vec::new();
+        let mut values = Vec::new();
+        for (field, name) in obj.fields.iter().zip(property_names(obj, self.naming)) {
+            let (from, to) = match direction {
+                Direction::Serialize => (name, field.original_name.clone()),
+                Direction::Deserialize => (field.original_name.clone(), name),
+            };
+            if let Some(codec) = self.type_codec(&field.field_type, direction) {
+                values.push((from.clone(), codec.function()));
+            }
+            if from != to {
+                names.push((from, to));
+            }
+        }
+        (!names.is_empty() || !values.is_empty()).then_some(Codec::Keys { names, values })
+    }
+
+    fn type_codec(&self, ty: &IrType, direction: Direction) -> Option<Codec> {
+        match ty {
+            IrType::Ref(name) if self.schemas.contains(name.as_str()) => {
+                Some(Codec::Function(format!("{}{name}", direction.prefix())))
+            }
+            IrType::Array(item) => {
+                let codec = self.type_codec(item, direction)?;
+                Some(Codec::Function(format!("arrayOf({})", codec.function())))
+            }
+            IrType::Map(item, _) => {
+                let codec = self.type_codec(item, direction)?;
+                Some(Codec::Function(format!("recordOf({})", codec.function())))
+            }
+            IrType::Union(variants) => self.union_codec(variants, None, direction),
+            IrType::Object(fields) => {
+                let values: Vec<(String, String)> = fields
+                    .iter()
+                    .filter_map(|(name, field_type, _)| {
+                        let codec = self.type_codec(field_type, direction)?;
+                        Some((name.clone(), codec.function()))
+                    })
+                    .collect();
+                (!values.is_empty()).then_some(Codec::Keys {
+                    names: Vec::new(),
+                    values,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// A union's codec: its one non-null variant's, the variants' by their
+    /// tag, or else their key mappings merged.
+    fn union_codec(
+        &self,
+        variants: &[IrType],
+        discriminator: Option<&IrDiscriminator>,
+        direction: Direction,
+    ) -> Option<Codec> {
+        let non_null: Vec<&IrType> = variants.iter().filter(|v| **v != IrType::Null).collect();
+        if let [variant] = non_null[..] {
+            return self.type_codec(variant, direction);
+        }
+
+        let tags: Option<(&str, Vec<(&str, IrType)>)> = match discriminator {
+            Some(d) if !d.mapping.is_empty() => Some((
+                d.property_name.as_str(),
+                d.mapping
+                    .iter()
+                    .map(|(tag, name)| (tag.as_str(), IrType::Ref(name.clone())))
+                    .collect(),
+            )),
+            _ => event_discriminator(self.ir, variants).map(|(property, tags)| {
+                (
+                    property,
+                    tags.into_iter()
+                        .map(|(tag, variant)| (tag, variant.clone()))
+                        .collect(),
+                )
+            }),
+        };
+        if let Some((property, tags)) = tags {
+            let values: Vec<(String, String)> = tags
+                .iter()
+                .filter_map(|(tag, variant)| {
+                    let codec = self.type_codec(variant, direction)?;
+                    Some((tag.to_string(), codec.function()))
+                })
+                .collect();
+            if values.is_empty() {
+                return None;
+            }
+            // Typed values carry the tag under its typed name.
+            let key = match direction {
+                Direction::Serialize => self.typed_property(&tags[0].1, property),
+                Direction::Deserialize => property.to_string(),
+            };
+            return Some(Codec::Function(format!(
+                "byTag({}, {})",
+                serde_json::to_string(&key).expect("strings always serialize"),
+                ts_object(&values, false)
+            )));
+        }
+
+        let mut names: Vec<(String, String)> = Vec::new();
+        let mut values: Vec<(String, String)> = Vec::new();
+        let mut conflicts = HashSet::new();
+        for variant in non_null {
+            let obj = self.object(variant)?;
+            let (variant_names, variant_values) = match self.object_codec(obj, direction) {
+                Some(Codec::Keys { names, values }) => (names, values),
+                Some(Codec::Function(_)) => return None,
+                None => continue,
+            };
+            for (from, to) in variant_names {
+                if !names.iter().any(|(name, _)| *name == from) {
+                    names.push((from, to));
+                }
+            }
+            for (key, codec) in variant_values {
+                match values.iter().find(|(k, _)| *k == key) {
+                    Some((_, existing)) if *existing != codec => {
+                        conflicts.insert(key);
+                    }
+                    Some(_) => {}
+                    None => values.push((key, codec)),
+                }
+            }
+        }
+        // Variants that map a key differently leave its value as is.
+        values.retain(|(key, _)| !conflicts.contains(key));
+        (!names.is_empty() || !values.is_empty()).then_some(Codec::Keys { names, values })
+    }
+}
+
+/// The codec functions and helpers `expressions` call, e.g. `arrayOf` and
+/// `serializePet` for `arrayOf(serializePet)`.
+pub fn codec_identifiers<'e>(
+    expressions: impl IntoIterator<Item = &'e String>,
+) -> BTreeSet<&'e str> {
+    expressions
+        .into_iter()
+        .flat_map(|expression| expression.split(|c: char| !c.is_ascii_alphanumeric() && c != '_'))
+        .filter(|identifier| !identifier.is_empty())
+        .collect()
+}
+
+/// Emit `codecs.ts` — a `serialize{Schema}` and `deserialize{Schema}` for
+/// every schema whose typed form differs from its wire form, and a
+/// `deserialize{Event}` for such SSE event unions — or `None` when `naming`
+/// keeps the wire names. The `arrayOf`-style helpers the client's codecs use
+/// are exported with `export_helpers`; a bundle keeps them to itself.
+pub fn emit_codecs(ir: &IrSpec, naming: PropertyNaming, export_helpers: bool) -> Option<String> {
+    let codecs = Codecs::new(ir, naming);
+    if codecs.is_empty() {
+        return None;
+    }
+
+    let mut entries = Vec::new();
+    let mut imported_types = BTreeSet::new();
+    for schema in &ir.schemas {
+        let name = schema.name().pascal_case.as_str();
+        if !codecs.schemas.contains(name) {
+            continue;
+        }
+        imported_types.insert(name);
+        entries.push((
+            name,
+            codecs
+                .schema_codec(schema, Direction::Serialize)
+                .map(Codec::apply),
+            codecs
+                .schema_codec(schema, Direction::Deserialize)
+                .map(Codec::apply),
+        ));
+    }
+    for event in sse_event_types(ir, &ir.operations) {
+        if codecs.events.contains(event.name) {
+            imported_types.insert(event.name);
+            entries.push((
+                event.name,
+                None,
+                codecs
+                    .union_codec(event.variants, None, Direction::Deserialize)
+                    .map(Codec::apply),
+            ));
+        }
+    }
+
+    let mut used: Vec<String> = entries
+        .iter()
+        .flat_map(|(_, serialize, deserialize)| serialize.iter().chain(deserialize))
+        .cloned()
+        .collect();
+    used.extend(
+        ir.operations
+            .iter()
+            .flat_map(|op| codecs.operation_codecs(op)),
+    );
+    let used = codec_identifiers(&used);
+    let helpers = context! {
+        objectOf => used.contains("objectOf"),
+        arrayOf => used.contains("arrayOf"),
+        recordOf => used.contains("recordOf"),
+        byTag => used.contains("byTag"),
+    };
+
+    let entries: Vec<minijinja::Value> = entries
+        .into_iter()
+        .map(|(name, serialize, deserialize)| {
+            context! {
+                name => name,
+                serialize => serialize,
+                deserialize => deserialize,
+            }
+        })
+        .collect();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template("codecs.ts.j2", include_str!("../../templates/codecs.ts.j2"))
+        .expect("template should be valid");
+    let tmpl = env.get_template("codecs.ts.j2").unwrap();
+    Some(
+        tmpl.render(context! {
+            imported_types => imported_types,
+            helpers => helpers,
+            codecs => entries,
+            export_helpers => export_helpers,
+        })
+        .expect("render should succeed"),
+    )
+}
+
+/// Add `codecs.ts` to `files` when `naming` gives the client codecs. A
+/// `bundled` index (`index.ts`, `index.tsx`) gets it inlined ahead of the
+/// client instead.
+pub fn add_codecs_module(
+    files: &mut Vec<GeneratedFile>,
+    ir: &IrSpec,
+    naming: PropertyNaming,
+    source_dir: &str,
+    index: &str,
+    bundled: bool,
+) {
+    let Some(content) = emit_codecs(ir, naming, !bundled) else {
+        return;
+    };
+    if !bundled {
+        files.push(GeneratedFile {
+            path: source_path(source_dir, "codecs.ts"),
+            content,
+        });
+        return;
+    }
+    let index_path = source_path(source_dir, index);
+    let Some(index) = files.iter_mut().find(|f| f.path == index_path) else {
+        return;
+    };
+    let section = format!(
+        "// === Codecs ===\n\n{}\n\n",
+        strip_relative_imports(&strip_auto_generated_header(&content)).trim()
+    );
+    match index.content.find(CLIENT_SECTION) {
+        Some(at) => index.content.insert_str(at, &section),
+        None => index.content.push_str(&section),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snake_case() -> IrSpec {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/snake-case.yaml"
+        ))
+        .unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn original_naming_needs_no_codecs() {
+        let ir = snake_case();
+        assert!(emit_codecs(&ir, PropertyNaming::Original, true).is_none());
+        let codecs = Codecs::new(&ir, PropertyNaming::Original);
+        assert!(
+            ir.operations
+                .iter()
+                .all(|op| codecs.operation_codecs(op).is_empty())
+        );
+    }
+
+    #[test]
+    fn codecs_rename_keys_and_map_nested_values() {
+        let content = emit_codecs(&snake_case(), PropertyNaming::Camel, true).unwrap();
+        assert!(content.contains(
+            "  return mapKeys(value, { pet_id: \"petId\", display_name: \"displayName\", created_at: \"createdAt\", extra_info: \"extraInfo\" }, { owner: deserializeOwner, tags: arrayOf(deserializeTag), extra_info: recordOf(deserializeTag) }) as Pet;"
+        ));
+        // Clashing names stay as they are.
+        assert!(content.contains("  return mapKeys(value, { lastName: \"last_name\" });"));
+        // Tagged unions pick a variant by the tag under its typed name.
+        assert!(content.contains(
+            "  return byTag(\"eventType\", { pet_fed: serializePetFed, pet_napped: serializePetNapped })(value as never);"
+        ));
+        assert!(content.contains("byTag(\"event_type\", { pet_fed: deserializePetFed"));
+        assert!(content.contains("export function arrayOf(codec: Codec): Codec {"));
+        assert!(!content.contains("function objectOf("));
+    }
+
+    #[test]
+    fn operations_encode_bodies_and_parameters_and_decode_responses() {
+        let ir = snake_case();
+        let codecs = Codecs::new(&ir, PropertyNaming::Camel);
+        let op = |name: &str| {
+            ir.operations
+                .iter()
+                .find(|op| op.name.camel_case == name)
+                .unwrap()
+        };
+        assert_eq!(
+            codecs.operation_codecs(op("listPets")),
+            ["serializePageFilter", "arrayOf(deserializePet)"]
+        );
+        assert_eq!(
+            codecs.operation_codecs(op("createPet")),
+            ["serializeNewPet", "deserializePet"]
+        );
+        assert_eq!(
+            codecs.event_decoder(op("streamPetEvents")).as_deref(),
+            Some("deserializePetEvent")
+        );
+    }
+
+    #[test]
+    fn typed_values_and_paths_use_typed_names() {
+        let ir = snake_case();
+        let codecs = Codecs::new(&ir, PropertyNaming::Camel);
+        let pet = IrType::Ref("Pet".to_string());
+        let value = serde_json::json!({
+            "pet_id": "1",
+            "owner": { "first_name": "Ada", "last_name": "Lovelace" },
+            "tags": [{ "tag_name": "good" }],
+        });
+        assert_eq!(
+            codecs.typed_value(&pet, &value),
+            serde_json::json!({
+                "petId": "1",
+                "owner": { "first_name": "Ada", "lastName": "Lovelace" },
+                "tags": [{ "tagName": "good" }],
+            })
+        );
+        assert_eq!(
+            codecs.typed_path(&pet, &["owner", "last_name"]),
+            ["owner", "lastName"]
+        );
+        let event = IrType::Ref("PetEvent".to_string());
+        assert_eq!(
+            codecs.typed_value(
+                &event,
+                &serde_json::json!({ "event_type": "pet_fed", "food_name": "kibble" })
+            ),
+            serde_json::json!({ "eventType": "pet_fed", "foodName": "kibble" })
+        );
+    }
+}
@@ -0,0 +1,158 @@
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::config::{ParamsStyle, PropertyNaming};
+use oag_core::contract::{
+    contract_status, is_contract_operation, sample_response_value, sample_value,
+};
+use oag_core::ir::{IrReturnType, IrSpec, IrType};
+use serde_json::Value;
+
+use crate::emitters::codecs::Codecs;
+use crate::emitters::tests::build_test_call_args;
+use crate::type_mapper::ts_property_key;
+
+/// Emit `contract/client.contract.ts`, which calls every covered operation on
+/// a live server and checks the status and body the FastAPI stubs answer
+/// with, plus the `contract/vitest.config.ts` that runs it. The suite lives
+/// outside the default vitest include so `vitest run` keeps to mocked tests.
+/// Samples and expectations are written in their typed form, as the client
+/// takes and returns them under `property_naming`.
+pub fn emit_contract_tests(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    source_dir: &str,
+) -> Vec<GeneratedFile> {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "client.contract.ts.j2",
+        include_str!("../../templates/client.contract.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("client.contract.ts.j2").unwrap();
+
+    let codecs = Codecs::new(ir, property_naming);
+    let sample =
+        |ir_type: &IrType| ts_literal(&codecs.typed_value(ir_type, &sample_value(ir, ir_type)));
+    let operations: Vec<minijinja::Value> = ir
+        .operations
+        .iter()
+        .filter(|op| is_contract_operation(op))
+        .map(|op| {
+            let expected = match &op.return_type {
+                IrReturnType::Standard(resp) => {
+                    let value = sample_response_value(ir, &resp.response_type);
+                    Some(ts_literal(&codecs.typed_value(&resp.response_type, &value)))
+                }
+                _ => None,
+            };
+            context! {
+                method_name => op.name.camel_case,
+                call_args => build_test_call_args(op, None, params_style, &sample),
+                status => contract_status(op),
+                expected => expected,
+            }
+        })
+        .collect();
+
+    let client_module = if source_dir.is_empty() {
+        "../index".to_string()
+    } else {
+        format!("../{source_dir}/index")
+    };
+    let content = tmpl
+        .render(context! {
+            operations => operations,
+            client_module => client_module,
+        })
+        .expect("render should succeed");
+
+    vec![
+        GeneratedFile {
+            path: "contract/client.contract.ts".to_string(),
+            content,
+        },
+        GeneratedFile {
+            path: "contract/vitest.config.ts".to_string(),
+            content: include_str!("../../templates/contract.vitest.config.ts.j2").to_string(),
+        },
+    ]
+}
+
+/// A TypeScript literal for a JSON value, with object keys written the way
+/// the generated types declare them.
+pub(crate) fn ts_literal(value: &Value) -> String {
+    match value {
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(ts_literal).collect();
+            format!("[{}]", items.join(", "))
+        }
+        Value::Object(members) if members.is_empty() => "{}".to_string(),
+        Value::Object(members) => {
+            let members: Vec<String> = members
+                .iter()
+                .map(|(key, value)| format!("{}: {}", ts_property_key(key), ts_literal(value)))
+                .collect();
+            format!("{{ {} }}", members.join(", "))
+        }
+        scalar => scalar.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn contract_test(params_style: ParamsStyle) -> String {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        emit_contract_tests(&ir, params_style, PropertyNaming::Original, "src")
+            .into_iter()
+            .find(|f| f.path == "contract/client.contract.ts")
+            .unwrap()
+            .content
+    }
+
+    #[test]
+    fn test_contract_calls_the_live_server() {
+        let content = contract_test(ParamsStyle::Positional);
+        assert!(content.contains("import { ApiClient } from \"../src/index\";"));
+        assert!(content.contains("process.env.CONTRACT_BASE_URL"));
+        assert!(!content.contains("vi.fn"));
+    }
+
+    #[test]
+    fn test_ts_literal_keys() {
+        let value = serde_json::json!({ "name": "test", "x-tags": ["a"], "n": 1 });
+        assert_eq!(
+            ts_literal(&value),
+            "{ n: 1, name: \"test\", \"x-tags\": [\"a\"] }"
+        );
+    }
+
+    #[test]
+    fn test_expected_responses_leave_out_write_only_fields() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/write-only.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_contract_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            "src",
+        )
+        .into_iter()
+        .find(|f| f.path == "contract/client.contract.ts")
+        .unwrap()
+        .content;
+
+        assert!(content.contains("password: \"<redacted>\""));
+        assert!(content.contains("{ email: \"test\", id: \"test\" }"));
+    }
+}
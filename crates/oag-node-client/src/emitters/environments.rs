@@ -0,0 +1,211 @@
+use indexmap::IndexMap;
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::ir::IrSpec;
+use oag_core::transform::normalize_name;
+
+use super::bundled::{strip_auto_generated_header, strip_relative_imports};
+use super::source_path;
+use crate::type_mapper::ts_property_key;
+
+/// The environments `environments.ts` maps to base URLs: `configured`, else
+/// the spec's servers when it lists more than one, each named after its
+/// description in camelCase (`Staging server` → `stagingServer`), or
+/// `server{n}` without one. Empty when there is nothing to choose between.
+pub fn environments(
+    ir: &IrSpec,
+    configured: &IndexMap<String, String>,
+) -> IndexMap<String, String> {
+    if !configured.is_empty() {
+        return configured.clone();
+    }
+    if ir.servers.len() < 2 {
+        return IndexMap::new();
+    }
+    let mut environments = IndexMap::new();
+    for (i, server) in ir.servers.iter().enumerate() {
+        let name = server
+            .description
+            .as_deref()
+            .map(|d| normalize_name(d).camel_case)
+            .filter(|name| !name.is_empty() && !environments.contains_key(name))
+            .unwrap_or_else(|| format!("server{}", i + 1));
+        environments.insert(name, server.default_url.clone());
+    }
+    environments
+}
+
+/// Emit `environments.ts` — `ENVIRONMENTS`, the base URL of each of
+/// `environments` by name, its `Environment` names, and `clientForEnv`, which
+/// builds a client for one. `None` when there are no environments.
+pub fn emit_environments(environments: &IndexMap<String, String>) -> Option<String> {
+    if environments.is_empty() {
+        return None;
+    }
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "environments.ts.j2",
+        include_str!("../../templates/environments.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("environments.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            environments => environment_contexts(environments),
+        })
+        .expect("render should succeed");
+    Some(content)
+}
+
+/// Emit `environments.test.ts` — vitest coverage of each environment's base
+/// URL and of an explicit `baseUrl` taking precedence. `module` is where
+/// `environments.ts`'s exports are imported from and `client_module` where
+/// `ApiClient` is: `./environments` and `./client`, or both `./index` in a
+/// bundle. `None` when there are no environments.
+pub fn emit_environments_tests(
+    environments: &IndexMap<String, String>,
+    module: &str,
+    client_module: &str,
+) -> Option<String> {
+    let first = environments.keys().next()?;
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "environments.test.ts.j2",
+        include_str!("../../templates/environments.test.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("environments.test.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            environments => environment_contexts(environments),
+            first => string_literal(first),
+            module => module,
+            client_module => client_module,
+        })
+        .expect("render should succeed");
+    Some(content)
+}
+
+/// Add `environments.ts` to `files` when there are environments. A `bundled`
+/// index (`index.ts`, `index.tsx`) gets it inlined after the client instead.
+pub fn add_environments_module(
+    files: &mut Vec<GeneratedFile>,
+    environments: &IndexMap<String, String>,
+    source_dir: &str,
+    index: &str,
+    bundled: bool,
+) {
+    let Some(content) = emit_environments(environments) else {
+        return;
+    };
+    if !bundled {
+        files.push(GeneratedFile {
+            path: source_path(source_dir, "environments.ts"),
+            content,
+        });
+        return;
+    }
+    let index_path = source_path(source_dir, index);
+    let Some(index) = files.iter_mut().find(|f| f.path == index_path) else {
+        return;
+    };
+    if !index.content.ends_with('\n') {
+        index.content.push('\n');
+    }
+    index.content.push_str(&format!(
+        "\n// === Environments ===\n\n{}\n",
+        strip_relative_imports(&strip_auto_generated_header(&content)).trim()
+    ));
+}
+
+fn environment_contexts(environments: &IndexMap<String, String>) -> Vec<minijinja::Value> {
+    environments
+        .iter()
+        .map(|(name, url)| {
+            context! {
+                key => ts_property_key(name),
+                literal => string_literal(name),
+                url => string_literal(url),
+                title => string_literal(&format!("uses the base URL of {name}")),
+            }
+        })
+        .collect()
+}
+
+fn string_literal(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn environments_ir() -> IrSpec {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/environments.yaml"
+        ))
+        .unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn servers_seed_the_environments() {
+        let environments = environments(&environments_ir(), &IndexMap::new());
+        assert_eq!(
+            environments.into_iter().collect::<Vec<_>>(),
+            [
+                ("local".to_string(), "http://localhost:8080".to_string()),
+                (
+                    "staging".to_string(),
+                    "https://staging.example.com/v1".to_string()
+                ),
+                (
+                    "server3".to_string(),
+                    "https://api.example.com/v1".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn configured_environments_replace_the_servers() {
+        let configured = IndexMap::from([
+            ("dev".to_string(), "http://localhost:3000".to_string()),
+            ("prod-eu".to_string(), "https://eu.example.com".to_string()),
+        ]);
+        assert_eq!(environments(&environments_ir(), &configured), configured);
+
+        let content = emit_environments(&configured).unwrap();
+        assert!(content.contains("import { ApiClient, type ClientConfig } from \"./client\";"));
+        assert!(content.contains(
+            "export const ENVIRONMENTS = {\n  dev: \"http://localhost:3000\",\n  \"prod-eu\": \"https://eu.example.com\",\n} as const;"
+        ));
+        assert!(content.contains("export type Environment = keyof typeof ENVIRONMENTS;"));
+        assert!(content.contains("baseUrl: config.baseUrl ?? ENVIRONMENTS[environment]"));
+        assert!(content.contains(
+            "export function clientForEnv(environment: Environment, config?: EnvironmentClientConfig): ApiClient {"
+        ));
+
+        let tests = emit_environments_tests(&configured, "./environments", "./client").unwrap();
+        assert!(tests.contains("import { ApiClient } from \"./client\";"));
+        assert!(tests.contains(
+            "expect(environmentConfig(\"prod-eu\").baseUrl).toBe(\"https://eu.example.com\");"
+        ));
+        assert!(tests.contains(
+            "environmentConfig(\"dev\", { baseUrl: \"https://override.test\", timeout: 5 })"
+        ));
+    }
+
+    #[test]
+    fn a_single_server_has_no_environments() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert!(environments(&ir, &IndexMap::new()).is_empty());
+        assert!(emit_environments(&IndexMap::new()).is_none());
+    }
+}
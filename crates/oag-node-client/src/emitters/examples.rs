@@ -0,0 +1,115 @@
+use minijinja::{Environment, context};
+use oag_core::config::{ExamplesMode, PropertyNaming};
+use oag_core::ir::{IrSpec, IrType};
+
+use crate::emitters::codecs::Codecs;
+use crate::emitters::contract::ts_literal;
+
+/// Emit `examples.ts` — a `{schema}Example` constant for every schema with an
+/// `example` in the spec, or `None` when there are none or `mode` is `Skip`.
+///
+/// With `Emit` each constant is checked with `satisfies`, so `tsc` rejects an
+/// example that doesn't match its generated type and names the constant;
+/// `EmitUnchecked` casts instead. `types_module` is the module the types are
+/// imported from (`./types`, or `./index` in the bundled layout). Examples are
+/// written in the wire form, so their keys are renamed by `property_naming`.
+pub fn emit_examples(
+    ir: &IrSpec,
+    mode: ExamplesMode,
+    types_module: &str,
+    property_naming: PropertyNaming,
+) -> Option<String> {
+    if mode == ExamplesMode::Skip {
+        return None;
+    }
+    let codecs = Codecs::new(ir, property_naming);
+    let examples: Vec<minijinja::Value> = ir
+        .schemas
+        .iter()
+        .filter_map(|schema| {
+            let name = schema.name();
+            Some(context! {
+                type_name => name.pascal_case,
+                const_name => format!("{}Example", name.camel_case),
+                value => ts_literal(&codecs.typed_value(
+                    &IrType::Ref(name.pascal_case.clone()),
+                    schema.example()?,
+                )),
+            })
+        })
+        .collect();
+    if examples.is_empty() {
+        return None;
+    }
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "examples.ts.j2",
+        include_str!("../../templates/examples.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("examples.ts.j2").unwrap();
+    Some(
+        tmpl.render(context! {
+            examples => examples,
+            checked => mode == ExamplesMode::Emit,
+            types_module => types_module,
+        })
+        .expect("render should succeed"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn examples(mode: ExamplesMode) -> Option<String> {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/schema-examples.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        emit_examples(&ir, mode, "./types", PropertyNaming::Original)
+    }
+
+    #[test]
+    fn test_emit_examples_checks_each_constant() {
+        let content = examples(ExamplesMode::Emit).unwrap();
+        assert!(content.contains("import type {\n  Pet,\n  Order,\n} from \"./types\";\n"));
+        assert!(content.contains(
+            "export const petExample = { id: 1, name: \"Rex\", status: \"available\" } satisfies Pet;"
+        ));
+        // Invalid examples are emitted too: tsc reports them against `Order`.
+        assert!(
+            content.contains(
+                "export const orderExample = { id: 7, quantity: \"two\" } satisfies Order;"
+            )
+        );
+        assert!(!content.contains("PetStatus"));
+    }
+
+    #[test]
+    fn test_emit_unchecked_examples_cast() {
+        let content = examples(ExamplesMode::EmitUnchecked).unwrap();
+        assert!(content.contains(
+            "export const orderExample = { id: 7, quantity: \"two\" } as unknown as Order;"
+        ));
+        assert!(!content.contains("satisfies"));
+    }
+
+    #[test]
+    fn test_skip_and_specs_without_examples_emit_nothing() {
+        assert_eq!(examples(ExamplesMode::Skip), None);
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert_eq!(
+            emit_examples(&ir, ExamplesMode::Emit, "./types", PropertyNaming::Original),
+            None
+        );
+    }
+}
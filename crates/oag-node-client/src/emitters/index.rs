@@ -1,4 +1,185 @@
-/// Emit `index.ts` — barrel re-exports.
-pub fn emit_index() -> String {
-    include_str!("../../templates/index.ts.j2").to_string()
+use oag_core::GeneratedFile;
+use oag_core::config::PropertyNaming;
+use oag_core::ir::IrSpec;
+
+use super::codecs::Codecs;
+use super::source_path;
+
+/// What a module contributes to the `index.ts` barrel.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Exports {
+    /// `export * from "./module";`
+    All,
+    /// `export type * from "./module";`, for modules of types only.
+    Types,
+    /// `export { ... } from "./module";`, for modules with internals; type-only
+    /// symbols are written `type Name`.
+    Named(Vec<String>),
+}
+
+/// A module the barrel re-exports when it was generated, by its import path
+/// relative to the source directory (`client`, `hooks`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BarrelModule {
+    pub name: String,
+    pub exports: Exports,
+}
+
+impl BarrelModule {
+    pub fn all(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            exports: Exports::All,
+        }
+    }
+
+    pub fn types(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            exports: Exports::Types,
+        }
+    }
+
+    pub fn named<S: Into<String>>(
+        name: impl Into<String>,
+        symbols: impl IntoIterator<Item = S>,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            exports: Exports::Named(symbols.into_iter().map(Into::into).collect()),
+        }
+    }
+
+    /// Whether `files` has the module under `source_dir`: `{name}.ts(x)`, or
+    /// a directory with an `index.ts(x)`.
+    fn generated(&self, files: &[GeneratedFile], source_dir: &str) -> bool {
+        let base = source_path(source_dir, &self.name);
+        ["ts", "tsx"].iter().any(|ext| {
+            let file = format!("{base}.{ext}");
+            let index = format!("{base}/index.{ext}");
+            files.iter().any(|f| f.path == file || f.path == index)
+        })
+    }
+
+    fn line(&self) -> Option<String> {
+        let module = &self.name;
+        match &self.exports {
+            Exports::All => Some(format!("export * from \"./{module}\";")),
+            Exports::Types => Some(format!("export type * from \"./{module}\";")),
+            Exports::Named(symbols) if symbols.is_empty() => None,
+            Exports::Named(symbols) => Some(format!(
+                "export {{ {} }} from \"./{module}\";",
+                symbols.join(", ")
+            )),
+        }
+    }
+}
+
+/// Emit the `index.ts` (or `index.tsx`) barrel: a re-export of each of
+/// `modules`, in order, that `files` holds, so a module that wasn't generated
+/// is left out and an optional one that was shows up from the package root.
+pub fn emit_index(files: &[GeneratedFile], source_dir: &str, modules: &[BarrelModule]) -> String {
+    format!(
+        "// Auto-generated by oag — do not edit\n{}",
+        reexports(files, source_dir, modules)
+    )
+}
+
+/// The re-export lines of [`emit_index`], e.g. for a bundled `index.ts` that
+/// declares the client itself but not the optional modules beside it.
+pub fn reexports(files: &[GeneratedFile], source_dir: &str, modules: &[BarrelModule]) -> String {
+    modules
+        .iter()
+        .filter(|module| module.generated(files, source_dir))
+        .filter_map(BarrelModule::line)
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// The client's own modules, which lead the barrel: `types`, `client` and
+/// `sse`.
+pub fn client_modules() -> Vec<BarrelModule> {
+    vec![
+        BarrelModule::all("types"),
+        BarrelModule::named("client", super::client::EXPORTS.iter().copied()),
+        BarrelModule::named("sse", super::sse::EXPORTS.iter().copied()),
+    ]
+}
+
+/// [`client_modules`] for `minimal_runtime`, leaving out the client's URL and
+/// query helpers.
+pub fn minimal_client_modules() -> Vec<BarrelModule> {
+    let helpers = super::client::HELPER_EXPORTS;
+    vec![
+        BarrelModule::all("types"),
+        BarrelModule::named(
+            "client",
+            super::client::EXPORTS
+                .iter()
+                .copied()
+                .filter(|name| !helpers.contains(name)),
+        ),
+        BarrelModule::named("sse", super::sse::EXPORTS.iter().copied()),
+    ]
+}
+
+/// The optional modules the barrel re-exports after the client's and the
+/// layout's own, when they were generated.
+pub fn feature_modules(ir: &IrSpec, property_naming: PropertyNaming) -> Vec<BarrelModule> {
+    vec![
+        BarrelModule::all("auth"),
+        BarrelModule::all("environments"),
+        BarrelModule::all("logging"),
+        BarrelModule::named("codecs", Codecs::new(ir, property_naming).exports()),
+        BarrelModule::types("webhooks"),
+        BarrelModule::all("routes"),
+        BarrelModule::all("examples"),
+        BarrelModule::all("streaming"),
+        BarrelModule::all("streaming-helpers"),
+        BarrelModule::all("batch"),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(path: &str) -> GeneratedFile {
+        GeneratedFile {
+            path: path.to_string(),
+            content: String::new(),
+        }
+    }
+
+    #[test]
+    fn only_generated_modules_are_exported() {
+        let modules = [
+            BarrelModule::all("types"),
+            BarrelModule::named("client", ["ApiClient", "type ClientConfig"]),
+            BarrelModule::all("hooks"),
+            BarrelModule::types("webhooks"),
+            BarrelModule::named("codecs", Vec::<String>::new()),
+            BarrelModule::all("routes"),
+        ];
+        let files = [
+            file("src/types.ts"),
+            file("src/client.ts"),
+            file("src/hooks/index.tsx"),
+            file("src/webhooks.ts"),
+            file("src/codecs.ts"),
+        ];
+        assert_eq!(
+            emit_index(&files, "src", &modules),
+            "// Auto-generated by oag — do not edit\n\
+             export * from \"./types\";\n\
+             export { ApiClient, type ClientConfig } from \"./client\";\n\
+             export * from \"./hooks\";\n\
+             export type * from \"./webhooks\";\n"
+        );
+        assert_eq!(reexports(&files[..1], "", &modules), "");
+        assert_eq!(
+            reexports(&[file("types.ts")], "", &modules),
+            "export * from \"./types\";\n"
+        );
+    }
 }
@@ -0,0 +1,348 @@
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::ir::{IrOperation, IrSchema, IrSpec, IrType};
+
+use super::bundled::{CLIENT_SECTION, strip_auto_generated_header, strip_relative_imports};
+use super::source_path;
+
+/// Emit `logging.ts` — `createDebugLogger`, the request/response logger behind
+/// `ClientConfig.debug`, with each operation's `writeOnly` request body fields
+/// listed for redaction.
+pub fn emit_logging(ir: &IrSpec) -> String {
+    let write_only_fields: Vec<minijinja::Value> = write_only_body_fields(ir)
+        .into_iter()
+        .map(|(key, paths)| {
+            context! {
+                key => json(&key),
+                paths => paths.iter().map(|p| json(p)).collect::<Vec<_>>(),
+            }
+        })
+        .collect();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "logging.ts.j2",
+        include_str!("../../templates/logging.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("logging.ts.j2").unwrap();
+    tmpl.render(context! {
+        write_only_fields => write_only_fields,
+    })
+    .expect("render should succeed")
+}
+
+/// Emit `logging.test.ts` — vitest coverage of `createDebugLogger`'s header
+/// redaction, and of its body redaction on the first operation with a
+/// `writeOnly` field. `module` is where `logging.ts`'s exports are imported
+/// from: `./logging`, or `./index` in a bundle.
+pub fn emit_logging_tests(ir: &IrSpec, module: &str) -> String {
+    let operation = |op: &IrOperation| {
+        format!(
+            "{{ name: {}, method: {}, path: {}, tags: [] }}",
+            json(&op.name.camel_case),
+            json(op.method.as_str()),
+            json(&op.normalized_path),
+        )
+    };
+    let write_only = ir.operations.iter().find_map(|op| {
+        let path = write_only_paths(ir, &op.request_body.as_ref()?.body_type)
+            .into_iter()
+            .next()?;
+        Some(context! {
+            operation => operation(op),
+            path => path,
+            body => secret_body(&path).to_string(),
+        })
+    });
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "logging.test.ts.j2",
+        include_str!("../../templates/logging.test.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("logging.test.ts.j2").unwrap();
+    tmpl.render(context! {
+        module => module,
+        write_only => write_only,
+    })
+    .expect("render should succeed")
+}
+
+/// Add `logging.ts` to `files`. A `bundled` index gets it inlined ahead of
+/// the client instead.
+pub fn add_logging_module(
+    files: &mut Vec<GeneratedFile>,
+    ir: &IrSpec,
+    source_dir: &str,
+    bundled: bool,
+) {
+    let content = emit_logging(ir);
+    if !bundled {
+        files.push(GeneratedFile {
+            path: source_path(source_dir, "logging.ts"),
+            content,
+        });
+        return;
+    }
+    let index_path = source_path(source_dir, "index.ts");
+    let Some(index) = files.iter_mut().find(|f| f.path == index_path) else {
+        return;
+    };
+    let section = format!(
+        "// === Logging ===\n\n{}\n",
+        strip_relative_imports(&strip_auto_generated_header(&content))
+    );
+    match index.content.find(CLIENT_SECTION) {
+        Some(at) => index.content.insert_str(at, &section),
+        None => index.content.push_str(&section),
+    }
+}
+
+/// Each operation's `writeOnly` request body fields, keyed as the client logs
+/// them (`"POST /users"`); operations without any are left out.
+pub fn write_only_body_fields(ir: &IrSpec) -> Vec<(String, Vec<String>)> {
+    ir.operations
+        .iter()
+        .filter_map(|op| {
+            let paths = write_only_paths(ir, &op.request_body.as_ref()?.body_type);
+            let key = format!("{} {}", op.method.as_str(), op.normalized_path);
+            (!paths.is_empty()).then_some((key, paths))
+        })
+        .collect()
+}
+
+/// The dotted paths to the `writeOnly` fields a body of type `body_type` can
+/// carry, e.g. `password` or `members[].password`.
+fn write_only_paths(ir: &IrSpec, body_type: &IrType) -> Vec<String> {
+    let mut paths = Vec::new();
+    collect_write_only(ir, body_type, "", &mut Vec::new(), &mut paths);
+    paths
+}
+
+fn collect_write_only<'a>(
+    ir: &'a IrSpec,
+    ty: &IrType,
+    prefix: &str,
+    visiting: &mut Vec<&'a str>,
+    paths: &mut Vec<String>,
+) {
+    match ty {
+        IrType::Array(item) => {
+            collect_write_only(ir, item, &format!("{prefix}[]"), visiting, paths)
+        }
+        IrType::Union(variants) | IrType::Intersection(variants) => {
+            for variant in variants {
+                collect_write_only(ir, variant, prefix, visiting, paths);
+            }
+        }
+        IrType::Ref(name) => {
+            let Some(schema) = ir.schemas.iter().find(|s| s.name().pascal_case == *name) else {
+                return;
+            };
+            let name = schema.name().pascal_case.as_str();
+            // A recursive schema's fields were already collected further up.
+            if visiting.contains(&name) {
+                return;
+            }
+            visiting.push(name);
+            match schema {
+                IrSchema::Object(obj) => {
+                    for field in &obj.fields {
+                        let path = match prefix {
+                            "" => field.original_name.clone(),
+                            _ => format!("{prefix}.{}", field.original_name),
+                        };
+                        if field.write_only {
+                            if !paths.contains(&path) {
+                                paths.push(path);
+                            }
+                        } else {
+                            collect_write_only(ir, &field.field_type, &path, visiting, paths);
+                        }
+                    }
+                }
+                IrSchema::Alias(alias) => {
+                    collect_write_only(ir, &alias.target, prefix, visiting, paths)
+                }
+                IrSchema::Union(union) => {
+                    for variant in &union.variants {
+                        collect_write_only(ir, variant, prefix, visiting, paths);
+                    }
+                }
+                IrSchema::Enum(_) => {}
+            }
+            visiting.pop();
+        }
+        _ => {}
+    }
+}
+
+/// A JSON body with `"s3cret"` at `path`, e.g. `{"members":[{"password":"s3cret"}]}`.
+fn secret_body(path: &str) -> serde_json::Value {
+    path.split('.')
+        .rev()
+        .fold(
+            serde_json::json!("s3cret"),
+            |value, segment| match segment.strip_suffix("[]") {
+                Some("") => serde_json::json!([value]),
+                Some(key) => serde_json::json!({ key: [value] }),
+                None => serde_json::json!({ segment: value }),
+            },
+        )
+}
+
+fn json(s: &str) -> String {
+    serde_json::to_string(s).expect("strings always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oag_core::config::{ParamsStyle, PropertyNaming};
+
+    fn transform(yaml: &str) -> IrSpec {
+        let spec = oag_core::parse::from_yaml(yaml).unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    fn write_only_ir() -> IrSpec {
+        transform(include_str!(
+            "../../../oag-core/tests/fixtures/write-only.yaml"
+        ))
+    }
+
+    #[test]
+    fn test_write_only_request_fields_are_redacted() {
+        let ir = write_only_ir();
+        // `AuthToken.refreshToken` is only ever a response field.
+        assert_eq!(
+            write_only_body_fields(&ir),
+            [("POST /users".to_string(), vec!["password".to_string()])]
+        );
+        let content = emit_logging(&ir);
+        assert!(content.contains("  \"POST /users\": [\"password\"],\n"));
+
+        let tests = emit_logging_tests(&ir, "./logging");
+        assert!(tests.contains("import { createDebugLogger, REDACTED } from \"./logging\";"));
+        assert!(tests.contains("body: JSON.stringify({\"password\":\"s3cret\"})"));
+        assert!(tests.contains(
+            "operation: { name: \"createUser\", method: \"POST\", path: \"/users\", tags: [] },"
+        ));
+    }
+
+    #[test]
+    fn test_nested_and_recursive_write_only_paths() {
+        let ir = transform(
+            r##"
+openapi: "3.1.0"
+info: { title: Teams, version: "1.0" }
+paths:
+  /teams:
+    post:
+      operationId: createTeams
+      requestBody:
+        content:
+          application/json:
+            schema:
+              type: array
+              items: { $ref: "#/components/schemas/Team" }
+      responses:
+        "204": { description: Created }
+components:
+  schemas:
+    Team:
+      type: object
+      properties:
+        members:
+          type: array
+          items: { $ref: "#/components/schemas/Member" }
+        parent: { $ref: "#/components/schemas/Team" }
+    Member:
+      type: object
+      properties:
+        name: { type: string }
+        password: { type: string, writeOnly: true }
+"##,
+        );
+        let fields = write_only_body_fields(&ir);
+        assert_eq!(fields[0].1, ["[].members[].password"]);
+        assert_eq!(
+            secret_body(&fields[0].1[0]).to_string(),
+            r#"[{"members":[{"password":"s3cret"}]}]"#
+        );
+    }
+
+    #[test]
+    fn test_specs_without_write_only_fields_redact_headers_only() {
+        let ir = transform(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ));
+        assert!(write_only_body_fields(&ir).is_empty());
+        assert!(emit_logging(&ir).contains(
+            "export const WRITE_ONLY_BODY_FIELDS: Readonly<Record<string, readonly string[]>> = {};"
+        ));
+        assert!(!emit_logging_tests(&ir, "./logging").contains("writeOnly body fields"));
+    }
+
+    #[test]
+    fn test_client_logs_only_with_debug_logging() {
+        let ir = write_only_ir();
+        let plain = crate::emitters::client::emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        assert!(!plain.contains("debug"));
+
+        let content = crate::emitters::client::emit_client(
+            &ir,
+            false,
+            None,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            true,
+            false,
+            false,
+        );
+        assert!(content.contains(
+            "import { createDebugLogger, type DebugLogger, type DebugLoggingOptions } from \"./logging\";"
+        ));
+        assert!(content.contains("  debug?: boolean | DebugLoggingOptions;\n"));
+        assert!(content.contains("    this.debugLogger?.onRequest({ ...req, operation });\n"));
+    }
+
+    #[test]
+    fn test_bundled_index_inlines_logging_ahead_of_the_client() {
+        let ir = write_only_ir();
+        let mut files = vec![GeneratedFile {
+            path: "src/index.ts".to_string(),
+            content: crate::emitters::bundled::emit_bundled(
+                &ir,
+                false,
+                Default::default(),
+                None,
+                ParamsStyle::Positional,
+                PropertyNaming::Original,
+                None,
+                true,
+                false,
+                false,
+            ),
+        }];
+        add_logging_module(&mut files, &ir, "src", true);
+        assert_eq!(files.len(), 1);
+        let index = &files[0].content;
+        let logging = index.find("// === Logging ===").unwrap();
+        assert!(logging < index.find(CLIENT_SECTION).unwrap());
+        assert!(!index.contains("from \"./"));
+    }
+}
@@ -1,11 +1,49 @@
+pub mod auth;
+pub mod batch;
 pub mod bundled;
 pub mod client;
+pub mod codecs;
+pub mod contract;
+pub mod environments;
+pub mod examples;
 pub mod index;
+pub mod logging;
+pub mod next;
+pub mod routes;
 pub mod scaffold;
 pub mod split;
 pub mod sse;
+pub mod streaming;
 pub mod tests;
 pub mod types;
+pub mod webhooks;
+
+/// Template filter rendering a description as a JSDoc block, e.g.
+/// `{{ field.description | jsdoc("  ") }}` with the block's indentation and an
+/// optional `@see` URL.
+pub fn jsdoc_filter(value: String, indent: Option<String>, see: Option<String>) -> String {
+    oag_core::docs::jsdoc(&value, indent.as_deref().unwrap_or(""), see.as_deref())
+}
+
+/// Remove the JSDoc blocks that sit on lines of their own, for
+/// `minimal_runtime`: templates write their docs unconditionally.
+pub fn strip_jsdoc(content: &str) -> String {
+    let mut out = String::with_capacity(content.len());
+    let mut in_doc = false;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim();
+        if !in_doc && trimmed.starts_with("/**") {
+            in_doc = !trimmed.ends_with("*/");
+            continue;
+        }
+        if in_doc {
+            in_doc = !trimmed.ends_with("*/");
+            continue;
+        }
+        out.push_str(line);
+    }
+    out
+}
 
 /// Build a file path under the configured source directory.
 ///
@@ -0,0 +1,633 @@
+use std::collections::HashSet;
+
+use minijinja::{Environment, context};
+use oag_core::config::ParamsStyle;
+use oag_core::ir::{
+    IrOperation, IrParameter, IrParameterLocation, IrReturnType, IrSchema, IrSpec, IrType,
+    is_json_media_type,
+};
+use oag_core::{GeneratedFile, GeneratorError};
+
+use crate::emitters::auth::has_bearer_auth;
+use crate::emitters::client::{method_arguments, param_ident, params_object_members};
+use crate::emitters::streaming::{HelperModules, add_imports, import_contexts};
+
+/// An operation's entry in the route handler table.
+struct Handler {
+    method: &'static str,
+    /// The route pattern, a regular expression literal.
+    pattern: String,
+    /// Path parameters in the pattern, to try routes with fewer first.
+    path_params: usize,
+    /// `ParamSpec` object literals.
+    params: Vec<String>,
+    /// The `BodySpec` object literal.
+    body: Option<String>,
+    /// The `send` function's destructured input.
+    input: String,
+    /// The `{name}Raw(...)` call, when the operation has a non-streaming method.
+    call: Option<String>,
+    /// The `{streamName}(...)` call, for SSE operations.
+    stream: Option<String>,
+    /// How the `Raw` call's data becomes the response body: `json`, `raw` or `void`.
+    response: &'static str,
+}
+
+/// Emit the Next.js route handlers under `dir`:
+///
+/// - `handlers.ts` — `handle(request, segments)`, which routes a request to its
+///   operation, validates its parameters and body, and forwards it upstream
+/// - `upstream.ts` — `upstreamClient()`, the client configured from the
+///   server's `OAG_UPSTREAM_URL` and `OAG_UPSTREAM_TOKEN`
+/// - `[...oag]/route.ts` — the catch-all route exporting one handler per HTTP
+///   method the spec uses
+///
+/// `modules` are relative to `source_dir`, as for the helper modules.
+pub fn emit_next_handlers(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    modules: &HelperModules,
+    dir: &str,
+    source_dir: &str,
+    default_base_url: Option<&str>,
+) -> Result<Vec<GeneratedFile>, GeneratorError> {
+    let (handlers, types) = handlers(ir, params_style);
+    let client_module = source_module(dir, source_dir, modules.client);
+    let types_module = source_module(dir, source_dir, modules.types);
+
+    let mut imports = Vec::new();
+    add_imports(
+        &mut imports,
+        &client_module,
+        ["ApiClient".to_string(), "ApiResponse".to_string()],
+    );
+    add_imports(&mut imports, &types_module, types);
+
+    let mut methods: Vec<&str> = Vec::new();
+    for handler in &handlers {
+        if !methods.contains(&handler.method) {
+            methods.push(handler.method);
+        }
+    }
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    for (name, source) in [
+        (
+            "next-handlers.ts.j2",
+            include_str!("../../templates/next-handlers.ts.j2"),
+        ),
+        (
+            "next-upstream.ts.j2",
+            include_str!("../../templates/next-upstream.ts.j2"),
+        ),
+        (
+            "next-route.ts.j2",
+            include_str!("../../templates/next-route.ts.j2"),
+        ),
+    ] {
+        env.add_template(name, source)
+            .expect("template should be valid");
+    }
+    let render = |name: &str, ctx: minijinja::Value| {
+        env.get_template(name)
+            .unwrap()
+            .render(ctx)
+            .map_err(|e| GeneratorError::Render(e.to_string()))
+    };
+
+    let handlers_ts = render(
+        "next-handlers.ts.j2",
+        context! {
+            imports => import_contexts(imports),
+            handlers => handlers
+                .iter()
+                .map(|h| context! {
+                    method => h.method,
+                    pattern => h.pattern,
+                    params => h.params,
+                    body => h.body,
+                    input => h.input,
+                    call => h.call,
+                    stream => h.stream,
+                    response => h.response,
+                })
+                .collect::<Vec<_>>(),
+        },
+    )?;
+    let upstream_ts = render(
+        "next-upstream.ts.j2",
+        context! {
+            client_module => client_module,
+            default_base_url => default_base_url.is_some(),
+            bearer_auth => has_bearer_auth(ir),
+        },
+    )?;
+    let route_ts = render("next-route.ts.j2", context! { methods => methods })?;
+
+    Ok(vec![
+        GeneratedFile {
+            path: format!("{dir}/handlers.ts"),
+            content: handlers_ts,
+        },
+        GeneratedFile {
+            path: format!("{dir}/upstream.ts"),
+            content: upstream_ts,
+        },
+        GeneratedFile {
+            path: format!("{dir}/[...oag]/route.ts"),
+            content: route_ts,
+        },
+    ])
+}
+
+/// A module of the source directory (`./client`) as imported from `dir`,
+/// e.g. `../src/client`.
+fn source_module(dir: &str, source_dir: &str, module: &str) -> String {
+    let depth = dir
+        .split('/')
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .count();
+    let module = module.trim_start_matches("./");
+    let up = "../".repeat(depth);
+    if source_dir.is_empty() {
+        format!("{up}{module}")
+    } else {
+        format!("{up}{source_dir}/{module}")
+    }
+}
+
+/// The handler table, routes with fewer path parameters first so literal
+/// segments win over placeholders, and the types-module names its calls use.
+/// Like the client, an operation whose method names an earlier one took is
+/// left out.
+fn handlers(ir: &IrSpec, params_style: ParamsStyle) -> (Vec<Handler>, Vec<String>) {
+    let mut seen = HashSet::new();
+    let mut types = Vec::new();
+    let mut handlers: Vec<Handler> = ir
+        .operations
+        .iter()
+        .filter_map(|op| {
+            let (stream, call) = match &op.return_type {
+                IrReturnType::Sse(sse) => {
+                    let stream = seen
+                        .insert(sse.stream_name.camel_case.clone())
+                        .then(|| sse.stream_name.camel_case.clone());
+                    let call = sse.json_response.as_ref().and_then(|_| {
+                        seen.insert(op.name.camel_case.clone())
+                            .then(|| format!("{}Raw", op.name.camel_case))
+                    });
+                    (stream, call)
+                }
+                _ => (
+                    None,
+                    seen.insert(op.name.camel_case.clone())
+                        .then(|| format!("{}Raw", op.name.camel_case)),
+                ),
+            };
+            if stream.is_none() && call.is_none() {
+                return None;
+            }
+            let arguments = method_arguments(op, params_style);
+            types.extend(arguments.types);
+            let (args, uses) = call_arguments(&arguments.parts, params_style);
+            let mut input = Vec::new();
+            if !uses.args.is_empty() {
+                input.push(format!("args: {{ {} }}", uses.args.join(", ")));
+            }
+            if uses.params {
+                input.push("params".to_string());
+            }
+            if uses.body {
+                input.push("body".to_string());
+            }
+            if stream.is_some() && call.is_some() {
+                input.push("stream".to_string());
+            }
+            input.push("signal".to_string());
+            let args: String = args.iter().map(|arg| format!("{arg}, ")).collect();
+            let (pattern, placeholders) = path_pattern(&op.raw_path);
+            Some(Handler {
+                method: op.method.as_str(),
+                pattern,
+                path_params: placeholders.len(),
+                params: param_specs(ir, op, params_style, &placeholders),
+                body: op.request_body.as_ref().map(|body| {
+                    let kind = if body.is_json() {
+                        "json"
+                    } else if matches!(
+                        body.content_type.as_str(),
+                        "multipart/form-data" | "application/x-www-form-urlencoded"
+                    ) {
+                        "form"
+                    } else if body.is_text() {
+                        "text"
+                    } else {
+                        "blob"
+                    };
+                    format!("{{ kind: \"{kind}\", required: {} }}", body.required)
+                }),
+                input: input.join(", "),
+                call: call.map(|name| format!("{name}({args}{{ signal }})")),
+                stream: stream.map(|name| format!("{name}({args}{{ signal }})")),
+                response: response_kind(op),
+            })
+        })
+        .collect();
+    handlers.sort_by_key(|h| h.path_params);
+    types.sort();
+    types.dedup();
+    (handlers, types)
+}
+
+/// Which parts of the handler input a call reads.
+#[derive(Default)]
+struct InputUses {
+    /// Argument names read from `args`.
+    args: Vec<String>,
+    params: bool,
+    body: bool,
+}
+
+/// The call's arguments before `options`, each cast from the handler input,
+/// from a method's parameter declarations (`petId: string`, `limit?: number`).
+fn call_arguments(parts: &[String], params_style: ParamsStyle) -> (Vec<String>, InputUses) {
+    let mut uses = InputUses::default();
+    let args = parts
+        .iter()
+        .map(|part| {
+            let (name, ty) = part.split_once(": ").unwrap_or((part, "unknown"));
+            let (name, ty) = match name.strip_suffix('?') {
+                Some(name) => (name, format!("{ty} | undefined")),
+                None => (name, ty.to_string()),
+            };
+            match name {
+                "body" => uses.body = true,
+                "params" if params_style == ParamsStyle::Object => uses.params = true,
+                _ => uses.args.push(name.to_string()),
+            }
+            format!("{name} as {ty}")
+        })
+        .collect();
+    (args, uses)
+}
+
+/// A regular expression literal matching `raw_path`, each `{placeholder}` one
+/// path segment in a capture group, and the placeholders in order.
+fn path_pattern(raw_path: &str) -> (String, Vec<String>) {
+    let mut pattern = String::from("/^");
+    let mut placeholders = Vec::new();
+    let mut rest = raw_path;
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        escape_regex(&rest[..start], &mut pattern);
+        placeholders.push(rest[start + 1..start + end].to_string());
+        pattern.push_str("([^/]+)");
+        rest = &rest[start + end + 1..];
+    }
+    escape_regex(rest, &mut pattern);
+    pattern.push_str("$/");
+    (pattern, placeholders)
+}
+
+fn escape_regex(literal: &str, out: &mut String) {
+    for c in literal.chars() {
+        if "\\^$.|?*+()[]{}/".contains(c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+}
+
+/// The `ParamSpec` literals of the parameters callers pass: path, query and
+/// header ones, pinned parameters left out.
+fn param_specs(
+    ir: &IrSpec,
+    op: &IrOperation,
+    params_style: ParamsStyle,
+    placeholders: &[String],
+) -> Vec<String> {
+    let object_members: Vec<&str> = if params_style == ParamsStyle::Object {
+        params_object_members(op)
+            .iter()
+            .map(|p| p.original_name.as_str())
+            .collect()
+    } else {
+        Vec::new()
+    };
+    op.parameters
+        .iter()
+        .filter(|p| p.pinned_value.is_none())
+        .filter_map(|param| {
+            let location = match param.location {
+                IrParameterLocation::Path => "path",
+                IrParameterLocation::Query => "query",
+                IrParameterLocation::Header => "header",
+                _ => return None,
+            };
+            let object = object_members.contains(&param.original_name.as_str());
+            let key = if object {
+                param.name.camel_case.clone()
+            } else {
+                param_ident(param)
+            };
+            let (kind, values) = param_kind(ir, param);
+            let mut spec = format!(
+                "{{ name: {}, key: {}, in: \"{location}\", kind: \"{kind}\", required: {}",
+                quote(&param.original_name),
+                quote(&key),
+                param.required
+            );
+            if object {
+                spec.push_str(", object: true");
+            }
+            if param.query_object.is_none()
+                && matches!(strip_null(&param.param_type), IrType::Array(_))
+            {
+                match (param.location, param.query_array) {
+                    (IrParameterLocation::Query, None) => spec.push_str(", array: {}"),
+                    (IrParameterLocation::Query, Some(style)) => spec.push_str(&format!(
+                        ", array: {{ delimiter: {} }}",
+                        quote(style.delimiter())
+                    )),
+                    _ => spec.push_str(", array: { delimiter: \",\" }"),
+                }
+            }
+            if let Some(values) = values {
+                let values: Vec<String> = values.iter().map(|v| quote(v)).collect();
+                spec.push_str(&format!(", values: [{}]", values.join(", ")));
+            }
+            if let Some(notation) = param.query_object {
+                spec.push_str(&format!(", notation: \"{}\"", notation.as_str()));
+            }
+            if param.location == IrParameterLocation::Path
+                && let Some(index) = placeholders.iter().position(|p| *p == param.original_name)
+            {
+                spec.push_str(&format!(", group: {}", index + 1));
+            }
+            spec.push_str(" }");
+            Some(spec)
+        })
+        .collect()
+}
+
+/// How a parameter's string value is checked and converted, and the values
+/// an enum parameter accepts.
+fn param_kind(ir: &IrSpec, param: &IrParameter) -> (&'static str, Option<Vec<String>>) {
+    if param.query_object.is_some() {
+        return ("object", None);
+    }
+    if param
+        .content_type
+        .as_deref()
+        .is_some_and(is_json_media_type)
+    {
+        return ("json", None);
+    }
+    match strip_null(&param.param_type) {
+        IrType::Array(item) => type_kind(ir, strip_null(item)),
+        ty => type_kind(ir, ty),
+    }
+}
+
+fn type_kind(ir: &IrSpec, ty: &IrType) -> (&'static str, Option<Vec<String>>) {
+    match ty {
+        IrType::Number => ("number", None),
+        IrType::Integer => ("integer", None),
+        IrType::Boolean => ("boolean", None),
+        IrType::StringLiteral(value) => ("string", Some(vec![value.clone()])),
+        IrType::Union(variants) => {
+            let values: Option<Vec<String>> = variants
+                .iter()
+                .map(|v| match v {
+                    IrType::StringLiteral(value) => Some(value.clone()),
+                    _ => None,
+                })
+                .collect();
+            ("string", values)
+        }
+        IrType::Ref(name) => match ir.schemas.iter().find(|s| s.name().pascal_case == *name) {
+            // Only string enums keep their values; others take any value.
+            Some(IrSchema::Enum(e)) if !e.variants.is_empty() => {
+                ("string", Some(e.variants.clone()))
+            }
+            Some(IrSchema::Alias(alias)) => type_kind(ir, strip_null(&alias.target)),
+            _ => ("string", None),
+        },
+        _ => ("string", None),
+    }
+}
+
+/// `T` for a nullable `T | null`; parameters can't be sent as null.
+fn strip_null(ty: &IrType) -> &IrType {
+    match ty {
+        IrType::Union(variants) => {
+            let mut rest = variants.iter().filter(|v| **v != IrType::Null);
+            match (rest.next(), rest.next()) {
+                (Some(only), None) => only,
+                _ => ty,
+            }
+        }
+        _ => ty,
+    }
+}
+
+/// How the `Raw` call's data becomes the response body: JSON re-encoded, text
+/// or binary passed as-is, or none.
+fn response_kind(op: &IrOperation) -> &'static str {
+    let response = match &op.return_type {
+        IrReturnType::Standard(response) => response,
+        IrReturnType::Sse(sse) => match &sse.json_response {
+            Some(response) => response,
+            None => return "json",
+        },
+        IrReturnType::Void => return "void",
+    };
+    if response.is_text() || response.response_type == IrType::Binary {
+        "raw"
+    } else {
+        "json"
+    }
+}
+
+fn quote(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn modular() -> HelperModules<'static> {
+        HelperModules {
+            client: "./client",
+            sse: "./sse",
+            types: "./types",
+        }
+    }
+
+    fn transform(yaml: &str) -> IrSpec {
+        oag_core::transform::transform(&oag_core::parse::from_yaml(yaml).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn path_patterns_capture_placeholders() {
+        let (pattern, placeholders) = path_pattern("/stores/{store_id}/pets/{petId}.json");
+        assert_eq!(pattern, r"/^\/stores\/([^/]+)\/pets\/([^/]+)\.json$/");
+        assert_eq!(placeholders, ["store_id", "petId"]);
+        assert_eq!(path_pattern("/pets").0, r"/^\/pets$/");
+    }
+
+    #[test]
+    fn source_modules_are_relative_to_the_handlers() {
+        assert_eq!(source_module("next", "src", "./client"), "../src/client");
+        assert_eq!(
+            source_module("app/api", "src", "./index"),
+            "../../src/index"
+        );
+        assert_eq!(source_module("next", "", "./types"), "../types");
+    }
+
+    #[test]
+    fn handlers_validate_params_and_call_the_raw_methods() {
+        let ir = transform(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ));
+        let files = emit_next_handlers(
+            &ir,
+            ParamsStyle::Positional,
+            &modular(),
+            "next",
+            "src",
+            None,
+        )
+        .unwrap();
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "next/handlers.ts",
+                "next/upstream.ts",
+                "next/[...oag]/route.ts"
+            ]
+        );
+
+        let handlers = &files[0].content;
+        assert!(
+            handlers.contains("import type { ApiClient, ApiResponse } from \"../src/client\";")
+        );
+        assert!(handlers.contains(
+            "{ name: \"petId\", key: \"petId\", in: \"path\", kind: \"string\", required: true, group: 1 }"
+        ));
+        assert!(handlers.contains(
+            "{ name: \"limit\", key: \"limit\", in: \"query\", kind: \"integer\", required: false }"
+        ));
+        assert!(
+            handlers
+                .contains("forward(await client.getPetRaw(petId as string, { signal }), \"json\")")
+        );
+        assert!(handlers.contains("body: { kind: \"json\", required: true }"));
+        // `/pets` is tried before `/pets/{petId}`.
+        let pets = handlers.find(r"pattern: /^\/pets$/").unwrap();
+        let pet = handlers.find(r"pattern: /^\/pets\/([^/]+)$/").unwrap();
+        assert!(pets < pet);
+
+        let upstream = &files[1].content;
+        assert!(upstream.contains("import { ApiClient } from \"../src/client\";"));
+        assert!(upstream.contains("throw new Error(\"OAG_UPSTREAM_URL is not set\");"));
+
+        let route = &files[2].content;
+        assert!(route.contains("import { handle } from \"../handlers\";"));
+        assert!(route.contains("export const GET = route;"));
+        assert!(route.contains("export const POST = route;"));
+        assert!(!route.contains("export const PATCH"));
+    }
+
+    #[test]
+    fn non_string_enum_params_take_any_value() {
+        let ir = transform(
+            r##"
+openapi: "3.1.0"
+info: { title: Logs, version: "1" }
+paths:
+  /logs:
+    get:
+      operationId: listLogs
+      parameters:
+        - name: level
+          in: query
+          schema: { $ref: "#/components/schemas/Level" }
+        - name: order
+          in: query
+          schema: { $ref: "#/components/schemas/Order" }
+      responses:
+        "204": { description: Listed }
+components:
+  schemas:
+    Level: { type: integer, enum: [1, 2, 3] }
+    Order: { type: string, enum: [asc, desc] }
+"##,
+        );
+        let files = emit_next_handlers(
+            &ir,
+            ParamsStyle::Positional,
+            &modular(),
+            "next",
+            "src",
+            None,
+        )
+        .unwrap();
+        let handlers = &files[0].content;
+        assert!(handlers.contains(
+            "{ name: \"level\", key: \"level\", in: \"query\", kind: \"string\", required: false },"
+        ));
+        assert!(handlers.contains(
+            "{ name: \"order\", key: \"order\", in: \"query\", kind: \"string\", required: false, values: [\"asc\", \"desc\"] },"
+        ));
+    }
+
+    #[test]
+    fn sse_operations_stream_when_the_caller_accepts_it() {
+        let ir = transform(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ));
+        let files = emit_next_handlers(
+            &ir,
+            ParamsStyle::Positional,
+            &modular(),
+            "next",
+            "src",
+            Some("https://api.example.com"),
+        )
+        .unwrap();
+        let handlers = &files[0].content;
+        assert!(handlers.contains(
+            "stream\n        ? streamResponse(client.createChatCompletionStream(body as ChatCompletionRequest, { signal }))\n        : forward(await client.createChatCompletionRaw(body as ChatCompletionRequest, { signal }), \"json\")"
+        ));
+        assert!(
+            files[1]
+                .content
+                .contains("...(baseUrl ? { baseUrl } : {}),")
+        );
+    }
+
+    #[test]
+    fn object_params_are_collected_into_the_params_argument() {
+        let ir = transform(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ));
+        let files =
+            emit_next_handlers(&ir, ParamsStyle::Object, &modular(), "next", "src", None).unwrap();
+        let handlers = &files[0].content;
+        assert!(handlers.contains(
+            "{ name: \"limit\", key: \"limit\", in: \"query\", kind: \"integer\", required: false, object: true }"
+        ));
+        assert!(
+            handlers
+                .contains("client.listPetsRaw(params as ListPetsParams | undefined, { signal })")
+        );
+        assert!(handlers.contains("import type { ListPetsParams"));
+    }
+}
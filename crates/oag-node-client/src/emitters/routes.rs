@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+use minijinja::{Environment, context};
+use oag_core::ir::{IrParameterLocation, IrReturnType, IrSpec, IrType};
+
+use crate::emitters::client::{collect_types_from_ir_type, collect_types_from_request_body};
+use crate::type_mapper::{ir_type_to_ts, request_body_ts_type};
+
+/// Emit `routes.ts` — a typed table of every operation's method, path, path
+/// parameters and body, response and SSE event types, for proxies, routers
+/// and contract tests that don't need the client.
+///
+/// `types_module` is the module the types are imported from (`./types`, or
+/// `./index` in the bundled layout).
+pub fn emit_routes(ir: &IrSpec, types_module: &str) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template("routes.ts.j2", include_str!("../../templates/routes.ts.j2"))
+        .expect("template should be valid");
+    let tmpl = env.get_template("routes.ts.j2").unwrap();
+
+    let json = |s: &str| serde_json::to_string(s).expect("strings always serialize");
+    let mut seen = HashSet::new();
+    let mut imported_types = HashSet::new();
+    let mut routes = Vec::new();
+    for op in &ir.operations {
+        if !seen.insert(op.name.camel_case.as_str()) {
+            continue;
+        }
+        let request_body = op.request_body.as_ref();
+        let (response, events) = match &op.return_type {
+            IrReturnType::Standard(resp) => (Some(resp.response_type.clone()), None),
+            IrReturnType::Void => (None, None),
+            IrReturnType::Sse(sse) => (
+                sse.json_response.as_ref().map(|r| r.response_type.clone()),
+                Some(match &sse.event_type_name {
+                    Some(name) => IrType::Ref(name.clone()),
+                    None => sse.event_type.clone(),
+                }),
+            ),
+        };
+        if let Some(body) = request_body {
+            collect_types_from_request_body(body, &mut imported_types);
+        }
+        for ty in response.iter().chain(&events) {
+            collect_types_from_ir_type(ty, &mut imported_types);
+        }
+
+        let path_params: Vec<String> = op
+            .parameters
+            .iter()
+            .filter(|p| p.location == IrParameterLocation::Path)
+            .map(|p| json(&p.name.camel_case))
+            .collect();
+        routes.push(context! {
+            key => op.name.camel_case.clone(),
+            method => op.method.as_str(),
+            path => json(&op.normalized_path),
+            path_params => path_params,
+            request_body => request_body.map(request_body_ts_type),
+            response => response.as_ref().map(ir_type_to_ts),
+            events => events.as_ref().map(ir_type_to_ts),
+        });
+    }
+
+    let mut imported_types: Vec<String> = imported_types.into_iter().collect();
+    imported_types.sort();
+    tmpl.render(context! {
+        routes => routes,
+        imported_types => imported_types,
+        types_module => types_module,
+    })
+    .expect("render should succeed")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_emit_routes() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_routes(&ir, "./types");
+
+        assert!(content.contains("} from \"./types\";"));
+        assert!(content.contains(
+            "  getModel: {\n    method: \"GET\";\n    path: \"/v1/models/{modelId}\";\n    pathParams: readonly [\"modelId\"];\n    response: ModelInfo;\n  };"
+        ));
+        assert!(content.contains(
+            "  getModel: { method: \"GET\", path: \"/v1/models/{modelId}\", pathParams: [\"modelId\"] },"
+        ));
+        // The streaming operation has a JSON variant too.
+        let create = content.split("  createMessage: {\n").nth(1).unwrap();
+        let create = &create[..create.find("  };").unwrap()];
+        assert!(create.contains("requestBody: CreateMessageRequest;"));
+        assert!(create.contains("response: MessageResponse;"));
+        assert!(create.contains("events: CreateMessageStreamEvent;"));
+        // A void operation has no response.
+        let cancel = content.split("  cancelBatch: {\n").nth(1).unwrap();
+        let cancel = &cancel[..cancel.find("  };").unwrap()];
+        assert!(!cancel.contains("response"));
+    }
+}
@@ -1,11 +1,12 @@
 use minijinja::{Environment, context};
 use oag_core::GeneratedFile;
-use oag_core::config::ToolSetting;
+use oag_core::config::{ScaffoldVersion, ToolSetting};
+use oag_core::transform::name_normalizer::normalize_name;
 use serde::Deserialize;
 
 /// Node/TS-specific scaffold configuration, parsed from the opaque `serde_json::Value`.
 #[derive(Debug, Clone, Default, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct NodeScaffoldConfig {
     pub package_name: Option<String>,
     pub repository: Option<String>,
@@ -13,7 +14,82 @@ pub struct NodeScaffoldConfig {
     pub formatter: Option<ToolSetting>,
     pub test_runner: Option<ToolSetting>,
     pub bundler: Option<ToolSetting>,
+    /// Generate into an existing project: no `package.json`, `tsconfig.json`,
+    /// `biome.json` or `tsdown.config.ts`, but a `DEPENDENCIES.md` listing what
+    /// the generated code needs, and tests only with a declared `test_runner`.
     pub existing_repo: Option<bool>,
+    /// Emit a `.gitignore` (default: only when not `existing_repo`).
+    pub gitignore: Option<bool>,
+    /// Client class name used by the React provider and hooks (default: derived from the spec title).
+    pub client_class_name: Option<String>,
+    /// Emit `usePrefetch*` helpers for SWR `fallback` data (React only).
+    pub swr_prefetch: Option<bool>,
+    /// Compiler checks the emitted `tsconfig.json` turns on (default: `default`).
+    pub tsconfig_preset: Option<TsconfigPreset>,
+    /// Where the SSE runtime comes from (default: `inline`).
+    pub sse_runtime: Option<SseRuntime>,
+    /// The `package.json` version (default: `0.1.0`).
+    pub version: Option<ScaffoldVersion>,
+}
+
+/// Where generated clients get the SSE runtime (`streamSse`, `SSEOptions`, ...).
+///
+/// In YAML: `inline`, or `package:@org/oag-sse` with an optional version
+/// range (`package:@org/oag-sse@^1.0.0`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub enum SseRuntime {
+    /// `sse.ts` is generated next to the client.
+    #[default]
+    Inline,
+    /// Imported from an npm package, so several clients can share one copy.
+    Package { name: String, version: String },
+}
+
+impl SseRuntime {
+    /// The package name in package mode.
+    pub fn package(&self) -> Option<&str> {
+        match self {
+            SseRuntime::Inline => None,
+            SseRuntime::Package { name, .. } => Some(name),
+        }
+    }
+}
+
+impl TryFrom<String> for SseRuntime {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value == "inline" {
+            return Ok(SseRuntime::Inline);
+        }
+        let Some(spec) = value.strip_prefix("package:").filter(|s| !s.is_empty()) else {
+            return Err(format!(
+                "expected `inline` or `package:<name>`, got `{value}`"
+            ));
+        };
+        // A version follows the last `@` that isn't a scope's leading one.
+        let (name, version) = match spec[1..].rfind('@') {
+            Some(at) => (&spec[..at + 1], &spec[at + 2..]),
+            None => (spec, "*"),
+        };
+        Ok(SseRuntime::Package {
+            name: name.to_string(),
+            version: version.to_string(),
+        })
+    }
+}
+
+/// Compiler checks of the emitted `tsconfig.json`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TsconfigPreset {
+    /// `strict` only.
+    #[default]
+    Default,
+    /// The checks of `@tsconfig/strictest`, e.g. `noUncheckedIndexedAccess` and
+    /// `exactOptionalPropertyTypes`.
+    Strictest,
 }
 
 /// Options controlling which scaffold files to generate.
@@ -33,16 +109,39 @@ pub struct ScaffoldOptions {
     pub bundler: Option<String>,
     /// Whether React target is included.
     pub react: bool,
-    /// Whether generating into an existing repo (skip all scaffold files).
+    /// Whether generating into an existing repo (skip the project files and
+    /// list their requirements in `DEPENDENCIES.md` instead).
     pub existing_repo: bool,
+    /// Whether to emit a `.gitignore`, independent of `existing_repo`.
+    pub gitignore: bool,
     /// Subdirectory for source files (e.g. "src", "lib", or "" for root).
     pub source_dir: String,
+    /// Client class name, e.g. `AiChatApiClient` (custom or derived from spec title).
+    pub client_class_name: String,
+    /// Whether React hooks include `usePrefetch*` helpers.
+    pub swr_prefetch: bool,
+    /// Compiler checks of the emitted `tsconfig.json`.
+    pub tsconfig_preset: TsconfigPreset,
+    /// Where the SSE runtime comes from; a package is added to `dependencies`.
+    pub sse_runtime: SseRuntime,
+    /// The `package.json` version, already resolved against the spec.
+    pub version: String,
 }
 
-/// Generate project scaffold files (package.json, tsconfig.json, biome.json, tsdown.config.ts).
-/// When `existing_repo` is true, only a root-level `index.ts` re-export is generated;
-/// all other scaffold files are skipped.
+/// Generate project scaffold files (package.json, tsconfig.json, biome.json, tsdown.config.ts,
+/// .gitignore). When `existing_repo` is true, only a root-level `index.ts` re-export and a
+/// `DEPENDENCIES.md` for the host project are generated; all other scaffold files are
+/// skipped unless `gitignore` is set explicitly.
 pub fn emit_scaffold(options: &ScaffoldOptions) -> Vec<GeneratedFile> {
+    let mut files = Vec::new();
+
+    if options.gitignore {
+        files.push(GeneratedFile {
+            path: ".gitignore".to_string(),
+            content: include_str!("../../templates/gitignore.j2").to_string(),
+        });
+    }
+
     if options.existing_repo {
         let ext = if options.react { "tsx" } else { "ts" };
         let import_path = if options.source_dir.is_empty() {
@@ -50,16 +149,19 @@ pub fn emit_scaffold(options: &ScaffoldOptions) -> Vec<GeneratedFile> {
         } else {
             format!("./{}/index", options.source_dir)
         };
-        return vec![GeneratedFile {
+        files.push(GeneratedFile {
             path: format!("index.{ext}"),
             content: format!(
                 "// Auto-generated by oag — do not edit\nexport * from \"{import_path}\";\n"
             ),
-        }];
+        });
+        files.push(GeneratedFile {
+            path: "DEPENDENCIES.md".to_string(),
+            content: emit_dependencies(options),
+        });
+        return files;
     }
 
-    let mut files = Vec::new();
-
     // package.json
     files.push(GeneratedFile {
         path: "package.json".to_string(),
@@ -110,9 +212,16 @@ fn emit_package_json(options: &ScaffoldOptions) -> String {
     let vitest = options.test_runner.as_deref() == Some("vitest");
     let tsdown = options.bundler.as_deref() == Some("tsdown");
 
+    let sse_package = match &options.sse_runtime {
+        SseRuntime::Inline => None,
+        SseRuntime::Package { name, version } => Some(context! { name, version }),
+    };
+
     tmpl.render(context! {
         name => pkg_name,
+        version => options.version,
         repository => options.repository,
+        sse_package => sse_package,
         react => options.react,
         biome => biome,
         vitest => vitest,
@@ -121,6 +230,47 @@ fn emit_package_json(options: &ScaffoldOptions) -> String {
     .expect("render should succeed")
 }
 
+/// `DEPENDENCIES.md` for an existing repo: the packages and scripts the
+/// generated code needs, read off the `package.json` it would otherwise get
+/// (without the formatter and bundler, which only the scaffold itself uses),
+/// and the compiler options its imports rely on.
+fn emit_dependencies(options: &ScaffoldOptions) -> String {
+    let package_json = emit_package_json(&ScaffoldOptions {
+        formatter: None,
+        bundler: None,
+        ..options.clone()
+    });
+    let pkg: serde_json::Value =
+        serde_json::from_str(&package_json).expect("package.json should be valid JSON");
+    let entries = |key: &str| -> Vec<(String, String)> {
+        pkg[key]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, value)| (name.clone(), value.as_str().unwrap_or_default().to_string()))
+            .collect()
+    };
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.set_keep_trailing_newline(true);
+    env.add_template(
+        "DEPENDENCIES.md.j2",
+        include_str!("../../templates/DEPENDENCIES.md.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("DEPENDENCIES.md.j2").unwrap();
+
+    tmpl.render(context! {
+        dependencies => entries("dependencies"),
+        dev_dependencies => entries("devDependencies"),
+        scripts => entries("scripts"),
+        react => options.react,
+        source_dir => options.source_dir,
+    })
+    .expect("render should succeed")
+}
+
 fn emit_tsconfig(options: &ScaffoldOptions) -> String {
     let mut env = Environment::new();
     env.add_template(
@@ -133,6 +283,7 @@ fn emit_tsconfig(options: &ScaffoldOptions) -> String {
     tmpl.render(context! {
         react => options.react,
         source_dir => options.source_dir,
+        strictest => options.tsconfig_preset == TsconfigPreset::Strictest,
     })
     .expect("render should succeed")
 }
@@ -157,6 +308,22 @@ fn emit_tsdown(react: bool, source_dir: &str) -> String {
     .expect("render should succeed")
 }
 
+/// Derive a client class name from a spec title (e.g. "AI Chat API" → `AiChatApiClient`).
+pub fn default_client_class_name(title: &str) -> String {
+    if title.trim().is_empty() {
+        return "ApiClient".to_string();
+    }
+    let mut base = normalize_name(title).pascal_case;
+    if base.starts_with(|c: char| c.is_ascii_digit()) {
+        base.insert_str(0, "Api");
+    }
+    if base.ends_with("Client") {
+        base
+    } else {
+        format!("{base}Client")
+    }
+}
+
 /// Convert a title to a kebab-case package name.
 fn slugify(title: &str) -> String {
     let slug: String = title
@@ -199,6 +366,17 @@ mod tests {
         assert_eq!(slugify("Petstore - OpenAPI 3.2"), "petstore-openapi-3-2");
     }
 
+    #[test]
+    fn test_default_client_class_name() {
+        assert_eq!(default_client_class_name("AI Chat API"), "AiChatApiClient");
+        assert_eq!(default_client_class_name("Billing Client"), "BillingClient");
+        assert_eq!(
+            default_client_class_name("3D Printer API"),
+            "Api3dPrinterApiClient"
+        );
+        assert_eq!(default_client_class_name(""), "ApiClient");
+    }
+
     #[test]
     fn test_emit_scaffold_with_all_options() {
         let options = ScaffoldOptions {
@@ -210,10 +388,17 @@ mod tests {
             test_runner: Some("vitest".to_string()),
             react: true,
             existing_repo: false,
+            gitignore: true,
             source_dir: "src".to_string(),
+            client_class_name: "ApiClient".to_string(),
+            swr_prefetch: false,
+            tsconfig_preset: TsconfigPreset::Default,
+            sse_runtime: SseRuntime::Inline,
+            version: "0.1.0".to_string(),
         };
         let files = emit_scaffold(&options);
-        assert_eq!(files.len(), 4);
+        assert_eq!(files.len(), 5);
+        assert!(files.iter().any(|f| f.path == ".gitignore"));
         assert!(files.iter().any(|f| f.path == "package.json"));
         assert!(files.iter().any(|f| f.path == "tsconfig.json"));
         assert!(files.iter().any(|f| f.path == "biome.json"));
@@ -231,10 +416,81 @@ mod tests {
             test_runner: None,
             react: false,
             existing_repo: false,
+            gitignore: true,
             source_dir: "src".to_string(),
+            client_class_name: "ApiClient".to_string(),
+            swr_prefetch: false,
+            tsconfig_preset: TsconfigPreset::Default,
+            sse_runtime: SseRuntime::Inline,
+            version: "0.1.0".to_string(),
         };
         let files = emit_scaffold(&options);
-        assert_eq!(files.len(), 2); // Only package.json + tsconfig.json
+        assert_eq!(files.len(), 3); // Only .gitignore + package.json + tsconfig.json
+
+        // Existing repos keep their own .gitignore and project files.
+        let existing = ScaffoldOptions {
+            existing_repo: true,
+            gitignore: false,
+            ..options.clone()
+        };
+        let files = emit_scaffold(&existing);
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, ["index.ts", "DEPENDENCIES.md"]);
+
+        // ...unless a .gitignore is asked for explicitly.
+        let files = emit_scaffold(&ScaffoldOptions {
+            gitignore: true,
+            ..existing
+        });
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, [".gitignore", "index.ts", "DEPENDENCIES.md"]);
+    }
+
+    #[test]
+    fn test_existing_repo_lists_dependencies() {
+        let options = ScaffoldOptions {
+            name: "Test".to_string(),
+            package_name: None,
+            repository: None,
+            formatter: Some("biome".to_string()),
+            bundler: Some("tsdown".to_string()),
+            test_runner: None,
+            react: false,
+            existing_repo: true,
+            gitignore: false,
+            source_dir: "src".to_string(),
+            client_class_name: "ApiClient".to_string(),
+            swr_prefetch: false,
+            tsconfig_preset: TsconfigPreset::Default,
+            sse_runtime: SseRuntime::Inline,
+            version: "0.1.0".to_string(),
+        };
+        let deps = emit_dependencies(&options);
+        assert!(deps.contains(
+            "## dependencies\n\nNone: the generated client only uses `fetch`.\n\n## devDependencies\n\n- `typescript`: `^5.7.0`\n\n## scripts\n\n- `typecheck`: `tsc --noEmit`\n"
+        ));
+        assert!(deps.ends_with("and `include` has to cover the generated `src` directory.\n"));
+        // The formatter and bundler belong to the scaffold, not the host.
+        assert!(!deps.contains("biome"));
+        assert!(!deps.contains("tsdown"));
+        assert!(!deps.contains("jsx"));
+
+        let deps = emit_dependencies(&ScaffoldOptions {
+            react: true,
+            test_runner: Some("vitest".to_string()),
+            sse_runtime: SseRuntime::Package {
+                name: "@org/oag-sse".into(),
+                version: "^1.0.0".into(),
+            },
+            ..options
+        });
+        assert!(
+            deps.contains("- `@org/oag-sse`: `^1.0.0`\n- `react`: `^19.0.0`\n- `swr`: `^2.2.0`\n")
+        );
+        assert!(deps.contains("- `vitest`: `^3.0.0`\n"));
+        assert!(deps.contains("- `jsdom`: `^26.0.0`\n"));
+        assert!(deps.contains("- `test`: `vitest run`\n"));
+        assert!(deps.contains("- `\"jsx\": \"react-jsx\"`\n"));
     }
 
     #[test]
@@ -248,10 +504,130 @@ mod tests {
             test_runner: None,
             react: false,
             existing_repo: false,
+            gitignore: true,
             source_dir: "src".to_string(),
+            client_class_name: "ApiClient".to_string(),
+            swr_prefetch: false,
+            tsconfig_preset: TsconfigPreset::Default,
+            sse_runtime: SseRuntime::Inline,
+            version: "0.1.0".to_string(),
         };
         let files = emit_scaffold(&options);
         let pkg = files.iter().find(|f| f.path == "package.json").unwrap();
         assert!(pkg.content.contains("@myorg/api-client"));
     }
+
+    #[test]
+    fn test_sse_runtime_setting() {
+        let parse = |value: &str| {
+            serde_json::from_value::<NodeScaffoldConfig>(
+                serde_json::json!({ "sse_runtime": value }),
+            )
+            .map(|c| c.sse_runtime.unwrap())
+        };
+        assert_eq!(parse("inline").unwrap(), SseRuntime::Inline);
+        assert_eq!(
+            parse("package:@org/oag-sse").unwrap(),
+            SseRuntime::Package {
+                name: "@org/oag-sse".into(),
+                version: "*".into()
+            }
+        );
+        assert_eq!(
+            parse("package:@org/oag-sse@workspace:*").unwrap(),
+            SseRuntime::Package {
+                name: "@org/oag-sse".into(),
+                version: "workspace:*".into()
+            }
+        );
+        assert_eq!(
+            parse("package:oag-sse@^1.2.0").unwrap(),
+            SseRuntime::Package {
+                name: "oag-sse".into(),
+                version: "^1.2.0".into()
+            }
+        );
+        let err = parse("npm:oag-sse").unwrap_err().to_string();
+        assert!(
+            err.contains("expected `inline` or `package:<name>`"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_sse_package_is_a_dependency() {
+        let options = ScaffoldOptions {
+            name: "Test".to_string(),
+            package_name: None,
+            repository: None,
+            formatter: None,
+            bundler: None,
+            test_runner: None,
+            react: false,
+            existing_repo: false,
+            gitignore: true,
+            source_dir: "src".to_string(),
+            client_class_name: "ApiClient".to_string(),
+            swr_prefetch: false,
+            tsconfig_preset: TsconfigPreset::Default,
+            sse_runtime: SseRuntime::Package {
+                name: "@org/oag-sse".into(),
+                version: "workspace:*".into(),
+            },
+            version: "0.1.0".to_string(),
+        };
+        let pkg: serde_json::Value = serde_json::from_str(&emit_package_json(&options)).unwrap();
+        assert_eq!(
+            pkg["dependencies"],
+            serde_json::json!({ "@org/oag-sse": "workspace:*" })
+        );
+
+        let pkg: serde_json::Value = serde_json::from_str(&emit_package_json(&ScaffoldOptions {
+            react: true,
+            ..options
+        }))
+        .unwrap();
+        assert_eq!(pkg["dependencies"]["@org/oag-sse"], "workspace:*");
+        assert_eq!(pkg["dependencies"]["swr"], "^2.2.0");
+    }
+
+    #[test]
+    fn test_strictest_tsconfig_preset() {
+        let options = ScaffoldOptions {
+            name: "Test".to_string(),
+            package_name: None,
+            repository: None,
+            formatter: None,
+            bundler: None,
+            test_runner: Some("vitest".to_string()),
+            react: false,
+            existing_repo: false,
+            gitignore: true,
+            source_dir: "src".to_string(),
+            client_class_name: "ApiClient".to_string(),
+            swr_prefetch: false,
+            tsconfig_preset: TsconfigPreset::Default,
+            sse_runtime: SseRuntime::Inline,
+            version: "0.1.0".to_string(),
+        };
+        let default = emit_tsconfig(&options);
+        assert!(!default.contains("noUncheckedIndexedAccess"));
+        assert!(!default.contains("exclude"));
+
+        let strictest = emit_tsconfig(&ScaffoldOptions {
+            tsconfig_preset: TsconfigPreset::Strictest,
+            ..options
+        });
+        let tsconfig: serde_json::Value = serde_json::from_str(&strictest).unwrap();
+        let compiler = &tsconfig["compilerOptions"];
+        assert_eq!(compiler["strict"], true);
+        assert_eq!(compiler["noUncheckedIndexedAccess"], true);
+        assert_eq!(compiler["exactOptionalPropertyTypes"], true);
+        assert_eq!(compiler["noPropertyAccessFromIndexSignature"], true);
+        assert_eq!(tsconfig["include"], serde_json::json!(["src"]));
+        assert_eq!(
+            tsconfig["exclude"],
+            serde_json::json!(["src/**/*.test.ts", "src/**/*.test.tsx"])
+        );
+    }
 }
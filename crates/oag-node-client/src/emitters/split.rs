@@ -1,8 +1,14 @@
+use std::collections::BTreeSet;
+
 use oag_core::GeneratedFile;
-use oag_core::config::SplitBy;
-use oag_core::ir::{IrSpec, OperationGroup, group_operations};
+use oag_core::config::{ParamsStyle, PropertyNaming, SplitBy, SseTransport};
+use oag_core::ir::{
+    IrSpec, OperationGroup, group_operations, partition_schemas, schema_refs, type_refs,
+};
+use oag_core::transform::name_normalizer::{TS_RESERVED_WORDS, escape_reserved};
 
 use crate::emitters;
+use crate::emitters::index::BarrelModule;
 use crate::emitters::source_path;
 
 /// Emit files for split layout mode.
@@ -11,58 +17,254 @@ use crate::emitters::source_path;
 /// - `types.ts` — centralized types (same as modular)
 /// - `_client-base.ts` — the ApiClient class with the private `request` method
 /// - `{group}.ts` — per-group files with standalone functions
-/// - `sse.ts` — SSE runtime (same as modular)
-/// - `index.ts` — barrel re-export
+/// - `sse.ts` — SSE runtime (same as modular, and likewise left out with
+///   `minimal_runtime` when no operation streams)
+///
+/// With `split_types`, `types.ts` becomes a barrel over `types/{group}.ts` (the
+/// schemas only that group reaches) and `types/shared.ts` (everything else,
+/// `RetryConfig` included).
+#[allow(clippy::too_many_arguments)]
 pub fn emit_split(
     ir: &IrSpec,
     no_jsdoc: bool,
     split_by: SplitBy,
+    sse_transport: SseTransport,
     source_dir: &str,
+    default_base_url: Option<&str>,
+    split_types: bool,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    debug_logging: bool,
+    minimal_runtime: bool,
+    retry: bool,
 ) -> Vec<GeneratedFile> {
     let groups = group_operations(ir, split_by);
     let mut files = Vec::new();
 
     // Centralized types
-    files.push(GeneratedFile {
-        path: source_path(source_dir, "types.ts"),
-        content: emitters::types::emit_types(ir),
-    });
+    let retry_config = emitters::types::needs_retry_config(ir, retry, minimal_runtime);
+    if split_types {
+        files.extend(emit_split_types(
+            ir,
+            &groups,
+            source_dir,
+            params_style,
+            property_naming,
+            retry_config,
+        ));
+    } else {
+        files.push(GeneratedFile {
+            path: source_path(source_dir, "types.ts"),
+            content: emitters::types::emit_types(ir, params_style, property_naming, retry_config),
+        });
+    }
 
     // SSE runtime
-    files.push(GeneratedFile {
-        path: source_path(source_dir, "sse.ts"),
-        content: emitters::sse::emit_sse(),
-    });
+    if emitters::sse::needs_runtime(ir, minimal_runtime) {
+        files.push(GeneratedFile {
+            path: source_path(source_dir, "sse.ts"),
+            content: emitters::sse::emit_sse(sse_transport),
+        });
+    }
 
     // Client base — full client class
     files.push(GeneratedFile {
         path: source_path(source_dir, "client.ts"),
-        content: emitters::client::emit_client(ir, no_jsdoc),
+        content: emitters::client::emit_client(
+            ir,
+            no_jsdoc,
+            default_base_url,
+            params_style,
+            property_naming,
+            debug_logging,
+            minimal_runtime,
+            retry,
+        ),
     });
 
     // Per-group files — re-export from client for the group's operations
-    let mut group_names = Vec::new();
     for group in &groups {
-        let group_file_name = source_path(source_dir, &format!("{}.ts", group.name.snake_case));
-        let content = emit_group_file(ir, group);
-        group_names.push(group.name.snake_case.clone());
+        let module = group_module_name(group);
         files.push(GeneratedFile {
-            path: group_file_name,
-            content,
+            path: source_path(source_dir, &format!("{module}.ts")),
+            content: emit_group_file(ir, group, split_types),
         });
     }
 
-    // Index barrel
+    files
+}
+
+/// The per-group modules, re-exported whole from the barrel after the
+/// client's own.
+pub fn group_modules(ir: &IrSpec, split_by: SplitBy) -> Vec<BarrelModule> {
+    group_operations(ir, split_by)
+        .iter()
+        .map(|group| BarrelModule::all(group_module_name(group)))
+        .collect()
+}
+
+/// Modules split layout always writes (with the react generator's `hooks` and
+/// `provider` on top), which a group can't take over.
+const GENERATED_MODULES: &[&str] = &[
+    "client",
+    "codecs",
+    "hooks",
+    "index",
+    "provider",
+    "sse",
+    "streaming",
+    "types",
+];
+
+/// The file stem for a group's module: its snake_case name, with a trailing `_`
+/// if that's a TypeScript reserved word or one of the generated modules.
+pub fn group_module_name(group: &OperationGroup) -> String {
+    let name = escape_reserved(&group.name.snake_case, TS_RESERVED_WORDS);
+    escape_reserved(&name, GENERATED_MODULES)
+}
+
+/// Emit `types.ts` as a barrel over one type module per group plus a shared one.
+/// An operation's `{Op}Params` interface lives with its group, unless several
+/// groups contain the operation.
+fn emit_split_types(
+    ir: &IrSpec,
+    groups: &[OperationGroup],
+    source_dir: &str,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    retry_config: bool,
+) -> Vec<GeneratedFile> {
+    let partition = partition_schemas(ir, groups);
+    // A group can't take the shared module's name.
+    let shared = if groups.iter().any(|g| group_module_name(g) == "shared") {
+        "_shared"
+    } else {
+        "shared"
+    };
+
+    // An SSE event type lives with its operation's group, unless several
+    // groups contain the operation.
+    let group_events: Vec<Vec<_>> = groups
+        .iter()
+        .map(|g| {
+            emitters::types::sse_event_types(
+                ir,
+                g.operation_indices.iter().map(|&i| &ir.operations[i]),
+            )
+        })
+        .collect();
+    let event_groups = |name: &str| {
+        group_events
+            .iter()
+            .filter(|events| events.iter().any(|e| e.name == name))
+            .count()
+    };
+    let shared_events: Vec<_> = emitters::types::sse_event_types(ir, &ir.operations)
+        .into_iter()
+        .filter(|e| event_groups(e.name) > 1)
+        .collect();
+
+    let op_groups = |op: usize| {
+        groups
+            .iter()
+            .filter(|g| g.operation_indices.contains(&op))
+            .count()
+    };
+    let params_ops = |ops: &[usize], shared: bool| -> Vec<usize> {
+        if params_style != ParamsStyle::Object {
+            return Vec::new();
+        }
+        ops.iter()
+            .copied()
+            .filter(|&op| (op_groups(op) > 1) == shared)
+            .collect()
+    };
+    let all_ops: Vec<usize> = (0..ir.operations.len()).collect();
+
+    let mut files = vec![GeneratedFile {
+        path: source_path(source_dir, &format!("types/{shared}.ts")),
+        content: emitters::types::emit_type_module(
+            ir,
+            &partition.shared,
+            params_ops(&all_ops, true)
+                .iter()
+                .map(|&i| &ir.operations[i]),
+            &shared_events,
+            // Patch body types are shared by every group's operations.
+            &emitters::types::patch_formats(&ir.operations),
+            &[],
+            property_naming,
+            retry_config,
+        ),
+    }];
+    let mut barrel = vec![
+        "// Auto-generated by oag — do not edit".to_string(),
+        format!("export * from \"./types/{shared}\";"),
+    ];
+
+    for ((group, schemas), events) in groups.iter().zip(&partition.groups).zip(&group_events) {
+        let events: Vec<_> = events
+            .iter()
+            .filter(|e| event_groups(e.name) == 1)
+            .copied()
+            .collect();
+
+        // Everything a group's types reference is either its own or shared.
+        let mut refs = Vec::new();
+        for &i in schemas {
+            schema_refs(&ir.schemas[i], &mut refs);
+        }
+        for event in &events {
+            for variant in event.variants {
+                type_refs(variant, &mut refs);
+            }
+        }
+        let group_params = params_ops(&group.operation_indices, false);
+        for &op in &group_params {
+            for param in &ir.operations[op].parameters {
+                type_refs(&param.param_type, &mut refs);
+            }
+        }
+        let own: BTreeSet<&str> = schemas
+            .iter()
+            .map(|&i| ir.schemas[i].name().pascal_case.as_str())
+            .collect();
+        let imported: BTreeSet<&str> = refs.into_iter().filter(|r| !own.contains(r)).collect();
+        let imports = if imported.is_empty() {
+            vec![]
+        } else {
+            vec![(
+                format!("./{shared}"),
+                imported.into_iter().map(String::from).collect(),
+            )]
+        };
+
+        let name = group_module_name(group);
+        files.push(GeneratedFile {
+            path: source_path(source_dir, &format!("types/{name}.ts")),
+            content: emitters::types::emit_type_module(
+                ir,
+                schemas,
+                group_params.iter().map(|&i| &ir.operations[i]),
+                &events,
+                &[],
+                &imports,
+                property_naming,
+                false,
+            ),
+        });
+        barrel.push(format!("export * from \"./types/{name}\";"));
+    }
+
     files.push(GeneratedFile {
-        path: source_path(source_dir, "index.ts"),
-        content: emit_split_index(&group_names),
+        path: source_path(source_dir, "types.ts"),
+        content: barrel.join("\n") + "\n",
     });
-
     files
 }
 
 /// Emit a per-group file that re-exports the relevant operations from the client.
-fn emit_group_file(ir: &IrSpec, group: &OperationGroup) -> String {
+fn emit_group_file(ir: &IrSpec, group: &OperationGroup, split_types: bool) -> String {
     let mut lines = Vec::new();
     lines.push("// Auto-generated by oag — do not edit".to_string());
     lines.push(format!("// Operations group: {}", group.name.original));
@@ -84,24 +286,175 @@ fn emit_group_file(ir: &IrSpec, group: &OperationGroup) -> String {
     lines.push("// import { ApiClient } from \"./client\";".to_string());
     lines.push(String::new());
     lines.push("export { ApiClient } from \"./client\";".to_string());
-    lines.push("export * from \"./types\";".to_string());
+    if split_types {
+        lines.push(format!(
+            "export * from \"./types/{}\";",
+            group_module_name(group)
+        ));
+    } else {
+        lines.push("export * from \"./types\";".to_string());
+    }
 
     lines.join("\n") + "\n"
 }
 
-/// Emit the barrel index for split mode.
-fn emit_split_index(group_names: &[String]) -> String {
-    let mut lines = vec![
-        "// Auto-generated by oag — do not edit".to_string(),
-        "export * from \"./types\";".to_string(),
-        "export { ApiClient, type ClientConfig, type RequestOptions } from \"./client\";"
-            .to_string(),
-        "export { streamSse, SSEError, type SSEOptions } from \"./sse\";".to_string(),
-    ];
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
 
-    for name in group_names {
-        lines.push(format!("export * from \"./{name}\";"));
+    fn anthropic_split_types() -> Vec<GeneratedFile> {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        emit_split(
+            &ir,
+            false,
+            SplitBy::Tag,
+            SseTransport::Fetch,
+            "src",
+            None,
+            true,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        )
     }
 
-    lines.join("\n") + "\n"
+    #[test]
+    fn test_split_types_defines_each_type_once() {
+        let files = anthropic_split_types();
+        let type_files: Vec<&GeneratedFile> = files
+            .iter()
+            .filter(|f| f.path.starts_with("src/types/"))
+            .collect();
+        let paths: Vec<&str> = type_files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "src/types/shared.ts",
+                "src/types/batches.ts",
+                "src/types/messages.ts",
+                "src/types/models.ts",
+                "src/types/tokens.ts",
+            ]
+        );
+
+        let mut defined: HashMap<String, &str> = HashMap::new();
+        for file in &type_files {
+            for line in file.content.lines() {
+                let Some(rest) = line
+                    .strip_prefix("export interface ")
+                    .or_else(|| line.strip_prefix("export type "))
+                else {
+                    continue;
+                };
+                let name: String = rest
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || *c == '_')
+                    .collect();
+                if let Some(first) = defined.insert(name.clone(), &file.path) {
+                    panic!("{name} defined in both {first} and {}", file.path);
+                }
+            }
+        }
+        for file in &type_files {
+            for line in file
+                .content
+                .lines()
+                .filter(|l| l.starts_with("import type"))
+            {
+                assert!(line.ends_with("from \"./shared\";"), "{line}");
+                let names = line
+                    .trim_start_matches("import type { ")
+                    .split(" } from")
+                    .next()
+                    .unwrap();
+                for name in names.split(", ") {
+                    assert_eq!(defined.get(name), Some(&"src/types/shared.ts"), "{name}");
+                }
+            }
+        }
+
+        let barrel = files.iter().find(|f| f.path == "src/types.ts").unwrap();
+        assert!(barrel.content.contains("export * from \"./types/shared\";"));
+        assert!(barrel.content.contains("export * from \"./types/models\";"));
+        let models = files.iter().find(|f| f.path == "src/models.ts").unwrap();
+        assert!(models.content.contains("export * from \"./types/models\";"));
+    }
+
+    #[test]
+    fn test_group_files_avoid_reserved_and_generated_names() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/untagged-operations.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let files = emit_split(
+            &ir,
+            false,
+            SplitBy::Tag,
+            SseTransport::Fetch,
+            "src",
+            None,
+            false,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        let paths: Vec<&str> = files.iter().map(|f| f.path.as_str()).collect();
+        // `types` would overwrite the types barrel; `default` is reserved.
+        for path in ["src/default_.ts", "src/default_2.ts", "src/types_.ts"] {
+            assert!(paths.contains(&path), "missing {path} in {paths:?}");
+        }
+        assert_eq!(paths.iter().filter(|&&p| p == "src/types.ts").count(), 1);
+
+        let modules = group_modules(&ir, SplitBy::Tag);
+        let index = crate::emitters::index::emit_index(&files, "src", &modules);
+        assert!(index.contains("export * from \"./default_\";"));
+        assert!(index.contains("export * from \"./types_\";"));
+    }
+
+    #[test]
+    fn test_custom_modules_become_group_files() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let options = oag_core::transform::TransformOptions::new()
+            .with_module("admin", ["deletePet", "/store/*"]);
+        let ir = oag_core::transform::transform_with_options(&spec, &options).unwrap();
+        let files = emit_split(
+            &ir,
+            false,
+            SplitBy::Tag,
+            SseTransport::Fetch,
+            "src",
+            None,
+            false,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+            false,
+            false,
+        );
+        // `deletePet` (tagged `pets`) and `getInventory` (tagged `store`)
+        // share one file, and the store module is left empty.
+        let admin = files.iter().find(|f| f.path == "src/admin.ts").unwrap();
+        assert!(
+            admin
+                .content
+                .contains("//   - deletePet\n//   - getInventory\n")
+        );
+        let pets = files.iter().find(|f| f.path == "src/pets.ts").unwrap();
+        assert!(!pets.content.contains("deletePet"));
+        assert!(!files.iter().any(|f| f.path == "src/store.ts"));
+    }
 }
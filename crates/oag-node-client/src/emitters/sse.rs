@@ -1,4 +1,165 @@
+use minijinja::{Environment, context};
+use oag_core::GeneratedFile;
+use oag_core::config::SseTransport;
+use oag_core::ir::{IrReturnType, IrSpec};
+
+use super::source_path;
+
+/// What `sse.ts` offers the package root.
+pub const EXPORTS: &[&str] = &[
+    "type EventSourceFactory",
+    "type EventSourceLike",
+    "type SSEOptions",
+    "type SSETransport",
+    "SSEError",
+    "SSETimeoutError",
+    "streamSse",
+];
+
+/// Whether the client gets the SSE runtime: always, unless `minimal_runtime`
+/// is set and no operation streams.
+pub fn needs_runtime(ir: &IrSpec, minimal_runtime: bool) -> bool {
+    !minimal_runtime
+        || ir
+            .operations
+            .iter()
+            .any(|op| matches!(op.return_type, IrReturnType::Sse(_)))
+}
+
 /// Emit `sse.ts` — the inlined SSE runtime (no external dependencies).
-pub fn emit_sse() -> String {
-    include_str!("../../templates/sse.ts.j2").to_string()
+///
+/// `transport` is the default used when neither the client nor the call picks one.
+pub fn emit_sse(transport: SseTransport) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template("sse.ts.j2", include_str!("../../templates/sse.ts.j2"))
+        .expect("template should be valid");
+    let tmpl = env.get_template("sse.ts.j2").unwrap();
+    tmpl.render(context! {
+        default_transport => transport.as_str(),
+    })
+    .expect("render should succeed")
+}
+
+/// Emit `sse-runtime.d.ts` — the runtime API generated clients expect from the
+/// `package` set with `sse_runtime`, for the package to conform to.
+pub fn emit_sse_reference(package: &str) -> String {
+    let mut env = Environment::new();
+    env.add_template(
+        "sse-runtime.d.ts.j2",
+        include_str!("../../templates/sse-runtime.d.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("sse-runtime.d.ts.j2").unwrap();
+    tmpl.render(context! { package => package })
+        .expect("render should succeed")
+}
+
+/// Point `content`'s imports and re-exports of the generated `sse` module
+/// (`"./sse"`, `"../sse"`) at `package` instead.
+pub fn import_sse_from(content: &str, package: &str) -> String {
+    ["\"./sse\";", "\"../sse\";"]
+        .iter()
+        .fold(content.to_string(), |content, module| {
+            content.replace(&format!("from {module}"), &format!("from \"{package}\";"))
+        })
+}
+
+/// Switch generated `files` to the SSE runtime in `package`: drop `sse.ts`,
+/// import from the package instead, and add `sse-runtime.d.ts` describing
+/// what the package must export.
+pub fn use_sse_package(files: &mut Vec<GeneratedFile>, source_dir: &str, package: &str) {
+    let runtime_path = source_path(source_dir, "sse.ts");
+    files.retain(|f| f.path != runtime_path);
+    for file in files
+        .iter_mut()
+        .filter(|f| f.path.ends_with(".ts") || f.path.ends_with(".tsx"))
+    {
+        file.content = import_sse_from(&file.content, package);
+    }
+    files.push(GeneratedFile {
+        path: "sse-runtime.d.ts".to_string(),
+        content: emit_sse_reference(package),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_transport_is_baked_in() {
+        let fetch = emit_sse(SseTransport::Fetch);
+        assert!(fetch.contains("const DEFAULT_SSE_TRANSPORT: SSETransport = \"fetch\";"));
+
+        let eventsource = emit_sse(SseTransport::EventSource);
+        assert!(
+            eventsource.contains("const DEFAULT_SSE_TRANSPORT: SSETransport = \"eventsource\";")
+        );
+        assert!(eventsource.contains("async function* streamViaEventSource<T>("));
+    }
+
+    #[test]
+    fn test_streams_have_idle_and_duration_timeouts() {
+        let content = emit_sse(SseTransport::Fetch);
+        assert!(content.contains("export class SSETimeoutError extends SSEError {"));
+        assert!(content.contains("  idleTimeoutMs?: number | undefined;"));
+        assert!(content.contains("  maxDurationMs?: number | undefined;"));
+    }
+
+    #[test]
+    fn test_reference_declares_every_runtime_export() {
+        // The names after `export`'s keywords, e.g. `streamSse` in
+        // `export async function* streamSse<T>(`.
+        let exports = |content: &str| -> Vec<String> {
+            content
+                .lines()
+                .filter_map(|line| line.strip_prefix("export "))
+                .filter_map(|rest| {
+                    rest.split([' ', '<', '('])
+                        .find(|w| {
+                            !matches!(
+                                *w,
+                                "" | "declare"
+                                    | "async"
+                                    | "function"
+                                    | "function*"
+                                    | "class"
+                                    | "type"
+                                    | "interface"
+                            )
+                        })
+                        .map(String::from)
+                })
+                .collect()
+        };
+        let runtime = exports(&emit_sse(SseTransport::Fetch));
+        let reference = emit_sse_reference("@org/oag-sse");
+        assert_eq!(
+            runtime,
+            [
+                "SSEError",
+                "SSETimeoutError",
+                "SSETransport",
+                "EventSourceLike",
+                "EventSourceFactory",
+                "SSEOptions",
+                "streamSse"
+            ]
+        );
+        // Plus `RetryConfig`, which the inline runtime imports from the types.
+        let mut expected = vec!["RetryConfig".to_string()];
+        expected.extend(runtime);
+        assert_eq!(exports(&reference), expected);
+        assert!(reference.contains("clients import from \"@org/oag-sse\""));
+    }
+
+    #[test]
+    fn test_imports_point_at_the_package() {
+        let content = "import { streamSse } from \"./sse\";\nimport type { SSEOptions } from \"../sse\";\nimport type { Pet } from \"./types\";\n";
+        assert_eq!(
+            import_sse_from(content, "@org/oag-sse"),
+            "import { streamSse } from \"@org/oag-sse\";\nimport type { SSEOptions } from \"@org/oag-sse\";\nimport type { Pet } from \"./types\";\n"
+        );
+    }
 }
@@ -0,0 +1,560 @@
+use std::collections::HashSet;
+
+use minijinja::{Environment, context};
+use oag_core::GeneratorError;
+use oag_core::config::{ParamsStyle, PropertyNaming, StreamAccumulator};
+use oag_core::ir::{IrReturnType, IrSpec, IrType};
+
+use crate::emitters::client::{collect_types_from_ir_type, method_arguments};
+use crate::emitters::codecs::Codecs;
+use crate::emitters::types::{event_discriminator, member_access};
+use crate::type_mapper::ir_type_to_ts;
+
+/// One SSE operation whose event union follows the configured delta pattern.
+struct Accumulator {
+    /// The operation's PascalCase name, e.g. `CreateMessage`.
+    name: String,
+    /// The client method returning the stream, e.g. `createMessageStream`.
+    method: String,
+    event_type: String,
+    done_type: String,
+    /// The tag's property, as the typed events name it.
+    tag_property: String,
+    /// The typed property names along the configured `delta_field`.
+    delta_path: Vec<String>,
+    delta_tag: String,
+    done_tag: String,
+}
+
+/// Emit `streaming.ts`: `collectEvents`, plus an `accumulate{Op}Stream` helper
+/// for each SSE operation whose event union has both of the `accumulator`'s
+/// tagged events. `None` when the spec has no SSE operations. Events are read
+/// by their property names under `property_naming`.
+///
+/// Fails when `accumulator` is set but no event union matches it.
+pub fn emit_streaming(
+    ir: &IrSpec,
+    accumulator: Option<&StreamAccumulator>,
+    types_module: &str,
+    property_naming: PropertyNaming,
+) -> Result<Option<String>, GeneratorError> {
+    if !has_sse(ir) {
+        return Ok(None);
+    }
+    let accumulators = accumulators(ir, accumulator, property_naming)?;
+
+    let mut type_imports: Vec<&str> = accumulators
+        .iter()
+        .flat_map(|a| [a.event_type.as_str(), a.done_type.as_str()])
+        .collect();
+    type_imports.sort();
+    type_imports.dedup();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "streaming.ts.j2",
+        include_str!("../../templates/streaming.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("streaming.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            types_module => types_module,
+            type_imports => type_imports,
+            accumulators => accumulator
+                .map(|config| accumulator_contexts(config, &accumulators))
+                .unwrap_or_default(),
+        })
+        .map_err(|e| GeneratorError::Render(e.to_string()))?;
+    Ok(Some(content))
+}
+
+/// Emit `streaming.test.ts`, feeding each helper a synthetic stream. `None`
+/// when there is no `streaming.ts`.
+pub fn emit_streaming_tests(
+    ir: &IrSpec,
+    accumulator: Option<&StreamAccumulator>,
+    property_naming: PropertyNaming,
+) -> Result<Option<String>, GeneratorError> {
+    if !has_sse(ir) {
+        return Ok(None);
+    }
+    let accumulators = accumulators(ir, accumulator, property_naming)?;
+
+    let cases: Vec<minijinja::Value> = match accumulator {
+        Some(_) => accumulators
+            .iter()
+            .map(|a| {
+                let delta = |text: &str| {
+                    let mut value = serde_json::Value::String(text.to_string());
+                    for key in a.delta_path.iter().rev() {
+                        value = serde_json::json!({ key: value });
+                    }
+                    value[&a.tag_property] = a.delta_tag.clone().into();
+                    value.to_string()
+                };
+                let done = serde_json::json!({ &a.tag_property: a.done_tag }).to_string();
+                context! {
+                    name => a.name,
+                    event_type => a.event_type,
+                    events => vec![delta("Hello, "), delta("world"), done, delta("ignored")],
+                }
+            })
+            .collect(),
+        None => Vec::new(),
+    };
+    let mut type_imports: Vec<&str> = accumulators.iter().map(|a| a.event_type.as_str()).collect();
+    type_imports.sort();
+    type_imports.dedup();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "streaming.test.ts.j2",
+        include_str!("../../templates/streaming.test.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("streaming.test.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            type_imports => type_imports,
+            accumulators => cases,
+        })
+        .map_err(|e| GeneratorError::Render(e.to_string()))?;
+    Ok(Some(content))
+}
+
+/// Where a helper module (`streaming-helpers.ts`, `batch.ts`) imports the
+/// client, SSE runtime and types from.
+pub struct HelperModules<'a> {
+    pub client: &'a str,
+    pub sse: &'a str,
+    pub types: &'a str,
+}
+
+/// One SSE client method, as its `{method}ToWritable` helper calls it.
+struct StreamMethod {
+    method: String,
+    event_type: String,
+    /// The method's parameters before `options`, optional ones as `T | undefined`
+    /// so they can precede the helper's `writable`.
+    parts: Vec<String>,
+    /// The argument names, in order.
+    args: Vec<String>,
+    /// Types from the types module the event type uses.
+    event_types: Vec<String>,
+    /// Types from the types module the helper's signature uses.
+    types: Vec<String>,
+}
+
+/// Emit `streaming-helpers.ts`: `pumpToWritable`, which pipes any event stream
+/// into a `WritableStream` with backpressure, and a `{method}ToWritable` helper
+/// for each SSE client method. `None` when the spec has no SSE operations.
+pub fn emit_streaming_helpers(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    modules: &HelperModules,
+) -> Result<Option<String>, GeneratorError> {
+    if !has_sse(ir) {
+        return Ok(None);
+    }
+    let methods = stream_methods(ir, params_style);
+
+    let mut imports = Vec::new();
+    if !methods.is_empty() {
+        add_imports(&mut imports, modules.client, ["ApiClient".to_string()]);
+        add_imports(&mut imports, modules.sse, ["SSEOptions".to_string()]);
+        add_imports(
+            &mut imports,
+            modules.types,
+            methods.iter().flat_map(|m| m.types.iter().cloned()),
+        );
+    }
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "streaming-helpers.ts.j2",
+        include_str!("../../templates/streaming-helpers.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("streaming-helpers.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            imports => import_contexts(imports),
+            helpers => methods
+                .iter()
+                .map(|m| context! {
+                    method => m.method,
+                    event_type => m.event_type,
+                    parts => m.parts,
+                    args => m.args.iter().map(String::as_str).chain(["options"]).collect::<Vec<_>>().join(", "),
+                })
+                .collect::<Vec<_>>(),
+        })
+        .map_err(|e| GeneratorError::Render(e.to_string()))?;
+    Ok(Some(content))
+}
+
+/// Emit `streaming-helpers.test.ts`: `pumpToWritable` against an array-backed
+/// writable, and each `{method}ToWritable` against a stubbed client. `None`
+/// when there is no `streaming-helpers.ts`.
+pub fn emit_streaming_helper_tests(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    modules: &HelperModules,
+) -> Result<Option<String>, GeneratorError> {
+    if !has_sse(ir) {
+        return Ok(None);
+    }
+    let methods = stream_methods(ir, params_style);
+
+    let mut imports = Vec::new();
+    if !methods.is_empty() {
+        add_imports(&mut imports, modules.client, ["ApiClient".to_string()]);
+        add_imports(
+            &mut imports,
+            modules.types,
+            methods.iter().flat_map(|m| m.event_types.iter().cloned()),
+        );
+    }
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "streaming-helpers.test.ts.j2",
+        include_str!("../../templates/streaming-helpers.test.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("streaming-helpers.test.ts.j2").unwrap();
+    let content = tmpl
+        .render(context! {
+            imports => import_contexts(imports),
+            helpers => methods
+                .iter()
+                .map(|m| context! {
+                    method => m.method,
+                    event_type => m.event_type,
+                    placeholders => "undefined as never, ".repeat(m.args.len()),
+                })
+                .collect::<Vec<_>>(),
+        })
+        .map_err(|e| GeneratorError::Render(e.to_string()))?;
+    Ok(Some(content))
+}
+
+/// The SSE methods of the client, once per method name.
+fn stream_methods(ir: &IrSpec, params_style: ParamsStyle) -> Vec<StreamMethod> {
+    let mut seen = HashSet::new();
+    ir.operations
+        .iter()
+        .filter_map(|op| {
+            let IrReturnType::Sse(sse) = &op.return_type else {
+                return None;
+            };
+            let method = sse.stream_name.camel_case.clone();
+            if !seen.insert(method.clone()) {
+                return None;
+            }
+            let mut event_types = HashSet::new();
+            let event_type = match &sse.event_type_name {
+                Some(name) => {
+                    event_types.insert(name.clone());
+                    name.clone()
+                }
+                None => {
+                    collect_types_from_ir_type(&sse.event_type, &mut event_types);
+                    ir_type_to_ts(&sse.event_type)
+                }
+            };
+            let arguments = method_arguments(op, params_style);
+            let mut types: Vec<String> = event_types.iter().cloned().collect();
+            types.extend(arguments.types);
+            let event_types = event_types.into_iter().collect();
+            let (parts, args) = arguments
+                .parts
+                .iter()
+                .map(|part| match part.split_once("?: ") {
+                    Some((name, ty)) => (format!("{name}: {ty} | undefined"), name.to_string()),
+                    None => {
+                        let name = part.split_once(": ").map_or(part.as_str(), |(n, _)| n);
+                        (part.clone(), name.to_string())
+                    }
+                })
+                .unzip();
+            Some(StreamMethod {
+                method,
+                event_type,
+                parts,
+                args,
+                event_types,
+                types,
+            })
+        })
+        .collect()
+}
+
+/// Add `names` to the imports from `module`; in bundled layout every module
+/// is `./index`, so they share one import.
+pub(crate) fn add_imports<'a>(
+    imports: &mut Vec<(&'a str, Vec<String>)>,
+    module: &'a str,
+    names: impl IntoIterator<Item = String>,
+) {
+    match imports.iter_mut().find(|(m, _)| *m == module) {
+        Some((_, existing)) => existing.extend(names),
+        None => imports.push((module, names.into_iter().collect())),
+    }
+}
+
+/// Import contexts for the templates, names sorted and deduplicated, modules
+/// with nothing to import dropped.
+pub(crate) fn import_contexts(imports: Vec<(&str, Vec<String>)>) -> Vec<minijinja::Value> {
+    imports
+        .into_iter()
+        .filter(|(_, names)| !names.is_empty())
+        .map(|(module, mut names)| {
+            names.sort();
+            names.dedup();
+            context! { module => module, names => names }
+        })
+        .collect()
+}
+
+fn has_sse(ir: &IrSpec) -> bool {
+    ir.operations
+        .iter()
+        .any(|op| matches!(op.return_type, IrReturnType::Sse(_)))
+}
+
+/// The SSE operations whose event union has both tagged events of `config`.
+fn accumulators(
+    ir: &IrSpec,
+    config: Option<&StreamAccumulator>,
+    property_naming: PropertyNaming,
+) -> Result<Vec<Accumulator>, GeneratorError> {
+    let Some(config) = config else {
+        return Ok(Vec::new());
+    };
+    let codecs = Codecs::new(ir, property_naming);
+    let delta_field: Vec<&str> = config.delta_field.split('.').collect();
+    let accumulators: Vec<Accumulator> = ir
+        .operations
+        .iter()
+        .filter_map(|op| {
+            let IrReturnType::Sse(sse) = &op.return_type else {
+                return None;
+            };
+            let (tag_property, tags) = event_discriminator(ir, &sse.variants)?;
+            let variant = |tag: &str| -> Option<&IrType> {
+                tags.iter().find(|(t, _)| *t == tag).map(|(_, v)| *v)
+            };
+            let delta = variant(&config.delta_event)?;
+            let done = variant(&config.done_event)?;
+            Some(Accumulator {
+                name: op.name.pascal_case.clone(),
+                method: sse.stream_name.camel_case.clone(),
+                event_type: sse
+                    .event_type_name
+                    .clone()
+                    .unwrap_or_else(|| ir_type_to_ts(&sse.event_type)),
+                done_type: ir_type_to_ts(done),
+                tag_property: codecs.typed_property(delta, tag_property),
+                delta_path: codecs.typed_path(delta, &delta_field),
+                delta_tag: config.delta_event.clone(),
+                done_tag: config.done_event.clone(),
+            })
+        })
+        .collect();
+
+    if accumulators.is_empty() {
+        return Err(GeneratorError::Other(format!(
+            "stream_accumulator: no SSE event union has both `{}` and `{}` events",
+            config.delta_event, config.done_event
+        )));
+    }
+    Ok(accumulators)
+}
+
+fn accumulator_contexts(
+    config: &StreamAccumulator,
+    accumulators: &[Accumulator],
+) -> Vec<minijinja::Value> {
+    let json = |s: &str| serde_json::to_string(s).expect("strings always serialize");
+    accumulators
+        .iter()
+        .map(|a| {
+            let delta_path: Vec<String> = a.delta_path.iter().map(|key| json(key)).collect();
+            context! {
+                name => a.name,
+                method => a.method,
+                event_type => a.event_type,
+                done_type => a.done_type,
+                tag_property => a.tag_property,
+                tag_access => member_access(&a.tag_property),
+                delta_event => a.delta_tag,
+                done_event => a.done_tag,
+                delta_tag => json(&a.delta_tag),
+                done_tag => json(&a.done_tag),
+                delta_field => config.delta_field,
+                delta_path => delta_path.join(", "),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn anthropic() -> IrSpec {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    fn text_deltas() -> StreamAccumulator {
+        StreamAccumulator {
+            delta_event: "content_block_delta".to_string(),
+            delta_field: "delta.text".to_string(),
+            done_event: "message_stop".to_string(),
+        }
+    }
+
+    #[test]
+    fn unconfigured_streaming_only_collects() {
+        let content = emit_streaming(&anthropic(), None, "./types", PropertyNaming::Original)
+            .unwrap()
+            .unwrap();
+        assert!(content.contains("export async function collectEvents<T>("));
+        assert!(!content.contains("accumulate"));
+        assert!(!content.contains("import type"));
+    }
+
+    #[test]
+    fn configured_streaming_accumulates_deltas() {
+        let config = text_deltas();
+        let content = emit_streaming(
+            &anthropic(),
+            Some(&config),
+            "./types",
+            PropertyNaming::Original,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(content.contains(
+            "import type { CreateMessageStreamEvent, MessageStopEvent } from \"./types\";"
+        ));
+        assert!(content.contains("export async function accumulateCreateMessageStream("));
+        assert!(content.contains("readPath(event, [\"delta\", \"text\"])"));
+        assert!(content.contains("  done?: MessageStopEvent;\n"));
+
+        let tests = emit_streaming_tests(&anthropic(), Some(&config), PropertyNaming::Original)
+            .unwrap()
+            .unwrap();
+        assert!(
+            tests.contains("{\"delta\":{\"text\":\"Hello, \"},\"type\":\"content_block_delta\"}")
+        );
+        assert!(tests.contains("expect(result.text).toBe(\"Hello, world\");"));
+    }
+
+    #[test]
+    fn unmatched_accumulator_is_an_error() {
+        let config = StreamAccumulator {
+            done_event: "message_end".to_string(),
+            ..text_deltas()
+        };
+        let err = emit_streaming(
+            &anthropic(),
+            Some(&config),
+            "./types",
+            PropertyNaming::Original,
+        )
+        .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("`content_block_delta` and `message_end`")
+        );
+    }
+
+    #[test]
+    fn streaming_helpers_forward_the_method_arguments() {
+        let modules = HelperModules {
+            client: "./client",
+            sse: "./sse",
+            types: "./types",
+        };
+        let content = emit_streaming_helpers(&anthropic(), ParamsStyle::Positional, &modules)
+            .unwrap()
+            .unwrap();
+        assert!(content.contains("import type { ApiClient } from \"./client\";"));
+        assert!(content.contains("import type { SSEOptions } from \"./sse\";"));
+        assert!(content.contains(
+            "import type { CreateMessageRequest, CreateMessageStreamEvent } from \"./types\";"
+        ));
+        assert!(content.contains("export async function pumpToWritable<T>("));
+        assert!(content.contains(
+            "export function createMessageStreamToWritable(\n  client: ApiClient,\n  anthropicVersion: string,\n  body: CreateMessageRequest,\n  writable: WritableStream<CreateMessageStreamEvent>,\n  options?: SSEOptions & PumpOptions,\n): Promise<void> {"
+        ));
+        assert!(content.contains(
+            "return pumpToWritable(client.createMessageStream(anthropicVersion, body, options), writable, options);"
+        ));
+
+        let content = emit_streaming_helpers(&anthropic(), ParamsStyle::Object, &modules)
+            .unwrap()
+            .unwrap();
+        assert!(content.contains("  params: CreateMessageParams,\n"));
+        assert!(content.contains("client.createMessageStream(params, body, options)"));
+
+        let tests = emit_streaming_helper_tests(&anthropic(), ParamsStyle::Positional, &modules)
+            .unwrap()
+            .unwrap();
+        assert!(tests.contains("import type { CreateMessageStreamEvent } from \"./types\";"));
+        assert!(tests.contains(
+            "await createMessageStreamToWritable(client, undefined as never, undefined as never, writable, options);"
+        ));
+    }
+
+    #[test]
+    fn bundled_streaming_helpers_import_from_index_once() {
+        let modules = HelperModules {
+            client: "./index",
+            sse: "./index",
+            types: "./index",
+        };
+        let content = emit_streaming_helpers(&anthropic(), ParamsStyle::Positional, &modules)
+            .unwrap()
+            .unwrap();
+        assert!(content.contains(
+            "import type { ApiClient, CreateMessageRequest, CreateMessageStreamEvent, SSEOptions } from \"./index\";"
+        ));
+        assert_eq!(content.matches("import type").count(), 1);
+    }
+
+    #[test]
+    fn specs_without_sse_have_no_streaming_module() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert!(
+            emit_streaming(&ir, None, "./types", PropertyNaming::Original)
+                .unwrap()
+                .is_none()
+        );
+        let modules = HelperModules {
+            client: "./client",
+            sse: "./sse",
+            types: "./types",
+        };
+        assert!(
+            emit_streaming_helpers(&ir, ParamsStyle::Positional, &modules)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
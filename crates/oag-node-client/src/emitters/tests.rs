@@ -1,10 +1,29 @@
 use minijinja::{Environment, context};
-use oag_core::ir::{IrOperation, IrParameterLocation, IrReturnType, IrSpec, IrType};
+use oag_core::config::{ParamsStyle, PropertyNaming};
+use oag_core::contract::{sample_response_value, sample_value};
+use oag_core::ir::{
+    IrOperation, IrParameter, IrParameterLocation, IrRequestBody, IrReturnType, IrSchema, IrSpec,
+    IrType, PatchFormat, QueryObjectNotation,
+};
+use oag_core::test_report::TestCoverage;
 
-use crate::type_mapper::ir_type_to_ts;
+use crate::emitters::client::{
+    is_upload_op, operation_meta, params_object_members, params_object_required,
+};
+use crate::emitters::codecs::Codecs;
+use crate::emitters::contract::ts_literal;
+use crate::type_mapper::ts_property_key;
 
-/// Emit `client.test.ts` — vitest tests for the API client.
-pub fn emit_client_tests(ir: &IrSpec) -> String {
+/// Emit `client.test.ts` — vitest tests for the API client. Bodies and
+/// parameters are passed in their typed form under `property_naming`, and
+/// checked as they're sent on the wire. The retry tests need `retry`.
+pub fn emit_client_tests(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    retry: bool,
+) -> String {
+    let codecs = Codecs::new(ir, property_naming);
     let mut env = Environment::new();
     env.set_trim_blocks(true);
     env.add_template(
@@ -14,127 +33,145 @@ pub fn emit_client_tests(ir: &IrSpec) -> String {
     .expect("template should be valid");
     let tmpl = env.get_template("client.test.ts.j2").unwrap();
 
-    let mut seen_methods = std::collections::HashSet::new();
-    let mut used_op_indices = std::collections::HashSet::new();
-    let operations: Vec<minijinja::Value> = ir
-        .operations
-        .iter()
-        .enumerate()
-        .flat_map(|(idx, op)| {
-            build_test_operation_contexts(op)
-                .into_iter()
-                .map(move |ctx| (idx, ctx))
-        })
-        .filter(|(idx, op)| {
-            let name = op
-                .get_attr("method_name")
-                .ok()
-                .and_then(|v| v.as_str().map(String::from));
-            match name {
-                Some(n) => {
-                    if seen_methods.insert(n) {
-                        used_op_indices.insert(*idx);
-                        true
-                    } else {
-                        false
-                    }
-                }
-                None => true,
-            }
-        })
+    let operations: Vec<minijinja::Value> = test_operations(ir, &codecs, params_style)
+        .into_iter()
         .map(|(_, ctx)| ctx)
         .collect();
 
-    // Collect type names only from operations that survived dedup
-    let type_imports: Vec<String> = collect_type_imports(
-        ir.operations
-            .iter()
-            .enumerate()
-            .filter(|(i, _)| used_op_indices.contains(i))
-            .map(|(_, op)| op),
-    );
+    let has_sse = operations.iter().any(|op| {
+        op.get_attr("kind")
+            .ok()
+            .is_some_and(|v| v.as_str() == Some("sse"))
+    });
+
+    let has_cookie_auth = operations
+        .iter()
+        .any(|op| op.get_attr("cookie").is_ok_and(|v| !v.is_none()));
+    let has_uploads = operations
+        .iter()
+        .any(|op| op.get_attr("upload_call_args").is_ok_and(|v| !v.is_none()));
 
     tmpl.render(context! {
         operations => operations,
-        type_imports => type_imports,
+        has_sse => has_sse,
+        has_cookie_auth => has_cookie_auth,
+        has_uploads => has_uploads,
+        retry => retry,
     })
     .expect("render should succeed")
 }
 
-/// Collect unique type names used in mock values across surviving operations.
-fn collect_type_imports<'a>(ops: impl Iterator<Item = &'a IrOperation>) -> Vec<String> {
-    let mut names = std::collections::BTreeSet::new();
-
-    for op in ops {
-        // Request body refs
-        if let Some(ref body) = op.request_body {
-            collect_ref_names(&body.body_type, &mut names);
+/// The `client.test.ts` suites calling each operation (`test_report: true`),
+/// built from the same contexts as [`emit_client_tests`].
+pub fn client_test_coverage(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+) -> TestCoverage {
+    let codecs = Codecs::new(ir, property_naming);
+    let mut coverage = TestCoverage::new(ir);
+    for (op, ctx) in test_operations(ir, &codecs, params_style) {
+        let attr = |name| {
+            ctx.get_attr(name)
+                .ok()
+                .and_then(|v| v.as_str().map(String::from))
+        };
+        let Some(method_name) = attr("method_name") else {
+            continue;
+        };
+        let mut suites = vec![method_name.clone()];
+        if attr("kind").as_deref() != Some("sse") {
+            suites.push(format!("{method_name}Raw"));
         }
-        // Return type refs (used in mock_response via guess_mock_type)
-        match &op.return_type {
-            IrReturnType::Standard(resp) => {
-                collect_ref_names(&resp.response_type, &mut names);
-            }
-            IrReturnType::Sse(sse) => {
-                collect_ref_names(&sse.event_type, &mut names);
-                if let Some(ref json_resp) = sse.json_response {
-                    collect_ref_names(&json_resp.response_type, &mut names);
-                }
-            }
-            IrReturnType::Void => {}
+        for suite in suites {
+            coverage.add(op, format!("client.test.ts > ApiClient > {suite}"));
         }
     }
-
-    names.into_iter().collect()
+    coverage
 }
 
-fn collect_ref_names(ir_type: &IrType, names: &mut std::collections::BTreeSet<String>) {
-    match ir_type {
-        IrType::Ref(name) => {
-            names.insert(name.clone());
-        }
-        IrType::Array(inner) => collect_ref_names(inner, names),
-        IrType::Union(variants) | IrType::Intersection(variants) => {
-            for v in variants {
-                collect_ref_names(v, names);
+/// The test contexts `client.test.ts` renders, one per client method, with
+/// the operation each one tests. A method name an earlier operation already
+/// took, like a dual endpoint's `{name}Stream` clashing with an operation of
+/// that name, isn't tested twice.
+fn test_operations<'a>(
+    ir: &'a IrSpec,
+    codecs: &Codecs,
+    params_style: ParamsStyle,
+) -> Vec<(&'a IrOperation, minijinja::Value)> {
+    let mut seen_methods = std::collections::HashSet::new();
+    ir.operations
+        .iter()
+        .flat_map(|op| {
+            build_test_operation_contexts(ir, codecs, op, params_style)
+                .into_iter()
+                .map(move |ctx| (op, ctx))
+        })
+        .filter(|(_, ctx)| {
+            let name = ctx
+                .get_attr("method_name")
+                .ok()
+                .and_then(|v| v.as_str().map(String::from));
+            match name {
+                Some(n) => seen_methods.insert(n),
+                None => true,
             }
-        }
-        _ => {}
-    }
+        })
+        .collect()
 }
 
-fn build_test_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
+fn build_test_operation_contexts(
+    ir: &IrSpec,
+    codecs: &Codecs,
+    op: &IrOperation,
+    params_style: ParamsStyle,
+) -> Vec<minijinja::Value> {
     let mut results = Vec::new();
 
     match &op.return_type {
         IrReturnType::Standard(resp) => {
-            let return_type = ir_type_to_ts(&resp.response_type);
             results.push(build_test_context(
+                ir,
+                codecs,
                 op,
+                params_style,
                 "standard",
                 &op.name.camel_case,
-                &return_type,
+                &resp.response_type,
             ));
         }
         IrReturnType::Void => {
-            results.push(build_test_context(op, "void", &op.name.camel_case, "void"));
+            results.push(build_test_context(
+                ir,
+                codecs,
+                op,
+                params_style,
+                "void",
+                &op.name.camel_case,
+                &IrType::Void,
+            ));
         }
         IrReturnType::Sse(sse) => {
-            let sse_name = if sse.also_has_json {
-                format!("{}Stream", op.name.camel_case)
-            } else {
-                op.name.camel_case.clone()
-            };
-            let return_type = if let Some(ref name) = sse.event_type_name {
-                name.clone()
-            } else {
-                ir_type_to_ts(&sse.event_type)
-            };
-            results.push(build_test_context(op, "sse", &sse_name, &return_type));
+            results.push(build_test_context(
+                ir,
+                codecs,
+                op,
+                params_style,
+                "sse",
+                &sse.stream_name.camel_case,
+                &sse.event_type,
+            ));
 
             if let Some(ref json_resp) = sse.json_response {
-                let rt = ir_type_to_ts(&json_resp.response_type);
-                results.push(build_test_context(op, "standard", &op.name.camel_case, &rt));
+                results.push(build_test_context(
+                    ir,
+                    codecs,
+                    op,
+                    params_style,
+                    "standard",
+                    &op.name.camel_case,
+                    &json_resp.response_type,
+                ));
             }
         }
     }
@@ -142,130 +179,1262 @@ fn build_test_operation_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
     results
 }
 
+#[allow(clippy::too_many_arguments)]
 fn build_test_context(
+    ir: &IrSpec,
+    codecs: &Codecs,
     op: &IrOperation,
+    params_style: ParamsStyle,
     kind: &str,
     method_name: &str,
-    return_type: &str,
+    response_type: &IrType,
 ) -> minijinja::Value {
     let has_body = op.request_body.is_some();
-    let test_call_args = build_test_call_args(op);
-    let expected_url_pattern = build_expected_url_pattern(op);
-    let mock_response = mock_value_ts(&if return_type == "void" {
-        IrType::Void
+    let mock = |ir_type: &IrType| mock_value_ts(ir, codecs, ir_type);
+    // A JSON body as it's sent, and as the method takes it.
+    let json_body =
+        op.request_body
+            .as_ref()
+            .filter(|body| body.content_type.ends_with("json"))
+            .map(|body| {
+                let wire = match body.patch_format() {
+                    // An empty JSON Patch is valid, whatever schema the spec gives it.
+                    Some(PatchFormat::JsonPatch) => serde_json::json!([]),
+                    _ => example_json_body(ir, body)
+                        .unwrap_or_else(|| sample_value(ir, &body.body_type)),
+                };
+                let typed = ts_literal(&codecs.typed_value(&body.body_type, &wire));
+                (typed, ts_literal(&wire))
+            });
+    let body_arg = json_body.as_ref().map(|(typed, _)| typed.as_str());
+    let test_call_args = build_test_call_args(op, body_arg, params_style, &mock);
+    // Upload tests pass `options`, so every parameter before it needs a value.
+    let upload_call_args = (is_upload_op(op) && kind != "sse")
+        .then(|| build_full_call_args(op, body_arg, params_style, &mock, &|_| None));
+    // Every parameter filled in, so a test can pass `options` after them.
+    let options_call_prefix =
+        match build_full_call_args(op, body_arg, params_style, &mock, &|_| None) {
+            args if args.is_empty() => args,
+            args => format!("{args}, "),
+        };
+    // Plain-text bodies should arrive as the string passed in.
+    let text_body = op
+        .request_body
+        .as_ref()
+        .filter(|body| body.is_text())
+        .map(|body| (mock(&body.body_type), body.content_type.clone()));
+    let text_response = matches!(&op.return_type, IrReturnType::Standard(resp) if resp.is_text());
+    // A bodiless success status declared next to the typed one resolves to `undefined`.
+    let empty_status = match &op.return_type {
+        IrReturnType::Standard(resp) if kind == "standard" => resp
+            .empty_statuses
+            .iter()
+            .find(|status| status.parse::<u16>().is_ok())
+            .cloned(),
+        _ => None,
+    };
+    // The `Accept` header a standard method sends: its response's media type.
+    let json_response = match &op.return_type {
+        IrReturnType::Standard(resp) => Some(resp),
+        IrReturnType::Sse(sse) => sse.json_response.as_ref(),
+        IrReturnType::Void => None,
+    };
+    let accept = json_response.map(|resp| {
+        resp.content_type
+            .clone()
+            .unwrap_or_else(|| "application/json".to_string())
+    });
+    // Query and header parameters filled with values that need encoding, for
+    // the serialization tests; only built for operations that take any.
+    let wire_values: Vec<(&IrParameter, String, WirePairs)> = if kind == "sse" {
+        Vec::new()
     } else {
-        // Use a simple mock for the response
-        guess_mock_type(return_type)
+        params_object_members(op)
+            .into_iter()
+            .filter_map(|p| wire_test_value(ir, codecs, p).map(|(value, sent)| (p, value, sent)))
+            .collect()
+    };
+    // One check per wire key: a query object's flattened keys each get one.
+    let mut query_keys: Vec<(&str, Vec<&str>)> = Vec::new();
+    for (key, value) in wire_values
+        .iter()
+        .filter(|(p, ..)| p.location == IrParameterLocation::Query)
+        .flat_map(|(_, _, sent)| sent)
+    {
+        match query_keys.iter_mut().find(|(k, _)| k == key) {
+            Some((_, values)) => values.push(value),
+            None => query_keys.push((key, vec![value])),
+        }
+    }
+    let query_checks: Vec<minijinja::Value> = query_keys
+        .iter()
+        .map(|(key, values)| {
+            let encoded = values
+                .iter()
+                .map(|value| format!("{}={}", form_urlencode(key), form_urlencode(value)))
+                .collect::<Vec<_>>()
+                .join("&");
+            context! {
+                name => ts_string(key),
+                values => serde_json::to_string(values).expect("strings always serialize"),
+                encoded => ts_string(&encoded),
+            }
+        })
+        .collect();
+    let header_checks: Vec<minijinja::Value> = wire_values
+        .iter()
+        .filter(|(p, ..)| p.location == IrParameterLocation::Header && p.required)
+        .map(|(p, _, sent)| {
+            let values: Vec<&str> = sent.iter().map(|(_, value)| value.as_str()).collect();
+            context! {
+                name => ts_string(&p.original_name),
+                value => ts_string(&values.join(",")),
+            }
+        })
+        .collect();
+    let param_call_args = (!query_checks.is_empty() || !header_checks.is_empty()).then(|| {
+        build_full_call_args(op, body_arg, params_style, &mock, &|param| {
+            wire_values
+                .iter()
+                .find(|(p, ..)| std::ptr::eq(*p, param))
+                .map(|(_, value, _)| value.clone())
+        })
+    });
+    let expected_url_pattern = build_expected_url_pattern(ir, op, &|_| None);
+    // A required query or header parameter left out, and a query parameter
+    // with `allowEmptyValue` sent empty; neither is checked for streams.
+    let members = if kind == "sse" {
+        Vec::new()
+    } else {
+        params_object_members(op)
+    };
+    let call_args_with = |target: &IrParameter, value: &str| {
+        build_full_call_args(op, body_arg, params_style, &mock, &|p| {
+            std::ptr::eq(p, target).then(|| value.to_string())
+        })
+    };
+    // An `unknown` parameter takes `undefined`, so leaving it out isn't a type error.
+    let required_check = members
+        .iter()
+        .find(|p| p.required && p.param_type != IrType::Any)
+        .map(|p| {
+            context! {
+                call_args => call_args_with(p, "undefined"),
+                message => ts_string(&format!(
+                    "{method_name}: missing required parameter \"{}\"",
+                    p.original_name
+                )),
+            }
+        });
+    let empty_check = members
+        .iter()
+        .find(|p| p.location == IrParameterLocation::Query && p.allow_empty_value)
+        .map(|p| {
+            context! {
+                call_args => call_args_with(p, "\"\""),
+                name => ts_string(&p.original_name),
+            }
+        });
+    // A string path parameter holding a space and a slash, to check it's
+    // percent-encoded (slashes kept for `allowReserved` ones).
+    let path_check = op
+        .parameters
+        .iter()
+        .filter(|_| kind != "sse")
+        .find(|p| p.location == IrParameterLocation::Path && matches!(p.param_type, IrType::String))
+        .map(|target| {
+            let is_target = |p: &IrParameter| std::ptr::eq(p, target);
+            let encoded = if target.allow_reserved {
+                "a%20b/c"
+            } else {
+                "a%20b%2Fc"
+            };
+            context! {
+                call_args => build_full_call_args(
+                    op,
+                    body_arg,
+                    params_style,
+                    &mock,
+                    &|p| is_target(p).then(|| ts_string("a b/c")),
+                ),
+                expected => ts_string(&build_expected_url_pattern(ir, op, &|p| {
+                    is_target(p).then(|| encoded.to_string())
+                })),
+            }
+        });
+    // The response's preferred example, else a simple mock of its type.
+    let response_example = json_response
+        .filter(|resp| kind == "standard" && !resp.is_text())
+        .and_then(|resp| resp.example.as_ref());
+    let mock_response = match response_example {
+        Some(example) => ts_literal(example),
+        None if *response_type == IrType::Void => "undefined".to_string(),
+        None => ts_literal(&sample_response_value(ir, response_type)),
+    };
+    // The cookie to configure, as an object key, and the `Cookie` header it should produce.
+    let cookie = op.cookie_auth.first().map(|name| {
+        context! {
+            key => ts_property_key(name),
+            header => serde_json::to_string(&format!("{name}=test-session"))
+                .expect("strings always serialize"),
+        }
     });
 
     context! {
         kind => kind,
         method_name => method_name,
+        meta => operation_meta(op, method_name),
         http_method => op.method.as_str(),
-        return_type => return_type,
         has_body => has_body,
         test_call_args => test_call_args,
+        upload_call_args => upload_call_args,
+        options_call_prefix => options_call_prefix,
         expected_url_pattern => expected_url_pattern,
         mock_response => mock_response,
+        json_body => json_body.map(|(_, wire)| wire),
+        text_body => text_body.as_ref().map(|(value, _)| value.clone()),
+        text_content_type => text_body.map(|(_, content_type)| content_type),
+        text_response => text_response,
+        empty_status => empty_status,
+        accept => accept,
+        cookie => cookie,
+        bearer => op.bearer_auth.is_some(),
+        param_call_args => param_call_args,
+        query_checks => query_checks,
+        header_checks => header_checks,
+        path_check => path_check,
+        required_check => required_check,
+        empty_check => empty_check,
     }
 }
 
-/// Build test call arguments for an operation.
-/// Uses a single pass over `op.parameters` to match the same order as `build_params_raw` in client.rs.
-fn build_test_call_args(op: &IrOperation) -> String {
+/// A JSON body's preferred example, when it's an object, as it's sent. An
+/// object schema's example keeps only the properties it declares, unless it
+/// allows additional ones, and gets a sample of each required one it leaves
+/// out, so it satisfies the body's type.
+fn example_json_body(ir: &IrSpec, body: &IrRequestBody) -> Option<serde_json::Value> {
+    let mut example = body.example.as_ref()?.as_object()?.clone();
+    let obj = match &body.body_type {
+        IrType::Ref(name) => ir.schemas.iter().find_map(|s| match s {
+            IrSchema::Object(obj) if obj.name.pascal_case == *name => Some(obj),
+            _ => None,
+        }),
+        _ => None,
+    };
+    if let Some(obj) = obj {
+        if obj.additional_properties.is_none() {
+            example.retain(|key, _| obj.fields.iter().any(|f| f.original_name == *key));
+        }
+        let sample = sample_value(ir, &body.body_type);
+        for field in obj.fields.iter().filter(|f| f.required) {
+            if let Some(value) = sample.get(&field.original_name) {
+                example
+                    .entry(field.original_name.clone())
+                    .or_insert_with(|| value.clone());
+            }
+        }
+    }
+    Some(serde_json::Value::Object(example))
+}
+
+/// Build test call arguments for an operation, with `value` rendering each
+/// argument's mock. Uses a single pass over `op.parameters` to match the same
+/// order as `build_params_raw` in client.rs.
+pub(crate) fn build_test_call_args(
+    op: &IrOperation,
+    body: Option<&str>,
+    params_style: ParamsStyle,
+    value: &dyn Fn(&IrType) -> String,
+) -> String {
+    if params_style == ParamsStyle::Object && !params_object_members(op).is_empty() {
+        return build_params_object_call_args(op, body, value);
+    }
     let mut args = Vec::new();
 
     for param in &op.parameters {
         match param.location {
             IrParameterLocation::Path => {
-                args.push(mock_value_ts(&param.param_type));
+                args.push(param_value(param, value));
             }
-            IrParameterLocation::Query | IrParameterLocation::Header => {
-                if param.required {
-                    args.push(mock_value_ts(&param.param_type));
-                }
+            IrParameterLocation::Query | IrParameterLocation::Header
+                if param.required && param.pinned_value.is_none() =>
+            {
+                args.push(param_value(param, value));
             }
             _ => {}
         }
     }
 
-    if let Some(ref body) = op.request_body {
-        args.push(mock_value_ts(&body.body_type));
+    if let Some(ref request_body) = op.request_body {
+        args.push(body.map_or_else(|| value(&request_body.body_type), String::from));
+    }
+
+    args.join(", ")
+}
+
+/// Test call arguments with `ParamsStyle::Object`: path parameters, then the
+/// params object with its required members, then the body.
+fn build_params_object_call_args(
+    op: &IrOperation,
+    body: Option<&str>,
+    value: &dyn Fn(&IrType) -> String,
+) -> String {
+    let mut args: Vec<String> = op
+        .parameters
+        .iter()
+        .filter(|p| p.location == IrParameterLocation::Path)
+        .map(|p| param_value(p, value))
+        .collect();
+
+    let params = mock_params_object(op, value);
+
+    match &op.request_body {
+        // A required body comes before an optional params object.
+        Some(request_body) if request_body.required && !params_object_required(op) => {
+            args.push(body.map_or_else(|| value(&request_body.body_type), String::from));
+        }
+        Some(request_body) => {
+            args.push(params);
+            args.push(body.map_or_else(|| value(&request_body.body_type), String::from));
+        }
+        None if params_object_required(op) => args.push(params),
+        None => {}
     }
 
     args.join(", ")
 }
 
-/// Build the expected URL pattern for assertions.
-fn build_expected_url_pattern(op: &IrOperation) -> String {
-    let mut path = op.path.clone();
+/// A `ParamsStyle::Object` params object with a value for each required member.
+fn mock_params_object(op: &IrOperation, value: &dyn Fn(&IrType) -> String) -> String {
+    let members: Vec<String> = params_object_members(op)
+        .into_iter()
+        .filter(|p| p.required)
+        .map(|p| {
+            format!(
+                "{}: {}",
+                ts_property_key(&p.name.camel_case),
+                param_value(p, value)
+            )
+        })
+        .collect();
+    if members.is_empty() {
+        "{}".to_string()
+    } else {
+        format!("{{ {} }}", members.join(", "))
+    }
+}
+
+/// `param`'s spec example as a TypeScript literal, else `value` of its type.
+/// Path parameters only use examples that go into a URL as is, so the call
+/// matches [`build_expected_url_pattern`].
+fn param_value(param: &IrParameter, value: &dyn Fn(&IrType) -> String) -> String {
+    let example = match param.location {
+        IrParameterLocation::Path => param.path_example().and(param.example.as_ref()),
+        _ => param.example.as_ref(),
+    };
+    example.map_or_else(|| value(&param.param_type), ToString::to_string)
+}
+
+/// A value for every parameter of the method in signature order (required
+/// ones, then optional ones), so the caller can append `options`. Parameters
+/// take their value from `fill`, else `mock` of their type when required and
+/// `undefined` when optional.
+fn build_full_call_args(
+    op: &IrOperation,
+    body: Option<&str>,
+    params_style: ParamsStyle,
+    mock: &dyn Fn(&IrType) -> String,
+    fill: &dyn Fn(&IrParameter) -> Option<String>,
+) -> String {
+    let members = params_object_members(op);
+    let params_object = params_style == ParamsStyle::Object && !members.is_empty();
+    let mut required = Vec::new();
+    let mut optional = Vec::new();
+    for param in &op.parameters {
+        match param.location {
+            IrParameterLocation::Path => {
+                required.push(fill(param).unwrap_or_else(|| param_value(param, mock)))
+            }
+            IrParameterLocation::Query | IrParameterLocation::Header
+                if param.pinned_value.is_none() && !params_object =>
+            {
+                match fill(param) {
+                    Some(value) if param.required => required.push(value),
+                    Some(value) => optional.push(value),
+                    None if param.required => required.push(param_value(param, mock)),
+                    None => optional.push("undefined".to_string()),
+                }
+            }
+            _ => {}
+        }
+    }
+    if params_object {
+        let filled: Vec<String> = members
+            .iter()
+            .filter_map(|p| {
+                let value = fill(p).or_else(|| p.required.then(|| param_value(p, mock)))?;
+                Some(format!("{}: {value}", ts_property_key(&p.name.camel_case)))
+            })
+            .collect();
+        let object = if filled.is_empty() {
+            "undefined".to_string()
+        } else {
+            format!("{{ {} }}", filled.join(", "))
+        };
+        if params_object_required(op) {
+            required.push(object);
+        } else {
+            optional.push(object);
+        }
+    }
+    if let Some(request_body) = &op.request_body {
+        let value = body.map_or_else(|| mock(&request_body.body_type), String::from);
+        if request_body.required {
+            required.push(value);
+        } else {
+            optional.push(value);
+        }
+    }
+    required.extend(optional);
+    required.join(", ")
+}
+
+/// `(wire name, value)` pairs a test value is sent as.
+type WirePairs = Vec<(String, String)>;
+
+/// A query or header parameter value for the serialization tests, with
+/// characters that need URL-encoding, and the wire names and strings the
+/// client should send for it (one per field for query objects). `None` for
+/// values without a plain string form (other objects, `content` parameters
+/// sent as JSON).
+fn wire_test_value(
+    ir: &IrSpec,
+    codecs: &Codecs,
+    param: &IrParameter,
+) -> Option<(String, WirePairs)> {
+    if param.content_type.is_some() {
+        return None;
+    }
+    let name = &param.original_name;
+    if let Some(notation) = param.query_object {
+        // Every required field and any optional scalar one, sent as
+        // `name[field]` or `name.field`.
+        let mut members = Vec::new();
+        let mut sent = Vec::new();
+        for (field, typed, field_type, required) in object_fields(ir, codecs, &param.param_type)? {
+            match scalar_test_value(ir, &field_type) {
+                Some((value, wire)) => {
+                    members.push(format!("{}: {value}", ts_string(&typed)));
+                    let key = match notation {
+                        QueryObjectNotation::Bracket => format!("{name}[{field}]"),
+                        QueryObjectNotation::Dot => format!("{name}.{field}"),
+                    };
+                    sent.push((key, wire));
+                }
+                None if required => return None,
+                None => {}
+            }
+        }
+        return (!sent.is_empty()).then(|| (format!("{{ {} }}", members.join(", ")), sent));
+    }
+    match &param.param_type {
+        // Two items joined into one value, or one sent under a repeated key.
+        IrType::Array(inner) => {
+            let (value, sent) = scalar_test_value(ir, inner)?;
+            Some(match param.query_array {
+                Some(style) => (
+                    format!("[{value}, {value}]"),
+                    vec![(name.clone(), [sent.as_str(); 2].join(style.delimiter()))],
+                ),
+                None => (format!("[{value}]"), vec![(name.clone(), sent)]),
+            })
+        }
+        other => {
+            scalar_test_value(ir, other).map(|(value, sent)| (value, vec![(name.clone(), sent)]))
+        }
+    }
+}
+
+/// The `(wire name, typed name, type, required)` fields of an inline or named
+/// object type.
+fn object_fields(
+    ir: &IrSpec,
+    codecs: &Codecs,
+    ir_type: &IrType,
+) -> Option<Vec<(String, String, IrType, bool)>> {
+    match ir_type {
+        IrType::Object(fields) => Some(
+            fields
+                .iter()
+                .map(|(name, ty, required)| (name.clone(), name.clone(), ty.clone(), *required))
+                .collect(),
+        ),
+        IrType::Ref(name) => match ir.schemas.iter().find(|s| s.name().pascal_case == *name)? {
+            IrSchema::Object(obj) => Some(
+                obj.fields
+                    .iter()
+                    .map(|f| {
+                        (
+                            f.original_name.clone(),
+                            codecs.typed_property(ir_type, &f.original_name),
+                            f.field_type.clone(),
+                            f.required,
+                        )
+                    })
+                    .collect(),
+            ),
+            IrSchema::Alias(alias) => object_fields(ir, codecs, &alias.target),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn scalar_test_value(ir: &IrSpec, ir_type: &IrType) -> Option<(String, String)> {
+    let literal = |s: &str| (ts_string(s), s.to_string());
+    match ir_type {
+        IrType::String => Some(literal("a b&c/d")),
+        IrType::StringLiteral(s) => Some(literal(s)),
+        IrType::Integer | IrType::Number => Some(("2".to_string(), "2".to_string())),
+        IrType::Boolean => Some(("true".to_string(), "true".to_string())),
+        IrType::Union(variants) => variants
+            .iter()
+            .find(|v| **v != IrType::Null)
+            .and_then(|v| scalar_test_value(ir, v)),
+        IrType::Ref(name) => match ir.schemas.iter().find(|s| s.name().pascal_case == *name)? {
+            IrSchema::Enum(e) => e.variants.first().map(|v| literal(v)),
+            IrSchema::Alias(alias) => scalar_test_value(ir, &alias.target),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// `value` encoded the way `URLSearchParams` writes it.
+fn form_urlencode(value: &str) -> String {
+    let mut out = String::new();
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => {
+                out.push(byte as char)
+            }
+            b' ' => out.push('+'),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+/// A double-quoted TypeScript string literal.
+fn ts_string(value: &str) -> String {
+    serde_json::to_string(value).expect("strings always serialize")
+}
+
+/// Build the expected URL pattern for assertions, with each path parameter's
+/// segment from `fill`, else its example where it has one.
+fn build_expected_url_pattern(
+    ir: &IrSpec,
+    op: &IrOperation,
+    fill: &dyn Fn(&IrParameter) -> Option<String>,
+) -> String {
+    let mut path = op.normalized_path.clone();
     for param in &op.parameters {
         if param.location == IrParameterLocation::Path {
-            let placeholder = format!("{{{}}}", param.original_name);
-            path = path.replace(&placeholder, &mock_path_value_ts(&param.param_type));
+            let placeholder = format!("{{{}}}", param.name.camel_case);
+            let value = fill(param)
+                .or_else(|| param.path_example())
+                .unwrap_or_else(|| mock_path_value_ts(ir, &param.param_type));
+            path = path.replace(&placeholder, &value);
         }
     }
     path
 }
 
-/// Generate a mock TypeScript value for a given IrType.
-fn mock_value_ts(ir_type: &IrType) -> String {
+/// A TypeScript value of `ir_type`, as a method takes it, for parameters and
+/// bodies other than JSON: a literal for scalars, and a [`sample_value`] under
+/// the typed property names for schemas, with `Blob`s for a multipart body's
+/// binary fields. Either way it satisfies the type without a cast.
+fn mock_value_ts(ir: &IrSpec, codecs: &Codecs, ir_type: &IrType) -> String {
     match ir_type {
         IrType::String | IrType::DateTime => "\"test\"".to_string(),
-        IrType::StringLiteral(s) => format!("\"{s}\""),
+        IrType::StringLiteral(s) => ts_string(s),
         IrType::Number | IrType::Integer => "1".to_string(),
         IrType::Boolean => "true".to_string(),
         IrType::Null | IrType::Void => "undefined".to_string(),
         IrType::Array(_) => "[]".to_string(),
-        IrType::Object(_) | IrType::Map(_) | IrType::Any => "{}".to_string(),
-        IrType::Ref(name) => format!("{{}} as {}", name),
+        IrType::Map(..) | IrType::Any => "{}".to_string(),
         IrType::Binary => "new Blob()".to_string(),
-        IrType::Union(variants) | IrType::Intersection(variants) => {
-            if let Some(first) = variants.first() {
-                mock_value_ts(first)
-            } else {
-                "{}".to_string()
+        IrType::Union(variants) => variants
+            .iter()
+            .find(|v| **v != IrType::Null)
+            .map_or_else(|| "null".to_string(), |v| mock_value_ts(ir, codecs, v)),
+        _ => {
+            let sample = sample_value(ir, ir_type);
+            let is_binary = |ty: &IrType| match ty {
+                IrType::Array(item) => **item == IrType::Binary,
+                ty => *ty == IrType::Binary,
+            };
+            match object_fields(ir, codecs, ir_type) {
+                Some(fields) if fields.iter().any(|(_, _, ty, _)| is_binary(ty)) => {
+                    let entries: Vec<String> = fields
+                        .iter()
+                        .filter_map(|(wire, typed, ty, _)| {
+                            let value = if is_binary(ty) {
+                                mock_value_ts(ir, codecs, ty)
+                            } else {
+                                ts_literal(&codecs.typed_value(ty, sample.get(wire)?))
+                            };
+                            Some(format!("{}: {value}", ts_property_key(typed)))
+                        })
+                        .collect();
+                    format!("{{ {} }}", entries.join(", "))
+                }
+                _ => ts_literal(&codecs.typed_value(ir_type, &sample)),
             }
         }
     }
 }
 
-/// Mock path parameter value as a string for URL patterns.
-fn mock_path_value_ts(ir_type: &IrType) -> String {
+/// Mock path parameter value as a string for URL patterns, matching
+/// [`mock_value_ts`].
+fn mock_path_value_ts(ir: &IrSpec, ir_type: &IrType) -> String {
     match ir_type {
         IrType::Integer | IrType::Number => "1".to_string(),
+        IrType::Ref(_) => match sample_value(ir, ir_type) {
+            serde_json::Value::String(s) => s,
+            other => other.to_string(),
+        },
         _ => "test".to_string(),
     }
 }
 
-/// Guess a mock IrType from a return type string for simple response mocking.
-fn guess_mock_type(return_type: &str) -> IrType {
-    match return_type {
-        "string" => IrType::String,
-        "number" => IrType::Number,
-        "boolean" => IrType::Boolean,
-        "void" => IrType::Void,
-        t if t.ends_with("[]") => IrType::Array(Box::new(IrType::Any)),
-        _ => IrType::Ref(return_type.to_string()),
-    }
-}
-
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_mock_value_ts() {
-        assert_eq!(mock_value_ts(&IrType::String), "\"test\"");
-        assert_eq!(mock_value_ts(&IrType::Integer), "1");
-        assert_eq!(mock_value_ts(&IrType::Boolean), "true");
-        assert_eq!(mock_value_ts(&IrType::Void), "undefined");
-        assert_eq!(mock_value_ts(&IrType::Ref("Pet".to_string())), "{} as Pet");
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-polymorphic.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let codecs = Codecs::new(&ir, PropertyNaming::Original);
+        let mock = |ir_type: &IrType| mock_value_ts(&ir, &codecs, ir_type);
+        assert_eq!(mock(&IrType::String), "\"test\"");
+        assert_eq!(mock(&IrType::Integer), "1");
+        assert_eq!(mock(&IrType::Boolean), "true");
+        assert_eq!(mock(&IrType::Void), "undefined");
+        // Schemas get a value of their type rather than a cast.
+        let pet = mock(&IrType::Ref("Pet".to_string()));
+        assert!(
+            pet.starts_with("{ ") && pet.contains("petType: \"cat\""),
+            "{pet}"
+        );
     }
 
     #[test]
     fn test_mock_path_value() {
-        assert_eq!(mock_path_value_ts(&IrType::Integer), "1");
-        assert_eq!(mock_path_value_ts(&IrType::String), "test");
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-polymorphic.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert_eq!(mock_path_value_ts(&ir, &IrType::Integer), "1");
+        assert_eq!(mock_path_value_ts(&ir, &IrType::String), "test");
+    }
+
+    /// The lines of generated TypeScript with a type assertion (`value as Type`)
+    /// outside strings and comments.
+    fn type_assertions(source: &str) -> Vec<&str> {
+        source
+            .lines()
+            .filter(|line| {
+                let mut code = String::new();
+                let mut quote = None;
+                let mut chars = line.chars().peekable();
+                while let Some(c) = chars.next() {
+                    match quote {
+                        Some(_) if c == '\\' => {
+                            chars.next();
+                        }
+                        Some(q) if c == q => quote = None,
+                        Some(_) => {}
+                        None if matches!(c, '"' | '\'' | '`') => {
+                            quote = Some(c);
+                            code.push(' ');
+                        }
+                        None if c == '/' && chars.peek() == Some(&'/') => break,
+                        None => code.push(c),
+                    }
+                }
+                code.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                    .any(|word| word == "as")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_type_assertions() {
+        assert_eq!(
+            type_assertions("const a = {} as Pet;\nit(\"sends dates as ISO strings\");\n// as is"),
+            vec!["const a = {} as Pet;"]
+        );
+        // Mocks satisfy their types, so the tests type-check the client's
+        // signatures the way a caller's code would.
+        for fixture in [
+            include_str!("../../../oag-core/tests/fixtures/petstore-3.2.yaml"),
+            include_str!("../../../oag-core/tests/fixtures/sse-chat.yaml"),
+            include_str!("../../../oag-core/tests/fixtures/anthropic-messages.yaml"),
+            include_str!("../../../oag-core/tests/fixtures/petstore-polymorphic.yaml"),
+        ] {
+            let ir = oag_core::transform::transform(&oag_core::parse::from_yaml(fixture).unwrap())
+                .unwrap();
+            let content = emit_client_tests(
+                &ir,
+                ParamsStyle::Positional,
+                PropertyNaming::Original,
+                false,
+            );
+            assert_eq!(type_assertions(&content), Vec::<&str>::new());
+            assert!(!content.contains("from \"./types\""));
+        }
+    }
+
+    #[test]
+    fn test_every_operation_asserts_http_method() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        for op in &ir.operations {
+            let method = op.method.as_str();
+            assert!(
+                content.contains(&format!("makes {method} request to correct URL")),
+                "no request test for {}",
+                op.name.original
+            );
+        }
+        assert!(content.contains("sseTransport: \"fetch\""));
+        assert!(content.contains("expect(init.method).toBe(\"POST\");"));
+        assert!(content.contains("expect(mockFetch.mock.calls[0][1].method).toBe(\"GET\");"));
+    }
+
+    #[test]
+    fn test_dual_endpoint_variants_send_their_accept_header() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        let json_variant = content
+            .split("describe(\"createChatCompletion\"")
+            .nth(1)
+            .unwrap();
+        assert!(json_variant.contains("expect(init.headers.Accept).toBe(\"application/json\");"));
+        let stream_variant = content
+            .split("describe(\"createChatCompletionStream\"")
+            .nth(1)
+            .unwrap();
+        let stream_variant = stream_variant.split("describe(").next().unwrap();
+        assert!(
+            stream_variant.contains("expect(init.headers.Accept).toBe(\"text/event-stream\");")
+        );
+        assert!(
+            stream_variant.contains("expect(init.headers[\"Cache-Control\"]).toBe(\"no-store\");")
+        );
+        assert!(content.contains(
+            "await client.createChatCompletion({ maxTokens: 1, messages: [{ content: \"test\", role: \"system\" }], model: \"test\", stream: true, temperature: 1 }, { headers: { Accept: \"*/*\" } });"
+        ));
+    }
+
+    #[test]
+    fn test_coverage_keeps_operations_whose_method_name_was_taken() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/stream-name-collision.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let coverage = client_test_coverage(&ir, ParamsStyle::Positional, PropertyNaming::Original);
+
+        // `chat`'s stream method is tested as `chatStream`, so the
+        // `chatStream` operation is left without tests rather than dropped.
+        assert_eq!(coverage.untested(), ["chatStream"]);
+        assert_eq!(
+            coverage.operations["chat"],
+            [
+                "client.test.ts > ApiClient > chatStream",
+                "client.test.ts > ApiClient > chat",
+                "client.test.ts > ApiClient > chatRaw",
+            ]
+        );
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        for test in coverage.operations.values().flatten() {
+            let suite = test.rsplit(" > ").next().unwrap();
+            assert!(content.contains(&format!("describe(\"{suite}\"")), "{test}");
+        }
+    }
+
+    #[test]
+    fn test_request_body_keys_are_wire_names() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/property-names.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains(
+            "await client.createAccount({ \"2fa_enabled\": true, \"content-type\": \"test\", display_name: \"test\", \"login-count\": 1, \"x-labels\": {} });"
+        ));
+        assert!(content.contains(
+            "expect(JSON.parse(init.body)).toEqual({ \"2fa_enabled\": true, \"content-type\": \"test\", display_name: \"test\", \"login-count\": 1, \"x-labels\": {} });"
+        ));
+    }
+
+    #[test]
+    fn test_camel_property_naming_passes_typed_bodies() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/snake-case.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(&ir, ParamsStyle::Positional, PropertyNaming::Camel, false);
+
+        assert!(content.contains(
+            "await client.createPet({ displayName: \"Rex\", owner: { firstName: \"Ada L.\", first_name: \"Ada\" } });"
+        ));
+        // The wire form: keys as the spec names them.
+        assert!(content.contains(
+            "expect(JSON.parse(init.body)).toEqual({ display_name: \"Rex\", owner: { firstName: \"Ada L.\", first_name: \"Ada\" } });"
+        ));
+    }
+
+    #[test]
+    fn test_write_only_fields_are_redacted_in_mock_bodies() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/write-only.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains(
+            "await client.createUser({ email: \"test\", id: \"test\", password: \"<redacted>\" });"
+        ));
+    }
+
+    #[test]
+    fn test_parameter_examples_replace_mocks() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/parameter-examples.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("await client.getPet(42, \"name,status\");"));
+        assert!(content.contains("/pets/42"));
+        // Examples that would need percent-encoding keep the mock.
+        assert!(content.contains("await client.getOwner(\"test\");"));
+    }
+
+    #[test]
+    fn test_preferred_body_examples_replace_mocks() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/body-examples.yaml"
+        ))
+        .unwrap();
+        let options = oag_core::transform::TransformOptions {
+            example_preference: vec!["minimal".to_string()],
+            ..Default::default()
+        };
+        let ir = oag_core::transform::transform_with_options(&spec, &options).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("await client.createPet({ name: \"Rex\" });"));
+        assert!(content.contains("expect(JSON.parse(init.body)).toEqual({ name: \"Rex\" });"));
+        // The response's single `example`.
+        assert!(content.contains("createMockFetch(200, { id: 7, name: \"Rex\" })"));
+        // No preferred or single example: a sample of the type.
+        assert!(content.contains("createMockFetch(200, { id: 1, name: \"test\", tag: \"test\" })"));
+    }
+
+    #[test]
+    fn test_interceptor_receives_operation_meta() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("describe(\"operation metadata\""));
+        assert!(
+            content
+                .contains("it(\"createMessageStream passes its metadata to requestInterceptor\"")
+        );
+        assert!(content.contains(
+            "expect(requestInterceptor.mock.calls[0][0].operation).toEqual({ name: \"cancelBatch\", method: \"DELETE\", path: \"/v1/messages/batches/{batchId}\", tags: [\"batches\"] });"
+        ));
+        assert_eq!(
+            content
+                .matches("expect(requestInterceptor.mock.calls[0][0].operation)")
+                .count(),
+            6
+        );
+    }
+
+    #[test]
+    fn test_upload_progress_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/file-uploads.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("class FakeXMLHttpRequest {"));
+        // The optional `overwrite` is skipped with `undefined` to reach `options`.
+        assert!(content.contains(
+            "await client.uploadPhoto(\"test\", { file: new Blob(), caption: \"test\" }, undefined, { onUploadProgress });"
+        ));
+        assert!(content.contains(
+            "await client.putFile(\"test\", \"test\", new Blob(), { onUploadProgress });"
+        ));
+        assert!(!content.contains("client.listPhotos(\"test\", { onUploadProgress })"));
+        // One browser test per upload.
+        assert_eq!(
+            content
+                .matches("vi.stubGlobal(\"XMLHttpRequest\", FakeXMLHttpRequest);")
+                .count(),
+            2
+        );
+
+        let content = emit_client_tests(&ir, ParamsStyle::Object, PropertyNaming::Original, false);
+        assert!(content.contains(
+            "await client.uploadPhoto(\"test\", { file: new Blob(), caption: \"test\" }, undefined, { onUploadProgress });"
+        ));
+    }
+
+    #[test]
+    fn test_query_and_header_serialization_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        // Optional query parameters get values too; strings need encoding.
+        assert!(
+            content.contains("await client.listModels(\"a b&c/d\", 2, \"a b&c/d\", \"a b&c/d\");")
+        );
+        assert!(content.contains("expect(url).toContain(\"after_id=a+b%26c%2Fd\");"));
+        assert!(content.contains("expect(query.getAll(\"limit\")).toEqual([\"2\"]);"));
+        assert!(content.contains("expect(init.headers[\"anthropic-version\"]).toBe(\"a b&c/d\");"));
+        // Only operations with query parameters get the query test.
+        assert!(!content.contains("it(\"getModel URL-encodes query parameters"));
+        assert!(!content.contains("it(\"createMessageStream sends required header"));
+        assert_eq!(
+            content
+                .matches("expect(error).toMatchObject({ name: \"ApiError\", statusCode: 500 });")
+                .count(),
+            ir.operations.len()
+        );
+
+        let content = emit_client_tests(&ir, ParamsStyle::Object, PropertyNaming::Original, false);
+        assert!(content.contains(
+            "await client.listModels({ anthropicVersion: \"a b&c/d\", limit: 2, afterId: \"a b&c/d\", beforeId: \"a b&c/d\" });"
+        ));
+    }
+
+    #[test]
+    fn test_path_encoding_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/reserved-path-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("await client.getPet(\"a b/c\");"));
+        assert!(content.contains("expect(url).toContain(\"/pets/a%20b%2Fc\");"));
+        // `allowReserved` keeps the slash.
+        assert!(content.contains("expect(url).toContain(\"/files/a%20b/c\");"));
+        // Only string parameters get the odd value; the rest keep their mock.
+        assert!(content.contains("await client.getVisit(1, \"a b/c\");"));
+        assert!(content.contains("expect(url).toContain(\"/pets/1/visits/a%20b%2Fc\");"));
+    }
+
+    #[test]
+    fn test_required_and_empty_parameter_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(content.contains(
+            "        // @ts-expect-error the required parameter is left out\n        client.listModels(undefined, undefined, undefined, undefined),\n      ).rejects.toThrow("
+        ));
+        assert!(
+            content
+                .contains("\"listModels: missing required parameter \\\"anthropic-version\\\"\",")
+        );
+        // Streams aren't checked.
+        assert!(!content.contains("client.createMessageStream(undefined"));
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(&ir, ParamsStyle::Object, PropertyNaming::Original, false);
+        assert!(content.contains("await client.listThings({ q: \"\" });"));
+        assert!(content.contains(
+            "expect(new URL(url).search.slice(1).split(\"&\")).toContain(encodeURIComponent(\"q\"));"
+        ));
+        assert!(
+            content.contains("        client.searchThings({ q: undefined }),\n      ).rejects")
+        );
+    }
+
+    #[test]
+    fn test_bodiless_success_status_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/empty-success.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("it(\"returns the body on 200\""));
+        assert!(content.contains("it(\"returns undefined on 204\""));
+        assert!(content.contains("new Response(null, { status: 204 })"));
+        // Only getPet declares a bodiless status.
+        assert_eq!(content.matches("it(\"returns undefined on").count(), 1);
+    }
+
+    #[test]
+    fn test_delimited_query_array_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/query-arrays.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        // Delimited arrays get two items so the test sees the delimiter.
+        assert!(content.contains(
+            "await client.searchPets([\"a b&c/d\", \"a b&c/d\"], [\"a b&c/d\", \"a b&c/d\"], [\"available\", \"available\"], [\"a b&c/d\"], undefined, true);"
+        ));
+        assert!(content.contains("expect(query.getAll(\"ids\")).toEqual([\"a b&c/d,a b&c/d\"]);"));
+        assert!(content.contains("expect(query.getAll(\"tags\")).toEqual([\"a b&c/d a b&c/d\"]);"));
+        assert!(
+            content
+                .contains("expect(query.getAll(\"status\")).toEqual([\"available|available\"]);")
+        );
+        assert!(content.contains("expect(query.getAll(\"breed\")).toEqual([\"a b&c/d\"]);"));
+        assert!(content.contains("expect(query.getAll(\"vaccinated\")).toEqual([\"true\"]);"));
+    }
+
+    #[test]
+    fn test_query_object_encoding_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/query-objects.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(
+            content.contains("await client.listPets({ \"name\": \"a b&c/d\", \"age\": 2 }, 2);")
+        );
+        assert!(content.contains("expect(url).toContain(\"filter%5Bname%5D=a+b%26c%2Fd\");"));
+        assert!(content.contains("expect(query.getAll(\"filter[age]\")).toEqual([\"2\"]);"));
+        assert!(
+            content.contains("await client.listOwners({ \"city\": \"a b&c/d\", \"minPets\": 2 });")
+        );
+        assert!(content.contains("expect(url).toContain(\"where.city=a+b%26c%2Fd\");"));
+        assert!(content.contains("expect(query.getAll(\"where.minPets\")).toEqual([\"2\"]);"));
+    }
+
+    #[test]
+    fn test_cookie_auth_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/cookie-auth.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("import { afterEach, beforeEach, describe,"));
+        assert!(content.contains("cookies: { sid: \"test-session\" },"));
+        assert!(content.contains("expect(init.headers.Cookie).toBe(\"sid=test-session\");"));
+        assert!(content.contains("vi.stubGlobal(\"document\", {});"));
+        // getMe, streamEvents and health, each outside and inside a browser.
+        assert_eq!(
+            content
+                .matches("expect(init.credentials).toBe(\"include\");")
+                .count(),
+            6
+        );
+        assert!(!content.contains("it(\"login sends configured cookies"));
+        assert!(!content.contains("it(\"status sends configured cookies"));
+    }
+
+    #[test]
+    fn test_bearer_auth_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/bearer-auth.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+
+        assert!(content.contains("describe(\"bearer auth\", () => {"));
+        // getMe, deleteMe, streamEvents and listKeys, with a string and a provider.
+        assert_eq!(
+            content
+                .matches("expect(init.headers.Authorization).toBe(\"Bearer test-token\");")
+                .count(),
+            4
+        );
+        assert_eq!(content.matches("        bearerToken,\n").count(), 4);
+        assert!(
+            content.contains("it(\"streamEvents sends bearerToken as an Authorization header\"")
+        );
+        assert!(!content.contains("it(\"createToken sends bearerToken"));
+    }
+
+    #[test]
+    fn test_params_object_call_args() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(&ir, ParamsStyle::Object, PropertyNaming::Original, false);
+
+        assert!(content.contains("await client.listThings();"));
+        assert!(content.contains("await client.createThing({ name: \"test\" });"));
+        assert!(content.contains("await client.searchThings({ q: \"test\" });"));
+        assert!(content.contains(
+            "await client.updateThing(\"test\", { reason: \"test\" }, { name: \"test\" });"
+        ));
+    }
+
+    #[test]
+    fn test_retry_tests_only_with_retry() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_client_tests(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(!content.contains("describe(\"retry\""));
+
+        let content =
+            emit_client_tests(&ir, ParamsStyle::Positional, PropertyNaming::Original, true);
+        assert!(content.contains("describe(\"retry\""));
+        assert!(content.contains("{ status: 429, statusText: \"Too Many Requests\", headers: { \"Retry-After\": \"0\" } }"));
+        assert!(content.contains("{ status: 503, statusText: \"Service Unavailable\", headers: { \"Retry-After\": \"0\" } }"));
     }
 }
@@ -1,47 +1,193 @@
 use std::collections::HashSet;
 
 use minijinja::{Environment, context};
-use oag_core::ir::{IrObjectSchema, IrReturnType, IrSchema, IrSpec};
+use oag_core::config::{ParamsStyle, PropertyNaming};
+use oag_core::ir::{
+    IrObjectSchema, IrOperation, IrParameter, IrReturnType, IrSchema, IrSpec, IrType, PatchFormat,
+};
 
-use crate::type_mapper::ir_type_to_ts;
+use crate::emitters::client::{params_interface_name, params_object_members};
+use crate::emitters::codecs::{Codecs, property_names};
+use crate::type_mapper::{ir_type_to_ts, map_key_note, ts_property_key};
 
-/// Escape `*/` sequences that would prematurely close JSDoc comment blocks.
-fn escape_jsdoc(value: String) -> String {
-    value.replace("*/", "*\\/")
+/// Emit `types.ts` containing all interfaces, enums, aliases, and SSE event union
+/// types, plus the `{Op}Params` interfaces with `ParamsStyle::Object`, and the
+/// `DeepPartial` and `JsonPatchOp` body types of patch operations. Properties
+/// are named by `property_naming`. With `retry_config`, also declares the
+/// `RetryConfig` taken by client retries and SSE reconnects.
+pub fn emit_types(
+    ir: &IrSpec,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    retry_config: bool,
+) -> String {
+    let mut schemas: Vec<_> = ir
+        .schemas
+        .iter()
+        .map(|schema| schema_to_ctx(schema, property_naming))
+        .collect();
+    if params_style == ParamsStyle::Object {
+        schemas.extend(params_interfaces(&ir.operations));
+    }
+    render_types(
+        ir,
+        schemas,
+        &sse_event_types(ir, &ir.operations),
+        &patch_formats(&ir.operations),
+        &[],
+        property_naming,
+        retry_config,
+    )
+}
+
+/// Whether `types.ts` declares `RetryConfig`: for the client's `retry` options,
+/// or for the SSE runtime's reconnects.
+pub fn needs_retry_config(ir: &IrSpec, retry: bool, minimal_runtime: bool) -> bool {
+    retry || super::sse::needs_runtime(ir, minimal_runtime)
+}
+
+/// Emit one file of a split types layout: the given schemas, `{Op}Params`
+/// interfaces of `params_ops`, SSE event union types and the body types of
+/// `patch_formats`, with `import type` lines for each `(module, names)` in
+/// `imports`, and `RetryConfig` with `retry_config`.
+#[allow(clippy::too_many_arguments)]
+pub fn emit_type_module<'a>(
+    ir: &IrSpec,
+    schema_indices: &[usize],
+    params_ops: impl IntoIterator<Item = &'a IrOperation>,
+    sse_event_types: &[SseEventType],
+    patch_formats: &[PatchFormat],
+    imports: &[(String, Vec<String>)],
+    property_naming: PropertyNaming,
+    retry_config: bool,
+) -> String {
+    let mut schemas: Vec<_> = schema_indices
+        .iter()
+        .map(|&i| schema_to_ctx(&ir.schemas[i], property_naming))
+        .collect();
+    schemas.extend(params_interfaces(params_ops));
+    render_types(
+        ir,
+        schemas,
+        sse_event_types,
+        patch_formats,
+        imports,
+        property_naming,
+        retry_config,
+    )
+}
+
+/// The patch formats `ops` take request bodies in, each once.
+pub fn patch_formats<'a>(ops: impl IntoIterator<Item = &'a IrOperation>) -> Vec<PatchFormat> {
+    let mut formats = Vec::new();
+    for format in ops
+        .into_iter()
+        .filter_map(|op| op.request_body.as_ref()?.patch_format())
+    {
+        if !formats.contains(&format) {
+            formats.push(format);
+        }
+    }
+    formats
 }
 
-/// Emit `types.ts` containing all interfaces, enums, aliases, and SSE event union types.
-pub fn emit_types(ir: &IrSpec) -> String {
+/// One `{Op}Params` interface per operation with query or header parameters,
+/// keyed by their camelCase names.
+fn params_interfaces<'a>(ops: impl IntoIterator<Item = &'a IrOperation>) -> Vec<minijinja::Value> {
+    let mut seen = HashSet::new();
+    ops.into_iter()
+        .filter(|op| !params_object_members(op).is_empty())
+        .filter(|op| seen.insert(params_interface_name(op)))
+        .map(|op| {
+            let fields: Vec<minijinja::Value> = params_object_members(op)
+                .into_iter()
+                .map(|p| {
+                    context! {
+                        key => ts_property_key(&p.name.camel_case),
+                        type => ir_type_to_ts(&p.param_type),
+                        required => p.required,
+                        description => with_example(describe(&p.description, &p.param_type), p),
+                    }
+                })
+                .collect();
+            context! {
+                kind => "object",
+                name => params_interface_name(op),
+                description => format!("Query and header parameters of `{}`.", op.name.camel_case),
+                fields => fields,
+            }
+        })
+        .collect()
+}
+
+fn render_types(
+    ir: &IrSpec,
+    schemas: Vec<minijinja::Value>,
+    sse_event_types: &[SseEventType],
+    patch_formats: &[PatchFormat],
+    imports: &[(String, Vec<String>)],
+    property_naming: PropertyNaming,
+    retry_config: bool,
+) -> String {
+    let codecs = Codecs::new(ir, property_naming);
     let mut env = Environment::new();
     env.set_trim_blocks(true);
-    env.add_filter("escape_jsdoc", escape_jsdoc);
+    env.add_filter("jsdoc", super::jsdoc_filter);
     env.add_template("types.ts.j2", include_str!("../../templates/types.ts.j2"))
         .expect("template should be valid");
     let tmpl = env.get_template("types.ts.j2").unwrap();
 
-    let schemas: Vec<_> = ir.schemas.iter().map(schema_to_ctx).collect();
-    let schema_names: HashSet<String> = ir
-        .schemas
+    let sse_event_types: Vec<minijinja::Value> = sse_event_types
         .iter()
-        .map(|s| match s {
-            IrSchema::Object(o) => o.name.pascal_case.clone(),
-            IrSchema::Enum(e) => e.name.pascal_case.clone(),
-            IrSchema::Alias(a) => a.name.pascal_case.clone(),
-            IrSchema::Union(u) => u.name.pascal_case.clone(),
+        .map(|event| {
+            // Handlers receive typed events, tagged under the typed name.
+            let discriminator = event_discriminator(ir, event.variants).map(|(property, tags)| {
+                (codecs.typed_property(tags[0].1, property), tags)
+            });
+            let handlers: Option<Vec<minijinja::Value>> =
+                discriminator.as_ref().map(|(_, tags)| {
+                    tags.iter()
+                        .map(|(tag, variant)| {
+                            context! {
+                                key => ts_property_key(tag),
+                                access => member_access(tag),
+                                tag => serde_json::to_string(tag).expect("strings always serialize"),
+                                type => ir_type_to_ts(variant),
+                            }
+                        })
+                        .collect()
+                });
+            let tag_access = discriminator
+                .as_ref()
+                .map(|(property, _)| member_access(property));
+            context! {
+                name => event.name,
+                variants => event.variants.iter().map(ir_type_to_ts).collect::<Vec<_>>(),
+                tag_property => discriminator.map(|(property, _)| property),
+                tag_access => tag_access,
+                handlers => handlers,
+            }
         })
         .collect();
-    let sse_event_types = collect_sse_event_types(ir, &schema_names);
+    let imports: Vec<minijinja::Value> = imports
+        .iter()
+        .map(|(module, names)| context! { module => module, names => names })
+        .collect();
 
     tmpl.render(context! {
+        imports => imports,
         schemas => schemas,
         sse_event_types => sse_event_types,
+        merge_patch => patch_formats.contains(&PatchFormat::MergePatch),
+        json_patch => patch_formats.contains(&PatchFormat::JsonPatch),
+        retry_config => retry_config,
     })
     .expect("render should succeed")
 }
 
-fn schema_to_ctx(schema: &IrSchema) -> minijinja::Value {
+fn schema_to_ctx(schema: &IrSchema, property_naming: PropertyNaming) -> minijinja::Value {
     match schema {
-        IrSchema::Object(obj) => object_to_ctx(obj),
+        IrSchema::Object(obj) => object_to_ctx(obj, property_naming),
         IrSchema::Enum(e) => {
             let variants: Vec<String> = e.variants.iter().map(|v| format!("\"{v}\"")).collect();
             context! {
@@ -55,7 +201,7 @@ fn schema_to_ctx(schema: &IrSchema) -> minijinja::Value {
             context! {
                 kind => "alias",
                 name => a.name.pascal_case.clone(),
-                description => a.description.clone(),
+                description => describe(&a.description, &a.target),
                 target => ir_type_to_ts(&a.target),
             }
         }
@@ -71,17 +217,22 @@ fn schema_to_ctx(schema: &IrSchema) -> minijinja::Value {
     }
 }
 
-fn object_to_ctx(obj: &IrObjectSchema) -> minijinja::Value {
+fn object_to_ctx(obj: &IrObjectSchema, property_naming: PropertyNaming) -> minijinja::Value {
     let fields: Vec<minijinja::Value> = obj
         .fields
         .iter()
-        .map(|f| {
+        .zip(property_names(obj, property_naming))
+        .map(|(f, name)| {
             context! {
-                name => f.name.camel_case.clone(),
-                original_name => f.original_name.clone(),
+                key => ts_property_key(&name),
                 type => ir_type_to_ts(&f.field_type),
                 required => f.required,
-                description => f.description.clone(),
+                description => with_tags(
+                    describe(&f.description, &f.field_type),
+                    &f.number_hints.tags(),
+                ),
+                deprecated => f.deprecated,
+                write_only => f.write_only,
             }
         })
         .collect();
@@ -97,25 +248,337 @@ fn object_to_ctx(obj: &IrObjectSchema) -> minijinja::Value {
     }
 }
 
-fn collect_sse_event_types(ir: &IrSpec, schema_names: &HashSet<String>) -> Vec<minijinja::Value> {
+/// A schema or field description, followed by any key constraint the TypeScript
+/// type drops.
+fn describe(description: &Option<String>, ir_type: &IrType) -> Option<String> {
+    match (description, map_key_note(ir_type)) {
+        (Some(desc), Some(note)) => Some(format!("{desc} {note}")),
+        (desc, note) => desc.clone().or(note),
+    }
+}
+
+/// A parameter's description followed by an `@example` tag with its spec
+/// example, if it has one.
+fn with_example(description: Option<String>, param: &IrParameter) -> Option<String> {
+    match &param.example {
+        Some(example) => with_tags(description, &[format!("@example {example}")]),
+        None => description,
+    }
+}
+
+/// A description followed by JSDoc `tags`, one per line, e.g. a numeric
+/// field's `@format int32` and `@minimum 0`, which its `number` type drops.
+fn with_tags(description: Option<String>, tags: &[String]) -> Option<String> {
+    if tags.is_empty() {
+        return description;
+    }
+    let tags = tags.join("\n");
+    Some(match description {
+        Some(description) => format!("{description}\n\n{tags}"),
+        None => tags,
+    })
+}
+
+/// `.name`, or `["name"]` when `name` isn't an identifier.
+pub(crate) fn member_access(name: &str) -> String {
+    let key = ts_property_key(name);
+    if key.starts_with('"') {
+        format!("[{key}]")
+    } else {
+        format!(".{key}")
+    }
+}
+
+/// The property each variant of an event union tags with its own string
+/// literal (e.g. `type: "message_start"`), with every variant's tag. `None`
+/// unless all variants are object schemas with distinct tags.
+pub(crate) fn event_discriminator<'a>(
+    ir: &'a IrSpec,
+    variants: &'a [IrType],
+) -> Option<(&'a str, Vec<(&'a str, &'a IrType)>)> {
+    let objects: Vec<&IrObjectSchema> = variants
+        .iter()
+        .map(|variant| match variant {
+            IrType::Ref(name) => ir.schemas.iter().find_map(|s| match s {
+                IrSchema::Object(obj) if obj.name.pascal_case == *name => Some(obj),
+                _ => None,
+            }),
+            _ => None,
+        })
+        .collect::<Option<_>>()?;
+    let tag_of = |obj: &'a IrObjectSchema, property: &str| {
+        obj.fields.iter().find_map(|f| match &f.field_type {
+            IrType::StringLiteral(tag) if f.required && f.original_name == property => {
+                Some(tag.as_str())
+            }
+            _ => None,
+        })
+    };
+
+    objects.first()?.fields.iter().find_map(|candidate| {
+        let property = candidate.original_name.as_str();
+        let tags: Vec<&str> = objects
+            .iter()
+            .map(|obj| tag_of(obj, property))
+            .collect::<Option<_>>()?;
+        // `_` is the fallback handler's key.
+        let distinct: HashSet<&str> = tags.iter().copied().collect();
+        if distinct.len() != tags.len() || distinct.contains("_") {
+            return None;
+        }
+        Some((property, tags.into_iter().zip(variants).collect()))
+    })
+}
+
+/// The union type of an SSE operation's event variants, e.g. `ChatEvent`.
+#[derive(Debug, Clone, Copy)]
+pub struct SseEventType<'a> {
+    pub name: &'a str,
+    pub variants: &'a [IrType],
+}
+
+/// SSE event union types declared by `operations`, once per name. Names already
+/// taken by a schema are skipped.
+pub fn sse_event_types<'a>(
+    ir: &IrSpec,
+    operations: impl IntoIterator<Item = &'a IrOperation>,
+) -> Vec<SseEventType<'a>> {
+    let schema_names: HashSet<&str> = ir
+        .schemas
+        .iter()
+        .map(|s| s.name().pascal_case.as_str())
+        .collect();
     let mut event_types = Vec::new();
     let mut seen = HashSet::new();
-    for op in &ir.operations {
+    for op in operations {
         if let IrReturnType::Sse(sse) = &op.return_type
             && let Some(ref event_name) = sse.event_type_name
+            && !sse.variants.is_empty()
+            && !schema_names.contains(event_name.as_str())
+            && seen.insert(event_name.as_str())
         {
-            if seen.contains(event_name) || schema_names.contains(event_name) {
-                continue;
-            }
-            let variants: Vec<String> = sse.variants.iter().map(ir_type_to_ts).collect();
-            if !variants.is_empty() {
-                seen.insert(event_name.clone());
-                event_types.push(context! {
-                    name => event_name.clone(),
-                    variants => variants,
-                });
-            }
+            event_types.push(SseEventType {
+                name: event_name,
+                variants: &sse.variants,
+            });
         }
     }
     event_types
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ir(yaml: &str) -> IrSpec {
+        let spec = oag_core::parse::from_yaml(yaml).unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn tagged_event_unions_get_exhaustive_handlers() {
+        let types = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/anthropic-messages.yaml"
+            )),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains("export interface CreateMessageStreamEventHandlerMap<R = void> {"));
+        assert!(types.contains("  message_start: (event: MessageStartEvent) => R;\n"));
+        assert!(types.contains(
+            "  | (Partial<CreateMessageStreamEventHandlerMap<R>> & { _: (event: CreateMessageStreamEvent) => R });"
+        ));
+        assert!(types.contains("  switch (event.type) {\n    case \"message_start\":\n"));
+        assert!(
+            types
+                .contains("      return handlers.ping ? handlers.ping(event) : fallback(event);\n")
+        );
+    }
+
+    #[test]
+    fn camel_property_naming_renames_interface_properties() {
+        let yaml = include_str!("../../../oag-core/tests/fixtures/snake-case.yaml");
+        let types = emit_types(
+            &ir(yaml),
+            ParamsStyle::Positional,
+            PropertyNaming::Camel,
+            false,
+        );
+        assert!(types.contains("  petId: string;\n  displayName: string;\n"));
+        assert!(types.contains("  extraInfo?: Record<string, Tag>;\n"));
+        // `first_name` would clash with `firstName`, so both keep their names.
+        assert!(types.contains("  first_name: string;\n"));
+        assert!(types.contains("  firstName?: string;\n  lastName?: string;\n"));
+
+        let types = emit_types(
+            &ir(yaml),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains("  pet_id: string;\n  display_name: string;\n"));
+    }
+
+    #[test]
+    fn params_fields_show_their_examples() {
+        let types = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/parameter-examples.yaml"
+            )),
+            ParamsStyle::Object,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains("  /** @example \"name,status\" */\n  fields: string;\n"));
+    }
+
+    #[test]
+    fn enums_keep_empty_and_case_only_variants_once() {
+        let types = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/pathological-enums.yaml"
+            )),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(
+            types.contains("export type Mode = \"a\" | \"A\" | \"\" | \"none\" | \"1\" | \"-\";")
+        );
+        assert!(types.contains("kind?: \"on\" | \"off\";"));
+    }
+
+    #[test]
+    fn untagged_event_unions_only_take_a_fallback() {
+        let types = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/sse-chat.yaml"
+            )),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains("export function handleCreateChatCompletionStreamEvent<R = void>("));
+        assert!(types.contains("  _: (event: CreateChatCompletionStreamEvent) => R;\n"));
+        assert!(!types.contains("HandlerMap"));
+    }
+
+    #[test]
+    fn nullable_unions_are_aliases() {
+        let types = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/nullable-unions.yaml"
+            )),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains("export type NullablePet = Pet | null;"));
+        assert!(types.contains("  pet: NullablePet;"));
+        assert!(types.contains("  previousPet?: Pet | null;"));
+        assert!(types.contains("  tags?: string[] | null;"));
+    }
+
+    #[test]
+    fn params_interfaces_only_in_object_style() {
+        let ir = ir(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ));
+        let types = emit_types(&ir, ParamsStyle::Object, PropertyNaming::Original, false);
+        assert!(types.contains(
+            "/** Query and header parameters of `listThings`. */\nexport interface ListThingsParams {\n  limit?: number;\n"
+        ));
+        assert!(types.contains("  /** Only things in this state */\n  status?: ThingStatus;\n"));
+        assert!(types.contains("  xRequestId?: string;\n"));
+        assert!(types.contains("export interface SearchThingsParams {\n  q: string;\n"));
+        assert!(!types.contains("DeleteThingParams"));
+
+        assert!(
+            !emit_types(
+                &ir,
+                ParamsStyle::Positional,
+                PropertyNaming::Original,
+                false
+            )
+            .contains("Params")
+        );
+    }
+
+    #[test]
+    fn deprecated_fields_are_tagged() {
+        let types = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/deprecated-fields.yaml"
+            )),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains(
+            "  /** The numeric id from before the migration. */\n  /** @deprecated */\n  legacyId?: number;\n"
+        ));
+        assert!(types.contains("  /** @deprecated */\n  nickname?: string;\n"));
+        assert!(types.contains("  id: string;\n  /** The numeric"));
+        assert_eq!(types.matches("@deprecated").count(), 2);
+    }
+
+    #[test]
+    fn write_only_fields_are_annotated() {
+        let types = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/write-only.yaml"
+            )),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains(
+            "  /** At least 12 characters. */\n  /** Write-only: sent in requests, never returned in responses. */\n  password: string;\n"
+        ));
+        assert_eq!(types.matches("Write-only").count(), 2);
+    }
+
+    #[test]
+    fn patch_body_types_are_emitted_when_used() {
+        let patch = ir(include_str!(
+            "../../../oag-core/tests/fixtures/patch-bodies.yaml"
+        ));
+        let types = emit_types(
+            &patch,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(types.contains("export type DeepPartial<T> = T extends readonly unknown[]\n"));
+        assert!(types.contains("  | { op: \"remove\"; path: string }\n"));
+
+        let plain = emit_types(
+            &ir(include_str!(
+                "../../../oag-core/tests/fixtures/write-only.yaml"
+            )),
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(!plain.contains("DeepPartial"));
+        assert!(!plain.contains("JsonPatchOp"));
+    }
+
+    #[test]
+    fn retry_config_is_declared_on_request() {
+        let ir = ir(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ));
+        let types = emit_types(
+            &ir,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            false,
+        );
+        assert!(!types.contains("RetryConfig"));
+        let types = emit_types(&ir, ParamsStyle::Positional, PropertyNaming::Original, true);
+        assert!(types.contains("export interface RetryConfig {"));
+    }
+}
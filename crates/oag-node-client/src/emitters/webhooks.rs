@@ -0,0 +1,88 @@
+use minijinja::{Environment, context};
+use oag_core::ir::{IrReturnType, IrSpec};
+
+use crate::emitters::client::collect_imported_types;
+use crate::type_mapper::ir_type_to_ts;
+
+/// Emit `webhooks.ts` — handler types for the spec's `webhooks` section.
+///
+/// `types_module` is the module the payload types are imported from
+/// (`./types`, or `./index` in the bundled layout).
+pub fn emit_webhooks(ir: &IrSpec, types_module: &str) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_filter("jsdoc", super::jsdoc_filter);
+    env.add_template(
+        "webhooks.ts.j2",
+        include_str!("../../templates/webhooks.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("webhooks.ts.j2").unwrap();
+
+    let webhooks: Vec<minijinja::Value> = ir
+        .webhooks
+        .iter()
+        .map(|op| {
+            let return_type = match &op.return_type {
+                IrReturnType::Standard(resp) => ir_type_to_ts(&resp.response_type),
+                IrReturnType::Sse(_) | IrReturnType::Void => "void".to_string(),
+            };
+            context! {
+                key => property_key(&op.raw_path),
+                handler_name => format!("{}Handler", op.name.pascal_case),
+                payload_type => op.request_body.as_ref().map(|b| ir_type_to_ts(&b.body_type)),
+                return_type => return_type,
+                doc => op.doc(),
+            }
+        })
+        .collect();
+
+    tmpl.render(context! {
+        webhooks => webhooks,
+        imported_types => collect_imported_types(ir.webhooks.iter()),
+        types_module => types_module,
+    })
+    .expect("render should succeed")
+}
+
+/// Quote a webhook name unless it is a valid TypeScript identifier.
+fn property_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_ident = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_ident {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "\\\""))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_property_key() {
+        assert_eq!(property_key("itemCreated"), "itemCreated");
+        assert_eq!(property_key("item.created"), "\"item.created\"");
+        assert_eq!(property_key("1st"), "\"1st\"");
+    }
+
+    #[test]
+    fn test_emit_webhooks() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/mixed-endpoints.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_webhooks(&ir, "./types");
+        assert!(content.contains("} from \"./types\";"));
+        assert!(content.contains(
+            "export type OnItemCreatedHandler = (payload: Item) => void | Promise<void>;"
+        ));
+        assert!(content.contains("=> OnItemDeletedResponse | Promise<OnItemDeletedResponse>;"));
+        assert!(content.contains("  itemDeleted?: OnItemDeletedHandler;"));
+    }
+}
@@ -1,14 +1,47 @@
-use oag_core::config::{GeneratorConfig, GeneratorId, OutputLayout, SplitBy, ToolSetting};
+use oag_core::config::{
+    BaseUrl, ConfigWarning, GeneratorConfig, GeneratorId, OutputLayout, SplitBy, ToolSetting,
+};
 use oag_core::ir::IrSpec;
-use oag_core::{CodeGenerator, GeneratedFile, GeneratorError, normalize_generated};
+use oag_core::test_report::REPORT_FILE;
+use oag_core::{CodeGenerator, GeneratedFile, GeneratorError};
 
 use crate::emitters;
-use crate::emitters::scaffold::{NodeScaffoldConfig, ScaffoldOptions};
+use crate::emitters::scaffold::{NodeScaffoldConfig, ScaffoldOptions, default_client_class_name};
 use crate::emitters::source_path;
+use crate::emitters::streaming::HelperModules;
 
 /// TypeScript/Node code generator.
 pub struct NodeClientGenerator;
 
+/// The options node-client reads, besides `output`, `files` and `naming`.
+const SUPPORTED_OPTIONS: &[&str] = &[
+    "layout",
+    "split_by",
+    "split_types",
+    "base_url",
+    "environments",
+    "no_jsdoc",
+    "sse_transport",
+    "stream_accumulator",
+    "auth",
+    "route_table",
+    "streaming_helpers",
+    "batch_helpers",
+    "debug_logging",
+    "retry",
+    "minimal_runtime",
+    "next_handlers",
+    "next_handlers_dir",
+    "examples",
+    "contract_tests",
+    "test_report",
+    "pinned_parameters",
+    "params_style",
+    "property_naming",
+    "source_dir",
+    "scaffold",
+];
+
 impl NodeClientGenerator {
     /// Build scaffold options from a GeneratorConfig.
     pub fn build_scaffold_options(
@@ -18,19 +51,62 @@ impl NodeClientGenerator {
     ) -> Option<ScaffoldOptions> {
         let raw = config.scaffold.as_ref()?;
         let scaffold: NodeScaffoldConfig = serde_json::from_value(raw.clone()).ok()?;
+        let existing_repo = scaffold.existing_repo.unwrap_or(false);
         Some(ScaffoldOptions {
             name: ir.info.title.clone(),
             package_name: scaffold.package_name,
             repository: scaffold.repository,
             formatter: ToolSetting::resolve(scaffold.formatter.as_ref(), "biome").map(String::from),
-            test_runner: ToolSetting::resolve(scaffold.test_runner.as_ref(), "vitest")
-                .map(String::from),
+            // An existing project gets tests only for the runner it declares.
+            test_runner: match (&scaffold.test_runner, existing_repo) {
+                (None, true) => None,
+                (setting, _) => ToolSetting::resolve(setting.as_ref(), "vitest").map(String::from),
+            },
             bundler: ToolSetting::resolve(scaffold.bundler.as_ref(), "tsdown").map(String::from),
             react,
-            existing_repo: scaffold.existing_repo.unwrap_or(false),
+            existing_repo,
+            gitignore: scaffold.gitignore.unwrap_or(!existing_repo),
             source_dir: config.source_dir.clone(),
+            client_class_name: scaffold
+                .client_class_name
+                .unwrap_or_else(|| default_client_class_name(&ir.info.title)),
+            swr_prefetch: scaffold.swr_prefetch.unwrap_or(false),
+            tsconfig_preset: scaffold.tsconfig_preset.unwrap_or_default(),
+            sse_runtime: scaffold.sse_runtime.unwrap_or_default(),
+            version: scaffold.version.map_or_else(
+                || "0.1.0".to_string(),
+                |version| version.semver(&ir.info.version),
+            ),
         })
     }
+
+    /// The source files of types-only mode: `types.ts` and an `index.ts`
+    /// re-exporting it, or the types alone in `index.ts` for bundled layout.
+    pub fn types_only_files(ir: &IrSpec, config: &GeneratorConfig) -> Vec<GeneratedFile> {
+        let sd = &config.source_dir;
+        let types =
+            emitters::types::emit_types(ir, config.params_style, config.property_naming, false);
+        match config.layout {
+            OutputLayout::Bundled => vec![GeneratedFile {
+                path: source_path(sd, "index.ts"),
+                content: types,
+            }],
+            // Modular and split, and any layout added later
+            _ => {
+                let mut files = vec![GeneratedFile {
+                    path: source_path(sd, "types.ts"),
+                    content: types,
+                }];
+                let index =
+                    emitters::index::emit_index(&files, sd, &emitters::index::client_modules());
+                files.push(GeneratedFile {
+                    path: source_path(sd, "index.ts"),
+                    content: index,
+                });
+                files
+            }
+        }
+    }
 }
 
 impl CodeGenerator for NodeClientGenerator {
@@ -43,43 +119,267 @@ impl CodeGenerator for NodeClientGenerator {
         ir: &IrSpec,
         config: &GeneratorConfig,
     ) -> Result<Vec<GeneratedFile>, GeneratorError> {
-        let no_jsdoc = config.no_jsdoc.unwrap_or(false);
+        // `source_dir` also ends up in tsconfig.json and tsdown.config.ts.
+        oag_core::check_relative_path(&config.source_dir)?;
+        let ir = &*ir.with_pinned_parameters(&config.pinned_parameters);
+        let minimal_runtime = config.minimal_runtime;
+        let no_jsdoc = config.no_jsdoc.unwrap_or(false) || minimal_runtime;
+        let sse_transport = config.sse_transport.unwrap_or_default();
+        let params_style = config.params_style;
+        let property_naming = config.property_naming;
+        let base_url = BaseUrl::resolve(config.base_url.as_ref(), ir.default_base_url());
         let sd = &config.source_dir;
         let scaffold_options = Self::build_scaffold_options(ir, config, false);
+        let sse_runtime = scaffold_options
+            .as_ref()
+            .map(|s| s.sse_runtime.clone())
+            .unwrap_or_default();
+
+        if config.types_only {
+            let mut files = Self::types_only_files(ir, config);
+            if let Some(ref scaffold) = scaffold_options {
+                files.extend(emitters::scaffold::emit_scaffold(scaffold));
+            }
+            return Ok(files);
+        }
 
         let mut files = match config.layout {
             OutputLayout::Bundled => {
-                let content = emitters::bundled::emit_bundled(ir, no_jsdoc);
+                let content = emitters::bundled::emit_bundled(
+                    ir,
+                    no_jsdoc,
+                    sse_transport,
+                    base_url,
+                    params_style,
+                    property_naming,
+                    sse_runtime.package(),
+                    config.debug_logging,
+                    minimal_runtime,
+                    config.retry,
+                );
                 vec![GeneratedFile {
                     path: source_path(sd, "index.ts"),
                     content,
                 }]
             }
             OutputLayout::Modular => {
-                vec![
-                    GeneratedFile {
-                        path: source_path(sd, "types.ts"),
-                        content: emitters::types::emit_types(ir),
-                    },
-                    GeneratedFile {
+                let mut files = vec![GeneratedFile {
+                    path: source_path(sd, "types.ts"),
+                    content: emitters::types::emit_types(
+                        ir,
+                        params_style,
+                        property_naming,
+                        emitters::types::needs_retry_config(ir, config.retry, minimal_runtime),
+                    ),
+                }];
+                if emitters::sse::needs_runtime(ir, minimal_runtime) {
+                    files.push(GeneratedFile {
                         path: source_path(sd, "sse.ts"),
-                        content: emitters::sse::emit_sse(),
-                    },
-                    GeneratedFile {
-                        path: source_path(sd, "client.ts"),
-                        content: emitters::client::emit_client(ir, no_jsdoc),
-                    },
-                    GeneratedFile {
-                        path: source_path(sd, "index.ts"),
-                        content: emitters::index::emit_index(),
-                    },
-                ]
+                        content: emitters::sse::emit_sse(sse_transport),
+                    });
+                }
+                files.push(GeneratedFile {
+                    path: source_path(sd, "client.ts"),
+                    content: emitters::client::emit_client(
+                        ir,
+                        no_jsdoc,
+                        base_url,
+                        params_style,
+                        property_naming,
+                        config.debug_logging,
+                        minimal_runtime,
+                        config.retry,
+                    ),
+                });
+                files
             }
             OutputLayout::Split => {
                 let split_by = config.split_by.unwrap_or(SplitBy::Tag);
-                emitters::split::emit_split(ir, no_jsdoc, split_by, sd)
+                emitters::split::emit_split(
+                    ir,
+                    no_jsdoc,
+                    split_by,
+                    sse_transport,
+                    sd,
+                    base_url,
+                    config.split_types,
+                    params_style,
+                    property_naming,
+                    config.debug_logging,
+                    minimal_runtime,
+                    config.retry,
+                )
+            }
+            layout => {
+                return Err(GeneratorError::Other(format!(
+                    "unsupported layout {layout:?}"
+                )));
+            }
+        };
+
+        emitters::auth::add_auth_module(
+            &mut files,
+            ir,
+            &config.auth,
+            sd,
+            "index.ts",
+            config.layout == OutputLayout::Bundled,
+        );
+        emitters::codecs::add_codecs_module(
+            &mut files,
+            ir,
+            property_naming,
+            sd,
+            "index.ts",
+            config.layout == OutputLayout::Bundled,
+        );
+        let environments = emitters::environments::environments(ir, &config.environments);
+        emitters::environments::add_environments_module(
+            &mut files,
+            &environments,
+            sd,
+            "index.ts",
+            config.layout == OutputLayout::Bundled,
+        );
+        if config.debug_logging {
+            emitters::logging::add_logging_module(
+                &mut files,
+                ir,
+                sd,
+                config.layout == OutputLayout::Bundled,
+            );
+        }
+
+        // Bundled output keeps its types in index.ts
+        let types_module = match config.layout {
+            OutputLayout::Bundled => "./index",
+            _ => "./types",
+        };
+
+        if !ir.webhooks.is_empty() {
+            files.push(GeneratedFile {
+                path: source_path(sd, "webhooks.ts"),
+                content: emitters::webhooks::emit_webhooks(ir, types_module),
+            });
+        }
+
+        if config.route_table {
+            files.push(GeneratedFile {
+                path: source_path(sd, "routes.ts"),
+                content: emitters::routes::emit_routes(ir, types_module),
+            });
+        }
+
+        if let Some(content) =
+            emitters::examples::emit_examples(ir, config.examples, types_module, property_naming)
+        {
+            files.push(GeneratedFile {
+                path: source_path(sd, "examples.ts"),
+                content,
+            });
+        }
+
+        let accumulator = config.stream_accumulator.as_ref();
+        if let Some(content) =
+            emitters::streaming::emit_streaming(ir, accumulator, types_module, property_naming)?
+        {
+            files.push(GeneratedFile {
+                path: source_path(sd, "streaming.ts"),
+                content,
+            });
+        }
+
+        let helper_modules = match config.layout {
+            OutputLayout::Bundled => HelperModules {
+                client: "./index",
+                sse: "./index",
+                types: "./index",
+            },
+            _ => HelperModules {
+                client: "./client",
+                sse: "./sse",
+                types: "./types",
+            },
+        };
+        let streaming_helpers = if config.streaming_helpers {
+            emitters::streaming::emit_streaming_helpers(ir, params_style, &helper_modules)?
+        } else {
+            None
+        };
+        if let Some(content) = streaming_helpers {
+            files.push(GeneratedFile {
+                path: source_path(sd, "streaming-helpers.ts"),
+                content,
+            });
+        }
+
+        let batch_helpers = if config.batch_helpers {
+            emitters::batch::emit_batch_helpers(ir, params_style, &helper_modules)?
+        } else {
+            None
+        };
+        if let Some(content) = batch_helpers {
+            files.push(GeneratedFile {
+                path: source_path(sd, "batch.ts"),
+                content,
+            });
+        }
+
+        if config.next_handlers {
+            oag_core::check_relative_path(&config.next_handlers_dir)?;
+            files.extend(emitters::next::emit_next_handlers(
+                ir,
+                params_style,
+                &helper_modules,
+                &config.next_handlers_dir,
+                sd,
+                base_url,
+            )?);
+        }
+
+        // The barrel goes last, re-exporting the modules actually generated. A
+        // bundle declares the client's own modules itself.
+        let client_modules = if minimal_runtime {
+            emitters::index::minimal_client_modules()
+        } else {
+            emitters::index::client_modules()
+        };
+        let mut modules = match config.layout {
+            OutputLayout::Bundled => Vec::new(),
+            OutputLayout::Split => {
+                let split_by = config.split_by.unwrap_or(SplitBy::Tag);
+                let mut modules = client_modules;
+                modules.extend(emitters::split::group_modules(ir, split_by));
+                modules
             }
+            _ => client_modules,
         };
+        modules.extend(emitters::index::feature_modules(ir, property_naming));
+        let index_path = source_path(sd, "index.ts");
+        if config.layout == OutputLayout::Bundled {
+            let exports = emitters::index::reexports(&files, sd, &modules);
+            if let Some(index) = files.iter_mut().find(|f| f.path == index_path)
+                && !exports.is_empty()
+            {
+                if !index.content.ends_with('\n') {
+                    index.content.push('\n');
+                }
+                index.content.push_str(&exports);
+            }
+        } else {
+            let content = emitters::index::emit_index(&files, sd, &modules);
+            files.push(GeneratedFile {
+                path: index_path,
+                content,
+            });
+        }
+
+        // Templates write their docs regardless of `no_jsdoc`.
+        if minimal_runtime {
+            for file in &mut files {
+                file.content = emitters::strip_jsdoc(&file.content);
+            }
+        }
 
         if let Some(ref scaffold) = scaffold_options {
             files.extend(emitters::scaffold::emit_scaffold(scaffold));
@@ -87,14 +387,664 @@ impl CodeGenerator for NodeClientGenerator {
             if scaffold.test_runner.is_some() {
                 files.push(GeneratedFile {
                     path: source_path(sd, "client.test.ts"),
-                    content: emitters::tests::emit_client_tests(ir),
+                    content: emitters::tests::emit_client_tests(
+                        ir,
+                        params_style,
+                        property_naming,
+                        config.retry,
+                    ),
                 });
+                if config.test_report {
+                    let coverage =
+                        emitters::tests::client_test_coverage(ir, params_style, property_naming);
+                    files.push(GeneratedFile::new(
+                        source_path(sd, REPORT_FILE),
+                        coverage.to_json(),
+                    ));
+                }
+                if let Some(content) =
+                    emitters::streaming::emit_streaming_tests(ir, accumulator, property_naming)?
+                {
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "streaming.test.ts"),
+                        content,
+                    });
+                }
+                if config.streaming_helpers
+                    && let Some(content) = emitters::streaming::emit_streaming_helper_tests(
+                        ir,
+                        params_style,
+                        &helper_modules,
+                    )?
+                {
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "streaming-helpers.test.ts"),
+                        content,
+                    });
+                }
+                if config.batch_helpers
+                    && let Some(content) =
+                        emitters::batch::emit_batch_tests(ir, params_style, &helper_modules)?
+                {
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "batch.test.ts"),
+                        content,
+                    });
+                }
+                if config.debug_logging {
+                    let module = match config.layout {
+                        OutputLayout::Bundled => "./index",
+                        _ => "./logging",
+                    };
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "logging.test.ts"),
+                        content: emitters::logging::emit_logging_tests(ir, module),
+                    });
+                }
+                if emitters::auth::has_bearer_auth(ir) {
+                    let module = match config.layout {
+                        OutputLayout::Bundled => "./index",
+                        _ => "./auth",
+                    };
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "auth.test.ts"),
+                        content: emitters::auth::emit_auth_tests(ir, &config.auth, module),
+                    });
+                }
+                let module = match config.layout {
+                    OutputLayout::Bundled => "./index",
+                    _ => "./environments",
+                };
+                if let Some(content) = emitters::environments::emit_environments_tests(
+                    &environments,
+                    module,
+                    helper_modules.client,
+                ) {
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "environments.test.ts"),
+                        content,
+                    });
+                }
             }
         }
 
-        for file in &mut files {
-            file.content = normalize_generated(&file.content);
+        if config.contract_tests {
+            files.extend(emitters::contract::emit_contract_tests(
+                ir,
+                params_style,
+                property_naming,
+                sd,
+            ));
         }
+
+        if let Some(package) = sse_runtime.package() {
+            emitters::sse::use_sse_package(&mut files, sd, package);
+        }
+
         Ok(files)
     }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Vec<ConfigWarning> {
+        let mut warnings = config.unsupported_options(self.id(), SUPPORTED_OPTIONS);
+        warnings.extend(config.split_option_warnings());
+        match config.parse_scaffold::<NodeScaffoldConfig>() {
+            Ok(Some(scaffold)) => {
+                if scaffold.swr_prefetch.is_some() {
+                    warnings.push(ConfigWarning::new(
+                        "scaffold.swr_prefetch",
+                        "only applies to react-swr-client; ignored",
+                    ));
+                }
+                let sse_package = scaffold.sse_runtime.as_ref().and_then(|r| r.package());
+                if sse_package.is_some() && config.sse_transport.is_some() {
+                    warnings.push(ConfigWarning::new(
+                        "sse_transport",
+                        "the SSE runtime comes from the `sse_runtime` package; ignored",
+                    ));
+                }
+            }
+            Ok(None) => {}
+            Err(warning) => warnings.push(warning),
+        }
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oag_core::config::Streaming;
+
+    use super::*;
+
+    #[test]
+    fn validate_config_flags_react_options_and_scaffold_typos() {
+        let config = GeneratorConfig {
+            key_style: oag_core::config::KeyStyle::Path,
+            split_by: Some(SplitBy::Operation),
+            scaffold: Some(serde_json::json!({ "formater": "biome" })),
+            ..Default::default()
+        };
+        let warnings = NodeClientGenerator.validate_config(&config);
+        let options: Vec<&str> = warnings.iter().map(|w| w.option.as_str()).collect();
+        assert_eq!(options, ["key_style", "split_by", "scaffold"]);
+        assert!(warnings[2].message.contains("unknown field `formater`"));
+
+        assert!(
+            NodeClientGenerator
+                .validate_config(&GeneratorConfig::default())
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn bearer_auth_emits_auth_module() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/bearer-auth.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            auth: oag_core::config::AuthConfig {
+                refresh_before_expiry_s: Some(30),
+            },
+            scaffold: Some(serde_json::json!({ "test_runner": "vitest" })),
+            ..Default::default()
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+
+        let auth = &file("src/auth.ts").unwrap().content;
+        assert!(auth.contains("export const REFRESH_BEFORE_EXPIRY_S = 30;"));
+        assert!(
+            file("src/index.ts")
+                .unwrap()
+                .content
+                .contains("export * from \"./auth\";\n")
+        );
+        assert!(
+            file("src/auth.test.ts")
+                .unwrap()
+                .content
+                .contains("from \"./auth\";")
+        );
+
+        let bundled = GeneratorConfig {
+            layout: OutputLayout::Bundled,
+            ..config
+        };
+        let files = NodeClientGenerator.generate(&ir, &bundled).unwrap();
+        assert!(!files.iter().any(|f| f.path == "src/auth.ts"));
+        let index = &files
+            .iter()
+            .find(|f| f.path == "src/index.ts")
+            .unwrap()
+            .content;
+        let auth_at = index.find("// === Auth ===").unwrap();
+        assert!(auth_at < index.find("// === Client ===").unwrap());
+        assert!(index.contains("export class BearerAuth {"));
+        assert!(!index.contains("from \"./auth\""));
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let files = NodeClientGenerator
+            .generate(&ir, &GeneratorConfig::default())
+            .unwrap();
+        assert!(!files.iter().any(|f| f.path.contains("auth")));
+    }
+
+    #[test]
+    fn index_exports_the_optional_modules_generated() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let index = |config: &GeneratorConfig| {
+            let files = NodeClientGenerator.generate(&ir, config).unwrap();
+            files
+                .into_iter()
+                .find(|f| f.path == "src/index.ts")
+                .unwrap()
+                .content
+        };
+
+        let plain = index(&GeneratorConfig::default());
+        assert!(plain.contains("export * from \"./types\";\n"));
+        assert!(!plain.contains("./routes"));
+        assert!(!plain.contains("./logging"));
+
+        for layout in [OutputLayout::Modular, OutputLayout::Split] {
+            let config = GeneratorConfig {
+                layout,
+                route_table: true,
+                debug_logging: true,
+                ..Default::default()
+            };
+            let index = index(&config);
+            assert!(
+                index.contains("export * from \"./routes\";\n"),
+                "{layout:?}"
+            );
+            assert!(
+                index.contains("export * from \"./logging\";\n"),
+                "{layout:?}"
+            );
+            assert!(
+                index.contains("ApiError, type ApiErrorBodies"),
+                "{layout:?}"
+            );
+        }
+
+        // A bundle inlines logging, but routes.ts stays beside it.
+        let bundled = index(&GeneratorConfig {
+            layout: OutputLayout::Bundled,
+            route_table: true,
+            debug_logging: true,
+            ..Default::default()
+        });
+        assert!(bundled.contains("// === Logging ==="));
+        assert!(!bundled.contains("./logging"));
+        assert!(bundled.ends_with("export * from \"./routes\";\n"));
+    }
+
+    #[test]
+    fn streaming_helpers_are_opt_in() {
+        let spec =
+            oag_core::parse::from_yaml(include_str!("../../oag-core/tests/fixtures/sse-chat.yaml"))
+                .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            scaffold: Some(serde_json::json!({ "test_runner": "vitest" })),
+            ..Default::default()
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        assert!(!files.iter().any(|f| f.path.contains("streaming-helpers")));
+
+        let config = GeneratorConfig {
+            streaming_helpers: true,
+            ..config
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(
+            file("src/streaming-helpers.ts")
+                .unwrap()
+                .content
+                .contains("export function createChatCompletionStreamToWritable(")
+        );
+        assert!(
+            file("src/index.ts")
+                .unwrap()
+                .content
+                .contains("export * from \"./streaming-helpers\";\n")
+        );
+        assert!(
+            file("src/streaming-helpers.test.ts")
+                .unwrap()
+                .content
+                .contains("describe(\"createChatCompletionStreamToWritable\", () => {")
+        );
+
+        let packaged = GeneratorConfig {
+            scaffold: Some(serde_json::json!({ "sse_runtime": "package:@org/oag-sse" })),
+            ..config
+        };
+        let files = NodeClientGenerator.generate(&ir, &packaged).unwrap();
+        let helpers = &files
+            .iter()
+            .find(|f| f.path == "src/streaming-helpers.ts")
+            .unwrap()
+            .content;
+        assert!(helpers.contains("import type { SSEOptions } from \"@org/oag-sse\";"));
+    }
+
+    #[test]
+    fn batch_helpers_are_opt_in() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/batch-endpoints.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            scaffold: Some(serde_json::json!({ "test_runner": "vitest" })),
+            ..Default::default()
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        assert!(!files.iter().any(|f| f.path.contains("batch")));
+
+        let config = GeneratorConfig {
+            batch_helpers: true,
+            ..config
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(
+            file("src/batch.ts")
+                .unwrap()
+                .content
+                .contains("export function createPetsBatched(")
+        );
+        assert!(
+            file("src/index.ts")
+                .unwrap()
+                .content
+                .contains("export * from \"./batch\";\n")
+        );
+        assert!(
+            file("src/batch.test.ts")
+                .unwrap()
+                .content
+                .contains("describe(\"createPetsBatched\", () => {")
+        );
+
+        let bundled = GeneratorConfig {
+            layout: OutputLayout::Bundled,
+            ..config
+        };
+        let files = NodeClientGenerator.generate(&ir, &bundled).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(file("src/batch.ts").unwrap().content.contains(
+            "import type { ApiClient, BatchSummary, NewPet, RequestOptions } from \"./index\";"
+        ));
+        assert!(
+            file("src/index.ts")
+                .unwrap()
+                .content
+                .ends_with("export * from \"./batch\";\n")
+        );
+    }
+
+    #[test]
+    fn next_handlers_are_opt_in_and_stay_in_their_directory() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let files = NodeClientGenerator
+            .generate(&ir, &GeneratorConfig::default())
+            .unwrap();
+        assert!(!files.iter().any(|f| f.path.contains("handlers")));
+
+        let config = GeneratorConfig {
+            next_handlers: true,
+            next_handlers_dir: "app/api".to_string(),
+            layout: OutputLayout::Bundled,
+            ..Default::default()
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let next: Vec<&str> = files
+            .iter()
+            .map(|f| f.path.as_str())
+            .filter(|path| !path.starts_with("src/"))
+            .collect();
+        assert_eq!(
+            next,
+            [
+                "app/api/handlers.ts",
+                "app/api/upstream.ts",
+                "app/api/[...oag]/route.ts"
+            ]
+        );
+        let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().content;
+        let handlers = file("app/api/handlers.ts");
+        assert!(
+            handlers.contains(
+                "import type { ApiClient, ApiResponse, NewPet } from \"../../src/index\";"
+            )
+        );
+        assert!(!file("src/index.ts").contains("handlers"));
+
+        let escaping = GeneratorConfig {
+            next_handlers_dir: "../app".to_string(),
+            ..config
+        };
+        assert!(NodeClientGenerator.generate(&ir, &escaping).is_err());
+    }
+
+    #[test]
+    fn environments_come_from_config_or_servers() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/environments.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            scaffold: Some(serde_json::json!({ "test_runner": "vitest" })),
+            ..Default::default()
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        let environments = &file("src/environments.ts").unwrap().content;
+        assert!(environments.contains(
+            "  local: \"http://localhost:8080\",\n  staging: \"https://staging.example.com/v1\",\n  server3: \"https://api.example.com/v1\",\n"
+        ));
+        assert!(
+            file("src/index.ts")
+                .unwrap()
+                .content
+                .contains("export * from \"./environments\";\n")
+        );
+        assert!(file("src/environments.test.ts").unwrap().content.contains(
+            "import { ENVIRONMENTS, clientForEnv, environmentConfig } from \"./environments\";"
+        ));
+
+        let config = GeneratorConfig {
+            layout: OutputLayout::Bundled,
+            ..config.with_environment("dev", "http://localhost:3000")
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(file("src/environments.ts").is_none());
+        let index = &file("src/index.ts").unwrap().content;
+        assert!(index.contains("// === Environments ===\n\n/** The API's base URL in each environment, by name. */\nexport const ENVIRONMENTS = {\n  dev: \"http://localhost:3000\",\n} as const;"));
+        assert!(!index.contains("staging"));
+        assert!(file("src/environments.test.ts").unwrap().content.contains(
+            "import { ApiClient, ENVIRONMENTS, clientForEnv, environmentConfig } from \"./index\";"
+        ));
+    }
+
+    #[test]
+    fn sse_runtime_package_replaces_inline_runtime() {
+        let spec =
+            oag_core::parse::from_yaml(include_str!("../../oag-core/tests/fixtures/sse-chat.yaml"))
+                .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            scaffold: Some(serde_json::json!({ "sse_runtime": "package:@org/oag-sse@^1.2.0" })),
+            ..Default::default()
+        };
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+
+        assert!(file("src/sse.ts").is_none());
+        let client = &file("src/client.ts").unwrap().content;
+        assert!(client.contains("from \"@org/oag-sse\";"));
+        assert!(!client.contains("from \"./sse\""));
+        assert!(
+            file("src/index.ts")
+                .unwrap()
+                .content
+                .contains("from \"@org/oag-sse\";")
+        );
+        assert!(
+            file("package.json")
+                .unwrap()
+                .content
+                .contains("\"@org/oag-sse\": \"^1.2.0\"")
+        );
+        assert!(
+            file("sse-runtime.d.ts")
+                .unwrap()
+                .content
+                .contains("export declare function streamSse<T>(")
+        );
+
+        let bundled = GeneratorConfig {
+            layout: OutputLayout::Bundled,
+            ..config.clone()
+        };
+        let files = NodeClientGenerator.generate(&ir, &bundled).unwrap();
+        let index = &files
+            .iter()
+            .find(|f| f.path == "src/index.ts")
+            .unwrap()
+            .content;
+        assert!(!index.contains("export async function* streamSse"));
+        assert!(index.contains("streamSse } from \"@org/oag-sse\";"));
+
+        let warnings = NodeClientGenerator.validate_config(&GeneratorConfig {
+            sse_transport: Some(oag_core::config::SseTransport::Fetch),
+            ..config
+        });
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].option, "sse_transport");
+    }
+
+    #[test]
+    fn scaffold_version_sets_the_package_version() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/anthropic-messages.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let version = |scaffold: serde_json::Value| {
+            let config = GeneratorConfig {
+                scaffold: Some(scaffold),
+                ..Default::default()
+            };
+            let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+            let package = &files.iter().find(|f| f.path == "package.json").unwrap();
+            let package: serde_json::Value = serde_json::from_str(&package.content).unwrap();
+            package["version"].as_str().unwrap().to_string()
+        };
+        assert_eq!(version(serde_json::json!({})), "0.1.0");
+        // `info.version: "2024-01-01"` is a calendar date.
+        assert_eq!(
+            version(serde_json::json!({ "version": "spec" })),
+            "2024.1.1"
+        );
+        assert_eq!(version(serde_json::json!({ "version": "v3.1" })), "3.1.0");
+        assert_eq!(version(serde_json::json!({ "version": "latest" })), "0.0.0");
+    }
+
+    #[test]
+    fn existing_repo_skips_project_files_and_undeclared_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let paths = |scaffold: serde_json::Value| {
+            let config = GeneratorConfig {
+                scaffold: Some(scaffold),
+                ..Default::default()
+            };
+            let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+            files.into_iter().map(|f| f.path).collect::<Vec<_>>()
+        };
+
+        let files = paths(serde_json::json!({ "existing_repo": true }));
+        for path in [
+            "package.json",
+            "tsconfig.json",
+            "biome.json",
+            "tsdown.config.ts",
+            ".gitignore",
+            "src/client.test.ts",
+        ] {
+            assert!(!files.iter().any(|f| f == path), "{path} was generated");
+        }
+        assert!(files.iter().any(|f| f == "index.ts"));
+        assert!(files.iter().any(|f| f == "DEPENDENCIES.md"));
+
+        // A declared test runner still gets its tests.
+        let files = paths(serde_json::json!({ "existing_repo": true, "test_runner": "vitest" }));
+        assert!(files.iter().any(|f| f == "src/client.test.ts"));
+        assert!(!files.iter().any(|f| f == "package.json"));
+    }
+
+    #[test]
+    fn minimal_runtime_leaves_out_unused_runtime() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig::default().with_minimal_runtime(true);
+        let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| &files.iter().find(|f| f.path == path).unwrap().content;
+
+        assert!(!files.iter().any(|f| f.path == "src/sse.ts"));
+        let client = file("src/client.ts");
+        assert!(!client.contains("from \"./sse\""));
+        assert!(!client.contains("sseTransport"));
+        assert!(!client.contains("buildFormData"));
+        assert!(!client.contains("/**"));
+        let index = file("src/index.ts");
+        assert!(!index.contains("./sse"));
+        assert!(!index.contains("joinUrl"));
+    }
+
+    #[test]
+    fn minimal_runtime_keeps_a_small_bundle_small() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/empty-success.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        assert_eq!(ir.operations.len(), 2);
+        let bundle = |minimal_runtime: bool| {
+            let config = GeneratorConfig::default()
+                .with_layout(OutputLayout::Bundled)
+                .with_minimal_runtime(minimal_runtime);
+            let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+            files
+                .into_iter()
+                .find(|f| f.path == "src/index.ts")
+                .unwrap()
+                .content
+        };
+        let minimal = bundle(true);
+        let full = bundle(false);
+        assert!(
+            minimal.len() < 16 * 1024,
+            "minimal bundle is {} bytes",
+            minimal.len()
+        );
+        assert!(minimal.len() < full.len());
+    }
+
+    #[test]
+    fn streaming_overrides_change_method_shapes() {
+        let spec =
+            oag_core::parse::from_yaml(include_str!("../../oag-core/tests/fixtures/sse-chat.yaml"))
+                .unwrap();
+        let options = oag_core::transform::TransformOptions::new()
+            .with_streaming("createChatCompletion", Streaming::Off)
+            .with_streaming("listModels", Streaming::Force);
+        let ir = oag_core::transform::transform_with_options(&spec, &options).unwrap();
+        let files = NodeClientGenerator
+            .generate(&ir, &GeneratorConfig::default())
+            .unwrap();
+        let client = &files
+            .iter()
+            .find(|f| f.path == "src/client.ts")
+            .unwrap()
+            .content;
+        assert!(
+            client.contains("async *listModels(options?: SSEOptions): AsyncGenerator<ModelList> {")
+        );
+        assert!(!client.contains("async listModels("));
+        assert!(client.contains(
+            "async createChatCompletion(body: ChatCompletionRequest, options?: RequestOptions): Promise<ChatCompletionResponse> {"
+        ));
+        // The stream method is the stream-only endpoint's, no longer the dual one's
+        assert!(client.contains(
+            "createChatCompletionStream: { name: \"createChatCompletionStream\", method: \"POST\", path: \"/chat/completions/stream\""
+        ));
+    }
 }
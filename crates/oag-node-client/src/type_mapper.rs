@@ -1,10 +1,10 @@
-use oag_core::ir::IrType;
+use oag_core::ir::{IrMapKey, IrRequestBody, IrResponse, IrType, PatchFormat};
 
 /// Map an `IrType` to its TypeScript type string representation.
 pub fn ir_type_to_ts(ir_type: &IrType) -> String {
     match ir_type {
         IrType::String => "string".to_string(),
-        IrType::StringLiteral(s) => format!("\"{s}\""),
+        IrType::StringLiteral(s) => serde_json::to_string(s).expect("strings always serialize"),
         IrType::Number => "number".to_string(),
         IrType::Integer => "number".to_string(),
         IrType::Boolean => "boolean".to_string(),
@@ -22,9 +22,12 @@ pub fn ir_type_to_ts(ir_type: &IrType) -> String {
                 format!("{inner_ts}[]")
             }
         }
-        IrType::Map(value_type) => {
+        IrType::Map(value_type, key) => {
             let value_ts = ir_type_to_ts(value_type);
-            format!("Record<string, {value_ts}>")
+            match key {
+                Some(IrMapKey::Integer) => format!("Record<`${{number}}`, {value_ts}>"),
+                _ => format!("Record<string, {value_ts}>"),
+            }
         }
         IrType::Object(fields) => {
             if fields.is_empty() {
@@ -33,11 +36,12 @@ pub fn ir_type_to_ts(ir_type: &IrType) -> String {
             let field_strs: Vec<String> = fields
                 .iter()
                 .map(|(name, ty, required)| {
+                    let key = ts_property_key(name);
                     let ts_type = ir_type_to_ts(ty);
                     if *required {
-                        format!("{name}: {ts_type}")
+                        format!("{key}: {ts_type}")
                     } else {
-                        format!("{name}?: {ts_type}")
+                        format!("{key}?: {ts_type}")
                     }
                 })
                 .collect();
@@ -51,6 +55,58 @@ pub fn ir_type_to_ts(ir_type: &IrType) -> String {
             let part_strs: Vec<String> = parts.iter().map(ir_type_to_ts).collect();
             part_strs.join(" & ")
         }
+        // Types added to the IR later stay usable, if loosely typed.
+        _ => "unknown".to_string(),
+    }
+}
+
+/// The TypeScript type a call resolves to for a response: its body type,
+/// widened with `undefined` when other success statuses (e.g. `204`) have no body.
+pub fn response_ts_type(resp: &IrResponse) -> String {
+    let ts = ir_type_to_ts(&resp.response_type);
+    if resp.empty_statuses.is_empty() {
+        ts
+    } else {
+        format!("{ts} | undefined")
+    }
+}
+
+/// The TypeScript type a request body is sent as: `DeepPartial<T>` for a JSON
+/// merge patch and `JsonPatchOp[]` for a JSON Patch (both from `types.ts`),
+/// else its schema's type.
+pub fn request_body_ts_type(body: &IrRequestBody) -> String {
+    match body.patch_format() {
+        Some(PatchFormat::MergePatch) => format!("DeepPartial<{}>", ir_type_to_ts(&body.body_type)),
+        Some(PatchFormat::JsonPatch) => "JsonPatchOp[]".to_string(),
+        None => ir_type_to_ts(&body.body_type),
+    }
+}
+
+/// A property name as written in an interface or object literal: the wire name
+/// itself, quoted when it isn't an identifier (`"content-type"`, `"2fa_enabled"`).
+/// Properties keep their wire names, so bodies need no key mapping.
+pub fn ts_property_key(name: &str) -> String {
+    let mut chars = name.chars();
+    let is_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$');
+    if is_identifier {
+        name.to_string()
+    } else {
+        serde_json::to_string(name).expect("strings always serialize")
+    }
+}
+
+/// JSDoc note for key constraints TypeScript cannot express, e.g. the
+/// `propertyNames` pattern of a `Record<string, T>`.
+pub fn map_key_note(ir_type: &IrType) -> Option<String> {
+    match ir_type {
+        IrType::Map(_, Some(IrMapKey::Pattern(pattern))) => {
+            Some(format!("Keys match `{pattern}`."))
+        }
+        IrType::Union(variants) => variants.iter().find_map(map_key_note),
+        _ => None,
     }
 }
 
@@ -69,6 +125,26 @@ mod tests {
         assert_eq!(ir_type_to_ts(&IrType::Void), "void");
     }
 
+    #[test]
+    fn test_string_literal_is_escaped() {
+        assert_eq!(
+            ir_type_to_ts(&IrType::StringLiteral("active".into())),
+            "\"active\""
+        );
+        assert_eq!(
+            ir_type_to_ts(&IrType::StringLiteral(r#"He said "hello" \ bye"#.into())),
+            r#""He said \"hello\" \\ bye""#
+        );
+    }
+
+    #[test]
+    fn test_void_inside_composites() {
+        assert_eq!(
+            ir_type_to_ts(&IrType::Union(vec![IrType::Void, IrType::Null])),
+            "void | null"
+        );
+    }
+
     #[test]
     fn test_array() {
         assert_eq!(
@@ -87,9 +163,16 @@ mod tests {
     #[test]
     fn test_map() {
         assert_eq!(
-            ir_type_to_ts(&IrType::Map(Box::new(IrType::String))),
+            ir_type_to_ts(&IrType::Map(Box::new(IrType::String), None)),
             "Record<string, string>"
         );
+        assert_eq!(
+            ir_type_to_ts(&IrType::Map(
+                Box::new(IrType::String),
+                Some(IrMapKey::Integer)
+            )),
+            "Record<`${number}`, string>"
+        );
     }
 
     #[test]
@@ -104,4 +187,38 @@ mod tests {
             "string | number"
         );
     }
+
+    #[test]
+    fn test_ts_property_key() {
+        assert_eq!(ts_property_key("name"), "name");
+        assert_eq!(ts_property_key("$ref"), "$ref");
+        assert_eq!(ts_property_key("class"), "class");
+        assert_eq!(ts_property_key("content-type"), "\"content-type\"");
+        assert_eq!(ts_property_key("2fa_enabled"), "\"2fa_enabled\"");
+        assert_eq!(
+            ir_type_to_ts(&IrType::Object(vec![(
+                "x-id".to_string(),
+                IrType::String,
+                false
+            )])),
+            "{ \"x-id\"?: string }"
+        );
+    }
+
+    #[test]
+    fn test_map_key_note() {
+        let labels = IrType::Map(
+            Box::new(IrType::String),
+            Some(IrMapKey::Pattern("^[a-z]+$".to_string())),
+        );
+        assert_eq!(ir_type_to_ts(&labels), "Record<string, string>");
+        assert_eq!(
+            map_key_note(&IrType::Union(vec![labels, IrType::Null])).as_deref(),
+            Some("Keys match `^[a-z]+$`.")
+        );
+        assert_eq!(
+            map_key_note(&IrType::Map(Box::new(IrType::String), None)),
+            None
+        );
+    }
 }
@@ -0,0 +1,66 @@
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, GeneratedFile, parse, transform};
+use oag_node_client::NodeClientGenerator;
+
+const MARKDOWN_DOCS: &str = include_str!("../../oag-core/tests/fixtures/markdown-docs.yaml");
+const NUMERIC_FORMATS: &str = include_str!("../../oag-core/tests/fixtures/numeric-formats.yaml");
+const DOCUMENTED_PARAMS: &str =
+    include_str!("../../oag-core/tests/fixtures/documented-params.yaml");
+
+fn generate(yaml: &str) -> Vec<GeneratedFile> {
+    let spec = parse::from_yaml(yaml).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    NodeClientGenerator
+        .generate(&ir, &GeneratorConfig::default())
+        .unwrap()
+}
+
+fn file<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+    &files
+        .iter()
+        .find(|f| f.path == path)
+        .unwrap_or_else(|| panic!("{path} should be generated"))
+        .content
+}
+
+/// The JSDoc block directly above the line containing `marker`.
+fn doc_block(source: &str, marker: &str) -> String {
+    let lines: Vec<&str> = source.lines().collect();
+    let end = lines.iter().position(|l| l.contains(marker)).unwrap();
+    let start = lines[..end]
+        .iter()
+        .rposition(|l| l.trim_start().starts_with("/**"))
+        .unwrap();
+    lines[start..end].join("\n")
+}
+
+#[test]
+fn operation_jsdoc_is_wrapped_and_escaped() {
+    let files = generate(MARKDOWN_DOCS);
+    insta::assert_snapshot!(doc_block(
+        file(&files, "src/client.ts"),
+        "async listWidgets("
+    ));
+}
+
+#[test]
+fn operation_jsdoc_documents_parameters_and_result() {
+    let files = generate(DOCUMENTED_PARAMS);
+    insta::assert_snapshot!(doc_block(file(&files, "src/client.ts"), "async getPet("));
+}
+
+#[test]
+fn schema_jsdoc_keeps_paragraphs() {
+    let files = generate(MARKDOWN_DOCS);
+    insta::assert_snapshot!(file(&files, "src/types.ts"));
+}
+
+#[test]
+fn numeric_jsdoc_documents_format_and_bounds() {
+    let files = generate(NUMERIC_FORMATS);
+    let types = file(&files, "src/types.ts");
+    insta::assert_snapshot!(doc_block(types, "quantity: number;"));
+    assert!(types.contains("   * @format double\n   * @exclusiveMinimum 0\n"));
+    // Strings keep their format to themselves.
+    assert!(!types.contains("@format uuid"));
+}
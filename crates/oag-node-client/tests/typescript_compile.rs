@@ -1,8 +1,12 @@
 use std::fs;
-use std::process::Command;
+use std::path::Path;
+use std::process::{Command, Output};
 
-use oag_core::config::GeneratorConfig;
-use oag_core::{CodeGenerator, parse, transform};
+use oag_core::config::{
+    AuthConfig, ExamplesMode, GeneratorConfig, OutputLayout, ParamsStyle, PropertyNaming, SplitBy,
+    StreamAccumulator,
+};
+use oag_core::{CodeGenerator, GeneratedFile, parse, postprocess, transform};
 use oag_node_client::NodeClientGenerator;
 
 const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
@@ -10,6 +14,20 @@ const SSE_CHAT: &str = include_str!("../../oag-core/tests/fixtures/sse-chat.yaml
 const ANTHROPIC: &str = include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml");
 const PETSTORE_POLY: &str = include_str!("../../oag-core/tests/fixtures/petstore-polymorphic.yaml");
 const MIXED: &str = include_str!("../../oag-core/tests/fixtures/mixed-endpoints.yaml");
+const RESERVED_WORDS: &str = include_str!("../../oag-core/tests/fixtures/reserved-words.yaml");
+const PROPERTY_NAMES: &str = include_str!("../../oag-core/tests/fixtures/property-names.yaml");
+const SNAKE_CASE: &str = include_str!("../../oag-core/tests/fixtures/snake-case.yaml");
+const PLAIN_TEXT: &str = include_str!("../../oag-core/tests/fixtures/plain-text.yaml");
+const EMPTY_SUCCESS: &str = include_str!("../../oag-core/tests/fixtures/empty-success.yaml");
+const MANY_QUERY_PARAMS: &str =
+    include_str!("../../oag-core/tests/fixtures/many-query-params.yaml");
+const SCHEMA_EXAMPLES: &str = include_str!("../../oag-core/tests/fixtures/schema-examples.yaml");
+const BEARER_AUTH: &str = include_str!("../../oag-core/tests/fixtures/bearer-auth.yaml");
+const DEFAULT_ERRORS: &str = include_str!("../../oag-core/tests/fixtures/default-errors.yaml");
+const WRITE_ONLY: &str = include_str!("../../oag-core/tests/fixtures/write-only.yaml");
+const PATCH_BODIES: &str = include_str!("../../oag-core/tests/fixtures/patch-bodies.yaml");
+const BATCH_ENDPOINTS: &str = include_str!("../../oag-core/tests/fixtures/batch-endpoints.yaml");
+const ENVIRONMENTS: &str = include_str!("../../oag-core/tests/fixtures/environments.yaml");
 
 fn scaffold_config() -> GeneratorConfig {
     GeneratorConfig {
@@ -23,41 +41,29 @@ fn scaffold_config() -> GeneratorConfig {
     }
 }
 
+/// `scaffold_config` with the `strictest` tsconfig preset
+/// (`noUncheckedIndexedAccess`, `exactOptionalPropertyTypes`, ...).
+fn strictest_config() -> GeneratorConfig {
+    let mut config = scaffold_config();
+    config.scaffold.as_mut().unwrap()["tsconfig_preset"] = "strictest".into();
+    config
+}
+
 fn compile_typescript(yaml: &str) {
-    let spec = parse::from_yaml(yaml).unwrap();
-    let ir = transform::transform(&spec).unwrap();
+    compile_typescript_with(yaml, scaffold_config());
+}
 
-    let config = scaffold_config();
-    let files = NodeClientGenerator.generate(&ir, &config).unwrap();
+fn compile_typescript_with(yaml: &str, config: GeneratorConfig) {
+    compile_typescript_alongside(yaml, config, &[]);
+}
 
-    let tmp = tempfile::tempdir().unwrap();
+/// Compile the generated client together with `extra` hand-written files
+/// (path, content) that exercise its types.
+fn compile_typescript_alongside(yaml: &str, config: GeneratorConfig, extra: &[(&str, &str)]) {
+    let (tmp, files) = install(yaml, config, extra);
     let dir = tmp.path();
 
-    for file in &files {
-        let dest = dir.join(&file.path);
-        if let Some(parent) = dest.parent() {
-            fs::create_dir_all(parent).unwrap();
-        }
-        fs::write(&dest, &file.content).unwrap();
-    }
-
-    let install = Command::new("npm")
-        .args(["install", "--no-audit", "--no-fund"])
-        .current_dir(dir)
-        .output()
-        .expect("failed to run npm install");
-    if !install.status.success() {
-        panic!(
-            "npm install failed:\n{}",
-            String::from_utf8_lossy(&install.stderr)
-        );
-    }
-
-    let tsc = Command::new("npx")
-        .args(["tsc", "--noEmit"])
-        .current_dir(dir)
-        .output()
-        .expect("failed to run tsc");
+    let tsc = tsc(dir);
     if !tsc.status.success() {
         panic!(
             "tsc failed:\nstdout: {}\nstderr: {}",
@@ -66,6 +72,22 @@ fn compile_typescript(yaml: &str) {
         );
     }
 
+    // Run the generated vitest suite, when the scaffold includes one.
+    if files.iter().any(|f| f.path == "src/client.test.ts") {
+        let vitest = Command::new("npx")
+            .args(["vitest", "run"])
+            .current_dir(dir)
+            .output()
+            .expect("failed to run vitest");
+        if !vitest.status.success() {
+            panic!(
+                "vitest failed:\nstdout: {}\nstderr: {}",
+                String::from_utf8_lossy(&vitest.stdout),
+                String::from_utf8_lossy(&vitest.stderr),
+            );
+        }
+    }
+
     // Apply safe auto-fixes (formatting, import ordering) then verify.
     let biome_fix = Command::new("npx")
         .args(["@biomejs/biome", "check", "--write", "."])
@@ -94,6 +116,57 @@ fn compile_typescript(yaml: &str) {
     }
 }
 
+/// Write the generated client and `extra` files to a temporary package and
+/// install its dependencies.
+fn install(
+    yaml: &str,
+    config: GeneratorConfig,
+    extra: &[(&str, &str)],
+) -> (tempfile::TempDir, Vec<GeneratedFile>) {
+    let spec = parse::from_yaml(yaml).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+
+    let mut files = NodeClientGenerator.generate(&ir, &config).unwrap();
+    postprocess::normalize_files(&NodeClientGenerator, &mut files);
+
+    let tmp = tempfile::tempdir().unwrap();
+    let dir = tmp.path();
+
+    for (path, content) in files
+        .iter()
+        .map(|f| (f.path.clone(), f.content.as_str()))
+        .chain(extra.iter().map(|&(path, content)| (path.into(), content)))
+    {
+        let dest = dir.join(path);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent).unwrap();
+        }
+        fs::write(&dest, content).unwrap();
+    }
+
+    let install = Command::new("npm")
+        .args(["install", "--no-audit", "--no-fund"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run npm install");
+    if !install.status.success() {
+        panic!(
+            "npm install failed:\n{}",
+            String::from_utf8_lossy(&install.stderr)
+        );
+    }
+
+    (tmp, files)
+}
+
+fn tsc(dir: &Path) -> Output {
+    Command::new("npx")
+        .args(["tsc", "--noEmit"])
+        .current_dir(dir)
+        .output()
+        .expect("failed to run tsc")
+}
+
 #[test]
 fn generated_typescript_petstore_compiles() {
     compile_typescript(PETSTORE);
@@ -109,6 +182,247 @@ fn generated_typescript_anthropic_compiles() {
     compile_typescript(ANTHROPIC);
 }
 
+#[test]
+fn generated_typescript_petstore_strictest_compiles() {
+    compile_typescript_with(PETSTORE, strictest_config());
+}
+
+#[test]
+fn generated_typescript_anthropic_strictest_compiles() {
+    compile_typescript_with(ANTHROPIC, strictest_config());
+}
+
+#[test]
+fn generated_typescript_sse_runtime_package_compiles() {
+    // A local stub package declared by the emitted reference.
+    let reference = oag_node_client::emitters::sse::emit_sse_reference("@org/oag-sse");
+    let mut config = strictest_config();
+    config.scaffold.as_mut().unwrap()["sse_runtime"] = "package:@org/oag-sse@file:./oag-sse".into();
+    compile_typescript_alongside(
+        SSE_CHAT,
+        config,
+        &[
+            (
+                "oag-sse/package.json",
+                r#"{ "name": "@org/oag-sse", "version": "0.0.0", "type": "module", "types": "index.d.ts", "main": "index.js" }
+"#,
+            ),
+            ("oag-sse/index.d.ts", &reference),
+            (
+                "oag-sse/index.js",
+                r#"export class SSEError extends Error {}
+export class SSETimeoutError extends SSEError {}
+export async function* streamSse() {}
+"#,
+            ),
+        ],
+    );
+}
+
+#[test]
+fn generated_typescript_bearer_auth_compiles() {
+    // Runs the generated vitest coverage of the JWT refresh too.
+    compile_typescript_with(
+        BEARER_AUTH,
+        GeneratorConfig {
+            auth: AuthConfig {
+                refresh_before_expiry_s: Some(30),
+            },
+            scaffold: Some(serde_json::json!({
+                "package_name": "@test/node-client",
+                "formatter": "biome",
+                "bundler": false,
+                "test_runner": "vitest",
+                "tsconfig_preset": "strictest",
+            })),
+            ..GeneratorConfig::default()
+        },
+    );
+}
+
+#[test]
+fn generated_typescript_anthropic_split_types_compiles() {
+    compile_typescript_with(
+        ANTHROPIC,
+        GeneratorConfig {
+            layout: OutputLayout::Split,
+            split_by: Some(SplitBy::Tag),
+            split_types: true,
+            ..scaffold_config()
+        },
+    );
+}
+
+#[test]
+fn generated_typescript_anthropic_stream_accumulator_compiles() {
+    compile_typescript_with(
+        ANTHROPIC,
+        GeneratorConfig {
+            stream_accumulator: Some(StreamAccumulator {
+                delta_event: "content_block_delta".to_string(),
+                delta_field: "delta.text".to_string(),
+                done_event: "message_stop".to_string(),
+            }),
+            // Type-check and run the generated vitest coverage of the helpers too.
+            scaffold: Some(serde_json::json!({
+                "package_name": "@test/node-client",
+                "formatter": "biome",
+                "bundler": false,
+                "test_runner": "vitest",
+            })),
+            ..GeneratorConfig::default()
+        },
+    );
+}
+
+#[test]
+fn generated_typescript_anthropic_streaming_helpers_compiles() {
+    compile_typescript_with(
+        ANTHROPIC,
+        GeneratorConfig {
+            streaming_helpers: true,
+            // Type-check and run the generated vitest coverage of the helpers too.
+            scaffold: Some(serde_json::json!({
+                "package_name": "@test/node-client",
+                "formatter": "biome",
+                "bundler": false,
+                "test_runner": "vitest",
+            })),
+            ..GeneratorConfig::default()
+        },
+    );
+}
+
+#[test]
+fn generated_typescript_batch_helpers_compiles() {
+    let mut config = strictest_config();
+    config.batch_helpers = true;
+    // Type-check and run the generated vitest coverage of the helpers too.
+    config.scaffold.as_mut().unwrap()["test_runner"] = "vitest".into();
+    compile_typescript_with(BATCH_ENDPOINTS, config);
+}
+
+#[test]
+fn generated_typescript_environments_compiles() {
+    let mut config = strictest_config();
+    // Type-check and run the generated vitest coverage of the environments too.
+    config.scaffold.as_mut().unwrap()["test_runner"] = "vitest".into();
+    compile_typescript_with(ENVIRONMENTS, config.clone());
+    config.layout = OutputLayout::Bundled;
+    compile_typescript_with(ENVIRONMENTS, config);
+}
+
+#[test]
+fn generated_typescript_write_only_debug_logging_compiles() {
+    let mut config = strictest_config();
+    config.debug_logging = true;
+    // Type-check and run the generated vitest coverage of the redaction too.
+    config.scaffold.as_mut().unwrap()["test_runner"] = "vitest".into();
+    compile_typescript_with(WRITE_ONLY, config);
+}
+
+#[test]
+fn generated_typescript_retry_compiles() {
+    let mut config = strictest_config();
+    config.retry = true;
+    // Type-check and run the generated vitest coverage of the retries too.
+    config.scaffold.as_mut().unwrap()["test_runner"] = "vitest".into();
+    compile_typescript_with(PETSTORE, config.clone());
+    config.layout = OutputLayout::Bundled;
+    compile_typescript_with(PETSTORE, config.clone());
+    // The SSE runtime and the client share the split types' `RetryConfig`.
+    config.layout = OutputLayout::Split;
+    config.split_types = true;
+    compile_typescript_with(ANTHROPIC, config);
+}
+
+#[test]
+fn generated_typescript_anthropic_route_table_compiles() {
+    compile_typescript_alongside(
+        ANTHROPIC,
+        GeneratorConfig {
+            route_table: true,
+            ..scaffold_config()
+        },
+        &[(
+            "src/routes.check.ts",
+            r#"import type { CreateMessageStreamEvent, MessageResponse, ModelInfo } from "./index";
+import { type Route, type Routes, routes } from "./index";
+
+routes.getModel satisfies {
+  method: "GET";
+  path: "/v1/models/{modelId}";
+  pathParams: readonly ["modelId"];
+};
+({}) as Routes satisfies Record<string, Route>;
+
+export const model: ModelInfo = {} as Routes["getModel"]["response"];
+export const message: MessageResponse = {} as Routes["createMessage"]["response"];
+export const event: CreateMessageStreamEvent = {} as Routes["createMessage"]["events"];
+"#,
+        )],
+    );
+}
+
+#[test]
+fn generated_typescript_patch_bodies_compiles() {
+    compile_typescript_alongside(
+        PATCH_BODIES,
+        GeneratorConfig {
+            route_table: true,
+            ..scaffold_config()
+        },
+        &[(
+            "src/patch.check.ts",
+            r#"import type { DeepPartial, JsonPatchOp, Pet, Routes } from "./index";
+import { ApiClient } from "./index";
+
+const client = new ApiClient({ baseUrl: "https://api.example.com" });
+
+// A merge patch sets any fields, at any depth, and keeps their types.
+export const merge = () => client.updatePet("rex", { owner: { email: "new@example.com" } });
+// @ts-expect-error `name` is still a string
+export const badMerge = () => client.updatePet("rex", { name: 1 });
+// A full replacement still needs every field.
+// @ts-expect-error `owner` and `tags` are missing
+export const badReplace = () => client.replacePet("rex", { name: "Rex" });
+
+export const patch = () =>
+  client.patchPet("rex", [
+    { op: "replace", path: "/name", value: "Rex" },
+    { op: "move", from: "/tags/0", path: "/tags/1" },
+    { op: "remove", path: "/owner" },
+  ]);
+// @ts-expect-error `rename` is not a JSON Patch operation
+export const badPatch = () => client.patchPet("rex", [{ op: "rename", path: "/name" }]);
+
+export const mergeBody: DeepPartial<Pet> = {} as Routes["updatePet"]["requestBody"];
+export const patchBody: JsonPatchOp[] = [] as Routes["patchPet"]["requestBody"];
+"#,
+        )],
+    );
+}
+
+#[test]
+fn generated_typescript_anthropic_pinned_version_compiles() {
+    compile_typescript_with(
+        ANTHROPIC,
+        GeneratorConfig {
+            pinned_parameters: [("anthropic-version".to_string(), "2023-06-01".to_string())]
+                .into_iter()
+                .collect(),
+            // The generated tests call every method without the pinned header.
+            scaffold: Some(serde_json::json!({
+                "package_name": "@test/node-client",
+                "formatter": "biome",
+                "bundler": false,
+                "test_runner": "vitest",
+            })),
+            ..GeneratorConfig::default()
+        },
+    );
+}
+
 #[test]
 fn generated_typescript_petstore_polymorphic_compiles() {
     compile_typescript(PETSTORE_POLY);
@@ -118,3 +432,209 @@ fn generated_typescript_petstore_polymorphic_compiles() {
 fn generated_typescript_mixed_compiles() {
     compile_typescript(MIXED);
 }
+
+#[test]
+fn generated_typescript_reserved_words_compiles() {
+    compile_typescript(RESERVED_WORDS);
+}
+
+#[test]
+fn generated_typescript_property_names_compiles() {
+    compile_typescript(PROPERTY_NAMES);
+}
+
+#[test]
+fn generated_typescript_camel_property_naming_compiles() {
+    for layout in [
+        OutputLayout::Bundled,
+        OutputLayout::Modular,
+        OutputLayout::Split,
+    ] {
+        compile_typescript_alongside(
+            SNAKE_CASE,
+            GeneratorConfig {
+                layout,
+                property_naming: PropertyNaming::Camel,
+                // Run the generated tests, which check the wire names sent.
+                scaffold: Some(serde_json::json!({
+                    "package_name": "@test/node-client",
+                    "formatter": "biome",
+                    "bundler": false,
+                    "test_runner": "vitest",
+                    "tsconfig_preset": "strictest",
+                })),
+                ..GeneratorConfig::default()
+            },
+            &[(
+                "src/naming.check.ts",
+                r#"import type { Pet } from "./index";
+import { ApiClient, deserializePet, serializeNewPet } from "./index";
+
+const client = new ApiClient({ baseUrl: "https://api.example.com" });
+
+export const create = () =>
+  client.createPet({ displayName: "Rex", owner: { first_name: "Ada", lastName: "Lovelace" } });
+// @ts-expect-error bodies take the camelCase names
+export const badCreate = () => client.createPet({ display_name: "Rex" });
+
+export const pet: Pet = deserializePet({
+  pet_id: "1",
+  display_name: "Rex",
+  created_at: "2024-01-01T00:00:00Z",
+});
+export const wire: unknown = serializeNewPet({ displayName: pet.displayName });
+"#,
+            )],
+        );
+    }
+}
+
+#[test]
+fn generated_typescript_plain_text_compiles() {
+    compile_typescript_with(
+        PLAIN_TEXT,
+        GeneratorConfig {
+            // Type-check and run the generated text body and response tests too.
+            scaffold: Some(serde_json::json!({
+                "package_name": "@test/node-client",
+                "formatter": "biome",
+                "bundler": false,
+                "test_runner": "vitest",
+            })),
+            ..GeneratorConfig::default()
+        },
+    );
+}
+
+#[test]
+fn generated_typescript_default_errors_compiles() {
+    compile_typescript(DEFAULT_ERRORS);
+}
+
+#[test]
+fn generated_typescript_empty_success_compiles() {
+    compile_typescript_with(
+        EMPTY_SUCCESS,
+        GeneratorConfig {
+            // Run the generated 200 and 204 tests too.
+            scaffold: Some(serde_json::json!({
+                "package_name": "@test/node-client",
+                "formatter": "biome",
+                "bundler": false,
+                "test_runner": "vitest",
+            })),
+            ..GeneratorConfig::default()
+        },
+    );
+}
+
+/// Both parameter styles, with the generated tests type-checked against the
+/// method signatures and run, query and header serialization included.
+#[test]
+fn generated_typescript_params_styles_compiles() {
+    for params_style in [ParamsStyle::Positional, ParamsStyle::Object] {
+        compile_typescript_with(
+            MANY_QUERY_PARAMS,
+            GeneratorConfig {
+                params_style,
+                scaffold: Some(serde_json::json!({
+                    "package_name": "@test/node-client",
+                    "formatter": "biome",
+                    "bundler": false,
+                    "test_runner": "vitest",
+                })),
+                ..GeneratorConfig::default()
+            },
+        );
+    }
+}
+
+#[test]
+fn generated_typescript_params_object_split_types_compiles() {
+    compile_typescript_with(
+        MANY_QUERY_PARAMS,
+        GeneratorConfig {
+            layout: OutputLayout::Split,
+            split_by: Some(SplitBy::Tag),
+            split_types: true,
+            params_style: ParamsStyle::Object,
+            ..scaffold_config()
+        },
+    );
+}
+
+#[test]
+fn generated_typescript_schema_examples_compiles() {
+    // The fixture's `Order` example doesn't match its schema.
+    let (tmp, _) = install(SCHEMA_EXAMPLES, scaffold_config(), &[]);
+    let tsc = tsc(tmp.path());
+    let stdout = String::from_utf8_lossy(&tsc.stdout);
+    assert!(
+        !tsc.status.success(),
+        "an invalid example should not compile"
+    );
+    assert!(stdout.contains("src/examples.ts"), "{stdout}");
+    assert!(!stdout.contains("petExample"), "{stdout}");
+
+    for examples in [ExamplesMode::Skip, ExamplesMode::EmitUnchecked] {
+        compile_typescript_with(
+            SCHEMA_EXAMPLES,
+            GeneratorConfig {
+                examples,
+                ..scaffold_config()
+            },
+        );
+    }
+}
+
+/// Type-checks `src` and the `next_handlers` directory together, against
+/// next's own types.
+const TSCONFIG_NEXT: &str = r#"{
+  "extends": "./tsconfig.json",
+  "compilerOptions": { "rootDir": ".", "types": ["node"] },
+  "include": ["src", "next"]
+}
+"#;
+
+#[test]
+#[ignore = "needs Node.js and npm, and installs next"]
+fn generated_next_handlers_compile() {
+    for (yaml, params_style) in [
+        (PETSTORE, ParamsStyle::Object),
+        (SSE_CHAT, ParamsStyle::Positional),
+    ] {
+        let config = GeneratorConfig {
+            next_handlers: true,
+            params_style,
+            ..strictest_config()
+        };
+        let (tmp, _) = install(yaml, config, &[("tsconfig.next.json", TSCONFIG_NEXT)]);
+        let dir = tmp.path();
+
+        let next = Command::new("npm")
+            .args(["install", "--no-save", "--no-audit", "--no-fund"])
+            .args(["next@^15", "@types/node"])
+            .current_dir(dir)
+            .output()
+            .expect("failed to run npm install");
+        if !next.status.success() {
+            panic!(
+                "npm install next failed:\n{}",
+                String::from_utf8_lossy(&next.stderr)
+            );
+        }
+
+        let tsc = Command::new("npx")
+            .args(["tsc", "--noEmit", "-p", "tsconfig.next.json"])
+            .current_dir(dir)
+            .output()
+            .expect("failed to run tsc");
+        if !tsc.status.success() {
+            panic!(
+                "tsc failed:\nstdout: {}\nstderr: {}",
+                String::from_utf8_lossy(&tsc.stdout),
+                String::from_utf8_lossy(&tsc.stderr),
+            );
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use minijinja::{Environment, context};
+use oag_core::config::ParamsStyle;
+use oag_core::ir::IrSpec;
+use oag_node_client::emitters::batch::batch_methods;
+
+/// Where `batch-hooks.tsx` imports the client, provider and types from; the
+/// helpers always come from the sibling `batch.ts`.
+pub struct BatchHookModules<'a> {
+    pub client: &'a str,
+    pub provider: &'a str,
+    pub types: &'a str,
+}
+
+/// Emit `batch-hooks.tsx`: a `use{Op}Batched` hook per `{op}Batched` helper of
+/// `batch.ts`, taking the operation's other parameters and sending the items
+/// given to its `trigger`, with the batch's progress, result and error as
+/// state. `None` when there is no `batch.ts`.
+pub fn emit_batch_hooks(
+    ir: &IrSpec,
+    client_class_name: &str,
+    params_style: ParamsStyle,
+    modules: &BatchHookModules,
+) -> Option<String> {
+    let methods = batch_methods(ir, params_style);
+    if methods.is_empty() {
+        return None;
+    }
+
+    let mut batch_names: Vec<String> = methods
+        .iter()
+        .map(|m| format!("{}Batched", m.method))
+        .collect();
+    batch_names.extend(
+        [
+            "type BatchOptions",
+            "type BatchProgress",
+            "type BatchResult",
+        ]
+        .map(String::from),
+    );
+    let mut imports: Vec<(&str, Vec<String>)> = vec![("./batch", batch_names)];
+    add_import(
+        &mut imports,
+        modules.client,
+        vec!["type RequestOptions".to_string()],
+    );
+    add_import(
+        &mut imports,
+        modules.provider,
+        vec![format!("use{client_class_name}")],
+    );
+    add_import(
+        &mut imports,
+        modules.types,
+        methods
+            .iter()
+            .flat_map(|m| m.types.iter().map(|t| format!("type {t}")))
+            .collect(),
+    );
+
+    let hooks: Vec<minijinja::Value> = methods
+        .iter()
+        .map(|m| {
+            // The hook takes the method's other parameters, optional ones
+            // optional again now that `items` no longer follows them.
+            let params: Vec<String> = m
+                .parts
+                .iter()
+                .zip(&m.args)
+                .filter(|(_, arg)| *arg != "chunk")
+                .map(|(part, _)| match part.strip_suffix(" | undefined") {
+                    Some(part) => part.replacen(": ", "?: ", 1),
+                    None => part.clone(),
+                })
+                .collect();
+            let call_args: Vec<&str> = m
+                .args
+                .iter()
+                .map(|arg| {
+                    if arg == "chunk" {
+                        "items"
+                    } else {
+                        arg.as_str()
+                    }
+                })
+                .chain(["options"])
+                .collect();
+            context! {
+                hook_name => format!("use{}Batched", m.name),
+                method => m.method,
+                item_type => m.item_type,
+                response_type => m.response_type,
+                params_signature => params.join(", "),
+                call_args => call_args.join(", "),
+            }
+        })
+        .collect();
+
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "batch_hooks.ts.j2",
+        include_str!("../../templates/batch_hooks.ts.j2"),
+    )
+    .expect("template should be valid");
+    let content = env
+        .get_template("batch_hooks.ts.j2")
+        .unwrap()
+        .render(context! {
+            imports => import_clauses(imports),
+            hooks => hooks,
+            client_class_name => client_class_name,
+        })
+        .expect("render should succeed");
+    Some(content)
+}
+
+/// Add `names` to the imports from `module`; in bundled layout the client,
+/// provider and types are all `./index`, so they share one import.
+fn add_import<'a>(imports: &mut Vec<(&'a str, Vec<String>)>, module: &'a str, names: Vec<String>) {
+    match imports.iter_mut().find(|(m, _)| *m == module) {
+        Some((_, existing)) => existing.extend(names),
+        None => imports.push((module, names)),
+    }
+}
+
+/// Import contexts for the template: `type { A, B }` when a module only gives
+/// types, `{ a, type B }` otherwise, names sorted and deduplicated.
+fn import_clauses(imports: Vec<(&str, Vec<String>)>) -> Vec<minijinja::Value> {
+    imports
+        .into_iter()
+        .filter(|(_, names)| !names.is_empty())
+        .map(|(module, mut names)| {
+            let bare = |name: &String| name.trim_start_matches("type ").to_string();
+            names.sort_by_key(bare);
+            names.dedup();
+            let clause = if names.iter().all(|n| n.starts_with("type ")) {
+                let names: Vec<String> = names.iter().map(bare).collect();
+                format!("type {{ {} }}", names.join(", "))
+            } else {
+                format!("{{ {} }}", names.join(", "))
+            };
+            context! { module => module, clause => clause }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn batch_endpoints() -> IrSpec {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/batch-endpoints.yaml"
+        ))
+        .unwrap();
+        oag_core::transform::transform(&spec).unwrap()
+    }
+
+    #[test]
+    fn batched_hooks_take_the_other_parameters() {
+        let modules = BatchHookModules {
+            client: "./client",
+            provider: "./provider",
+            types: "./types",
+        };
+        let content = emit_batch_hooks(
+            &batch_endpoints(),
+            "PetImportClient",
+            ParamsStyle::Positional,
+            &modules,
+        )
+        .unwrap();
+        assert!(content.contains(
+            "import { type BatchOptions, type BatchProgress, type BatchResult, addStoreTagsBatched, createPetsBatched } from \"./batch\";"
+        ));
+        assert!(content.contains("import type { RequestOptions } from \"./client\";"));
+        assert!(content.contains("import { usePetImportClient } from \"./provider\";"));
+        assert!(content.contains("import type { BatchSummary, NewPet } from \"./types\";"));
+        assert!(content.contains(
+            "export function useCreatePetsBatched() {\n  const client = usePetImportClient();\n  return useBatch<NewPet, BatchSummary>((items, options) =>\n    createPetsBatched(client, items, options),\n  );"
+        ));
+        assert!(content.contains(
+            "export function useAddStoreTagsBatched(storeId: string, dryRun?: boolean) {"
+        ));
+        assert!(content.contains("addStoreTagsBatched(client, storeId, items, dryRun, options)"));
+    }
+
+    #[test]
+    fn bundled_hooks_import_from_index() {
+        let modules = BatchHookModules {
+            client: "./index",
+            provider: "./index",
+            types: "./index",
+        };
+        let content = emit_batch_hooks(
+            &batch_endpoints(),
+            "ApiClient",
+            ParamsStyle::Positional,
+            &modules,
+        )
+        .unwrap();
+        assert!(content.contains(
+            "import { type BatchSummary, type NewPet, type RequestOptions, useApiClient } from \"./index\";"
+        ));
+        assert_eq!(content.matches(" from \"./index\";").count(), 1);
+    }
+}
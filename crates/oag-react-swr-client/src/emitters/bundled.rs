@@ -0,0 +1,136 @@
+use oag_core::config::{HookNaming, KeyStyle, ParamsStyle, PropertyNaming, SseTransport};
+use oag_core::ir::IrSpec;
+use oag_node_client::emitters::bundled::{strip_auto_generated_header, strip_relative_imports};
+
+use crate::emitters;
+
+/// Header the node client's bundle starts with.
+const BUNDLED_HEADER: &str = "// Auto-generated by oag — do not edit (bundled)\n";
+
+/// Emit a single `index.tsx` that bundles the node client (types, SSE runtime,
+/// client) with the provider and hooks. Their external imports (`react`, `swr`)
+/// are hoisted to the top; relative imports are dropped since everything is inlined.
+#[allow(clippy::too_many_arguments)]
+pub fn emit_bundled(
+    ir: &IrSpec,
+    no_jsdoc: bool,
+    sse_transport: SseTransport,
+    default_base_url: Option<&str>,
+    client_class_name: &str,
+    naming: &HookNaming,
+    prefetch: bool,
+    params_style: ParamsStyle,
+    property_naming: PropertyNaming,
+    key_style: KeyStyle,
+    sse_package: Option<&str>,
+    has_environments: bool,
+    retry: bool,
+) -> String {
+    let base = oag_node_client::emitters::bundled::emit_bundled(
+        ir,
+        no_jsdoc,
+        sse_transport,
+        default_base_url,
+        params_style,
+        property_naming,
+        sse_package,
+        false,
+        false,
+        retry,
+    );
+    let base = base.strip_prefix(BUNDLED_HEADER).unwrap_or(&base);
+
+    let (provider_imports, provider) = split_imports(&emitters::provider::emit_provider(
+        client_class_name,
+        default_base_url.is_some(),
+        has_environments,
+    ));
+    let (hooks_imports, hooks) = split_imports(&emitters::hooks::apply_no_jsdoc(
+        emitters::hooks::emit_hooks(
+            ir,
+            client_class_name,
+            naming,
+            prefetch,
+            params_style,
+            key_style,
+            retry,
+        ),
+        no_jsdoc,
+    ));
+
+    let mut output = String::new();
+    output.push_str(BUNDLED_HEADER);
+    output.push('\n');
+    for import in provider_imports.iter().chain(&hooks_imports) {
+        output.push_str(import);
+        output.push('\n');
+    }
+    output.push_str(base);
+    output.push('\n');
+
+    output.push_str("\n// === Provider ===\n\n");
+    output.push_str(&provider);
+    output.push('\n');
+
+    output.push_str("\n// === Hooks ===\n\n");
+    output.push_str(&hooks);
+    output.push('\n');
+
+    output
+}
+
+/// Split a module into its external import lines and the code after them,
+/// dropping the header and relative imports.
+fn split_imports(content: &str) -> (Vec<String>, String) {
+    let stripped = strip_relative_imports(&strip_auto_generated_header(content));
+    let mut imports = Vec::new();
+    let mut lines = stripped.lines().peekable();
+    while let Some(line) = lines.next_if(|l| l.is_empty() || l.starts_with("import ")) {
+        if !line.is_empty() {
+            imports.push(line.to_string());
+        }
+    }
+    (imports, lines.collect::<Vec<_>>().join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bundle_hoists_external_imports() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_bundled(
+            &ir,
+            false,
+            SseTransport::default(),
+            None,
+            "ApiClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Positional,
+            PropertyNaming::Original,
+            KeyStyle::default(),
+            None,
+            false,
+            false,
+        );
+
+        assert!(content.starts_with(BUNDLED_HEADER));
+        assert!(!content.contains("from \"./"));
+        let first_code = content
+            .find("// === Types ===")
+            .expect("bundle should inline the types");
+        for import in ["from \"react\";", "from \"swr\";", "from \"swr/mutation\";"] {
+            let pos = content.find(import).expect(import);
+            assert!(pos < first_code, "{import} should be hoisted");
+        }
+        assert!(content.contains("export function ApiProvider("));
+        assert!(content.contains("export function useListPets("));
+        assert_eq!(content.matches("Auto-generated by oag").count(), 1);
+    }
+}
@@ -1,122 +1,514 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use minijinja::{Environment, context};
-use oag_core::ir::{HttpMethod, IrOperation, IrParameterLocation, IrReturnType, IrSpec, IrType};
-use oag_node_client::type_mapper::ir_type_to_ts;
+use oag_core::config::{HookNaming, KeyStyle, ParamsStyle};
+use oag_core::ir::{
+    HttpMethod, IrOperation, IrParameter, IrParameterLocation, IrReturnType, IrSpec, IrType,
+    OperationGroup,
+};
+use oag_node_client::emitters::client::{
+    collect_types_from_request_body, param_ident, param_tags, params_interface_name,
+    params_member_access, params_object_members, params_object_required,
+};
+use oag_node_client::emitters::split::group_module_name;
+use oag_node_client::emitters::{jsdoc_filter, strip_jsdoc};
+use oag_node_client::type_mapper::{
+    ir_type_to_ts, request_body_ts_type, response_ts_type, ts_property_key,
+};
+
+/// Doc lines for a JSDoc block the template opens itself (` * {{ text | jsdoc_body }}`).
+fn jsdoc_body(value: String) -> String {
+    oag_core::docs::jsdoc_body(&value, "")
+}
+
+/// `content` without its JSDoc blocks when `no_jsdoc` is set: the hook
+/// templates write their docs unconditionally.
+pub fn apply_no_jsdoc(content: String, no_jsdoc: bool) -> String {
+    if no_jsdoc {
+        strip_jsdoc(&content)
+    } else {
+        content
+    }
+}
 
-/// Escape `*/` sequences that would prematurely close JSDoc comment blocks.
-fn escape_jsdoc(value: String) -> String {
-    value.replace("*/", "*\\/")
+/// Emit `hooks.ts` — React hooks wrapping the API client. With `prefetch`, each
+/// query hook also gets a `usePrefetch*` helper for SWR `fallback` data. With
+/// `ParamsStyle::Object`, hooks take the client's `{Op}Params` object and spread
+/// its members into their keys in declaration order. `key_style` picks whether
+/// SWR keys start with the operation name or the path template. With `retry`,
+/// query and mutation hooks pass their `config.retry` to the client call.
+pub fn emit_hooks(
+    ir: &IrSpec,
+    client_class_name: &str,
+    naming: &HookNaming,
+    prefetch: bool,
+    params_style: ParamsStyle,
+    key_style: KeyStyle,
+    retry: bool,
+) -> String {
+    render_hooks(
+        ir,
+        hook_contexts(ir, naming, params_style, key_style),
+        HooksModule {
+            root: ".",
+            shared: None,
+        },
+        client_class_name,
+        prefetch,
+        retry,
+    )
 }
 
-/// Emit `hooks.ts` — React hooks wrapping the API client.
-pub fn emit_hooks(ir: &IrSpec) -> String {
+/// Emit hooks for split layout, as `(path, content)` pairs relative to `hooks/`:
+/// `{module}.tsx` per group that owns any hooks, `shared.ts` with the helpers
+/// they have in common (`isRelatedKey`, the SSE stream state) when any are
+/// needed, and an `index.ts` barrel.
+///
+/// An operation in several groups gets its hooks in the first one only, so
+/// the barrel's `export *`s never collide.
+#[allow(clippy::too_many_arguments)]
+pub fn emit_split_hooks(
+    ir: &IrSpec,
+    groups: &[OperationGroup],
+    client_class_name: &str,
+    naming: &HookNaming,
+    prefetch: bool,
+    params_style: ParamsStyle,
+    key_style: KeyStyle,
+    retry: bool,
+) -> Vec<(String, String)> {
+    let hooks = hook_contexts(ir, naming, params_style, key_style);
+    let has_related_keys = hooks.iter().any(|(_, h)| has_related_keys(h));
+    let has_sse = hooks.iter().any(|(_, h)| is_kind(h, "sse"));
+    // A group can't take the shared module's name.
+    let shared = if groups.iter().any(|g| group_module_name(g) == "shared") {
+        "_shared"
+    } else {
+        "shared"
+    };
+
+    let mut owner: HashMap<usize, usize> = HashMap::new();
+    for (group, g) in groups.iter().enumerate() {
+        for &i in &g.operation_indices {
+            owner.entry(i).or_insert(group);
+        }
+    }
+
+    let mut modules = Vec::new();
+    for (group, g) in groups.iter().enumerate() {
+        let group_hooks: Vec<_> = hooks
+            .iter()
+            .filter(|(idx, _)| owner.get(idx) == Some(&group))
+            .cloned()
+            .collect();
+        if group_hooks.is_empty() {
+            continue;
+        }
+        let content = render_hooks(
+            ir,
+            group_hooks,
+            HooksModule {
+                root: "..",
+                shared: Some(shared),
+            },
+            client_class_name,
+            prefetch,
+            retry,
+        );
+        modules.push((group_module_name(g), content));
+    }
+
     let mut env = Environment::new();
     env.set_trim_blocks(true);
-    env.add_filter("escape_jsdoc", escape_jsdoc);
-    env.add_template("hooks.ts.j2", include_str!("../../templates/hooks.ts.j2"))
-        .expect("template should be valid");
-    let tmpl = env.get_template("hooks.ts.j2").unwrap();
+    add_helper_templates(&mut env);
+    env.add_template(
+        "hooks_shared.ts.j2",
+        include_str!("../../templates/hooks_shared.ts.j2"),
+    )
+    .expect("template should be valid");
+    env.add_template(
+        "hooks_barrel.ts.j2",
+        include_str!("../../templates/hooks_barrel.ts.j2"),
+    )
+    .expect("template should be valid");
+    let barrel = env
+        .get_template("hooks_barrel.ts.j2")
+        .unwrap()
+        .render(context! {
+            has_related_keys => has_related_keys,
+            modules => modules.iter().map(|(m, _)| m.as_str()).collect::<Vec<_>>(),
+            shared => shared,
+        })
+        .expect("render should succeed");
+
+    let mut files: Vec<(String, String)> = modules
+        .into_iter()
+        .map(|(module, content)| (format!("{module}.tsx"), content))
+        .collect();
+    if has_related_keys || has_sse {
+        let content = env
+            .get_template("hooks_shared.ts.j2")
+            .unwrap()
+            .render(context! {
+                has_related_keys => has_related_keys,
+                has_sse => has_sse,
+                export_helpers => true,
+            })
+            .expect("render should succeed");
+        files.push((format!("{shared}.ts"), content));
+    }
+    files.push(("index.ts".to_string(), barrel));
+    files
+}
 
+/// Where a rendered hooks module lives relative to the source root, and the
+/// sibling module its helpers come from; without one it defines them itself.
+struct HooksModule<'a> {
+    root: &'a str,
+    shared: Option<&'a str>,
+}
+
+/// Hook contexts for every operation, paired with the operation's index.
+/// A hook name is kept only for the first operation that produces it.
+fn hook_contexts(
+    ir: &IrSpec,
+    naming: &HookNaming,
+    params_style: ParamsStyle,
+    key_style: KeyStyle,
+) -> Vec<(usize, minijinja::Value)> {
+    let query_keys = query_keys(ir, key_style);
     let mut seen_hooks = HashSet::new();
-    let mut used_op_indices = HashSet::new();
-    let hooks: Vec<minijinja::Value> = ir
-        .operations
+    ir.operations
         .iter()
         .enumerate()
         .flat_map(|(idx, op)| {
-            build_hook_contexts(op)
+            build_hook_contexts(op, naming, &query_keys, params_style, key_style)
                 .into_iter()
                 .map(move |ctx| (idx, ctx))
         })
-        .filter(|(idx, h)| {
+        .filter(|(_, h)| {
             let name = h
                 .get_attr("hook_name")
                 .ok()
                 .and_then(|v| v.as_str().map(String::from));
             match name {
-                Some(n) => {
-                    if seen_hooks.insert(n) {
-                        used_op_indices.insert(*idx);
-                        true
-                    } else {
-                        false
-                    }
-                }
+                Some(n) => seen_hooks.insert(n),
                 None => true,
             }
         })
-        .map(|(_, ctx)| ctx)
-        .collect();
-    let imported_types = collect_imported_types(
+        .collect()
+}
+
+fn has_related_keys(hook: &minijinja::Value) -> bool {
+    hook.get_attr("related_keys")
+        .ok()
+        .is_some_and(|v| v.len().unwrap_or(0) > 0)
+}
+
+fn is_kind(hook: &minijinja::Value, kind: &str) -> bool {
+    hook.get_attr("kind")
+        .ok()
+        .is_some_and(|v| v.as_str() == Some(kind))
+}
+
+/// The helpers hooks modules share: `isRelatedKey` and `useEventStream`.
+fn add_helper_templates(env: &mut Environment<'_>) {
+    env.add_template(
+        "related_key.ts.j2",
+        include_str!("../../templates/related_key.ts.j2"),
+    )
+    .expect("template should be valid");
+    env.add_template(
+        "event_stream.ts.j2",
+        include_str!("../../templates/event_stream.ts.j2"),
+    )
+    .expect("template should be valid");
+}
+
+fn render_hooks(
+    ir: &IrSpec,
+    hooks: Vec<(usize, minijinja::Value)>,
+    module: HooksModule<'_>,
+    client_class_name: &str,
+    prefetch: bool,
+    retry: bool,
+) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_filter("jsdoc", jsdoc_filter);
+    env.add_filter("jsdoc_body", jsdoc_body);
+    add_helper_templates(&mut env);
+    env.add_template("hooks.ts.j2", include_str!("../../templates/hooks.ts.j2"))
+        .expect("template should be valid");
+    let tmpl = env.get_template("hooks.ts.j2").unwrap();
+
+    let used_op_indices: HashSet<usize> = hooks.iter().map(|(idx, _)| *idx).collect();
+    let hooks: Vec<minijinja::Value> = hooks.into_iter().map(|(_, ctx)| ctx).collect();
+    let mut imported_types = collect_imported_types(
         ir.operations
             .iter()
             .enumerate()
             .filter(|(i, _)| used_op_indices.contains(i))
             .map(|(_, op)| op),
     );
-    let has_queries = hooks.iter().any(|h| {
-        h.get_attr("kind")
-            .ok()
-            .is_some_and(|v| v.as_str() == Some("query"))
-    });
-    let has_mutations = hooks.iter().any(|h| {
-        h.get_attr("kind")
+    imported_types.extend(hooks.iter().filter_map(|h| {
+        h.get_attr("params_interface")
             .ok()
-            .is_some_and(|v| v.as_str() == Some("mutation"))
-    });
-    let has_sse = hooks.iter().any(|h| {
-        h.get_attr("kind")
-            .ok()
-            .is_some_and(|v| v.as_str() == Some("sse"))
-    });
+            .and_then(|v| v.as_str().map(String::from))
+    }));
+    imported_types.sort();
+    imported_types.dedup();
+    let has_kind = |kind: &str| hooks.iter().any(|h| is_kind(h, kind));
+    let has_queries = has_kind("query");
+    let define_related_key = module.shared.is_none() && hooks.iter().any(has_related_keys);
 
     tmpl.render(context! {
+        root => module.root,
         imported_types => imported_types,
         hooks => hooks,
         has_queries => has_queries,
-        has_mutations => has_mutations,
-        has_sse => has_sse,
+        has_prefetch => prefetch && has_queries,
+        has_mutations => has_kind("mutation"),
+        has_related_keys => define_related_key,
+        has_sse => has_kind("sse"),
+        shared => module.shared,
+        export_helpers => false,
+        client_class_name => client_class_name,
+        retry => retry,
     })
     .expect("render should succeed")
 }
 
-fn build_hook_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
+/// Check that the configured suffixes don't make hooks of different operations
+/// share a name. Collisions that already exist without suffixes are left to the
+/// regular deduplication.
+pub fn check_hook_names(ir: &IrSpec, naming: &HookNaming) -> Result<(), String> {
+    let plain = HookNaming::default();
+    let mut owners: HashMap<String, (usize, String)> = HashMap::new();
+
+    for (idx, op) in ir.operations.iter().enumerate() {
+        let names = hook_names(op, naming);
+        let plain_names = hook_names(op, &plain);
+        for (name, plain_name) in names.into_iter().zip(plain_names) {
+            match owners.get(&name) {
+                Some((other, other_plain)) if *other != idx && *other_plain != plain_name => {
+                    return Err(format!(
+                        "hook name `{name}` is generated for both `{other_plain}` and `{plain_name}` after applying hook_naming suffixes"
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    owners.insert(name, (idx, plain_name));
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Names of all hooks generated for an operation, in emission order.
+pub fn hook_names(op: &IrOperation, naming: &HookNaming) -> Vec<String> {
+    build_hook_contexts(op, naming, &[], ParamsStyle::Positional, KeyStyle::Path)
+        .iter()
+        .filter_map(|ctx| {
+            ctx.get_attr("hook_name")
+                .ok()
+                .and_then(|v| v.as_str().map(String::from))
+        })
+        .collect()
+}
+
+/// SSE hooks with placeholder arguments for calling them and their `trigger`
+/// in tests, as `(hook_name, hook_args, trigger_args)`: `"1"` for each required
+/// parameter and `{}` for a required body, cast to `never`.
+pub fn sse_hook_test_args(
+    ir: &IrSpec,
+    naming: &HookNaming,
+    params_style: ParamsStyle,
+) -> Vec<(String, String, String)> {
+    const PLACEHOLDER: &str = "\"1\" as never";
+    ir.operations
+        .iter()
+        .filter(|op| matches!(op.return_type, IrReturnType::Sse(_)))
+        .filter_map(|op| {
+            // The SSE hook comes before a dual endpoint's JSON hook.
+            let hook_name = hook_names(op, naming).into_iter().next()?;
+            let object = (params_style == ParamsStyle::Object)
+                .then(|| ObjectParams::new(op))
+                .flatten();
+            let mut hook_args: Vec<String> = match &object {
+                Some(object) => object
+                    .path_idents
+                    .iter()
+                    .map(|_| PLACEHOLDER.into())
+                    .collect(),
+                None => client_params(op)
+                    .filter(|p| match p.location {
+                        IrParameterLocation::Path => true,
+                        IrParameterLocation::Query | IrParameterLocation::Header => p.required,
+                        IrParameterLocation::Cookie => false,
+                    })
+                    .map(|_| PLACEHOLDER.into())
+                    .collect(),
+            };
+            if object.as_ref().is_some_and(|o| o.required) {
+                let members: Vec<String> = params_object_members(op)
+                    .into_iter()
+                    .filter(|p| p.required)
+                    .map(|p| format!("\"{}\": \"1\"", p.name.camel_case))
+                    .collect();
+                hook_args.push(format!("{{ {} }} as never", members.join(", ")));
+            }
+            let trigger_args = match &op.request_body {
+                Some(body) if body.required => "{} as never".to_string(),
+                _ => String::new(),
+            };
+            Some((hook_name, hook_args.join(", "), trigger_args))
+        })
+        .collect()
+}
+
+/// Mutation hooks that have related query keys, as `(hook_name, const_name, keys)`.
+pub fn mutation_related_keys(
+    ir: &IrSpec,
+    naming: &HookNaming,
+    key_style: KeyStyle,
+) -> Vec<(String, String, Vec<String>)> {
+    let query_keys = query_keys(ir, key_style);
+    let mut seen = HashSet::new();
+    ir.operations
+        .iter()
+        .flat_map(|op| {
+            build_hook_contexts(op, naming, &query_keys, ParamsStyle::Positional, key_style)
+        })
+        .filter_map(|ctx| {
+            let attr = |name: &str| {
+                ctx.get_attr(name)
+                    .ok()
+                    .and_then(|v| v.as_str().map(String::from))
+            };
+            let hook_name = attr("hook_name")?;
+            let const_name = attr("related_keys_name")?;
+            let keys: Vec<String> = ctx
+                .get_attr("related_keys")
+                .ok()?
+                .try_iter()
+                .ok()?
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect();
+            (!keys.is_empty() && seen.insert(hook_name.clone()))
+                .then_some((hook_name, const_name, keys))
+        })
+        .collect()
+}
+
+/// Every query hook's path paired with the first element of its SWR key (the
+/// path again or the operation name), in spec order.
+fn query_keys(ir: &IrSpec, key_style: KeyStyle) -> Vec<(String, String)> {
+    let mut keys: Vec<(String, String)> = Vec::new();
+    for op in &ir.operations {
+        let is_query = op.method == HttpMethod::Get
+            && match &op.return_type {
+                IrReturnType::Standard(_) => true,
+                IrReturnType::Sse(sse) => sse.json_response.is_some(),
+                IrReturnType::Void => false,
+            };
+        let key = match key_style {
+            KeyStyle::Operation => op.name.camel_case.clone(),
+            KeyStyle::Path => op.normalized_path.clone(),
+        };
+        if is_query && !keys.iter().any(|(_, k)| *k == key) {
+            keys.push((op.normalized_path.clone(), key));
+        }
+    }
+    keys
+}
+
+/// Keys of the queries under a mutation's collection path, i.e. its path with
+/// trailing parameters dropped: `POST /pets` and `DELETE /pets/{petId}` both
+/// relate to `/pets` and `/pets/{petId}`.
+fn related_query_keys(mutation_path: &str, query_keys: &[(String, String)]) -> Vec<String> {
+    let mut collection: Vec<&str> = mutation_path.split('/').filter(|s| !s.is_empty()).collect();
+    while collection.last().is_some_and(|s| s.starts_with('{')) {
+        collection.pop();
+    }
+    if collection.is_empty() {
+        return Vec::new();
+    }
+
+    query_keys
+        .iter()
+        .filter(|(path, _)| {
+            let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+            segments.starts_with(&collection)
+        })
+        .map(|(_, key)| key.clone())
+        .collect()
+}
+
+fn build_hook_contexts(
+    op: &IrOperation,
+    naming: &HookNaming,
+    query_keys: &[(String, String)],
+    params_style: ParamsStyle,
+    key_style: KeyStyle,
+) -> Vec<minijinja::Value> {
+    let object = (params_style == ParamsStyle::Object)
+        .then(|| ObjectParams::new(op))
+        .flatten();
+    let params_interface = object.as_ref().map(|o| o.interface.clone());
+    let query_hook = format!("use{}{}", op.name.pascal_case, naming.query_suffix);
+    let mutation_hook = format!("use{}{}", op.name.pascal_case, naming.mutation_suffix);
+    let related_keys = related_query_keys(&op.normalized_path, query_keys);
+    let related_keys_name = format!("{}RelatedKeys", op.name.camel_case);
+    // Mutation hooks document their arguments after the text about `config`.
+    let tags = param_tags(op, params_style).join("\n");
+    let doc = op
+        .doc()
+        .into_iter()
+        .chain((!tags.is_empty()).then(|| tags.clone()))
+        .collect::<Vec<_>>()
+        .join("\n\n");
     let mut results = Vec::new();
 
     match (&op.method, &op.return_type) {
         // GET → useSWR query hook
         (HttpMethod::Get, IrReturnType::Standard(resp)) => {
-            let return_type = ir_type_to_ts(&resp.response_type);
-            let (params_sig, swr_key, call_args) = build_query_params(op);
+            let return_type = response_ts_type(resp);
+            let (params_sig, swr_key, call_args) =
+                build_query_params(op, object.as_ref(), key_style);
             results.push(context! {
                 kind => "query",
-                hook_name => format!("use{}", op.name.pascal_case),
+                hook_name => query_hook,
+                prefetch_name => format!("usePrefetch{}", op.name.pascal_case),
                 method_name => op.name.camel_case.clone(),
                 params_signature => params_sig,
                 return_type => return_type,
                 swr_key => swr_key,
                 call_args => call_args,
-                description => op.summary.clone().or(op.description.clone()),
+                description => doc.clone(),
+                params_interface => params_interface.clone(),
             });
         }
         // POST/PUT/DELETE non-streaming → useSWRMutation hook
         (_, IrReturnType::Standard(_)) | (_, IrReturnType::Void) => {
             let return_type = match &op.return_type {
-                IrReturnType::Standard(r) => ir_type_to_ts(&r.response_type),
+                IrReturnType::Standard(r) => response_ts_type(r),
                 _ => "void".to_string(),
             };
             let has_body = op.request_body.is_some();
             let body_type = op
                 .request_body
                 .as_ref()
-                .map(|b| ir_type_to_ts(&b.body_type))
+                .map(request_body_ts_type)
                 .unwrap_or_else(|| "void".to_string());
 
-            let (path_params_sig, swr_key, call_args, swr_key_type) = build_mutation_params(op);
+            let (path_params_sig, swr_key, call_args, swr_key_type) =
+                build_mutation_params(op, object.as_ref(), key_style);
             results.push(context! {
                 kind => "mutation",
-                hook_name => format!("use{}", op.name.pascal_case),
+                hook_name => mutation_hook,
                 method_name => op.name.camel_case.clone(),
                 path_params_signature => path_params_sig,
                 return_type => return_type,
@@ -125,7 +517,11 @@ fn build_hook_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
                 swr_key => swr_key,
                 swr_key_type => swr_key_type,
                 call_args => call_args,
-                description => op.summary.clone().or(op.description.clone()),
+                related_keys => related_keys.clone(),
+                related_keys_name => related_keys_name.clone(),
+                description => op.doc(),
+                param_tags => tags.clone(),
+                params_interface => params_interface.clone(),
             });
         }
         // SSE → custom streaming hook
@@ -135,23 +531,10 @@ fn build_hook_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
             } else {
                 ir_type_to_ts(&sse.event_type)
             };
-            let event_type_array = if event_type.contains('|') {
-                format!("({event_type})[]")
-            } else {
-                format!("{event_type}[]")
-            };
-            let method_name = if sse.also_has_json {
-                format!("{}Stream", op.name.camel_case)
-            } else {
-                op.name.camel_case.clone()
-            };
-            let hook_name = if sse.also_has_json {
-                format!("use{}Stream", op.name.pascal_case)
-            } else {
-                format!("use{}", op.name.pascal_case)
-            };
-            let (path_params_sig, trigger_params, stream_call_args, deps) =
-                build_sse_hook_params(op);
+            let method_name = sse.stream_name.camel_case.clone();
+            let hook_name = format!("use{}{}", sse.stream_name.pascal_case, naming.sse_suffix);
+            let (path_params_sig, trigger_params, stream_call_args) =
+                build_sse_hook_params(op, object.as_ref());
 
             results.push(context! {
                 kind => "sse",
@@ -159,28 +542,30 @@ fn build_hook_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
                 method_name => method_name,
                 path_params_signature => path_params_sig,
                 event_type => event_type,
-                event_type_array => event_type_array,
                 trigger_params => trigger_params,
                 stream_call_args => stream_call_args,
-                deps => deps,
-                description => op.summary.clone().or(op.description.clone()),
+                description => doc.clone(),
+                params_interface => params_interface.clone(),
             });
 
             // If dual endpoint, also generate the JSON query/mutation hook
             if let Some(ref json_resp) = sse.json_response {
-                let return_type = ir_type_to_ts(&json_resp.response_type);
+                let return_type = response_ts_type(json_resp);
                 match op.method {
                     HttpMethod::Get => {
-                        let (params_sig, swr_key, call_args) = build_query_params(op);
+                        let (params_sig, swr_key, call_args) =
+                            build_query_params(op, object.as_ref(), key_style);
                         results.push(context! {
                             kind => "query",
-                            hook_name => format!("use{}", op.name.pascal_case),
+                            hook_name => query_hook,
+                            prefetch_name => format!("usePrefetch{}", op.name.pascal_case),
                             method_name => op.name.camel_case.clone(),
                             params_signature => params_sig,
                             return_type => return_type,
                             swr_key => swr_key,
                             call_args => call_args,
-                            description => op.summary.clone().or(op.description.clone()),
+                            description => doc.clone(),
+                            params_interface => params_interface.clone(),
                         });
                     }
                     _ => {
@@ -188,13 +573,13 @@ fn build_hook_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
                         let body_type = op
                             .request_body
                             .as_ref()
-                            .map(|b| ir_type_to_ts(&b.body_type))
+                            .map(request_body_ts_type)
                             .unwrap_or_else(|| "void".to_string());
                         let (path_params_sig, swr_key, call_args, swr_key_type) =
-                            build_mutation_params(op);
+                            build_mutation_params(op, object.as_ref(), key_style);
                         results.push(context! {
                             kind => "mutation",
-                            hook_name => format!("use{}", op.name.pascal_case),
+                            hook_name => mutation_hook,
                             method_name => op.name.camel_case.clone(),
                             path_params_signature => path_params_sig,
                             return_type => return_type,
@@ -203,7 +588,11 @@ fn build_hook_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
                             swr_key => swr_key,
                             swr_key_type => swr_key_type,
                             call_args => call_args,
-                            description => op.summary.clone().or(op.description.clone()),
+                            related_keys => related_keys.clone(),
+                            related_keys_name => related_keys_name.clone(),
+                            description => op.doc(),
+                            param_tags => tags.clone(),
+                            params_interface => params_interface.clone(),
                         });
                     }
                 }
@@ -214,14 +603,26 @@ fn build_hook_contexts(op: &IrOperation) -> Vec<minijinja::Value> {
     results
 }
 
-fn build_query_params(op: &IrOperation) -> (String, String, String) {
+/// The parameters the client method takes, i.e. all but the pinned ones.
+fn client_params(op: &IrOperation) -> impl Iterator<Item = &IrParameter> {
+    op.parameters.iter().filter(|p| p.pinned_value.is_none())
+}
+
+fn build_query_params(
+    op: &IrOperation,
+    object: Option<&ObjectParams>,
+    key_style: KeyStyle,
+) -> (String, String, String) {
+    let (swr_key, _) = swr_key(op, object, key_style);
+    if let Some(object) = object {
+        return (object.signature(), swr_key, object.call_args(None));
+    }
     let mut required_sig = Vec::new();
     let mut optional_sig = Vec::new();
     let mut required_call = Vec::new();
     let mut optional_call = Vec::new();
-    let mut key_parts = Vec::new();
 
-    for param in &op.parameters {
+    for param in client_params(op) {
         match param.location {
             IrParameterLocation::Path
             | IrParameterLocation::Query
@@ -229,13 +630,12 @@ fn build_query_params(op: &IrOperation) -> (String, String, String) {
                 let ts = ir_type_to_ts(&param.param_type);
                 let is_required = param.required || param.location == IrParameterLocation::Path;
                 if is_required {
-                    required_sig.push(format!("{}: {}", param.name.camel_case, ts));
-                    required_call.push(param.name.camel_case.clone());
+                    required_sig.push(format!("{}: {}", param_ident(param), ts));
+                    required_call.push(param_ident(param));
                 } else {
-                    optional_sig.push(format!("{}?: {}", param.name.camel_case, ts));
-                    optional_call.push(param.name.camel_case.clone());
+                    optional_sig.push(format!("{}?: {}", param_ident(param), ts));
+                    optional_call.push(param_ident(param));
                 }
-                key_parts.push(param.name.camel_case.clone());
             }
             _ => {}
         }
@@ -246,27 +646,33 @@ fn build_query_params(op: &IrOperation) -> (String, String, String) {
     let mut call_parts = required_call;
     call_parts.extend(optional_call);
 
-    let swr_key = if key_parts.is_empty() {
-        format!("\"{}\"", op.path)
-    } else {
-        format!("[\"{}\", {}] as const", op.path, key_parts.join(", "))
-    };
-
     let params_sig = sig_parts.join(", ");
     let call_args = call_parts.join(", ");
 
     (params_sig, swr_key, call_args)
 }
 
-fn build_mutation_params(op: &IrOperation) -> (String, String, String, String) {
+fn build_mutation_params(
+    op: &IrOperation,
+    object: Option<&ObjectParams>,
+    key_style: KeyStyle,
+) -> (String, String, String, String) {
+    let (swr_key, swr_key_type) = swr_key(op, object, key_style);
+    if let Some(object) = object {
+        let body = op.request_body.as_ref().map(|b| ("arg", b.required));
+        return (
+            object.signature(),
+            swr_key,
+            object.call_args(body),
+            swr_key_type,
+        );
+    }
     let mut required_sig = Vec::new();
     let mut optional_sig = Vec::new();
     let mut required_call = Vec::new();
     let mut optional_call = Vec::new();
-    let mut key_parts = Vec::new();
-    let mut key_type_parts = Vec::new();
 
-    for param in &op.parameters {
+    for param in client_params(op) {
         match param.location {
             IrParameterLocation::Path
             | IrParameterLocation::Query
@@ -274,14 +680,12 @@ fn build_mutation_params(op: &IrOperation) -> (String, String, String, String) {
                 let ts = ir_type_to_ts(&param.param_type);
                 let is_required = param.required || param.location == IrParameterLocation::Path;
                 if is_required {
-                    required_sig.push(format!("{}: {}", param.name.camel_case, ts));
-                    required_call.push(param.name.camel_case.clone());
+                    required_sig.push(format!("{}: {}", param_ident(param), ts));
+                    required_call.push(param_ident(param));
                 } else {
-                    optional_sig.push(format!("{}?: {}", param.name.camel_case, ts));
-                    optional_call.push(param.name.camel_case.clone());
+                    optional_sig.push(format!("{}?: {}", param_ident(param), ts));
+                    optional_call.push(param_ident(param));
                 }
-                key_parts.push(param.name.camel_case.clone());
-                key_type_parts.push(ts);
             }
             _ => {}
         }
@@ -298,29 +702,139 @@ fn build_mutation_params(op: &IrOperation) -> (String, String, String, String) {
     }
 
     let path_params_sig = sig_parts.join(", ");
-    let swr_key = if key_parts.is_empty() {
-        format!("\"{}\"", op.path)
-    } else {
-        format!("[\"{}\", {}] as const", op.path, key_parts.join(", "))
-    };
-    let swr_key_type = if key_type_parts.is_empty() {
-        "string".to_string()
-    } else {
-        format!("readonly [string, {}]", key_type_parts.join(", "))
-    };
     let call_args = call_parts.join(", ");
 
     (path_params_sig, swr_key, call_args, swr_key_type)
 }
 
-fn build_sse_hook_params(op: &IrOperation) -> (String, String, String, String) {
+/// A hook's SWR key expression and its type.
+///
+/// With `KeyStyle::Operation` the key is the operation name, followed by an
+/// object of the parameters when there are any: `["listPets", { limit, status }]`.
+/// SWR hashes objects with their keys sorted and `undefined` values skipped, so
+/// omitted optional parameters don't change the key. With `KeyStyle::Path` it's
+/// the path template followed by each parameter positionally.
+fn swr_key(
+    op: &IrOperation,
+    object: Option<&ObjectParams>,
+    key_style: KeyStyle,
+) -> (String, String) {
+    let params: Vec<&IrParameter> = client_params(op)
+        .filter(|p| p.location != IrParameterLocation::Cookie)
+        .collect();
+    let tuple = |head: &str, parts: &str| format!("[\"{head}\", {parts}] as const");
+
+    match (key_style, object) {
+        (KeyStyle::Operation, Some(object)) => {
+            let path: Vec<&IrParameter> = params
+                .iter()
+                .copied()
+                .filter(|p| p.location == IrParameterLocation::Path)
+                .collect();
+            let (mut entries, members) = key_object_entries(&path);
+            entries.push("...params".to_string());
+            let key_type = if members.is_empty() {
+                object.interface.clone()
+            } else {
+                format!("{{ {} }} & {}", members.join("; "), object.interface)
+            };
+            (
+                tuple(
+                    &op.name.camel_case,
+                    &format!("{{ {} }}", entries.join(", ")),
+                ),
+                format!("readonly [string, {key_type}]"),
+            )
+        }
+        (KeyStyle::Operation, None) if params.is_empty() => {
+            (format!("\"{}\"", op.name.camel_case), "string".to_string())
+        }
+        (KeyStyle::Operation, None) => {
+            let (entries, members) = key_object_entries(&params);
+            (
+                tuple(
+                    &op.name.camel_case,
+                    &format!("{{ {} }}", entries.join(", ")),
+                ),
+                format!("readonly [string, {{ {} }}]", members.join("; ")),
+            )
+        }
+        (KeyStyle::Path, Some(object)) => (
+            tuple(&op.normalized_path, &object.key_parts.join(", ")),
+            object.path_key_type(),
+        ),
+        (KeyStyle::Path, None) if params.is_empty() => {
+            (format!("\"{}\"", op.normalized_path), "string".to_string())
+        }
+        (KeyStyle::Path, None) => {
+            let idents: Vec<String> = params.iter().map(|p| param_ident(p)).collect();
+            let types: Vec<String> = params
+                .iter()
+                .map(|p| {
+                    let ts = ir_type_to_ts(&p.param_type);
+                    if p.location != IrParameterLocation::Path && !p.required {
+                        format!("{ts} | undefined")
+                    } else {
+                        ts
+                    }
+                })
+                .collect();
+            (
+                tuple(&op.normalized_path, &idents.join(", ")),
+                format!("readonly [string, {}]", types.join(", ")),
+            )
+        }
+    }
+}
+
+/// The entries of an operation-style key's params object (`limit` or
+/// `"x-id": xId`) and the matching members of its type.
+fn key_object_entries(params: &[&IrParameter]) -> (Vec<String>, Vec<String>) {
+    params
+        .iter()
+        .map(|param| {
+            let key = ts_property_key(&param.name.camel_case);
+            let ident = param_ident(param);
+            let optional = param.location != IrParameterLocation::Path && !param.required;
+            let ts = ir_type_to_ts(&param.param_type);
+            // The entry is `undefined` when the argument is omitted.
+            let member = if optional {
+                format!("{key}?: {ts} | undefined")
+            } else {
+                format!("{key}: {ts}")
+            };
+            let entry = if key == ident {
+                ident
+            } else {
+                format!("{key}: {ident}")
+            };
+            (entry, member)
+        })
+        .unzip()
+}
+
+fn build_sse_hook_params(
+    op: &IrOperation,
+    object: Option<&ObjectParams>,
+) -> (String, String, String) {
+    if let Some(object) = object {
+        let trigger_params = op
+            .request_body
+            .as_ref()
+            .map(|body| {
+                let optional = if body.required { "" } else { "?" };
+                format!("body{optional}: {}", request_body_ts_type(body))
+            })
+            .unwrap_or_default();
+        let body = op.request_body.as_ref().map(|b| ("body", b.required));
+        return (object.signature(), trigger_params, object.call_args(body));
+    }
     let mut required_sig = Vec::new();
     let mut optional_sig = Vec::new();
     let mut required_call = Vec::new();
     let mut optional_call = Vec::new();
-    let mut deps_parts = Vec::new();
 
-    for param in &op.parameters {
+    for param in client_params(op) {
         match param.location {
             IrParameterLocation::Path
             | IrParameterLocation::Query
@@ -328,13 +842,12 @@ fn build_sse_hook_params(op: &IrOperation) -> (String, String, String, String) {
                 let ts = ir_type_to_ts(&param.param_type);
                 let is_required = param.required || param.location == IrParameterLocation::Path;
                 if is_required {
-                    required_sig.push(format!("{}: {}", param.name.camel_case, ts));
-                    required_call.push(param.name.camel_case.clone());
+                    required_sig.push(format!("{}: {}", param_ident(param), ts));
+                    required_call.push(param_ident(param));
                 } else {
-                    optional_sig.push(format!("{}?: {}", param.name.camel_case, ts));
-                    optional_call.push(param.name.camel_case.clone());
+                    optional_sig.push(format!("{}?: {}", param_ident(param), ts));
+                    optional_call.push(param_ident(param));
                 }
-                deps_parts.push(format!(", {}", param.name.camel_case));
             }
             _ => {}
         }
@@ -346,7 +859,7 @@ fn build_sse_hook_params(op: &IrOperation) -> (String, String, String, String) {
     stream_call_parts.extend(optional_call);
 
     let trigger_params = if let Some(ref body) = op.request_body {
-        let ts = ir_type_to_ts(&body.body_type);
+        let ts = request_body_ts_type(body);
         stream_call_parts.push("body".to_string());
         if body.required {
             format!("body: {}", ts)
@@ -359,9 +872,96 @@ fn build_sse_hook_params(op: &IrOperation) -> (String, String, String, String) {
 
     let path_params_sig = sig_parts.join(", ");
     let stream_call_args = stream_call_parts.join(", ");
-    let deps = deps_parts.join("");
 
-    (path_params_sig, trigger_params, stream_call_args, deps)
+    (path_params_sig, trigger_params, stream_call_args)
+}
+
+/// A hook's arguments with `ParamsStyle::Object`: the path parameters, then the
+/// operation's `{Op}Params` object.
+struct ObjectParams {
+    interface: String,
+    required: bool,
+    /// `name: type` of each path parameter.
+    path_signature: Vec<String>,
+    path_types: Vec<String>,
+    path_idents: Vec<String>,
+    /// Path parameters, then each params member read off the object.
+    key_parts: Vec<String>,
+    /// `{Op}Params["member"]` for each params member.
+    member_types: Vec<String>,
+}
+
+impl ObjectParams {
+    /// `None` when the operation has no query or header parameters to group.
+    fn new(op: &IrOperation) -> Option<Self> {
+        let members = params_object_members(op);
+        if members.is_empty() {
+            return None;
+        }
+        let interface = params_interface_name(op);
+        let required = params_object_required(op);
+        let path: Vec<&IrParameter> = client_params(op)
+            .filter(|p| p.location == IrParameterLocation::Path)
+            .collect();
+        let path_idents: Vec<String> = path.iter().map(|p| param_ident(p)).collect();
+        let path_types: Vec<String> = path.iter().map(|p| ir_type_to_ts(&p.param_type)).collect();
+        let key_parts = path_idents
+            .iter()
+            .cloned()
+            .chain(members.iter().map(|p| params_member_access(p, required)))
+            .collect();
+        let member_types = members
+            .iter()
+            .map(|p| format!("{interface}[\"{}\"]", p.name.camel_case))
+            .collect();
+        Some(Self {
+            path_signature: path_idents
+                .iter()
+                .zip(&path_types)
+                .map(|(ident, ts)| format!("{ident}: {ts}"))
+                .collect(),
+            interface,
+            required,
+            path_types,
+            path_idents,
+            key_parts,
+            member_types,
+        })
+    }
+
+    fn signature(&self) -> String {
+        let optional = if self.required { "" } else { "?" };
+        let mut parts = self.path_signature.clone();
+        parts.push(format!("params{optional}: {}", self.interface));
+        parts.join(", ")
+    }
+
+    /// The key type with `KeyStyle::Path`.
+    fn path_key_type(&self) -> String {
+        let parts: Vec<&str> = self
+            .path_types
+            .iter()
+            .chain(&self.member_types)
+            .map(String::as_str)
+            .collect();
+        format!("readonly [string, {}]", parts.join(", "))
+    }
+
+    /// Arguments in the client method's order: path parameters, then the params
+    /// object and `body` (an expression and whether it's required), required
+    /// ones first.
+    fn call_args(&self, body: Option<(&str, bool)>) -> String {
+        let mut args = self.path_idents.clone();
+        let body_first = body.is_some_and(|(_, required)| required && !self.required);
+        if body_first && let Some((body, _)) = body {
+            args.push(body.to_string());
+        }
+        args.push("params".to_string());
+        if !body_first && let Some((body, _)) = body {
+            args.push(body.to_string());
+        }
+        args.join(", ")
+    }
 }
 
 fn collect_imported_types<'a>(ops: impl Iterator<Item = &'a IrOperation>) -> Vec<String> {
@@ -385,7 +985,7 @@ fn collect_imported_types<'a>(ops: impl Iterator<Item = &'a IrOperation>) -> Vec
             IrReturnType::Void => {}
         }
         if let Some(ref body) = op.request_body {
-            collect_refs(&body.body_type, &mut types);
+            collect_types_from_request_body(body, &mut types);
         }
     }
 
@@ -394,12 +994,12 @@ fn collect_imported_types<'a>(ops: impl Iterator<Item = &'a IrOperation>) -> Vec
     sorted
 }
 
-fn collect_refs(ir_type: &IrType, types: &mut HashSet<String>) {
+pub(crate) fn collect_refs(ir_type: &IrType, types: &mut HashSet<String>) {
     match ir_type {
         IrType::Ref(name) => {
             types.insert(name.clone());
         }
-        IrType::Array(inner) | IrType::Map(inner) => collect_refs(inner, types),
+        IrType::Array(inner) | IrType::Map(inner, _) => collect_refs(inner, types),
         IrType::Union(variants) | IrType::Intersection(variants) => {
             for v in variants {
                 collect_refs(v, types);
@@ -1,4 +1,29 @@
-/// Emit `index.ts` — barrel re-exports for React.
-pub fn emit_index() -> String {
-    include_str!("../../templates/index.ts.j2").to_string()
+use oag_core::config::PropertyNaming;
+use oag_core::ir::IrSpec;
+use oag_node_client::emitters::index::{BarrelModule, client_modules};
+
+use crate::emitters::provider;
+
+/// The modules `index.tsx` re-exports when they were generated: the node
+/// client's, then the provider and hooks, then the optional ones.
+pub fn barrel_modules(
+    ir: &IrSpec,
+    client_class_name: &str,
+    property_naming: PropertyNaming,
+) -> Vec<BarrelModule> {
+    let mut modules = client_modules();
+    modules.push(BarrelModule::named(
+        "provider",
+        provider::exports(client_class_name),
+    ));
+    modules.push(BarrelModule::all("hooks"));
+    modules.extend(feature_modules(ir, property_naming));
+    modules
+}
+
+/// The optional modules: the node client's, then the batched hooks.
+pub fn feature_modules(ir: &IrSpec, property_naming: PropertyNaming) -> Vec<BarrelModule> {
+    let mut modules = oag_node_client::emitters::index::feature_modules(ir, property_naming);
+    modules.push(BarrelModule::all("batch-hooks"));
+    modules
 }
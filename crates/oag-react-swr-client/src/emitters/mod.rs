@@ -1,3 +1,5 @@
+pub mod batch;
+pub mod bundled;
 pub mod hooks;
 pub mod index;
 pub mod provider;
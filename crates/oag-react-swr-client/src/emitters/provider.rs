@@ -1,4 +1,108 @@
+use minijinja::{Environment, context};
+
 /// Emit `provider.ts` — React context provider for the API client.
-pub fn emit_provider() -> String {
-    include_str!("../../templates/provider.ts.j2").to_string()
+///
+/// The context, hook and provider names derive from `client_class_name` so that
+/// several generated clients can be composed in the same app. When the client
+/// has a default base URL, `config` is optional. With `environments.ts`, the
+/// provider takes an `environment` whose base URL the client uses unless
+/// `config.baseUrl` is set, required when there is no default.
+pub fn emit_provider(
+    client_class_name: &str,
+    has_default_base_url: bool,
+    has_environments: bool,
+) -> String {
+    let mut env = Environment::new();
+    env.set_trim_blocks(true);
+    env.add_template(
+        "provider.ts.j2",
+        include_str!("../../templates/provider.ts.j2"),
+    )
+    .expect("template should be valid");
+    let tmpl = env.get_template("provider.ts.j2").unwrap();
+
+    tmpl.render(context! {
+        client_class_name => client_class_name,
+        provider_name => provider_name(client_class_name),
+        has_default_base_url => has_default_base_url,
+        has_environments => has_environments,
+    })
+    .expect("render should succeed")
+}
+
+/// What `provider.tsx` offers the package root.
+pub fn exports(client_class_name: &str) -> Vec<String> {
+    let provider = provider_name(client_class_name);
+    vec![
+        format!("type {provider}Props"),
+        provider,
+        format!("use{client_class_name}"),
+    ]
+}
+
+/// Provider component name for a client class: `AiChatApiClient` → `AiChatApiProvider`.
+pub fn provider_name(client_class_name: &str) -> String {
+    let base = client_class_name
+        .strip_suffix("Client")
+        .filter(|s| !s.is_empty())
+        .unwrap_or(client_class_name);
+    format!("{base}Provider")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_name() {
+        assert_eq!(provider_name("ApiClient"), "ApiProvider");
+        assert_eq!(provider_name("AiChatApiClient"), "AiChatApiProvider");
+        assert_eq!(provider_name("Billing"), "BillingProvider");
+        assert_eq!(provider_name("Client"), "ClientProvider");
+    }
+
+    #[test]
+    fn test_emit_provider_uses_client_class_name() {
+        let content = emit_provider("BillingApiClient", false, false);
+        assert!(content.contains("export function useBillingApiClient(): ApiClient {"));
+        assert!(content.contains("export interface BillingApiProviderProps {"));
+        assert!(content.contains("export function BillingApiProvider({"));
+        assert!(!content.contains("useApiClient"));
+        assert!(content.contains("  config: ClientConfig;"));
+    }
+
+    #[test]
+    fn test_emit_provider_config_optional_with_default_base_url() {
+        let content = emit_provider("ApiClient", true, false);
+        assert!(content.contains("  config?: ClientConfig;"));
+        assert!(content.contains("const client = new ApiClient(config);"));
+    }
+
+    #[test]
+    fn test_emit_provider_with_environments() {
+        let content = emit_provider("ApiClient", true, true);
+        assert!(
+            content.contains(
+                "import { type Environment, environmentConfig } from \"./environments\";"
+            )
+        );
+        assert!(content.contains("  environment?: Environment;\n"));
+        assert!(content.contains("  config?: ClientConfig;\n"));
+        assert!(content.contains(
+            "const client = new ApiClient(environment ? environmentConfig(environment, config) : config);"
+        ));
+
+        // Without a default base URL, the environment supplies it.
+        let content = emit_provider("ApiClient", false, true);
+        assert!(content.contains(
+            "import { type Environment, type EnvironmentClientConfig, environmentConfig } from \"./environments\";"
+        ));
+        assert!(
+            content.contains("  environment: Environment;\n  config?: EnvironmentClientConfig;\n")
+        );
+        assert!(
+            content
+                .contains("const client = new ApiClient(environmentConfig(environment, config));")
+        );
+    }
 }
@@ -1,8 +1,24 @@
 use minijinja::{Environment, context};
-use oag_core::ir::{IrOperation, IrReturnType, IrSpec};
+use oag_core::config::{HookNaming, KeyStyle, ParamsStyle};
+use oag_core::ir::{HttpMethod, IrOperation, IrReturnType, IrSpec};
+use oag_core::test_report::TestCoverage;
+use oag_node_client::type_mapper::{ir_type_to_ts, response_ts_type};
 
-/// Emit `hooks.test.ts` — vitest smoke tests for React hook exports.
-pub fn emit_hooks_tests(ir: &IrSpec) -> String {
+use crate::emitters::hooks::{collect_refs, hook_names, mutation_related_keys, sse_hook_test_args};
+use crate::emitters::provider::provider_name;
+
+/// Emit `hooks.test.tsx` — vitest smoke tests for React hook exports. SSE hooks
+/// are also rendered (under jsdom and `StrictMode`) to check that they open one
+/// stream per trigger and abort it on unmount. For dual endpoints, `expectTypeOf`
+/// pins the JSON hook's `data` to the JSON response and the stream hook's
+/// `events` to the event type.
+pub fn emit_hooks_tests(
+    ir: &IrSpec,
+    client_class_name: &str,
+    naming: &HookNaming,
+    params_style: ParamsStyle,
+    key_style: KeyStyle,
+) -> String {
     let mut env = Environment::new();
     env.set_trim_blocks(true);
     env.add_template(
@@ -12,43 +28,126 @@ pub fn emit_hooks_tests(ir: &IrSpec) -> String {
     .expect("template should be valid");
     let tmpl = env.get_template("hooks.test.ts.j2").unwrap();
 
-    let mut seen = std::collections::HashSet::new();
-    let hook_names: Vec<String> = ir
-        .operations
-        .iter()
-        .flat_map(build_hook_names)
-        .filter(|n| seen.insert(n.clone()))
+    let hook_names: Vec<String> = exported_hooks(ir, naming)
+        .into_iter()
+        .map(|(_, name)| name)
         .collect();
 
-    tmpl.render(context! { hook_names => hook_names })
-        .expect("render should succeed")
-}
+    let related_keys: Vec<minijinja::Value> = mutation_related_keys(ir, naming, key_style)
+        .into_iter()
+        .map(|(hook_name, const_name, keys)| {
+            context! {
+                hook_name => hook_name,
+                const_name => const_name,
+                query_key => keys[0].clone(),
+            }
+        })
+        .collect();
 
-fn build_hook_names(op: &IrOperation) -> Vec<String> {
-    let mut names = Vec::new();
-
-    match &op.return_type {
-        IrReturnType::Sse(sse) => {
-            if sse.also_has_json {
-                names.push(format!("use{}Stream", op.name.pascal_case));
-                // Also has a JSON hook
-                names.push(format!("use{}", op.name.pascal_case));
-            } else {
-                names.push(format!("use{}", op.name.pascal_case));
+    let sse_hooks: Vec<minijinja::Value> = sse_hook_test_args(ir, naming, params_style)
+        .into_iter()
+        .map(|(name, args, trigger_args)| {
+            context! {
+                name => name,
+                args => args,
+                trigger_args => trigger_args,
             }
-        }
-        _ => {
-            names.push(format!("use{}", op.name.pascal_case));
-        }
+        })
+        .collect();
+
+    let (dual_hooks, dual_types) = dual_hook_types(ir, naming);
+
+    tmpl.render(context! {
+        hook_names => hook_names,
+        related_keys => related_keys,
+        sse_hooks => sse_hooks,
+        dual_hooks => dual_hooks,
+        dual_types => dual_types,
+        provider_name => provider_name(client_class_name),
+    })
+    .expect("render should succeed")
+}
+
+/// The `hooks.test.tsx` tests calling each operation's hooks
+/// (`test_report: true`), built from the same names as [`emit_hooks_tests`].
+pub fn hooks_test_coverage(ir: &IrSpec, naming: &HookNaming) -> TestCoverage {
+    let mut coverage = TestCoverage::new(ir);
+    for (op, hook) in exported_hooks(ir, naming) {
+        coverage.add(
+            op,
+            format!("hooks.test.tsx > hooks exports > exports {hook} as a function"),
+        );
     }
+    coverage
+}
 
-    names
+/// Each hook the export tests check, with its operation. A hook name an
+/// earlier operation already took is checked once, for that operation.
+fn exported_hooks<'a>(ir: &'a IrSpec, naming: &HookNaming) -> Vec<(&'a IrOperation, String)> {
+    let mut seen = std::collections::HashSet::new();
+    ir.operations
+        .iter()
+        .flat_map(|op| {
+            hook_names(op, naming)
+                .into_iter()
+                .map(move |name| (op, name))
+        })
+        .filter(|(_, name)| seen.insert(name.clone()))
+        .collect()
+}
+
+/// The expected `data` and `events` types of each dual endpoint's hooks, worked
+/// out from the IR rather than the hook contexts so the tests catch drift in
+/// either, plus the `./types` names they mention.
+fn dual_hook_types(ir: &IrSpec, naming: &HookNaming) -> (Vec<minijinja::Value>, Vec<String>) {
+    let mut types = std::collections::HashSet::new();
+    let hooks = ir
+        .operations
+        .iter()
+        .filter_map(|op| {
+            let IrReturnType::Sse(sse) = &op.return_type else {
+                return None;
+            };
+            let json = sse.json_response.as_ref()?;
+            let pascal = &op.name.pascal_case;
+            let json_hook = match op.method {
+                HttpMethod::Get => format!("use{pascal}{}", naming.query_suffix),
+                _ => format!("use{pascal}{}", naming.mutation_suffix),
+            };
+            let event_type = match &sse.event_type_name {
+                Some(name) => {
+                    types.insert(name.clone());
+                    name.clone()
+                }
+                None => {
+                    collect_refs(&sse.event_type, &mut types);
+                    ir_type_to_ts(&sse.event_type)
+                }
+            };
+            collect_refs(&json.response_type, &mut types);
+            Some(context! {
+                json_hook => json_hook,
+                data_type => response_ts_type(json),
+                stream_hook => format!("use{}{}", sse.stream_name.pascal_case, naming.sse_suffix),
+                event_type => event_type,
+            })
+        })
+        .collect();
+    let mut types: Vec<String> = types.into_iter().collect();
+    types.sort();
+    (hooks, types)
 }
 
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use super::*;
-    use oag_core::ir::{HttpMethod, IrResponse, IrType, NormalizedName};
+    use crate::emitters::hooks::{check_hook_names, emit_hooks};
+    use oag_core::config::ParamsStyle;
+    use oag_core::ir::{
+        HttpMethod, IrInfo, IrOperation, IrResponse, IrReturnType, IrSseReturn, IrType,
+        NormalizedName,
+    };
 
     fn make_name(name: &str) -> NormalizedName {
         NormalizedName {
@@ -66,24 +165,502 @@ mod tests {
         }
     }
 
-    #[test]
-    fn test_standard_hook_name() {
-        let op = IrOperation {
-            name: make_name("ListPets"),
-            method: HttpMethod::Get,
-            path: "/pets".to_string(),
+    fn make_op(name: &str, method: HttpMethod, return_type: IrReturnType) -> IrOperation {
+        IrOperation {
+            name: make_name(name),
+            method,
+            raw_path: "/pets".to_string(),
+            normalized_path: "/pets".to_string(),
             summary: None,
             description: None,
+            external_docs: None,
             tags: vec![],
             parameters: vec![],
             request_body: None,
-            return_type: IrReturnType::Standard(IrResponse {
-                response_type: IrType::Array(Box::new(IrType::Ref("Pet".to_string()))),
+            return_type,
+            errors: vec![],
+            deprecated: false,
+            is_webhook: false,
+            source_location: None,
+            security: None,
+            cookie_auth: vec![],
+            bearer_auth: None,
+            links: vec![],
+            extensions: Default::default(),
+        }
+    }
+
+    fn make_spec(operations: Vec<IrOperation>) -> IrSpec {
+        IrSpec {
+            info: IrInfo {
+                title: "Test".to_string(),
                 description: None,
+                version: "1.0".to_string(),
+            },
+            servers: vec![],
+            schemas: vec![],
+            operations,
+            webhooks: vec![],
+            diagnostics: vec![],
+            security: vec![],
+            modules: vec![],
+            raw_spec: None,
+        }
+    }
+
+    fn pets_response() -> IrReturnType {
+        IrReturnType::Standard(IrResponse {
+            response_type: IrType::Array(Box::new(IrType::Ref("Pet".to_string()))),
+            description: None,
+            content_type: None,
+            example: None,
+            examples: Default::default(),
+            empty_statuses: vec![],
+        })
+    }
+
+    fn suffixed() -> HookNaming {
+        HookNaming {
+            query_suffix: "Query".to_string(),
+            mutation_suffix: "Mutation".to_string(),
+            sse_suffix: "Subscription".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_standard_hook_name() {
+        let op = make_op("ListPets", HttpMethod::Get, pets_response());
+        let names = hook_names(&op, &HookNaming::default());
+        assert_eq!(names, vec!["useListPets"]);
+    }
+
+    #[test]
+    fn test_hook_names_with_suffixes() {
+        let naming = suffixed();
+        let query = make_op("ListPets", HttpMethod::Get, pets_response());
+        let mutation = make_op("CreatePet", HttpMethod::Post, IrReturnType::Void);
+        let sse = make_op(
+            "WatchPets",
+            HttpMethod::Get,
+            IrReturnType::Sse(IrSseReturn {
+                event_type: IrType::Ref("Pet".to_string()),
+                variants: vec![],
+                event_type_name: None,
+                stream_name: make_name("WatchPets"),
+                also_has_json: false,
+                json_response: None,
             }),
-            deprecated: false,
+        );
+        assert_eq!(hook_names(&query, &naming), vec!["useListPetsQuery"]);
+        assert_eq!(hook_names(&mutation, &naming), vec!["useCreatePetMutation"]);
+        assert_eq!(hook_names(&sse, &naming), vec!["useWatchPetsSubscription"]);
+
+        let ir = make_spec(vec![query, mutation, sse]);
+        let content = emit_hooks_tests(
+            &ir,
+            "TestClient",
+            &naming,
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+        );
+        assert!(content.contains("typeof hooks.useListPetsQuery"));
+        assert!(content.contains("typeof hooks.useCreatePetMutation"));
+        assert!(content.contains("typeof hooks.useWatchPetsSubscription"));
+        assert!(!content.contains("typeof hooks.useListPets)"));
+    }
+
+    #[test]
+    fn test_suffix_collision_detected() {
+        let ir = make_spec(vec![
+            make_op("Pets", HttpMethod::Get, pets_response()),
+            make_op("PetsQuery", HttpMethod::Post, IrReturnType::Void),
+        ]);
+        assert!(check_hook_names(&ir, &HookNaming::default()).is_ok());
+
+        let naming = HookNaming {
+            query_suffix: "Query".to_string(),
+            ..HookNaming::default()
         };
-        let names = build_hook_names(&op);
-        assert_eq!(names, vec!["useListPets"]);
+        let err = check_hook_names(&ir, &naming).unwrap_err();
+        assert!(err.contains("usePetsQuery"));
+    }
+
+    #[test]
+    fn test_related_keys_share_collection_path() {
+        let mut get = make_op("GetPet", HttpMethod::Get, pets_response());
+        get.normalized_path = "/pets/{petId}".to_string();
+        let mut orders = make_op("ListOrders", HttpMethod::Get, pets_response());
+        orders.normalized_path = "/orders".to_string();
+        let ir = make_spec(vec![
+            make_op("ListPets", HttpMethod::Get, pets_response()),
+            get,
+            orders,
+            make_op("CreatePet", HttpMethod::Post, IrReturnType::Void),
+        ]);
+
+        let related = mutation_related_keys(&ir, &HookNaming::default(), KeyStyle::Operation);
+        assert_eq!(related.len(), 1);
+        let (hook_name, const_name, keys) = &related[0];
+        assert_eq!(hook_name, "useCreatePet");
+        assert_eq!(const_name, "createPetRelatedKeys");
+        assert_eq!(keys, &["listPets", "getPet"]);
+
+        let related = mutation_related_keys(&ir, &HookNaming::default(), KeyStyle::Path);
+        assert_eq!(related[0].2, ["/pets", "/pets/{petId}"]);
+
+        let content = emit_hooks_tests(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            ParamsStyle::Positional,
+            KeyStyle::Operation,
+        );
+        assert!(
+            content.contains("hooks.isRelatedKey(JSON.parse(key), hooks.createPetRelatedKeys)")
+        );
+    }
+
+    #[test]
+    fn test_prefetch_helpers_for_queries_only() {
+        let ir = make_spec(vec![
+            make_op("ListPets", HttpMethod::Get, pets_response()),
+            make_op("CreatePet", HttpMethod::Post, IrReturnType::Void),
+        ]);
+
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            true,
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+            false,
+        );
+        assert!(content.contains("unstable_serialize } from \"swr\""));
+        assert!(content.contains("import type { ApiClient } from \"./client\";"));
+        assert!(content.contains("export function usePrefetchListPets(client: ApiClient)"));
+        assert!(!content.contains("usePrefetchCreatePet"));
+
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+            false,
+        );
+        assert!(!content.contains("usePrefetch"));
+        assert!(!content.contains("unstable_serialize"));
+    }
+
+    #[test]
+    fn test_hooks_forward_retry_to_the_client() {
+        let ir = make_spec(vec![
+            make_op("ListPets", HttpMethod::Get, pets_response()),
+            make_op("CreatePet", HttpMethod::Post, IrReturnType::Void),
+        ]);
+
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+            true,
+        );
+        assert!(content.contains("import type { RequestOptions } from \"./client\";"));
+        assert!(content.contains(
+            "export function useListPets(config?: SWRConfiguration<Pet[]> & Pick<RequestOptions, \"retry\">)"
+        ));
+        assert!(content.contains("() => client.listPets({ retry: config?.retry }),"));
+        assert!(content.contains("never> & Pick<RequestOptions, \"retry\">)"));
+        assert!(content.contains("=> client.createPet({ retry: config?.retry }),"));
+
+        // Without `retry`, the client has no `retry` option to pass.
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+            false,
+        );
+        assert!(!content.contains("RequestOptions"));
+        assert!(content.contains("() => client.listPets(),"));
+        assert!(content.contains("=> client.createPet(),"));
+    }
+
+    #[test]
+    fn test_bodiless_success_status_widens_hook_types() {
+        let pet_or_empty = IrReturnType::Standard(IrResponse {
+            response_type: IrType::Ref("Pet".to_string()),
+            description: None,
+            content_type: None,
+            example: None,
+            examples: Default::default(),
+            empty_statuses: vec!["204".to_string()],
+        });
+        let ir = make_spec(vec![
+            make_op("GetPet", HttpMethod::Get, pet_or_empty.clone()),
+            make_op("TouchPet", HttpMethod::Post, pet_or_empty),
+        ]);
+
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+            false,
+        );
+        assert!(content.contains("SWRConfiguration<Pet | undefined>"));
+        assert!(content.contains("SWRMutationConfiguration<Pet | undefined"));
+    }
+
+    #[test]
+    fn test_params_object_hooks() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Object,
+            KeyStyle::Path,
+            false,
+        );
+
+        assert!(content.contains("  ListThingsParams,\n"));
+        assert!(content.contains(
+            "export function useListThings(params?: ListThingsParams, config?: SWRConfiguration<Thing[]>)"
+        ));
+        assert!(content.contains(
+            "[\"/things\", params?.limit, params?.offset, params?.status, params?.sort, params?.q, params?.tag, params?.xRequestId] as const"
+        ));
+        assert!(content.contains("() => client.listThings(params),"));
+        assert!(content.contains("[\"/things/{thingId}\", thingId, params?.expand] as const"));
+        assert!(content.contains("() => client.getThing(thingId, params),"));
+        assert!(content.contains("readonly [string, CreateThingParams[\"dryRun\"]]"));
+        assert!(content.contains("client.createThing(arg, params)"));
+        assert!(content.contains("client.updateThing(thingId, params, arg)"));
+        assert!(content.contains("client.watchThing(thingId, params, { ...options, signal }),"));
+    }
+
+    #[test]
+    fn test_operation_keys_differ_per_method() {
+        let mut create = make_op("CreatePet", HttpMethod::Post, IrReturnType::Void);
+        create.request_body = Some(oag_core::ir::IrRequestBody {
+            body_type: IrType::Ref("Pet".to_string()),
+            required: true,
+            content_type: "application/json".to_string(),
+            description: None,
+            encoding: None,
+            example: None,
+            examples: Default::default(),
+        });
+        let ir = make_spec(vec![
+            make_op("ListPets", HttpMethod::Get, pets_response()),
+            create,
+        ]);
+
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            true,
+            ParamsStyle::Positional,
+            KeyStyle::Operation,
+            false,
+        );
+        assert!(content.contains("    \"listPets\",\n    () => client.listPets(),"));
+        assert!(content.contains("key: unstable_serialize(\"listPets\"),"));
+        assert!(content.contains("    \"createPet\",\n"));
+        assert!(content.contains("export const createPetRelatedKeys = [\"listPets\"] as const;"));
+        assert!(!content.contains("\"/pets\""));
+
+        // Both operations live on /pets, so path keys collide.
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Positional,
+            KeyStyle::Path,
+            false,
+        );
+        assert_eq!(content.matches("    \"/pets\",\n").count(), 2);
+    }
+
+    #[test]
+    fn test_operation_keys_name_their_params() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Positional,
+            KeyStyle::Operation,
+            false,
+        );
+        assert!(content.contains(
+            "[\"listThings\", { limit, offset, status, sort, q, tag, xRequestId }] as const"
+        ));
+        assert!(content.contains("[\"getThing\", { thingId, expand }] as const"));
+        assert!(content.contains("readonly [string, { dryRun?: boolean | undefined }]"));
+
+        let content = emit_hooks(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            false,
+            ParamsStyle::Object,
+            KeyStyle::Operation,
+            false,
+        );
+        assert!(content.contains("[\"listThings\", { ...params }] as const"));
+        assert!(content.contains("[\"getThing\", { thingId, ...params }] as const"));
+        assert!(content.contains("readonly [string, { thingId: string } & UpdateThingParams]"));
+    }
+
+    #[test]
+    fn test_sse_hook_lifecycle_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_hooks_tests(
+            &ir,
+            "ChatClient",
+            &HookNaming::default(),
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+        );
+        assert!(
+            content.starts_with(
+                "// Auto-generated by oag — do not edit\n// @vitest-environment jsdom\n"
+            )
+        );
+        assert!(content.contains("import { ChatProvider } from \"./provider\";"));
+        assert!(
+            content.contains("useCreateChatCompletionStream opens one stream under StrictMode")
+        );
+        assert!(content.contains("const stream = hooks.useCreateChatCompletionStream();"));
+        assert!(content.contains("void stream.trigger({} as never);"));
+        assert!(content.contains("expect(signals[0].aborted).toBe(true);"));
+
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_hooks_tests(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            ParamsStyle::Object,
+            KeyStyle::default(),
+        );
+        assert!(content.contains("hooks.useWatchThing(\"1\" as never)"));
+        assert!(content.contains("void stream.trigger();"));
+
+        // Without SSE hooks, the tests stay in node.
+        let content = emit_hooks_tests(
+            &make_spec(vec![make_op("ListPets", HttpMethod::Get, pets_response())]),
+            "TestClient",
+            &HookNaming::default(),
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+        );
+        assert!(!content.contains("jsdom"));
+        assert!(!content.contains("SSE hook lifecycle"));
+    }
+
+    #[test]
+    fn test_dual_endpoint_hook_types() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_hooks_tests(
+            &ir,
+            "ChatClient",
+            &HookNaming::default(),
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+        );
+        assert!(content.contains("import { afterEach, describe, expect, expectTypeOf, it, vi }"));
+        assert!(content.contains(
+            "import type { ChatCompletionResponse, CreateChatCompletionStreamEvent } from \"./types\";"
+        ));
+        assert!(content.contains(
+            "expectTypeOf(hooks.useCreateChatCompletion)\n      .returns.toHaveProperty(\"data\")\n      .toEqualTypeOf<ChatCompletionResponse | undefined>();"
+        ));
+        assert!(content.contains(
+            "expectTypeOf(hooks.useCreateChatCompletionStream)\n      .returns.toHaveProperty(\"events\")\n      .toEqualTypeOf<Array<CreateChatCompletionStreamEvent>>();"
+        ));
+
+        // SSE-only endpoints have no JSON hook to compare against.
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/many-query-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let content = emit_hooks_tests(
+            &ir,
+            "TestClient",
+            &HookNaming::default(),
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+        );
+        assert!(!content.contains("expectTypeOf"));
+        assert!(!content.contains("from \"./types\""));
+    }
+
+    #[test]
+    fn test_hooks_coverage_names_rendered_tests() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../../oag-core/tests/fixtures/sse-chat.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let coverage = hooks_test_coverage(&ir, &HookNaming::default());
+        let content = emit_hooks_tests(
+            &ir,
+            "ChatClient",
+            &HookNaming::default(),
+            ParamsStyle::Positional,
+            KeyStyle::default(),
+        );
+
+        // `createChatCompletion`'s stream hook takes the name the
+        // `createChatCompletionStream` operation's hook would have.
+        assert_eq!(coverage.untested(), ["createChatCompletionStream"]);
+        assert_eq!(
+            coverage.operations["createChatCompletion"],
+            [
+                "hooks.test.tsx > hooks exports > exports useCreateChatCompletionStream as a function",
+                "hooks.test.tsx > hooks exports > exports useCreateChatCompletion as a function",
+            ]
+        );
+        for test in coverage.operations.values().flatten() {
+            let name = test.rsplit(" > ").next().unwrap();
+            assert!(content.contains(&format!("it(\"{name}\"")), "{test}");
+        }
     }
 }
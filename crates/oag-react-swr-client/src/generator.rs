@@ -1,14 +1,43 @@
-use oag_core::config::{GeneratorConfig, GeneratorId};
-use oag_core::ir::IrSpec;
-use oag_core::{CodeGenerator, GeneratedFile, GeneratorError, normalize_generated};
+use oag_core::config::{
+    BaseUrl, ConfigWarning, GeneratorConfig, GeneratorId, OutputLayout, SplitBy,
+};
+use oag_core::ir::{IrSpec, group_operations};
+use oag_core::test_report::REPORT_FILE;
+use oag_core::{CodeGenerator, GeneratedFile, GeneratorError};
 use oag_node_client::NodeClientGenerator;
+use oag_node_client::emitters::scaffold::{NodeScaffoldConfig, default_client_class_name};
 use oag_node_client::emitters::source_path;
+use oag_node_client::emitters::streaming::HelperModules;
 
 use crate::emitters;
 
 /// React/SWR code generator. Produces the TypeScript client files plus React hooks.
 pub struct ReactSwrClientGenerator;
 
+/// The options react-swr-client reads, besides `output`, `files` and `naming`.
+const SUPPORTED_OPTIONS: &[&str] = &[
+    "layout",
+    "split_by",
+    "split_types",
+    "base_url",
+    "environments",
+    "no_jsdoc",
+    "sse_transport",
+    "auth",
+    "batch_helpers",
+    "retry",
+    "next_handlers",
+    "next_handlers_dir",
+    "hook_naming",
+    "key_style",
+    "test_report",
+    "pinned_parameters",
+    "params_style",
+    "property_naming",
+    "source_dir",
+    "scaffold",
+];
+
 impl CodeGenerator for ReactSwrClientGenerator {
     fn id(&self) -> GeneratorId {
         GeneratorId::ReactSwrClient
@@ -19,62 +48,599 @@ impl CodeGenerator for ReactSwrClientGenerator {
         ir: &IrSpec,
         config: &GeneratorConfig,
     ) -> Result<Vec<GeneratedFile>, GeneratorError> {
+        oag_core::check_relative_path(&config.source_dir)?;
+        let ir = &*ir.with_pinned_parameters(&config.pinned_parameters);
         let scaffold_options = NodeClientGenerator::build_scaffold_options(ir, config, true);
+        // Without hooks there's nothing React about the output.
+        if config.types_only {
+            let mut files = NodeClientGenerator::types_only_files(ir, config);
+            if let Some(ref scaffold) = scaffold_options {
+                files.extend(oag_node_client::emitters::scaffold::emit_scaffold(scaffold));
+            }
+            return Ok(files);
+        }
+        emitters::hooks::check_hook_names(ir, &config.hook_naming)
+            .map_err(GeneratorError::Other)?;
+        let client_class_name = scaffold_options
+            .as_ref()
+            .map(|s| s.client_class_name.clone())
+            .unwrap_or_else(|| default_client_class_name(&ir.info.title));
+        let swr_prefetch = scaffold_options.as_ref().is_some_and(|s| s.swr_prefetch);
 
         // Generate base TypeScript client files via the node-client generator
         // We manually produce the files to inject react scaffold options
         let no_jsdoc = config.no_jsdoc.unwrap_or(false);
+        let sse_transport = config.sse_transport.unwrap_or_default();
+        let base_url = BaseUrl::resolve(config.base_url.as_ref(), ir.default_base_url());
+        let environments =
+            oag_node_client::emitters::environments::environments(ir, &config.environments);
         let sd = &config.source_dir;
-        let mut files = vec![
-            GeneratedFile {
-                path: source_path(sd, "types.ts"),
-                content: oag_node_client::emitters::types::emit_types(ir),
-            },
-            GeneratedFile {
-                path: source_path(sd, "sse.ts"),
-                content: oag_node_client::emitters::sse::emit_sse(),
+        let sse_runtime = scaffold_options
+            .as_ref()
+            .map(|s| s.sse_runtime.clone())
+            .unwrap_or_default();
+
+        let mut files = match config.layout {
+            OutputLayout::Bundled => vec![GeneratedFile {
+                path: source_path(sd, "index.tsx"),
+                content: emitters::bundled::emit_bundled(
+                    ir,
+                    no_jsdoc,
+                    sse_transport,
+                    base_url,
+                    &client_class_name,
+                    &config.hook_naming,
+                    swr_prefetch,
+                    config.params_style,
+                    config.property_naming,
+                    config.key_style,
+                    sse_runtime.package(),
+                    !environments.is_empty(),
+                    config.retry,
+                ),
+            }],
+            OutputLayout::Modular => vec![
+                GeneratedFile {
+                    path: source_path(sd, "types.ts"),
+                    content: oag_node_client::emitters::types::emit_types(
+                        ir,
+                        config.params_style,
+                        config.property_naming,
+                        oag_node_client::emitters::types::needs_retry_config(
+                            ir,
+                            config.retry,
+                            false,
+                        ),
+                    ),
+                },
+                GeneratedFile {
+                    path: source_path(sd, "sse.ts"),
+                    content: oag_node_client::emitters::sse::emit_sse(sse_transport),
+                },
+                GeneratedFile {
+                    path: source_path(sd, "client.ts"),
+                    content: oag_node_client::emitters::client::emit_client(
+                        ir,
+                        no_jsdoc,
+                        base_url,
+                        config.params_style,
+                        config.property_naming,
+                        false,
+                        false,
+                        config.retry,
+                    ),
+                },
+                GeneratedFile {
+                    path: source_path(sd, "hooks.tsx"),
+                    content: emitters::hooks::apply_no_jsdoc(
+                        emitters::hooks::emit_hooks(
+                            ir,
+                            &client_class_name,
+                            &config.hook_naming,
+                            swr_prefetch,
+                            config.params_style,
+                            config.key_style,
+                            config.retry,
+                        ),
+                        no_jsdoc,
+                    ),
+                },
+            ],
+            OutputLayout::Split => {
+                let split_by = config.split_by.unwrap_or(SplitBy::Tag);
+                let mut files: Vec<GeneratedFile> = oag_node_client::emitters::split::emit_split(
+                    ir,
+                    no_jsdoc,
+                    split_by,
+                    sse_transport,
+                    sd,
+                    base_url,
+                    config.split_types,
+                    config.params_style,
+                    config.property_naming,
+                    false,
+                    false,
+                    config.retry,
+                );
+
+                let hooks = emitters::hooks::emit_split_hooks(
+                    ir,
+                    &group_operations(ir, split_by),
+                    &client_class_name,
+                    &config.hook_naming,
+                    swr_prefetch,
+                    config.params_style,
+                    config.key_style,
+                    config.retry,
+                );
+                for (path, content) in hooks {
+                    files.push(GeneratedFile {
+                        path: source_path(sd, &format!("hooks/{path}")),
+                        content: emitters::hooks::apply_no_jsdoc(content, no_jsdoc),
+                    });
+                }
+                files
+            }
+            layout => {
+                return Err(GeneratorError::Other(format!(
+                    "unsupported layout {layout:?}"
+                )));
+            }
+        };
+
+        // Bundled output inlines the provider into its index.tsx.
+        if config.layout != OutputLayout::Bundled {
+            files.push(GeneratedFile {
+                path: source_path(sd, "provider.tsx"),
+                content: emitters::provider::emit_provider(
+                    &client_class_name,
+                    base_url.is_some(),
+                    !environments.is_empty(),
+                ),
+            });
+        }
+        oag_node_client::emitters::auth::add_auth_module(
+            &mut files,
+            ir,
+            &config.auth,
+            sd,
+            "index.tsx",
+            config.layout == OutputLayout::Bundled,
+        );
+        oag_node_client::emitters::codecs::add_codecs_module(
+            &mut files,
+            ir,
+            config.property_naming,
+            sd,
+            "index.tsx",
+            config.layout == OutputLayout::Bundled,
+        );
+        oag_node_client::emitters::environments::add_environments_module(
+            &mut files,
+            &environments,
+            sd,
+            "index.tsx",
+            config.layout == OutputLayout::Bundled,
+        );
+        let helper_modules = match config.layout {
+            OutputLayout::Bundled => HelperModules {
+                client: "./index",
+                sse: "./index",
+                types: "./index",
             },
-            GeneratedFile {
-                path: source_path(sd, "client.ts"),
-                content: oag_node_client::emitters::client::emit_client(ir, no_jsdoc),
+            _ => HelperModules {
+                client: "./client",
+                sse: "./sse",
+                types: "./types",
             },
-        ];
+        };
+        if config.batch_helpers
+            && let Some(content) = oag_node_client::emitters::batch::emit_batch_helpers(
+                ir,
+                config.params_style,
+                &helper_modules,
+            )?
+        {
+            files.push(GeneratedFile {
+                path: source_path(sd, "batch.ts"),
+                content,
+            });
+            let hook_modules = emitters::batch::BatchHookModules {
+                client: helper_modules.client,
+                provider: match config.layout {
+                    OutputLayout::Bundled => "./index",
+                    _ => "./provider",
+                },
+                types: helper_modules.types,
+            };
+            if let Some(content) = emitters::batch::emit_batch_hooks(
+                ir,
+                &client_class_name,
+                config.params_style,
+                &hook_modules,
+            ) {
+                files.push(GeneratedFile {
+                    path: source_path(sd, "batch-hooks.tsx"),
+                    content,
+                });
+            }
+        }
+
+        if config.next_handlers {
+            oag_core::check_relative_path(&config.next_handlers_dir)?;
+            files.extend(oag_node_client::emitters::next::emit_next_handlers(
+                ir,
+                config.params_style,
+                &helper_modules,
+                &config.next_handlers_dir,
+                sd,
+                base_url,
+            )?);
+        }
+
+        // The barrel goes last, re-exporting the modules actually generated. A
+        // bundle declares the client, provider and hooks itself.
+        let index_path = source_path(sd, "index.tsx");
+        if config.layout == OutputLayout::Bundled {
+            let modules = emitters::index::feature_modules(ir, config.property_naming);
+            let exports = oag_node_client::emitters::index::reexports(&files, sd, &modules);
+            if let Some(index) = files.iter_mut().find(|f| f.path == index_path)
+                && !exports.is_empty()
+            {
+                if !index.content.ends_with('\n') {
+                    index.content.push('\n');
+                }
+                index.content.push_str(&exports);
+            }
+        } else {
+            let modules =
+                emitters::index::barrel_modules(ir, &client_class_name, config.property_naming);
+            let content = oag_node_client::emitters::index::emit_index(&files, sd, &modules);
+            files.push(GeneratedFile {
+                path: index_path,
+                content,
+            });
+        }
 
         if let Some(ref scaffold) = scaffold_options {
             files.extend(oag_node_client::emitters::scaffold::emit_scaffold(scaffold));
 
             if scaffold.test_runner.is_some() {
-                files.push(GeneratedFile {
-                    path: source_path(sd, "client.test.ts"),
-                    content: oag_node_client::emitters::tests::emit_client_tests(ir),
-                });
-                files.push(GeneratedFile {
-                    path: source_path(sd, "hooks.test.tsx"),
-                    content: emitters::tests::emit_hooks_tests(ir),
-                });
+                let mut tests = [
+                    GeneratedFile {
+                        path: source_path(sd, "client.test.ts"),
+                        content: oag_node_client::emitters::tests::emit_client_tests(
+                            ir,
+                            config.params_style,
+                            config.property_naming,
+                            config.retry,
+                        ),
+                    },
+                    GeneratedFile {
+                        path: source_path(sd, "hooks.test.tsx"),
+                        content: emitters::tests::emit_hooks_tests(
+                            ir,
+                            &client_class_name,
+                            &config.hook_naming,
+                            config.params_style,
+                            config.key_style,
+                        ),
+                    },
+                ];
+                if config.layout == OutputLayout::Bundled {
+                    for test in &mut tests {
+                        test.content = import_from_index(&test.content);
+                    }
+                }
+                files.extend(tests);
+                if config.test_report {
+                    let mut coverage = oag_node_client::emitters::tests::client_test_coverage(
+                        ir,
+                        config.params_style,
+                        config.property_naming,
+                    );
+                    coverage.merge(emitters::tests::hooks_test_coverage(
+                        ir,
+                        &config.hook_naming,
+                    ));
+                    files.push(GeneratedFile::new(
+                        source_path(sd, REPORT_FILE),
+                        coverage.to_json(),
+                    ));
+                }
+                if config.batch_helpers
+                    && let Some(content) = oag_node_client::emitters::batch::emit_batch_tests(
+                        ir,
+                        config.params_style,
+                        &helper_modules,
+                    )?
+                {
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "batch.test.ts"),
+                        content,
+                    });
+                }
+                let module = match config.layout {
+                    OutputLayout::Bundled => "./index",
+                    _ => "./environments",
+                };
+                if let Some(content) =
+                    oag_node_client::emitters::environments::emit_environments_tests(
+                        &environments,
+                        module,
+                        helper_modules.client,
+                    )
+                {
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "environments.test.ts"),
+                        content,
+                    });
+                }
+                if oag_node_client::emitters::auth::has_bearer_auth(ir) {
+                    let module = match config.layout {
+                        OutputLayout::Bundled => "./index",
+                        _ => "./auth",
+                    };
+                    files.push(GeneratedFile {
+                        path: source_path(sd, "auth.test.ts"),
+                        content: oag_node_client::emitters::auth::emit_auth_tests(
+                            ir,
+                            &config.auth,
+                            module,
+                        ),
+                    });
+                }
             }
         }
 
-        // Add React-specific files
-        files.push(GeneratedFile {
-            path: source_path(sd, "hooks.tsx"),
-            content: emitters::hooks::emit_hooks(ir),
-        });
+        if let Some(package) = sse_runtime.package() {
+            oag_node_client::emitters::sse::use_sse_package(&mut files, sd, package);
+        }
+
+        Ok(files)
+    }
+
+    fn validate_config(&self, config: &GeneratorConfig) -> Vec<ConfigWarning> {
+        let mut warnings = config.unsupported_options(self.id(), SUPPORTED_OPTIONS);
+        warnings.extend(config.split_option_warnings());
+        if let Err(warning) = config.parse_scaffold::<NodeScaffoldConfig>() {
+            warnings.push(warning);
+        }
+        warnings
+    }
+}
+
+/// Point a module's relative imports (`./client`, `./hooks`, ...) at the bundle.
+fn import_from_index(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| match line.split_once("from \"./") {
+            Some((head, _)) if line.starts_with("import ") || line.starts_with("} from ") => {
+                format!("{head}from \"./index\";")
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn index_exports_the_optional_modules_generated() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/bearer-auth.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let index = |config: GeneratorConfig| {
+            let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+            files
+                .into_iter()
+                .find(|f| f.path == "src/index.tsx")
+                .unwrap()
+                .content
+        };
+
+        let modular = index(GeneratorConfig::default());
+        assert!(modular.contains("export * from \"./hooks\";\n"));
+        assert!(modular.contains("export * from \"./auth\";\n"));
 
-        files.push(GeneratedFile {
-            path: source_path(sd, "provider.tsx"),
-            content: emitters::provider::emit_provider(),
+        let split = index(GeneratorConfig {
+            layout: OutputLayout::Split,
+            ..Default::default()
         });
+        assert!(split.contains("export * from \"./hooks\";\n"));
+        assert!(split.contains("export * from \"./auth\";\n"));
+    }
+
+    #[test]
+    fn batch_helpers_add_batched_hooks() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/batch-endpoints.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            batch_helpers: true,
+            scaffold: Some(serde_json::json!({ "test_runner": "vitest" })),
+            ..Default::default()
+        };
+        let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(
+            file("src/batch.ts")
+                .unwrap()
+                .content
+                .contains("export function createPetsBatched(")
+        );
+        assert!(
+            file("src/batch-hooks.tsx")
+                .unwrap()
+                .content
+                .contains("export function useCreatePetsBatched() {")
+        );
+        assert!(file("src/batch.test.ts").is_some());
+        let index = &file("src/index.tsx").unwrap().content;
+        assert!(index.contains("export * from \"./batch\";\nexport * from \"./batch-hooks\";\n"));
+
+        let bundled = GeneratorConfig {
+            layout: OutputLayout::Bundled,
+            ..config
+        };
+        let files = ReactSwrClientGenerator.generate(&ir, &bundled).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(
+            file("src/batch-hooks.tsx")
+                .unwrap()
+                .content
+                .contains(
+                    "import { type BatchSummary, type NewPet, type RequestOptions, usePetImportClient } from \"./index\";"
+                )
+        );
+        let index = &file("src/index.tsx").unwrap().content;
+        assert!(index.ends_with("export * from \"./batch\";\nexport * from \"./batch-hooks\";\n"));
 
-        // Add React index.tsx (includes hooks + provider exports)
-        files.push(GeneratedFile {
-            path: source_path(sd, "index.tsx"),
-            content: emitters::index::emit_index(),
+        let files = ReactSwrClientGenerator
+            .generate(&ir, &GeneratorConfig::default())
+            .unwrap();
+        assert!(!files.iter().any(|f| f.path.contains("batch")));
+    }
+
+    #[test]
+    fn hooks_document_their_arguments_unless_no_jsdoc() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/documented-params.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let hooks = |config: GeneratorConfig| {
+            let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+            files
+                .into_iter()
+                .find(|f| f.path == "src/hooks.tsx")
+                .unwrap()
+                .content
+        };
+
+        let content = hooks(GeneratorConfig::default());
+        assert!(content.contains(
+            "/**\n * Get a pet\n *\n * @param petId - The id of the pet to fetch\n * @param fields - Comma-separated fields to include. Defaults to all of them.\n */\nexport function useGetPet("
+        ));
+        // The tags come last, and the body is the mutation's `arg`, not a hook
+        // argument.
+        assert!(content.contains(
+            "isRelatedKey(key, updatePetRelatedKeys))`.\n *\n * @param petId - The id of the pet to update\n */\nexport function useUpdatePet("
+        ));
+
+        let content = hooks(GeneratorConfig {
+            no_jsdoc: Some(true),
+            ..Default::default()
         });
+        assert!(!content.contains("/**"));
+        assert!(content.contains("export function useGetPet("));
+    }
 
-        for file in &mut files {
-            file.content = normalize_generated(&file.content);
-        }
-        Ok(files)
+    #[test]
+    fn next_handlers_import_the_client_not_the_hooks() {
+        let spec =
+            oag_core::parse::from_yaml(include_str!("../../oag-core/tests/fixtures/sse-chat.yaml"))
+                .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            next_handlers: true,
+            ..Default::default()
+        };
+        let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        let handlers = &file("next/handlers.ts").unwrap().content;
+        assert!(handlers.contains("from \"../src/client\";"));
+        assert!(handlers.contains("streamResponse(client.createChatCompletionStream("));
+        assert!(
+            file("next/upstream.ts")
+                .unwrap()
+                .content
+                .contains("import { ApiClient } from \"../src/client\";")
+        );
+        assert!(file("next/[...oag]/route.ts").is_some());
+    }
+
+    #[test]
+    fn environments_add_a_provider_prop() {
+        let spec = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/environments.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&spec).unwrap();
+        let config = GeneratorConfig {
+            scaffold: Some(serde_json::json!({ "test_runner": "vitest" })),
+            ..Default::default()
+        };
+        let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(
+            file("src/environments.ts")
+                .unwrap()
+                .content
+                .contains("  staging: \"https://staging.example.com/v1\",\n")
+        );
+        assert!(file("src/environments.test.ts").is_some());
+        let provider = &file("src/provider.tsx").unwrap().content;
+        assert!(provider.contains("  environment?: Environment;\n"));
+        assert!(
+            file("src/index.tsx")
+                .unwrap()
+                .content
+                .contains("export * from \"./environments\";\n")
+        );
+
+        // `base_url: false` leaves the environment to supply it.
+        let bundled = GeneratorConfig {
+            layout: OutputLayout::Bundled,
+            base_url: Some(BaseUrl::Disabled),
+            ..config.with_environment("dev", "http://localhost:3000")
+        };
+        let files = ReactSwrClientGenerator.generate(&ir, &bundled).unwrap();
+        let file = |path: &str| files.iter().find(|f| f.path == path);
+        assert!(file("src/environments.ts").is_none());
+        let index = &file("src/index.tsx").unwrap().content;
+        assert!(!index.contains("from \"./"));
+        assert!(
+            index.contains("  environment: Environment;\n  config?: EnvironmentClientConfig;\n")
+        );
+        assert!(index.contains(
+            "export const ENVIRONMENTS = {\n  dev: \"http://localhost:3000\",\n} as const;"
+        ));
+
+        let petstore = oag_core::parse::from_yaml(include_str!(
+            "../../oag-core/tests/fixtures/petstore-3.2.yaml"
+        ))
+        .unwrap();
+        let ir = oag_core::transform::transform(&petstore).unwrap();
+        let files = ReactSwrClientGenerator
+            .generate(&ir, &GeneratorConfig::default())
+            .unwrap();
+        assert!(!files.iter().any(|f| f.path.contains("environments")));
+    }
+
+    #[test]
+    fn validate_config_flags_node_only_options() {
+        let config = GeneratorConfig {
+            route_table: true,
+            layout: OutputLayout::Split,
+            split_by: Some(SplitBy::Operation),
+            scaffold: Some(serde_json::json!({ "swr_prefetch": true })),
+            ..Default::default()
+        };
+        let warnings = ReactSwrClientGenerator.validate_config(&config);
+        assert_eq!(
+            warnings,
+            [ConfigWarning::new(
+                "route_table",
+                "not supported by react-swr-client; ignored"
+            )]
+        );
     }
 }
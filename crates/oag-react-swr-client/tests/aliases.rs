@@ -0,0 +1,54 @@
+use oag_core::config::GeneratorConfig;
+use oag_core::transform::{self, TransformOptions};
+use oag_core::{CodeGenerator, GeneratedFile, parse};
+use oag_react_swr_client::ReactSwrClientGenerator;
+
+const SSE_CHAT: &str = include_str!("../../oag-core/tests/fixtures/sse-chat.yaml");
+
+fn generate_aliased() -> Vec<GeneratedFile> {
+    let spec = parse::from_yaml(SSE_CHAT).unwrap();
+    let options = TransformOptions::new().with_alias("createChatCompletion", "chat");
+    let ir = transform::transform_with_options(&spec, &options).unwrap();
+    let config = GeneratorConfig {
+        scaffold: Some(serde_json::json!({ "test_runner": "vitest" })),
+        ..GeneratorConfig::default()
+    };
+    ReactSwrClientGenerator.generate(&ir, &config).unwrap()
+}
+
+fn content<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+    &files
+        .iter()
+        .find(|f| f.path == path)
+        .unwrap_or_else(|| panic!("{path} should be generated"))
+        .content
+}
+
+#[test]
+fn aliased_dual_endpoint_names_its_event_union_the_same_everywhere() {
+    let files = generate_aliased();
+
+    // Nothing refers to the union by the operation's original name.
+    for file in &files {
+        assert!(
+            !file.content.contains("CreateChatCompletionStreamEvent"),
+            "{} uses the unaliased event union name",
+            file.path
+        );
+    }
+
+    let types = content(&files, "src/types.ts");
+    assert!(types.contains("export type ChatStreamEvent ="));
+
+    let client = content(&files, "src/client.ts");
+    assert!(client.contains("ChatStreamEvent"));
+    assert!(client.contains("chatStream("));
+
+    let hooks = content(&files, "src/hooks.tsx");
+    assert!(hooks.contains("ChatStreamEvent"));
+    assert!(hooks.contains("export function useChatStream("));
+    assert!(hooks.contains("client.chatStream("));
+
+    let hooks_test = content(&files, "src/hooks.test.tsx");
+    assert!(hooks_test.contains("ChatStreamEvent"));
+}
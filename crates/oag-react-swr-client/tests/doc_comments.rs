@@ -0,0 +1,23 @@
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, parse, transform};
+use oag_react_swr_client::ReactSwrClientGenerator;
+
+const MARKDOWN_DOCS: &str = include_str!("../../oag-core/tests/fixtures/markdown-docs.yaml");
+
+#[test]
+fn query_hook_jsdoc_is_wrapped_and_escaped() {
+    let spec = parse::from_yaml(MARKDOWN_DOCS).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let files = ReactSwrClientGenerator
+        .generate(&ir, &GeneratorConfig::default())
+        .unwrap();
+    let hooks = &files
+        .iter()
+        .find(|f| f.path == "src/hooks.tsx")
+        .expect("src/hooks.tsx should be generated")
+        .content;
+
+    let start = hooks.find("/**\n * List widgets").unwrap();
+    let end = hooks.find("export function useListWidgets(").unwrap();
+    insta::assert_snapshot!(&hooks[start..end]);
+}
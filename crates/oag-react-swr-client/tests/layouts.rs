@@ -0,0 +1,189 @@
+use oag_core::config::{GeneratorConfig, OutputLayout};
+use oag_core::{CodeGenerator, GeneratedFile, parse, transform};
+use oag_react_swr_client::ReactSwrClientGenerator;
+
+const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
+
+fn generate(layout: OutputLayout) -> Vec<GeneratedFile> {
+    let spec = parse::from_yaml(PETSTORE).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let config = GeneratorConfig {
+        layout,
+        scaffold: Some(serde_json::json!({
+            "package_name": "@test/react-client",
+            "bundler": false,
+        })),
+        ..GeneratorConfig::default()
+    };
+    ReactSwrClientGenerator.generate(&ir, &config).unwrap()
+}
+
+fn source_files(files: &[GeneratedFile]) -> Vec<&str> {
+    files
+        .iter()
+        .map(|f| f.path.as_str())
+        .filter(|p| p.starts_with("src/") && !p.contains(".test."))
+        .collect()
+}
+
+fn content<'a>(files: &'a [GeneratedFile], path: &str) -> &'a str {
+    &files
+        .iter()
+        .find(|f| f.path == path)
+        .unwrap_or_else(|| panic!("{path} should be generated"))
+        .content
+}
+
+#[test]
+fn bundled_layout_emits_a_single_index() {
+    let files = generate(OutputLayout::Bundled);
+    assert_eq!(source_files(&files), ["src/index.tsx"]);
+
+    let index = content(&files, "src/index.tsx");
+    assert!(!index.contains("from \"./"));
+    assert!(index.contains("export class ApiClient"));
+    assert!(index.contains("export function PetstoreProvider("));
+    assert!(index.contains("export function useListPets("));
+
+    // The tests import everything from the bundle.
+    let hooks_test = content(&files, "src/hooks.test.tsx");
+    assert!(hooks_test.contains("import * as hooks from \"./index\";"));
+    let client_test = content(&files, "src/client.test.ts");
+    assert!(
+        client_test
+            .contains("import { ApiClient, ApiError, joinUrl, operationMeta, serializeQueryValue } from \"./index\";")
+    );
+    assert!(!client_test.contains("from \"./client\""));
+}
+
+#[test]
+fn split_layout_emits_hooks_per_group() {
+    let files = generate(OutputLayout::Split);
+    assert_eq!(
+        source_files(&files),
+        [
+            "src/types.ts",
+            "src/sse.ts",
+            "src/client.ts",
+            "src/pets.ts",
+            "src/store.ts",
+            "src/hooks/pets.tsx",
+            "src/hooks/store.tsx",
+            "src/hooks/shared.ts",
+            "src/hooks/index.ts",
+            "src/provider.tsx",
+            "src/index.tsx",
+        ]
+    );
+
+    let pets = content(&files, "src/hooks/pets.tsx");
+    assert!(pets.contains("import { usePetstoreClient } from \"../provider\";"));
+    assert!(pets.contains("} from \"../types\";"));
+    assert!(pets.contains("export function useListPets("));
+    assert!(!pets.contains("export function useGetInventory("));
+    assert!(!pets.contains("export function isRelatedKey("));
+
+    let shared = content(&files, "src/hooks/shared.ts");
+    assert_eq!(shared.matches("export function isRelatedKey(").count(), 1);
+    assert!(!shared.contains("useEventStream"));
+
+    let barrel = content(&files, "src/hooks/index.ts");
+    assert!(barrel.contains("export * from \"./pets\";\nexport * from \"./store\";"));
+    assert!(barrel.contains("export { isRelatedKey } from \"./shared\";"));
+    assert!(content(&files, "src/index.tsx").contains("export * from \"./hooks\";"));
+}
+
+#[test]
+fn split_layout_shares_sse_plumbing_across_hook_modules() {
+    let spec = parse::from_yaml(include_str!(
+        "../../oag-core/tests/fixtures/anthropic-messages.yaml"
+    ))
+    .unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let config = GeneratorConfig {
+        layout: OutputLayout::Split,
+        ..GeneratorConfig::default()
+    };
+    let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+
+    let shared = content(&files, "src/hooks/shared.ts");
+    assert!(shared.contains("export function useEventStream<"));
+
+    let modules: Vec<&GeneratedFile> = files
+        .iter()
+        .filter(|f| f.path.starts_with("src/hooks/") && f.path.ends_with(".tsx"))
+        .collect();
+    let mut exported = Vec::new();
+    for module in &modules {
+        assert!(!module.content.contains("useState"), "{}", module.path);
+        for line in module.content.lines() {
+            if let Some(rest) = line.strip_prefix("export function ") {
+                exported.push(rest.split(['(', '<']).next().unwrap());
+            }
+        }
+    }
+    let total = exported.len();
+    exported.sort_unstable();
+    exported.dedup();
+    assert_eq!(exported.len(), total, "a hook is exported twice");
+
+    let messages = content(&files, "src/hooks/messages.tsx");
+    assert!(messages.contains("import { useEventStream } from \"./shared\";"));
+    assert!(messages.contains("export function useCreateMessageStream("));
+
+    // Every relative import points at a generated module.
+    for module in &modules {
+        for line in module.content.lines() {
+            let Some(target) = line
+                .split(" from \"")
+                .nth(1)
+                .and_then(|t| t.strip_suffix("\";"))
+            else {
+                continue;
+            };
+            let path = if let Some(rest) = target.strip_prefix("../") {
+                format!("src/{rest}")
+            } else if let Some(rest) = target.strip_prefix("./") {
+                format!("src/hooks/{rest}")
+            } else {
+                continue;
+            };
+            assert!(
+                ["ts", "tsx"]
+                    .iter()
+                    .any(|ext| files.iter().any(|f| f.path == format!("{path}.{ext}"))),
+                "{}: {target} does not resolve",
+                module.path
+            );
+        }
+    }
+}
+
+#[test]
+fn split_layout_imports_the_sse_runtime_package() {
+    let spec =
+        parse::from_yaml(include_str!("../../oag-core/tests/fixtures/sse-chat.yaml")).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let config = GeneratorConfig {
+        layout: OutputLayout::Split,
+        scaffold: Some(serde_json::json!({
+            "bundler": false,
+            "sse_runtime": "package:@org/oag-sse",
+        })),
+        ..GeneratorConfig::default()
+    };
+    let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+    assert!(!files.iter().any(|f| f.path == "src/sse.ts"));
+    assert!(files.iter().any(|f| f.path == "sse-runtime.d.ts"));
+    for file in files.iter().filter(|f| f.path.starts_with("src/")) {
+        assert!(!file.content.contains("/sse\""), "{}", file.path);
+    }
+    assert!(
+        files
+            .iter()
+            .filter(|f| f.path.starts_with("src/hooks/"))
+            .any(|f| f
+                .content
+                .contains("import type { SSEOptions } from \"@org/oag-sse\";"))
+    );
+}
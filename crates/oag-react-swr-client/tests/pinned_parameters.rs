@@ -0,0 +1,34 @@
+use oag_core::config::GeneratorConfig;
+use oag_core::{CodeGenerator, parse, transform};
+use oag_react_swr_client::ReactSwrClientGenerator;
+
+const ANTHROPIC: &str = include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml");
+
+#[test]
+fn pinned_header_is_left_out_of_hooks() {
+    let spec = parse::from_yaml(ANTHROPIC).unwrap();
+    let ir = transform::transform(&spec).unwrap();
+    let config = GeneratorConfig {
+        pinned_parameters: [("anthropic-version".to_string(), "2023-06-01".to_string())]
+            .into_iter()
+            .collect(),
+        ..GeneratorConfig::default()
+    };
+    let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+    let file = |path: &str| {
+        &files
+            .iter()
+            .find(|f| f.path == path)
+            .unwrap_or_else(|| panic!("{path} should be generated"))
+            .content
+    };
+
+    let hooks = file("src/hooks.tsx");
+    assert!(!hooks.contains("anthropicVersion"));
+    assert!(hooks.contains(
+        "export function useListModels(limit?: number, afterId?: string, beforeId?: string, config?: SWRConfiguration<ModelListResponse>)"
+    ));
+
+    let client = file("src/client.ts");
+    assert!(client.contains("\"anthropic-version\": this.pinnedParameters[\"anthropic-version\"]"));
+}
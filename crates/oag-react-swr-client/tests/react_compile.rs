@@ -1,8 +1,8 @@
 use std::fs;
 use std::process::Command;
 
-use oag_core::config::GeneratorConfig;
-use oag_core::{CodeGenerator, parse, transform};
+use oag_core::config::{GeneratorConfig, OutputLayout, ParamsStyle};
+use oag_core::{CodeGenerator, parse, postprocess, transform};
 use oag_react_swr_client::ReactSwrClientGenerator;
 
 const PETSTORE: &str = include_str!("../../oag-core/tests/fixtures/petstore-3.2.yaml");
@@ -10,6 +10,11 @@ const SSE_CHAT: &str = include_str!("../../oag-core/tests/fixtures/sse-chat.yaml
 const ANTHROPIC: &str = include_str!("../../oag-core/tests/fixtures/anthropic-messages.yaml");
 const PETSTORE_POLY: &str = include_str!("../../oag-core/tests/fixtures/petstore-polymorphic.yaml");
 const MIXED: &str = include_str!("../../oag-core/tests/fixtures/mixed-endpoints.yaml");
+const RESERVED_WORDS: &str = include_str!("../../oag-core/tests/fixtures/reserved-words.yaml");
+const MANY_QUERY_PARAMS: &str =
+    include_str!("../../oag-core/tests/fixtures/many-query-params.yaml");
+const BATCH_ENDPOINTS: &str = include_str!("../../oag-core/tests/fixtures/batch-endpoints.yaml");
+const ENVIRONMENTS: &str = include_str!("../../oag-core/tests/fixtures/environments.yaml");
 
 fn scaffold_config() -> GeneratorConfig {
     GeneratorConfig {
@@ -23,12 +28,105 @@ fn scaffold_config() -> GeneratorConfig {
     }
 }
 
+/// `scaffold_config` with the `strictest` tsconfig preset
+/// (`noUncheckedIndexedAccess`, `exactOptionalPropertyTypes`, ...).
+fn strictest_config() -> GeneratorConfig {
+    let mut config = scaffold_config();
+    config.scaffold.as_mut().unwrap()["tsconfig_preset"] = "strictest".into();
+    config
+}
+
+/// A component dispatching the anthropic stream's events with the generated
+/// `handleCreateMessageStreamEvent` helper.
+const ANTHROPIC_STREAM_COMPONENT: &str = r#"import type { CreateMessageStreamEvent } from "./index";
+import { handleCreateMessageStreamEvent, useCreateMessageStream } from "./index";
+
+function describe(event: CreateMessageStreamEvent): string {
+  return handleCreateMessageStreamEvent(event, {
+    message_start: () => "started",
+    content_block_start: (e) => `block ${e.index} started`,
+    content_block_delta: (e) => `block ${e.index} delta`,
+    content_block_stop: (e) => `block ${e.index} stopped`,
+    message_delta: (e) => `${e.usage.output_tokens} tokens`,
+    message_stop: () => "stopped",
+    ping: () => "ping",
+    error: (e) => e.error.message,
+  });
+}
+
+export function StreamStatus() {
+  const { events } = useCreateMessageStream("2023-06-01");
+  const blocks = events.filter((event) =>
+    handleCreateMessageStreamEvent(event, {
+      content_block_start: () => true,
+      _: () => false,
+    }),
+  );
+  const last = events.length > 0 ? events[events.length - 1] : undefined;
+  return (
+    <p>
+      {blocks.length} blocks, {last ? describe(last) : "waiting"}
+    </p>
+  );
+}
+
+// @ts-expect-error every variant needs a handler unless `_` is given
+handleCreateMessageStreamEvent({ type: "ping" }, { ping: () => {} });
+"#;
+
+/// A component importing pets through the generated batched hooks.
+const BATCH_IMPORT_COMPONENT: &str = r#"import { useAddStoreTagsBatched, useCreatePetsBatched } from "./index";
+
+export function ImportPets({ names }: { names: string[] }) {
+  const { trigger, progress, result } = useCreatePetsBatched();
+  const tags = useAddStoreTagsBatched("store-1");
+  const created = result?.results.reduce((sum, chunk) => sum + chunk.value.created, 0) ?? 0;
+  const start = () => {
+    void trigger(
+      names.map((name) => ({ name })),
+      { chunkSize: 50, concurrency: 2 },
+    );
+    void tags.trigger(["imported"]);
+  };
+  return (
+    <button type="button" onClick={start}>
+      {progress ? `${progress.settled}/${progress.total}` : created}
+    </button>
+  );
+}
+"#;
+
+/// A component picking the provider's environment, and a client for another.
+const ENVIRONMENT_COMPONENT: &str = r#"import type { ReactNode } from "react";
+import { InventoryProvider, clientForEnv } from "./index";
+
+export const local = clientForEnv("local", { baseUrl: "http://localhost:9090" });
+
+export function StagingApp({ children }: { children: ReactNode }) {
+  return <InventoryProvider environment="staging">{children}</InventoryProvider>;
+}
+
+export function UnknownApp({ children }: { children: ReactNode }) {
+  // @ts-expect-error -- not one of ENVIRONMENTS
+  return <InventoryProvider environment="production">{children}</InventoryProvider>;
+}
+"#;
+
 fn compile_react(yaml: &str) {
+    compile_react_with(yaml, &[]);
+}
+
+/// Compile the generated client plus `extra` `(path, content)` files.
+fn compile_react_with(yaml: &str, extra: &[(&str, &str)]) {
+    compile_react_config(yaml, &scaffold_config(), extra);
+}
+
+fn compile_react_config(yaml: &str, config: &GeneratorConfig, extra: &[(&str, &str)]) {
     let spec = parse::from_yaml(yaml).unwrap();
     let ir = transform::transform(&spec).unwrap();
 
-    let config = scaffold_config();
-    let files = ReactSwrClientGenerator.generate(&ir, &config).unwrap();
+    let mut files = ReactSwrClientGenerator.generate(&ir, config).unwrap();
+    postprocess::normalize_files(&ReactSwrClientGenerator, &mut files);
 
     let tmp = tempfile::tempdir().unwrap();
     let dir = tmp.path();
@@ -40,6 +138,9 @@ fn compile_react(yaml: &str) {
         }
         fs::write(&dest, &file.content).unwrap();
     }
+    for (path, content) in extra {
+        fs::write(dir.join(path), content).unwrap();
+    }
 
     let install = Command::new("npm")
         .args(["install", "--no-audit", "--no-fund"])
@@ -99,6 +200,24 @@ fn generated_react_petstore_compiles() {
     compile_react(PETSTORE);
 }
 
+#[test]
+fn generated_react_petstore_bundled_compiles() {
+    let config = GeneratorConfig {
+        layout: OutputLayout::Bundled,
+        ..scaffold_config()
+    };
+    compile_react_config(PETSTORE, &config, &[]);
+}
+
+#[test]
+fn generated_react_petstore_split_compiles() {
+    let config = GeneratorConfig {
+        layout: OutputLayout::Split,
+        ..scaffold_config()
+    };
+    compile_react_config(PETSTORE, &config, &[]);
+}
+
 #[test]
 fn generated_react_sse_chat_compiles() {
     compile_react(SSE_CHAT);
@@ -106,7 +225,43 @@ fn generated_react_sse_chat_compiles() {
 
 #[test]
 fn generated_react_anthropic_compiles() {
-    compile_react(ANTHROPIC);
+    compile_react_with(
+        ANTHROPIC,
+        &[("src/StreamStatus.tsx", ANTHROPIC_STREAM_COMPONENT)],
+    );
+}
+
+#[test]
+fn generated_react_anthropic_split_compiles() {
+    // tsc rejects a hook two `export *`s of the barrel both provide.
+    let config = GeneratorConfig {
+        layout: OutputLayout::Split,
+        ..scaffold_config()
+    };
+    compile_react_config(
+        ANTHROPIC,
+        &config,
+        &[("src/StreamStatus.tsx", ANTHROPIC_STREAM_COMPONENT)],
+    );
+}
+
+#[test]
+fn generated_react_petstore_strictest_compiles() {
+    compile_react_config(PETSTORE, &strictest_config(), &[]);
+}
+
+#[test]
+fn generated_react_anthropic_strictest_compiles() {
+    compile_react_config(ANTHROPIC, &strictest_config(), &[]);
+}
+
+#[test]
+fn generated_react_petstore_retry_compiles() {
+    let config = GeneratorConfig {
+        retry: true,
+        ..strictest_config()
+    };
+    compile_react_config(PETSTORE, &config, &[]);
 }
 
 #[test]
@@ -118,3 +273,45 @@ fn generated_react_petstore_polymorphic_compiles() {
 fn generated_react_mixed_compiles() {
     compile_react(MIXED);
 }
+
+#[test]
+fn generated_react_reserved_words_compiles() {
+    compile_react(RESERVED_WORDS);
+}
+
+#[test]
+fn generated_react_params_styles_compiles() {
+    compile_react(MANY_QUERY_PARAMS);
+    let config = GeneratorConfig {
+        params_style: ParamsStyle::Object,
+        ..scaffold_config()
+    };
+    compile_react_config(MANY_QUERY_PARAMS, &config, &[]);
+}
+
+#[test]
+fn generated_react_batch_helpers_compiles() {
+    let config = GeneratorConfig {
+        batch_helpers: true,
+        ..strictest_config()
+    };
+    let extra = [("src/ImportPets.tsx", BATCH_IMPORT_COMPONENT)];
+    compile_react_config(BATCH_ENDPOINTS, &config, &extra);
+    let bundled = GeneratorConfig {
+        layout: OutputLayout::Bundled,
+        ..config
+    };
+    compile_react_config(BATCH_ENDPOINTS, &bundled, &extra);
+}
+
+#[test]
+fn generated_react_environments_compiles() {
+    let config = strictest_config();
+    let extra = [("src/StagingApp.tsx", ENVIRONMENT_COMPONENT)];
+    compile_react_config(ENVIRONMENTS, &config, &extra);
+    let bundled = GeneratorConfig {
+        layout: OutputLayout::Bundled,
+        ..config
+    };
+    compile_react_config(ENVIRONMENTS, &bundled, &extra);
+}